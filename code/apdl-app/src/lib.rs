@@ -0,0 +1,16 @@
+//! APDL (APDS Protocol Definition Language)
+//!
+//! Umbrella crate re-exporting the APDL component crates.
+//! `use apdl_app::prelude::*;` brings the commonly used types into scope.
+
+pub use apdl_core;
+pub use apdl_lsk;
+pub use apdl_poem;
+pub use apdl_pvpae;
+
+/// Commonly used types from all APDL crates
+pub mod prelude {
+    pub use apdl_lsk::prelude::*;
+    pub use apdl_poem::prelude::*;
+    pub use apdl_pvpae::prelude::*;
+}
@@ -2,7 +2,7 @@
 //!
 //! Main entry point for the APDL system.
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -14,12 +14,96 @@ struct Args {
     /// Enable verbose output
     #[arg(short, long)]
     verbose: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Trace how a frame is parsed field by field
+    Explain {
+        /// Path to the DSL protocol definition
+        #[arg(long)]
+        def: String,
+
+        /// Frame bytes as a hex string
+        #[arg(long)]
+        hex: String,
+    },
+    /// Disassemble a frame into a field-by-field table
+    Disassemble {
+        /// Path to the DSL protocol definition
+        #[arg(long)]
+        protocol_file: String,
+
+        /// Frame bytes as a hex string, with or without spaces
+        #[arg(long)]
+        hex: String,
+    },
+    /// Propose a skeleton definition from a hex capture
+    Guess {
+        /// Captured frame bytes as a hex string
+        #[arg(long)]
+        hex: String,
+
+        /// Length of each frame in bytes
+        #[arg(long)]
+        frame_len: usize,
+
+        /// Detect a trailing CRC16 over the preceding bytes
+        #[arg(long)]
+        crc: bool,
+    },
 }
 
 #[tokio::main]
 async fn main() {
     let args = Args::parse();
 
+    if let Some(command) = &args.command {
+        let result = match command {
+            Command::Explain { def, hex } => apdl_iam::cli::explain::run_explain(def, hex),
+            Command::Disassemble { protocol_file, hex } => {
+                apdl_iam::cli::disassemble::run_disassemble(protocol_file, hex)
+            }
+            Command::Guess {
+                hex,
+                frame_len,
+                crc,
+            } => apdl_iam::cli::guess::run_guess(hex, *frame_len, *crc),
+        };
+        match result {
+            Ok(output) => print!("{output}"),
+            Err(e) => {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if let Some(file) = &args.protocol_file {
+        match apdl_iam::cli::validate::run_validate(file) {
+            Ok(report) => {
+                if args.verbose {
+                    for (layer, field_count) in &report.layers {
+                        println!("Layer {layer}: {field_count} field(s)");
+                    }
+                }
+                print!("{}", report.summary(file));
+                if !report.passed() {
+                    std::process::exit(1);
+                }
+            }
+            Err(e) => {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
     println!("APDL (APDS Protocol Definition Language) System");
     println!("===============================================");
 
@@ -27,11 +111,6 @@ async fn main() {
         println!("Verbose mode enabled");
     }
 
-    if let Some(file) = &args.protocol_file {
-        println!("Loading protocol definition from: {file}");
-        // Here we would load and process the protocol definition
-    }
-
     println!("Starting APDL system...");
 
     // Placeholder for the actual APDL system startup
@@ -0,0 +1,85 @@
+//! DSL往返测试
+//!
+//! 验证`MetaConverter::unit_to_dsl`输出的DSL解析后得到相同的`SyntaxUnit`
+
+use apdl_app::prelude::*;
+use apdl_core::utils::Crc16Params;
+use apdl_dpe::MetaConverter;
+
+const FRAME_DSL: &str = r#"
+field: sync; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; constraint: fixed(0xEB90); desc: "同步字"
+field: version; type: Bit(3); length: 3bit; scope: layer(link); cover: entire_field; constraint: range(0..=7); desc: "Version"
+field: ptype; type: Bit(1); length: 1bit; scope: cross_layer(net→link); cover: entire_field; constraint: enum(TM=0,TC=1); associate: apid, seq; desc: "Packet type"
+field: length; type: Uint16; length: 2byte; scope: layer(net); cover: entire_field; endian: le; unit: "bytes"; long_desc: "Data length minus one"; desc: "Length"
+field: time; type: CUC(4,2); length: 6byte; scope: global(mission); cover: entire_field; desc: "Onboard time"
+field: data; type: RawData; length: (length + 1); scope: layer(app); cover: entire_field; desc: "User data"
+field: fecf; type: Uint16; length: 2byte; scope: layer(link); cover: data[0..4]; alg: crc16; desc: "Frame check"
+"#;
+
+fn assert_round_trip(unit: &SyntaxUnit) {
+    let dsl = MetaConverter::new().unit_to_dsl(unit);
+    let parsed = DslParserImpl::new()
+        .parse_syntax_unit(&dsl)
+        .unwrap_or_else(|e| panic!("failed to parse '{dsl}': {e}"));
+    assert_eq!(&parsed, unit, "round trip through '{dsl}'");
+}
+
+#[test]
+fn test_parsed_units_round_trip() {
+    let units = DslParserImpl::new()
+        .parse_protocol_structure(FRAME_DSL)
+        .unwrap();
+    assert_eq!(units.len(), 7);
+    for unit in &units {
+        assert_round_trip(unit);
+    }
+
+    let dsl = MetaConverter::new().unit_to_dsl(&units[0]);
+    assert_eq!(
+        dsl,
+        "field: sync; type: Uint16; length: 2byte; scope: layer(link); \
+         cover: entire_field; constraint: fixed(0xEB90); desc: \"同步字\""
+    );
+}
+
+#[test]
+fn test_constructed_units_round_trip() {
+    let mut units = DslParserImpl::new()
+        .parse_protocol_structure(FRAME_DSL)
+        .unwrap();
+
+    let fecf = &mut units[6];
+    fecf.alg = Some(AlgorithmAst::Crc16With(Crc16Params {
+        poly: 0x8005,
+        refin: true,
+        ..Crc16Params::CCSDS
+    }));
+    fecf.cover = CoverDesc::Expression("$frame_cover".to_string());
+    assert_round_trip(fecf);
+
+    let data = &mut units[5];
+    data.length = LengthDesc {
+        size: 0,
+        unit: LengthUnit::Dynamic,
+    };
+    data.alg = Some(AlgorithmAst::XorSum);
+    assert_round_trip(data);
+
+    // 分号和反斜杠转义后原样还原
+    let length = &mut units[3];
+    length.desc = "Length; minus one".to_string();
+    length.long_description = Some(r"C:\data; \; raw".to_string());
+    let dsl = MetaConverter::new().unit_to_dsl(length);
+    assert!(dsl.contains(r#"desc: "Length\; minus one""#));
+    assert_round_trip(length);
+
+    // 双引号和换行转义后原样还原，经完整文档解析也不丢失
+    length.desc = r#"Say "hi""#.to_string();
+    length.long_description = Some("first line\nsecond line".to_string());
+    let dsl = MetaConverter::new().unit_to_dsl(length);
+    assert!(dsl.contains(r#"desc: "Say \"hi\"""#));
+    assert!(dsl.contains(r#"long_desc: "first line\nsecond line""#));
+    assert_round_trip(length);
+    let parsed = DslParserImpl::new().parse_protocol_structure(&dsl).unwrap();
+    assert_eq!(parsed, vec![length.clone()]);
+}
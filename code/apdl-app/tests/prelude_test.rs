@@ -0,0 +1,43 @@
+//! Prelude compile test
+//!
+//! Checks that `use apdl_app::prelude::*;` brings the key types into scope.
+
+use apdl_app::prelude::*;
+
+const FRAME_DSL: &str = r#"
+field: version; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "Version"
+field: data; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Data"
+"#;
+
+#[test]
+fn test_prelude_brings_key_types_into_scope() {
+    let parser = DslParserImpl::new();
+    let units: Vec<SyntaxUnit> = parser.parse_protocol_structure(FRAME_DSL).unwrap();
+
+    let mut assembler = FrameAssembler::new();
+    let mut disassembler = FrameDisassembler::new();
+    for unit in &units {
+        assembler.add_field(unit.clone());
+        disassembler.add_field(unit.clone());
+    }
+    assembler.set_field_value("version", &[0x01]).unwrap();
+    assembler.set_field_value("data", &[0x12, 0x34]).unwrap();
+    let frame = assembler.assemble_frame().unwrap();
+
+    let fields: Vec<ParsedField> = disassembler.parse_frame_named(&frame).unwrap();
+    assert_eq!(fields[1].value, vec![0x12, 0x34]);
+
+    let error: Result<(), ProtocolError> = assembler.set_field_value("missing", &[0x00]);
+    assert!(error.is_err());
+
+    let _generator = DataGenerator::new(&units);
+    let _verifier = ProtocolVerifier::new();
+    assert_eq!(FrameBoundary::Fixed(3).frame_len(&frame).unwrap(), Some(3));
+}
+
+#[test]
+fn test_existing_paths_still_work() {
+    let _unit_type: apdl_core::protocol_meta::UnitType = apdl_core::UnitType::RawData;
+    let _generator = apdl_lsk::data_generator::DataGenerator::new(&[]);
+    let _assembler = apdl_app::apdl_poem::FrameAssembler::new();
+}
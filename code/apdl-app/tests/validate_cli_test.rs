@@ -0,0 +1,108 @@
+//! 协议文件验证命令测试
+//!
+//! 以`apdl --protocol-file`运行示例定义，检查验证摘要和退出码
+
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+const OVERLAP_DSL: &str = r#"
+field: sync; type: Uint16; length: 1byte; scope: layer(link); cover: entire_field; desc: "Sync"
+field: apid; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "APID"
+"#;
+
+const CLEAN_DSL: &str = r#"
+field: sync; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Sync"
+field: apid; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "APID"
+"#;
+
+fn write_sample(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("apdl_validate_{}_{name}", std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+fn run_apdl(path: &PathBuf) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_apdl"))
+        .arg("--protocol-file")
+        .arg(path)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_overlap_fails_verification() {
+    let path = write_sample("overlap.apdl", OVERLAP_DSL);
+    let output = run_apdl(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "stdout: {stdout}");
+    assert!(stdout.contains("Fields: 2"), "stdout: {stdout}");
+    assert!(stdout.contains("Issues: 1 (1 error(s), 0 warning(s))"));
+    assert!(stdout.contains("Field 'sync' overlaps 'apid' by 1 byte(s)"));
+    assert!(stdout.contains("Verification failed"));
+}
+
+#[test]
+fn test_clean_layout_passes_verification() {
+    let path = write_sample("clean.apdl", CLEAN_DSL);
+    let output = run_apdl(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "stdout: {stdout}");
+    assert!(stdout.contains("Fields: 2"));
+    assert!(stdout.contains("Issues: 0"));
+    assert!(stdout.contains("Verification passed"));
+}
+
+#[test]
+fn test_json_package_definition_is_detected_by_extension() {
+    let json = r#"{
+        "name": "telemetry",
+        "display_name": "Telemetry packet",
+        "package_type": "telemetry",
+        "description": "Telemetry packet",
+        "pack_unpack_spec": null,
+        "layers": [{
+            "name": "header",
+            "units": [{
+                "field_id": "version",
+                "unit_type": {"Uint": 16},
+                "length": {"size": 1, "unit": "Byte"},
+                "scope": {"Layer": "header"},
+                "cover": "EntireField",
+                "constraint": null,
+                "alg": null,
+                "associate": [],
+                "desc": "Version"
+            }],
+            "rules": []
+        }]
+    }"#;
+    let path = write_sample("package.json", json);
+    let output = run_apdl(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(
+        output.status.code(),
+        Some(1),
+        "stdout: {stdout} stderr: {stderr}"
+    );
+    assert!(stdout.contains("(Json)"));
+    // 16位字段只声明了1字节长度
+    assert!(stdout.contains("Issues: 1 (1 error(s), 0 warning(s))"));
+    assert!(
+        stdout.contains("telemetry.header: Field 'version' overlaps '<end>' by 1 byte(s)"),
+        "stdout: {stdout}"
+    );
+}
+
+#[test]
+fn test_missing_file_exits_with_error() {
+    let output = run_apdl(&PathBuf::from("/nonexistent/protocol.apdl"));
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Failed to read"));
+}
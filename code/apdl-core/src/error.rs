@@ -18,6 +18,12 @@ pub enum ProtocolError {
     LengthError(String),
     /// 校验错误
     ChecksumError(String),
+    /// 校验字段中的校验和与计算值不一致
+    ChecksumMismatch {
+        field: String,
+        expected: u64,
+        actual: u64,
+    },
     /// 依赖关系错误
     DependencyError(String),
     /// 无效的表达式
@@ -26,6 +32,10 @@ pub enum ProtocolError {
     SynchronizationError(String),
     /// 值超出范围错误
     ValueOutOfRange(String),
+    /// 约束值超出字段位宽
+    ConstraintOutOfWidth(String),
+    /// 字段值违反约束
+    ConstraintViolation(String),
     /// 类型错误
     TypeError(String),
     /// 其他错误
@@ -44,10 +54,22 @@ impl fmt::Display for ProtocolError {
             ProtocolError::ValidationError(msg) => write!(f, "Validation error: {msg}"),
             ProtocolError::LengthError(msg) => write!(f, "Length error: {msg}"),
             ProtocolError::ChecksumError(msg) => write!(f, "Checksum error: {msg}"),
+            ProtocolError::ChecksumMismatch {
+                field,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Checksum mismatch in field {field}: expected {expected:#X}, got {actual:#X}"
+            ),
             ProtocolError::DependencyError(msg) => write!(f, "Dependency error: {msg}"),
             ProtocolError::InvalidExpression(msg) => write!(f, "Invalid expression: {msg}"),
             ProtocolError::SynchronizationError(msg) => write!(f, "Synchronization error: {msg}"),
             ProtocolError::ValueOutOfRange(msg) => write!(f, "Value out of range: {msg}"),
+            ProtocolError::ConstraintOutOfWidth(msg) => {
+                write!(f, "Constraint out of width: {msg}")
+            }
+            ProtocolError::ConstraintViolation(msg) => write!(f, "Constraint violation: {msg}"),
             ProtocolError::TypeError(msg) => write!(f, "Type error: {msg}"),
             ProtocolError::Other(msg) => write!(f, "Other error: {msg}"),
         }
@@ -71,3 +93,14 @@ impl From<&str> for ProtocolError {
         ProtocolError::ParseError(s.to_string())
     }
 }
+
+impl From<crate::protocol_meta::DslParseError> for ProtocolError {
+    fn from(error: crate::protocol_meta::DslParseError) -> Self {
+        use crate::protocol_meta::DslParseError;
+        match error {
+            DslParseError::ParseError(msg) => ProtocolError::ParseError(msg),
+            DslParseError::ValidationError(msg) => ProtocolError::ValidationError(msg),
+            syntax @ DslParseError::Syntax { .. } => ProtocolError::ParseError(syntax.to_string()),
+        }
+    }
+}
@@ -4,6 +4,7 @@
 //! APDL (APDS Protocol Definition Language) system.
 
 pub mod error;
+pub mod prelude;
 pub mod protocol_meta;
 pub mod utils;
 
@@ -0,0 +1,10 @@
+//! 常用类型预导入
+//!
+//! `use apdl_core::prelude::*;` 即可引入协议定义中最常用的类型
+
+pub use crate::error::ProtocolError;
+pub use crate::protocol_meta::{
+    AlgorithmAst, BitNumbering, ByteOrder, ChecksumAlgorithm, Constraint, CoverDesc, LengthDesc,
+    LengthUnit, ParsedField, ScopeDesc, SemanticRule, SyntaxUnit, UnitType,
+};
+pub use crate::{DslParser, ProtocolUnit};
@@ -2,6 +2,7 @@
 //!
 //! 定义协议相关的元数据结构
 
+use crate::ProtocolError;
 use serde::{Deserialize, Serialize};
 
 /// 协议层枚举
@@ -23,6 +24,10 @@ pub struct FieldDefinition {
     pub length: usize,   // 以字节为单位
     pub position: usize, // 在帧中的位置
     pub constraints: Vec<Constraint>,
+    #[serde(default)]
+    pub unit_label: Option<String>, // 物理量单位
+    #[serde(default)]
+    pub long_description: Option<String>, // 详细说明
 }
 
 /// 字段类型
@@ -85,6 +90,31 @@ pub enum UnitType {
     Bit(u8),  // Bit(1), Bit(2), etc.
     RawData,
     Ip6Addr,
+    /// CCSDS CUC时间码，CUC(coarse,fine)：粗时间和细时间字节数
+    CucTime {
+        coarse: u8,
+        fine: u8,
+    },
+    /// CCSDS CDS时间码，CDS(day_bytes,submilli_bytes)：天字节数和亚毫秒字节数
+    CdsTime {
+        day_bytes: u8,
+        submilli_bytes: u8,
+    },
+}
+
+impl UnitType {
+    /// 获取固定长度类型的字节数，变长或按位定义的类型返回None
+    pub fn fixed_byte_size(&self) -> Option<usize> {
+        match self {
+            UnitType::Ip6Addr => Some(16),
+            UnitType::CucTime { coarse, fine } => Some(*coarse as usize + *fine as usize),
+            UnitType::CdsTime {
+                day_bytes,
+                submilli_bytes,
+            } => Some(*day_bytes as usize + 4 + *submilli_bytes as usize),
+            UnitType::Uint(_) | UnitType::Bit(_) | UnitType::RawData => None,
+        }
+    }
 }
 
 /// 长度描述
@@ -103,6 +133,17 @@ pub enum LengthUnit {
     Expression(String),
 }
 
+impl LengthDesc {
+    /// 固定长度字段的bit数，动态长度和表达式长度返回None
+    pub fn fixed_bits(&self) -> Option<usize> {
+        match self.unit {
+            LengthUnit::Byte => Some(self.size * 8),
+            LengthUnit::Bit => Some(self.size),
+            LengthUnit::Dynamic | LengthUnit::Expression(_) => None,
+        }
+    }
+}
+
 /// 作用范围描述
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ScopeDesc {
@@ -111,7 +152,37 @@ pub enum ScopeDesc {
     Global(String),             // global(end2end)
 }
 
+impl ScopeDesc {
+    /// 判断作用范围是否包含指定层
+    pub fn includes_layer(&self, layer: &str) -> bool {
+        match self {
+            ScopeDesc::Layer(name) => name == layer,
+            ScopeDesc::CrossLayer(first, second) => first == layer || second == layer,
+            ScopeDesc::Global(_) => true,
+        }
+    }
+
+    /// 判断以该作用范围声明的校验字段能否覆盖指定字段
+    ///
+    /// 全局范围覆盖所有字段；层和跨层范围覆盖所含层的字段、全局字段以及承载上层数据的RawData字段
+    pub fn covers_field(&self, field: &SyntaxUnit) -> bool {
+        if matches!(self, ScopeDesc::Global(_)) || field.unit_type == UnitType::RawData {
+            return true;
+        }
+        match &field.scope {
+            ScopeDesc::Layer(layer) => self.includes_layer(layer),
+            ScopeDesc::CrossLayer(first, second) => {
+                self.includes_layer(first) || self.includes_layer(second)
+            }
+            ScopeDesc::Global(_) => true,
+        }
+    }
+}
+
 /// 覆盖描述
+///
+/// `Range(field, start, end)`为字段内的半开字节区间`[start, end)`：
+/// DSL中`field[0..2]`覆盖2字节，`field[0..=2]`覆盖3字节并解析为`Range(field, 0, 3)`
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum CoverDesc {
     Range(String, usize, usize), // frame_header[0..1]
@@ -119,6 +190,21 @@ pub enum CoverDesc {
     EntireField,                 // entire_field
 }
 
+impl CoverDesc {
+    /// 获取在长度为`field_len`字节的字段内覆盖的字节区间
+    ///
+    /// 区间为空、超出字段或为表达式时返回None
+    pub fn byte_range(&self, field_len: usize) -> Option<std::ops::Range<usize>> {
+        match self {
+            CoverDesc::EntireField => Some(0..field_len),
+            CoverDesc::Range(_, start, end) if start < end && *end <= field_len => {
+                Some(*start..*end)
+            }
+            CoverDesc::Range(..) | CoverDesc::Expression(_) => None,
+        }
+    }
+}
+
 /// 字节序类型
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum ByteOrder {
@@ -149,6 +235,30 @@ impl Default for BitOrder {
     }
 }
 
+/// 字节内位编号方式
+///
+/// MSB-0：bit偏移0对应字节最高位（CCSDS等）；LSB-0：bit偏移0对应字节最低位（部分车载协议）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum BitNumbering {
+    #[default]
+    #[serde(rename = "msb0")]
+    Msb0,
+    #[serde(rename = "lsb0")]
+    Lsb0,
+}
+
+/// 约束检查模式
+///
+/// Lenient：不检查字段值是否满足约束（可用于构造异常帧）；Strict：违反约束的字段值被拒绝
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ConstraintMode {
+    #[default]
+    #[serde(rename = "lenient")]
+    Lenient,
+    #[serde(rename = "strict")]
+    Strict,
+}
+
 /// 填充策略
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum PaddingStrategy {
@@ -197,6 +307,10 @@ fn default_alignment() -> u8 {
     1
 }
 
+fn default_covers_self() -> bool {
+    true
+}
+
 impl Default for PackUnpackSpec {
     fn default() -> Self {
         PackUnpackSpec {
@@ -213,12 +327,27 @@ impl Default for PackUnpackSpec {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AlgorithmAst {
     Crc16,
+    /// 指定多项式、初值等参数的CRC16
+    Crc16With(crate::utils::Crc16Params),
     Crc32,
     Crc15, // CAN协议专用
     XorSum,
     Custom(String),
 }
 
+impl AlgorithmAst {
+    /// 转换为对应的校验和算法，自定义算法返回None
+    pub fn checksum_algorithm(&self) -> Option<ChecksumAlgorithm> {
+        match self {
+            AlgorithmAst::Crc16 | AlgorithmAst::Crc16With(_) => Some(ChecksumAlgorithm::CRC16),
+            AlgorithmAst::Crc32 => Some(ChecksumAlgorithm::CRC32),
+            AlgorithmAst::Crc15 => Some(ChecksumAlgorithm::CRC15),
+            AlgorithmAst::XorSum => Some(ChecksumAlgorithm::XOR),
+            AlgorithmAst::Custom(_) => None,
+        }
+    }
+}
+
 /// 枚举映射条目
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EnumMappingEntry {
@@ -307,8 +436,32 @@ pub struct FieldMappingEntry {
 pub enum DslParseError {
     ParseError(String),
     ValidationError(String),
+    /// 定位到具体位置的语法错误，行号和列号从1开始，列号按字符计
+    Syntax {
+        line: usize,
+        column: usize,
+        token: String,
+        message: String,
+    },
+}
+
+impl std::fmt::Display for DslParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DslParseError::ParseError(msg) => write!(f, "Parse error: {msg}"),
+            DslParseError::ValidationError(msg) => write!(f, "Validation error: {msg}"),
+            DslParseError::Syntax {
+                line,
+                column,
+                token,
+                message,
+            } => write!(f, "{line}:{column}: {message} (at '{token}')"),
+        }
+    }
 }
 
+impl std::error::Error for DslParseError {}
+
 /// DSL验证错误
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DslValidateError {
@@ -329,6 +482,42 @@ pub struct SyntaxUnit {
     pub desc: String,
     /// 字段级别的打包/拆包规范，覆盖包级别的默认配置
     pub pack_unpack_spec: Option<PackUnpackSpec>,
+    /// 物理量单位（如 "K"、"count"）
+    #[serde(default)]
+    pub unit_label: Option<String>,
+    /// 详细说明文档
+    #[serde(default)]
+    pub long_description: Option<String>,
+}
+
+/// 已解析的字段（按帧中顺序）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParsedField {
+    /// 字段名
+    pub name: String,
+    /// 字段值（bit字段为大端字节，其余按`byte_order`排列）
+    pub value: Vec<u8>,
+    /// 字段值的字节序
+    #[serde(default)]
+    pub byte_order: ByteOrder,
+    /// 字段约束
+    pub constraint: Option<Constraint>,
+    /// 字段在帧中覆盖的原始字节（bit字段为其所跨越的完整字节）
+    pub raw: Vec<u8>,
+    /// 字段在帧中的起始bit偏移
+    pub bit_offset: usize,
+    /// 字段的bit长度
+    pub bit_len: usize,
+    /// 渲染后的字段值（整数、枚举标签或十六进制）
+    #[serde(default)]
+    pub decoded: String,
+}
+
+impl ParsedField {
+    /// 按字段字节序将字段值解释为无符号整数，超过8字节时返回None
+    pub fn numeric_value(&self) -> Option<u64> {
+        crate::utils::decode_uint(&self.value, self.byte_order)
+    }
 }
 
 // 新增语义规则类型
@@ -338,6 +527,9 @@ pub enum SemanticRule {
         algorithm: ChecksumAlgorithm,
         start_field: String,
         end_field: String,
+        /// 范围内的校验字段是否按全零计入计算，为false时排除其字节
+        #[serde(default = "default_covers_self")]
+        covers_self: bool,
     },
     Dependency {
         dependent_field: String,
@@ -358,10 +550,23 @@ pub enum SemanticRule {
         field_name: String,
         algorithm: String,
     },
+    /// 根据选择字段的取值选择校验算法
+    AlgorithmSelect {
+        selector_field: String,
+        /// 使用选中算法的校验字段，未指定时为首个声明了候选算法之一的字段
+        #[serde(default)]
+        target_field: Option<String>,
+        cases: Vec<(u64, AlgorithmAst)>,
+    },
     LengthRule {
         field_name: String,
         expression: String,
     },
+    /// 字段对齐：在字段前插入填充字节，使字段起始字节偏移为boundary的整数倍
+    Alignment {
+        field_name: String,
+        boundary: usize,
+    },
     // CCSDS协议特有语义规则
     RoutingDispatch {
         fields: Vec<String>,
@@ -458,6 +663,57 @@ pub enum SemanticRule {
     },
 }
 
+impl SemanticRule {
+    /// 获取语义规则的类型名
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SemanticRule::ChecksumRange { .. } => "checksum_range",
+            SemanticRule::Dependency { .. } => "dependency",
+            SemanticRule::Conditional { .. } => "conditional",
+            SemanticRule::Order { .. } => "order",
+            SemanticRule::Pointer { .. } => "pointer",
+            SemanticRule::Algorithm { .. } => "algorithm",
+            SemanticRule::AlgorithmSelect { .. } => "algorithm_select",
+            SemanticRule::LengthRule { .. } => "length_rule",
+            SemanticRule::Alignment { .. } => "alignment",
+            SemanticRule::RoutingDispatch { .. } => "routing_dispatch",
+            SemanticRule::SequenceControl { .. } => "sequence_control",
+            SemanticRule::Validation { .. } => "validation",
+            SemanticRule::Synchronization { .. } => "synchronization",
+            SemanticRule::LengthValidation { .. } => "length_validation",
+            SemanticRule::Multiplexing { .. } => "multiplexing",
+            SemanticRule::PriorityProcessing { .. } => "priority_processing",
+            SemanticRule::StateMachine { .. } => "state_machine",
+            SemanticRule::PeriodicTransmission { .. } => "periodic_transmission",
+            SemanticRule::MessageFiltering { .. } => "message_filtering",
+            SemanticRule::ErrorDetection { .. } => "error_detection",
+            SemanticRule::FlowControl { .. } => "flow_control",
+            SemanticRule::TimeSynchronization { .. } => "time_synchronization",
+            SemanticRule::AddressResolution { .. } => "address_resolution",
+            SemanticRule::Security { .. } => "security",
+            SemanticRule::Redundancy { .. } => "redundancy",
+            SemanticRule::FieldMapping { .. } => "field_mapping",
+        }
+    }
+
+    /// 计算字段位于给定字节偏移时对齐规则要求插入的填充字节数
+    pub fn alignment_padding(rules: &[SemanticRule], field_name: &str, offset: usize) -> usize {
+        rules
+            .iter()
+            .filter_map(|rule| match rule {
+                SemanticRule::Alignment {
+                    field_name: name,
+                    boundary,
+                } if name == field_name && *boundary > 1 => {
+                    Some(offset.next_multiple_of(*boundary) - offset)
+                }
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ChecksumAlgorithm {
     CRC16,
@@ -607,3 +863,141 @@ impl ProtocolStackDefinition {
         }
     }
 }
+
+impl Constraint {
+    /// 检查约束中的所有取值是否能容纳在指定位宽内
+    pub fn check_width(&self, field_name: &str, bit_width: u32) -> Result<(), ProtocolError> {
+        let max_value = if bit_width >= 64 {
+            u64::MAX
+        } else {
+            (1u64 << bit_width) - 1
+        };
+        let out_of_width = |desc: String, value: u64| {
+            ProtocolError::ConstraintOutOfWidth(format!(
+                "Field '{field_name}' {desc} value {value:#X} exceeds {bit_width}-bit width (max {max_value:#X})"
+            ))
+        };
+
+        match self {
+            Constraint::Range(min, max) => {
+                if *min > max_value {
+                    return Err(out_of_width("range min".to_string(), *min));
+                }
+                if *max > max_value {
+                    return Err(out_of_width("range max".to_string(), *max));
+                }
+            }
+            Constraint::FixedValue(value) => {
+                if *value > max_value {
+                    return Err(out_of_width("fixed".to_string(), *value));
+                }
+            }
+            Constraint::Enum(entries) => {
+                for (name, value) in entries {
+                    if *value > max_value {
+                        return Err(out_of_width(format!("enum '{name}'"), *value));
+                    }
+                }
+            }
+            Constraint::Custom(_) => {}
+        }
+        Ok(())
+    }
+
+    /// 检查字段值是否满足约束，自定义约束不做检查
+    pub fn check_value(&self, field_name: &str, value: u64) -> Result<(), ProtocolError> {
+        let satisfied = match self {
+            Constraint::Range(min, max) => (*min..=*max).contains(&value),
+            Constraint::FixedValue(expected) => value == *expected,
+            Constraint::Enum(entries) => entries.iter().any(|(_, entry)| *entry == value),
+            Constraint::Custom(_) => true,
+        };
+
+        if satisfied {
+            Ok(())
+        } else {
+            Err(ProtocolError::ConstraintViolation(format!(
+                "Field '{field_name}' value {value:#X} violates constraint {self:?}"
+            )))
+        }
+    }
+}
+
+impl SyntaxUnit {
+    /// 创建覆盖整个字段、无约束和算法的字段，描述默认为字段名
+    pub fn new(field_id: &str, unit_type: UnitType, length: LengthDesc, scope: ScopeDesc) -> Self {
+        Self {
+            field_id: field_id.to_string(),
+            unit_type,
+            length,
+            scope,
+            cover: CoverDesc::EntireField,
+            constraint: None,
+            alg: None,
+            associate: Vec::new(),
+            desc: field_id.to_string(),
+            pack_unpack_spec: None,
+            unit_label: None,
+            long_description: None,
+        }
+    }
+
+    /// 获取字段声明的位宽，动态长度或表达式长度返回None
+    pub fn bit_width(&self) -> Option<u32> {
+        match self.unit_type {
+            UnitType::Uint(bits) | UnitType::Bit(bits) => Some(bits as u32),
+            UnitType::CucTime { .. } | UnitType::CdsTime { .. } => {
+                self.unit_type.fixed_byte_size().map(|size| size as u32 * 8)
+            }
+            _ => match self.length.unit {
+                LengthUnit::Byte => Some((self.length.size * 8) as u32),
+                LengthUnit::Bit => Some(self.length.size as u32),
+                LengthUnit::Dynamic | LengthUnit::Expression(_) => None,
+            },
+        }
+    }
+
+    /// 检查字段约束是否超出字段位宽
+    pub fn check_constraint_width(&self) -> Result<(), ProtocolError> {
+        match (&self.constraint, self.bit_width()) {
+            (Some(constraint), Some(bit_width)) => {
+                constraint.check_width(&self.field_id, bit_width)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 解析出的字段值的字节序
+    ///
+    /// bit字段的值已按位编号方式还原为大端字节，其余字段按打包规范的字节序
+    pub fn value_byte_order(&self) -> ByteOrder {
+        match &self.pack_unpack_spec {
+            Some(spec) if !matches!(self.unit_type, UnitType::Bit(_)) => spec.byte_order,
+            _ => ByteOrder::BigEndian,
+        }
+    }
+
+    /// 将解析出的字段值渲染为便于阅读的文本
+    ///
+    /// 1~8字节的整数按字段字节序解码为十进制，命中枚举约束时显示标签；
+    /// RawData字段和超过8字节的字段显示为十六进制
+    pub fn render_value(&self, value: &[u8]) -> String {
+        self.render_value_with(value, self.value_byte_order())
+    }
+
+    /// 按指定字节序渲染解析出的字段值
+    pub fn render_value_with(&self, value: &[u8], byte_order: ByteOrder) -> String {
+        if self.unit_type == UnitType::RawData || value.is_empty() || value.len() > 8 {
+            return crate::utils::bytes_to_hex(value);
+        }
+
+        let number = crate::utils::decode_uint(value, byte_order).unwrap_or_default();
+
+        if let Some(Constraint::Enum(entries)) = &self.constraint {
+            if let Some((label, _)) = entries.iter().find(|(_, entry)| *entry == number) {
+                return label.clone();
+            }
+        }
+        number.to_string()
+    }
+}
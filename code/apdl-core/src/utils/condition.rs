@@ -0,0 +1,127 @@
+//! 字段比较解析
+//!
+//! 条件规则和多路复用条件中`field.value OP literal`形式的比较，
+//! 组装时按字段当前值判断，静态验证时按取值区间分析
+
+/// 比较运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CompareOp {
+    /// 运算符文本，双字符运算符在前以免被单字符运算符截断
+    const SYMBOLS: [(&'static str, CompareOp); 6] = [
+        ("==", CompareOp::Eq),
+        ("!=", CompareOp::Ne),
+        ("<=", CompareOp::Le),
+        (">=", CompareOp::Ge),
+        ("<", CompareOp::Lt),
+        (">", CompareOp::Gt),
+    ];
+
+    /// 判断`actual OP expected`是否成立
+    pub fn holds(self, actual: u64, expected: u64) -> bool {
+        match self {
+            CompareOp::Eq => actual == expected,
+            CompareOp::Ne => actual != expected,
+            CompareOp::Lt => actual < expected,
+            CompareOp::Le => actual <= expected,
+            CompareOp::Gt => actual > expected,
+            CompareOp::Ge => actual >= expected,
+        }
+    }
+}
+
+/// 字段比较，如`version == 9`或`flag.value != 0x01`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldComparison {
+    pub field_name: String,
+    pub op: CompareOp,
+    pub value: u64,
+}
+
+impl FieldComparison {
+    /// 解析单个比较
+    ///
+    /// 取最靠前的运算符，字段名可带`.value`后缀，字面量为十进制或`0x`十六进制，
+    /// 字面量之后的内容被忽略；格式不符时返回`None`
+    pub fn parse(expression: &str) -> Option<Self> {
+        let (position, symbol, op) = CompareOp::SYMBOLS
+            .iter()
+            .filter_map(|&(symbol, op)| {
+                expression
+                    .find(symbol)
+                    .map(|position| (position, symbol, op))
+            })
+            .min_by_key(|(position, symbol, _)| (*position, usize::MAX - symbol.len()))?;
+
+        let lhs = expression[..position].trim();
+        let field_name = lhs.strip_suffix(".value").unwrap_or(lhs).trim();
+        if field_name.is_empty() || !field_name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return None;
+        }
+
+        let rhs = expression[position + symbol.len()..]
+            .split_whitespace()
+            .next()?;
+        let value = match rhs.strip_prefix("0x").or_else(|| rhs.strip_prefix("0X")) {
+            Some(hex) => u64::from_str_radix(hex, 16).ok()?,
+            None => rhs.parse().ok()?,
+        };
+
+        Some(Self {
+            field_name: field_name.to_string(),
+            op,
+            value,
+        })
+    }
+
+    /// 判断字段取值是否满足比较
+    pub fn holds_for(&self, actual: u64) -> bool {
+        self.op.holds(actual, self.value)
+    }
+
+    /// 比较成立的取值区间（闭区间）
+    pub fn intervals(&self) -> Vec<(u64, u64)> {
+        let value = self.value;
+        let below = value.checked_sub(1).map(|max| (0, max));
+        let above = value.checked_add(1).map(|min| (min, u64::MAX));
+        match self.op {
+            CompareOp::Eq => vec![(value, value)],
+            CompareOp::Ne => below.into_iter().chain(above).collect(),
+            CompareOp::Lt => below.into_iter().collect(),
+            CompareOp::Le => vec![(0, value)],
+            CompareOp::Gt => above.into_iter().collect(),
+            CompareOp::Ge => vec![(value, u64::MAX)],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_comparison() {
+        let comparison = FieldComparison::parse("flag.value >= 0x10").unwrap();
+        assert_eq!(comparison.field_name, "flag");
+        assert_eq!(comparison.op, CompareOp::Ge);
+        assert_eq!(comparison.value, 16);
+        assert!(comparison.holds_for(16));
+        assert!(!comparison.holds_for(15));
+
+        let comparison = FieldComparison::parse("version != 0").unwrap();
+        assert_eq!(comparison.op, CompareOp::Ne);
+        assert_eq!(comparison.intervals(), vec![(1, u64::MAX)]);
+
+        assert!(FieldComparison::parse("flag == abc").is_none());
+        assert!(FieldComparison::parse("a + b < 3").is_none());
+        assert!(FieldComparison::parse("flag").is_none());
+    }
+}
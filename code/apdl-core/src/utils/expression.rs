@@ -0,0 +1,318 @@
+//! 长度表达式求值
+//!
+//! 长度规则表达式的递归下降解析器。文法：`expr := term (('+' | '-') term)*`，
+//! `term := factor (('*' | '/') factor)*`，
+//! `factor := number | '(' expr ')' | ('min' | 'max') '(' expr ',' expr ')' | func '(' field ')' | ident`；
+//! 数值支持十进制和`0x`十六进制，整个表达式可用单引号或双引号包围。
+//! 运算语义和字段引用由调用方的[`ExpressionContext`]提供：组装时按实际值求值，静态验证时按取值区间求值
+
+use std::cell::RefCell;
+
+/// 二元运算（`min`/`max`按二元运算处理）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Min,
+    Max,
+}
+
+/// 表达式求值上下文
+pub trait ExpressionContext {
+    type Value;
+    type Error;
+
+    /// 数值字面量
+    fn number(&self, value: u64) -> Self::Value;
+
+    /// 二元运算
+    fn binary(
+        &self,
+        op: BinaryOp,
+        left: Self::Value,
+        right: Self::Value,
+    ) -> Result<Self::Value, Self::Error>;
+
+    /// 字段函数调用，如`len(field)`、`pos(field)`、`val(field)`，参数为去除空白的字段名
+    fn function(&self, name: &str, field_name: &str) -> Result<Self::Value, Self::Error>;
+
+    /// 标识符，如`total_length`、`<field>_length`
+    fn identifier(&self, name: &str) -> Result<Self::Value, Self::Error>;
+
+    /// 语法错误
+    fn syntax_error(&self, expression: &str, reason: &str) -> Self::Error;
+}
+
+/// 按上下文求值表达式
+pub fn evaluate_expression<C: ExpressionContext>(
+    expression: &str,
+    context: &C,
+) -> Result<C::Value, C::Error> {
+    let expression = strip_quotes(expression.trim());
+    let mut parser = Parser {
+        expression,
+        pos: 0,
+        context,
+    };
+    let value = parser.parse_expr()?;
+    match parser.peek() {
+        None => Ok(value),
+        Some(_) => Err(parser.unexpected()),
+    }
+}
+
+/// 表达式中的字段函数调用（函数名，字段名），按出现顺序返回；表达式无法解析时返回已识别的部分
+pub fn function_references(expression: &str) -> Vec<(String, String)> {
+    let collector = ReferenceCollector::default();
+    let _ = evaluate_expression(expression, &collector);
+    collector.references.into_inner()
+}
+
+fn strip_quotes(expression: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = expression
+            .strip_prefix(quote)
+            .and_then(|rest| rest.strip_suffix(quote))
+        {
+            return inner.trim();
+        }
+    }
+    expression
+}
+
+struct Parser<'a, C> {
+    expression: &'a str,
+    pos: usize,
+    context: &'a C,
+}
+
+impl<'a, C: ExpressionContext> Parser<'a, C> {
+    fn parse_expr(&mut self) -> Result<C::Value, C::Error> {
+        let mut value = self.parse_term()?;
+        while let Some(op @ ('+' | '-')) = self.peek() {
+            self.pos += 1;
+            let right = self.parse_term()?;
+            let op = if op == '+' {
+                BinaryOp::Add
+            } else {
+                BinaryOp::Sub
+            };
+            value = self.context.binary(op, value, right)?;
+        }
+        Ok(value)
+    }
+
+    fn parse_term(&mut self) -> Result<C::Value, C::Error> {
+        let mut value = self.parse_factor()?;
+        while let Some(op @ ('*' | '/')) = self.peek() {
+            self.pos += 1;
+            let right = self.parse_factor()?;
+            let op = if op == '*' {
+                BinaryOp::Mul
+            } else {
+                BinaryOp::Div
+            };
+            value = self.context.binary(op, value, right)?;
+        }
+        Ok(value)
+    }
+
+    fn parse_factor(&mut self) -> Result<C::Value, C::Error> {
+        match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                let value = self.parse_expr()?;
+                self.expect(')')?;
+                Ok(value)
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let word = self.word();
+                let number = match word.strip_prefix("0x").or_else(|| word.strip_prefix("0X")) {
+                    Some(hex) => u64::from_str_radix(hex, 16).ok(),
+                    None => word.parse::<u64>().ok(),
+                };
+                number
+                    .map(|value| self.context.number(value))
+                    .ok_or_else(|| self.error(&format!("invalid number '{word}'")))
+            }
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+                let name = self.word();
+                if self.peek() != Some('(') {
+                    return self.context.identifier(name);
+                }
+                self.pos += 1;
+                if name == "min" || name == "max" {
+                    let left = self.parse_expr()?;
+                    self.expect(',')?;
+                    let right = self.parse_expr()?;
+                    self.expect(')')?;
+                    let op = if name == "min" {
+                        BinaryOp::Min
+                    } else {
+                        BinaryOp::Max
+                    };
+                    return self.context.binary(op, left, right);
+                }
+                let rest = &self.expression[self.pos..];
+                let end = rest
+                    .find(')')
+                    .ok_or_else(|| self.error(&format!("unclosed call to '{name}'")))?;
+                self.pos += end + 1;
+                self.context.function(name, rest[..end].trim())
+            }
+            Some(_) => Err(self.unexpected()),
+            None => Err(self.error("unexpected end of expression")),
+        }
+    }
+
+    /// 读取由字母、数字和下划线组成的单词
+    fn word(&mut self) -> &'a str {
+        let expression = self.expression;
+        let start = self.pos;
+        let len = expression[start..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(expression.len() - start);
+        self.pos += len;
+        &expression[start..self.pos]
+    }
+
+    /// 跳过空白后的下一个字符
+    fn peek(&mut self) -> Option<char> {
+        let rest = &self.expression[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+        self.expression[self.pos..].chars().next()
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), C::Error> {
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{expected}'")))
+        }
+    }
+
+    fn unexpected(&mut self) -> C::Error {
+        match self.peek() {
+            Some(c) => self.error(&format!("unexpected character '{c}'")),
+            None => self.error("unexpected end of expression"),
+        }
+    }
+
+    fn error(&self, reason: &str) -> C::Error {
+        self.context.syntax_error(self.expression, reason)
+    }
+}
+
+/// 收集字段函数调用的上下文
+#[derive(Default)]
+struct ReferenceCollector {
+    references: RefCell<Vec<(String, String)>>,
+}
+
+impl ExpressionContext for ReferenceCollector {
+    type Value = ();
+    type Error = ();
+
+    fn number(&self, _value: u64) {}
+
+    fn binary(&self, _op: BinaryOp, _left: (), _right: ()) -> Result<(), ()> {
+        Ok(())
+    }
+
+    fn function(&self, name: &str, field_name: &str) -> Result<(), ()> {
+        self.references
+            .borrow_mut()
+            .push((name.to_string(), field_name.to_string()));
+        Ok(())
+    }
+
+    fn identifier(&self, _name: &str) -> Result<(), ()> {
+        Ok(())
+    }
+
+    fn syntax_error(&self, _expression: &str, _reason: &str) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 按u64求值的测试上下文，`len(x)`为字段名长度
+    struct Plain;
+
+    impl ExpressionContext for Plain {
+        type Value = u64;
+        type Error = String;
+
+        fn number(&self, value: u64) -> u64 {
+            value
+        }
+
+        fn binary(&self, op: BinaryOp, left: u64, right: u64) -> Result<u64, String> {
+            Ok(match op {
+                BinaryOp::Add => left + right,
+                BinaryOp::Sub => left.saturating_sub(right),
+                BinaryOp::Mul => left * right,
+                BinaryOp::Div => left.checked_div(right).ok_or("Division by zero")?,
+                BinaryOp::Min => left.min(right),
+                BinaryOp::Max => left.max(right),
+            })
+        }
+
+        fn function(&self, name: &str, field_name: &str) -> Result<u64, String> {
+            match name {
+                "len" => Ok(field_name.len() as u64),
+                _ => Err(format!("Unknown function: {name}")),
+            }
+        }
+
+        fn identifier(&self, name: &str) -> Result<u64, String> {
+            match name {
+                "total_length" => Ok(100),
+                _ => Err(format!("Unknown identifier: {name}")),
+            }
+        }
+
+        fn syntax_error(&self, expression: &str, reason: &str) -> String {
+            format!("Invalid expression '{expression}': {reason}")
+        }
+    }
+
+    #[test]
+    fn test_evaluate_expression() {
+        let eval = |expr: &str| evaluate_expression(expr, &Plain);
+
+        assert_eq!(eval("2 + 3 * 4"), Ok(14));
+        assert_eq!(eval("(2 + 3) * 4"), Ok(20));
+        assert_eq!(eval("\"total_length - 0x10\""), Ok(84));
+        assert_eq!(eval("'(total_length - len(abc) - 1)'"), Ok(96));
+        assert_eq!(eval("min(len( abcd ), 3) + max(1, 2)"), Ok(5));
+        assert_eq!(eval("1 / (2 - 2)"), Err("Division by zero".to_string()));
+        assert_eq!(
+            eval("(1 + 2"),
+            Err("Invalid expression '(1 + 2': expected ')'".to_string())
+        );
+        assert_eq!(
+            eval("1 + 2 $"),
+            Err("Invalid expression '1 + 2 $': unexpected character '$'".to_string())
+        );
+        assert!(eval("pos(x)").is_err());
+        assert!(eval("12ab").is_err());
+    }
+
+    #[test]
+    fn test_function_references() {
+        assert_eq!(
+            function_references("val(a) + len(b) * min(val( c ), 2)"),
+            vec![
+                ("val".to_string(), "a".to_string()),
+                ("len".to_string(), "b".to_string()),
+                ("val".to_string(), "c".to_string()),
+            ]
+        );
+        assert!(function_references("total_length - 1").is_empty());
+    }
+}
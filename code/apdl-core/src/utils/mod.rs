@@ -2,20 +2,113 @@
 //!
 //! 提供APDL系统中常用的工具函数
 
-/// CRC-16校验算法
-pub fn calculate_ccsds_crc(data: &[u8]) -> u16 {
-    let mut crc = 0xFFFF;
+pub mod condition;
+pub mod expression;
+pub mod time_code;
+pub mod value_format;
+
+use serde::{Deserialize, Serialize};
+
+pub use value_format::ValueFormat;
+
+/// CRC-16参数（Rocksoft模型）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Crc16Params {
+    /// 生成多项式（不含最高位）
+    pub poly: u16,
+    /// 寄存器初值
+    pub init: u16,
+    /// 输入字节是否按位反转
+    pub refin: bool,
+    /// 输出是否按位反转
+    pub refout: bool,
+    /// 输出异或值
+    pub xorout: u16,
+}
+
+impl Crc16Params {
+    /// CCSDS使用的CRC-16/CCITT-FALSE
+    pub const CCSDS: Crc16Params = Crc16Params {
+        poly: 0x1021,
+        init: 0xFFFF,
+        refin: false,
+        refout: false,
+        xorout: 0x0000,
+    };
+
+    /// CRC-16/ARC
+    pub const ARC: Crc16Params = Crc16Params {
+        poly: 0x8005,
+        init: 0x0000,
+        refin: true,
+        refout: true,
+        xorout: 0x0000,
+    };
+}
+
+impl Default for Crc16Params {
+    fn default() -> Self {
+        Crc16Params::CCSDS
+    }
+}
+
+/// 按指定参数计算CRC-16
+pub fn calculate_crc16_with(params: Crc16Params, data: &[u8]) -> u16 {
+    let mut crc = params.init;
     for &byte in data {
+        let byte = if params.refin {
+            byte.reverse_bits()
+        } else {
+            byte
+        };
         crc ^= (byte as u16) << 8;
         for _ in 0..8 {
             if (crc & 0x8000) != 0 {
-                crc = (crc << 1) ^ 0x1021;
+                crc = (crc << 1) ^ params.poly;
             } else {
                 crc <<= 1;
             }
         }
     }
-    crc
+    if params.refout {
+        crc = crc.reverse_bits();
+    }
+    crc ^ params.xorout
+}
+
+/// CRC-16校验算法
+pub fn calculate_ccsds_crc(data: &[u8]) -> u16 {
+    calculate_crc16_with(Crc16Params::CCSDS, data)
+}
+
+/// CRC-16/CCITT-FALSE查找表（多项式0x1021），编译期生成
+const CCSDS_CRC_TABLE: [u16; 256] = build_ccsds_crc_table();
+
+const fn build_ccsds_crc_table() -> [u16; 256] {
+    let mut table = [0u16; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = (i as u16) << 8;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if (crc & 0x8000) != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// CRC-16校验算法（查表实现，结果与`calculate_ccsds_crc`一致）
+pub fn calculate_ccsds_crc_table(data: &[u8]) -> u16 {
+    data.iter().fold(0xFFFF, |crc, &byte| {
+        (crc << 8) ^ CCSDS_CRC_TABLE[((crc >> 8) as u8 ^ byte) as usize]
+    })
 }
 
 /// 将字节数组转换为十六进制字符串
@@ -27,6 +120,25 @@ pub fn bytes_to_hex(bytes: &[u8]) -> String {
         .join(" ")
 }
 
+/// 规范化用户输入的十六进制文本：去除`0x`前缀和所有空白，
+/// 数字个数为奇数或含非十六进制字符时报错
+pub fn normalize_hex(hex: &str) -> Result<String, String> {
+    let hex = hex.trim();
+    let hex: String = hex
+        .strip_prefix("0x")
+        .or_else(|| hex.strip_prefix("0X"))
+        .unwrap_or(hex)
+        .split_whitespace()
+        .collect();
+    if let Some(c) = hex.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(format!("invalid hex digit '{c}'"));
+    }
+    if !hex.len().is_multiple_of(2) {
+        return Err(format!("odd number of digits ({})", hex.len()));
+    }
+    Ok(hex)
+}
+
 /// 将十六进制字符串转换为字节数组
 pub fn hex_to_bytes(hex_str: &str) -> Result<Vec<u8>, std::num::ParseIntError> {
     let clean_str = hex_str.replace(" ", "");
@@ -39,8 +151,34 @@ pub fn hex_to_bytes(hex_str: &str) -> Result<Vec<u8>, std::num::ParseIntError> {
     Ok(bytes)
 }
 
+/// 按字节序将不超过8字节的值解码为无符号整数，超过8字节时返回None
+pub fn decode_uint(value: &[u8], byte_order: crate::ByteOrder) -> Option<u64> {
+    if value.len() > 8 {
+        return None;
+    }
+    let fold = |acc: u64, &byte: &u8| (acc << 8) | byte as u64;
+    Some(match byte_order {
+        crate::ByteOrder::BigEndian => value.iter().fold(0, fold),
+        crate::ByteOrder::LittleEndian => value.iter().rev().fold(0, fold),
+    })
+}
+
+/// 查找字节模式在数据中出现的所有起始偏移
+pub fn find_pattern_offsets(data: &[u8], pattern: &[u8]) -> Vec<usize> {
+    if pattern.is_empty() || pattern.len() > data.len() {
+        return Vec::new();
+    }
+    data.windows(pattern.len())
+        .enumerate()
+        .filter(|(_, window)| *window == pattern)
+        .map(|(offset, _)| offset)
+        .collect()
+}
+
 /// 位操作工具
 pub mod bit_ops {
+    use crate::BitNumbering;
+
     /// 从字节数组中提取指定范围的位
     pub fn extract_bits(data: &[u8], start_bit: usize, bit_count: usize) -> u64 {
         let mut result = 0u64;
@@ -75,11 +213,40 @@ pub mod bit_ops {
             }
         }
     }
+
+    /// 按指定的位编号方式将值写入位数组
+    ///
+    /// `BitNumbering::Lsb0`时bit偏移0为首字节最低位，值的低位写入前面的位
+    pub fn set_bits_with_numbering(
+        data: &mut [u8],
+        start_bit: usize,
+        bit_count: usize,
+        value: u64,
+        numbering: BitNumbering,
+    ) {
+        match numbering {
+            BitNumbering::Msb0 => set_bits(data, start_bit, bit_count, value),
+            BitNumbering::Lsb0 => {
+                for i in 0..bit_count {
+                    let bit_pos = start_bit + i;
+                    if let Some(byte) = data.get_mut(bit_pos / 8) {
+                        let mask = 1u8 << (bit_pos % 8);
+                        if (value >> i) & 1 == 1 {
+                            *byte |= mask;
+                        } else {
+                            *byte &= !mask;
+                        }
+                    }
+                }
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{BitNumbering, ByteOrder};
 
     #[test]
     fn test_calculate_ccsds_crc() {
@@ -88,6 +255,60 @@ mod tests {
         assert!(crc != 0); // 简单测试，确保函数正常工作
     }
 
+    #[test]
+    fn test_ccsds_crc_table_matches_bitwise() {
+        assert_eq!(calculate_ccsds_crc_table(b"123456789"), 0x29B1);
+        assert_eq!(calculate_ccsds_crc_table(&[]), 0xFFFF);
+
+        // xorshift伪随机输入，覆盖不同长度
+        let mut state: u32 = 0x2545_F491;
+        for len in 0..512 {
+            let data: Vec<u8> = (0..len)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 17;
+                    state ^= state << 5;
+                    state as u8
+                })
+                .collect();
+            assert_eq!(calculate_ccsds_crc_table(&data), calculate_ccsds_crc(&data));
+        }
+    }
+
+    #[test]
+    fn test_set_bits_with_numbering() {
+        let mut data = [0u8; 2];
+        bit_ops::set_bits_with_numbering(&mut data, 4, 8, 0xAB, BitNumbering::Msb0);
+        assert_eq!(data, [0x0A, 0xB0]);
+
+        let mut data = [0xFFu8; 2];
+        bit_ops::set_bits_with_numbering(&mut data, 4, 8, 0xA5, BitNumbering::Lsb0);
+        assert_eq!(data, [0x5F, 0xFA]);
+    }
+
+    #[test]
+    fn test_crc16_with_params() {
+        let check = b"123456789";
+        assert_eq!(calculate_crc16_with(Crc16Params::CCSDS, check), 0x29B1);
+        assert_eq!(calculate_crc16_with(Crc16Params::ARC, check), 0xBB3D);
+        // CRC-16/UMTS：多项式0x8005，不反转
+        let umts = Crc16Params {
+            poly: 0x8005,
+            init: 0x0000,
+            ..Crc16Params::CCSDS
+        };
+        assert_eq!(calculate_crc16_with(umts, check), 0xFEE8);
+        // CRC-16/X-25：反转且输出取反
+        let x25 = Crc16Params {
+            poly: 0x1021,
+            init: 0xFFFF,
+            refin: true,
+            refout: true,
+            xorout: 0xFFFF,
+        };
+        assert_eq!(calculate_crc16_with(x25, check), 0x906E);
+    }
+
     #[test]
     fn test_bytes_to_hex() {
         let bytes = [0xAB, 0xCD, 0xEF];
@@ -102,6 +323,30 @@ mod tests {
         assert_eq!(bytes, [0xAB, 0xCD, 0xEF]);
     }
 
+    #[test]
+    fn test_normalize_hex() {
+        assert_eq!(
+            normalize_hex(" 0xEB 90\t1a\n2B "),
+            Ok("EB901a2B".to_string())
+        );
+        assert_eq!(
+            normalize_hex("EB9"),
+            Err("odd number of digits (3)".to_string())
+        );
+        assert_eq!(
+            normalize_hex("EB 9G"),
+            Err("invalid hex digit 'G'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_decode_uint_by_byte_order() {
+        let value = [0x12, 0x34, 0x56];
+        assert_eq!(decode_uint(&value, ByteOrder::BigEndian), Some(0x123456));
+        assert_eq!(decode_uint(&value, ByteOrder::LittleEndian), Some(0x563412));
+        assert_eq!(decode_uint(&[0xFF; 9], ByteOrder::BigEndian), None);
+    }
+
     #[test]
     fn test_extract_bits() {
         let data = [0b11001010, 0b10110101];
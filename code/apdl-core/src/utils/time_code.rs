@@ -0,0 +1,269 @@
+//! CCSDS时间码工具
+//!
+//! 实现CUC（非分段时间码）和CDS（日分段时间码）的编解码（CCSDS 301.0-B-4）
+
+use crate::ProtocolError;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// CCSDS纪元（1958-01-01）相对UNIX纪元的秒数
+const CCSDS_EPOCH_OFFSET_SECS: u64 = 378_691_200;
+
+/// 每天的秒数
+const SECS_PER_DAY: u64 = 86_400;
+
+/// 获取CCSDS推荐纪元 1958-01-01T00:00:00
+pub fn ccsds_epoch() -> SystemTime {
+    UNIX_EPOCH - Duration::from_secs(CCSDS_EPOCH_OFFSET_SECS)
+}
+
+/// 检查CUC格式参数
+pub fn check_cuc_format(coarse: u8, fine: u8) -> Result<(), ProtocolError> {
+    if !(1..=4).contains(&coarse) || fine > 3 {
+        return Err(ProtocolError::InvalidFieldDefinition(format!(
+            "Invalid CUC format: coarse={coarse} (1..=4), fine={fine} (0..=3)"
+        )));
+    }
+    Ok(())
+}
+
+/// 将相对纪元的时间间隔编码为CUC时间码（大端）
+pub fn encode_cuc(elapsed: Duration, coarse: u8, fine: u8) -> Result<Vec<u8>, ProtocolError> {
+    check_cuc_format(coarse, fine)?;
+
+    let coarse_bits = coarse as u32 * 8;
+    let seconds = elapsed.as_secs();
+    if coarse_bits < 64 && seconds >> coarse_bits != 0 {
+        return Err(ProtocolError::ValueOutOfRange(format!(
+            "CUC coarse time {seconds} exceeds {coarse} bytes"
+        )));
+    }
+
+    let fine_bits = fine as u32 * 8;
+    let fraction = ((elapsed.subsec_nanos() as u128) << fine_bits) / 1_000_000_000;
+
+    let mut bytes = Vec::with_capacity((coarse + fine) as usize);
+    for i in (0..coarse).rev() {
+        bytes.push((seconds >> (i as u32 * 8)) as u8);
+    }
+    for i in (0..fine).rev() {
+        bytes.push((fraction >> (i as u32 * 8)) as u8);
+    }
+    Ok(bytes)
+}
+
+/// 将CUC时间码解码为相对纪元的时间间隔
+pub fn decode_cuc(data: &[u8], coarse: u8, fine: u8) -> Result<Duration, ProtocolError> {
+    check_cuc_format(coarse, fine)?;
+
+    let size = (coarse + fine) as usize;
+    if data.len() < size {
+        return Err(ProtocolError::LengthError(format!(
+            "CUC time code expected {size} bytes, got {}",
+            data.len()
+        )));
+    }
+
+    let (coarse_bytes, fine_bytes) = data[..size].split_at(coarse as usize);
+    let seconds = coarse_bytes
+        .iter()
+        .fold(0u64, |acc, &b| (acc << 8) | b as u64);
+    let fraction = fine_bytes
+        .iter()
+        .fold(0u128, |acc, &b| (acc << 8) | b as u128);
+    let nanos = (fraction * 1_000_000_000) >> (fine as u32 * 8);
+
+    Ok(Duration::new(seconds, nanos as u32))
+}
+
+/// 检查CDS格式参数
+pub fn check_cds_format(day_bytes: u8, submilli_bytes: u8) -> Result<(), ProtocolError> {
+    if !matches!(day_bytes, 2 | 3) || !matches!(submilli_bytes, 0 | 2 | 4) {
+        return Err(ProtocolError::InvalidFieldDefinition(format!(
+            "Invalid CDS format: day_bytes={day_bytes} (2|3), submilli_bytes={submilli_bytes} (0|2|4)"
+        )));
+    }
+    Ok(())
+}
+
+/// 将相对纪元的时间间隔编码为CDS时间码（天 + 日内毫秒 + 亚毫秒）
+pub fn encode_cds(
+    elapsed: Duration,
+    day_bytes: u8,
+    submilli_bytes: u8,
+) -> Result<Vec<u8>, ProtocolError> {
+    check_cds_format(day_bytes, submilli_bytes)?;
+
+    let seconds = elapsed.as_secs();
+    let days = seconds / SECS_PER_DAY;
+    if days >> (day_bytes as u32 * 8) != 0 {
+        return Err(ProtocolError::ValueOutOfRange(format!(
+            "CDS day count {days} exceeds {day_bytes} bytes"
+        )));
+    }
+
+    let nanos = elapsed.subsec_nanos() as u64;
+    let millis_of_day = (seconds % SECS_PER_DAY) * 1000 + nanos / 1_000_000;
+    let submillis = match submilli_bytes {
+        2 => (nanos % 1_000_000) / 1000, // 微秒
+        4 => (nanos % 1_000_000) * 1000, // 皮秒
+        _ => 0,
+    };
+
+    let mut bytes = Vec::with_capacity((day_bytes + 4 + submilli_bytes) as usize);
+    for i in (0..day_bytes).rev() {
+        bytes.push((days >> (i as u32 * 8)) as u8);
+    }
+    bytes.extend_from_slice(&(millis_of_day as u32).to_be_bytes());
+    for i in (0..submilli_bytes).rev() {
+        bytes.push((submillis >> (i as u32 * 8)) as u8);
+    }
+    Ok(bytes)
+}
+
+/// 将CDS时间码解码为相对纪元的时间间隔
+pub fn decode_cds(
+    data: &[u8],
+    day_bytes: u8,
+    submilli_bytes: u8,
+) -> Result<Duration, ProtocolError> {
+    check_cds_format(day_bytes, submilli_bytes)?;
+
+    let size = (day_bytes + 4 + submilli_bytes) as usize;
+    if data.len() < size {
+        return Err(ProtocolError::LengthError(format!(
+            "CDS time code expected {size} bytes, got {}",
+            data.len()
+        )));
+    }
+
+    let to_u64 = |bytes: &[u8]| bytes.iter().fold(0u64, |acc, &b| (acc << 8) | b as u64);
+    let day_end = day_bytes as usize;
+    let days = to_u64(&data[..day_end]);
+    let millis_of_day = to_u64(&data[day_end..day_end + 4]);
+    let submillis = to_u64(&data[day_end + 4..size]);
+
+    let sub_nanos = match submilli_bytes {
+        2 => submillis * 1000,
+        4 => submillis / 1000,
+        _ => 0,
+    };
+
+    Ok(Duration::from_secs(days * SECS_PER_DAY)
+        + Duration::from_millis(millis_of_day)
+        + Duration::from_nanos(sub_nanos))
+}
+
+/// 计算时间相对纪元的时间间隔
+fn elapsed_since(time: SystemTime, epoch: SystemTime) -> Result<Duration, ProtocolError> {
+    time.duration_since(epoch).map_err(|_| {
+        ProtocolError::ValueOutOfRange("Time is earlier than time code epoch".to_string())
+    })
+}
+
+/// 将系统时间编码为CUC时间码
+pub fn encode_cuc_time(
+    time: SystemTime,
+    epoch: SystemTime,
+    coarse: u8,
+    fine: u8,
+) -> Result<Vec<u8>, ProtocolError> {
+    encode_cuc(elapsed_since(time, epoch)?, coarse, fine)
+}
+
+/// 将CUC时间码解码为系统时间
+pub fn decode_cuc_time(
+    data: &[u8],
+    epoch: SystemTime,
+    coarse: u8,
+    fine: u8,
+) -> Result<SystemTime, ProtocolError> {
+    Ok(epoch + decode_cuc(data, coarse, fine)?)
+}
+
+/// 将系统时间编码为CDS时间码
+pub fn encode_cds_time(
+    time: SystemTime,
+    epoch: SystemTime,
+    day_bytes: u8,
+    submilli_bytes: u8,
+) -> Result<Vec<u8>, ProtocolError> {
+    encode_cds(elapsed_since(time, epoch)?, day_bytes, submilli_bytes)
+}
+
+/// 将CDS时间码解码为系统时间
+pub fn decode_cds_time(
+    data: &[u8],
+    epoch: SystemTime,
+    day_bytes: u8,
+    submilli_bytes: u8,
+) -> Result<SystemTime, ProtocolError> {
+    Ok(epoch + decode_cds(data, day_bytes, submilli_bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cuc_4_2_round_trip_known_epoch_time() {
+        // 2000-01-01T12:00:00.5 UTC（UNIX 946728000.5）
+        let time = UNIX_EPOCH + Duration::from_millis(946_728_000_500);
+        let epoch = ccsds_epoch();
+
+        let encoded = encode_cuc_time(time, epoch, 4, 2).unwrap();
+        // 946728000 + 378691200 = 1325419200 = 0x4F004AC0，0.5秒 = 0x8000
+        assert_eq!(encoded, vec![0x4F, 0x00, 0x4A, 0xC0, 0x80, 0x00]);
+
+        let decoded = decode_cuc_time(&encoded, epoch, 4, 2).unwrap();
+        assert_eq!(decoded, time);
+    }
+
+    #[test]
+    fn test_cuc_fine_resolution() {
+        let elapsed = Duration::new(100, 123_456_789);
+        let encoded = encode_cuc(elapsed, 4, 2).unwrap();
+        let decoded = decode_cuc(&encoded, 4, 2).unwrap();
+
+        // 2字节小数部分分辨率约15.3微秒
+        let diff = elapsed.as_nanos() - decoded.as_nanos();
+        assert!(diff < 15_259, "diff = {diff}ns");
+    }
+
+    #[test]
+    fn test_cuc_invalid_format_and_overflow() {
+        assert!(encode_cuc(Duration::from_secs(1), 0, 2).is_err());
+        assert!(encode_cuc(Duration::from_secs(1), 4, 4).is_err());
+        assert!(encode_cuc(Duration::from_secs(0x1_0000), 2, 0).is_err());
+        assert!(decode_cuc(&[0x00, 0x01], 4, 2).is_err());
+    }
+
+    #[test]
+    fn test_cds_round_trip() {
+        // 1958-01-02T00:00:01.002003
+        let elapsed = Duration::new(SECS_PER_DAY + 1, 2_003_000);
+        let encoded = encode_cds(elapsed, 2, 2).unwrap();
+        assert_eq!(
+            encoded,
+            vec![0x00, 0x01, 0x00, 0x00, 0x03, 0xEA, 0x00, 0x03]
+        );
+
+        let decoded = decode_cds(&encoded, 2, 2).unwrap();
+        assert_eq!(decoded, elapsed);
+    }
+
+    #[test]
+    fn test_time_code_byte_size_does_not_overflow_u8() {
+        use crate::UnitType;
+
+        let cuc = UnitType::CucTime {
+            coarse: 200,
+            fine: 100,
+        };
+        assert_eq!(cuc.fixed_byte_size(), Some(300));
+        let cds = UnitType::CdsTime {
+            day_bytes: 255,
+            submilli_bytes: 255,
+        };
+        assert_eq!(cds.fixed_byte_size(), Some(514));
+    }
+}
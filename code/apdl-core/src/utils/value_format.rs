@@ -0,0 +1,137 @@
+//! 数值格式化工具
+//!
+//! 按指定进制渲染字段值和约束，供报告和文档导出使用
+
+use crate::Constraint;
+
+/// 数值显示进制
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ValueFormat {
+    /// 十进制
+    Dec,
+    /// 十六进制，按字段位宽补零
+    Hex,
+    /// 二进制，按字段位宽补零
+    Bin,
+    /// 自动选择：掩码和同步标志等固定值使用十六进制，范围和普通数值使用十进制
+    #[default]
+    Auto,
+}
+
+impl ValueFormat {
+    /// 格式化普通数值（Auto使用十进制）
+    pub fn format_value(self, value: u64, bit_width: Option<u32>) -> String {
+        self.resolve(ValueFormat::Dec).render(value, bit_width)
+    }
+
+    /// 格式化掩码（Auto使用十六进制）
+    pub fn format_mask(self, mask: u64, bit_width: Option<u32>) -> String {
+        self.resolve(ValueFormat::Hex).render(mask, bit_width)
+    }
+
+    /// 格式化字段值，带固定值约束的字段（如同步标志）在Auto下使用十六进制
+    pub fn format_field_value(
+        self,
+        value: u64,
+        bit_width: Option<u32>,
+        constraint: Option<&Constraint>,
+    ) -> String {
+        match constraint {
+            Some(Constraint::FixedValue(_)) => self.format_mask(value, bit_width),
+            _ => self.format_value(value, bit_width),
+        }
+    }
+
+    /// 格式化约束，固定值（如同步标志）在Auto下使用十六进制，范围和枚举使用十进制
+    pub fn format_constraint(self, constraint: &Constraint, bit_width: Option<u32>) -> String {
+        match constraint {
+            Constraint::FixedValue(value) => format!(
+                "fixed({})",
+                self.resolve(ValueFormat::Hex).render(*value, bit_width)
+            ),
+            Constraint::Range(min, max) => {
+                let format = self.resolve(ValueFormat::Dec);
+                format!(
+                    "range({}..={})",
+                    format.render(*min, bit_width),
+                    format.render(*max, bit_width)
+                )
+            }
+            Constraint::Enum(entries) => {
+                let format = self.resolve(ValueFormat::Dec);
+                let values: Vec<String> = entries
+                    .iter()
+                    .map(|(name, value)| format!("{name}={}", format.render(*value, bit_width)))
+                    .collect();
+                format!("enum({})", values.join(", "))
+            }
+            Constraint::Custom(expr) => expr.clone(),
+        }
+    }
+
+    /// Auto时替换为给定的默认进制
+    fn resolve(self, auto: ValueFormat) -> ValueFormat {
+        match self {
+            ValueFormat::Auto => auto,
+            format => format,
+        }
+    }
+
+    /// 按进制渲染数值
+    fn render(self, value: u64, bit_width: Option<u32>) -> String {
+        match self {
+            ValueFormat::Hex => {
+                let digits = bit_width.map_or(0, |bits| bits.div_ceil(4) as usize);
+                format!("0x{value:0digits$X}")
+            }
+            ValueFormat::Bin => {
+                let digits = bit_width.unwrap_or(0) as usize;
+                format!("0b{value:0digits$b}")
+            }
+            ValueFormat::Dec | ValueFormat::Auto => value.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_value_bases() {
+        assert_eq!(ValueFormat::Dec.format_value(0x2A, Some(8)), "42");
+        assert_eq!(ValueFormat::Hex.format_value(0x2A, Some(16)), "0x002A");
+        assert_eq!(ValueFormat::Bin.format_value(5, Some(4)), "0b0101");
+        assert_eq!(ValueFormat::Auto.format_value(42, Some(8)), "42");
+        assert_eq!(ValueFormat::Auto.format_mask(0x07FF, Some(16)), "0x07FF");
+
+        let sync = Constraint::FixedValue(0xEB90);
+        assert_eq!(
+            ValueFormat::Auto.format_field_value(0xEB90, Some(16), Some(&sync)),
+            "0xEB90"
+        );
+    }
+
+    #[test]
+    fn test_auto_format_constraints() {
+        let sync = Constraint::FixedValue(0xEB90);
+        assert_eq!(
+            ValueFormat::Auto.format_constraint(&sync, Some(16)),
+            "fixed(0xEB90)"
+        );
+        assert_eq!(
+            ValueFormat::Dec.format_constraint(&sync, Some(16)),
+            "fixed(60304)"
+        );
+
+        let range = Constraint::Range(0, 2047);
+        assert_eq!(
+            ValueFormat::Auto.format_constraint(&range, Some(11)),
+            "range(0..=2047)"
+        );
+        assert_eq!(
+            ValueFormat::Hex.format_constraint(&range, Some(11)),
+            "range(0x000..=0x7FF)"
+        );
+    }
+}
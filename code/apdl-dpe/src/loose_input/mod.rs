@@ -10,8 +10,79 @@ impl LooseInputAdapter {
         Self
     }
 
+    /// 将粘贴的DSL文本规范化为`DslParserImpl`可解析的形式
+    ///
+    /// - 去除BOM，统一换行符
+    /// - 中文弯引号转换为ASCII引号
+    /// - 引号外的全角字符（如输入法产生的`：；（）`）转换为ASCII，`0X`前缀统一为`0x`
+    /// - 引号外的连续空白合并为一个空格，`;`统一为`; `，并去除行首尾空白
+    ///
+    /// 引号内的描述文本保持原样
     pub fn adapt(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
-        // 简单的适配逻辑，实际实现会更复杂
-        Ok(input.to_string())
+        let input = input.replace('\u{FEFF}', "");
+        let lines: Vec<String> = input.lines().map(normalize_line).collect();
+        Ok(lines.join("\n"))
     }
 }
+
+fn normalize_line(line: &str) -> String {
+    let chars: Vec<char> = line.chars().map(ascii_quote).collect();
+    let mut output = String::with_capacity(line.len());
+    let mut in_quote = false;
+
+    for (index, &c) in chars.iter().enumerate() {
+        if c == '"' {
+            in_quote = !in_quote;
+            output.push(c);
+            continue;
+        }
+        if in_quote {
+            output.push(c);
+            continue;
+        }
+        match half_width(c) {
+            c if c.is_whitespace() => {
+                if !output.is_empty() && !output.ends_with(' ') {
+                    output.push(' ');
+                }
+            }
+            ';' => {
+                output.truncate(output.trim_end().len());
+                output.push_str("; ");
+            }
+            'X' if is_hex_prefix(&output, chars.get(index + 1).copied()) => output.push('x'),
+            c => output.push(c),
+        }
+    }
+    output.trim().to_string()
+}
+
+/// 弯引号转换为ASCII引号
+fn ascii_quote(c: char) -> char {
+    match c {
+        '\u{201C}' | '\u{201D}' | '\u{FF02}' => '"',
+        '\u{2018}' | '\u{2019}' | '\u{FF07}' => '\'',
+        c => c,
+    }
+}
+
+/// 全角ASCII字符和全角空格转换为半角
+fn half_width(c: char) -> char {
+    match c {
+        '\u{3000}' => ' ',
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        c => c,
+    }
+}
+
+/// 判断`X`是否为十六进制前缀：前面是独立的`0`，后面是十六进制数字
+fn is_hex_prefix(output: &str, next: Option<char>) -> bool {
+    let Some(before_zero) = output.strip_suffix('0') else {
+        return false;
+    };
+    let standalone = !before_zero
+        .chars()
+        .next_back()
+        .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_');
+    standalone && next.map(half_width).is_some_and(|c| c.is_ascii_hexdigit())
+}
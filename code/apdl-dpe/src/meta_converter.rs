@@ -3,6 +3,10 @@
 //! 实现协议元数据在不同格式间的转换
 
 use apdl_core::protocol_meta::UnitMeta;
+use apdl_core::{
+    AlgorithmAst, ByteOrder, Constraint, CoverDesc, LengthUnit, PackUnpackSpec, ScopeDesc,
+    SyntaxUnit, UnitType,
+};
 
 /// 元数据转换器
 #[derive(Default)]
@@ -41,4 +45,130 @@ impl MetaConverter {
             dsl_definition: json_str.to_string(),
         })
     }
+
+    /// 将语法单元还原为单行DSL定义，可由`DslParserImpl::parse_syntax_unit`重新解析
+    ///
+    /// 描述类文本用双引号包裹，其中的`;`转义为`\;`以免截断定义行；
+    /// 打包规范只保留字节序（`endian:`），其余设置无法用DSL表达
+    pub fn unit_to_dsl(&self, unit: &SyntaxUnit) -> String {
+        let mut parts = vec![
+            format!("field: {}", unit.field_id),
+            format!("type: {}", type_to_dsl(&unit.unit_type)),
+            format!("length: {}", length_to_dsl(unit)),
+            format!("scope: {}", scope_to_dsl(&unit.scope)),
+            format!("cover: {}", cover_to_dsl(&unit.cover)),
+        ];
+        if let Some(constraint) = &unit.constraint {
+            parts.push(format!("constraint: {}", constraint_to_dsl(constraint)));
+        }
+        if let Some(alg) = &unit.alg {
+            parts.push(format!("alg: {}", algorithm_to_dsl(alg)));
+        }
+        if !unit.associate.is_empty() {
+            parts.push(format!("associate: {}", unit.associate.join(", ")));
+        }
+        if let Some(PackUnpackSpec { byte_order, .. }) = &unit.pack_unpack_spec {
+            let endian = match byte_order {
+                ByteOrder::BigEndian => "be",
+                ByteOrder::LittleEndian => "le",
+            };
+            parts.push(format!("endian: {endian}"));
+        }
+        if let Some(unit_label) = &unit.unit_label {
+            parts.push(format!("unit: {}", quote_text(unit_label)));
+        }
+        if let Some(long_description) = &unit.long_description {
+            parts.push(format!("long_desc: {}", quote_text(long_description)));
+        }
+        parts.push(format!("desc: {}", quote_text(&unit.desc)));
+        parts.join("; ")
+    }
+}
+
+fn type_to_dsl(unit_type: &UnitType) -> String {
+    match unit_type {
+        UnitType::Uint(bits) => format!("Uint{bits}"),
+        UnitType::Bit(bits) => format!("Bit({bits})"),
+        UnitType::RawData => "RawData".to_string(),
+        UnitType::Ip6Addr => "Ip6Addr".to_string(),
+        UnitType::CucTime { coarse, fine } => format!("CUC({coarse},{fine})"),
+        UnitType::CdsTime {
+            day_bytes,
+            submilli_bytes,
+        } => format!("CDS({day_bytes},{submilli_bytes})"),
+    }
+}
+
+fn length_to_dsl(unit: &SyntaxUnit) -> String {
+    let size = unit.length.size;
+    match &unit.length.unit {
+        // 解析器拒绝`0byte`，不带单位的长度默认为字节
+        LengthUnit::Byte if size == 0 => "0".to_string(),
+        LengthUnit::Byte => format!("{size}byte"),
+        LengthUnit::Bit => format!("{size}bit"),
+        LengthUnit::Dynamic => "dynamic".to_string(),
+        LengthUnit::Expression(expression) => expression.clone(),
+    }
+}
+
+fn scope_to_dsl(scope: &ScopeDesc) -> String {
+    match scope {
+        ScopeDesc::Layer(layer) => format!("layer({layer})"),
+        ScopeDesc::CrossLayer(first, second) => format!("cross_layer({first}→{second})"),
+        ScopeDesc::Global(name) => format!("global({name})"),
+    }
+}
+
+fn cover_to_dsl(cover: &CoverDesc) -> String {
+    match cover {
+        CoverDesc::EntireField => "entire_field".to_string(),
+        CoverDesc::Range(field, start, end) => format!("{field}[{start}..{end}]"),
+        CoverDesc::Expression(expression) => expression.clone(),
+    }
+}
+
+fn constraint_to_dsl(constraint: &Constraint) -> String {
+    match constraint {
+        Constraint::FixedValue(value) => format!("fixed(0x{value:X})"),
+        Constraint::Range(min, max) => format!("range({min}..={max})"),
+        Constraint::Enum(entries) => {
+            let entries: Vec<String> = entries
+                .iter()
+                .map(|(name, value)| format!("{name}={value}"))
+                .collect();
+            format!("enum({})", entries.join(","))
+        }
+        Constraint::Custom(text) => text.clone(),
+    }
+}
+
+fn algorithm_to_dsl(alg: &AlgorithmAst) -> String {
+    match alg {
+        AlgorithmAst::Crc16 => "crc16".to_string(),
+        AlgorithmAst::Crc16With(params) => format!(
+            "crc16(poly=0x{:04X},init=0x{:04X},xorout=0x{:04X},refin={},refout={})",
+            params.poly, params.init, params.xorout, params.refin, params.refout
+        ),
+        AlgorithmAst::Crc32 => "crc32".to_string(),
+        AlgorithmAst::Crc15 => "crc15".to_string(),
+        AlgorithmAst::XorSum => "xor_sum".to_string(),
+        AlgorithmAst::Custom(name) => name.clone(),
+    }
+}
+
+/// 用双引号包裹文本，转义反斜杠、DSL分隔符`;`、双引号和换行，使其解析后原样还原
+fn quote_text(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            ';' => quoted.push_str("\\;"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
 }
@@ -2,6 +2,10 @@
 //!
 //! 实现对多种文档格式的解析功能
 
+mod yaml;
+
+pub use yaml::{YamlDocument, YamlError, YamlParser};
+
 use std::collections::HashMap;
 
 /// 解析器类型
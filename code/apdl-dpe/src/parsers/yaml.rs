@@ -0,0 +1,724 @@
+//! YAML协议定义解析器
+//!
+//! 将YAML文档解析为`PackageDefinition`和`ProtocolStackDefinition`。
+//! 字段的类型、长度、约束等取值沿用DSL的写法，直接调用apdl-poem的DSL解析函数映射为核心枚举，
+//! 保证两种定义格式的取值语法和错误信息一致。
+//!
+//! 只支持YAML的一个子集：块映射、块列表（含`- key: value`形式的映射元素）、
+//! 单行流式列表`[a, b]`、单/双引号字符串和`#`注释。
+//! 锚点与别名、标签、合并键、块标量（`|`、`>`）、流式映射、嵌套流式列表和多文档
+//! 不被支持，遇到时返回`YamlError::Syntax`，不会按普通字符串静默读取。
+//!
+//! ```yaml
+//! packages:
+//!   - name: space_packet
+//!     type: telemetry
+//!     layers:
+//!       - name: net
+//!         fields:
+//!           - field: version
+//!             type: Bit(3)
+//!             length: 3bit
+//!             constraint: fixed(0)
+//!         rules:
+//!           - "length_rule(field: length; expression: len(data) - 1)"
+//! stack:
+//!   name: tm_stack
+//!   packages: [space_packet]
+//! ```
+
+use apdl_core::{
+    LayerDefinition, LengthUnit, PackageDefinition, ProtocolStackDefinition, SemanticRule,
+    SyntaxUnit,
+};
+use apdl_poem::dsl::parser_utils::{
+    byte_order_pack_spec, parse_algorithm, parse_byte_order, parse_constraint, parse_cover_desc,
+    parse_length_desc, parse_scope_desc, parse_unit_type,
+};
+use apdl_poem::DslParserImpl;
+use std::fmt;
+
+/// YAML解析错误
+#[derive(Debug, Clone, PartialEq)]
+pub enum YamlError {
+    /// YAML语法错误，行号从1开始
+    Syntax { line: usize, message: String },
+    /// 文档结构或取值错误，`path`为出错位置，如`space_packet/net/apid`
+    Invalid { path: String, message: String },
+}
+
+impl fmt::Display for YamlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            YamlError::Syntax { line, message } => {
+                write!(f, "YAML syntax error on line {line}: {message}")
+            }
+            YamlError::Invalid { path, message } => write!(f, "Invalid '{path}': {message}"),
+        }
+    }
+}
+
+impl std::error::Error for YamlError {}
+
+/// YAML文档解析结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct YamlDocument {
+    pub packages: Vec<PackageDefinition>,
+    pub stack: Option<ProtocolStackDefinition>,
+}
+
+/// YAML协议定义解析器
+#[derive(Default)]
+pub struct YamlParser;
+
+impl YamlParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 解析YAML文档
+    ///
+    /// 顶层可包含`packages`（包列表）和`stack`（协议栈）。字段可用`bit_offset`声明起始位，
+    /// 与前序字段重叠或留有空隙时返回错误
+    pub fn parse(&self, content: &str) -> Result<YamlDocument, YamlError> {
+        let root = read_document(content)?;
+        let root = expect_map(&root, "<root>")?;
+        check_keys(root, &["packages", "stack"], "<root>")?;
+
+        let packages = match lookup(root, "packages") {
+            Some(value) => expect_list(value, "packages")?
+                .iter()
+                .enumerate()
+                .map(|(index, package)| self.parse_package(package, index))
+                .collect::<Result<_, _>>()?,
+            None => Vec::new(),
+        };
+        let stack = lookup(root, "stack")
+            .map(|stack| self.parse_stack(stack))
+            .transpose()?;
+
+        Ok(YamlDocument { packages, stack })
+    }
+
+    fn parse_package(
+        &self,
+        value: &YamlValue,
+        index: usize,
+    ) -> Result<PackageDefinition, YamlError> {
+        let path = format!("packages[{index}]");
+        let map = expect_map(value, &path)?;
+        check_keys(
+            map,
+            &["name", "display_name", "type", "desc", "layers"],
+            &path,
+        )?;
+        let name = required_str(map, "name", &path)?;
+
+        let mut package = PackageDefinition::new(
+            name.to_string(),
+            optional_value(map, "display_name", &path)?
+                .unwrap_or(name)
+                .to_string(),
+            optional_str(map, "type", &path)?.to_string(),
+            optional_str(map, "desc", &path)?.to_string(),
+        );
+        if let Some(layers) = lookup(map, "layers") {
+            for layer in expect_list(layers, name)? {
+                package.layers.push(self.parse_layer(layer, name)?);
+            }
+        }
+        Ok(package)
+    }
+
+    fn parse_layer(&self, value: &YamlValue, package: &str) -> Result<LayerDefinition, YamlError> {
+        let map = expect_map(value, package)?;
+        check_keys(map, &["name", "fields", "rules"], package)?;
+        let name = required_str(map, "name", package)?;
+        let path = format!("{package}/{name}");
+
+        let mut units = Vec::new();
+        // 下一字段的起始位，动态长度字段之后未知
+        let mut position: Option<(usize, String)> = Some((0, String::new()));
+        for field in lookup(map, "fields")
+            .map(|fields| expect_list(fields, &path))
+            .transpose()?
+            .unwrap_or_default()
+        {
+            let (unit, bit_offset) = self.parse_field(field, name, &path)?;
+            let field_path = format!("{path}/{}", unit.field_id);
+            if let (Some(offset), Some((expected, previous))) = (bit_offset, &position) {
+                if offset < *expected {
+                    return Err(invalid(
+                        &field_path,
+                        format!(
+                            "bit_offset {offset} overlaps '{previous}', which ends at bit {expected}"
+                        ),
+                    ));
+                }
+                if offset > *expected {
+                    return Err(invalid(
+                        &field_path,
+                        format!("bit_offset {offset} leaves a gap after bit {expected}"),
+                    ));
+                }
+            }
+            position = match (&position, &unit.length.unit) {
+                (Some((start, _)), LengthUnit::Byte) => {
+                    Some((start + unit.length.size * 8, unit.field_id.clone()))
+                }
+                (Some((start, _)), LengthUnit::Bit) => {
+                    Some((start + unit.length.size, unit.field_id.clone()))
+                }
+                _ => None,
+            };
+            units.push(unit);
+        }
+
+        let mut rules = Vec::new();
+        if let Some(values) = lookup(map, "rules") {
+            for rule in expect_list(values, &path)? {
+                rules.push(parse_rule(expect_str(rule, &path)?, &path)?);
+            }
+        }
+
+        Ok(LayerDefinition {
+            name: name.to_string(),
+            units,
+            rules,
+        })
+    }
+
+    /// 解析字段，返回语法单元和声明的起始位
+    fn parse_field(
+        &self,
+        value: &YamlValue,
+        layer: &str,
+        path: &str,
+    ) -> Result<(SyntaxUnit, Option<usize>), YamlError> {
+        let map = expect_map(value, path)?;
+        let field_id = required_str(map, "field", path)?;
+        let path = format!("{path}/{field_id}");
+        check_keys(
+            map,
+            &[
+                "field",
+                "type",
+                "length",
+                "scope",
+                "cover",
+                "constraint",
+                "alg",
+                "associate",
+                "endian",
+                "unit",
+                "long_desc",
+                "desc",
+                "bit_offset",
+            ],
+            &path,
+        )?;
+        let with_path = |message: String| invalid(&path, message);
+
+        let unit_type = parse_unit_type(required_str(map, "type", &path)?).map_err(with_path)?;
+        let length = parse_length_desc(required_str(map, "length", &path)?).map_err(with_path)?;
+        let scope = match lookup(map, "scope") {
+            Some(scope) => parse_scope_desc(expect_str(scope, &path)?).map_err(with_path)?,
+            None => apdl_core::ScopeDesc::Layer(layer.to_string()),
+        };
+        let cover =
+            parse_cover_desc(optional_value(map, "cover", &path)?.unwrap_or("entire_field"))
+                .map_err(with_path)?;
+        let constraint = optional_value(map, "constraint", &path)?
+            .map(parse_constraint)
+            .transpose()
+            .map_err(with_path)?;
+        let alg = optional_value(map, "alg", &path)?
+            .map(parse_algorithm)
+            .transpose()
+            .map_err(with_path)?;
+        let byte_order = optional_value(map, "endian", &path)?
+            .map(parse_byte_order)
+            .transpose()
+            .map_err(with_path)?;
+        let associate = match lookup(map, "associate") {
+            Some(YamlValue::List(items)) => items
+                .iter()
+                .map(|item| expect_str(item, &path).map(str::to_string))
+                .collect::<Result<_, _>>()?,
+            Some(value) => expect_str(value, &path)?
+                .split(',')
+                .map(|name| name.trim().to_string())
+                .collect(),
+            None => Vec::new(),
+        };
+        let bit_offset = optional_value(map, "bit_offset", &path)?
+            .map(|offset| {
+                offset
+                    .parse::<usize>()
+                    .map_err(|_| with_path(format!("Invalid bit_offset: {offset}")))
+            })
+            .transpose()?;
+
+        let unit = SyntaxUnit {
+            field_id: field_id.to_string(),
+            unit_type,
+            length,
+            scope,
+            cover,
+            constraint,
+            alg,
+            associate,
+            desc: optional_str(map, "desc", &path)?.to_string(),
+            pack_unpack_spec: byte_order_pack_spec(byte_order),
+            unit_label: optional_value(map, "unit", &path)?.map(str::to_string),
+            long_description: optional_value(map, "long_desc", &path)?.map(str::to_string),
+        };
+        Ok((unit, bit_offset))
+    }
+
+    fn parse_stack(&self, value: &YamlValue) -> Result<ProtocolStackDefinition, YamlError> {
+        let map = expect_map(value, "stack")?;
+        check_keys(map, &["name", "packages", "connectors", "desc"], "stack")?;
+        let mut stack = ProtocolStackDefinition::new(
+            required_str(map, "name", "stack")?.to_string(),
+            optional_str(map, "desc", "stack")?.to_string(),
+        );
+        stack.packages = string_list(map, "packages", "stack")?;
+        stack.connectors = string_list(map, "connectors", "stack")?;
+        Ok(stack)
+    }
+}
+
+/// 使用DSL规则解析器解析单条语义规则，如`checksum_range(start: a; end: b)`
+fn parse_rule(text: &str, path: &str) -> Result<SemanticRule, YamlError> {
+    let mut rules = DslParserImpl::new()
+        .parse_semantic_rules(&format!("rule: {text}"))
+        .map_err(|e| invalid(path, e.to_string()))?;
+    match rules.pop() {
+        Some(rule) if rules.is_empty() => Ok(rule),
+        _ => Err(invalid(path, format!("Invalid rule: {text}"))),
+    }
+}
+
+fn invalid(path: &str, message: String) -> YamlError {
+    YamlError::Invalid {
+        path: path.to_string(),
+        message,
+    }
+}
+
+type YamlMap = [(String, YamlValue)];
+
+fn lookup<'a>(map: &'a YamlMap, key: &str) -> Option<&'a YamlValue> {
+    map.iter()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value)
+}
+
+fn check_keys(map: &YamlMap, allowed: &[&str], path: &str) -> Result<(), YamlError> {
+    match map.iter().find(|(key, _)| !allowed.contains(&key.as_str())) {
+        Some((key, _)) => Err(invalid(
+            path,
+            format!(
+                "Unknown key '{key}', expected one of: {}",
+                allowed.join(", ")
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+fn expect_map<'a>(value: &'a YamlValue, path: &str) -> Result<&'a YamlMap, YamlError> {
+    match value {
+        YamlValue::Map(map) => Ok(map),
+        _ => Err(invalid(path, "Expected a mapping".to_string())),
+    }
+}
+
+fn expect_list<'a>(value: &'a YamlValue, path: &str) -> Result<&'a [YamlValue], YamlError> {
+    match value {
+        YamlValue::List(items) => Ok(items),
+        _ => Err(invalid(path, "Expected a list".to_string())),
+    }
+}
+
+fn expect_str<'a>(value: &'a YamlValue, path: &str) -> Result<&'a str, YamlError> {
+    match value {
+        YamlValue::Scalar(text) => Ok(text),
+        _ => Err(invalid(path, "Expected a scalar value".to_string())),
+    }
+}
+
+fn required_str<'a>(map: &'a YamlMap, key: &str, path: &str) -> Result<&'a str, YamlError> {
+    match lookup(map, key) {
+        Some(value) => expect_str(value, path),
+        None => Err(invalid(path, format!("Missing required key '{key}'"))),
+    }
+}
+
+fn optional_value<'a>(
+    map: &'a YamlMap,
+    key: &str,
+    path: &str,
+) -> Result<Option<&'a str>, YamlError> {
+    lookup(map, key)
+        .map(|value| expect_str(value, path))
+        .transpose()
+}
+
+fn optional_str<'a>(map: &'a YamlMap, key: &str, path: &str) -> Result<&'a str, YamlError> {
+    Ok(optional_value(map, key, path)?.unwrap_or(""))
+}
+
+fn string_list(map: &YamlMap, key: &str, path: &str) -> Result<Vec<String>, YamlError> {
+    match lookup(map, key) {
+        Some(value) => expect_list(value, path)?
+            .iter()
+            .map(|item| expect_str(item, path).map(str::to_string))
+            .collect(),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// YAML节点
+#[derive(Debug, Clone, PartialEq)]
+enum YamlValue {
+    Scalar(String),
+    List(Vec<YamlValue>),
+    Map(Vec<(String, YamlValue)>),
+}
+
+/// 去除注释后的有效行
+struct Line {
+    number: usize,
+    indent: usize,
+    text: String,
+}
+
+fn read_document(content: &str) -> Result<YamlValue, YamlError> {
+    let mut lines: Vec<Line> = Vec::new();
+    for (index, raw) in content.lines().enumerate() {
+        let text = strip_comment(raw).trim_end();
+        let trimmed = text.trim_start();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if trimmed == "---" || trimmed == "..." {
+            if lines.is_empty() {
+                continue;
+            }
+            return Err(YamlError::Syntax {
+                line: index + 1,
+                message: "Multiple documents are not supported".to_string(),
+            });
+        }
+        if text.starts_with('\t') {
+            return Err(YamlError::Syntax {
+                line: index + 1,
+                message: "Tabs are not allowed for indentation".to_string(),
+            });
+        }
+        lines.push(Line {
+            number: index + 1,
+            indent: text.len() - trimmed.len(),
+            text: trimmed.to_string(),
+        });
+    }
+
+    let Some(first) = lines.first() else {
+        return Ok(YamlValue::Map(Vec::new()));
+    };
+    let indent = first.indent;
+    let mut pos = 0;
+    let value = read_node(&mut lines, &mut pos, indent)?;
+    match lines.get(pos) {
+        Some(line) => Err(YamlError::Syntax {
+            line: line.number,
+            message: "Unexpected indentation".to_string(),
+        }),
+        None => Ok(value),
+    }
+}
+
+/// 去除引号外的`#`注释
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut previous = ' ';
+    for (index, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), _) if c == open => quote = None,
+            (None, '#') if previous.is_whitespace() => return &line[..index],
+            _ => {}
+        }
+        previous = c;
+    }
+    line
+}
+
+fn is_list_item(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+fn read_node(lines: &mut [Line], pos: &mut usize, indent: usize) -> Result<YamlValue, YamlError> {
+    if is_list_item(&lines[*pos].text) {
+        read_list(lines, pos, indent)
+    } else {
+        read_map(lines, pos, indent)
+    }
+}
+
+fn read_list(lines: &mut [Line], pos: &mut usize, indent: usize) -> Result<YamlValue, YamlError> {
+    let mut items = Vec::new();
+    while *pos < lines.len() && lines[*pos].indent == indent && is_list_item(&lines[*pos].text) {
+        let line = &mut lines[*pos];
+        let content = line.text[1..].trim_start().to_string();
+        if content.is_empty() {
+            *pos += 1;
+            items.push(read_nested(lines, pos, indent)?);
+        } else if split_key(&content).is_some() {
+            // `- key: value`：元素为映射，其余键与首个键对齐
+            line.indent = indent + line.text.len() - content.len();
+            line.text = content;
+            let item_indent = line.indent;
+            items.push(read_map(lines, pos, item_indent)?);
+        } else {
+            let number = line.number;
+            *pos += 1;
+            items.push(read_inline(&content, number)?);
+        }
+    }
+    Ok(YamlValue::List(items))
+}
+
+fn read_map(lines: &mut [Line], pos: &mut usize, indent: usize) -> Result<YamlValue, YamlError> {
+    let mut entries: Vec<(String, YamlValue)> = Vec::new();
+    while *pos < lines.len() && lines[*pos].indent == indent && !is_list_item(&lines[*pos].text) {
+        let number = lines[*pos].number;
+        let Some((key, value)) = split_key(&lines[*pos].text) else {
+            return Err(YamlError::Syntax {
+                line: number,
+                message: format!("Expected 'key: value', found '{}'", lines[*pos].text),
+            });
+        };
+        let (key, value) = (key.to_string(), value.to_string());
+        if key == "<<" {
+            return Err(YamlError::Syntax {
+                line: number,
+                message: "Merge keys are not supported".to_string(),
+            });
+        }
+        if entries.iter().any(|(name, _)| *name == key) {
+            return Err(YamlError::Syntax {
+                line: number,
+                message: format!("Duplicate key '{key}'"),
+            });
+        }
+        *pos += 1;
+        let value = if value.is_empty() {
+            read_nested(lines, pos, indent)?
+        } else {
+            read_inline(&value, number)?
+        };
+        entries.push((key, value));
+    }
+    Ok(YamlValue::Map(entries))
+}
+
+/// 读取键或列表项之后缩进更深的块（列表也可与键对齐），没有时为空值
+fn read_nested(lines: &mut [Line], pos: &mut usize, indent: usize) -> Result<YamlValue, YamlError> {
+    match lines.get(*pos) {
+        Some(next)
+            if next.indent > indent || (next.indent == indent && is_list_item(&next.text)) =>
+        {
+            let next_indent = next.indent;
+            read_node(lines, pos, next_indent)
+        }
+        _ => Ok(YamlValue::Scalar(String::new())),
+    }
+}
+
+/// 拆分`key: value`，键不含引号
+fn split_key(text: &str) -> Option<(&str, &str)> {
+    if text.starts_with(['"', '\'', '[']) {
+        return None;
+    }
+    let (key, value) = match text.strip_suffix(':') {
+        Some(key) if !key.contains(": ") => (key, ""),
+        _ => text.split_once(": ")?,
+    };
+    let key = key.trim();
+    (!key.is_empty() && !key.contains(char::is_whitespace)).then_some((key, value.trim()))
+}
+
+fn read_inline(text: &str, line: usize) -> Result<YamlValue, YamlError> {
+    let syntax = |message: String| YamlError::Syntax { line, message };
+    if let Some(inner) = text.strip_prefix('[') {
+        let inner = inner
+            .strip_suffix(']')
+            .ok_or_else(|| syntax(format!("Unclosed flow list: {text}")))?;
+        let items = split_flow_items(inner)
+            .into_iter()
+            .filter(|item| !item.is_empty())
+            .map(|item| read_scalar(item).map_err(&syntax))
+            .collect::<Result<_, _>>()?;
+        return Ok(YamlValue::List(items));
+    }
+    read_scalar(text).map_err(syntax)
+}
+
+/// 按顶层逗号拆分流式列表，忽略引号和括号内的逗号
+fn split_flow_items(inner: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut quote = None;
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (index, c) in inner.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), _) if c == open => quote = None,
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, ',') if depth == 0 => {
+                items.push(inner[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(inner[start..].trim());
+    items
+}
+
+fn read_scalar(text: &str) -> Result<YamlValue, String> {
+    let text = text.trim();
+    let unquoted = if let Some(inner) = text.strip_prefix('"') {
+        let inner = inner
+            .strip_suffix('"')
+            .ok_or_else(|| format!("Unclosed string: {text}"))?;
+        inner.replace("\\\"", "\"").replace("\\\\", "\\")
+    } else if let Some(inner) = text.strip_prefix('\'') {
+        let inner = inner
+            .strip_suffix('\'')
+            .ok_or_else(|| format!("Unclosed string: {text}"))?;
+        inner.replace("''", "'")
+    } else if let Some(feature) = unsupported_feature(text) {
+        return Err(format!("{feature} are not supported: {text}"));
+    } else {
+        text.to_string()
+    };
+    Ok(YamlValue::Scalar(unquoted))
+}
+
+/// 未加引号的取值若以子集外语法的指示符开头，返回该语法的名称
+fn unsupported_feature(text: &str) -> Option<&'static str> {
+    match text.chars().next()? {
+        '&' => Some("Anchors"),
+        '*' => Some("Aliases"),
+        '!' => Some("Tags"),
+        '|' | '>' => Some("Block scalars"),
+        '{' => Some("Flow mappings"),
+        '[' => Some("Nested flow lists"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_nested_document() {
+        let value = read_document(
+            "# comment\n\
+             name: frame # trailing\n\
+             tags: [a, 'b c', \"enum(X=1,Y=2)\"]\n\
+             items:\n\
+             - id: 1\n\
+             \x20 desc: \"one # not a comment\"\n\
+             - plain\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            value,
+            YamlValue::Map(vec![
+                ("name".to_string(), YamlValue::Scalar("frame".to_string())),
+                (
+                    "tags".to_string(),
+                    YamlValue::List(vec![
+                        YamlValue::Scalar("a".to_string()),
+                        YamlValue::Scalar("b c".to_string()),
+                        YamlValue::Scalar("enum(X=1,Y=2)".to_string()),
+                    ])
+                ),
+                (
+                    "items".to_string(),
+                    YamlValue::List(vec![
+                        YamlValue::Map(vec![
+                            ("id".to_string(), YamlValue::Scalar("1".to_string())),
+                            (
+                                "desc".to_string(),
+                                YamlValue::Scalar("one # not a comment".to_string())
+                            ),
+                        ]),
+                        YamlValue::Scalar("plain".to_string()),
+                    ])
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_read_syntax_errors() {
+        assert_eq!(
+            read_document("a: 1\na: 2\n"),
+            Err(YamlError::Syntax {
+                line: 2,
+                message: "Duplicate key 'a'".to_string()
+            })
+        );
+        assert!(matches!(
+            read_document("a:\n  b: 1\n    c: 2\n"),
+            Err(YamlError::Syntax { line: 3, .. })
+        ));
+        assert!(matches!(
+            read_document("a: [1, 2\n"),
+            Err(YamlError::Syntax { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_unsupported_syntax_is_rejected() {
+        let cases = [
+            ("base: &id 1\n", 1, "Anchors"),
+            ("a: 1\nb: *id\n", 2, "Aliases"),
+            ("a: !!str 1\n", 1, "Tags"),
+            ("desc: |\n  text\n", 1, "Block scalars"),
+            ("desc: >-\n  text\n", 1, "Block scalars"),
+            ("a: {b: 1}\n", 1, "Flow mappings"),
+            ("a: [1, [2]]\n", 1, "Nested flow lists"),
+            ("- &item a\n", 1, "Anchors"),
+            ("a:\n  <<: *base\n", 2, "Merge keys"),
+            ("---\na: 1\n---\nb: 2\n", 3, "Multiple documents"),
+        ];
+        for (document, expected_line, feature) in cases {
+            match read_document(document) {
+                Err(YamlError::Syntax { line, message }) => {
+                    assert_eq!(line, expected_line, "{document:?}");
+                    assert!(message.starts_with(feature), "{document:?}: {message}");
+                }
+                other => panic!("{document:?} was accepted: {other:?}"),
+            }
+        }
+
+        // 引号内的指示符是普通字符串
+        assert_eq!(
+            read_document("a: \"&id\"\n").unwrap(),
+            YamlValue::Map(vec![(
+                "a".to_string(),
+                YamlValue::Scalar("&id".to_string())
+            )])
+        );
+    }
+}
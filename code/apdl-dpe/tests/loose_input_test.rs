@@ -0,0 +1,60 @@
+//! 宽松输入适配测试
+//!
+//! 验证LooseInputAdapter规范化后的文本可由DSL解析器直接解析
+
+use apdl_core::*;
+use apdl_dpe::LooseInputAdapter;
+use apdl_poem::DslParserImpl;
+
+/// 中文输入法下粘贴的DSL：BOM、全角标点、弯引号、大写十六进制前缀和不一致的空白
+const MESSY_DSL: &str = "\u{FEFF}field：sync；type: Uint16 ；length：2byte;scope: layer（link）；  cover: entire_field;constraint: fixed(0XEB90)；desc：“同步字（主）；帧头”\r\n\
+\u{3000}\u{3000}field: vcid;\ttype: Bit(3)；length: 3bit；scope: layer(link)；cover: entire_field；constraint: enum(RT＝0，PB＝0X1)；desc: “虚拟信道”\r\n";
+
+#[test]
+fn test_messy_paste_is_normalized() {
+    let clean = LooseInputAdapter::new().adapt(MESSY_DSL).unwrap();
+
+    assert_eq!(
+        clean,
+        "field:sync; type: Uint16; length:2byte; scope: layer(link); cover: entire_field; \
+         constraint: fixed(0xEB90); desc:\"同步字（主）；帧头\"\n\
+         field: vcid; type: Bit(3); length: 3bit; scope: layer(link); cover: entire_field; \
+         constraint: enum(RT=0,PB=0x1); desc: \"虚拟信道\""
+    );
+}
+
+#[test]
+fn test_normalized_paste_parses() {
+    let clean = LooseInputAdapter::new().adapt(MESSY_DSL).unwrap();
+    let units = DslParserImpl::new()
+        .parse_protocol_structure(&clean)
+        .unwrap();
+
+    assert_eq!(units.len(), 2);
+    assert_eq!(units[0].field_id, "sync");
+    assert_eq!(units[0].unit_type, UnitType::Uint(16));
+    assert_eq!(units[0].constraint, Some(Constraint::FixedValue(0xEB90)));
+    assert_eq!(units[0].desc, "同步字（主）；帧头");
+    assert_eq!(units[1].scope, ScopeDesc::Layer("link".to_string()));
+    assert_eq!(
+        units[1].constraint,
+        Some(Constraint::Enum(vec![
+            ("RT".to_string(), 0),
+            ("PB".to_string(), 1)
+        ]))
+    );
+}
+
+#[test]
+fn test_identifiers_and_clean_input_are_kept() {
+    let adapter = LooseInputAdapter::new();
+
+    // 字段名中的0X不是十六进制前缀
+    assert_eq!(
+        adapter.adapt("field: reg_0XA; desc: \"0X1\"").unwrap(),
+        "field: reg_0XA; desc: \"0X1\""
+    );
+
+    let clean = "field: version; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: \"Version\"";
+    assert_eq!(adapter.adapt(clean).unwrap(), clean);
+}
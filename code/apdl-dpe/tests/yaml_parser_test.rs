@@ -0,0 +1,97 @@
+//! YAML协议定义解析测试
+//!
+//! 验证YamlParser与等价DSL解析得到相同的模型，并对布局和类型错误给出描述性错误
+
+use apdl_core::*;
+use apdl_dpe::parsers::{YamlError, YamlParser};
+use apdl_poem::DslParserImpl;
+use std::fs;
+use std::path::PathBuf;
+
+const NET_DSL: &str = r#"
+field: version; type: Bit(3); length: 3bit; scope: layer(net); cover: entire_field; constraint: fixed(0); desc: "Packet version"
+field: type; type: Bit(1); length: 1bit; scope: layer(net); cover: entire_field; constraint: enum(TM=0,TC=1); desc: "Packet type"
+field: sec_hdr_flag; type: Bit(1); length: 1bit; scope: layer(net); cover: entire_field; desc: "Secondary header flag"
+field: apid; type: Bit(11); length: 11bit; scope: layer(net); cover: entire_field; constraint: range(0..=2047); associate: type, version; desc: "Application process ID"
+field: seq_count; type: Uint16; length: 2byte; scope: layer(net); cover: entire_field; desc: "Sequence flags and count"
+field: length; type: Uint16; length: 2byte; scope: layer(net); cover: entire_field; endian: le; unit: "bytes"; long_desc: "Packet data length minus one"; desc: "Packet data length"
+"#;
+
+const APP_DSL: &str = r#"
+field: time; type: CUC(4,2); length: 6byte; scope: global(mission); cover: entire_field; desc: "Onboard time"
+field: data; type: RawData; length: dynamic; scope: layer(app); cover: entire_field; desc: "User data"
+field: crc; type: Uint16; length: 2byte; scope: layer(app); cover: data[0..4]; alg: crc16; desc: "Packet error control"
+rule: checksum_range(start: time to data);
+rule: alignment(field: data; boundary: 4);
+"#;
+
+fn fixture() -> String {
+    let path =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/ccsds_space_packet.yaml");
+    fs::read_to_string(path).unwrap()
+}
+
+#[test]
+fn test_yaml_matches_equivalent_dsl() {
+    let document = YamlParser::new().parse(&fixture()).unwrap();
+    let dsl = DslParserImpl::new();
+
+    assert_eq!(document.packages.len(), 1);
+    let package = &document.packages[0];
+    assert_eq!(package.name, "space_packet");
+    assert_eq!(package.display_name, "CCSDS Space Packet");
+    assert_eq!(package.package_type, "telemetry");
+    assert_eq!(package.description, "CCSDS 133.0-B 空间包");
+
+    let expected_layers = vec![
+        LayerDefinition {
+            name: "net".to_string(),
+            units: dsl.parse_protocol_structure(NET_DSL).unwrap(),
+            rules: vec![],
+        },
+        LayerDefinition {
+            name: "app".to_string(),
+            units: dsl.parse_protocol_structure(APP_DSL).unwrap(),
+            rules: dsl.parse_semantic_rules(APP_DSL).unwrap(),
+        },
+    ];
+    assert_eq!(package.layers, expected_layers);
+
+    let stack = document.stack.unwrap();
+    assert_eq!(stack.name, "tm_stack");
+    assert_eq!(stack.packages, vec!["tm_frame", "space_packet"]);
+    assert_eq!(stack.connectors, vec!["tm_to_packet"]);
+}
+
+#[test]
+fn test_overlapping_position_is_rejected() {
+    let yaml = fixture().replace("bit_offset: 16", "bit_offset: 12");
+
+    let error = YamlParser::new().parse(&yaml).unwrap_err();
+    assert_eq!(
+        error,
+        YamlError::Invalid {
+            path: "space_packet/net/seq_count".to_string(),
+            message: "bit_offset 12 overlaps 'apid', which ends at bit 16".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_unknown_type_is_rejected() {
+    let yaml = fixture().replace("type: CUC(4,2)", "type: Float32");
+
+    let error = YamlParser::new().parse(&yaml).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid 'space_packet/app/time': Unknown type: Float32"
+    );
+
+    let yaml = fixture().replace("endian: le", "endianness: le");
+    let error = YamlParser::new().parse(&yaml).unwrap_err();
+    assert!(matches!(
+        error,
+        YamlError::Invalid { ref path, ref message }
+            if path == "space_packet/net/length" && message.starts_with("Unknown key 'endianness'")
+    ));
+}
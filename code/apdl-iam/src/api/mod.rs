@@ -1,16 +1,389 @@
 //! API模块
 //!
-//! 提供REST API接口
+//! 提供REST API接口，基于标准库TCP实现的最小HTTP/1.1服务：
+//! - `GET /health`：健康检查
+//! - `POST /parse`：请求体为DSL文本，返回解析得到的`SyntaxUnit`列表（JSON）
+//! - `POST /assemble`：请求体为`{"dsl": "...", "values": {"字段名": "十六进制值"}}`，
+//!   按定义中的字段和语义规则组帧，返回`{"frame": "十六进制帧"}`
+//!
+//! 每个连接设置读写超时，请求行、头部和请求体均有大小上限，同时处理的连接数超过上限时返回503
+
+use apdl_poem::{DslParserImpl, FrameAssembler};
+use serde_json::{json, Value};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// `start`使用的默认监听地址
+pub const DEFAULT_ADDR: &str = "127.0.0.1:8080";
+
+/// 请求体大小上限
+const MAX_BODY_SIZE: usize = 1 << 20;
+
+/// 请求行和单个头部行的长度上限
+const MAX_LINE_LENGTH: usize = 8 * 1024;
 
-#[derive(Default)]
-pub struct RestApiServer;
+/// 头部行数上限
+const MAX_HEADER_COUNT: usize = 64;
+
+/// 响应后丢弃的未读请求数据上限
+const MAX_DRAIN_SIZE: usize = 64 * 1024;
+
+/// 响应后丢弃未读请求数据的总时长上限
+const LINGER_TIME: Duration = Duration::from_millis(100);
+
+/// 默认读写超时
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 默认同时处理的连接数上限
+pub const DEFAULT_MAX_CONNECTIONS: usize = 64;
+
+pub struct RestApiServer {
+    timeout: Duration,
+    max_connections: usize,
+}
+
+impl Default for RestApiServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl RestApiServer {
     pub fn new() -> Self {
-        Self
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+        }
+    }
+
+    /// 设置连接的读写超时，超时未收到完整请求时返回408
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// 设置同时处理的连接数上限
+    pub fn with_max_connections(mut self, max_connections: usize) -> Self {
+        self.max_connections = max_connections;
+        self
+    }
+
+    /// 在默认地址上启动服务并阻塞直到服务结束
+    pub fn start(&self) -> io::Result<()> {
+        let handle = self.run(DEFAULT_ADDR)?;
+        println!("REST API server listening on {}", handle.local_addr());
+        handle
+            .join()
+            .map_err(|_| io::Error::other("REST API server thread panicked"))
+    }
+
+    /// 在指定地址上启动服务，返回可等待或关闭的服务句柄
+    ///
+    /// 地址端口为0时由系统分配，实际地址见[`ServerHandle::local_addr`]
+    pub fn run(&self, addr: impl ToSocketAddrs) -> io::Result<ServerHandle> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let stop = Arc::clone(&shutdown);
+        let active = Arc::new(AtomicUsize::new(0));
+        let (timeout, max_connections) = (self.timeout, self.max_connections);
+
+        let thread = thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        eprintln!("REST API accept error: {e}");
+                        continue;
+                    }
+                };
+                let result = stream
+                    .set_read_timeout(Some(timeout))
+                    .and_then(|_| stream.set_write_timeout(Some(timeout)));
+                if let Err(e) = result {
+                    eprintln!("REST API connection error: {e}");
+                    continue;
+                }
+                let Some(guard) = ConnectionGuard::acquire(&active, max_connections) else {
+                    // 在独立线程中回复503，客户端再慢也不会阻塞accept
+                    thread::spawn(move || {
+                        let busy = json!({ "error": "Too many connections" });
+                        if let Err(e) = write_response(stream, 503, &busy) {
+                            eprintln!("REST API connection error: {e}");
+                        }
+                    });
+                    continue;
+                };
+                thread::spawn(move || {
+                    let _guard = guard;
+                    if let Err(e) = handle_connection(stream) {
+                        eprintln!("REST API connection error: {e}");
+                    }
+                });
+            }
+        });
+
+        Ok(ServerHandle {
+            local_addr,
+            shutdown,
+            thread,
+        })
+    }
+}
+
+/// 运行中的服务句柄
+pub struct ServerHandle {
+    local_addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+impl ServerHandle {
+    /// 服务实际监听的地址
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// 等待服务线程结束
+    pub fn join(self) -> thread::Result<()> {
+        self.thread.join()
+    }
+
+    /// 停止接受新连接并等待服务线程结束
+    pub fn shutdown(self) -> thread::Result<()> {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // 建立一个连接以唤醒阻塞在accept上的线程
+        let _ = TcpStream::connect(self.local_addr);
+        self.join()
+    }
+}
+
+/// 占用一个连接名额，释放时归还
+struct ConnectionGuard(Arc<AtomicUsize>);
+
+impl ConnectionGuard {
+    /// 活动连接数未达上限时占用一个名额
+    fn acquire(active: &Arc<AtomicUsize>, max_connections: usize) -> Option<Self> {
+        active
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+                (count < max_connections).then_some(count + 1)
+            })
+            .ok()
+            .map(|_| Self(Arc::clone(active)))
     }
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// 解析后的HTTP请求
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+/// 读取一个请求并写回响应，响应后关闭连接
+fn handle_connection(stream: TcpStream) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let (status, body) = match read_request(&mut reader) {
+        Ok(request) => route(&request),
+        Err(e)
+            if matches!(
+                e.kind(),
+                io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+            ) =>
+        {
+            (408, json!({ "error": "Request timed out" }))
+        }
+        Err(e) => (400, json!({ "error": e.to_string() })),
+    };
+    write_response(stream, status, &body)
+}
+
+fn read_request(reader: &mut impl BufRead) -> io::Result<Request> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+
+    let request_line = read_line(reader)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(invalid("Malformed request line"));
+    };
+    let path = target.split('?').next().unwrap_or(target).to_string();
+    let method = method.to_string();
+
+    let mut content_length = 0;
+    let mut header_count = 0;
+    loop {
+        let line = read_line(reader)?;
+        if line.is_empty() {
+            return Err(invalid("Unexpected end of headers"));
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        header_count += 1;
+        if header_count > MAX_HEADER_COUNT {
+            return Err(invalid("Too many headers"));
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| invalid("Invalid Content-Length"))?;
+            }
+        }
+    }
+    if content_length > MAX_BODY_SIZE {
+        return Err(invalid("Request body too large"));
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Request { method, path, body })
+}
+
+/// 读取一行（含行尾），超过长度上限时报错
+fn read_line(reader: &mut impl BufRead) -> io::Result<String> {
+    let mut line = String::new();
+    reader
+        .take(MAX_LINE_LENGTH as u64 + 1)
+        .read_line(&mut line)?;
+    if line.len() > MAX_LINE_LENGTH {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Request line or header too long",
+        ));
+    }
+    Ok(line)
+}
+
+/// 按方法和路径分发请求，返回状态码和JSON响应体
+fn route(request: &Request) -> (u16, Value) {
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/health") => (200, json!({ "status": "ok" })),
+        ("POST", "/parse") => parse_dsl(&request.body),
+        ("POST", "/assemble") => assemble_frame(&request.body),
+        (_, "/health" | "/parse" | "/assemble") => (405, json!({ "error": "Method not allowed" })),
+        _ => (404, json!({ "error": "Not found" })),
+    }
+}
+
+fn parse_dsl(body: &[u8]) -> (u16, Value) {
+    let Ok(dsl) = std::str::from_utf8(body) else {
+        return (
+            400,
+            json!({ "error": "Request body must be UTF-8 DSL text" }),
+        );
+    };
+    match DslParserImpl::new().parse_protocol_structure(dsl) {
+        Ok(units) => match serde_json::to_value(units) {
+            Ok(value) => (200, value),
+            Err(e) => (500, json!({ "error": e.to_string() })),
+        },
+        Err(e) => (400, json!({ "error": e.to_string(), "details": e })),
+    }
+}
+
+fn assemble_frame(body: &[u8]) -> (u16, Value) {
+    match try_assemble_frame(body) {
+        Ok(frame) => (200, json!({ "frame": hex::encode(frame) })),
+        Err(message) => (400, json!({ "error": message })),
+    }
+}
+
+/// 解析组帧请求、设置字段值并组帧，错误信息直接作为响应返回
+fn try_assemble_frame(body: &[u8]) -> Result<Vec<u8>, String> {
+    let request: Value =
+        serde_json::from_slice(body).map_err(|e| format!("Invalid JSON request: {e}"))?;
+    let dsl = request["dsl"]
+        .as_str()
+        .ok_or("Request is missing the 'dsl' string")?;
+    let values = match &request["values"] {
+        Value::Null => serde_json::Map::new(),
+        Value::Object(values) => values.clone(),
+        _ => return Err("'values' must be an object of hex strings".to_string()),
+    };
+
+    let parser = DslParserImpl::new();
+    let mut assembler = FrameAssembler::new();
+    for unit in parser
+        .parse_protocol_structure(dsl)
+        .map_err(|e| e.to_string())?
+    {
+        assembler.add_field(unit);
+    }
+    for rule in parser
+        .parse_semantic_rules(dsl)
+        .map_err(|e| e.to_string())?
+    {
+        assembler.add_semantic_rule(rule);
+    }
+
+    for (field, value) in &values {
+        let hex_value = value
+            .as_str()
+            .ok_or_else(|| format!("Value of field '{field}' must be a hex string"))?;
+        let hex_value = hex_value.trim_start_matches("0x").trim_start_matches("0X");
+        let bytes = hex::decode(hex_value)
+            .map_err(|e| format!("Invalid hex value for field '{field}': {e}"))?;
+        assembler
+            .set_field_value(field, &bytes)
+            .map_err(|e| e.to_string())?;
+    }
+    assembler.assemble_frame().map_err(|e| e.to_string())
+}
+
+fn write_response(mut stream: TcpStream, status: u16, body: &Value) -> io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    };
+    let body = body.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()?;
+
+    // 关闭前丢弃未读取的请求数据，避免连接被重置导致客户端收不到响应
+    stream.shutdown(Shutdown::Write)?;
+    drain(&mut stream);
+    Ok(())
+}
 
-    pub fn start(&self) {
-        println!("Starting REST API server...");
+/// 在`LINGER_TIME`内丢弃至多`MAX_DRAIN_SIZE`字节的未读数据
+fn drain(stream: &mut TcpStream) {
+    let deadline = Instant::now() + LINGER_TIME;
+    let mut buffer = [0u8; 4096];
+    let mut drained = 0;
+    while drained < MAX_DRAIN_SIZE {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() || stream.set_read_timeout(Some(remaining)).is_err() {
+            break;
+        }
+        match stream.read(&mut buffer) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => drained += n,
+        }
     }
 }
@@ -0,0 +1,173 @@
+//! disassemble命令
+//!
+//! 按DSL协议定义拆解十六进制帧，输出逐字段的偏移、取值和约束检查结果表
+
+use apdl_core::utils::{bytes_to_hex, hex_to_bytes, normalize_hex};
+use apdl_core::ParsedField;
+use apdl_lsk::FrameDisassembler;
+use std::fmt::Write;
+
+use super::explain::load_disassembler;
+
+/// 表头
+const HEADERS: [&str; 6] = ["Field", "Bytes", "Bits", "Raw", "Value", "Constraint"];
+
+/// 读取协议定义文件和十六进制帧数据，返回字段表
+pub fn run_disassemble(protocol_file: &str, hex: &str) -> Result<String, String> {
+    let definition = std::fs::read_to_string(protocol_file)
+        .map_err(|e| format!("Failed to read definition {protocol_file}: {e}"))?;
+    let disassembler = load_disassembler(&definition)?;
+
+    let hex = normalize_hex(hex).map_err(|e| format!("Invalid hex frame: {e}"))?;
+    let frame = hex_to_bytes(&hex).map_err(|e| format!("Invalid hex frame: {e}"))?;
+
+    disassemble_table(&disassembler, &frame)
+}
+
+/// 拆解帧数据并生成字段表，末尾汇总约束违反数
+///
+/// 约束违反不中断拆解，在对应字段的Constraint列中标记为FAIL
+pub fn disassemble_table(disassembler: &FrameDisassembler, frame: &[u8]) -> Result<String, String> {
+    let (fields, errors) = disassembler
+        .disassemble_frame_validated(frame)
+        .map_err(|e| e.to_string())?;
+
+    let rows: Vec<[String; 6]> = fields
+        .iter()
+        .map(|field| {
+            let check = match errors.iter().find(|error| error.field_name == field.name) {
+                Some(error) => format!("FAIL ({})", error.error_message),
+                None if field.constraint.is_some() => "ok".to_string(),
+                None => "-".to_string(),
+            };
+            table_row(field, check)
+        })
+        .collect();
+
+    let mut widths = HEADERS.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut out = String::new();
+    let _ = writeln!(out, "Frame: {} bytes", frame.len());
+    write_row(&mut out, &HEADERS.map(str::to_string), &widths);
+    for row in &rows {
+        write_row(&mut out, row, &widths);
+    }
+    let _ = writeln!(out, "Constraint violations: {}", errors.len());
+    Ok(out)
+}
+
+/// 单个字段的表格行
+fn table_row(field: &ParsedField, check: String) -> [String; 6] {
+    let bit_end = field.bit_offset + field.bit_len;
+    let value = if !field.decoded.is_empty() {
+        field.decoded.clone()
+    } else {
+        match field.numeric_value() {
+            Some(value) => value.to_string(),
+            None => format!("{} bytes", field.value.len()),
+        }
+    };
+    [
+        field.name.clone(),
+        format!("{}..{}", field.bit_offset / 8, bit_end.div_ceil(8)),
+        format!("{}..{bit_end}", field.bit_offset),
+        bytes_to_hex(&field.raw),
+        value,
+        check,
+    ]
+}
+
+fn write_row(out: &mut String, cells: &[String; 6], widths: &[usize; 6]) {
+    let line = cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, &width)| format!("{cell:<width$}"))
+        .collect::<Vec<_>>()
+        .join("  ");
+    let _ = writeln!(out, "{}", line.trim_end());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME_DSL: &str = r#"
+field: sync; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; constraint: fixed(0xEB90); desc: "Sync"
+field: version; type: Bit(3); length: 3bit; scope: layer(link); cover: entire_field; constraint: range(0..=1); desc: "Version"
+field: apid; type: Bit(5); length: 5bit; scope: layer(link); cover: entire_field; desc: "APID"
+field: data; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Data"
+"#;
+
+    fn table(frame: &[u8]) -> String {
+        let disassembler = load_disassembler(FRAME_DSL).unwrap();
+        disassemble_table(&disassembler, frame).unwrap()
+    }
+
+    fn row<'a>(output: &'a str, field: &str) -> Vec<&'a str> {
+        output
+            .lines()
+            .find(|line| line.starts_with(field))
+            .unwrap_or_else(|| panic!("no row for {field} in:\n{output}"))
+            .split("  ")
+            .map(str::trim)
+            .filter(|cell| !cell.is_empty())
+            .collect()
+    }
+
+    #[test]
+    fn test_table_lists_fields_with_offsets_and_values() {
+        let output = table(&[0xEB, 0x90, 0x25, 0x12, 0x34]);
+
+        assert!(output.starts_with("Frame: 5 bytes\nField"));
+        assert_eq!(
+            row(&output, "sync"),
+            ["sync", "0..2", "0..16", "EB 90", "60304", "ok"]
+        );
+        assert_eq!(
+            row(&output, "version"),
+            ["version", "2..3", "16..19", "25", "1", "ok"]
+        );
+        assert_eq!(
+            row(&output, "apid"),
+            ["apid", "2..3", "19..24", "25", "5", "-"]
+        );
+        assert_eq!(
+            row(&output, "data"),
+            ["data", "3..5", "24..40", "12 34", "4660", "-"]
+        );
+        assert!(output.ends_with("Constraint violations: 0\n"));
+    }
+
+    #[test]
+    fn test_table_marks_fixed_value_mismatch() {
+        let output = table(&[0xEB, 0x91, 0x25, 0x12, 0x34]);
+
+        let sync = row(&output, "sync");
+        assert!(sync[5].starts_with("FAIL ("), "{output}");
+        assert!(sync[5].contains("actual 60305 (0xEB91)"), "{output}");
+        assert_eq!(row(&output, "version")[5], "ok");
+        assert!(output.ends_with("Constraint violations: 1\n"));
+    }
+
+    #[test]
+    fn test_run_disassemble_accepts_spaced_hex() {
+        let path =
+            std::env::temp_dir().join(format!("apdl_disassemble_{}.apdl", std::process::id()));
+        std::fs::write(&path, FRAME_DSL).unwrap();
+        let spaced = run_disassemble(path.to_str().unwrap(), "EB 90 25 12 34");
+        let compact = run_disassemble(path.to_str().unwrap(), "0xEB90251234");
+        let multiline = run_disassemble(path.to_str().unwrap(), "EB90\n25\t1234\n");
+        let odd = run_disassemble(path.to_str().unwrap(), "EB9");
+        std::fs::remove_file(&path).unwrap();
+
+        let compact = compact.unwrap();
+        assert_eq!(spaced.unwrap(), compact);
+        assert_eq!(multiline.unwrap(), compact);
+        assert!(odd.unwrap_err().contains("odd number of digits"));
+    }
+}
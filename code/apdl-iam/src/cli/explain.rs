@@ -0,0 +1,252 @@
+//! explain命令
+//!
+//! 逐步输出帧的解析过程：每个字段的字节/bit范围、原始字节、解码值以及约束和校验和检查结果
+
+use apdl_core::utils::{bytes_to_hex, hex_to_bytes, normalize_hex};
+use apdl_core::{ChecksumAlgorithm, ParsedField, SemanticRule, SyntaxUnit};
+use apdl_lsk::{FieldValidator, FrameDisassembler};
+use apdl_poem::{DslParserImpl, FrameAssembler};
+use std::fmt::Write;
+
+/// 读取协议定义文件和十六进制帧数据，返回解析过程追踪
+pub fn run_explain(def_path: &str, hex: &str) -> Result<String, String> {
+    let definition = std::fs::read_to_string(def_path)
+        .map_err(|e| format!("Failed to read definition {def_path}: {e}"))?;
+    let disassembler = load_disassembler(&definition)?;
+
+    let hex = normalize_hex(hex).map_err(|e| format!("Invalid hex frame: {e}"))?;
+    let frame = hex_to_bytes(&hex).map_err(|e| format!("Invalid hex frame: {e}"))?;
+
+    explain_frame(&disassembler, &frame)
+}
+
+/// 由DSL协议定义创建拆包器
+pub fn load_disassembler(definition: &str) -> Result<FrameDisassembler, String> {
+    let parser = DslParserImpl::new();
+    let units = parser
+        .parse_protocol_structure(definition)
+        .map_err(|e| e.to_string())?;
+    let rules = parser
+        .parse_semantic_rules(definition)
+        .map_err(|e| e.to_string())?;
+    Ok(build_disassembler(&units, &rules))
+}
+
+/// 由字段定义和语义规则创建拆包器
+pub fn build_disassembler(units: &[SyntaxUnit], rules: &[SemanticRule]) -> FrameDisassembler {
+    let mut disassembler = FrameDisassembler::new();
+    for unit in units {
+        disassembler.add_field(unit.clone());
+    }
+    for rule in rules {
+        disassembler.add_semantic_rule(rule.clone());
+    }
+    disassembler
+}
+
+/// 解析帧数据并生成逐字段的解析追踪
+pub fn explain_frame(disassembler: &FrameDisassembler, frame: &[u8]) -> Result<String, String> {
+    let fields = disassembler
+        .parse_frame_named(frame)
+        .map_err(|e| e.to_string())?;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "Frame: {} bytes [{}]",
+        frame.len(),
+        bytes_to_hex(frame)
+    );
+    for (step, field) in fields.iter().enumerate() {
+        explain_field(&mut out, step + 1, field);
+    }
+    let assembler = checksum_assembler(disassembler, &fields);
+    for rule in &disassembler.semantic_rules {
+        if let SemanticRule::ChecksumRange {
+            algorithm,
+            start_field,
+            end_field,
+            covers_self,
+        } = rule
+        {
+            explain_checksum(
+                &mut out,
+                &assembler,
+                frame,
+                algorithm,
+                start_field,
+                end_field,
+                *covers_self,
+            );
+        }
+    }
+    Ok(out)
+}
+
+/// 输出单个字段的解析步骤
+fn explain_field(out: &mut String, step: usize, field: &ParsedField) {
+    let bit_end = field.bit_offset + field.bit_len;
+    let byte_start = field.bit_offset / 8;
+    let byte_end = bit_end.div_ceil(8);
+    let decoded = match field.numeric_value() {
+        Some(value) => format!("{value} (0x{value:X})"),
+        None => format!("{} bytes", field.value.len()),
+    };
+
+    let _ = writeln!(
+        out,
+        "{step}. {}: bytes {byte_start}..{byte_end}, bits {}..{bit_end}, raw [{}], value {decoded}",
+        field.name,
+        field.bit_offset,
+        bytes_to_hex(&field.raw)
+    );
+
+    if let Some(constraint) = &field.constraint {
+        let outcome = match FieldValidator::validate(&field.name, &field.value, constraint) {
+            Ok(()) => "ok".to_string(),
+            Err(e) => format!("FAILED ({e})"),
+        };
+        let _ = writeln!(out, "   constraint {constraint:?}: {outcome}");
+    }
+}
+
+/// 按解析结果构建与组装时相同的校验和上下文
+///
+/// 动态长度字段取解析得到的值，未出现在解析结果中的字段视为省略，使字段偏移与帧一致
+fn checksum_assembler(disassembler: &FrameDisassembler, fields: &[ParsedField]) -> FrameAssembler {
+    let mut assembler = FrameAssembler::new();
+    for unit in &disassembler.fields {
+        assembler.add_field(unit.clone());
+        match fields.iter().find(|field| field.name == unit.field_id) {
+            Some(field) => {
+                assembler
+                    .field_values
+                    .insert(field.name.clone(), field.value.clone());
+            }
+            None => {
+                assembler.skipped_fields.insert(unit.field_id.clone());
+            }
+        }
+    }
+    for rule in &disassembler.semantic_rules {
+        assembler.add_semantic_rule(rule.clone());
+    }
+    assembler
+}
+
+/// 输出校验和范围规则的检查结果
+///
+/// 校验字段的确定、参与计算的字节和校验算法均与组装器一致
+fn explain_checksum(
+    out: &mut String,
+    assembler: &FrameAssembler,
+    frame: &[u8],
+    algorithm: &ChecksumAlgorithm,
+    start_field: &str,
+    end_field: &str,
+    covers_self: bool,
+) {
+    let start_field = start_field.trim_start_matches("start: ").trim();
+    let end_field = end_field.trim_start_matches("end: ").trim();
+    let label = format!("checksum {algorithm:?} {start_field}..{end_field}");
+
+    let target = match assembler.resolve_checksum_field(algorithm, end_field) {
+        Ok(Some(index)) => index,
+        Ok(None) => {
+            let _ = writeln!(out, "{label}: skipped (no checksum field)");
+            return;
+        }
+        Err(e) => {
+            let _ = writeln!(out, "{label}: skipped ({e})");
+            return;
+        }
+    };
+    let target_name = &assembler.fields[target].field_id;
+
+    let checked = assembler
+        .checksum_input(frame, start_field, end_field, Some(target), covers_self)
+        .and_then(|data| {
+            let calculated = assembler.compute_field_checksum(algorithm, Some(target), &data);
+            let expected = assembler.read_checksum_from_field(frame, target)?;
+            Ok((expected, calculated))
+        });
+    let outcome = match checked {
+        Ok((expected, calculated)) if expected == calculated => format!("ok (0x{calculated:X})"),
+        Ok((expected, calculated)) => {
+            format!("FAILED (expected 0x{expected:X}, calculated 0x{calculated:X})")
+        }
+        Err(e) => format!("not checked ({e})"),
+    };
+    let _ = writeln!(out, "{label} -> {target_name}: {outcome}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apdl_core::utils::calculate_ccsds_crc;
+
+    const FRAME_DSL: &str = r#"
+field: version; type: Bit(3); length: 3bit; scope: layer(link); cover: entire_field; constraint: fixed(1); desc: "Version"
+field: apid; type: Bit(5); length: 5bit; scope: layer(link); cover: entire_field; desc: "APID"
+field: data; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Data"
+field: fecf; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; alg: crc16; desc: "Frame check"
+rule: crc_range(start: version to data);
+"#;
+
+    fn build_frame() -> Vec<u8> {
+        let mut frame = vec![0x25, 0x12, 0x34];
+        let crc = calculate_ccsds_crc(&frame);
+        frame.extend_from_slice(&crc.to_be_bytes());
+        frame
+    }
+
+    #[test]
+    fn test_explain_mentions_each_field_and_offset() {
+        let disassembler = load_disassembler(FRAME_DSL).unwrap();
+        let output = explain_frame(&disassembler, &build_frame()).unwrap();
+
+        assert!(output.contains("1. version: bytes 0..1, bits 0..3"));
+        assert!(output.contains("2. apid: bytes 0..1, bits 3..8"));
+        assert!(
+            output.contains("3. data: bytes 1..3, bits 8..24, raw [12 34], value 4660 (0x1234)")
+        );
+        assert!(output.contains("4. fecf: bytes 3..5, bits 24..40"));
+        assert!(output.contains("constraint FixedValue(1): ok"));
+        assert!(output.contains("checksum CRC16 version..data -> fecf: ok"));
+    }
+
+    #[test]
+    fn test_explain_reports_failed_checks() {
+        let disassembler = load_disassembler(FRAME_DSL).unwrap();
+        let mut frame = build_frame();
+        frame[0] = 0x45;
+
+        let output = explain_frame(&disassembler, &frame).unwrap();
+        assert!(output.contains("constraint FixedValue(1): FAILED"));
+        assert!(output.contains("checksum CRC16 version..data -> fecf: FAILED"));
+    }
+
+    #[test]
+    fn test_explain_checks_checksum_like_the_assembler() {
+        // CRC32校验字段不紧随范围之后，由alg确定；期望值由组装器生成
+        let dsl = r#"
+field: data; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Data"
+field: flags; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "Flags"
+field: fecf; type: Uint32; length: 4byte; scope: layer(link); cover: entire_field; alg: crc32; desc: "Frame check"
+"#;
+        let mut disassembler = load_disassembler(dsl).unwrap();
+        disassembler.add_semantic_rule(SemanticRule::ChecksumRange {
+            algorithm: ChecksumAlgorithm::CRC32,
+            start_field: "data".to_string(),
+            end_field: "data".to_string(),
+            covers_self: true,
+        });
+        let mut assembler = checksum_assembler(&disassembler, &[]);
+        assembler.skipped_fields.clear();
+        assembler.set_field_value("data", &[0x12, 0x34]).unwrap();
+        let frame = assembler.assemble_frame().unwrap();
+
+        let output = explain_frame(&disassembler, &frame).unwrap();
+        assert!(output.contains("checksum CRC32 data..data -> fecf: ok"));
+    }
+}
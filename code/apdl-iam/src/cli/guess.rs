@@ -0,0 +1,161 @@
+//! guess命令
+//!
+//! 根据十六进制抓包数据生成协议定义骨架：按字节划分字段，可选检测帧尾CRC16
+
+use apdl_core::utils::{calculate_crc16_with, hex_to_bytes, normalize_hex, Crc16Params};
+use std::fmt::Write;
+
+/// 解析十六进制抓包数据并输出DSL协议定义骨架
+pub fn run_guess(hex: &str, frame_len: usize, detect_crc: bool) -> Result<String, String> {
+    let hex = normalize_hex(hex).map_err(|e| format!("Invalid hex capture: {e}"))?;
+    let capture = hex_to_bytes(&hex).map_err(|e| format!("Invalid hex capture: {e}"))?;
+
+    guess_definition(&capture, frame_len, detect_crc)
+}
+
+/// 将抓包数据按帧长切分并生成协议定义骨架
+///
+/// 字段命名为`f0..fn`，均为1字节；`detect_crc`为真且每帧最后2字节都是前面字节的CRC16
+/// （CCSDS或ARC参数）时，末尾2字节生成为带对应`alg`的`fecf`字段并附加`crc_range`规则
+pub fn guess_definition(
+    capture: &[u8],
+    frame_len: usize,
+    detect_crc: bool,
+) -> Result<String, String> {
+    if frame_len == 0 {
+        return Err("Frame length must be greater than 0".to_string());
+    }
+    if capture.is_empty() || !capture.len().is_multiple_of(frame_len) {
+        return Err(format!(
+            "Capture length {} is not a multiple of frame length {frame_len}",
+            capture.len()
+        ));
+    }
+
+    let frames: Vec<&[u8]> = capture.chunks(frame_len).collect();
+    let crc_params = if detect_crc {
+        trailing_crc16(&frames)
+    } else {
+        None
+    };
+    let has_crc = crc_params.is_some();
+    let byte_fields = if has_crc { frame_len - 2 } else { frame_len };
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "// Skeleton guessed from {} frame(s) of {frame_len} bytes",
+        frames.len()
+    );
+    for index in 0..byte_fields {
+        let _ = writeln!(
+            out,
+            "field: f{index}; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: \"Byte {index}\""
+        );
+    }
+    if let Some(params) = crc_params {
+        let _ = writeln!(
+            out,
+            "field: fecf; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; alg: {}; desc: \"Frame check (CRC16)\"",
+            crc16_alg(params)
+        );
+        let _ = writeln!(out, "rule: crc_range(start: f0 to f{});", byte_fields - 1);
+    } else if detect_crc {
+        let _ = writeln!(out, "// No trailing CRC16 detected");
+    }
+    Ok(out)
+}
+
+/// 查找使每帧最后2字节均为前面字节的CRC16（大端）的参数
+fn trailing_crc16(frames: &[&[u8]]) -> Option<Crc16Params> {
+    [Crc16Params::CCSDS, Crc16Params::ARC]
+        .into_iter()
+        .find(|&params| {
+            frames.iter().all(|frame| {
+                let Some(split) = frame.len().checked_sub(2).filter(|&split| split > 0) else {
+                    return false;
+                };
+                let (data, crc) = frame.split_at(split);
+                calculate_crc16_with(params, data) == u16::from_be_bytes([crc[0], crc[1]])
+            })
+        })
+}
+
+/// CRC16参数对应的DSL算法声明
+fn crc16_alg(params: Crc16Params) -> String {
+    if params == Crc16Params::CCSDS {
+        return "crc16".to_string();
+    }
+    format!(
+        "crc16(poly=0x{:04X},init=0x{:04X},refin={},refout={},xorout=0x{:04X})",
+        params.poly, params.init, params.refin, params.refout, params.xorout
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::explain::{explain_frame, load_disassembler};
+
+    fn frame_with_crc(data: &[u8]) -> Vec<u8> {
+        frame_with_crc_params(Crc16Params::CCSDS, data)
+    }
+
+    fn frame_with_crc_params(params: Crc16Params, data: &[u8]) -> Vec<u8> {
+        let mut frame = data.to_vec();
+        frame.extend_from_slice(&calculate_crc16_with(params, data).to_be_bytes());
+        frame
+    }
+
+    #[test]
+    fn test_guess_detects_trailing_crc16() {
+        let mut capture = frame_with_crc(&[0x1A, 0xCF, 0x01, 0x02]);
+        capture.extend(frame_with_crc(&[0x1A, 0xCF, 0x03, 0x04]));
+        let hex: String = capture.iter().map(|byte| format!("{byte:02X}")).collect();
+
+        let definition = run_guess(&hex, 6, true).unwrap();
+        assert!(definition.contains("field: f3; type: Uint8"));
+        assert!(!definition.contains("field: f4;"));
+        assert!(definition.contains("field: fecf; type: Uint16; length: 2byte"));
+        assert!(definition.contains("rule: crc_range(start: f0 to f3);"));
+
+        // 生成的骨架可直接用于解析并通过CRC检查
+        let disassembler = load_disassembler(&definition).unwrap();
+        let trace = explain_frame(&disassembler, &capture[..6]).unwrap();
+        assert!(trace.contains("checksum CRC16 f0..f3 -> fecf: ok"));
+    }
+
+    #[test]
+    fn test_guess_detects_arc_crc16() {
+        let mut capture = frame_with_crc_params(Crc16Params::ARC, &[0x1A, 0xCF, 0x01, 0x02]);
+        capture.extend(frame_with_crc_params(
+            Crc16Params::ARC,
+            &[0x1A, 0xCF, 0x03, 0x04],
+        ));
+
+        let definition = guess_definition(&capture, 6, true).unwrap();
+        assert!(definition
+            .contains("alg: crc16(poly=0x8005,init=0x0000,refin=true,refout=true,xorout=0x0000)"));
+
+        // 生成的算法声明按同样的参数校验
+        let disassembler = load_disassembler(&definition).unwrap();
+        let trace = explain_frame(&disassembler, &capture[6..]).unwrap();
+        assert!(trace.contains("checksum CRC16 f0..f3 -> fecf: ok"));
+    }
+
+    #[test]
+    fn test_guess_without_valid_crc() {
+        let mut capture = frame_with_crc(&[0x1A, 0xCF, 0x01, 0x02]);
+        capture[5] ^= 0xFF;
+
+        let definition = guess_definition(&capture, 6, true).unwrap();
+        assert!(definition.contains("field: f5; type: Uint8"));
+        assert!(!definition.contains("fecf"));
+        assert!(definition.contains("No trailing CRC16 detected"));
+
+        let definition = guess_definition(&capture, 6, false).unwrap();
+        assert!(!definition.contains("crc"));
+
+        assert!(guess_definition(&capture, 4, false).is_err());
+    }
+}
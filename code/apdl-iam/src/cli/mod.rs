@@ -2,6 +2,11 @@
 //!
 //! 提供命令行交互功能
 
+pub mod disassemble;
+pub mod explain;
+pub mod guess;
+pub mod validate;
+
 pub struct CommandLineInterface;
 
 impl CommandLineInterface {
@@ -0,0 +1,161 @@
+//! validate命令
+//!
+//! 加载协议定义文件（`.json`按JSON解析，其余按DSL解析），检查各层字段布局并输出摘要
+
+use apdl_core::{LayerDefinition, PackageDefinition};
+use apdl_poem::dsl::json_parser::JsonParser;
+use apdl_poem::DslParserImpl;
+use apdl_pvpae::{LayoutIssue, ProtocolVerifier};
+use std::fmt::Write;
+use std::path::Path;
+
+/// 协议定义文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefinitionFormat {
+    Dsl,
+    Json,
+}
+
+impl DefinitionFormat {
+    /// 按文件扩展名判断格式
+    pub fn from_path(path: &str) -> Self {
+        let is_json = Path::new(path)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        if is_json {
+            DefinitionFormat::Json
+        } else {
+            DefinitionFormat::Dsl
+        }
+    }
+}
+
+/// 布局检查结果
+#[derive(Debug, Clone)]
+pub struct ValidationReport {
+    pub format: DefinitionFormat,
+    /// 各层名称及其字段数
+    pub layers: Vec<(String, usize)>,
+    /// 各层名称及其布局问题
+    pub issues: Vec<(String, LayoutIssue)>,
+}
+
+impl ValidationReport {
+    /// 字段总数
+    pub fn field_count(&self) -> usize {
+        self.layers.iter().map(|(_, count)| count).sum()
+    }
+
+    /// 非警告问题数
+    pub fn error_count(&self) -> usize {
+        self.issues
+            .iter()
+            .filter(|(_, issue)| !issue.is_warning())
+            .count()
+    }
+
+    /// 没有非警告问题时检查通过
+    pub fn passed(&self) -> bool {
+        self.error_count() == 0
+    }
+
+    /// 输出检查摘要
+    pub fn summary(&self, path: &str) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "Protocol file: {path} ({:?})", self.format);
+        let _ = writeln!(out, "Layers: {}", self.layers.len());
+        let _ = writeln!(out, "Fields: {}", self.field_count());
+        let errors = self.error_count();
+        let _ = writeln!(
+            out,
+            "Issues: {} ({errors} error(s), {} warning(s))",
+            self.issues.len(),
+            self.issues.len() - errors
+        );
+        for (layer, issue) in &self.issues {
+            let level = if issue.is_warning() {
+                "warning"
+            } else {
+                "error"
+            };
+            let _ = writeln!(out, "  [{level}] {layer}: {issue}");
+        }
+        let verdict = if self.passed() {
+            "Verification passed"
+        } else {
+            "Verification failed"
+        };
+        let _ = writeln!(out, "{verdict}");
+        out
+    }
+}
+
+/// 读取协议定义文件并检查字段布局
+pub fn run_validate(path: &str) -> Result<ValidationReport, String> {
+    let (format, layers) = load_layers(path)?;
+
+    let verifier = ProtocolVerifier::new();
+    let mut report = ValidationReport {
+        format,
+        layers: Vec::new(),
+        issues: Vec::new(),
+    };
+    for layer in &layers {
+        report.layers.push((layer.name.clone(), layer.units.len()));
+        report.issues.extend(
+            verifier
+                .check_layout(&layer.units)
+                .into_iter()
+                .map(|issue| (layer.name.clone(), issue)),
+        );
+    }
+    Ok(report)
+}
+
+/// 读取协议定义文件，按扩展名解析为各层定义（从外到内）
+pub fn load_layers(path: &str) -> Result<(DefinitionFormat, Vec<LayerDefinition>), String> {
+    let definition =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to read {path}: {e}"))?;
+    let format = DefinitionFormat::from_path(path);
+    let layers = match format {
+        DefinitionFormat::Dsl => vec![load_dsl(&definition)?],
+        DefinitionFormat::Json => load_json(&definition)?,
+    };
+    Ok((format, layers))
+}
+
+/// DSL定义作为单个层
+fn load_dsl(definition: &str) -> Result<LayerDefinition, String> {
+    let parser = DslParserImpl::new();
+    Ok(LayerDefinition {
+        name: "main".to_string(),
+        units: parser
+            .parse_protocol_structure(definition)
+            .map_err(|e| e.to_string())?,
+        rules: parser
+            .parse_semantic_rules(definition)
+            .map_err(|e| e.to_string())?,
+    })
+}
+
+/// JSON定义可以是APDL协议格式（含`protocol_meta`）或包定义（单个或数组），返回所有包的各层
+fn load_json(definition: &str) -> Result<Vec<LayerDefinition>, String> {
+    let json = JsonParser::validate_json(definition)?;
+    let packages: Vec<PackageDefinition> = if json.get("protocol_meta").is_some() {
+        vec![JsonParser::parse_apdl_protocol_json(definition)?]
+    } else if json.is_array() {
+        serde_json::from_value(json).map_err(|e| format!("Invalid package definitions: {e}"))?
+    } else {
+        vec![serde_json::from_value(json).map_err(|e| format!("Invalid package definition: {e}"))?]
+    };
+    Ok(packages
+        .into_iter()
+        .flat_map(|package| {
+            let package_name = package.name;
+            package.layers.into_iter().map(move |mut layer| {
+                layer.name = format!("{package_name}.{}", layer.name);
+                layer
+            })
+        })
+        .collect())
+}
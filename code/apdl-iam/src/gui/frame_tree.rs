@@ -0,0 +1,250 @@
+//! 解析树构建
+//!
+//! 由已加载的协议定义对十六进制帧做分层拆包，并将`LayeredDisassembler`的输出整理为
+//! 层 → 字段 → 值的树，供GUI渲染；本模块不依赖egui，可在后台线程中运行
+
+use apdl_core::utils::{bytes_to_hex, decode_uint, hex_to_bytes, normalize_hex};
+use apdl_core::{ByteOrder, LayerDefinition, UnitType};
+use apdl_lsk::{DisassembleResult, LayeredDisassembler};
+
+use crate::cli::explain::build_disassembler;
+use crate::cli::validate::load_layers;
+
+/// 已加载的协议定义（各层从外到内）
+///
+/// 除最内层外，每层的最后一个`RawData`字段作为净荷交给下一层
+#[derive(Debug, Clone)]
+pub struct ProtocolStack {
+    pub name: String,
+    pub layers: Vec<LayerDefinition>,
+}
+
+impl ProtocolStack {
+    pub fn new(name: impl Into<String>, layers: Vec<LayerDefinition>) -> Self {
+        Self {
+            name: name.into(),
+            layers,
+        }
+    }
+
+    /// 读取协议定义文件（`.json`按JSON解析，其余按DSL解析），以文件名作为名称
+    pub fn load(path: &str) -> Result<Self, String> {
+        let (_, layers) = load_layers(path)?;
+        let name = std::path::Path::new(path)
+            .file_name()
+            .map_or(path.to_string(), |name| name.to_string_lossy().into_owned());
+        Ok(Self::new(name, layers))
+    }
+
+    /// 指定层的净荷字段名
+    pub fn payload_field(&self, layer_index: usize) -> Option<&str> {
+        if layer_index + 1 >= self.layers.len() {
+            return None;
+        }
+        self.layers[layer_index]
+            .units
+            .iter()
+            .rev()
+            .find(|unit| unit.unit_type == UnitType::RawData)
+            .map(|unit| unit.field_id.as_str())
+    }
+
+    /// 创建分层拆包器
+    pub fn disassembler(&self) -> LayeredDisassembler {
+        let mut layered = LayeredDisassembler::new();
+        for (index, layer) in self.layers.iter().enumerate() {
+            layered.add_layer(
+                layer.name.clone(),
+                build_disassembler(&layer.units, &layer.rules),
+                self.payload_field(index).map(str::to_string),
+            );
+        }
+        layered
+    }
+
+    /// 解析十六进制帧（可含空格或0x前缀）并构建解析树
+    pub fn parse_hex(&self, hex: &str) -> Result<FrameTree, String> {
+        let hex = normalize_hex(hex).map_err(|e| format!("Invalid hex frame: {e}"))?;
+        let frame = hex_to_bytes(&hex).map_err(|e| format!("Invalid hex frame: {e}"))?;
+        let result = self
+            .disassembler()
+            .disassemble_layers(&frame)
+            .map_err(|e| e.to_string())?;
+        Ok(build_frame_tree(self, &result))
+    }
+}
+
+/// 字段节点
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldNode {
+    pub name: String,
+    /// 渲染后的字段值
+    pub value: String,
+    /// 约束违反信息
+    pub error: Option<String>,
+}
+
+/// 层节点
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerNode {
+    pub name: String,
+    pub fields: Vec<FieldNode>,
+}
+
+impl LayerNode {
+    /// 该层是否有字段违反约束
+    pub fn has_errors(&self) -> bool {
+        self.fields.iter().any(|field| field.error.is_some())
+    }
+}
+
+/// 解析树
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FrameTree {
+    pub layers: Vec<LayerNode>,
+}
+
+impl FrameTree {
+    /// 约束违反总数
+    pub fn error_count(&self) -> usize {
+        self.layers
+            .iter()
+            .flat_map(|layer| &layer.fields)
+            .filter(|field| field.error.is_some())
+            .count()
+    }
+}
+
+/// 将分层拆包结果整理为解析树
+///
+/// 字段按协议定义中的顺序排列，约束违反按层索引和字段名挂到对应字段上
+pub fn build_frame_tree(stack: &ProtocolStack, result: &DisassembleResult) -> FrameTree {
+    let layers = result
+        .layers
+        .iter()
+        .map(|layer| {
+            let units = stack
+                .layers
+                .get(layer.layer_index)
+                .map_or(&[][..], |definition| definition.units.as_slice());
+            let fields = units
+                .iter()
+                .filter_map(|unit| {
+                    let value = layer.get_field(&unit.field_id)?;
+                    let error = result
+                        .errors
+                        .iter()
+                        .find(|error| {
+                            error.layer_index == layer.layer_index
+                                && error.field_name == unit.field_id
+                        })
+                        .map(|error| error.error_message.clone());
+                    Some(FieldNode {
+                        name: unit.field_id.clone(),
+                        value: render_value(value, unit.value_byte_order()),
+                        error,
+                    })
+                })
+                .collect();
+            LayerNode {
+                name: layer.layer_name.clone(),
+                fields,
+            }
+        })
+        .collect();
+
+    FrameTree { layers }
+}
+
+/// 不超过8字节的值按字段字节序显示为整数及十六进制，更长的值显示为字节序列
+fn render_value(value: &[u8], byte_order: ByteOrder) -> String {
+    match decode_uint(value, byte_order) {
+        Some(number) if !value.is_empty() => format!("{number} (0x{number:X})"),
+        _ => format!("[{}] ({} bytes)", bytes_to_hex(value), value.len()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apdl_poem::DslParserImpl;
+
+    const FRAME_DSL: &str = r#"
+field: sync; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; constraint: fixed(0xEB90); desc: "Sync"
+field: frame_data; type: RawData; length: 4byte; scope: layer(link); cover: entire_field; desc: "Frame data"
+"#;
+
+    const PACKET_DSL: &str = r#"
+field: apid; type: Uint8; length: 1byte; scope: layer(net); cover: entire_field; constraint: range(1..=10); desc: "APID"
+field: pkt_data; type: RawData; length: 3byte; scope: layer(net); cover: entire_field; desc: "Packet data"
+"#;
+
+    fn layer(name: &str, dsl: &str) -> LayerDefinition {
+        let parser = DslParserImpl::new();
+        LayerDefinition {
+            name: name.to_string(),
+            units: parser.parse_protocol_structure(dsl).unwrap(),
+            rules: parser.parse_semantic_rules(dsl).unwrap(),
+        }
+    }
+
+    fn stack() -> ProtocolStack {
+        ProtocolStack::new(
+            "tm",
+            vec![layer("frame", FRAME_DSL), layer("packet", PACKET_DSL)],
+        )
+    }
+
+    #[test]
+    fn test_tree_nests_fields_under_layers_in_definition_order() {
+        let stack = stack();
+        assert_eq!(stack.payload_field(0), Some("frame_data"));
+        assert_eq!(stack.payload_field(1), None);
+
+        let tree = stack.parse_hex("EB 90 05 AA BB CC").unwrap();
+        let names: Vec<Vec<&str>> = tree
+            .layers
+            .iter()
+            .map(|layer| layer.fields.iter().map(|f| f.name.as_str()).collect())
+            .collect();
+        assert_eq!(
+            names,
+            [vec!["sync", "frame_data"], vec!["apid", "pkt_data"]]
+        );
+        assert_eq!(tree.layers[0].name, "frame");
+        assert_eq!(tree.layers[0].fields[0].value, "60304 (0xEB90)");
+        assert_eq!(tree.layers[1].fields[0].value, "5 (0x5)");
+        assert_eq!(tree.layers[1].fields[1].value, "11189196 (0xAABBCC)");
+        assert_eq!(tree.error_count(), 0);
+    }
+
+    #[test]
+    fn test_tree_marks_constraint_failures_on_their_layer() {
+        let tree = stack().parse_hex("0xEB90 20 AABBCC").unwrap();
+
+        assert!(!tree.layers[0].has_errors());
+        assert!(tree.layers[1].has_errors());
+        let apid = &tree.layers[1].fields[0];
+        assert_eq!(apid.name, "apid");
+        assert!(apid.error.as_deref().unwrap().contains("32"));
+        assert_eq!(tree.error_count(), 1);
+    }
+
+    #[test]
+    fn test_values_follow_field_byte_order() {
+        let dsl = r#"
+field: msg_id; type: Uint16; length: 2byte; scope: layer(app); cover: entire_field; endian: le; desc: "Message id"
+field: counter; type: Uint16; length: 2byte; scope: layer(app); cover: entire_field; desc: "Counter"
+"#;
+        let stack = ProtocolStack::new("app", vec![layer("app", dsl)]);
+        let tree = stack.parse_hex("34 12 00 2A").unwrap();
+        assert_eq!(tree.layers[0].fields[0].value, "4660 (0x1234)");
+        assert_eq!(tree.layers[0].fields[1].value, "42 (0x2A)");
+    }
+
+    #[test]
+    fn test_parse_hex_rejects_odd_digits() {
+        let error = stack().parse_hex("EB9").unwrap_err();
+        assert!(error.contains("odd number of digits"));
+    }
+}
@@ -1,27 +1,183 @@
 //! 图形用户界面模块
 //!
-//! 基于egui/eframe的GUI应用程序
+//! 基于egui/eframe的GUI应用程序：加载协议定义，粘贴十六进制帧后在后台线程中分层拆包，
+//! 以可折叠的层 → 字段 → 值树显示结果，违反约束的字段以红色标出
+
+pub mod frame_tree;
+
+pub use frame_tree::{build_frame_tree, FieldNode, FrameTree, LayerNode, ProtocolStack};
 
 use eframe::egui;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::thread;
 
 #[derive(Default)]
-pub struct GuiApp;
+pub struct GuiApp {
+    /// 待加载的协议定义文件路径
+    definition_path: String,
+    /// 已加载的协议定义
+    stacks: Vec<Arc<ProtocolStack>>,
+    /// 当前选中的协议定义索引
+    selected: usize,
+    /// 十六进制帧输入
+    hex_input: String,
+    /// 最近一次解析得到的树
+    tree: Option<FrameTree>,
+    /// 加载或解析错误信息
+    error: Option<String>,
+    /// 后台解析结果
+    pending: Option<Receiver<Result<FrameTree, String>>>,
+}
 
 impl GuiApp {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    fn load_definition(&mut self) {
+        match ProtocolStack::load(self.definition_path.trim()) {
+            Ok(stack) => {
+                self.stacks.push(Arc::new(stack));
+                self.selected = self.stacks.len() - 1;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    /// 在后台线程中解析当前输入，完成后请求重绘
+    fn start_parse(&mut self, ctx: &egui::Context) {
+        let Some(stack) = self.stacks.get(self.selected).cloned() else {
+            self.error = Some("No protocol definition loaded".to_string());
+            return;
+        };
+        let hex = self.hex_input.clone();
+        let ctx = ctx.clone();
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = sender.send(stack.parse_hex(&hex));
+            ctx.request_repaint();
+        });
+        self.pending = Some(receiver);
+    }
+
+    fn poll_parse(&mut self) {
+        let Some(receiver) = &self.pending else {
+            return;
+        };
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Err("Parser thread stopped".to_string()),
+        };
+        self.pending = None;
+        match result {
+            Ok(tree) => {
+                self.tree = Some(tree);
+                self.error = None;
+            }
+            Err(e) => {
+                self.tree = None;
+                self.error = Some(e);
+            }
+        }
+    }
+
+    fn input_panel(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Definition:");
+            ui.text_edit_singleline(&mut self.definition_path);
+            if ui.button("Load").clicked() {
+                self.load_definition();
+            }
+        });
+
+        let selected_text = self
+            .stacks
+            .get(self.selected)
+            .map_or("(none)", |stack| stack.name.as_str());
+        egui::ComboBox::from_label("Protocol")
+            .selected_text(selected_text)
+            .show_ui(ui, |ui| {
+                for (index, stack) in self.stacks.iter().enumerate() {
+                    ui.selectable_value(&mut self.selected, index, stack.name.as_str());
+                }
+            });
+
+        ui.label("Hex frame:");
+        ui.add(
+            egui::TextEdit::multiline(&mut self.hex_input)
+                .code_editor()
+                .desired_rows(3)
+                .desired_width(f32::INFINITY),
+        );
+    }
+
+    fn tree_view(ui: &mut egui::Ui, tree: &FrameTree) {
+        for (layer_index, layer) in tree.layers.iter().enumerate() {
+            let mut title = egui::RichText::new(format!("{layer_index}: {}", layer.name));
+            if layer.has_errors() {
+                title = title.color(egui::Color32::RED);
+            }
+            egui::CollapsingHeader::new(title)
+                .id_salt(("layer", layer_index))
+                .default_open(true)
+                .show(ui, |ui| {
+                    for field in &layer.fields {
+                        Self::field_view(ui, layer_index, field);
+                    }
+                });
+        }
+    }
+
+    fn field_view(ui: &mut egui::Ui, layer_index: usize, field: &FieldNode) {
+        let mut title = egui::RichText::new(&field.name);
+        if field.error.is_some() {
+            title = title.color(egui::Color32::RED);
+        }
+        egui::CollapsingHeader::new(title)
+            .id_salt(("field", layer_index, &field.name))
+            .show(ui, |ui| {
+                ui.monospace(&field.value);
+                if let Some(error) = &field.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+            });
     }
 }
 
 impl eframe::App for GuiApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        egui::CentralPanel::default().show(ctx, |ui| {
-            ui.heading("APDL Interaction Access Module");
-            ui.label("Welcome to the APDL GUI interface!");
+        self.poll_parse();
 
-            if ui.button("Click me").clicked() {
-                println!("Button clicked!");
+        egui::TopBottomPanel::top("input").show(ctx, |ui| {
+            ui.heading("APDL Interaction Access Module");
+            self.input_panel(ui);
+            ui.horizontal(|ui| {
+                let parsing = self.pending.is_some();
+                if ui
+                    .add_enabled(!parsing, egui::Button::new("Parse"))
+                    .clicked()
+                {
+                    self.start_parse(ctx);
+                }
+                if parsing {
+                    ui.spinner();
+                }
+            });
+            if let Some(error) = &self.error {
+                ui.colored_label(egui::Color32::RED, error);
             }
         });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            egui::ScrollArea::vertical().show(ui, |ui| match &self.tree {
+                Some(tree) => Self::tree_view(ui, tree),
+                None => {
+                    ui.label("Load a protocol definition and paste a hex frame to parse it.");
+                }
+            });
+        });
     }
 }
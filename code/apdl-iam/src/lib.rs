@@ -6,6 +6,6 @@ pub mod api;
 pub mod cli;
 pub mod gui;
 
-pub use api::RestApiServer;
+pub use api::{RestApiServer, ServerHandle};
 pub use cli::CommandLineInterface;
 pub use gui::GuiApp;
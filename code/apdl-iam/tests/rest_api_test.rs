@@ -0,0 +1,226 @@
+//! REST API测试
+//!
+//! 在系统分配的端口上启动服务，通过HTTP请求解析DSL文档
+
+use apdl_core::utils::{calculate_crc16_with, Crc16Params};
+use apdl_iam::RestApiServer;
+use serde_json::{json, Value};
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::time::{Duration, Instant};
+
+const FRAME_DSL: &str = r#"field: sync; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; constraint: fixed(0xEB90); desc: "同步字"
+field: apid; type: Bit(11); length: 11bit; scope: layer(net); cover: entire_field; desc: "APID"
+"#;
+
+/// 组帧定义：长度字段由长度规则计算，帧校验由校验和规则计算
+const ASSEMBLE_DSL: &str = r#"field: len; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "Length"
+field: data; type: Uint32; length: 4byte; scope: layer(link); cover: entire_field; desc: "Data"
+field: fecf; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; alg: crc16; desc: "Frame check"
+rule: length_rule(field: len equals "(total_length - 1)");
+rule: crc_range(start: len to data);
+"#;
+
+/// 发送请求并返回状态码和JSON响应体
+fn request(addr: SocketAddr, method: &str, path: &str, body: &str) -> (u16, Value) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "{method} {path} HTTP/1.1\r\nHost: {addr}\r\nContent-Type: text/plain\r\n\
+         Content-Length: {}\r\n\r\n{body}",
+        body.len()
+    )
+    .unwrap();
+    read_response(stream)
+}
+
+/// 读取响应直到连接关闭，返回状态码和JSON响应体
+fn read_response(mut stream: TcpStream) -> (u16, Value) {
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+    (status, serde_json::from_str(body).unwrap())
+}
+
+#[test]
+fn test_parse_endpoint_returns_syntax_units() {
+    let server = RestApiServer::new().run("127.0.0.1:0").unwrap();
+    let addr = server.local_addr();
+
+    let (status, body) = request(addr, "GET", "/health", "");
+    assert_eq!(status, 200);
+    assert_eq!(body["status"], "ok");
+
+    let (status, body) = request(addr, "POST", "/parse", FRAME_DSL);
+    assert_eq!(status, 200);
+    let units = body.as_array().unwrap();
+    assert_eq!(units.len(), 2);
+    assert_eq!(units[0]["field_id"], "sync");
+    assert_eq!(units[0]["desc"], "同步字");
+    assert_eq!(units[1]["field_id"], "apid");
+
+    server.shutdown().unwrap();
+}
+
+#[test]
+fn test_parse_endpoint_reports_errors() {
+    let server = RestApiServer::new().run("127.0.0.1:0").unwrap();
+    let addr = server.local_addr();
+
+    let dsl = "field: x; type: Uint8; length: onebyte; scope: layer(app); cover: entire_field; desc: \"X\"";
+    let (status, body) = request(addr, "POST", "/parse", dsl);
+    assert_eq!(status, 400);
+    assert_eq!(
+        body["error"],
+        "1:32: Invalid byte length: onebyte (at 'onebyte')"
+    );
+    assert_eq!(body["details"]["Syntax"]["column"], 32);
+
+    assert_eq!(request(addr, "GET", "/parse", "").0, 405);
+    assert_eq!(request(addr, "GET", "/units", "").0, 404);
+
+    server.shutdown().unwrap();
+}
+
+#[test]
+fn test_assemble_endpoint_applies_rules() {
+    let server = RestApiServer::new().run("127.0.0.1:0").unwrap();
+    let addr = server.local_addr();
+
+    let request_body = json!({
+        "dsl": ASSEMBLE_DSL,
+        "values": {"len": "00", "data": "0x12345678"},
+    });
+    let (status, body) = request(addr, "POST", "/assemble", &request_body.to_string());
+    assert_eq!(status, 200, "{body}");
+
+    // 长度规则把len设为帧长减1，帧校验为len和data的CRC16
+    let mut expected = vec![0x06, 0x12, 0x34, 0x56, 0x78];
+    let crc = calculate_crc16_with(Crc16Params::CCSDS, &expected);
+    expected.extend_from_slice(&crc.to_be_bytes());
+    assert_eq!(body["frame"], hex::encode(&expected));
+
+    server.shutdown().unwrap();
+}
+
+#[test]
+fn test_assemble_endpoint_reports_protocol_errors() {
+    let server = RestApiServer::new().run("127.0.0.1:0").unwrap();
+    let addr = server.local_addr();
+
+    let missing_field = json!({"dsl": ASSEMBLE_DSL, "values": {"payload": "01"}});
+    let (status, body) = request(addr, "POST", "/assemble", &missing_field.to_string());
+    assert_eq!(status, 400);
+    assert_eq!(body["error"], "Field not found: payload");
+
+    let wrong_length = json!({"dsl": ASSEMBLE_DSL, "values": {"data": "1234"}});
+    let (status, body) = request(addr, "POST", "/assemble", &wrong_length.to_string());
+    assert_eq!(status, 400);
+    assert_eq!(
+        body["error"],
+        "Length error: Field data expected 4 bytes, got 2 bytes"
+    );
+
+    let (status, _) = request(addr, "POST", "/assemble", "not json");
+    assert_eq!(status, 400);
+
+    server.shutdown().unwrap();
+}
+
+#[test]
+fn test_oversized_request_is_rejected() {
+    let server = RestApiServer::new().run("127.0.0.1:0").unwrap();
+    let addr = server.local_addr();
+
+    let mut stream = TcpStream::connect(addr).unwrap();
+    let long_path = "a".repeat(16 * 1024);
+    write!(stream, "GET /{long_path} HTTP/1.1\r\n\r\n").unwrap();
+    let (status, body) = read_response(stream);
+    assert_eq!(status, 400);
+    assert_eq!(body["error"], "Request line or header too long");
+
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "POST /parse HTTP/1.1\r\nContent-Length: 2000000\r\n\r\n"
+    )
+    .unwrap();
+    let (status, body) = read_response(stream);
+    assert_eq!(status, 400);
+    assert_eq!(body["error"], "Request body too large");
+
+    server.shutdown().unwrap();
+}
+
+#[test]
+fn test_short_body_times_out() {
+    let server = RestApiServer::new()
+        .with_timeout(Duration::from_millis(200))
+        .run("127.0.0.1:0")
+        .unwrap();
+
+    // 声明的请求体长度大于实际发送的字节数
+    let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+    write!(
+        stream,
+        "POST /parse HTTP/1.1\r\nContent-Length: 10\r\n\r\nabc"
+    )
+    .unwrap();
+    let (status, body) = read_response(stream);
+    assert_eq!(status, 408);
+    assert_eq!(body["error"], "Request timed out");
+
+    server.shutdown().unwrap();
+}
+
+#[test]
+fn test_connection_limit() {
+    let server = RestApiServer::new()
+        .with_timeout(Duration::from_millis(500))
+        .with_max_connections(1)
+        .run("127.0.0.1:0")
+        .unwrap();
+    let addr = server.local_addr();
+
+    // 第一个连接不发送请求，占用唯一的连接名额直到超时
+    let idle = TcpStream::connect(addr).unwrap();
+    let (status, body) = request(addr, "GET", "/health", "");
+    assert_eq!(status, 503);
+    assert_eq!(body["error"], "Too many connections");
+
+    assert_eq!(read_response(idle).0, 408);
+    assert_eq!(request(addr, "GET", "/health", "").0, 200);
+
+    server.shutdown().unwrap();
+}
+
+#[test]
+fn test_slow_rejected_client_does_not_block_accept() {
+    let server = RestApiServer::new()
+        .with_timeout(Duration::from_secs(2))
+        .with_max_connections(1)
+        .run("127.0.0.1:0")
+        .unwrap();
+    let addr = server.local_addr();
+
+    let idle = TcpStream::connect(addr).unwrap();
+    // 被拒绝的连接每50毫秒发送一个字节
+    let mut slow = TcpStream::connect(addr).unwrap();
+    let dripper = std::thread::spawn(move || {
+        for _ in 0..20 {
+            if slow.write_all(b"G").is_err() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    });
+
+    let start = Instant::now();
+    assert_eq!(request(addr, "GET", "/health", "").0, 503);
+    assert!(start.elapsed() < Duration::from_millis(500));
+
+    dripper.join().unwrap();
+    drop(idle);
+    server.shutdown().unwrap();
+}
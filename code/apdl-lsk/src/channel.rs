@@ -2,6 +2,8 @@
 //!
 //! 实现仿真通信信道功能
 
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
 use std::collections::VecDeque;
 
 /// 通信信道类型
@@ -12,12 +14,86 @@ pub enum ChannelType {
     Multicast,
 }
 
+/// Gilbert-Elliott突发丢包模型
+///
+/// 信道在好、坏两个隐藏状态间按马尔可夫链转移，每帧按当前状态的丢包率丢弃
+#[derive(Debug)]
+pub struct GilbertElliott {
+    p_good_to_bad: f64,
+    p_bad_to_good: f64,
+    loss_good: f64,
+    loss_bad: f64,
+    in_bad_state: bool,
+    rng: StdRng,
+}
+
+impl GilbertElliott {
+    /// 创建模型，初始处于好状态；相同种子产生相同的丢包序列
+    pub fn new(
+        p_good_to_bad: f64,
+        p_bad_to_good: f64,
+        loss_good: f64,
+        loss_bad: f64,
+        seed: u64,
+    ) -> Self {
+        Self {
+            p_good_to_bad: p_good_to_bad.clamp(0.0, 1.0),
+            p_bad_to_good: p_bad_to_good.clamp(0.0, 1.0),
+            loss_good: loss_good.clamp(0.0, 1.0),
+            loss_bad: loss_bad.clamp(0.0, 1.0),
+            in_bad_state: false,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// 判断当前帧是否丢失，并推进一次状态转移
+    pub fn drop_frame(&mut self) -> bool {
+        let loss = if self.in_bad_state {
+            self.loss_bad
+        } else {
+            self.loss_good
+        };
+        let dropped = self.rng.random_range(0.0..1.0) < loss;
+
+        let transition = if self.in_bad_state {
+            self.p_bad_to_good
+        } else {
+            self.p_good_to_bad
+        };
+        if self.rng.random_range(0.0..1.0) < transition {
+            self.in_bad_state = !self.in_bad_state;
+        }
+        dropped
+    }
+
+    /// 是否处于坏状态
+    pub fn in_bad_state(&self) -> bool {
+        self.in_bad_state
+    }
+
+    /// 稳态丢包率：π_good·loss_good + π_bad·loss_bad
+    pub fn stationary_loss_rate(&self) -> f64 {
+        let total = self.p_good_to_bad + self.p_bad_to_good;
+        if total == 0.0 {
+            // 不发生状态转移时始终停留在初始的好状态
+            return self.loss_good;
+        }
+        let pi_bad = self.p_good_to_bad / total;
+        (1.0 - pi_bad) * self.loss_good + pi_bad * self.loss_bad
+    }
+}
+
 /// 通信信道结构
 pub struct Channel {
     id: String,
     channel_type: ChannelType,
     buffer: VecDeque<Vec<u8>>,
     capacity: usize,
+    loss_model: Option<GilbertElliott>,
+    frames_dropped: u64,
+    bit_error_rate: f64,
+    bit_error_rng: StdRng,
+    bits_flipped: u64,
 }
 
 impl Channel {
@@ -27,9 +103,67 @@ impl Channel {
             channel_type,
             buffer: VecDeque::new(),
             capacity,
+            loss_model: None,
+            frames_dropped: 0,
+            bit_error_rate: 0.0,
+            bit_error_rng: StdRng::seed_from_u64(0),
+            bits_flipped: 0,
         }
     }
 
+    /// 启用Gilbert-Elliott突发丢包模型，模型状态在多次`transmit`调用间保持
+    pub fn with_gilbert_elliott(
+        mut self,
+        p_good_to_bad: f64,
+        p_bad_to_good: f64,
+        loss_good: f64,
+        loss_bad: f64,
+        seed: u64,
+    ) -> Self {
+        self.loss_model = Some(GilbertElliott::new(
+            p_good_to_bad,
+            p_bad_to_good,
+            loss_good,
+            loss_bad,
+            seed,
+        ));
+        self
+    }
+
+    /// 设置误码率，传输时每个bit以该概率翻转
+    pub fn set_bit_error_rate(&mut self, ber: f64) {
+        self.bit_error_rate = ber.clamp(0.0, 1.0);
+    }
+
+    /// 设置误码注入的随机数种子，相同种子产生相同的误码图样
+    pub fn set_bit_error_seed(&mut self, seed: u64) {
+        self.bit_error_rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// 经丢包模型和误码注入传输一帧
+    ///
+    /// 帧被丢弃时返回`Ok(None)`，否则返回进入缓冲区的（可能含误码的）帧
+    pub fn transmit(&mut self, mut data: Vec<u8>) -> Result<Option<Vec<u8>>, &'static str> {
+        if let Some(model) = self.loss_model.as_mut() {
+            if model.drop_frame() {
+                self.frames_dropped += 1;
+                return Ok(None);
+            }
+        }
+        if self.bit_error_rate > 0.0 {
+            for byte in data.iter_mut() {
+                for bit in 0..8 {
+                    if self.bit_error_rng.random_range(0.0..1.0) < self.bit_error_rate {
+                        *byte ^= 1 << bit;
+                        self.bits_flipped += 1;
+                    }
+                }
+            }
+        }
+        self.send(data.clone())?;
+        Ok(Some(data))
+    }
+
     pub fn send(&mut self, data: Vec<u8>) -> Result<(), &'static str> {
         if self.buffer.len() >= self.capacity {
             return Err("Channel buffer full");
@@ -68,4 +202,124 @@ impl Channel {
     pub fn capacity(&self) -> usize {
         self.capacity
     }
+
+    /// 获取丢包模型
+    pub fn loss_model(&self) -> Option<&GilbertElliott> {
+        self.loss_model.as_ref()
+    }
+
+    /// 获取被丢包模型丢弃的帧数
+    pub fn frames_dropped(&self) -> u64 {
+        self.frames_dropped
+    }
+
+    /// 获取误码注入翻转的bit数
+    pub fn bits_flipped(&self) -> u64 {
+        self.bits_flipped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame_disassembler::FieldValidator;
+
+    fn bursty_channel(seed: u64) -> Channel {
+        Channel::new(
+            "downlink".to_string(),
+            ChannelType::PointToPoint,
+            usize::MAX,
+        )
+        .with_gilbert_elliott(0.05, 0.25, 0.01, 0.6, seed)
+    }
+
+    #[test]
+    fn test_gilbert_elliott_loss_rate_matches_stationary_expectation() {
+        let mut channel = bursty_channel(42);
+        let expected = channel.loss_model().unwrap().stationary_loss_rate();
+        // π_bad = 0.05 / 0.30，期望丢包率 = 5/6·0.01 + 1/6·0.6
+        assert!((expected - 0.108_333).abs() < 1e-6);
+
+        let frames = 200_000;
+        let delivered = (0..frames)
+            .filter(|i| channel.transmit(vec![*i as u8]).unwrap().is_some())
+            .count();
+        let observed = channel.frames_dropped() as f64 / frames as f64;
+        assert_eq!(delivered as u64 + channel.frames_dropped(), frames as u64);
+        assert!(
+            (observed - expected).abs() < 0.01,
+            "observed {observed}, expected {expected}"
+        );
+    }
+
+    #[test]
+    fn test_gilbert_elliott_is_reproducible_with_seed() {
+        let pattern = |seed| {
+            let mut channel = bursty_channel(seed);
+            (0..1000)
+                .map(|_| channel.transmit(vec![0]).unwrap().is_some())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(pattern(7), pattern(7));
+        assert_ne!(pattern(7), pattern(8));
+    }
+
+    #[test]
+    fn test_transmit_without_loss_model_delivers_all() {
+        let mut channel = Channel::new("uplink".to_string(), ChannelType::PointToPoint, 2);
+        assert_eq!(channel.transmit(vec![1]), Ok(Some(vec![1])));
+        assert_eq!(channel.transmit(vec![2]), Ok(Some(vec![2])));
+        assert_eq!(channel.transmit(vec![3]), Err("Channel buffer full"));
+        assert_eq!(channel.frames_dropped(), 0);
+    }
+
+    fn frame_with_crc(payload: &[u8]) -> Vec<u8> {
+        let mut frame = payload.to_vec();
+        let crc = apdl_core::utils::calculate_ccsds_crc(payload);
+        frame.extend_from_slice(&crc.to_be_bytes());
+        frame
+    }
+
+    fn crc_valid(frame: &[u8]) -> bool {
+        let (payload, crc) = frame.split_at(frame.len() - 2);
+        FieldValidator::verify_crc16(payload, u16::from_be_bytes([crc[0], crc[1]])).is_ok()
+    }
+
+    #[test]
+    fn test_bit_errors_are_caught_by_crc() {
+        let mut channel = Channel::new("noisy".to_string(), ChannelType::PointToPoint, usize::MAX);
+        channel.set_bit_error_rate(1e-3);
+        channel.set_bit_error_seed(2024);
+
+        let mut corrupted = 0;
+        for i in 0..500u32 {
+            let payload: Vec<u8> = (0..64).map(|j| (i as u8).wrapping_mul(31) ^ j).collect();
+            let frame = frame_with_crc(&payload);
+            let received = channel.transmit(frame.clone()).unwrap().unwrap();
+            if received != frame {
+                corrupted += 1;
+                assert!(!crc_valid(&received), "undetected corruption in frame {i}");
+            } else {
+                assert!(crc_valid(&received));
+            }
+        }
+        // 每帧528bit，BER 1e-3时约41%的帧含误码
+        assert!(corrupted > 150 && corrupted < 260, "corrupted {corrupted}");
+        assert!(channel.bits_flipped() >= corrupted);
+    }
+
+    #[test]
+    fn test_zero_bit_error_rate_leaves_data_untouched() {
+        let mut channel = Channel::new("clean".to_string(), ChannelType::PointToPoint, usize::MAX);
+        channel.set_bit_error_rate(0.0);
+        let frame = frame_with_crc(&[0xAA; 32]);
+        for _ in 0..100 {
+            assert_eq!(
+                channel.transmit(frame.clone()).unwrap(),
+                Some(frame.clone())
+            );
+        }
+        assert_eq!(channel.bits_flipped(), 0);
+        assert_eq!(channel.receive(), Some(frame));
+    }
 }
@@ -2,7 +2,11 @@
 //!
 //! 处理字段约束（范围、固定值、枚举），确保生成的数据符合约束条件
 
-use apdl_core::Constraint;
+use apdl_core::utils::decode_uint;
+use apdl_core::{Constraint, ParsedField, ProtocolError, SyntaxUnit};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
 
 /// 约束处理器
 pub struct ConstraintHandler;
@@ -93,26 +97,191 @@ impl ConstraintHandler {
     }
 }
 
+/// 约束违反记录
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstraintViolation {
+    /// 字段名
+    pub field_name: String,
+    /// 期望值描述（由约束生成）
+    pub expected: String,
+    /// 实际值
+    pub actual: u64,
+    /// 被违反的约束
+    pub constraint: Constraint,
+}
+
+/// 预编译的约束
+#[derive(Debug, Clone)]
+enum CompiledConstraint {
+    /// 范围比较
+    Range(u64, u64),
+    /// 固定值比较
+    Fixed(u64),
+    /// 枚举值集合
+    Enum(HashSet<u64>),
+    /// 匹配值十进制字符串的正则表达式
+    Pattern(Regex),
+    /// 不做检查的自定义约束
+    Unchecked,
+}
+
+impl CompiledConstraint {
+    fn compile(constraint: &Constraint) -> Self {
+        match constraint {
+            Constraint::Range(min, max) => CompiledConstraint::Range(*min, *max),
+            Constraint::FixedValue(value) => CompiledConstraint::Fixed(*value),
+            Constraint::Enum(entries) => {
+                CompiledConstraint::Enum(entries.iter().map(|(_, v)| *v).collect())
+            }
+            Constraint::Custom(expr) => expr
+                .trim()
+                .strip_prefix("pattern(")
+                .and_then(|rest| rest.strip_suffix(')'))
+                .and_then(|pattern| Regex::new(pattern).ok())
+                .map(CompiledConstraint::Pattern)
+                .unwrap_or(CompiledConstraint::Unchecked),
+        }
+    }
+
+    fn matches(&self, value: u64, digits: &mut DecimalBuffer) -> bool {
+        match self {
+            CompiledConstraint::Range(min, max) => value >= *min && value <= *max,
+            CompiledConstraint::Fixed(expected) => value == *expected,
+            CompiledConstraint::Enum(values) => values.contains(&value),
+            CompiledConstraint::Pattern(regex) => regex.is_match(digits.format(value)),
+            CompiledConstraint::Unchecked => true,
+        }
+    }
+}
+
+/// 栈上的十进制格式化缓冲区，避免模式匹配时分配字符串
+struct DecimalBuffer {
+    bytes: [u8; 20],
+    len: usize,
+}
+
+impl DecimalBuffer {
+    fn new() -> Self {
+        Self {
+            bytes: [0; 20],
+            len: 0,
+        }
+    }
+
+    fn format(&mut self, value: u64) -> &str {
+        self.len = 0;
+        let _ = write!(self, "{value}");
+        std::str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+}
+
+impl Write for DecimalBuffer {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        let end = self.len + s.len();
+        if end > self.bytes.len() {
+            return Err(std::fmt::Error);
+        }
+        self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
 /// 约束验证器
-pub struct ConstraintValidator;
+///
+/// 通过`new`创建时预编译约束：枚举值存入`HashSet`，范围转为比较，
+/// `pattern(...)`自定义约束编译为正则表达式，之后每次`validate`不再分配内存
+#[derive(Debug, Default, Clone)]
+pub struct ConstraintValidator {
+    compiled: Vec<CompiledConstraint>,
+}
 
 impl ConstraintValidator {
-    /// 验证值是否符合约束
+    /// 预编译约束条件列表
+    pub fn new(constraints: &[Constraint]) -> Self {
+        Self {
+            compiled: constraints
+                .iter()
+                .map(CompiledConstraint::compile)
+                .collect(),
+        }
+    }
+
+    /// 按字段定义的约束校验一条记录（字段名 -> 字段值），返回全部约束违反
     ///
-    /// # 参数
-    /// - `value`: 要验证的值
-    /// - `constraints`: 约束条件列表
+    /// 按字段定义顺序检查，不会在第一个错误处中断；字段值按字段字节序解码，
+    /// 记录中缺失的字段、无约束字段和超过8字节无法解码为整数的字段不检查
+    pub fn validate_record(
+        fields: &[SyntaxUnit],
+        record: &HashMap<String, Vec<u8>>,
+    ) -> Vec<(String, ConstraintViolation)> {
+        let mut violations = Vec::new();
+        for field in fields {
+            let Some(constraint) = &field.constraint else {
+                continue;
+            };
+            let Some(actual) = record
+                .get(&field.field_id)
+                .and_then(|value| decode_uint(value, field.value_byte_order()))
+            else {
+                continue;
+            };
+            if !Self::validate_single(actual, constraint) {
+                violations.push((
+                    field.field_id.clone(),
+                    ConstraintViolation {
+                        field_name: field.field_id.clone(),
+                        expected: Self::describe_constraint(constraint),
+                        actual,
+                        constraint: constraint.clone(),
+                    },
+                ));
+            }
+        }
+        violations
+    }
+
+    /// 验证值是否符合全部预编译约束
     ///
     /// # 返回
     /// - `true`: 值符合所有约束
     /// - `false`: 值不符合至少一个约束
-    pub fn validate(value: u64, constraints: &[Constraint]) -> bool {
-        for constraint in constraints {
-            if !Self::validate_single(value, constraint) {
-                return false;
+    pub fn validate(&self, value: u64) -> bool {
+        let mut digits = DecimalBuffer::new();
+        self.compiled
+            .iter()
+            .all(|constraint| constraint.matches(value, &mut digits))
+    }
+
+    /// 验证值是否符合约束（不预编译，适用于一次性检查）
+    ///
+    /// # 参数
+    /// - `value`: 要验证的值
+    /// - `constraints`: 约束条件列表
+    pub fn validate_value(value: u64, constraints: &[Constraint]) -> bool {
+        Self::new(constraints).validate(value)
+    }
+
+    /// 验证整帧的所有字段，返回全部约束违反记录（不会在第一个错误处中断）
+    pub fn validate_frame(&self, parsed: &[ParsedField]) -> Vec<ConstraintViolation> {
+        let mut violations = Vec::new();
+        for field in parsed {
+            let Some(constraint) = &field.constraint else {
+                continue;
+            };
+            let Some(actual) = field.numeric_value() else {
+                continue;
+            };
+            if !Self::validate_single(actual, constraint) {
+                violations.push(ConstraintViolation {
+                    field_name: field.name.clone(),
+                    expected: Self::describe_constraint(constraint),
+                    actual,
+                    constraint: constraint.clone(),
+                });
             }
         }
-        true
+        violations
     }
 
     /// 验证单个约束
@@ -128,6 +297,24 @@ impl ConstraintValidator {
         }
     }
 
+    /// 验证约束取值是否能容纳在字段位宽内
+    ///
+    /// # 返回
+    /// - `Ok(())`: 所有枚举值、固定值和范围边界都在位宽内
+    /// - `Err(ProtocolError::ConstraintOutOfWidth)`: 存在超出位宽的取值
+    pub fn validate_width(
+        field_name: &str,
+        constraint: &Constraint,
+        bit_width: u32,
+    ) -> Result<(), ProtocolError> {
+        constraint.check_width(field_name, bit_width)
+    }
+
+    /// 验证语法单元的约束是否超出其声明位宽
+    pub fn validate_unit_width(unit: &SyntaxUnit) -> Result<(), ProtocolError> {
+        unit.check_constraint_width()
+    }
+
     /// 获取约束描述字符串
     pub fn describe_constraint(constraint: &Constraint) -> String {
         match constraint {
@@ -145,6 +332,7 @@ impl ConstraintValidator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use apdl_core::ByteOrder;
 
     #[test]
     fn test_apply_range_constraint() {
@@ -194,20 +382,20 @@ mod tests {
     fn test_validate_range() {
         let constraints = vec![Constraint::Range(10, 100)];
         
-        assert!(ConstraintValidator::validate(50, &constraints));
-        assert!(ConstraintValidator::validate(10, &constraints));
-        assert!(ConstraintValidator::validate(100, &constraints));
-        assert!(!ConstraintValidator::validate(5, &constraints));
-        assert!(!ConstraintValidator::validate(101, &constraints));
+        assert!(ConstraintValidator::validate_value(50, &constraints));
+        assert!(ConstraintValidator::validate_value(10, &constraints));
+        assert!(ConstraintValidator::validate_value(100, &constraints));
+        assert!(!ConstraintValidator::validate_value(5, &constraints));
+        assert!(!ConstraintValidator::validate_value(101, &constraints));
     }
 
     #[test]
     fn test_validate_fixed_value() {
         let constraints = vec![Constraint::FixedValue(42)];
         
-        assert!(ConstraintValidator::validate(42, &constraints));
-        assert!(!ConstraintValidator::validate(41, &constraints));
-        assert!(!ConstraintValidator::validate(43, &constraints));
+        assert!(ConstraintValidator::validate_value(42, &constraints));
+        assert!(!ConstraintValidator::validate_value(41, &constraints));
+        assert!(!ConstraintValidator::validate_value(43, &constraints));
     }
 
     #[test]
@@ -218,10 +406,10 @@ mod tests {
             ("Blue".to_string(), 3),
         ])];
         
-        assert!(ConstraintValidator::validate(1, &constraints));
-        assert!(ConstraintValidator::validate(2, &constraints));
-        assert!(ConstraintValidator::validate(3, &constraints));
-        assert!(!ConstraintValidator::validate(4, &constraints));
+        assert!(ConstraintValidator::validate_value(1, &constraints));
+        assert!(ConstraintValidator::validate_value(2, &constraints));
+        assert!(ConstraintValidator::validate_value(3, &constraints));
+        assert!(!ConstraintValidator::validate_value(4, &constraints));
     }
 
     #[test]
@@ -262,4 +450,185 @@ mod tests {
         ]);
         assert_eq!(ConstraintValidator::describe_constraint(&enum_constraint), "枚举 [A=1, B=2]");
     }
+
+    fn record_field(name: &str, bytes: usize, constraint: Option<Constraint>) -> SyntaxUnit {
+        use apdl_core::{LengthDesc, LengthUnit, ScopeDesc, UnitType};
+        SyntaxUnit {
+            constraint,
+            ..SyntaxUnit::new(
+                name,
+                UnitType::Uint((bytes * 8) as u8),
+                LengthDesc {
+                    size: bytes,
+                    unit: LengthUnit::Byte,
+                },
+                ScopeDesc::Global("test".to_string()),
+            )
+        }
+    }
+
+    #[test]
+    fn test_validate_record_reports_all_violations() {
+        let fields = [
+            record_field("sync", 2, Some(Constraint::FixedValue(0xEB90))),
+            record_field("version", 1, Some(Constraint::Range(1, 3))),
+            record_field("payload", 4, None),
+            record_field(
+                "mode",
+                1,
+                Some(Constraint::Enum(vec![
+                    ("SAFE".to_string(), 0),
+                    ("NOMINAL".to_string(), 1),
+                ])),
+            ),
+            record_field("missing", 1, Some(Constraint::FixedValue(7))),
+        ];
+
+        let mut record = HashMap::new();
+        record.insert("sync".to_string(), vec![0xEB, 0x91]);
+        record.insert("version".to_string(), vec![0x09]);
+        record.insert("payload".to_string(), vec![0xFF; 4]);
+        record.insert("mode".to_string(), vec![0x05]);
+
+        let violations = ConstraintValidator::validate_record(&fields, &record);
+        let names: Vec<&str> = violations.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["sync", "version", "mode"]);
+
+        let (_, sync) = &violations[0];
+        assert_eq!(sync.actual, 0xEB91);
+        assert_eq!(sync.constraint, Constraint::FixedValue(0xEB90));
+        let (_, version) = &violations[1];
+        assert_eq!(version.actual, 9);
+        assert_eq!(version.expected, "范围 [1..=3]");
+        let (_, mode) = &violations[2];
+        assert_eq!(mode.actual, 5);
+        assert_eq!(mode.expected, "枚举 [SAFE=0, NOMINAL=1]");
+
+        record.insert("sync".to_string(), vec![0xEB, 0x90]);
+        record.insert("version".to_string(), vec![0x02]);
+        record.insert("mode".to_string(), vec![0x01]);
+        assert!(ConstraintValidator::validate_record(&fields, &record).is_empty());
+    }
+
+    #[test]
+    fn test_validate_width_enum_overflow() {
+        let constraint = Constraint::Enum(vec![
+            ("Ok".to_string(), 0x01),
+            ("TooBig".to_string(), 0x1FF),
+        ]);
+
+        let result = ConstraintValidator::validate_width("status", &constraint, 8);
+        assert!(matches!(result, Err(ProtocolError::ConstraintOutOfWidth(_))));
+
+        // 9位字段可以容纳0x1FF
+        assert!(ConstraintValidator::validate_width("status", &constraint, 9).is_ok());
+    }
+
+    #[test]
+    fn test_validate_width_fixed_and_range() {
+        assert!(ConstraintValidator::validate_width("f", &Constraint::FixedValue(0xFF), 8).is_ok());
+        assert!(
+            ConstraintValidator::validate_width("f", &Constraint::FixedValue(0x100), 8).is_err()
+        );
+        assert!(ConstraintValidator::validate_width("r", &Constraint::Range(0, 7), 3).is_ok());
+        assert!(ConstraintValidator::validate_width("r", &Constraint::Range(0, 8), 3).is_err());
+    }
+
+    #[test]
+    fn test_validate_unit_width_bit8() {
+        use apdl_core::{LengthDesc, LengthUnit, ScopeDesc, UnitType};
+
+        let unit = SyntaxUnit {
+            constraint: Some(Constraint::Enum(vec![("Overflow".to_string(), 0x1FF)])),
+            desc: "模式".to_string(),
+            ..SyntaxUnit::new(
+                "mode",
+                UnitType::Bit(8),
+                LengthDesc {
+                    size: 8,
+                    unit: LengthUnit::Bit,
+                },
+                ScopeDesc::Global("test".to_string()),
+            )
+        };
+
+        let err = ConstraintValidator::validate_unit_width(&unit).unwrap_err();
+        assert!(matches!(err, ProtocolError::ConstraintOutOfWidth(_)));
+        assert!(err.to_string().contains("Overflow"));
+    }
+
+    #[test]
+    fn test_validate_frame_reports_all_violations() {
+        let parsed = vec![
+            ParsedField {
+                name: "version".to_string(),
+                value: vec![0x09],
+                constraint: Some(Constraint::Range(0, 7)),
+                raw: vec![0x09],
+                bit_offset: 0,
+                bit_len: 8,
+                byte_order: ByteOrder::BigEndian,
+                decoded: String::new(),
+            },
+            ParsedField {
+                name: "sync".to_string(),
+                value: vec![0xEB, 0x90],
+                constraint: Some(Constraint::FixedValue(0xEB90)),
+                raw: vec![0xEB, 0x90],
+                bit_offset: 8,
+                bit_len: 16,
+                byte_order: ByteOrder::BigEndian,
+                decoded: String::new(),
+            },
+            ParsedField {
+                name: "length".to_string(),
+                value: vec![0x04, 0x00],
+                constraint: Some(Constraint::Range(1, 1000)),
+                raw: vec![0x04, 0x00],
+                bit_offset: 24,
+                bit_len: 16,
+                byte_order: ByteOrder::BigEndian,
+                decoded: String::new(),
+            },
+            ParsedField {
+                name: "data".to_string(),
+                value: vec![0xFF; 16],
+                constraint: None,
+                raw: vec![0xFF; 16],
+                bit_offset: 40,
+                bit_len: 128,
+                byte_order: ByteOrder::BigEndian,
+                decoded: String::new(),
+            },
+        ];
+
+        let violations = ConstraintValidator::default().validate_frame(&parsed);
+        assert_eq!(violations.len(), 2);
+
+        assert_eq!(violations[0].field_name, "version");
+        assert_eq!(violations[0].expected, "范围 [0..=7]");
+        assert_eq!(violations[0].actual, 9);
+
+        assert_eq!(violations[1].field_name, "length");
+        assert_eq!(violations[1].expected, "范围 [1..=1000]");
+        assert_eq!(violations[1].actual, 0x0400);
+    }
+
+    #[test]
+    fn test_compiled_validator() {
+        let validator = ConstraintValidator::new(&[
+            Constraint::Range(0, 999),
+            Constraint::Enum(vec![("Low".to_string(), 7), ("High".to_string(), 700)]),
+        ]);
+        assert!(validator.validate(7));
+        assert!(validator.validate(700));
+        assert!(!validator.validate(8));
+
+        // pattern(...)匹配值的十进制字符串
+        let validator = ConstraintValidator::new(&[Constraint::Custom("pattern(^1[0-9]*0$)".to_string())]);
+        assert!(validator.validate(10));
+        assert!(validator.validate(1230));
+        assert!(!validator.validate(123));
+        assert!(ConstraintValidator::new(&[Constraint::Custom("checksum_ok".to_string())]).validate(5));
+    }
 }
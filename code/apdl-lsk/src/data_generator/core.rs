@@ -3,11 +3,12 @@
 //! 提供基于协议模型定义的数据生成功能
 
 use apdl_core::{Constraint, LengthUnit, SyntaxUnit, UnitType};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use super::constraints::ConstraintHandler;
 use super::strategies::{
-    BoundaryValueStrategy, GenerationStrategy, RandomStrategy, SequentialStrategy,
+    BoundaryValueStrategy, CombinatorialMode, GenerationStrategy, RandomStrategy,
+    SequentialStrategy,
 };
 
 /// 数据生成器
@@ -194,9 +195,10 @@ impl DataGenerator {
                 let value = self.random_strategy.generate_bits(*bits as usize);
                 self.u64_to_bytes(value, length)
             }
-            UnitType::RawData | UnitType::Ip6Addr => {
-                self.random_strategy.generate_bytes(length)
-            }
+            UnitType::RawData
+            | UnitType::Ip6Addr
+            | UnitType::CucTime { .. }
+            | UnitType::CdsTime { .. } => self.random_strategy.generate_bytes(length),
         }
     }
 
@@ -218,7 +220,10 @@ impl DataGenerator {
                 let value = strategy.next() & ((1u64 << (*bits as usize)) - 1);
                 self.u64_to_bytes(value, length)
             }
-            UnitType::RawData | UnitType::Ip6Addr => strategy.generate_bytes(length),
+            UnitType::RawData
+            | UnitType::Ip6Addr
+            | UnitType::CucTime { .. }
+            | UnitType::CdsTime { .. } => strategy.generate_bytes(length),
         }
     }
 
@@ -247,7 +252,10 @@ impl DataGenerator {
                 let value = strategy.next();
                 self.u64_to_bytes(value, length)
             }
-            UnitType::RawData | UnitType::Ip6Addr => {
+            UnitType::RawData
+            | UnitType::Ip6Addr
+            | UnitType::CucTime { .. }
+            | UnitType::CdsTime { .. } => {
                 self.boundary_strategy.generate_bytes(length)
             }
         }
@@ -305,6 +313,154 @@ impl DataGenerator {
         (0..count).map(|_| self.generate_frame()).collect()
     }
 
+    /// 生成覆盖边界值和枚举值的字段组合
+    ///
+    /// 各字段的候选值取自约束：枚举值、固定值、范围两端及其相邻值；
+    /// 无约束的整数字段取0、1、最大值-1和最大值，其他类型取全0和全0xFF。
+    /// 成对模式按贪心方式从全组合中挑选覆盖最多未覆盖取值对的组合，适用于小字段集合
+    ///
+    /// # 参数
+    /// - `fields`: 参与组合的字段名，不存在的字段被忽略
+    /// - `mode`: 组合模式
+    ///
+    /// # 返回
+    /// 组合列表，每个组合为字段名到字段值的映射
+    pub fn generate_combinatorial(
+        &self,
+        fields: &[String],
+        mode: CombinatorialMode,
+    ) -> Vec<HashMap<String, Vec<u8>>> {
+        let units: Vec<&SyntaxUnit> = fields
+            .iter()
+            .filter_map(|name| self.model.get(name))
+            .collect();
+        if units.is_empty() {
+            return Vec::new();
+        }
+        let candidates: Vec<Vec<Vec<u8>>> = units
+            .iter()
+            .map(|unit| self.candidate_values(unit))
+            .collect();
+
+        let full = Self::cartesian_indices(&candidates);
+        let rows = match mode {
+            CombinatorialMode::Full => full,
+            CombinatorialMode::Pairwise if units.len() < 2 => full,
+            CombinatorialMode::Pairwise => Self::pairwise_cover(&candidates, full),
+        };
+
+        rows.into_iter()
+            .map(|row| {
+                units
+                    .iter()
+                    .zip(row)
+                    .enumerate()
+                    .map(|(field, (unit, index))| {
+                        (unit.field_id.clone(), candidates[field][index].clone())
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// 获取字段参与组合的候选值（去重，保持顺序）
+    fn candidate_values(&self, unit: &SyntaxUnit) -> Vec<Vec<u8>> {
+        let length = self.calculate_length(unit);
+        let bits = match unit.unit_type {
+            UnitType::Uint(bits) | UnitType::Bit(bits) => Some((bits as usize).min(64)),
+            _ => None,
+        };
+        let max_value = bits.map(|bits| {
+            if bits >= 64 {
+                u64::MAX
+            } else {
+                (1u64 << bits) - 1
+            }
+        });
+
+        let values: Vec<u64> = match (&unit.constraint, max_value) {
+            (Some(Constraint::Enum(entries)), _) => entries.iter().map(|(_, v)| *v).collect(),
+            (Some(Constraint::FixedValue(value)), _) => vec![*value],
+            (Some(Constraint::Range(min, max)), _) => vec![
+                *min,
+                min.saturating_add(1).min(*max),
+                max.saturating_sub(1).max(*min),
+                *max,
+            ],
+            (_, Some(max)) => vec![0, 1.min(max), max.saturating_sub(1), max],
+            (_, None) => return vec![vec![0x00; length], vec![0xFF; length]],
+        };
+
+        let mut seen = HashSet::new();
+        values
+            .into_iter()
+            .filter(|value| seen.insert(*value))
+            .map(|value| self.u64_to_bytes(value, length))
+            .collect()
+    }
+
+    /// 枚举所有候选值下标组合（第一个字段变化最慢）
+    fn cartesian_indices(candidates: &[Vec<Vec<u8>>]) -> Vec<Vec<usize>> {
+        candidates.iter().fold(vec![Vec::new()], |rows, values| {
+            rows.iter()
+                .flat_map(|row| {
+                    (0..values.len()).map(move |index| {
+                        let mut row = row.clone();
+                        row.push(index);
+                        row
+                    })
+                })
+                .collect()
+        })
+    }
+
+    /// 从全组合中贪心挑选组合直到覆盖所有字段取值对
+    fn pairwise_cover(candidates: &[Vec<Vec<u8>>], full: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+        let row_pairs = |row: &[usize]| {
+            let mut pairs = Vec::new();
+            for i in 0..row.len() {
+                for j in i + 1..row.len() {
+                    pairs.push((i, row[i], j, row[j]));
+                }
+            }
+            pairs
+        };
+
+        let mut uncovered: HashSet<(usize, usize, usize, usize)> = HashSet::new();
+        for i in 0..candidates.len() {
+            for j in i + 1..candidates.len() {
+                for a in 0..candidates[i].len() {
+                    for b in 0..candidates[j].len() {
+                        uncovered.insert((i, a, j, b));
+                    }
+                }
+            }
+        }
+
+        let mut selected = Vec::new();
+        while !uncovered.is_empty() {
+            // 取第一个覆盖数最多的组合，保证结果确定
+            let mut best: Option<(&Vec<usize>, usize)> = None;
+            for row in &full {
+                let gain = row_pairs(row)
+                    .iter()
+                    .filter(|pair| uncovered.contains(pair))
+                    .count();
+                if best.is_none_or(|(_, best_gain)| gain > best_gain) {
+                    best = Some((row, gain));
+                }
+            }
+            let Some((row, _)) = best else {
+                break;
+            };
+            for pair in row_pairs(row) {
+                uncovered.remove(&pair);
+            }
+            selected.push(row.clone());
+        }
+        selected
+    }
+
     /// 重置生成器状态
     pub fn reset(&mut self) {
         self.sequential_strategies.clear();
@@ -343,6 +499,8 @@ mod tests {
             associate: vec![],
             desc: "Test field".to_string(),
             pack_unpack_spec: None,
+            unit_label: None,
+            long_description: None,
         }
     }
 
@@ -464,4 +622,75 @@ mod tests {
         // 相同种子应该生成相同数据
         assert_eq!(data1, data2);
     }
+
+    fn enum_field(field_id: &str, values: &[u64]) -> SyntaxUnit {
+        let mut unit = create_test_syntax_unit(field_id, UnitType::Uint(8), 1);
+        unit.constraint = Some(Constraint::Enum(
+            values.iter().map(|v| (format!("v{v}"), *v)).collect(),
+        ));
+        unit
+    }
+
+    #[test]
+    fn test_generate_combinatorial_pairwise() {
+        let units = vec![
+            enum_field("mode", &[1, 2]),
+            enum_field("rate", &[10, 20]),
+            enum_field("band", &[0xA, 0xB]),
+            create_test_syntax_unit("payload", UnitType::Uint(8), 1),
+        ];
+        let generator = DataGenerator::new(&units);
+        let fields: Vec<String> = ["mode", "rate", "band"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let full = generator.generate_combinatorial(&fields, CombinatorialMode::Full);
+        assert_eq!(full.len(), 8);
+
+        // 3个二值字段的成对覆盖只需4个组合
+        let pairwise = generator.generate_combinatorial(&fields, CombinatorialMode::Pairwise);
+        assert_eq!(pairwise.len(), 4);
+        for i in 0..fields.len() {
+            for j in i + 1..fields.len() {
+                let pairs: HashSet<(Vec<u8>, Vec<u8>)> = pairwise
+                    .iter()
+                    .map(|row| (row[&fields[i]].clone(), row[&fields[j]].clone()))
+                    .collect();
+                assert_eq!(pairs.len(), 4, "{} x {}", fields[i], fields[j]);
+            }
+        }
+        assert!(pairwise.iter().all(|row| row.len() == 3));
+    }
+
+    #[test]
+    fn test_generate_combinatorial_boundaries() {
+        let mut level = create_test_syntax_unit("level", UnitType::Uint(16), 2);
+        level.constraint = Some(Constraint::Range(100, 200));
+        let units = vec![
+            enum_field("mode", &[1, 2, 3]),
+            enum_field("rate", &[10, 20, 30]),
+            level,
+            create_test_syntax_unit("flag", UnitType::Bit(1), 1),
+        ];
+        let generator = DataGenerator::new(&units);
+
+        let fields = vec!["level".to_string(), "flag".to_string()];
+        let full = generator.generate_combinatorial(&fields, CombinatorialMode::Full);
+        let levels: Vec<&Vec<u8>> = full.iter().step_by(2).map(|row| &row["level"]).collect();
+        assert_eq!(
+            levels,
+            vec![&vec![0, 100], &vec![0, 101], &vec![0, 199], &vec![0, 200]]
+        );
+        assert_eq!(full[0]["flag"], vec![0]);
+        assert_eq!(full[1]["flag"], vec![1]);
+
+        let fields = vec![
+            "mode".to_string(),
+            "rate".to_string(),
+            "missing".to_string(),
+        ];
+        let pairwise = generator.generate_combinatorial(&fields, CombinatorialMode::Pairwise);
+        assert_eq!(pairwise.len(), 9);
+    }
 }
@@ -1,7 +1,8 @@
 //! 自定义数据导入模块
 //!
-//! 支持从多种格式导入实际业务数据：二进制文件、十六进制字符串、文本数据
+//! 支持从多种格式导入实际业务数据：二进制文件、十六进制字符串、文本数据、字段值CSV
 
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
@@ -150,6 +151,89 @@ impl DataImporter {
         })
     }
 
+    /// 从CSV文件导入字段值
+    ///
+    /// 每行格式为`field_name,hex_value`，十六进制值可带0x前缀；
+    /// 空行和以`#`开头的注释行被跳过，同名字段以后出现的值为准
+    ///
+    /// # 参数
+    /// - `path`: CSV文件路径
+    ///
+    /// # 返回
+    /// - `Ok(HashMap<String, Vec<u8>>)`: 字段名到字段值的映射
+    /// - `Err(ImportError)`: 文件读取失败或某行格式错误（错误信息包含行号）
+    ///
+    /// # 示例
+    /// ```
+    /// use apdl_lsk::data_generator::DataImporter;
+    ///
+    /// // let values = DataImporter::from_csv("field_values.csv").unwrap();
+    /// ```
+    pub fn from_csv<P: AsRef<Path>>(path: P) -> Result<HashMap<String, Vec<u8>>, ImportError> {
+        let content = fs::read_to_string(&path).map_err(|e| {
+            ImportError::FileError(format!("无法读取文件 '{}': {}", path.as_ref().display(), e))
+        })?;
+        Self::from_csv_str(&content)
+    }
+
+    /// 从CSV文本导入字段值，格式同`from_csv`
+    ///
+    /// # 示例
+    /// ```
+    /// use apdl_lsk::data_generator::DataImporter;
+    ///
+    /// let values = DataImporter::from_csv_str("# 帧头\napid,0x0123\n").unwrap();
+    /// assert_eq!(values["apid"], vec![0x01, 0x23]);
+    /// ```
+    pub fn from_csv_str(content: &str) -> Result<HashMap<String, Vec<u8>>, ImportError> {
+        let mut values = HashMap::new();
+        for (index, line) in content.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((field_name, hex_value)) = line.split_once(',') else {
+                return Err(ImportError::ParseError(format!(
+                    "第{}行: 缺少逗号分隔的字段名和十六进制值",
+                    line_number
+                )));
+            };
+            let field_name = field_name.trim();
+            if field_name.is_empty() {
+                return Err(ImportError::ParseError(format!(
+                    "第{}行: 字段名为空",
+                    line_number
+                )));
+            }
+
+            let hex_value = hex_value.trim();
+            let hex_digits = hex_value
+                .strip_prefix("0x")
+                .or_else(|| hex_value.strip_prefix("0X"))
+                .unwrap_or(hex_value);
+            // hex_to_bytes按两个字符一组切片，先检查字符和长度
+            if hex_digits.is_empty()
+                || hex_digits.len() % 2 != 0
+                || !hex_digits.chars().all(|c| c.is_ascii_hexdigit())
+            {
+                return Err(ImportError::ParseError(format!(
+                    "第{}行: 字段 '{}' 的值 '{}' 不是有效的十六进制字节串",
+                    line_number, field_name, hex_value
+                )));
+            }
+            let bytes = apdl_core::utils::hex_to_bytes(hex_digits).map_err(|e| {
+                ImportError::ParseError(format!(
+                    "第{}行: 字段 '{}' 的值 '{}' 解析失败: {}",
+                    line_number, field_name, hex_value, e
+                ))
+            })?;
+            values.insert(field_name.to_string(), bytes);
+        }
+        Ok(values)
+    }
+
     /// 调整数据长度（截断或填充）
     ///
     /// # 参数
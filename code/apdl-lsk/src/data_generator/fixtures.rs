@@ -0,0 +1,260 @@
+//! 协议基准样例模块
+//!
+//! 提供CCSDS空间包和CAN 2.0帧的标准样例：包定义、字段取值及逐字节确定的期望帧，
+//! 用于组装与解析的回归测试
+
+use apdl_core::{
+    AlgorithmAst, ChecksumAlgorithm, Constraint, LayerDefinition, LengthDesc, LengthUnit,
+    PackageDefinition, ScopeDesc, SemanticRule, SyntaxUnit, UnitType,
+};
+use std::collections::HashMap;
+
+/// 基准样例：（包定义，需设置的字段值，期望帧）
+///
+/// 字段值不包含由长度规则和校验和规则计算的字段
+pub type ProtocolFixture = (PackageDefinition, HashMap<String, Vec<u8>>, Vec<u8>);
+
+/// CCSDS空间包样例
+///
+/// 主导头6字节（APID 0x123、独立包、序列计数1、包数据长度7）、
+/// 6字节应用数据和覆盖主导头及应用数据的CRC-16/CCITT-FALSE包差错控制字段
+pub fn ccsds_space_packet() -> ProtocolFixture {
+    let layer = "network";
+    let units = vec![
+        bit_field(
+            layer,
+            "pkt_version",
+            3,
+            Some(Constraint::FixedValue(0)),
+            "Packet Version Number",
+        ),
+        bit_field(
+            layer,
+            "pkt_type",
+            1,
+            Some(Constraint::Range(0, 1)),
+            "Packet Type",
+        ),
+        bit_field(
+            layer,
+            "sec_hdr_flag",
+            1,
+            Some(Constraint::Range(0, 1)),
+            "Secondary Header Flag",
+        ),
+        bit_field(layer, "apid", 11, None, "Application Process Identifier"),
+        bit_field(layer, "seq_flags", 2, None, "Sequence Flags"),
+        bit_field(layer, "pkt_seq_cnt", 14, None, "Packet Sequence Count"),
+        byte_field(
+            layer,
+            "pkt_len",
+            UnitType::Uint(16),
+            2,
+            "Packet Data Length",
+        ),
+        byte_field(layer, "pkt_data", UnitType::RawData, 6, "User Data Field"),
+        SyntaxUnit {
+            alg: Some(AlgorithmAst::Crc16),
+            ..byte_field(
+                layer,
+                "pkt_crc",
+                UnitType::Uint(16),
+                2,
+                "Packet Error Control",
+            )
+        },
+    ];
+    let rules = vec![
+        SemanticRule::LengthRule {
+            field_name: "pkt_len".to_string(),
+            expression: "len(pkt_data) + len(pkt_crc) - 1".to_string(),
+        },
+        SemanticRule::ChecksumRange {
+            algorithm: ChecksumAlgorithm::CRC16,
+            start_field: "pkt_version".to_string(),
+            end_field: "pkt_data".to_string(),
+            covers_self: true,
+        },
+    ];
+
+    let values = field_values(&[
+        ("pkt_version", &[0x00]),
+        ("pkt_type", &[0x00]),
+        ("sec_hdr_flag", &[0x00]),
+        ("apid", &[0x01, 0x23]),
+        ("seq_flags", &[0x03]),
+        ("pkt_seq_cnt", &[0x00, 0x01]),
+        ("pkt_data", &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06]),
+    ]);
+    let expected_frame = vec![
+        0x01, 0x23, // 版本0、类型0、无副导头、APID 0x123
+        0xC0, 0x01, // 独立包、序列计数1
+        0x00, 0x07, // 包数据长度：数据域8字节减1
+        0x01, 0x02, 0x03, 0x04, 0x05, 0x06, // 应用数据
+        0xE8, 0x49, // CRC-16/CCITT-FALSE
+    ];
+
+    (
+        package(
+            "ccsds_space_packet",
+            "CCSDS Space Packet",
+            "telemetry",
+            layer,
+            units,
+            rules,
+        ),
+        values,
+        expected_frame,
+    )
+}
+
+/// CAN 2.0标准帧样例
+///
+/// 仲裁段和控制段按字节对齐表示（11位标识符0x123、RTR、4位DLC），省略SOF、IDE、r0和位填充；
+/// 4字节数据和覆盖标识符到数据的CRC15
+pub fn can_frame() -> ProtocolFixture {
+    let layer = "data_link";
+    let units = vec![
+        bit_field(layer, "can_id", 11, None, "Identifier"),
+        bit_field(
+            layer,
+            "rtr",
+            1,
+            Some(Constraint::Range(0, 1)),
+            "Remote Transmission Request",
+        ),
+        bit_field(
+            layer,
+            "dlc",
+            4,
+            Some(Constraint::Range(0, 8)),
+            "Data Length Code",
+        ),
+        byte_field(layer, "can_data", UnitType::RawData, 4, "Data Field"),
+        SyntaxUnit {
+            alg: Some(AlgorithmAst::Crc15),
+            ..byte_field(layer, "can_crc", UnitType::Uint(16), 2, "CRC Sequence")
+        },
+    ];
+    let rules = vec![SemanticRule::ChecksumRange {
+        algorithm: ChecksumAlgorithm::CRC15,
+        start_field: "can_id".to_string(),
+        end_field: "can_data".to_string(),
+        covers_self: true,
+    }];
+
+    let values = field_values(&[
+        ("can_id", &[0x01, 0x23]),
+        ("rtr", &[0x00]),
+        ("dlc", &[0x04]),
+        ("can_data", &[0xDE, 0xAD, 0xBE, 0xEF]),
+    ]);
+    let expected_frame = vec![
+        0x24, 0x64, // 标识符0x123、数据帧、DLC 4
+        0xDE, 0xAD, 0xBE, 0xEF, // 数据
+        0x14, 0x01, // CRC15
+    ];
+
+    (
+        package(
+            "can_2_0_frame",
+            "CAN 2.0 Frame",
+            "command",
+            layer,
+            units,
+            rules,
+        ),
+        values,
+        expected_frame,
+    )
+}
+
+fn package(
+    name: &str,
+    display_name: &str,
+    package_type: &str,
+    layer: &str,
+    units: Vec<SyntaxUnit>,
+    rules: Vec<SemanticRule>,
+) -> PackageDefinition {
+    PackageDefinition {
+        name: name.to_string(),
+        display_name: display_name.to_string(),
+        package_type: package_type.to_string(),
+        layers: vec![LayerDefinition {
+            name: layer.to_string(),
+            units,
+            rules,
+        }],
+        description: format!("{display_name} reference fixture"),
+        pack_unpack_spec: None,
+    }
+}
+
+fn bit_field(
+    layer: &str,
+    field_id: &str,
+    bits: u8,
+    constraint: Option<Constraint>,
+    desc: &str,
+) -> SyntaxUnit {
+    SyntaxUnit {
+        constraint,
+        length: LengthDesc {
+            size: bits as usize,
+            unit: LengthUnit::Bit,
+        },
+        ..byte_field(layer, field_id, UnitType::Bit(bits), 0, desc)
+    }
+}
+
+fn byte_field(
+    layer: &str,
+    field_id: &str,
+    unit_type: UnitType,
+    size: usize,
+    desc: &str,
+) -> SyntaxUnit {
+    SyntaxUnit {
+        desc: desc.to_string(),
+        ..SyntaxUnit::new(
+            field_id,
+            unit_type,
+            LengthDesc {
+                size,
+                unit: LengthUnit::Byte,
+            },
+            ScopeDesc::Layer(layer.to_string()),
+        )
+    }
+}
+
+fn field_values(values: &[(&str, &[u8])]) -> HashMap<String, Vec<u8>> {
+    values
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_vec()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixture_values_cover_defined_fields() {
+        for (package, values, expected_frame) in [ccsds_space_packet(), can_frame()] {
+            let units = &package.layers[0].units;
+            assert!(values
+                .keys()
+                .all(|name| units.iter().any(|unit| &unit.field_id == name)));
+            let total_bits: usize = units
+                .iter()
+                .map(|unit| match unit.length.unit {
+                    LengthUnit::Bit => unit.length.size,
+                    _ => unit.length.size * 8,
+                })
+                .sum();
+            assert_eq!(total_bits, expected_frame.len() * 8);
+        }
+    }
+}
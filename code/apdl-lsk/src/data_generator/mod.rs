@@ -22,11 +22,12 @@
 pub mod constraints;
 pub mod core;
 pub mod custom_import;
+pub mod fixtures;
 pub mod strategies;
 pub mod test_helpers;
 
-pub use constraints::{ConstraintHandler, ConstraintValidator};
+pub use constraints::{ConstraintHandler, ConstraintValidator, ConstraintViolation};
 pub use core::DataGenerator;
 pub use custom_import::DataImporter;
-pub use strategies::{BoundaryValueStrategy, FixedStrategy, GenerationStrategy, RandomStrategy, SequentialStrategy};
+pub use strategies::{BoundaryValueStrategy, CombinatorialMode, FixedStrategy, GenerationStrategy, RandomStrategy, SequentialStrategy};
 pub use test_helpers::{patterns, TestDataGenerator};
@@ -2,6 +2,7 @@
 //!
 //! 提供多种数据生成策略：随机、顺序、固定值、边界值等
 
+use apdl_core::{Constraint, SyntaxUnit};
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
 
@@ -24,6 +25,15 @@ impl Default for GenerationStrategy {
     }
 }
 
+/// 组合生成模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombinatorialMode {
+    /// 成对覆盖：任意两个字段的每种取值组合至少出现一次
+    Pairwise,
+    /// 全组合：所有字段取值的笛卡尔积
+    Full,
+}
+
 /// 随机数据生成策略
 pub struct RandomStrategy {
     rng: StdRng,
@@ -252,6 +262,73 @@ impl BoundaryValueStrategy {
     pub fn reset(&mut self) {
         self.current_index = 0;
     }
+
+    /// 按字段约束生成全部边界值，每个值按字段宽度编码为大端字节
+    ///
+    /// - `Range(lo, hi)`: `lo`、`lo+1`、`hi-1`、`hi`及越界的`hi+1`
+    /// - `Enum`: 全部枚举值及一个不在枚举中的非法值
+    /// - `FixedValue(v)`: `v`及越界的`v+1`
+    /// - 无约束或自定义约束: 字段位宽的`0`、`1`、`max-1`、`max`
+    ///
+    /// 越界值超出字段位宽时无法表示，不会生成；动态长度字段返回空列表
+    pub fn generate_all_boundaries(field: &SyntaxUnit) -> Vec<Vec<u8>> {
+        let Some(bits) = field.bit_width() else {
+            return Vec::new();
+        };
+        let max_value = if bits >= 64 {
+            u64::MAX
+        } else {
+            (1u64 << bits) - 1
+        };
+
+        let candidates: Vec<Option<u64>> = match &field.constraint {
+            Some(Constraint::Range(lo, hi)) => vec![
+                Some(*lo),
+                lo.checked_add(1).filter(|v| v <= hi),
+                hi.checked_sub(1).filter(|v| v >= lo),
+                Some(*hi),
+                hi.checked_add(1),
+            ],
+            Some(Constraint::Enum(entries)) => {
+                let mut values: Vec<Option<u64>> = entries.iter().map(|(_, v)| Some(*v)).collect();
+                // 取最小的非枚举值作为非法值
+                values.push((0..=max_value).find(|v| entries.iter().all(|(_, e)| e != v)));
+                values
+            }
+            Some(Constraint::FixedValue(value)) => vec![Some(*value), value.checked_add(1)],
+            Some(Constraint::Custom(_)) | None => vec![
+                Some(0),
+                Some(1.min(max_value)),
+                Some(max_value.saturating_sub(1)),
+                Some(max_value),
+            ],
+        };
+
+        let length = (bits as usize).div_ceil(8);
+        let mut boundaries: Vec<Vec<u8>> = Vec::new();
+        for value in candidates.into_iter().flatten() {
+            if value > max_value {
+                continue;
+            }
+            let bytes = Self::encode_be(value, length);
+            if !boundaries.contains(&bytes) {
+                boundaries.push(bytes);
+            }
+        }
+        boundaries
+    }
+
+    /// 将值编码为指定长度的大端字节，超过8字节时高位补0
+    fn encode_be(value: u64, length: usize) -> Vec<u8> {
+        let be = value.to_be_bytes();
+        if length <= be.len() {
+            be[be.len() - length..].to_vec()
+        } else {
+            let mut bytes = vec![0u8; length - be.len()];
+            bytes.extend_from_slice(&be);
+            bytes
+        }
+    }
 }
 
 impl Default for BoundaryValueStrategy {
@@ -263,6 +340,8 @@ impl Default for BoundaryValueStrategy {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::data_generator::constraints::ConstraintValidator;
+    use apdl_core::{LengthDesc, LengthUnit, ScopeDesc, UnitType};
 
     #[test]
     fn test_random_strategy() {
@@ -318,6 +397,70 @@ mod tests {
         assert_eq!(strategy.next(), 0);
     }
 
+    fn constrained_field(unit_type: UnitType, bits: usize, constraint: Constraint) -> SyntaxUnit {
+        SyntaxUnit {
+            constraint: Some(constraint),
+            desc: "Field".to_string(),
+            ..SyntaxUnit::new(
+                "field",
+                unit_type,
+                LengthDesc {
+                    size: bits,
+                    unit: LengthUnit::Bit,
+                },
+                ScopeDesc::Global("test".to_string()),
+            )
+        }
+    }
+
+    #[test]
+    fn test_all_boundaries_for_range() {
+        let field = constrained_field(UnitType::Uint(16), 16, Constraint::Range(10, 1000));
+        let boundaries = BoundaryValueStrategy::generate_all_boundaries(&field);
+
+        let expected: Vec<Vec<u8>> = [10u16, 11, 999, 1000, 1001]
+            .iter()
+            .map(|v| v.to_be_bytes().to_vec())
+            .collect();
+        assert_eq!(boundaries, expected);
+
+        // 最后一个值越界，其余都应通过校验
+        let validator = ConstraintValidator::new(field.constraint.as_slice());
+        let valid: Vec<bool> = boundaries
+            .iter()
+            .map(|b| validator.validate(u16::from_be_bytes([b[0], b[1]]) as u64))
+            .collect();
+        assert_eq!(valid, vec![true, true, true, true, false]);
+    }
+
+    #[test]
+    fn test_all_boundaries_for_range_at_width_limit() {
+        // hi为位宽最大值时无法表示hi+1
+        let field = constrained_field(UnitType::Bit(3), 3, Constraint::Range(6, 7));
+        assert_eq!(
+            BoundaryValueStrategy::generate_all_boundaries(&field),
+            vec![vec![6], vec![7]]
+        );
+    }
+
+    #[test]
+    fn test_all_boundaries_for_enum() {
+        let field = constrained_field(
+            UnitType::Bit(4),
+            4,
+            Constraint::Enum(vec![
+                ("IDLE".to_string(), 0),
+                ("TM".to_string(), 1),
+                ("TC".to_string(), 3),
+            ]),
+        );
+        let boundaries = BoundaryValueStrategy::generate_all_boundaries(&field);
+
+        assert_eq!(boundaries, vec![vec![0], vec![1], vec![3], vec![2]]);
+        let validator = ConstraintValidator::new(field.constraint.as_slice());
+        assert!(!validator.validate(2));
+    }
+
     #[test]
     fn test_sequential_bytes() {
         let mut strategy = SequentialStrategy::with_start_and_step(0, 1);
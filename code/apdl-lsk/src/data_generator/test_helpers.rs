@@ -62,6 +62,8 @@ impl TestDataGenerator {
                 associate: vec![],
                 desc: "Generic data field".to_string(),
                 pack_unpack_spec: None,
+                unit_label: None,
+                long_description: None,
             },
             SyntaxUnit {
                 field_id: "sync_flag".to_string(),
@@ -77,6 +79,8 @@ impl TestDataGenerator {
                 associate: vec![],
                 desc: "Sync flag".to_string(),
                 pack_unpack_spec: None,
+                unit_label: None,
+                long_description: None,
             },
             SyntaxUnit {
                 field_id: "version".to_string(),
@@ -92,6 +96,8 @@ impl TestDataGenerator {
                 associate: vec![],
                 desc: "Version field".to_string(),
                 pack_unpack_spec: None,
+                unit_label: None,
+                long_description: None,
             },
             SyntaxUnit {
                 field_id: "payload".to_string(),
@@ -107,6 +113,8 @@ impl TestDataGenerator {
                 associate: vec![],
                 desc: "Payload data".to_string(),
                 pack_unpack_spec: None,
+                unit_label: None,
+                long_description: None,
             },
         ]
     }
@@ -8,4 +8,4 @@ pub mod reorder_buffer;
 
 pub use demultiplexer::{Demultiplexer, ChannelState};
 pub use sequence_validator::{SequenceValidator, ValidationResult};
-pub use reorder_buffer::ReorderBuffer;
+pub use reorder_buffer::{BufferedFrame, ReorderBuffer};
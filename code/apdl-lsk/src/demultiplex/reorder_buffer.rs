@@ -3,6 +3,16 @@
 //! 基于序列号对PDU进行排序，处理乱序接收的情况
 
 use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// 缓冲区输出的PDU及其序列号
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BufferedFrame {
+    /// 序列号
+    pub sequence: u32,
+    /// PDU数据
+    pub pdu: Vec<u8>,
+}
 
 /// 乱序重排缓冲区
 ///
@@ -18,6 +28,8 @@ pub struct ReorderBuffer {
     modulo: u32,
     /// 接收统计
     stats: ReorderStatistics,
+    /// 当前序列号空缺出现的时刻（缓冲区为空时为None）
+    gap_since: Option<Instant>,
 }
 
 /// 重排统计信息
@@ -33,6 +45,8 @@ pub struct ReorderStatistics {
     pub discarded: u64,
     /// 当前缓冲区大小
     pub buffer_size: usize,
+    /// 超时后跳过的缺失序列号数
+    pub skipped: u64,
 }
 
 impl ReorderBuffer {
@@ -56,6 +70,7 @@ impl ReorderBuffer {
             window_size,
             modulo,
             stats: ReorderStatistics::default(),
+            gap_since: None,
         }
     }
 
@@ -96,12 +111,17 @@ impl ReorderBuffer {
             self.next_expected = self.increment_sequence(self.next_expected);
 
             let mut result = vec![pdu];
-            result.extend(self.drain_ordered());
+            result.extend(
+                self.drain_ordered(Instant::now())
+                    .into_iter()
+                    .map(|frame| frame.pdu),
+            );
             result
         } else if self.is_in_window(sequence) {
             // 在窗口范围内，插入缓冲区
             self.buffer.insert(sequence, pdu);
             self.stats.buffer_size = self.buffer.len();
+            self.gap_since.get_or_insert_with(Instant::now);
 
             // 检查是否超出窗口大小限制
             if self.buffer.len() > self.window_size {
@@ -117,19 +137,72 @@ impl ReorderBuffer {
     }
 
     /// 提取所有连续的PDU
-    fn drain_ordered(&mut self) -> Vec<Vec<u8>> {
+    ///
+    /// 提取后缓冲区仍有PDU时，剩余的空缺从`now`开始计时
+    fn drain_ordered(&mut self, now: Instant) -> Vec<BufferedFrame> {
         let mut result = Vec::new();
 
         while let Some(pdu) = self.buffer.remove(&self.next_expected) {
-            result.push(pdu);
+            result.push(BufferedFrame {
+                sequence: self.next_expected,
+                pdu,
+            });
             self.stats.reordered_output += 1;
             self.next_expected = self.increment_sequence(self.next_expected);
         }
 
         self.stats.buffer_size = self.buffer.len();
+        self.gap_since = if self.buffer.is_empty() {
+            None
+        } else if result.is_empty() {
+            self.gap_since
+        } else {
+            Some(now)
+        };
         result
     }
 
+    /// 输出空缺超时后可以按序输出的PDU
+    ///
+    /// 空缺持续超过`timeout`时，将期望序列号推进到缓冲区中最近的序列号，
+    /// 跳过缺失的PDU并按序输出其后连续的PDU；未超时时返回空列表
+    ///
+    /// # 示例
+    /// ```
+    /// use apdl_lsk::demultiplex::ReorderBuffer;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let mut buffer = ReorderBuffer::new(16, 0x4000);
+    /// buffer.insert(0, vec![0x00]);
+    /// buffer.insert(2, vec![0x02]); // 序列号1丢失
+    ///
+    /// let timeout = Duration::from_millis(100);
+    /// assert!(buffer.poll_expired(Instant::now(), timeout).is_empty());
+    ///
+    /// let output = buffer.poll_expired(Instant::now() + timeout, timeout);
+    /// assert_eq!(output[0].sequence, 2);
+    /// ```
+    pub fn poll_expired(&mut self, now: Instant, timeout: Duration) -> Vec<BufferedFrame> {
+        let Some(gap_since) = self.gap_since else {
+            return vec![];
+        };
+        if now.saturating_duration_since(gap_since) < timeout {
+            return vec![];
+        }
+
+        let next_expected = self.next_expected;
+        let Some(&nearest) = self
+            .buffer
+            .keys()
+            .min_by_key(|&&sequence| self.calculate_distance(next_expected, sequence))
+        else {
+            return vec![];
+        };
+        self.stats.skipped += self.calculate_distance(next_expected, nearest) as u64;
+        self.next_expected = nearest;
+        self.drain_ordered(now)
+    }
+
     /// 检查序列号是否在窗口范围内
     fn is_in_window(&self, sequence: u32) -> bool {
         let distance = self.calculate_distance(self.next_expected, sequence);
@@ -171,6 +244,7 @@ impl ReorderBuffer {
         self.stats.reordered_output += result.len() as u64;
         self.buffer.clear();
         self.stats.buffer_size = 0;
+        self.gap_since = None;
         result
     }
 
@@ -179,6 +253,7 @@ impl ReorderBuffer {
         self.buffer.clear();
         self.next_expected = 0;
         self.stats = ReorderStatistics::default();
+        self.gap_since = None;
     }
 
     /// 设置下一个期望的序列号
@@ -386,4 +461,67 @@ mod tests {
         let rate = buffer.get_reorder_rate();
         assert!((rate - 0.2).abs() < 0.01);
     }
+
+    #[test]
+    fn test_poll_expired_skips_dropped_frame() {
+        let mut buffer = ReorderBuffer::new(16, 0x4000);
+        let timeout = Duration::from_millis(50);
+
+        buffer.insert(0, vec![0x00]);
+        // 序列号1丢失，2和3在缓冲区等待
+        assert!(buffer.insert(2, vec![0x02]).is_empty());
+        assert!(buffer.insert(3, vec![0x03]).is_empty());
+        let start = Instant::now();
+
+        // 未超时不输出
+        assert!(buffer.poll_expired(start, timeout).is_empty());
+        assert_eq!(buffer.buffer_size(), 2);
+
+        // 超时后跳过序列号1，按序输出2和3
+        let output = buffer.poll_expired(start + timeout, timeout);
+        assert_eq!(
+            output,
+            vec![
+                BufferedFrame {
+                    sequence: 2,
+                    pdu: vec![0x02]
+                },
+                BufferedFrame {
+                    sequence: 3,
+                    pdu: vec![0x03]
+                },
+            ]
+        );
+        assert_eq!(buffer.buffer_size(), 0);
+        assert_eq!(buffer.get_statistics().skipped, 1);
+
+        // 之后的PDU正常按序输出
+        assert_eq!(buffer.insert(4, vec![0x04]), vec![vec![0x04]]);
+        assert!(buffer
+            .poll_expired(start + timeout * 10, timeout)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_poll_expired_restarts_timer_for_next_gap() {
+        let mut buffer = ReorderBuffer::new(16, 0x4000);
+        let timeout = Duration::from_millis(50);
+
+        buffer.set_next_expected(0x3FFE);
+        // 0x3FFE和0x0000丢失，回绕前后各有一个空缺
+        buffer.insert(0x3FFF, vec![0xFF]);
+        buffer.insert(1, vec![0x01]);
+        let start = Instant::now();
+
+        let output = buffer.poll_expired(start + timeout, timeout);
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].sequence, 0x3FFF);
+
+        // 第二个空缺从上次输出时刻重新计时
+        assert!(buffer.poll_expired(start + timeout, timeout).is_empty());
+        let output = buffer.poll_expired(start + timeout * 2, timeout);
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].sequence, 1);
+        assert_eq!(buffer.get_statistics().skipped, 2);
+    }
 }
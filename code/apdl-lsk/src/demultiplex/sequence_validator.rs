@@ -11,10 +11,8 @@ pub enum ValidationResult {
     Ok,
     /// 检测到帧丢失，参数为丢失的帧数
     FrameLost(usize),
-    /// 检测到重复帧
+    /// 检测到重复帧（含落后于最后序列号、在重排窗口内的迟到帧）
     Duplicate,
-    /// 序列号回绕（正常）
-    Wraparound,
 }
 
 /// 序列号校验器
@@ -24,14 +22,17 @@ pub struct SequenceValidator {
     /// 各通道的最后序列号（channel_id -> last_sequence）
     last_sequence: HashMap<u16, u32>,
     /// 序列号模数（如CCSDS的14位序列号，模数为0x4000）
-    modulo: u32,
+    modulo: u64,
+    /// 重排窗口大小，默认为0（只有与最后序列号相同的帧判为重复帧）
+    reorder_window: u64,
 }
 
 impl SequenceValidator {
     /// 创建新的序列号校验器
     ///
     /// # 参数
-    /// - `modulo`: 序列号模数（如CCSDS 14位序列号为0x4000，即16384）
+    /// - `modulo`: 序列号模数，即计数器位宽对应的取值个数（如CCSDS 14位序列号为0x4000，
+    ///   32位计数器为`1 << 32`）
     ///
     /// # 示例
     /// ```
@@ -40,13 +41,34 @@ impl SequenceValidator {
     /// // CCSDS Space Packet使用14位序列号
     /// let validator = SequenceValidator::new(0x4000);
     /// ```
-    pub fn new(modulo: u32) -> Self {
+    pub fn new(modulo: u64) -> Self {
         Self {
             last_sequence: HashMap::new(),
-            modulo,
+            modulo: modulo.max(1),
+            reorder_window: 0,
         }
     }
 
+    /// 设置重排窗口
+    ///
+    /// 落后最后序列号不超过`window`的帧判为重复帧，不更新最后序列号；
+    /// 更远的后退按模运算视为前向跳变，判为帧丢失。窗口不超过模数的一半
+    ///
+    /// # 示例
+    /// ```
+    /// use apdl_lsk::demultiplex::{SequenceValidator, ValidationResult};
+    ///
+    /// // CAN应用层4位计数器
+    /// let mut validator = SequenceValidator::new(16).with_reorder_window(2);
+    /// validator.validate(0, 9);
+    /// assert_eq!(validator.validate(0, 7), ValidationResult::Duplicate);
+    /// assert_eq!(validator.validate(0, 6), ValidationResult::FrameLost(12));
+    /// ```
+    pub fn with_reorder_window(mut self, window: u64) -> Self {
+        self.reorder_window = window;
+        self
+    }
+
     /// 验证序列号
     ///
     /// # 参数
@@ -77,27 +99,21 @@ impl SequenceValidator {
     pub fn validate(&mut self, channel_id: u16, sequence: u32) -> ValidationResult {
         // 获取该通道的最后序列号
         if let Some(&last_seq) = self.last_sequence.get(&channel_id) {
-            // 计算期望的序列号
-            let expected = (last_seq + 1) % self.modulo;
+            // 按模运算计算当前序列号相对最后序列号的前向距离，期望值为(last + 1) % modulo
+            let forward = self.forward_distance(last_seq, sequence);
+            let backward = (self.modulo - forward) % self.modulo;
 
-            if sequence == expected {
-                // 序列号正常连续
+            if forward == 1 {
+                // 序列号正常连续（含从modulo-1回绕到0）
                 self.last_sequence.insert(channel_id, sequence);
                 ValidationResult::Ok
-            } else if sequence == last_seq {
-                // 重复帧
+            } else if forward == 0 || backward <= self.effective_reorder_window() {
+                // 重复帧或重排窗口内的迟到帧
                 ValidationResult::Duplicate
             } else {
-                // 检测到帧丢失，计算丢失的帧数
-                let lost = self.calculate_lost_count(last_seq, sequence);
+                // 检测到帧丢失
                 self.last_sequence.insert(channel_id, sequence);
-
-                if lost > 0 {
-                    ValidationResult::FrameLost(lost)
-                } else {
-                    // 序列号回绕
-                    ValidationResult::Wraparound
-                }
+                ValidationResult::FrameLost((forward - 1) as usize)
             }
         } else {
             // 第一次接收该通道的数据
@@ -106,26 +122,18 @@ impl SequenceValidator {
         }
     }
 
-    /// 计算丢失的帧数
-    ///
-    /// # 参数
-    /// - `last_seq`: 最后接收的序列号
-    /// - `current_seq`: 当前接收的序列号
+    /// 计算从最后序列号到当前序列号的前向距离（模运算）
     ///
-    /// # 返回
-    /// - 丢失的帧数
-    fn calculate_lost_count(&self, last_seq: u32, current_seq: u32) -> usize {
-        if current_seq > last_seq {
-            // 正常情况：current > last
-            (current_seq - last_seq - 1) as usize
-        } else {
-            // 序列号回绕：current < last
-            // 例如：last=0x3FFE, current=0x0002, modulo=0x4000
-            // lost = (0x4000 - 0x3FFE - 1) + 0x0002 = 1 + 2 = 3
-            let to_wrap = (self.modulo - last_seq - 1) as usize;
-            let after_wrap = current_seq as usize;
-            to_wrap + after_wrap
-        }
+    /// 例如：last=0x3FFE, current=0x0002, modulo=0x4000时距离为4，即丢失3帧
+    fn forward_distance(&self, last_seq: u32, current_seq: u32) -> u64 {
+        let last = last_seq as u64 % self.modulo;
+        let current = current_seq as u64 % self.modulo;
+        (current + self.modulo - last) % self.modulo
+    }
+
+    /// 实际生效的重排窗口，不超过模数的一半以保证前向丢帧可被识别
+    fn effective_reorder_window(&self) -> u64 {
+        self.reorder_window.min(self.modulo / 2)
     }
 
     /// 重置指定通道的序列号状态
@@ -308,4 +316,52 @@ mod tests {
             ValidationResult::Ok
         ));
     }
+
+    #[test]
+    fn test_wraparound_from_modulus_minus_one() {
+        // CAN应用层常见的4位计数器和32位计数器
+        for modulus in [16u64, 1 << 32] {
+            let mut validator = SequenceValidator::new(modulus);
+            let last = (modulus - 1) as u32;
+            validator.validate(0, last);
+            assert_eq!(validator.validate(0, 0), ValidationResult::Ok);
+            assert_eq!(validator.get_last_sequence(0), Some(0));
+
+            // 回绕处的丢帧按模运算计数
+            validator.validate(1, last - 1);
+            assert_eq!(validator.validate(1, 1), ValidationResult::FrameLost(2));
+        }
+    }
+
+    #[test]
+    fn test_small_gap_vs_large_backward_jump() {
+        let mut validator = SequenceValidator::new(0x4000).with_reorder_window(100);
+
+        validator.validate(0, 1000);
+        // 小跳变：丢失2帧
+        assert_eq!(validator.validate(0, 1003), ValidationResult::FrameLost(2));
+
+        // 后退60：在重排窗口内，判为迟到/重复帧，不计丢帧也不更新最后序列号
+        assert_eq!(validator.validate(0, 943), ValidationResult::Duplicate);
+        assert_eq!(validator.get_last_sequence(0), Some(1003));
+        assert_eq!(validator.validate(0, 1004), ValidationResult::Ok);
+
+        // 超出重排窗口的后退按模运算视为前向跳变
+        assert_eq!(
+            validator.validate(0, 804),
+            ValidationResult::FrameLost(0x4000 - 201)
+        );
+    }
+
+    #[test]
+    fn test_default_window_counts_backward_jump_as_loss() {
+        let mut validator = SequenceValidator::new(0x4000);
+
+        validator.validate(0, 1000);
+        assert_eq!(validator.validate(0, 1000), ValidationResult::Duplicate);
+        assert_eq!(
+            validator.validate(0, 999),
+            ValidationResult::FrameLost(0x4000 - 2)
+        );
+    }
 }
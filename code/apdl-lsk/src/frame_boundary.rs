@@ -0,0 +1,176 @@
+//! 帧边界识别模块
+//!
+//! 提供接收缓存和连续帧迭代器共用的帧长度确定策略
+
+use apdl_core::utils::find_pattern_offsets;
+use apdl_core::{ByteOrder, ProtocolError};
+
+/// 帧边界策略
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrameBoundary {
+    /// 固定帧长（字节）
+    Fixed(usize),
+    /// 帧内长度字段：帧长 = 长度字段值 + adjust
+    LengthField {
+        /// 长度字段相对帧起始的字节偏移
+        offset: usize,
+        /// 长度字段字节数（1~8）
+        width: usize,
+        /// 长度字段字节序
+        endian: ByteOrder,
+        /// 长度字段值到完整帧长的修正量（如未计入的头部长度）
+        adjust: i64,
+    },
+    /// 每帧以标志开头，帧延伸到下一个标志之前
+    Delimited {
+        /// 帧起始标志
+        marker: Vec<u8>,
+    },
+}
+
+impl FrameBoundary {
+    /// 根据从帧起始处开始的数据确定帧长度
+    ///
+    /// # 返回
+    /// - `Ok(Some(len))`: 帧长度（字节）
+    /// - `Ok(None)`: 数据不足以确定帧长度，分隔策略下表示尚未出现下一个标志
+    /// - `Err(ProtocolError)`: 长度字段定义或取值无效
+    pub fn frame_len(&self, data: &[u8]) -> Result<Option<usize>, ProtocolError> {
+        match self {
+            FrameBoundary::Fixed(size) => {
+                if *size == 0 {
+                    return Err(ProtocolError::InvalidFrameFormat(
+                        "Fixed frame length must be greater than 0".to_string(),
+                    ));
+                }
+                Ok(Some(*size))
+            }
+            FrameBoundary::LengthField {
+                offset,
+                width,
+                endian,
+                adjust,
+            } => {
+                if !(1..=8).contains(width) {
+                    return Err(ProtocolError::InvalidFrameFormat(format!(
+                        "Length field width {width} is not in 1..=8 bytes"
+                    )));
+                }
+                let Some(bytes) = data.get(*offset..offset + width) else {
+                    return Ok(None);
+                };
+
+                let fold = |acc: u64, &byte: &u8| (acc << 8) | byte as u64;
+                let value = match endian {
+                    ByteOrder::BigEndian => bytes.iter().fold(0u64, fold),
+                    ByteOrder::LittleEndian => bytes.iter().rev().fold(0u64, fold),
+                };
+
+                // 帧长至少覆盖长度字段本身
+                let frame_len = value as i128 + *adjust as i128;
+                if frame_len < (offset + width) as i128 || frame_len > usize::MAX as i128 {
+                    return Err(ProtocolError::InvalidFrameFormat(format!(
+                        "Length field value {value} with adjust {adjust} gives invalid frame length {frame_len}"
+                    )));
+                }
+                Ok(Some(frame_len as usize))
+            }
+            FrameBoundary::Delimited { marker } => {
+                if marker.is_empty() {
+                    return Err(ProtocolError::InvalidFrameFormat(
+                        "Frame delimiter must not be empty".to_string(),
+                    ));
+                }
+                let start = marker.len().min(data.len());
+                Ok(find_pattern_offsets(&data[start..], marker)
+                    .first()
+                    .map(|&relative| start + relative))
+            }
+        }
+    }
+
+    /// 将缓冲区切分为完整帧，返回各帧及剩余未成帧的字节数
+    pub fn split<'a>(&self, data: &'a [u8]) -> Result<(Vec<&'a [u8]>, usize), ProtocolError> {
+        let mut frames = Vec::new();
+        let mut offset = 0;
+        while offset < data.len() {
+            let rest = &data[offset..];
+            match self.frame_len(rest)? {
+                Some(len) if len <= rest.len() => {
+                    frames.push(&rest[..len]);
+                    offset += len;
+                }
+                _ => break,
+            }
+        }
+        Ok((frames, data.len() - offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_boundary_splits_frames() {
+        let data = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07];
+        let (frames, remaining) = FrameBoundary::Fixed(3).split(&data).unwrap();
+        assert_eq!(frames, vec![&data[0..3], &data[3..6]]);
+        assert_eq!(remaining, 1);
+
+        assert!(FrameBoundary::Fixed(0).frame_len(&data).is_err());
+    }
+
+    #[test]
+    fn test_length_field_boundary_splits_frames() {
+        // sync(2) + length(2，仅数据区长度) + data
+        let boundary = FrameBoundary::LengthField {
+            offset: 2,
+            width: 2,
+            endian: ByteOrder::BigEndian,
+            adjust: 4,
+        };
+        let mut data = vec![0xEB, 0x90, 0x00, 0x02, 0xAA, 0xBB];
+        data.extend_from_slice(&[0xEB, 0x90, 0x00, 0x03, 0x01, 0x02, 0x03]);
+        data.extend_from_slice(&[0xEB, 0x90, 0x00]);
+
+        let (frames, remaining) = boundary.split(&data).unwrap();
+        assert_eq!(frames, vec![&data[0..6], &data[6..13]]);
+        assert_eq!(remaining, 3);
+        // 长度字段不完整时无法确定帧长
+        assert_eq!(boundary.frame_len(&data[13..]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_length_field_little_endian_and_invalid_value() {
+        // 长度字段为小端序且包含整帧长度
+        let boundary = FrameBoundary::LengthField {
+            offset: 0,
+            width: 2,
+            endian: ByteOrder::LittleEndian,
+            adjust: 0,
+        };
+        let data = [0x04, 0x00, 0xAA, 0xBB, 0x03, 0x00, 0xCC];
+        let (frames, remaining) = boundary.split(&data).unwrap();
+        assert_eq!(frames, vec![&data[0..4], &data[4..7]]);
+        assert_eq!(remaining, 0);
+
+        // 帧长小于长度字段本身
+        assert!(boundary.frame_len(&[0x01, 0x00]).is_err());
+    }
+
+    #[test]
+    fn test_delimited_boundary_splits_frames() {
+        let boundary = FrameBoundary::Delimited {
+            marker: vec![0xEB, 0x90],
+        };
+        let mut data = vec![0xEB, 0x90, 0x01];
+        data.extend_from_slice(&[0xEB, 0x90, 0x02, 0x03]);
+        data.extend_from_slice(&[0xEB, 0x90, 0x04]);
+
+        let (frames, remaining) = boundary.split(&data).unwrap();
+        assert_eq!(frames, vec![&data[0..3], &data[3..7]]);
+        // 最后一帧之后尚未出现标志，帧是否完整未知
+        assert_eq!(remaining, 3);
+    }
+}
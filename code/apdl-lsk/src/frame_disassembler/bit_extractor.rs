@@ -2,7 +2,7 @@
 //!
 //! 提供精确的bit级字段提取功能，支持跨字节的bit字段
 
-use apdl_core::ProtocolError;
+use apdl_core::{BitNumbering, ByteOrder, ProtocolError};
 
 /// 从帧数据中提取bit字段值
 ///
@@ -50,18 +50,124 @@ pub fn extract_bit_field(
         )));
     }
 
-    // 读取涉及的所有字节并组合成一个大整数
-    let mut value = 0u64;
-    for &byte in &frame_data[start_byte..=end_byte] {
-        value = (value << 8) | (byte as u64);
-    }
+    // 读取涉及的所有字节并组合成一个大整数，64bit字段最多跨越9个字节
+    let value = frame_data[start_byte..=end_byte]
+        .iter()
+        .fold(0u128, |value, &byte| (value << 8) | byte as u128);
 
     // 计算需要右移的位数以提取目标bit范围
     let total_bits = (end_byte - start_byte + 1) * 8;
     let shift = total_bits - start_bit - bit_length;
-    let mask = (1u64 << bit_length) - 1;
+    let mask = u64::MAX >> (64 - bit_length);
+
+    Ok((value >> shift) as u64 & mask)
+}
+
+/// 按指定的位编号方式从帧数据中提取bit字段值
+///
+/// - `BitNumbering::Msb0`: 与`extract_bit_field`相同，bit偏移0为首字节最高位
+/// - `BitNumbering::Lsb0`: bit偏移0为首字节最低位，跨字节字段的低位在前面的字节中
+pub fn extract_bit_field_with_numbering(
+    frame_data: &[u8],
+    bit_offset: usize,
+    bit_length: usize,
+    numbering: BitNumbering,
+) -> Result<u64, ProtocolError> {
+    match numbering {
+        BitNumbering::Msb0 => extract_bit_field(frame_data, bit_offset, bit_length),
+        BitNumbering::Lsb0 => extract_bit_field_lsb0(frame_data, bit_offset, bit_length),
+    }
+}
+
+/// 按LSB-0编号提取bit字段值
+fn extract_bit_field_lsb0(
+    frame_data: &[u8],
+    bit_offset: usize,
+    bit_length: usize,
+) -> Result<u64, ProtocolError> {
+    if bit_length == 0 || bit_length > 64 {
+        return Err(ProtocolError::InvalidFrameFormat(format!(
+            "Invalid bit length: {bit_length}"
+        )));
+    }
+
+    let end_byte = (bit_offset + bit_length - 1) / 8;
+    if end_byte >= frame_data.len() {
+        return Err(ProtocolError::InvalidFrameFormat(format!(
+            "Bit field exceeds frame boundary: bit_offset={}, bit_length={}, frame_size={}",
+            bit_offset,
+            bit_length,
+            frame_data.len()
+        )));
+    }
+
+    let mut value = 0u64;
+    for i in 0..bit_length {
+        let pos = bit_offset + i;
+        let bit = (frame_data[pos / 8] >> (pos % 8)) & 0x01;
+        value |= (bit as u64) << i;
+    }
+
+    Ok(value)
+}
+
+/// 按CAN信号约定提取信号值
+///
+/// - `ByteOrder::LittleEndian`（Intel）: `start_bit`为信号最低位，按LSB-0编号向高位连续延伸
+/// - `ByteOrder::BigEndian`（Motorola）: `start_bit`为信号最高位，字节内向低位延伸，
+///   到达字节最低位后跳到下一字节的最高位
+///
+/// bit编号与DBC文件一致：字节`n`的第`k`位编号为`n * 8 + k`，`k = 0`为最低位
+pub fn extract_can_signal(
+    frame_data: &[u8],
+    start_bit: usize,
+    length: usize,
+    byte_order: ByteOrder,
+) -> Result<u64, ProtocolError> {
+    match byte_order {
+        ByteOrder::LittleEndian => extract_bit_field_lsb0(frame_data, start_bit, length),
+        ByteOrder::BigEndian => extract_can_signal_motorola(frame_data, start_bit, length),
+    }
+}
+
+/// 按Motorola字节序提取CAN信号值
+fn extract_can_signal_motorola(
+    frame_data: &[u8],
+    start_bit: usize,
+    length: usize,
+) -> Result<u64, ProtocolError> {
+    if length == 0 || length > 64 {
+        return Err(ProtocolError::InvalidFrameFormat(format!(
+            "Invalid bit length: {length}"
+        )));
+    }
 
-    Ok((value >> shift) & mask)
+    let mut value = 0u64;
+    let mut pos = start_bit;
+    for i in 0..length {
+        if pos / 8 >= frame_data.len() {
+            return Err(ProtocolError::InvalidFrameFormat(format!(
+                "CAN signal exceeds frame boundary: start_bit={}, length={}, frame_size={}",
+                start_bit,
+                length,
+                frame_data.len()
+            )));
+        }
+
+        let bit = (frame_data[pos / 8] >> (pos % 8)) & 0x01;
+        value = (value << 1) | bit as u64;
+
+        if i + 1 < length {
+            // 字节最低位之后接下一字节的最高位
+            pos = if pos.is_multiple_of(8) {
+                pos + 15
+            } else {
+                pos - 1
+            };
+        }
+    }
+
+    Ok(value)
 }
 
 /// 从字节数组中提取指定字节范围
@@ -216,6 +322,39 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_extract_bit_field_numbering() {
+        // 同一个物理字节 0xB4 = 1011_0100
+        let data = vec![0xB4];
+
+        // MSB-0: bit0-2为最高3位 101
+        let value = extract_bit_field_with_numbering(&data, 0, 3, BitNumbering::Msb0).unwrap();
+        assert_eq!(value, 0x05);
+
+        // LSB-0: bit0-2为最低3位 100
+        let value = extract_bit_field_with_numbering(&data, 0, 3, BitNumbering::Lsb0).unwrap();
+        assert_eq!(value, 0x04);
+
+        // bit3-7
+        let value = extract_bit_field_with_numbering(&data, 3, 5, BitNumbering::Msb0).unwrap();
+        assert_eq!(value, 0x14);
+        let value = extract_bit_field_with_numbering(&data, 3, 5, BitNumbering::Lsb0).unwrap();
+        assert_eq!(value, 0x16);
+    }
+
+    #[test]
+    fn test_extract_bit_field_lsb0_cross_bytes() {
+        // LSB-0跨字节：低位在前一字节的高位部分，高位在后一字节的低位部分
+        let data = vec![0xF0, 0x0A];
+
+        // bit4-11: 0xF0的高4位(1111)为低位，0x0A的低4位(1010)为高位
+        let value = extract_bit_field_with_numbering(&data, 4, 8, BitNumbering::Lsb0).unwrap();
+        assert_eq!(value, 0xAF);
+
+        let result = extract_bit_field_with_numbering(&data, 10, 8, BitNumbering::Lsb0);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_extract_byte_range() {
         let data = vec![0x01, 0x02, 0x03, 0x04, 0x05];
@@ -245,4 +384,34 @@ mod tests {
         let bytes = bit_value_to_bytes(0x245, 2);
         assert_eq!(bytes, vec![0x02, 0x45]);
     }
+
+    #[test]
+    fn test_extract_can_signal_intel() {
+        // 发动机转速信号：起始位24，长度16，Intel字节序，原始值0x1F40
+        let frame = [0x00, 0x00, 0x00, 0x40, 0x1F, 0x00, 0x00, 0x00];
+        let raw = extract_can_signal(&frame, 24, 16, ByteOrder::LittleEndian).unwrap();
+        assert_eq!(raw, 0x1F40);
+        assert_eq!(raw as f64 * 0.125, 1000.0);
+
+        // 非字节对齐：起始位4，长度12，跨越两个字节
+        let frame = [0x3F, 0xA2];
+        let raw = extract_can_signal(&frame, 4, 12, ByteOrder::LittleEndian).unwrap();
+        assert_eq!(raw, 0xA23);
+    }
+
+    #[test]
+    fn test_extract_can_signal_motorola() {
+        let frame = [0x3F, 0xA2];
+
+        // 起始位7为首字节最高位，长度16即大端序16位整数
+        let raw = extract_can_signal(&frame, 7, 16, ByteOrder::BigEndian).unwrap();
+        assert_eq!(raw, 0x3FA2);
+
+        // 起始位3，长度12：首字节低4位接第二字节8位
+        let raw = extract_can_signal(&frame, 3, 12, ByteOrder::BigEndian).unwrap();
+        assert_eq!(raw, 0xFA2);
+
+        let result = extract_can_signal(&frame, 7, 17, ByteOrder::BigEndian);
+        assert!(result.is_err());
+    }
 }
@@ -2,10 +2,17 @@
 //!
 //! 与FrameAssembler对称的拆包器，负责从二进制帧数据中提取字段
 
-use apdl_core::{LengthUnit, ProtocolError, SemanticRule, SyntaxUnit, UnitType};
+use apdl_core::utils::find_pattern_offsets;
+use apdl_core::{
+    BitNumbering, ByteOrder, Constraint, LengthUnit, ParsedField, ProtocolError, ScopeDesc,
+    SemanticRule, SyntaxUnit, UnitType,
+};
 use std::collections::HashMap;
 
-use super::bit_extractor::extract_bit_field;
+use super::bit_extractor::extract_bit_field_with_numbering;
+use super::tree::FieldGroup;
+use crate::data_generator::constraints::ConstraintValidator;
+use crate::layered_disassembler::ValidationError;
 
 /// 帧拆包器
 ///
@@ -18,6 +25,16 @@ pub struct FrameDisassembler {
     pub semantic_rules: Vec<SemanticRule>,
     /// 字段名到索引的映射
     pub field_index: HashMap<String, usize>,
+    /// 字节内位编号方式
+    pub bit_numbering: BitNumbering,
+    /// 解析前是否检查同步标志位于帧首且在负载中唯一
+    pub sync_marker_check: bool,
+    /// 字段分组定义，用于生成嵌套的解析树
+    pub groups: Vec<FieldGroup>,
+    /// 字段字节序（字段名 -> 字节序）
+    pub field_byte_orders: HashMap<String, ByteOrder>,
+    /// 层级默认字节序（层名 -> 字节序），字段未单独声明时使用
+    pub layer_byte_orders: HashMap<String, ByteOrder>,
 }
 
 impl Default for FrameDisassembler {
@@ -33,9 +50,105 @@ impl FrameDisassembler {
             fields: Vec::new(),
             semantic_rules: Vec::new(),
             field_index: HashMap::new(),
+            bit_numbering: BitNumbering::Msb0,
+            sync_marker_check: false,
+            groups: Vec::new(),
+            field_byte_orders: HashMap::new(),
+            layer_byte_orders: HashMap::new(),
         }
     }
 
+    /// 设置字节内位编号方式
+    pub fn set_bit_numbering(&mut self, numbering: BitNumbering) {
+        self.bit_numbering = numbering;
+    }
+
+    /// 设置字段字节序，与FrameAssembler的同名设置保持一致
+    pub fn set_field_byte_order(&mut self, field_name: &str, byte_order: ByteOrder) {
+        self.field_byte_orders
+            .insert(field_name.to_string(), byte_order);
+    }
+
+    /// 设置层内字段的默认字节序，字段级字节序优先
+    pub fn set_layer_byte_order(&mut self, layer: &str, byte_order: ByteOrder) {
+        self.layer_byte_orders.insert(layer.to_string(), byte_order);
+    }
+
+    /// 获取解析出的字段值的字节序
+    ///
+    /// bit字段的值已还原为大端字节；其余字段依次使用字段级字节序、打包规范、
+    /// 字段所在层（`layer(name)`范围）的默认字节序，最后为大端
+    pub fn field_byte_order(&self, field: &SyntaxUnit) -> ByteOrder {
+        if matches!(field.unit_type, UnitType::Bit(_)) {
+            return ByteOrder::BigEndian;
+        }
+        if let Some(&byte_order) = self.field_byte_orders.get(&field.field_id) {
+            return byte_order;
+        }
+        if let Some(spec) = &field.pack_unpack_spec {
+            return spec.byte_order;
+        }
+        match &field.scope {
+            ScopeDesc::Layer(layer) => self.layer_byte_orders.get(layer).copied(),
+            _ => None,
+        }
+        .unwrap_or(ByteOrder::BigEndian)
+    }
+
+    /// 设置是否在解析前检查同步标志
+    pub fn set_sync_marker_check(&mut self, enabled: bool) {
+        self.sync_marker_check = enabled;
+    }
+
+    /// 获取声明的同步标志字节
+    ///
+    /// 只认同步规则显式引用的字段，该字段需为带固定值约束的整字节字段；
+    /// 首字段恰好带固定值约束（如版本号）并不表示它是同步标志
+    pub fn declared_sync_marker(&self) -> Option<Vec<u8>> {
+        let sync_field = self.semantic_rules.iter().find_map(|rule| match rule {
+            SemanticRule::Synchronization { field_name, .. } => self
+                .field_index
+                .get(field_name)
+                .and_then(|&index| self.fields.get(index)),
+            _ => None,
+        })?;
+
+        let Some(Constraint::FixedValue(value)) = sync_field.constraint else {
+            return None;
+        };
+        let byte_size = match sync_field.length.unit {
+            LengthUnit::Byte => sync_field.length.size,
+            _ => return None,
+        };
+        if byte_size == 0 || byte_size > 8 {
+            return None;
+        }
+
+        Some(self.u64_to_bytes(value, byte_size))
+    }
+
+    /// 检查同步标志位于帧首，并扫描帧中其余位置意外出现的同步标志
+    ///
+    /// # 返回
+    /// - `Ok(Vec<usize>)`: 帧首之后出现同步标志的字节偏移（未声明同步标志时为空）
+    /// - `Err(ProtocolError)`: 帧首不是同步标志
+    pub fn check_sync_marker(&self, frame_data: &[u8]) -> Result<Vec<usize>, ProtocolError> {
+        let Some(marker) = self.declared_sync_marker() else {
+            return Ok(Vec::new());
+        };
+
+        if !frame_data.starts_with(&marker) {
+            return Err(ProtocolError::SynchronizationError(format!(
+                "Frame does not start with sync marker {marker:02X?}"
+            )));
+        }
+
+        Ok(find_pattern_offsets(frame_data, &marker)
+            .into_iter()
+            .filter(|&offset| offset > 0)
+            .collect())
+    }
+
     /// 添加字段定义
     pub fn add_field(&mut self, field: SyntaxUnit) {
         let field_name = field.field_id.clone();
@@ -61,17 +174,120 @@ impl FrameDisassembler {
         &self,
         frame_data: &[u8],
     ) -> Result<HashMap<String, Vec<u8>>, ProtocolError> {
-        let mut fields = HashMap::new();
+        let parsed = self.parse_frame_named(frame_data)?;
+        Ok(parsed
+            .into_iter()
+            .map(|field| (field.name, field.value))
+            .collect())
+    }
+
+    /// 解析帧数据并校验各字段约束
+    ///
+    /// 约束违反不会中断解析，全部收集后与字段值一同返回
+    ///
+    /// # 返回
+    /// - `Ok((Vec<ParsedField>, Vec<ValidationError>))`: 解析出的字段及约束违反记录
+    /// - `Err(ProtocolError)`: 解析错误
+    pub fn disassemble_frame_validated(
+        &self,
+        frame_data: &[u8],
+    ) -> Result<(Vec<ParsedField>, Vec<ValidationError>), ProtocolError> {
+        let parsed = self.parse_frame_named(frame_data)?;
+        let errors = self.validate_fields(&parsed);
+        Ok((parsed, errors))
+    }
+
+    /// 按字段约束（范围、固定值、枚举）校验已解析的字段
+    ///
+    /// 返回的错误层索引为0，由分层拆包器按实际层号改写
+    pub fn validate_fields(&self, parsed: &[ParsedField]) -> Vec<ValidationError> {
+        ConstraintValidator::default()
+            .validate_frame(parsed)
+            .into_iter()
+            .map(|violation| {
+                ValidationError::new(
+                    0,
+                    violation.field_name,
+                    format!(
+                        "expected {}, actual {} (0x{:X})",
+                        violation.expected, violation.actual, violation.actual
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    /// 按字段定义顺序解析帧数据
+    ///
+    /// # 返回
+    /// - `Ok(Vec<ParsedField>)`: 按顺序排列的字段名、字段值及其约束
+    /// - `Err(ProtocolError)`: 解析错误
+    pub fn parse_frame_named(&self, frame_data: &[u8]) -> Result<Vec<ParsedField>, ProtocolError> {
+        if self.sync_marker_check {
+            for offset in self.check_sync_marker(frame_data)? {
+                log::warn!("Sync marker found in payload at offset {offset}");
+            }
+        }
+
+        let (fields, _) = self.parse_fields(frame_data, None)?;
+        Ok(fields)
+    }
+
+    /// 解析帧数据直到指定字段（包含该字段），其余数据不解析
+    ///
+    /// 用于只需帧头字段即可做出路由判断的场景
+    ///
+    /// # 返回
+    /// - `Ok((Vec<ParsedField>, &[u8]))`: 已解析的字段及从下一个字节开始的剩余未解析数据
+    /// - `Err(ProtocolError)`: 字段不存在或解析错误
+    pub fn parse_until<'a>(
+        &self,
+        frame_data: &'a [u8],
+        field_name: &str,
+    ) -> Result<(Vec<ParsedField>, &'a [u8]), ProtocolError> {
+        if !self.field_index.contains_key(field_name) {
+            return Err(ProtocolError::FieldNotFound(field_name.to_string()));
+        }
+
+        let (fields, bit_offset) = self.parse_fields(frame_data, Some(field_name))?;
+        let remainder_start = bit_offset.div_ceil(8).min(frame_data.len());
+        Ok((fields, &frame_data[remainder_start..]))
+    }
+
+    /// 按字段定义顺序解析字段，`stop_after`指定时解析完该字段即停止
+    ///
+    /// 返回已解析的字段及解析结束时的bit偏移
+    fn parse_fields(
+        &self,
+        frame_data: &[u8],
+        stop_after: Option<&str>,
+    ) -> Result<(Vec<ParsedField>, usize), ProtocolError> {
+        let mut fields = Vec::with_capacity(self.fields.len());
         let mut bit_offset = 0usize; // 当前bit偏移
 
         for field in &self.fields {
             let field_name = &field.field_id;
+            // 跳过对齐填充字节：bit字段仅在字节边界处对齐，字节字段先对齐到字节边界
+            if !matches!(field.unit_type, UnitType::Bit(_)) || bit_offset.is_multiple_of(8) {
+                let byte_offset = bit_offset.div_ceil(8);
+                let padding =
+                    SemanticRule::alignment_padding(&self.semantic_rules, field_name, byte_offset);
+                if padding > 0 {
+                    bit_offset = (byte_offset + padding) * 8;
+                }
+            }
+            let mut field_start = bit_offset; // 字段起始bit偏移（字节对齐字段为对齐后的位置）
 
             // 根据字段类型提取值
             let value = match field.unit_type {
                 UnitType::Bit(bits) => {
                     // 提取bit字段
-                    let bit_value = extract_bit_field(frame_data, bit_offset, bits as usize)?;
+                    let bit_value = extract_bit_field_with_numbering(
+                        frame_data,
+                        bit_offset,
+                        bits as usize,
+                        self.field_bit_numbering(field),
+                    )?;
                     bit_offset += bits as usize;
 
                     // 将bit值转换为字节数组
@@ -88,15 +304,29 @@ impl FrameDisassembler {
                         )));
                     }
 
+                    field_start = byte_offset * 8;
                     let value = frame_data[byte_offset..byte_offset + byte_size].to_vec();
                     bit_offset = (byte_offset + byte_size) * 8;
                     value
                 }
                 UnitType::RawData => {
-                    // 动态长度数据字段，提取剩余所有数据
-                    let byte_offset = bit_offset.div_ceil(8);
-                    let value = frame_data[byte_offset..].to_vec();
-                    bit_offset = frame_data.len() * 8;
+                    // 声明了字节长度的数据字段按声明长度提取，动态长度数据字段提取剩余所有数据
+                    let byte_offset = bit_offset.div_ceil(8).min(frame_data.len());
+                    let size = field.length.size;
+                    let byte_end = match field.length.unit {
+                        LengthUnit::Byte if size > 0 => {
+                            if byte_offset + size > frame_data.len() {
+                                return Err(ProtocolError::InvalidFrameFormat(format!(
+                                    "Field {field_name} exceeds frame boundary"
+                                )));
+                            }
+                            byte_offset + size
+                        }
+                        _ => frame_data.len(),
+                    };
+                    field_start = byte_offset * 8;
+                    let value = frame_data[byte_offset..byte_end].to_vec();
+                    bit_offset = byte_end * 8;
                     value
                 }
                 UnitType::Ip6Addr => {
@@ -107,16 +337,47 @@ impl FrameDisassembler {
                             "IPv6 address field exceeds frame boundary".to_string(),
                         ));
                     }
+                    field_start = byte_offset * 8;
                     let value = frame_data[byte_offset..byte_offset + 16].to_vec();
                     bit_offset = (byte_offset + 16) * 8;
                     value
                 }
+                UnitType::CucTime { .. } | UnitType::CdsTime { .. } => {
+                    // 时间码字段，字节对齐且长度由格式决定
+                    let byte_offset = bit_offset.div_ceil(8);
+                    let byte_size = field.unit_type.fixed_byte_size().unwrap_or(0);
+                    if byte_offset + byte_size > frame_data.len() {
+                        return Err(ProtocolError::InvalidFrameFormat(format!(
+                            "Time code field {field_name} exceeds frame boundary"
+                        )));
+                    }
+                    field_start = byte_offset * 8;
+                    let value = frame_data[byte_offset..byte_offset + byte_size].to_vec();
+                    bit_offset = (byte_offset + byte_size) * 8;
+                    value
+                }
             };
 
-            fields.insert(field_name.clone(), value);
+            let raw_end = bit_offset.div_ceil(8).min(frame_data.len());
+            let raw_start = (field_start / 8).min(raw_end);
+            let byte_order = self.field_byte_order(field);
+            fields.push(ParsedField {
+                name: field_name.clone(),
+                decoded: field.render_value_with(&value, byte_order),
+                value,
+                byte_order,
+                constraint: field.constraint.clone(),
+                raw: frame_data[raw_start..raw_end].to_vec(),
+                bit_offset: field_start,
+                bit_len: bit_offset - field_start,
+            });
+
+            if stop_after == Some(field_name.as_str()) {
+                break;
+            }
         }
 
-        Ok(fields)
+        Ok((fields, bit_offset))
     }
 
     /// 提取单个字段值（支持bit字段）
@@ -140,7 +401,51 @@ impl FrameDisassembler {
         fields
             .get(field_name)
             .cloned()
-            .ok_or_else(|| ProtocolError::FieldNotFound(format!("Field not found: {field_name}")))
+            .ok_or_else(|| ProtocolError::FieldNotFound(field_name.to_string()))
+    }
+
+    /// 提取帧内任意bit范围，不依赖字段定义
+    ///
+    /// 按当前位编号方式读取`start_bit`起的`bit_count`个bit，结果右对齐为大端字节数组，
+    /// 占用`ceil(bit_count / 8)`字节
+    ///
+    /// # 返回
+    /// - `Ok(Vec<u8>)`: 提取的bit值
+    /// - `Err(ProtocolError)`: bit数为0或范围超出帧边界
+    pub fn extract_range(
+        &self,
+        data: &[u8],
+        start_bit: usize,
+        bit_count: usize,
+    ) -> Result<Vec<u8>, ProtocolError> {
+        let in_bounds = start_bit
+            .checked_add(bit_count)
+            .is_some_and(|end_bit| end_bit <= data.len() * 8);
+        if bit_count == 0 || !in_bounds {
+            return Err(ProtocolError::InvalidFrameFormat(format!(
+                "Bit range exceeds frame boundary: start_bit={}, bit_count={}, frame_size={}",
+                start_bit,
+                bit_count,
+                data.len()
+            )));
+        }
+
+        let bit_at = |pos: usize| match self.bit_numbering {
+            BitNumbering::Msb0 => (data[pos / 8] >> (7 - pos % 8)) & 0x01,
+            BitNumbering::Lsb0 => (data[pos / 8] >> (pos % 8)) & 0x01,
+        };
+
+        // 从结果最低位开始逐bit写入，MSB-0的最后一个bit和LSB-0的第一个bit为最低位
+        let mut bytes = vec![0u8; bit_count.div_ceil(8)];
+        let last = bytes.len() - 1;
+        for i in 0..bit_count {
+            let pos = match self.bit_numbering {
+                BitNumbering::Msb0 => start_bit + bit_count - 1 - i,
+                BitNumbering::Lsb0 => start_bit + i,
+            };
+            bytes[last - i / 8] |= bit_at(pos) << (i % 8);
+        }
+        Ok(bytes)
     }
 
     /// 获取字段的bit级位置
@@ -156,9 +461,7 @@ impl FrameDisassembler {
         field_name: &str,
     ) -> Result<(usize, usize), ProtocolError> {
         let Some(&field_index) = self.field_index.get(field_name) else {
-            return Err(ProtocolError::FieldNotFound(format!(
-                "Field not found: {field_name}"
-            )));
+            return Err(ProtocolError::FieldNotFound(field_name.to_string()));
         };
 
         let Some(field) = self.fields.get(field_index) else {
@@ -171,10 +474,12 @@ impl FrameDisassembler {
         let mut total_bits_before = 0usize;
         for i in 0..field_index {
             if let Some(prev_field) = self.fields.get(i) {
+                total_bits_before += self.alignment_padding_bits(prev_field, total_bits_before);
                 let field_bits = self.get_field_bit_length(prev_field)?;
                 total_bits_before += field_bits;
             }
         }
+        total_bits_before += self.alignment_padding_bits(field, total_bits_before);
 
         // 获取当前字段的bit长度
         let field_bit_length = self.get_field_bit_length(field)?;
@@ -182,6 +487,16 @@ impl FrameDisassembler {
         Ok((total_bits_before, field_bit_length))
     }
 
+    /// 获取字段位于给定bit偏移时对齐填充占用的bit数，未处于字节边界时不对齐
+    pub(crate) fn alignment_padding_bits(&self, field: &SyntaxUnit, bit_offset: usize) -> usize {
+        if bit_offset.is_multiple_of(8) {
+            SemanticRule::alignment_padding(&self.semantic_rules, &field.field_id, bit_offset / 8)
+                * 8
+        } else {
+            0
+        }
+    }
+
     /// 获取字段的bit长度
     fn get_field_bit_length(&self, field: &SyntaxUnit) -> Result<usize, ProtocolError> {
         match field.length.unit {
@@ -201,6 +516,16 @@ impl FrameDisassembler {
         }
     }
 
+    /// 获取bit字段的位编号方式
+    ///
+    /// 声明为小端序容器的bit字段按Intel（CAN）约定从低位开始打包，使用LSB-0编号
+    fn field_bit_numbering(&self, field: &SyntaxUnit) -> BitNumbering {
+        match &field.pack_unpack_spec {
+            Some(spec) if spec.byte_order == ByteOrder::LittleEndian => BitNumbering::Lsb0,
+            _ => self.bit_numbering,
+        }
+    }
+
     /// 将u64值转换为字节数组
     fn u64_to_bytes(&self, value: u64, size: usize) -> Vec<u8> {
         let mut bytes = Vec::new();
@@ -235,7 +560,7 @@ impl FrameDisassembler {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use apdl_core::{Constraint, CoverDesc, LengthDesc, ScopeDesc};
+    use apdl_core::{CoverDesc, LengthDesc, PackUnpackSpec, ScopeDesc};
 
     #[test]
     fn test_disassemble_simple_frame() {
@@ -254,6 +579,8 @@ mod tests {
             associate: vec![],
             desc: "Version".to_string(),
             pack_unpack_spec: None,
+            unit_label: None,
+            long_description: None,
         };
 
         let data_field = SyntaxUnit {
@@ -270,6 +597,8 @@ mod tests {
             associate: vec![],
             desc: "Data".to_string(),
             pack_unpack_spec: None,
+            unit_label: None,
+            long_description: None,
         };
 
         let mut disassembler = FrameDisassembler::new();
@@ -304,6 +633,8 @@ mod tests {
             associate: vec![],
             desc: "Version".to_string(),
             pack_unpack_spec: None,
+            unit_label: None,
+            long_description: None,
         };
 
         let type_field = SyntaxUnit {
@@ -320,6 +651,8 @@ mod tests {
             associate: vec![],
             desc: "Type".to_string(),
             pack_unpack_spec: None,
+            unit_label: None,
+            long_description: None,
         };
 
         let flag_field = SyntaxUnit {
@@ -336,6 +669,8 @@ mod tests {
             associate: vec![],
             desc: "Flag".to_string(),
             pack_unpack_spec: None,
+            unit_label: None,
+            long_description: None,
         };
 
         let apid_field = SyntaxUnit {
@@ -352,6 +687,8 @@ mod tests {
             associate: vec![],
             desc: "APID".to_string(),
             pack_unpack_spec: None,
+            unit_label: None,
+            long_description: None,
         };
 
         let mut disassembler = FrameDisassembler::new();
@@ -382,4 +719,362 @@ mod tests {
         let apid_value = ((apid[0] as u16) << 8) | (apid[1] as u16);
         assert_eq!(apid_value, 0x0245, "APID should be 0x0245");
     }
+
+    #[test]
+    fn test_disassemble_bit_numbering() {
+        let make_field = |name: &str, bits: u8| {
+            SyntaxUnit::new(
+                name,
+                UnitType::Bit(bits),
+                LengthDesc {
+                    size: bits as usize,
+                    unit: LengthUnit::Bit,
+                },
+                ScopeDesc::Global("test".to_string()),
+            )
+        };
+
+        let mut disassembler = FrameDisassembler::new();
+        disassembler.add_field(make_field("mode", 2));
+        disassembler.add_field(make_field("counter", 6));
+
+        // 0x9D = 1001_1101
+        let frame_data = vec![0x9D];
+
+        let msb0 = disassembler.disassemble_frame(&frame_data).unwrap();
+        assert_eq!(msb0.get("mode").unwrap(), &vec![0x02]);
+        assert_eq!(msb0.get("counter").unwrap(), &vec![0x1D]);
+
+        disassembler.set_bit_numbering(BitNumbering::Lsb0);
+        let lsb0 = disassembler.disassemble_frame(&frame_data).unwrap();
+        assert_eq!(lsb0.get("mode").unwrap(), &vec![0x01]);
+        assert_eq!(lsb0.get("counter").unwrap(), &vec![0x27]);
+    }
+
+    #[test]
+    fn test_sync_marker_in_payload() {
+        let make_field = |name: &str, unit_type: UnitType, length: LengthDesc| {
+            SyntaxUnit::new(
+                name,
+                unit_type,
+                length,
+                ScopeDesc::Global("test".to_string()),
+            )
+        };
+
+        let mut sync = make_field(
+            "sync",
+            UnitType::Uint(16),
+            LengthDesc {
+                size: 2,
+                unit: LengthUnit::Byte,
+            },
+        );
+        sync.constraint = Some(Constraint::FixedValue(0xEB90));
+
+        let mut disassembler = FrameDisassembler::new();
+        disassembler.add_field(sync);
+        disassembler.add_field(make_field(
+            "payload",
+            UnitType::RawData,
+            LengthDesc {
+                size: 0,
+                unit: LengthUnit::Dynamic,
+            },
+        ));
+        disassembler.set_sync_marker_check(true);
+        // 首字段带固定值约束但未被同步规则引用，不视为同步标志
+        assert_eq!(disassembler.declared_sync_marker(), None);
+        assert!(disassembler
+            .check_sync_marker(&[0x01, 0xEB, 0x90])
+            .unwrap()
+            .is_empty());
+
+        disassembler.add_semantic_rule(SemanticRule::Synchronization {
+            field_name: "sync".to_string(),
+            algorithm: "ccsds_sync".to_string(),
+            description: "Sync marker".to_string(),
+        });
+        assert_eq!(disassembler.declared_sync_marker(), Some(vec![0xEB, 0x90]));
+
+        // 负载中包含同步标志字节
+        let frame_data = vec![0xEB, 0x90, 0x01, 0xEB, 0x90, 0x02];
+        assert_eq!(
+            disassembler.check_sync_marker(&frame_data).unwrap(),
+            vec![3]
+        );
+        let fields = disassembler.disassemble_frame(&frame_data).unwrap();
+        assert_eq!(
+            fields.get("payload").unwrap(),
+            &vec![0x01, 0xEB, 0x90, 0x02]
+        );
+
+        // 负载中不含同步标志
+        assert!(disassembler
+            .check_sync_marker(&[0xEB, 0x90, 0x01, 0x02])
+            .unwrap()
+            .is_empty());
+
+        // 帧首不是同步标志
+        let result = disassembler.disassemble_frame(&[0x01, 0xEB, 0x90]);
+        assert!(matches!(
+            result,
+            Err(ProtocolError::SynchronizationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_frame_named_preserves_raw_bytes() {
+        let make_field = |name: &str, unit_type: UnitType, length: LengthDesc| {
+            SyntaxUnit::new(
+                name,
+                unit_type,
+                length,
+                ScopeDesc::Global("test".to_string()),
+            )
+        };
+        let bits = |size: usize| LengthDesc {
+            size,
+            unit: LengthUnit::Bit,
+        };
+
+        let mut disassembler = FrameDisassembler::new();
+        disassembler.add_field(make_field("version", UnitType::Bit(3), bits(3)));
+        disassembler.add_field(make_field("apid", UnitType::Bit(11), bits(11)));
+        disassembler.add_field(make_field("flags", UnitType::Bit(2), bits(2)));
+        disassembler.add_field(make_field(
+            "length",
+            UnitType::Uint(16),
+            LengthDesc {
+                size: 2,
+                unit: LengthUnit::Byte,
+            },
+        ));
+        disassembler.add_field(make_field(
+            "data",
+            UnitType::RawData,
+            LengthDesc {
+                size: 0,
+                unit: LengthUnit::Dynamic,
+            },
+        ));
+
+        let frame_data = vec![0x08, 0x17, 0x00, 0x03, 0xAA, 0xBB, 0xCC];
+        let parsed = disassembler.parse_frame_named(&frame_data).unwrap();
+
+        let positions: Vec<(usize, usize)> = parsed
+            .iter()
+            .map(|field| (field.bit_offset, field.bit_len))
+            .collect();
+        assert_eq!(
+            positions,
+            vec![(0, 3), (3, 11), (14, 2), (16, 16), (32, 24)]
+        );
+
+        // 原始字节与字段所跨越的输入切片一致
+        for field in &parsed {
+            let start = field.bit_offset / 8;
+            let end = (field.bit_offset + field.bit_len).div_ceil(8);
+            assert_eq!(field.raw, frame_data[start..end], "field {}", field.name);
+        }
+        assert_eq!(parsed[1].raw, vec![0x08, 0x17]);
+        assert_eq!(parsed[1].value, vec![0x02, 0x05]);
+        assert_eq!(parsed[3].raw, vec![0x00, 0x03]);
+        assert_eq!(parsed[4].raw, vec![0xAA, 0xBB, 0xCC]);
+    }
+
+    #[test]
+    fn test_parse_frame_named_renders_decoded_values() {
+        let make_field = |name: &str, unit_type: UnitType, size: usize| {
+            SyntaxUnit::new(
+                name,
+                unit_type,
+                LengthDesc {
+                    size,
+                    unit: LengthUnit::Byte,
+                },
+                ScopeDesc::Global("test".to_string()),
+            )
+        };
+
+        let mut disassembler = FrameDisassembler::new();
+        disassembler.add_field(make_field("u8", UnitType::Uint(8), 1));
+        disassembler.add_field(make_field("u16", UnitType::Uint(16), 2));
+        disassembler.add_field(make_field("u24", UnitType::Uint(24), 3));
+        let mut le32 = make_field("le32", UnitType::Uint(32), 4);
+        le32.pack_unpack_spec = Some(PackUnpackSpec {
+            byte_order: ByteOrder::LittleEndian,
+            ..Default::default()
+        });
+        disassembler.add_field(le32);
+        disassembler.add_field(make_field("u64", UnitType::Uint(64), 8));
+        let mut mode = make_field("mode", UnitType::Uint(8), 1);
+        mode.constraint = Some(Constraint::Enum(vec![
+            ("IDLE".to_string(), 0),
+            ("ACTIVE".to_string(), 2),
+        ]));
+        disassembler.add_field(mode);
+        disassembler.add_field(make_field("wide", UnitType::Uint(128), 16));
+        disassembler.add_field(make_field("data", UnitType::RawData, 0));
+
+        let mut frame_data = vec![0xFF, 0x12, 0x34, 0x01, 0x02, 0x03, 0x78, 0x56, 0x34, 0x12];
+        frame_data.extend_from_slice(&u64::MAX.to_be_bytes());
+        frame_data.push(0x02);
+        frame_data.extend(0x00..0x10);
+        frame_data.extend_from_slice(&[0xAA, 0x01]);
+
+        let parsed = disassembler.parse_frame_named(&frame_data).unwrap();
+        let decoded: Vec<&str> = parsed.iter().map(|field| field.decoded.as_str()).collect();
+        assert_eq!(
+            decoded,
+            vec![
+                "255",
+                "4660",
+                "66051",
+                "305419896",
+                "18446744073709551615",
+                "ACTIVE",
+                "00 01 02 03 04 05 06 07 08 09 0A 0B 0C 0D 0E 0F",
+                "AA 01",
+            ]
+        );
+        // 数值与渲染结果一致地按字段字节序解释
+        assert_eq!(parsed[3].byte_order, ByteOrder::LittleEndian);
+        assert_eq!(parsed[3].numeric_value(), Some(0x12345678));
+        assert_eq!(parsed[2].numeric_value(), Some(0x010203));
+        assert_eq!(parsed[6].numeric_value(), None);
+
+        // 未命中枚举值时显示数值，空字段不会出错
+        frame_data[18] = 0x05;
+        frame_data.truncate(35);
+        let parsed = disassembler.parse_frame_named(&frame_data).unwrap();
+        assert_eq!(parsed[5].decoded, "5");
+        assert_eq!(parsed[7].decoded, "");
+    }
+
+    #[test]
+    fn test_disassemble_little_endian_bit_group() {
+        // 两个bit字段打包在一个小端序16位容器中（CAN Intel字节序）
+        let make_field = |name: &str, bits: u8| SyntaxUnit {
+            pack_unpack_spec: Some(PackUnpackSpec {
+                byte_order: ByteOrder::LittleEndian,
+                ..Default::default()
+            }),
+            ..SyntaxUnit::new(
+                name,
+                UnitType::Bit(bits),
+                LengthDesc {
+                    size: bits as usize,
+                    unit: LengthUnit::Bit,
+                },
+                ScopeDesc::Global("test".to_string()),
+            )
+        };
+
+        let mut disassembler = FrameDisassembler::new();
+        disassembler.add_field(make_field("mode", 4));
+        disassembler.add_field(make_field("value", 12));
+
+        let frame_data = vec![0x3F, 0xA2];
+        let parsed = disassembler.parse_frame_named(&frame_data).unwrap();
+
+        // mode为起始位0、长度4的信号；value为起始位4、长度12的信号
+        assert_eq!(parsed[0].value, vec![0x0F]);
+        assert_eq!(parsed[1].value, vec![0x0A, 0x23]);
+        assert_eq!(
+            crate::frame_disassembler::extract_can_signal(
+                &frame_data,
+                4,
+                12,
+                ByteOrder::LittleEndian
+            )
+            .unwrap(),
+            0xA23
+        );
+    }
+
+    #[test]
+    fn test_parse_until_apid() {
+        let make_field = |name: &str, unit_type: UnitType, length: LengthDesc| {
+            SyntaxUnit::new(
+                name,
+                unit_type,
+                length,
+                ScopeDesc::Global("test".to_string()),
+            )
+        };
+        let bits = |size: usize| LengthDesc {
+            size,
+            unit: LengthUnit::Bit,
+        };
+
+        // CCSDS空间包主导头：version(3) + type(1) + sec_hdr(1) + apid(11) + ...
+        let mut disassembler = FrameDisassembler::new();
+        disassembler.add_field(make_field("version", UnitType::Bit(3), bits(3)));
+        disassembler.add_field(make_field("type", UnitType::Bit(1), bits(1)));
+        disassembler.add_field(make_field("sec_hdr", UnitType::Bit(1), bits(1)));
+        disassembler.add_field(make_field("apid", UnitType::Bit(11), bits(11)));
+        disassembler.add_field(make_field(
+            "length",
+            UnitType::Uint(16),
+            LengthDesc {
+                size: 2,
+                unit: LengthUnit::Byte,
+            },
+        ));
+        disassembler.add_field(make_field(
+            "data",
+            UnitType::RawData,
+            LengthDesc {
+                size: 0,
+                unit: LengthUnit::Dynamic,
+            },
+        ));
+
+        let frame_data = vec![0x0A, 0x45, 0x00, 0x02, 0xAA, 0xBB, 0xCC];
+        let (fields, remainder) = disassembler.parse_until(&frame_data, "apid").unwrap();
+
+        let names: Vec<&str> = fields.iter().map(|field| field.name.as_str()).collect();
+        assert_eq!(names, vec!["version", "type", "sec_hdr", "apid"]);
+        assert_eq!(fields[3].value, vec![0x02, 0x45]);
+        assert_eq!(remainder, &frame_data[2..]);
+
+        let result = disassembler.parse_until(&frame_data, "unknown");
+        assert!(matches!(result, Err(ProtocolError::FieldNotFound(_))));
+    }
+
+    #[test]
+    fn test_extract_range_straddles_byte_boundary() {
+        let disassembler = FrameDisassembler::new();
+        // 0x0A45 = 0000_1010_0100_0101，bit4~15为1010_0100_0101
+        let data = [0x0A, 0x45, 0xD2];
+        assert_eq!(
+            disassembler.extract_range(&data, 4, 12).unwrap(),
+            vec![0x0A, 0x45]
+        );
+        // bit6~17: 10_0100_0101_11
+        assert_eq!(
+            disassembler.extract_range(&data, 6, 12).unwrap(),
+            vec![0x09, 0x17]
+        );
+        // 超过64bit的范围同样可以提取
+        let long = [0xFF; 10];
+        assert_eq!(
+            disassembler.extract_range(&long, 4, 72).unwrap(),
+            vec![0xFF; 9]
+        );
+    }
+
+    #[test]
+    fn test_extract_range_out_of_bounds() {
+        let disassembler = FrameDisassembler::new();
+        let data = [0x0A, 0x45];
+        assert!(disassembler.extract_range(&data, 0, 16).is_ok());
+        assert!(matches!(
+            disassembler.extract_range(&data, 8, 12),
+            Err(ProtocolError::InvalidFrameFormat(_))
+        ));
+        assert!(disassembler.extract_range(&data, 4, 0).is_err());
+        assert!(disassembler.extract_range(&data, usize::MAX, 2).is_err());
+    }
 }
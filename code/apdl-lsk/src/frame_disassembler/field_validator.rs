@@ -2,6 +2,7 @@
 //!
 //! 负责验证提取的字段值是否满足约束条件
 
+use apdl_core::utils::{calculate_crc16_with, Crc16Params};
 use apdl_core::{Constraint, ProtocolError};
 
 /// 字段校验器
@@ -77,7 +78,16 @@ impl FieldValidator {
     /// - `Ok(())`: 校验通过
     /// - `Err(ProtocolError)`: 校验失败
     pub fn verify_crc16(data: &[u8], expected_crc: u16) -> Result<(), ProtocolError> {
-        let calculated_crc = Self::calculate_crc16(data);
+        Self::verify_crc16_with(Crc16Params::CCSDS, data, expected_crc)
+    }
+
+    /// 按指定CRC16参数验证校验和
+    pub fn verify_crc16_with(
+        params: Crc16Params,
+        data: &[u8],
+        expected_crc: u16,
+    ) -> Result<(), ProtocolError> {
+        let calculated_crc = calculate_crc16_with(params, data);
         if calculated_crc != expected_crc {
             return Err(ProtocolError::ChecksumError(format!(
                 "CRC16 mismatch: expected 0x{expected_crc:04X}, got 0x{calculated_crc:04X}"
@@ -86,22 +96,6 @@ impl FieldValidator {
         Ok(())
     }
 
-    /// 计算CRC16校验和（CCITT多项式）
-    fn calculate_crc16(data: &[u8]) -> u16 {
-        let mut crc: u16 = 0xFFFF;
-        for &byte in data {
-            crc ^= (byte as u16) << 8;
-            for _ in 0..8 {
-                if (crc & 0x8000) != 0 {
-                    crc = (crc << 1) ^ 0x1021;
-                } else {
-                    crc <<= 1;
-                }
-            }
-        }
-        crc
-    }
-
     /// 验证简单校验和
     pub fn verify_simple_checksum(data: &[u8], expected: u16) -> Result<(), ProtocolError> {
         let calculated: u16 = data.iter().map(|&b| b as u16).sum();
@@ -177,13 +171,16 @@ mod tests {
     fn test_crc16() {
         // 测试CRC16计算
         let data = b"123456789";
-        let crc = FieldValidator::calculate_crc16(data);
 
-        // 验证计算的CRC
-        assert!(FieldValidator::verify_crc16(data, crc).is_ok());
+        // 验证CRC-16/CCITT-FALSE
+        assert!(FieldValidator::verify_crc16(data, 0x29B1).is_ok());
 
         // 验证错误的CRC
-        assert!(FieldValidator::verify_crc16(data, crc + 1).is_err());
+        assert!(FieldValidator::verify_crc16(data, 0x29B2).is_err());
+
+        // 按指定参数验证CRC-16/ARC
+        assert!(FieldValidator::verify_crc16_with(Crc16Params::ARC, data, 0xBB3D).is_ok());
+        assert!(FieldValidator::verify_crc16_with(Crc16Params::ARC, data, 0x29B1).is_err());
     }
 
     #[test]
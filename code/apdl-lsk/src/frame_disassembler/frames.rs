@@ -0,0 +1,339 @@
+//! 连续帧迭代器
+//!
+//! 从包含多个连续帧的缓冲区中逐帧解析，支持解析失败后按同步标志重新同步
+
+use apdl_core::utils::find_pattern_offsets;
+use apdl_core::{ParsedField, ProtocolError, UnitType};
+
+use super::core::FrameDisassembler;
+use crate::frame_boundary::FrameBoundary;
+
+/// 重新同步记录
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResyncEvent {
+    /// 解析失败的帧起始偏移
+    pub offset: usize,
+    /// 跳过的字节数
+    pub skipped: usize,
+    /// 导致重新同步的解析错误
+    pub error: ProtocolError,
+}
+
+/// 连续帧迭代器
+///
+/// 帧长度默认由字段定义确定；含动态长度字段时，帧延伸到下一个同步标志或缓冲区末尾。
+/// 可通过`boundary`指定帧边界策略。未启用重新同步时，遇到解析错误返回该错误后结束迭代
+pub struct FrameIter<'a> {
+    disassembler: &'a FrameDisassembler,
+    data: &'a [u8],
+    offset: usize,
+    resync: bool,
+    sync_marker: Option<Vec<u8>>,
+    boundary: Option<FrameBoundary>,
+    resync_events: Vec<ResyncEvent>,
+    finished: bool,
+}
+
+impl<'a> FrameIter<'a> {
+    fn new(disassembler: &'a FrameDisassembler, data: &'a [u8]) -> Self {
+        let sync_marker = disassembler.declared_sync_marker();
+        let boundary = match (disassembler.fixed_frame_size(), &sync_marker) {
+            (Some(size), _) => Some(FrameBoundary::Fixed(size)),
+            (None, Some(marker)) => Some(FrameBoundary::Delimited {
+                marker: marker.clone(),
+            }),
+            (None, None) => None,
+        };
+        Self {
+            disassembler,
+            data,
+            offset: 0,
+            resync: false,
+            sync_marker,
+            boundary,
+            resync_events: Vec::new(),
+            finished: false,
+        }
+    }
+
+    /// 设置帧边界策略，替代由字段定义推断的帧长度
+    pub fn boundary(mut self, boundary: FrameBoundary) -> Self {
+        self.boundary = Some(boundary);
+        self
+    }
+
+    /// 设置解析失败时是否扫描到下一个同步标志继续解析
+    pub fn resync(mut self, enabled: bool) -> Self {
+        self.resync = enabled;
+        self
+    }
+
+    /// 获取重新同步记录
+    pub fn resync_events(&self) -> &[ResyncEvent] {
+        &self.resync_events
+    }
+
+    /// 获取因重新同步跳过的总字节数
+    pub fn skipped_bytes(&self) -> usize {
+        self.resync_events.iter().map(|event| event.skipped).sum()
+    }
+
+    /// 确定从当前偏移开始的帧长度
+    fn frame_len(&self) -> Result<usize, ProtocolError> {
+        let rest = &self.data[self.offset..];
+        match &self.boundary {
+            // 分隔的帧在缓冲区末尾之前未出现下一个标志时延伸到缓冲区末尾
+            Some(boundary @ FrameBoundary::Delimited { .. }) => {
+                Ok(boundary.frame_len(rest)?.unwrap_or(rest.len()))
+            }
+            Some(boundary) => boundary.frame_len(rest)?.ok_or_else(|| {
+                ProtocolError::InvalidFrameFormat(format!(
+                    "Truncated frame at offset {}: length field not available",
+                    self.offset
+                ))
+            }),
+            None => Ok(rest.len()),
+        }
+    }
+
+    /// 查找`from`之后下一个同步标志的偏移
+    fn next_sync_offset(&self, from: usize) -> Option<usize> {
+        let marker = self.sync_marker.as_ref()?;
+        let start = from.min(self.data.len());
+        find_pattern_offsets(&self.data[start..], marker)
+            .first()
+            .map(|&relative| start + relative)
+    }
+
+    /// 解析从当前偏移开始的帧，返回解析结果和帧长度
+    fn parse_current(&self) -> Result<(Vec<ParsedField>, usize), ProtocolError> {
+        let frame_len = self.frame_len()?;
+        let end = self.offset + frame_len;
+        if end > self.data.len() {
+            return Err(ProtocolError::InvalidFrameFormat(format!(
+                "Truncated frame at offset {}: need {} bytes, {} available",
+                self.offset,
+                frame_len,
+                self.data.len() - self.offset
+            )));
+        }
+
+        let frame = &self.data[self.offset..end];
+        self.disassembler.check_sync_marker(frame)?;
+        Ok((self.disassembler.parse_frame_named(frame)?, frame_len))
+    }
+}
+
+impl Iterator for FrameIter<'_> {
+    type Item = Result<Vec<ParsedField>, ProtocolError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished && self.offset < self.data.len() {
+            match self.parse_current() {
+                Ok((fields, frame_len)) => {
+                    self.offset += frame_len;
+                    return Some(Ok(fields));
+                }
+                Err(error) => {
+                    let next_sync = if self.resync {
+                        self.next_sync_offset(self.offset + 1)
+                    } else {
+                        None
+                    };
+
+                    let Some(next) = next_sync else {
+                        self.finished = true;
+                        return Some(Err(error));
+                    };
+
+                    self.resync_events.push(ResyncEvent {
+                        offset: self.offset,
+                        skipped: next - self.offset,
+                        error,
+                    });
+                    self.offset = next;
+                }
+            }
+        }
+        None
+    }
+}
+
+impl FrameDisassembler {
+    /// 创建逐帧解析缓冲区中连续帧的迭代器
+    pub fn frames<'a>(&'a self, data: &'a [u8]) -> FrameIter<'a> {
+        FrameIter::new(self, data)
+    }
+
+    /// 计算全部字段均为固定长度时的帧字节数（含对齐填充），含动态长度字段时返回None
+    pub fn fixed_frame_size(&self) -> Option<usize> {
+        let mut bit_offset = 0usize;
+        for field in &self.fields {
+            if !matches!(field.unit_type, UnitType::Bit(_)) {
+                bit_offset = bit_offset.div_ceil(8) * 8;
+            }
+            bit_offset += self.alignment_padding_bits(field, bit_offset);
+            bit_offset += match field.unit_type {
+                UnitType::Bit(bits) | UnitType::Uint(bits) => bits as usize,
+                UnitType::RawData => return None,
+                _ => field.unit_type.fixed_byte_size()? * 8,
+            };
+        }
+        (bit_offset > 0).then(|| bit_offset.div_ceil(8))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apdl_core::{Constraint, LengthDesc, LengthUnit, ScopeDesc, SemanticRule, SyntaxUnit};
+
+    fn make_field(name: &str, unit_type: UnitType, size: usize, unit: LengthUnit) -> SyntaxUnit {
+        SyntaxUnit::new(
+            name,
+            unit_type,
+            LengthDesc { size, unit },
+            ScopeDesc::Global("test".to_string()),
+        )
+    }
+
+    fn create_disassembler(dynamic: bool) -> FrameDisassembler {
+        let mut sync = make_field("sync", UnitType::Uint(16), 2, LengthUnit::Byte);
+        sync.constraint = Some(Constraint::FixedValue(0xEB90));
+
+        let mut disassembler = FrameDisassembler::new();
+        disassembler.add_field(sync);
+        disassembler.add_semantic_rule(SemanticRule::Synchronization {
+            field_name: "sync".to_string(),
+            algorithm: "ccsds_sync".to_string(),
+            description: "Sync marker".to_string(),
+        });
+        disassembler.add_field(make_field("id", UnitType::Uint(8), 1, LengthUnit::Byte));
+        if dynamic {
+            disassembler.add_field(make_field(
+                "data",
+                UnitType::RawData,
+                0,
+                LengthUnit::Dynamic,
+            ));
+        } else {
+            disassembler.add_field(make_field("data", UnitType::Uint(16), 2, LengthUnit::Byte));
+        }
+        disassembler
+    }
+
+    fn ids(frames: &[Vec<ParsedField>]) -> Vec<u8> {
+        frames.iter().map(|fields| fields[1].value[0]).collect()
+    }
+
+    #[test]
+    fn test_resync_recovers_frames_around_corrupt_frame() {
+        let disassembler = create_disassembler(false);
+        assert_eq!(disassembler.fixed_frame_size(), Some(5));
+
+        let mut data = vec![0xEB, 0x90, 0x01, 0xAA, 0xBB];
+        // 损坏的帧：同步标志错误且长度不完整
+        data.extend_from_slice(&[0xEB, 0x00, 0x02, 0xCC]);
+        data.extend_from_slice(&[0xEB, 0x90, 0x03, 0xDD, 0xEE]);
+
+        let mut frames = disassembler.frames(&data).resync(true);
+        let parsed: Vec<Vec<ParsedField>> = frames.by_ref().map(|r| r.unwrap()).collect();
+        assert_eq!(ids(&parsed), vec![0x01, 0x03]);
+        assert_eq!(parsed[1][2].value, vec![0xDD, 0xEE]);
+
+        assert_eq!(frames.skipped_bytes(), 4);
+        let events = frames.resync_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].offset, 5);
+        assert!(matches!(
+            events[0].error,
+            ProtocolError::SynchronizationError(_)
+        ));
+    }
+
+    #[test]
+    fn test_fixed_frame_size_includes_alignment_padding() {
+        let mut disassembler = create_disassembler(false);
+        disassembler.add_semantic_rule(SemanticRule::Alignment {
+            field_name: "data".to_string(),
+            boundary: 4,
+        });
+        // sync(2) + id(1) + 填充(1) + data(2)
+        assert_eq!(disassembler.fixed_frame_size(), Some(6));
+
+        let data = [
+            0xEB, 0x90, 0x01, 0x00, 0xAA, 0xBB, 0xEB, 0x90, 0x02, 0x00, 0xCC, 0xDD,
+        ];
+        let parsed: Vec<Vec<ParsedField>> =
+            disassembler.frames(&data).map(|r| r.unwrap()).collect();
+        assert_eq!(ids(&parsed), vec![0x01, 0x02]);
+        assert_eq!(parsed[1][2].value, vec![0xCC, 0xDD]);
+    }
+
+    #[test]
+    fn test_without_resync_stops_at_corrupt_frame() {
+        let disassembler = create_disassembler(false);
+
+        let mut data = vec![0xEB, 0x90, 0x01, 0xAA, 0xBB];
+        data.extend_from_slice(&[0x00, 0x00, 0x02, 0xCC]);
+        data.extend_from_slice(&[0xEB, 0x90, 0x03, 0xDD, 0xEE]);
+
+        let results: Vec<_> = disassembler.frames(&data).collect();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            results[1],
+            Err(ProtocolError::SynchronizationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_frames_with_length_field_boundary() {
+        let disassembler = create_disassembler(true);
+        // 长度字段（id）为数据区字节数，帧头3字节
+        let boundary = FrameBoundary::LengthField {
+            offset: 2,
+            width: 1,
+            endian: apdl_core::ByteOrder::BigEndian,
+            adjust: 3,
+        };
+
+        let mut data = vec![0xEB, 0x90, 0x01, 0xAA];
+        data.extend_from_slice(&[0xEB, 0x90, 0x03, 0xEB, 0x90, 0xCC]);
+        data.extend_from_slice(&[0xEB, 0x90, 0x02, 0xDD, 0xEE]);
+
+        let parsed: Vec<Vec<ParsedField>> = disassembler
+            .frames(&data)
+            .boundary(boundary)
+            .map(|r| r.unwrap())
+            .collect();
+        // 数据区中出现的同步标志不影响按长度字段切分
+        assert_eq!(ids(&parsed), vec![0x01, 0x03, 0x02]);
+        assert_eq!(parsed[1][2].value, vec![0xEB, 0x90, 0xCC]);
+    }
+
+    #[test]
+    fn test_resync_dynamic_frames_by_sync_marker() {
+        let disassembler = create_disassembler(true);
+        assert_eq!(disassembler.fixed_frame_size(), None);
+
+        let mut data = vec![0xEB, 0x90, 0x01, 0xAA];
+        data.extend_from_slice(&[0x55, 0x55, 0x55]);
+        data.extend_from_slice(&[0xEB, 0x90, 0x03, 0xDD, 0xEE]);
+
+        let mut frames = disassembler.frames(&data).resync(true);
+        let parsed: Vec<Vec<ParsedField>> = frames.by_ref().map(|r| r.unwrap()).collect();
+        // 动态长度帧延伸到下一个同步标志，尾部垃圾数据归入第一帧
+        assert_eq!(ids(&parsed), vec![0x01, 0x03]);
+        assert_eq!(parsed[0][2].value, vec![0xAA, 0x55, 0x55, 0x55]);
+        assert_eq!(frames.skipped_bytes(), 0);
+
+        // 缓冲区以垃圾数据开头时跳到第一个同步标志
+        let mut data = vec![0x00, 0x11];
+        data.extend_from_slice(&[0xEB, 0x90, 0x07, 0x01]);
+        let mut frames = disassembler.frames(&data).resync(true);
+        let parsed: Vec<Vec<ParsedField>> = frames.by_ref().map(|r| r.unwrap()).collect();
+        assert_eq!(ids(&parsed), vec![0x07]);
+        assert_eq!(frames.skipped_bytes(), 2);
+    }
+}
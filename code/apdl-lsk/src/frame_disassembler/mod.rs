@@ -5,11 +5,17 @@
 //! - 字段值校验（固定值、范围、约束）
 //! - CRC/Checksum验证
 //! - 字段到结构化数据的映射
+//! - 连续帧迭代及解析失败后的重新同步
+//! - 按字段分组输出嵌套的解析树
 
 pub mod bit_extractor;
 pub mod core;
 pub mod field_validator;
+pub mod frames;
+pub mod tree;
 
-pub use bit_extractor::extract_bit_field;
+pub use bit_extractor::{extract_bit_field, extract_bit_field_with_numbering, extract_can_signal};
 pub use core::FrameDisassembler;
 pub use field_validator::FieldValidator;
+pub use frames::{FrameIter, ResyncEvent};
+pub use tree::{FieldGroup, ParsedNode, ParsedTree};
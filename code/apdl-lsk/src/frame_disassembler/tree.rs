@@ -0,0 +1,253 @@
+//! 嵌套解析树
+//!
+//! 按字段分组将解析结果组织为树，分组成员可以是字段或其他分组
+
+use apdl_core::{ParsedField, ProtocolError};
+use std::collections::HashMap;
+
+use super::core::FrameDisassembler;
+
+/// 字段分组
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldGroup {
+    /// 分组名
+    pub name: String,
+    /// 成员名（字段名或子分组名）
+    pub members: Vec<String>,
+}
+
+/// 解析树节点
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsedNode {
+    /// 已解析的字段
+    Field(ParsedField),
+    /// 分组及其子节点
+    Group {
+        name: String,
+        children: Vec<ParsedNode>,
+    },
+}
+
+impl ParsedNode {
+    /// 获取节点名
+    pub fn name(&self) -> &str {
+        match self {
+            ParsedNode::Field(field) => &field.name,
+            ParsedNode::Group { name, .. } => name,
+        }
+    }
+
+    /// 获取子节点，字段节点返回空切片
+    pub fn children(&self) -> &[ParsedNode] {
+        match self {
+            ParsedNode::Field(_) => &[],
+            ParsedNode::Group { children, .. } => children,
+        }
+    }
+
+    fn flatten_into(&self, out: &mut Vec<ParsedField>) {
+        match self {
+            ParsedNode::Field(field) => out.push(field.clone()),
+            ParsedNode::Group { children, .. } => {
+                for child in children {
+                    child.flatten_into(out);
+                }
+            }
+        }
+    }
+}
+
+/// 嵌套解析树
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedTree {
+    /// 顶层节点（按帧中顺序）
+    pub nodes: Vec<ParsedNode>,
+}
+
+impl ParsedTree {
+    /// 按名称查找节点（深度优先）
+    pub fn find(&self, name: &str) -> Option<&ParsedNode> {
+        fn find_in<'a>(nodes: &'a [ParsedNode], name: &str) -> Option<&'a ParsedNode> {
+            nodes.iter().find_map(|node| {
+                if node.name() == name {
+                    Some(node)
+                } else {
+                    find_in(node.children(), name)
+                }
+            })
+        }
+        find_in(&self.nodes, name)
+    }
+
+    /// 展开为按帧中顺序排列的字段列表，与`parse_frame_named`的结果一致
+    pub fn flatten(&self) -> Vec<ParsedField> {
+        let mut fields = Vec::new();
+        for node in &self.nodes {
+            node.flatten_into(&mut fields);
+        }
+        fields
+    }
+
+    /// 将字段插入到分组路径对应的节点下，连续的同名分组合并为一个节点
+    fn insert(nodes: &mut Vec<ParsedNode>, path: &[&str], field: ParsedField) {
+        let Some((&group, rest)) = path.split_first() else {
+            nodes.push(ParsedNode::Field(field));
+            return;
+        };
+
+        let reuse_last = matches!(
+            nodes.last(),
+            Some(ParsedNode::Group { name, .. }) if name == group
+        );
+        if !reuse_last {
+            nodes.push(ParsedNode::Group {
+                name: group.to_string(),
+                children: Vec::new(),
+            });
+        }
+        if let Some(ParsedNode::Group { children, .. }) = nodes.last_mut() {
+            Self::insert(children, rest, field);
+        }
+    }
+}
+
+impl FrameDisassembler {
+    /// 添加字段分组，成员可以是字段名或其他分组名
+    pub fn add_group(&mut self, name: &str, members: &[&str]) {
+        self.groups.push(FieldGroup {
+            name: name.to_string(),
+            members: members.iter().map(|member| member.to_string()).collect(),
+        });
+    }
+
+    /// 解析帧数据并按字段分组生成嵌套解析树
+    pub fn parse_tree(&self, frame_data: &[u8]) -> Result<ParsedTree, ProtocolError> {
+        let parents: HashMap<&str, &str> = self
+            .groups
+            .iter()
+            .flat_map(|group| {
+                group
+                    .members
+                    .iter()
+                    .map(move |member| (member.as_str(), group.name.as_str()))
+            })
+            .collect();
+
+        let mut tree = ParsedTree::default();
+        for field in self.parse_frame_named(frame_data)? {
+            let path = Self::group_path(&parents, &field.name)?;
+            ParsedTree::insert(&mut tree.nodes, &path, field);
+        }
+        Ok(tree)
+    }
+
+    /// 获取字段所在的分组路径（从最外层分组开始）
+    fn group_path<'a>(
+        parents: &HashMap<&str, &'a str>,
+        field_name: &str,
+    ) -> Result<Vec<&'a str>, ProtocolError> {
+        let mut path = Vec::new();
+        let mut current = field_name;
+        while let Some(&parent) = parents.get(current) {
+            if path.contains(&parent) {
+                return Err(ProtocolError::InvalidFieldDefinition(format!(
+                    "Circular field group: {parent}"
+                )));
+            }
+            path.push(parent);
+            current = parent;
+        }
+        path.reverse();
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apdl_core::{LengthDesc, LengthUnit, ScopeDesc, SyntaxUnit, UnitType};
+
+    fn make_field(name: &str, unit_type: UnitType, size: usize, unit: LengthUnit) -> SyntaxUnit {
+        SyntaxUnit::new(
+            name,
+            unit_type,
+            LengthDesc { size, unit },
+            ScopeDesc::Global("test".to_string()),
+        )
+    }
+
+    fn create_disassembler() -> FrameDisassembler {
+        let mut disassembler = FrameDisassembler::new();
+        disassembler.add_field(make_field("version", UnitType::Bit(3), 3, LengthUnit::Bit));
+        disassembler.add_field(make_field(
+            "type_flag",
+            UnitType::Bit(2),
+            2,
+            LengthUnit::Bit,
+        ));
+        disassembler.add_field(make_field("apid", UnitType::Bit(11), 11, LengthUnit::Bit));
+        disassembler.add_field(make_field(
+            "length",
+            UnitType::Uint(16),
+            2,
+            LengthUnit::Byte,
+        ));
+        disassembler.add_field(make_field("data", UnitType::Uint(8), 1, LengthUnit::Byte));
+        disassembler.add_group("primary_header", &["version", "type_flag", "apid"]);
+        disassembler
+    }
+
+    #[test]
+    fn test_parse_tree_nests_primary_header() {
+        let disassembler = create_disassembler();
+        let frame = [0x22, 0x05, 0x00, 0x01, 0xAB];
+
+        let tree = disassembler.parse_tree(&frame).unwrap();
+        let names: Vec<&str> = tree.nodes.iter().map(ParsedNode::name).collect();
+        assert_eq!(names, vec!["primary_header", "length", "data"]);
+
+        let header = tree.find("primary_header").unwrap();
+        let children: Vec<&str> = header.children().iter().map(ParsedNode::name).collect();
+        assert_eq!(children, vec!["version", "type_flag", "apid"]);
+
+        let Some(ParsedNode::Field(apid)) = tree.find("apid") else {
+            panic!("apid not found");
+        };
+        assert_eq!(apid.value, vec![0x02, 0x05]);
+
+        // 展开后与平铺解析结果一致
+        assert_eq!(
+            tree.flatten(),
+            disassembler.parse_frame_named(&frame).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_tree_nested_groups() {
+        let mut disassembler = create_disassembler();
+        disassembler.add_group("header", &["primary_header", "length"]);
+
+        let tree = disassembler
+            .parse_tree(&[0x22, 0x05, 0x00, 0x01, 0xAB])
+            .unwrap();
+        let names: Vec<&str> = tree.nodes.iter().map(ParsedNode::name).collect();
+        assert_eq!(names, vec!["header", "data"]);
+
+        let header = tree.find("header").unwrap();
+        let children: Vec<&str> = header.children().iter().map(ParsedNode::name).collect();
+        assert_eq!(children, vec!["primary_header", "length"]);
+        assert_eq!(header.children()[0].children().len(), 3);
+    }
+
+    #[test]
+    fn test_parse_tree_rejects_circular_groups() {
+        let mut disassembler = create_disassembler();
+        disassembler.add_group("outer", &["primary_header"]);
+        disassembler.add_group("primary_header", &["outer"]);
+
+        assert!(matches!(
+            disassembler.parse_tree(&[0x22, 0x05, 0x00, 0x01, 0xAB]),
+            Err(ProtocolError::InvalidFieldDefinition(_))
+        ));
+    }
+}
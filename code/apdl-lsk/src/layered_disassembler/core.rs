@@ -93,25 +93,38 @@ impl LayeredDisassembler {
     ) -> Result<DisassembleResult, ProtocolError> {
         let mut result = DisassembleResult::new();
         let mut current_data = raw_data.to_vec(); // 使用拥有的数据
+        let mut current_start = 0usize; // 当前层数据在原始数据中的起始偏移
 
         // 逐层拆包
         for (layer_index, layer_info) in self.layer_disassemblers.iter().enumerate() {
             // 拆包当前层
-            let fields = layer_info.disassembler.disassemble_frame(&current_data)?;
+            let fields = layer_info.disassembler.parse_frame_named(&current_data)?;
 
             // 创建层数据
             let mut layer_data = LayerData::new(layer_info.layer_name.clone(), layer_index);
+            layer_data.header_range = current_start..current_start + current_data.len();
 
             // 添加所有字段
-            for (field_name, value) in &fields {
-                layer_data.add_field(field_name.clone(), value.clone());
+            for field in &fields {
+                layer_data.add_field(field.name.clone(), field.value.clone());
+            }
+
+            // 收集该层的约束违反
+            for mut error in layer_info.disassembler.validate_fields(&fields) {
+                error.layer_index = layer_index;
+                result.add_error(error);
             }
 
             // 提取净荷（如果有）
             if let Some(ref payload_field) = layer_info.payload_field_name {
-                if let Some(payload) = fields.get(payload_field) {
-                    layer_data.set_payload(payload_field.clone(), payload.clone());
-                    current_data = payload.clone(); // 下一层使用净荷数据
+                if let Some(payload) = fields.iter().find(|field| &field.name == payload_field) {
+                    let sdu_start = current_start + payload.bit_offset / 8;
+                    layer_data.header_range = current_start..sdu_start;
+                    layer_data.sdu_range = Some(sdu_start..sdu_start + payload.raw.len());
+                    layer_data.set_payload(payload_field.clone(), payload.value.clone());
+
+                    current_data = payload.value.clone(); // 下一层使用净荷数据
+                    current_start = sdu_start;
                 } else {
                     return Err(ProtocolError::FieldNotFound(format!(
                         "Payload field '{}' not found in layer {}",
@@ -226,6 +239,8 @@ mod tests {
             associate: vec![],
             desc: "Header".to_string(),
             pack_unpack_spec: None,
+            unit_label: None,
+            long_description: None,
         };
 
         disassembler.add_field(header_field);
@@ -246,6 +261,8 @@ mod tests {
                 associate: vec![],
                 desc: "Payload".to_string(),
                 pack_unpack_spec: None,
+                unit_label: None,
+                long_description: None,
             };
             disassembler.add_field(payload_field);
             Some(field_name.to_string())
@@ -352,4 +369,37 @@ mod tests {
         assert_eq!(names, vec!["Layer A", "Layer B"]);
         assert_eq!(layered.layer_count(), 2);
     }
+
+    #[test]
+    fn test_layer_sdu_ranges() {
+        let mut layered = LayeredDisassembler::new();
+
+        let (outer, outer_payload) = create_test_layer("outer", 4, Some("outer_payload"));
+        layered.add_layer("Outer Layer".to_string(), outer, outer_payload);
+
+        let (inner, inner_payload) = create_test_layer("inner", 2, Some("inner_data"));
+        layered.add_layer("Inner Layer".to_string(), inner, inner_payload);
+
+        let test_data = vec![0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF, 0x01, 0x02, 0x03, 0x04];
+        let result = layered.disassemble_layers(&test_data).unwrap();
+
+        let outer_layer = result.get_layer(0).unwrap();
+        assert_eq!(outer_layer.header_range, 0..4);
+        let outer_sdu = outer_layer.sdu_range.clone().unwrap();
+        assert_eq!(outer_sdu, 4..10);
+        assert_eq!(
+            &test_data[outer_sdu.clone()],
+            outer_layer.payload_data.as_deref().unwrap()
+        );
+
+        // 内层的范围均落在外层净荷范围内，内层头部从外层净荷起始处开始
+        let inner_layer = result.get_layer(1).unwrap();
+        assert_eq!(inner_layer.header_range, 4..6);
+        assert_eq!(inner_layer.header_range.start, outer_sdu.start);
+
+        let inner_sdu = inner_layer.sdu_range.clone().unwrap();
+        assert_eq!(inner_layer.header_range.start..inner_sdu.end, outer_sdu);
+        assert_eq!(inner_sdu, 6..10);
+        assert_eq!(&test_data[inner_sdu], result.application_data.as_slice());
+    }
 }
@@ -1,6 +1,7 @@
 //! 分层拆包数据结构
 
 use std::collections::HashMap;
+use std::ops::Range;
 
 /// 单层数据
 #[derive(Debug, Clone)]
@@ -15,6 +16,10 @@ pub struct LayerData {
     pub payload_field: Option<String>,
     /// 净荷数据（如果有）
     pub payload_data: Option<Vec<u8>>,
+    /// 该层头部在原始数据中的字节范围（净荷之前的部分，无净荷时为整层数据）
+    pub header_range: Range<usize>,
+    /// 交给下一层的SDU在原始数据中的字节范围（如果有）
+    pub sdu_range: Option<Range<usize>>,
 }
 
 impl LayerData {
@@ -26,6 +31,8 @@ impl LayerData {
             fields: HashMap::new(),
             payload_field: None,
             payload_data: None,
+            header_range: 0..0,
+            sdu_range: None,
         }
     }
 
@@ -56,12 +63,16 @@ impl LayerData {
             }
             println!();
         }
+        println!("头部范围: {:?}", self.header_range);
         if let Some(ref payload_field) = self.payload_field {
             if let Some(ref payload) = self.payload_data {
                 println!("净荷字段: {}", payload_field);
                 println!("净荷大小: {} 字节", payload.len());
             }
         }
+        if let Some(ref sdu_range) = self.sdu_range {
+            println!("SDU范围: {:?}", sdu_range);
+        }
     }
 }
 
@@ -1,9 +1,11 @@
 //! 分层拆包引擎
 //!
-//! 自动识别协议层级关系，递归拆包直到应用数据层
+//! 自动识别协议层级关系，递归拆包直到应用数据层；重组引擎执行逆过程
 
 pub mod core;
 pub mod layer_data;
+pub mod reassembler;
 
 pub use core::LayeredDisassembler;
 pub use layer_data::{DisassembleResult, LayerData, ValidationError};
+pub use reassembler::{LayerAssembler, LayeredReassembler};
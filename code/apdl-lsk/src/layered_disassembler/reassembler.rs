@@ -0,0 +1,121 @@
+//! 分层重组引擎
+//!
+//! 拆包的逆过程：将各层字段和应用数据从内到外逐层组装，还原原始帧
+
+use apdl_core::ProtocolError;
+use std::collections::HashMap;
+
+use super::layer_data::DisassembleResult;
+
+/// 单层组装接口
+///
+/// 根据字段值组装该层的完整帧，由各层的帧组装器（如apdl-poem的FrameAssembler）实现
+pub trait LayerAssembler {
+    /// 使用给定字段值组装一层帧，字段值中包含净荷字段
+    fn assemble_layer(
+        &mut self,
+        fields: &HashMap<String, Vec<u8>>,
+    ) -> Result<Vec<u8>, ProtocolError>;
+}
+
+/// 分层重组引擎
+///
+/// 与LayeredDisassembler对称：各层按从外到内的顺序添加，重组时从最内层开始，
+/// 每层的组装结果作为外层的净荷
+pub struct LayeredReassembler {
+    /// 各层的组装器（从外到内）
+    layer_assemblers: Vec<LayerAssemblerInfo>,
+}
+
+/// 单层组装器信息
+struct LayerAssemblerInfo {
+    /// 层名称
+    layer_name: String,
+    /// 帧组装器
+    assembler: Box<dyn LayerAssembler>,
+    /// 净荷字段名（承载下一层的字段）
+    payload_field_name: Option<String>,
+}
+
+impl LayeredReassembler {
+    /// 创建新的分层重组引擎
+    pub fn new() -> Self {
+        Self {
+            layer_assemblers: Vec::new(),
+        }
+    }
+
+    /// 添加一层组装器
+    ///
+    /// # 参数
+    /// - `layer_name`: 层名称
+    /// - `assembler`: 该层的帧组装器
+    /// - `payload_field_name`: 净荷字段名（如果有下一层或应用数据）
+    pub fn add_layer(
+        &mut self,
+        layer_name: String,
+        assembler: Box<dyn LayerAssembler>,
+        payload_field_name: Option<String>,
+    ) {
+        self.layer_assemblers.push(LayerAssemblerInfo {
+            layer_name,
+            assembler,
+            payload_field_name,
+        });
+    }
+
+    /// 将拆包结果重组为原始帧
+    ///
+    /// 最内层的净荷为拆包结果的应用数据，其余各层的净荷为内一层的组装结果
+    ///
+    /// # 参数
+    /// - `result`: LayeredDisassembler的拆包结果
+    ///
+    /// # 返回
+    /// - `Ok(Vec<u8>)`: 重组后的最外层帧
+    /// - `Err(ProtocolError)`: 层数不一致或某层组装失败
+    pub fn reassemble(&mut self, result: &DisassembleResult) -> Result<Vec<u8>, ProtocolError> {
+        if result.layer_count() != self.layer_assemblers.len() {
+            return Err(ProtocolError::Other(format!(
+                "Disassemble result has {} layers, reassembler has {}",
+                result.layer_count(),
+                self.layer_assemblers.len()
+            )));
+        }
+
+        let mut payload = result.application_data.clone();
+        for (layer_info, layer_data) in self.layer_assemblers.iter_mut().zip(&result.layers).rev() {
+            let mut fields = layer_data.fields.clone();
+            if let Some(ref payload_field) = layer_info.payload_field_name {
+                fields.insert(payload_field.clone(), payload);
+            }
+            payload = layer_info.assembler.assemble_layer(&fields).map_err(|e| {
+                ProtocolError::Other(format!(
+                    "Failed to reassemble layer {}: {}",
+                    layer_info.layer_name, e
+                ))
+            })?;
+        }
+
+        Ok(payload)
+    }
+
+    /// 获取层数
+    pub fn layer_count(&self) -> usize {
+        self.layer_assemblers.len()
+    }
+
+    /// 获取层名称列表
+    pub fn get_layer_names(&self) -> Vec<&str> {
+        self.layer_assemblers
+            .iter()
+            .map(|info| info.layer_name.as_str())
+            .collect()
+    }
+}
+
+impl Default for LayeredReassembler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
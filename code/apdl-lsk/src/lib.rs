@@ -5,23 +5,29 @@
 pub mod channel;
 pub mod data_generator;
 pub mod demultiplex;
+pub mod frame_boundary;
 pub mod frame_disassembler;
 pub mod layered_disassembler;
+pub mod prelude;
 pub mod receiver;
 pub mod simulator;
 pub mod traffic_generator;
 
-pub use channel::Channel;
+pub use channel::{Channel, GilbertElliott};
 pub use data_generator::{
-    patterns, BoundaryValueStrategy, ConstraintHandler, ConstraintValidator, DataGenerator,
-    DataImporter, FixedStrategy, GenerationStrategy, RandomStrategy, SequentialStrategy,
-    TestDataGenerator,
+    fixtures, patterns, BoundaryValueStrategy, ConstraintHandler, ConstraintValidator, ConstraintViolation,
+    DataGenerator, DataImporter, FixedStrategy, GenerationStrategy, RandomStrategy,
+    SequentialStrategy, TestDataGenerator,
 };
 pub use demultiplex::{
     ChannelState, Demultiplexer, ReorderBuffer, SequenceValidator, ValidationResult,
 };
+pub use frame_boundary::FrameBoundary;
 pub use frame_disassembler::{extract_bit_field, FieldValidator, FrameDisassembler};
-pub use layered_disassembler::{DisassembleResult, LayerData, LayeredDisassembler, ValidationError};
+pub use layered_disassembler::{
+    DisassembleResult, LayerAssembler, LayerData, LayeredDisassembler, LayeredReassembler,
+    ValidationError,
+};
 pub use receiver::{FrameSynchronizer, ReceiveBuffer, SyncMode};
 pub use simulator::ProtocolSimulator;
-pub use traffic_generator::TrafficGenerator;
+pub use traffic_generator::{PoissonArrivals, TrafficGenerator};
@@ -0,0 +1,10 @@
+//! 常用类型预导入
+//!
+//! 在`apdl_core::prelude`的基础上引入帧拆包、数据生成和接收相关类型
+
+pub use apdl_core::prelude::*;
+
+pub use crate::data_generator::DataGenerator;
+pub use crate::frame_boundary::FrameBoundary;
+pub use crate::frame_disassembler::{FieldValidator, FrameDisassembler};
+pub use crate::receiver::{FrameSynchronizer, ReceiveBuffer, SyncMode};
@@ -2,10 +2,11 @@
 //!
 //! 提供流式数据接收和缓存管理功能
 
-use apdl_core::ProtocolError;
+use apdl_core::{ByteOrder, ProtocolError};
 use std::collections::VecDeque;
 
 use super::sync::FrameSynchronizer;
+use crate::frame_boundary::FrameBoundary;
 
 /// 接收缓存
 ///
@@ -17,6 +18,10 @@ pub struct ReceiveBuffer {
     max_frame_size: usize,
     /// 帧同步器
     synchronizer: Option<FrameSynchronizer>,
+    /// 按bit对齐后尚不足一个字节的剩余位（低位有效）
+    pending_bits: u8,
+    /// 剩余位的个数（0表示数据字节对齐）
+    pending_bit_count: u8,
 }
 
 impl ReceiveBuffer {
@@ -29,6 +34,8 @@ impl ReceiveBuffer {
             buffer: VecDeque::new(),
             max_frame_size,
             synchronizer: None,
+            pending_bits: 0,
+            pending_bit_count: 0,
         }
     }
 
@@ -42,7 +49,17 @@ impl ReceiveBuffer {
     /// # 参数
     /// - `data`: 新接收的数据
     pub fn append(&mut self, data: &[u8]) {
-        self.buffer.extend(data);
+        if self.pending_bit_count == 0 {
+            self.buffer.extend(data);
+        } else {
+            // 按对齐时确定的bit滑动量继续移位拼接新数据
+            let count = self.pending_bit_count;
+            for &byte in data {
+                let combined = ((self.pending_bits as u16) << 8) | byte as u16;
+                self.buffer.push_back((combined >> count) as u8);
+                self.pending_bits = (combined & ((1 << count) - 1)) as u8;
+            }
+        }
 
         // 如果缓冲区超过最大值，移除最旧的数据
         while self.buffer.len() > self.max_frame_size * 2 {
@@ -63,6 +80,60 @@ impl ReceiveBuffer {
         }
     }
 
+    /// 搜索同步字，返回以bit计的帧起始偏移
+    pub fn find_sync_bit_offset(&self) -> Option<usize> {
+        self.synchronizer
+            .as_ref()
+            .and_then(|sync| sync.search_sync_bits(&self.buffer))
+    }
+
+    /// 搜索同步字并丢弃其前的数据，使帧起始对齐到缓冲区首字节
+    ///
+    /// 同步字位于字节内部（bit滑动）时，缓冲区内容整体左移对齐，
+    /// 之后追加的数据按同样的滑动量对齐
+    ///
+    /// # 返回
+    /// - `Some(bits)`: 找到同步字，返回丢弃的bit数
+    /// - `None`: 未找到同步字，缓冲区不变
+    pub fn align_to_sync(&mut self) -> Option<usize> {
+        let bit_offset = self.find_sync_bit_offset()?;
+        self.buffer.drain(..bit_offset / 8);
+
+        let shift = (bit_offset % 8) as u8;
+        if shift != 0 {
+            self.discard_bits(shift);
+        }
+        Some(bit_offset)
+    }
+
+    /// 丢弃缓冲区前端不足一个字节的`shift`位，其余数据左移
+    fn discard_bits(&mut self, shift: u8) {
+        let mut realigned = VecDeque::with_capacity(self.buffer.len());
+        let mut acc: u16 = 0;
+        let mut acc_bits = 0u8;
+        let mut skip = shift;
+        let pending = (self.pending_bits, self.pending_bit_count);
+        let bytes = self.buffer.iter().map(|&byte| (byte, 8u8));
+        for (value, width) in bytes.chain(std::iter::once(pending)) {
+            for bit in (0..width).rev() {
+                if skip > 0 {
+                    skip -= 1;
+                    continue;
+                }
+                acc = (acc << 1) | ((value >> bit) & 1) as u16;
+                acc_bits += 1;
+                if acc_bits == 8 {
+                    realigned.push_back(acc as u8);
+                    acc = 0;
+                    acc_bits = 0;
+                }
+            }
+        }
+        self.buffer = realigned;
+        self.pending_bits = acc as u8;
+        self.pending_bit_count = acc_bits;
+    }
+
     /// 基于长度字段计算帧长度
     ///
     /// # 参数
@@ -73,7 +144,7 @@ impl ReceiveBuffer {
     ///
     /// # 返回
     /// - `Some(frame_length)`: 计算出的完整帧长度
-    /// - `None`: 缓冲区数据不足以读取长度字段
+    /// - `None`: 缓冲区数据不足以读取长度字段，或长度值无效
     pub fn calculate_frame_length(
         &self,
         length_field_offset: usize,
@@ -81,25 +152,33 @@ impl ReceiveBuffer {
         length_includes_header: bool,
         header_size: usize,
     ) -> Option<usize> {
-        if self.buffer.len() < length_field_offset + length_field_size {
-            return None;
-        }
+        let boundary = Self::length_field_boundary(
+            length_field_offset,
+            length_field_size,
+            length_includes_header,
+            header_size,
+        );
+        let header = self.peek(length_field_offset + length_field_size)?;
+        boundary.frame_len(&header).ok().flatten()
+    }
 
-        // 读取长度字段值
-        let mut length_value = 0usize;
-        for i in 0..length_field_size {
-            let byte_val = *self.buffer.get(length_field_offset + i)?;
-            length_value = (length_value << 8) | (byte_val as usize);
+    /// 由长度字段参数构造大端长度字段边界策略
+    fn length_field_boundary(
+        length_field_offset: usize,
+        length_field_size: usize,
+        length_includes_header: bool,
+        header_size: usize,
+    ) -> FrameBoundary {
+        FrameBoundary::LengthField {
+            offset: length_field_offset,
+            width: length_field_size,
+            endian: ByteOrder::BigEndian,
+            adjust: if length_includes_header {
+                0
+            } else {
+                header_size as i64
+            },
         }
-
-        // 计算完整帧长度
-        let frame_length = if length_includes_header {
-            length_value
-        } else {
-            header_size + length_value
-        };
-
-        Some(frame_length)
     }
 
     /// 提取完整帧
@@ -120,6 +199,39 @@ impl ReceiveBuffer {
         Some(frame)
     }
 
+    /// 按固定帧长取出一帧
+    ///
+    /// 缓冲区累计满`len`字节后返回缓冲区前端的`len`字节，其余数据留待下次提取
+    ///
+    /// # 返回
+    /// - `Some(frame)`: 完整帧数据
+    /// - `None`: 数据不足，需要继续接收
+    pub fn take_fixed(&mut self, len: usize) -> Option<Vec<u8>> {
+        if len == 0 {
+            return None;
+        }
+        self.extract_frame(len)
+    }
+
+    /// 按分隔符取出一帧
+    ///
+    /// 返回从缓冲区起始到第一个分隔符（含分隔符）为止的数据，分隔符之后的数据留待下次提取
+    ///
+    /// # 返回
+    /// - `Some(frame)`: 以分隔符结尾的帧数据
+    /// - `None`: 分隔符为空或尚未接收到分隔符
+    pub fn take_delimited(&mut self, delim: &[u8]) -> Option<Vec<u8>> {
+        if delim.is_empty() {
+            return None;
+        }
+        let position = self
+            .buffer
+            .make_contiguous()
+            .windows(delim.len())
+            .position(|window| window == delim)?;
+        Some(self.buffer.drain(..position + delim.len()).collect())
+    }
+
     /// 尝试提取下一个完整帧（自动搜索同步字和计算长度）
     ///
     /// # 参数
@@ -139,37 +251,44 @@ impl ReceiveBuffer {
         length_includes_header: bool,
         header_size: usize,
     ) -> Result<Option<Vec<u8>>, ProtocolError> {
-        // 1. 搜索同步字
-        if let Some(sync_offset) = self.find_sync_marker() {
-            // 找到同步字，丢弃之前的数据
-            if sync_offset > 0 {
-                self.buffer.drain(..sync_offset);
-            }
-
-            // 2. 计算帧长度
-            if let Some(frame_length) = self.calculate_frame_length(
-                length_field_offset,
-                length_field_size,
-                length_includes_header,
-                header_size,
-            ) {
-                // 验证帧长度合理性
-                if frame_length > self.max_frame_size {
-                    return Err(ProtocolError::InvalidFrameFormat(format!(
-                        "Frame length {} exceeds maximum {}",
-                        frame_length, self.max_frame_size
-                    )));
-                }
+        let boundary = Self::length_field_boundary(
+            length_field_offset,
+            length_field_size,
+            length_includes_header,
+            header_size,
+        );
+        self.extract_frame_by(&boundary)
+    }
 
-                // 3. 提取完整帧
-                if let Some(frame) = self.extract_frame(frame_length) {
-                    return Ok(Some(frame));
-                }
-            }
+    /// 按帧边界策略提取下一个完整帧
+    ///
+    /// 设置了帧同步器时先丢弃同步字之前的数据，未找到同步字时等待更多数据；
+    /// 未设置帧同步器时帧从缓冲区起始处开始
+    ///
+    /// # 返回
+    /// - `Ok(Some(frame))`: 成功提取完整帧
+    /// - `Ok(None)`: 数据不足，需要继续接收
+    /// - `Err(ProtocolError)`: 帧长度无效或超过最大帧大小
+    pub fn extract_frame_by(
+        &mut self,
+        boundary: &FrameBoundary,
+    ) -> Result<Option<Vec<u8>>, ProtocolError> {
+        // 找到同步字时丢弃之前的数据（含同步字位于字节内部时的bit滑动）
+        if self.synchronizer.is_some() && self.align_to_sync().is_none() {
+            return Ok(None);
         }
 
-        // 数据不足或未找到同步字
-        Ok(None)
+        let Some(frame_length) = boundary.frame_len(self.buffer.make_contiguous())? else {
+            return Ok(None);
+        };
+        // 验证帧长度合理性
+        if frame_length > self.max_frame_size {
+            return Err(ProtocolError::InvalidFrameFormat(format!(
+                "Frame length {} exceeds maximum {}",
+                frame_length, self.max_frame_size
+            )));
+        }
+        Ok(self.extract_frame(frame_length))
     }
 
     /// 丢弃指定长度的数据
@@ -191,6 +310,8 @@ impl ReceiveBuffer {
     /// 清空缓冲区
     pub fn clear(&mut self) {
         self.buffer.clear();
+        self.pending_bits = 0;
+        self.pending_bit_count = 0;
     }
 
     /// 查看缓冲区内容（不移除）
@@ -235,6 +356,126 @@ mod tests {
         assert_eq!(sync_pos, Some(2));
     }
 
+    #[test]
+    fn test_align_to_sync_with_garbage_prefix() {
+        let mut buffer = ReceiveBuffer::new(1024);
+        buffer.set_synchronizer(FrameSynchronizer::new(SyncMode::AsmSearch {
+            marker: vec![0x1A, 0xCF, 0xFC, 0x1D],
+            max_bit_slip: 7,
+        }));
+
+        buffer.append(&[0xDE, 0xAD, 0xBE, 0xEF, 0x00]);
+        assert_eq!(buffer.align_to_sync(), None);
+        assert_eq!(buffer.len(), 5);
+
+        buffer.append(&[0x1A, 0xCF, 0xFC, 0x1D, 0x01, 0x02]);
+        assert_eq!(buffer.align_to_sync(), Some(40));
+        assert_eq!(
+            buffer.extract_frame(6),
+            Some(vec![0x1A, 0xCF, 0xFC, 0x1D, 0x01, 0x02])
+        );
+    }
+
+    #[test]
+    fn test_align_to_sync_with_bit_slip() {
+        let frame = [0x1A, 0xCF, 0xFC, 0x1D, 0x12, 0x34, 0x56];
+        // 前置2字节垃圾数据，整条流右移5位
+        let stream: Vec<u8> = [0x3C, 0xA5].iter().chain(&frame).copied().collect();
+        let mut shifted = vec![0b0000_0101 << 3 | stream[0] >> 5];
+        for pair in stream.windows(2) {
+            shifted.push(pair[0] << 3 | pair[1] >> 5);
+        }
+
+        let mut buffer = ReceiveBuffer::new(1024);
+        buffer.set_synchronizer(FrameSynchronizer::new(SyncMode::AsmSearch {
+            marker: frame[..4].to_vec(),
+            max_bit_slip: 7,
+        }));
+        // 帧的最后5位随下一批数据到达
+        let (head, tail) = shifted.split_at(7);
+        buffer.append(head);
+        assert_eq!(buffer.align_to_sync(), Some(21));
+        buffer.append(tail);
+        buffer.append(&[(frame[6] << 3) | 0b101]);
+
+        assert_eq!(buffer.extract_frame(frame.len()), Some(frame.to_vec()));
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_extract_frame_by_realigns_bit_slipped_asm() {
+        let frame = [0x1A, 0xCF, 0xFC, 0x1D, 0x12, 0x34];
+        // 前置1字节垃圾数据，整条流右移3位，末尾补齐
+        let stream: Vec<u8> = [0xFF]
+            .iter()
+            .chain(&frame)
+            .chain(&[0x00])
+            .copied()
+            .collect();
+        let mut shifted = vec![stream[0] >> 3];
+        for pair in stream.windows(2) {
+            shifted.push(pair[0] << 5 | pair[1] >> 3);
+        }
+
+        let mut buffer = ReceiveBuffer::new(1024);
+        buffer.set_synchronizer(FrameSynchronizer::new(SyncMode::AsmSearch {
+            marker: frame[..4].to_vec(),
+            max_bit_slip: 7,
+        }));
+        buffer.append(&shifted);
+
+        // 同步标志不在字节边界，按字节偏移搜索不返回结果
+        assert_eq!(buffer.find_sync_marker(), None);
+        assert_eq!(
+            buffer
+                .extract_frame_by(&FrameBoundary::Fixed(frame.len()))
+                .unwrap(),
+            Some(frame.to_vec())
+        );
+    }
+
+    #[test]
+    fn test_take_fixed_across_partial_chunks() {
+        let mut buffer = ReceiveBuffer::new(1024);
+
+        buffer.append(&[0x01, 0x02]);
+        assert_eq!(buffer.take_fixed(4), None);
+        buffer.append(&[0x03]);
+        assert_eq!(buffer.take_fixed(4), None);
+        buffer.append(&[0x04, 0x05, 0x06]);
+        assert_eq!(buffer.take_fixed(4), Some(vec![0x01, 0x02, 0x03, 0x04]));
+        assert_eq!(buffer.take_fixed(4), None);
+        assert_eq!(buffer.len(), 2);
+
+        buffer.append(&[0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C]);
+        assert_eq!(buffer.take_fixed(4), Some(vec![0x05, 0x06, 0x07, 0x08]));
+        assert_eq!(buffer.take_fixed(4), Some(vec![0x09, 0x0A, 0x0B, 0x0C]));
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.take_fixed(0), None);
+    }
+
+    #[test]
+    fn test_take_delimited_across_partial_chunks() {
+        let mut buffer = ReceiveBuffer::new(1024);
+        let delim = [0x0D, 0x0A];
+
+        buffer.append(b"AB");
+        assert_eq!(buffer.take_delimited(&delim), None);
+        // 分隔符被拆分在两次接收中
+        buffer.append(b"C\r");
+        assert_eq!(buffer.take_delimited(&delim), None);
+        buffer.append(b"\nDE\r\nF");
+        assert_eq!(buffer.take_delimited(&delim), Some(b"ABC\r\n".to_vec()));
+        assert_eq!(buffer.take_delimited(&delim), Some(b"DE\r\n".to_vec()));
+        assert_eq!(buffer.take_delimited(&delim), None);
+        assert_eq!(buffer.peek(1), Some(b"F".to_vec()));
+
+        buffer.append(b"\r\n");
+        assert_eq!(buffer.take_delimited(&delim), Some(b"F\r\n".to_vec()));
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.take_delimited(&[]), None);
+    }
+
     #[test]
     fn test_calculate_frame_length() {
         let mut buffer = ReceiveBuffer::new(1024);
@@ -272,6 +513,40 @@ mod tests {
         assert_eq!(frame[1], 0x90);
     }
 
+    #[test]
+    fn test_extract_frames_by_boundary() {
+        let mut buffer = ReceiveBuffer::new(1024);
+        let boundary = FrameBoundary::LengthField {
+            offset: 0,
+            width: 1,
+            endian: ByteOrder::BigEndian,
+            adjust: 1,
+        };
+
+        // 第二帧分两次到达
+        buffer.append(&[0x02, 0xAA, 0xBB, 0x03, 0x01]);
+        assert_eq!(
+            buffer.extract_frame_by(&boundary).unwrap(),
+            Some(vec![0x02, 0xAA, 0xBB])
+        );
+        assert_eq!(buffer.extract_frame_by(&boundary).unwrap(), None);
+        buffer.append(&[0x02, 0x03]);
+        assert_eq!(
+            buffer.extract_frame_by(&boundary).unwrap(),
+            Some(vec![0x03, 0x01, 0x02, 0x03])
+        );
+        assert!(buffer.is_empty());
+
+        buffer.append(&[0x01, 0x02, 0x03]);
+        assert_eq!(
+            buffer.extract_frame_by(&FrameBoundary::Fixed(2)).unwrap(),
+            Some(vec![0x01, 0x02])
+        );
+        assert!(buffer
+            .extract_frame_by(&FrameBoundary::Fixed(2048))
+            .is_err());
+    }
+
     #[test]
     fn test_buffer_overflow_protection() {
         let mut buffer = ReceiveBuffer::new(100);
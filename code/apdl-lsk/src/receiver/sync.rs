@@ -11,6 +11,8 @@ pub enum SyncMode {
     FixedMarker(Vec<u8>),
     /// 模式搜索（支持掩码）
     PatternSearch { pattern: Vec<u8>, mask: Vec<u8> },
+    /// 附加同步标志（ASM，如0x1ACFFC1D）搜索，允许同步标志相对字节边界滑动至多`max_bit_slip`位
+    AsmSearch { marker: Vec<u8>, max_bit_slip: u8 },
     /// 伪随机序列锁定（暂不实现）
     PseudoRandomLock,
 }
@@ -46,12 +48,17 @@ impl FrameSynchronizer {
     /// - `buffer`: 数据缓冲区
     ///
     /// # 返回
-    /// - `Some(offset)`: 找到同步字，返回偏移量
-    /// - `None`: 未找到同步字
+    /// - `Some(offset)`: 找到同步字，返回字节偏移量
+    /// - `None`: 未找到同步字；ASM搜索模式下同步标志不在字节边界时也返回`None`，
+    ///   此时应使用`search_sync_bits`
     pub fn search_sync(&self, buffer: &VecDeque<u8>) -> Option<usize> {
         match &self.mode {
             SyncMode::FixedMarker(marker) => self.search_fixed_marker(buffer, marker),
             SyncMode::PatternSearch { pattern, mask } => self.search_pattern(buffer, pattern, mask),
+            SyncMode::AsmSearch { .. } => self
+                .search_sync_bits(buffer)
+                .filter(|bits| bits % 8 == 0)
+                .map(|bits| bits / 8),
             SyncMode::PseudoRandomLock => {
                 // 伪随机序列锁定暂不实现
                 None
@@ -59,6 +66,59 @@ impl FrameSynchronizer {
         }
     }
 
+    /// 在数据缓冲区中搜索同步字，返回以bit计的帧起始偏移
+    ///
+    /// 字节对齐的同步模式返回字节偏移乘8；ASM搜索模式下偏移可落在字节内部
+    pub fn search_sync_bits(&self, buffer: &VecDeque<u8>) -> Option<usize> {
+        match &self.mode {
+            SyncMode::AsmSearch {
+                marker,
+                max_bit_slip,
+            } => self.search_asm(buffer, marker, *max_bit_slip),
+            _ => self.search_sync(buffer).map(|offset| offset * 8),
+        }
+    }
+
+    /// 逐字节搜索ASM，每个字节内再逐位尝试滑动窗口内的偏移
+    fn search_asm(&self, buffer: &VecDeque<u8>, marker: &[u8], max_bit_slip: u8) -> Option<usize> {
+        if marker.is_empty() || buffer.len() < marker.len() {
+            return None;
+        }
+
+        // 滑动8位即等同于下一字节的对齐位置
+        let max_shift = max_bit_slip.min(7) as usize;
+        let total_bits = buffer.len() * 8;
+        let marker_bits = marker.len() * 8;
+        for byte_offset in 0..=(buffer.len() - marker.len()) {
+            for shift in 0..=max_shift {
+                let bit_offset = byte_offset * 8 + shift;
+                if bit_offset + marker_bits > total_bits {
+                    break;
+                }
+                let matched = marker.iter().enumerate().all(|(j, &marker_byte)| {
+                    Self::byte_at_bit(buffer, bit_offset + j * 8) == marker_byte
+                });
+                if matched {
+                    return Some(bit_offset);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// 读取从指定bit偏移开始的8位（MSB优先），超出缓冲区的部分补0
+    fn byte_at_bit(buffer: &VecDeque<u8>, bit_offset: usize) -> u8 {
+        let index = bit_offset / 8;
+        let shift = bit_offset % 8;
+        let high = buffer.get(index).copied().unwrap_or(0);
+        if shift == 0 {
+            return high;
+        }
+        let low = buffer.get(index + 1).copied().unwrap_or(0);
+        (high << shift) | (low >> (8 - shift))
+    }
+
     /// 搜索固定同步字
     fn search_fixed_marker(&self, buffer: &VecDeque<u8>, marker: &[u8]) -> Option<usize> {
         if marker.is_empty() || buffer.len() < marker.len() {
@@ -215,6 +275,55 @@ mod tests {
         assert_eq!(pos, None);
     }
 
+    #[test]
+    fn test_asm_search_skips_leading_garbage() {
+        let sync = FrameSynchronizer::new(SyncMode::AsmSearch {
+            marker: vec![0x1A, 0xCF, 0xFC, 0x1D],
+            max_bit_slip: 0,
+        });
+
+        let mut buffer = VecDeque::new();
+        buffer.extend(&[0x55, 0x1A, 0xCF, 0x00, 0x1A, 0xCF, 0xFC, 0x1D, 0x01]);
+
+        assert_eq!(sync.search_sync(&buffer), Some(4));
+        assert_eq!(sync.search_sync_bits(&buffer), Some(32));
+    }
+
+    #[test]
+    fn test_asm_search_finds_bit_shifted_marker() {
+        let marker = [0x1A, 0xCF, 0xFC, 0x1D];
+        // 同步标志整体右移3位，前面填充1bit
+        let shifted = shift_right(&[0xFF, 0x1A, 0xCF, 0xFC, 0x1D, 0x00], 3, 0b111);
+        let mut buffer = VecDeque::new();
+        buffer.extend(&shifted);
+
+        let within_slip = FrameSynchronizer::new(SyncMode::AsmSearch {
+            marker: marker.to_vec(),
+            max_bit_slip: 3,
+        });
+        assert_eq!(within_slip.search_sync_bits(&buffer), Some(11));
+        // 同步标志不在字节边界，按字节偏移搜索不返回结果
+        assert_eq!(within_slip.search_sync(&buffer), None);
+
+        let byte_aligned = FrameSynchronizer::new(SyncMode::AsmSearch {
+            marker: marker.to_vec(),
+            max_bit_slip: 2,
+        });
+        assert_eq!(byte_aligned.search_sync_bits(&buffer), None);
+    }
+
+    /// 将数据整体右移`shift`位，高位用`fill`填充
+    fn shift_right(data: &[u8], shift: u32, fill: u8) -> Vec<u8> {
+        let mut carry = fill;
+        let mut shifted = Vec::with_capacity(data.len() + 1);
+        for &byte in data {
+            shifted.push((carry << (8 - shift)) | (byte >> shift));
+            carry = byte & ((1 << shift) - 1);
+        }
+        shifted.push(carry << (8 - shift));
+        shifted
+    }
+
     #[test]
     fn test_insufficient_buffer() {
         let sync = FrameSynchronizer::new(SyncMode::FixedMarker(vec![0xEB, 0x90]));
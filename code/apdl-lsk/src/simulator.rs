@@ -3,6 +3,17 @@
 //! 实现纯软件协议仿真功能
 
 use apdl_core::{error::ProtocolError, ProtocolUnit};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::traffic_generator::TrafficGenerator;
+
+/// pcap链路类型：DLT_USER0，用于没有标准链路类型的自定义协议帧
+pub const PCAP_LINKTYPE_USER0: u32 = 147;
+
+/// pcap快照长度，超过该长度的帧在记录中被截断
+const PCAP_SNAPLEN: u32 = 65535;
 
 /// 仿真器配置
 #[derive(Debug, Clone)]
@@ -29,6 +40,16 @@ pub struct ProtocolSimulator {
     units: Vec<Box<dyn ProtocolUnit>>,
     config: SimulatorConfig,
     stats: SimulationStats,
+    captured_frames: Vec<CapturedFrame>,
+    capture_clock_us: u64, // 捕获时钟，跨多次捕获连续推进
+    pcap_link_type: u32,   // 导出pcap时的链路类型
+}
+
+/// 仿真过程中捕获的帧
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedFrame {
+    pub timestamp_us: u64, // 相对仿真开始的时间戳（微秒）
+    pub data: Vec<u8>,
 }
 
 /// 仿真统计信息
@@ -47,6 +68,9 @@ impl ProtocolSimulator {
             units: Vec::new(),
             config,
             stats: SimulationStats::default(),
+            captured_frames: Vec::new(),
+            capture_clock_us: 0,
+            pcap_link_type: PCAP_LINKTYPE_USER0,
         }
     }
 
@@ -93,6 +117,73 @@ impl ProtocolSimulator {
         Ok(received_data)
     }
 
+    /// 按流量生成器的时隙捕获仿真流量
+    ///
+    /// 每个时隙推进`interval_ms`，生成器输出的帧经各协议单元打包后以时隙起始时刻为时间戳记录；
+    /// 没有帧输出的时隙只推进时间
+    pub fn capture_traffic(
+        &mut self,
+        generator: &mut TrafficGenerator,
+        window_ms: u64,
+    ) -> Result<(), ProtocolError> {
+        let interval_ms = generator.get_config().interval_ms.max(1);
+        for _ in 0..window_ms / interval_ms {
+            let timestamp_us = self.capture_clock_us;
+            self.capture_clock_us += interval_ms * 1000;
+            let Some(mut data) = generator.next_frame() else {
+                continue;
+            };
+            for unit in &self.units {
+                data = unit.pack(&data)?;
+            }
+            self.captured_frames
+                .push(CapturedFrame { timestamp_us, data });
+        }
+        Ok(())
+    }
+
+    /// 设置导出pcap时的链路类型，默认为`PCAP_LINKTYPE_USER0`
+    pub fn set_pcap_link_type(&mut self, link_type: u32) {
+        self.pcap_link_type = link_type;
+    }
+
+    /// 获取已捕获的帧
+    pub fn captured_frames(&self) -> &[CapturedFrame] {
+        &self.captured_frames
+    }
+
+    /// 将已捕获的帧导出为经典pcap文件
+    ///
+    /// 链路类型由`set_pcap_link_type`设置，每个捕获帧对应一条包记录
+    pub fn export_pcap<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_pcap(&mut writer)?;
+        writer.flush()
+    }
+
+    /// 将已捕获的帧以经典pcap格式（小端、微秒时间戳）写入
+    pub fn write_pcap<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        // 全局头：magic、版本2.4、时区、时间戳精度、快照长度、链路类型
+        writer.write_all(&0xA1B2_C3D4u32.to_le_bytes())?;
+        writer.write_all(&2u16.to_le_bytes())?;
+        writer.write_all(&4u16.to_le_bytes())?;
+        writer.write_all(&0i32.to_le_bytes())?;
+        writer.write_all(&0u32.to_le_bytes())?;
+        writer.write_all(&PCAP_SNAPLEN.to_le_bytes())?;
+        writer.write_all(&self.pcap_link_type.to_le_bytes())?;
+
+        for frame in &self.captured_frames {
+            let captured_len = frame.data.len().min(PCAP_SNAPLEN as usize);
+            // 包记录头：秒、微秒、记录长度、原始长度
+            writer.write_all(&((frame.timestamp_us / 1_000_000) as u32).to_le_bytes())?;
+            writer.write_all(&((frame.timestamp_us % 1_000_000) as u32).to_le_bytes())?;
+            writer.write_all(&(captured_len as u32).to_le_bytes())?;
+            writer.write_all(&(frame.data.len() as u32).to_le_bytes())?;
+            writer.write_all(&frame.data[..captured_len])?;
+        }
+        Ok(())
+    }
+
     pub fn get_stats(&self) -> &SimulationStats {
         &self.stats
     }
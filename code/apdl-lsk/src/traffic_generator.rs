@@ -2,6 +2,11 @@
 //!
 //! 实现协议流量的模拟生成
 
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+use std::collections::HashMap;
+use std::time::Duration;
+
 /// 流量类型
 #[derive(Debug, Clone)]
 pub enum TrafficType {
@@ -35,11 +40,24 @@ impl Default for TrafficConfig {
     }
 }
 
+/// 带权重的帧模板
+#[derive(Debug, Clone)]
+struct FrameTemplate {
+    name: String,
+    frame: Vec<u8>,
+    weight: f64,
+}
+
 /// 流量生成器
 pub struct TrafficGenerator {
     config: TrafficConfig,
     sequence_number: u32,
     last_generated: std::time::Instant,
+    idle_frame: Option<Vec<u8>>, // 空闲填充帧（CCSDS idle/fill帧）
+    pending_bits: f64,           // 数据源已产生但尚未发送的数据量（bit）
+    templates: Vec<FrameTemplate>,
+    template_rng: StdRng,
+    template_counts: HashMap<String, usize>,
 }
 
 impl TrafficGenerator {
@@ -48,13 +66,107 @@ impl TrafficGenerator {
             config,
             sequence_number: 0,
             last_generated: std::time::Instant::now(),
+            idle_frame: None,
+            pending_bits: 0.0,
+            templates: Vec::new(),
+            template_rng: StdRng::seed_from_u64(0),
+            template_counts: HashMap::new(),
+        }
+    }
+
+    /// 创建泊松到达过程的流量源
+    ///
+    /// 帧到达间隔服从均值为`1/lambda_per_sec`秒的指数分布，包大小使用默认配置
+    pub fn poisson(lambda_per_sec: f64, seed: u64) -> PoissonArrivals {
+        Self::new(TrafficConfig::default()).into_poisson(lambda_per_sec, seed)
+    }
+
+    /// 以当前配置的包大小生成泊松到达过程的流量
+    pub fn into_poisson(self, lambda_per_sec: f64, seed: u64) -> PoissonArrivals {
+        PoissonArrivals {
+            generator: self,
+            lambda_per_sec,
+            rng: StdRng::seed_from_u64(seed),
+            elapsed: Duration::ZERO,
         }
     }
 
+    /// 启用空闲帧填充
+    ///
+    /// 启用后`next_frame`在没有待发送数据的时隙输出空闲帧，以保持恒定速率（CBR）下行
+    pub fn with_idle_fill(mut self, idle_frame: Vec<u8>) -> Self {
+        self.idle_frame = Some(idle_frame);
+        self
+    }
+
+    /// 添加帧模板
+    ///
+    /// 添加模板后`generate_packet`按归一化权重随机选取模板帧输出（如由FrameAssembler
+    /// 预先组装的各类CCSDS包）；权重非正的模板不会被选中
+    pub fn add_template(&mut self, name: String, frame: Vec<u8>, weight: f64) {
+        let weight = if weight.is_finite() {
+            weight.max(0.0)
+        } else {
+            0.0
+        };
+        self.templates.push(FrameTemplate {
+            name,
+            frame,
+            weight,
+        });
+    }
+
+    /// 设置模板选择的随机数种子，相同种子产生相同的模板序列
+    pub fn set_template_seed(&mut self, seed: u64) {
+        self.template_rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// 获取各模板已生成的帧数
+    pub fn generated_stats(&self) -> HashMap<String, usize> {
+        self.template_counts.clone()
+    }
+
+    /// 获取下一个时隙的帧
+    ///
+    /// 每次调用推进一个`interval_ms`时隙，数据源在该时隙内按`rate_kbps`产生数据。
+    /// 累积的数据足够一个数据包时输出数据包；否则启用空闲填充时输出空闲帧，未启用时返回`None`
+    pub fn next_frame(&mut self) -> Option<Vec<u8>> {
+        // kbps × ms = bit
+        self.pending_bits += self.config.rate_kbps * self.config.interval_ms as f64;
+
+        let packet_bits = (self.packet_size_for(self.sequence_number + 1) * 8) as f64;
+        if self.pending_bits >= packet_bits {
+            self.pending_bits -= packet_bits;
+            Some(self.generate_packet())
+        } else {
+            self.idle_frame.clone()
+        }
+    }
+
+    /// 生成指定时间窗口内各时隙输出的帧
+    pub fn frames_in_window(&mut self, window_ms: u64) -> Vec<Vec<u8>> {
+        let slots = window_ms / self.config.interval_ms.max(1);
+        (0..slots).filter_map(|_| self.next_frame()).collect()
+    }
+
+    /// 判断帧是否为空闲填充帧
+    pub fn is_idle_frame(&self, frame: &[u8]) -> bool {
+        self.idle_frame.as_deref() == Some(frame)
+    }
+
     /// 生成单个数据包
     pub fn generate_packet(&mut self) -> Vec<u8> {
         self.sequence_number += 1;
 
+        if let Some(index) = self.pick_template() {
+            let template = &self.templates[index];
+            *self
+                .template_counts
+                .entry(template.name.clone())
+                .or_insert(0) += 1;
+            return template.frame.clone();
+        }
+
         // 根据配置生成包大小
         let packet_size = self.get_current_packet_size();
 
@@ -83,8 +195,35 @@ impl TrafficGenerator {
         packets
     }
 
+    /// 按归一化权重选取模板，没有可选模板时返回`None`
+    fn pick_template(&mut self) -> Option<usize> {
+        let total: f64 = self.templates.iter().map(|t| t.weight).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let mut target = self.template_rng.random_range(0.0..1.0) * total;
+        let mut last_candidate = None;
+        for (index, template) in self.templates.iter().enumerate() {
+            if template.weight <= 0.0 {
+                continue;
+            }
+            if target < template.weight {
+                return Some(index);
+            }
+            target -= template.weight;
+            last_candidate = Some(index);
+        }
+        // 浮点累加误差导致未命中时取最后一个可选模板
+        last_candidate
+    }
+
     /// 根据流量类型获取当前包大小
     fn get_current_packet_size(&self) -> usize {
+        self.packet_size_for(self.sequence_number)
+    }
+
+    /// 根据流量类型获取指定序列号的包大小
+    fn packet_size_for(&self, sequence_number: u32) -> usize {
         match self.config.traffic_type {
             TrafficType::Constant => {
                 // 恒定大小，取平均值
@@ -92,7 +231,7 @@ impl TrafficGenerator {
             }
             TrafficType::Burst => {
                 // 突发模式，偶尔大包
-                if self.sequence_number % 10 == 0 {
+                if sequence_number % 10 == 0 {
                     self.config.burst_size.min(self.config.packet_size_max)
                 } else {
                     self.config.packet_size_min
@@ -103,12 +242,12 @@ impl TrafficGenerator {
                 let range = self.config.packet_size_max - self.config.packet_size_min;
                 // 使用简单的伪随机算法
                 let size = self.config.packet_size_min
-                    + ((self.sequence_number as usize * 1103515245 + 12345) % (range + 1));
+                    + ((sequence_number as usize * 1103515245 + 12345) % (range + 1));
                 size.clamp(self.config.packet_size_min, self.config.packet_size_max)
             }
             TrafficType::Periodic => {
                 // 周期性模式
-                if self.sequence_number % 5 == 0 {
+                if sequence_number % 5 == 0 {
                     self.config.packet_size_max
                 } else {
                     self.config.packet_size_min
@@ -121,6 +260,8 @@ impl TrafficGenerator {
     pub fn reset(&mut self) {
         self.sequence_number = 0;
         self.last_generated = std::time::Instant::now();
+        self.pending_bits = 0.0;
+        self.template_counts.clear();
     }
 
     /// 获取当前配置
@@ -133,3 +274,163 @@ impl TrafficGenerator {
         self.config = config;
     }
 }
+
+/// 泊松到达过程的流量源
+///
+/// 迭代产生`(到达时刻, 数据包)`，到达时刻为相对流量起点的累计时间；
+/// 相同种子产生相同的到达时刻序列
+pub struct PoissonArrivals {
+    generator: TrafficGenerator,
+    lambda_per_sec: f64,
+    rng: StdRng,
+    elapsed: Duration,
+}
+
+impl PoissonArrivals {
+    /// 获取平均到达率（帧/秒）
+    pub fn lambda_per_sec(&self) -> f64 {
+        self.lambda_per_sec
+    }
+
+    /// 获取最近一帧的到达时刻
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+impl Iterator for PoissonArrivals {
+    type Item = (Duration, Vec<u8>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !(self.lambda_per_sec > 0.0 && self.lambda_per_sec.is_finite()) {
+            return None;
+        }
+        // 逆变换采样：-ln(1-U)/λ，U∈[0,1)保证对数参数为正
+        let gap = -(1.0 - self.rng.random_range(0.0f64..1.0)).ln() / self.lambda_per_sec;
+        self.elapsed += Duration::from_secs_f64(gap);
+        Some((self.elapsed, self.generator.generate_packet()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cbr_config() -> TrafficConfig {
+        TrafficConfig {
+            traffic_type: TrafficType::Constant,
+            rate_kbps: 4.0,
+            packet_size_min: 100,
+            packet_size_max: 100,
+            interval_ms: 50,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_idle_fill_maintains_cbr_slot_count() {
+        // CCSDS idle帧：首字节为帧头，其余为填充
+        let idle_frame = vec![0x03, 0xFF, 0xFF, 0xFF];
+        let mut generator = TrafficGenerator::new(cbr_config()).with_idle_fill(idle_frame.clone());
+
+        // 10秒窗口，50ms时隙：200个时隙
+        let frames = generator.frames_in_window(10_000);
+        assert_eq!(frames.len(), 200);
+
+        // 4kbps × 10s = 40000bit = 50个100字节数据包，其余时隙为空闲帧
+        let idle_count = frames.iter().filter(|f| generator.is_idle_frame(f)).count();
+        assert_eq!(idle_count, 150);
+        assert_eq!(frames.len() - idle_count, 50);
+        assert!(frames.iter().all(|f| f == &idle_frame || f.len() == 100));
+    }
+
+    #[test]
+    fn test_poisson_mean_gap_matches_rate() {
+        let lambda = 250.0;
+        let samples = 100_000;
+        let arrivals: Vec<Duration> = TrafficGenerator::poisson(lambda, 99)
+            .take(samples)
+            .map(|(timestamp, _)| timestamp)
+            .collect();
+
+        assert!(arrivals.windows(2).all(|pair| pair[0] <= pair[1]));
+        let mean_gap = arrivals[samples - 1].as_secs_f64() / samples as f64;
+        assert!(
+            (mean_gap * lambda - 1.0).abs() < 0.02,
+            "mean gap {mean_gap}, expected {}",
+            1.0 / lambda
+        );
+
+        // 指数分布的标准差等于均值
+        let gaps: Vec<f64> = std::iter::once(arrivals[0].as_secs_f64())
+            .chain(
+                arrivals
+                    .windows(2)
+                    .map(|pair| (pair[1] - pair[0]).as_secs_f64()),
+            )
+            .collect();
+        let variance = gaps.iter().map(|g| (g - mean_gap).powi(2)).sum::<f64>() / samples as f64;
+        assert!((variance.sqrt() * lambda - 1.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_poisson_is_reproducible_and_uses_config() {
+        let timestamps = |seed| {
+            TrafficGenerator::poisson(10.0, seed)
+                .take(50)
+                .map(|(timestamp, _)| timestamp)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(timestamps(1), timestamps(1));
+        assert_ne!(timestamps(1), timestamps(2));
+
+        let mut arrivals = TrafficGenerator::new(cbr_config()).into_poisson(10.0, 1);
+        let (_, packet) = arrivals.next().unwrap();
+        assert_eq!(packet.len(), 100);
+        assert_eq!(&packet[..4], &1u32.to_le_bytes());
+
+        assert_eq!(TrafficGenerator::poisson(0.0, 1).next(), None);
+    }
+
+    #[test]
+    fn test_weighted_templates_match_proportions() {
+        let mut generator = TrafficGenerator::new(TrafficConfig::default());
+        generator.set_template_seed(2024);
+        generator.add_template("housekeeping".to_string(), vec![0x08, 0x01], 6.0);
+        generator.add_template("science".to_string(), vec![0x08, 0x02], 3.0);
+        generator.add_template("event".to_string(), vec![0x08, 0x03], 1.0);
+        generator.add_template("disabled".to_string(), vec![0x08, 0x04], 0.0);
+
+        let total = 50_000;
+        let packets = generator.generate_batch(total);
+        let stats = generator.generated_stats();
+
+        assert_eq!(stats.values().sum::<usize>(), total);
+        assert!(!stats.contains_key("disabled"));
+        for (name, weight, tag) in [
+            ("housekeeping", 0.6, 0x01),
+            ("science", 0.3, 0x02),
+            ("event", 0.1, 0x03),
+        ] {
+            let observed = stats[name] as f64 / total as f64;
+            assert!(
+                (observed - weight).abs() < 0.01,
+                "{name}: observed {observed}, expected {weight}"
+            );
+            let emitted = packets.iter().filter(|p| p[1] == tag).count();
+            assert_eq!(emitted, stats[name]);
+        }
+
+        generator.reset();
+        assert!(generator.generated_stats().is_empty());
+    }
+
+    #[test]
+    fn test_without_idle_fill_only_data_frames() {
+        let mut generator = TrafficGenerator::new(cbr_config());
+
+        let frames = generator.frames_in_window(10_000);
+        assert_eq!(frames.len(), 50);
+        assert!(frames.iter().all(|f| f.len() == 100));
+    }
+}
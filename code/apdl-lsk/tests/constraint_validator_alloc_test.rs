@@ -0,0 +1,58 @@
+//! 约束验证器内存分配测试
+//!
+//! 验证预编译后的约束在热循环中验证时不分配内存
+
+use apdl_core::Constraint;
+use apdl_lsk::ConstraintValidator;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// 统计当前线程内存分配次数的分配器
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(|count| count.get())
+}
+
+#[test]
+fn test_validate_does_not_allocate_per_call() {
+    let validator = ConstraintValidator::new(&[
+        Constraint::Range(0, 100_000),
+        Constraint::Enum((0..1000).map(|v| (format!("V{v}"), v * 7)).collect()),
+        Constraint::Custom("pattern(^[0-9]*[05]$)".to_string()),
+    ]);
+
+    // 预热：正则表达式首次匹配时初始化内部缓存
+    assert!(validator.validate(35));
+
+    let before = allocations();
+    let mut valid = 0usize;
+    for value in 0..100_000u64 {
+        if validator.validate(value) {
+            valid += 1;
+        }
+    }
+    let after = allocations();
+
+    assert_eq!(after - before, 0, "validate allocated during the hot loop");
+    // 7的倍数且不超过6993，末位为0或5：即35的倍数
+    assert_eq!(valid, (0..1000u64).filter(|v| (v * 7) % 5 == 0).count());
+}
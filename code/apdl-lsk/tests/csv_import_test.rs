@@ -0,0 +1,76 @@
+//! 字段值CSV导入测试
+//!
+//! 验证从CSV文件导入字段值、导入结果组帧与基准样例一致，以及格式错误带行号报告
+
+use apdl_lsk::data_generator::custom_import::ImportError;
+use apdl_lsk::data_generator::DataImporter;
+use apdl_lsk::fixtures::ccsds_space_packet;
+use apdl_poem::FrameAssembler;
+use std::path::PathBuf;
+
+fn fixture_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/field_values.csv")
+}
+
+#[test]
+fn test_csv_fixture_matches_reference_values() {
+    let (_, expected_values, _) = ccsds_space_packet();
+    let values = DataImporter::from_csv(fixture_path()).unwrap();
+    assert_eq!(values, expected_values);
+}
+
+#[test]
+fn test_csv_values_round_trip_through_assembler() {
+    let (package, _, expected_frame) = ccsds_space_packet();
+    let values = DataImporter::from_csv(fixture_path()).unwrap();
+
+    let mut assembler = FrameAssembler::new();
+    for layer in &package.layers {
+        for unit in &layer.units {
+            assembler.add_field(unit.clone());
+        }
+        for rule in &layer.rules {
+            assembler.add_semantic_rule(rule.clone());
+        }
+    }
+    for (field_name, value) in &values {
+        assembler.set_field_value(field_name, value).unwrap();
+    }
+    let frame = assembler.assemble_frame().unwrap();
+    assert_eq!(frame, expected_frame);
+
+    // 导出为CSV后再次导入应得到相同字段值
+    let csv: String = values
+        .iter()
+        .map(|(field_name, value)| {
+            let hex: String = value.iter().map(|byte| format!("{byte:02X}")).collect();
+            format!("{field_name},{hex}\n")
+        })
+        .collect();
+    assert_eq!(DataImporter::from_csv_str(&csv).unwrap(), values);
+}
+
+#[test]
+fn test_csv_errors_report_line_number() {
+    for (content, line) in [
+        ("# 注释\napid,0123\nseq_flags\n", "第3行"),
+        ("apid,012\n", "第1行"),
+        ("\napid,01G3\n", "第2行"),
+        ("apid,0123\n,00\n", "第2行"),
+    ] {
+        match DataImporter::from_csv_str(content) {
+            Err(ImportError::ParseError(message)) => {
+                assert!(message.contains(line), "{message}")
+            }
+            other => panic!("expected parse error for {content:?}, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn test_csv_missing_file() {
+    assert!(matches!(
+        DataImporter::from_csv(fixture_path().with_file_name("missing.csv")),
+        Err(ImportError::FileError(_))
+    ));
+}
@@ -0,0 +1,131 @@
+//! 拆包约束校验测试
+//!
+//! 验证FrameDisassembler在提取字段后按约束收集校验错误
+
+use apdl_core::*;
+use apdl_lsk::{FrameDisassembler, LayeredDisassembler};
+
+fn field(name: &str, unit_type: UnitType, constraint: Option<Constraint>) -> SyntaxUnit {
+    let length = match unit_type {
+        UnitType::Bit(bits) => LengthDesc {
+            size: bits as usize,
+            unit: LengthUnit::Bit,
+        },
+        UnitType::Uint(bits) => LengthDesc {
+            size: bits as usize / 8,
+            unit: LengthUnit::Byte,
+        },
+        _ => LengthDesc {
+            size: 4,
+            unit: LengthUnit::Byte,
+        },
+    };
+    SyntaxUnit {
+        constraint,
+        ..SyntaxUnit::new(
+            name,
+            unit_type,
+            length,
+            ScopeDesc::Global("test".to_string()),
+        )
+    }
+}
+
+/// 同步标志(4字节) + 帧类型(4bit枚举) + 计数(4bit范围) + 数据(2字节)
+fn create_disassembler() -> FrameDisassembler {
+    let mut disassembler = FrameDisassembler::new();
+    disassembler.add_field(field(
+        "sync",
+        UnitType::Uint(32),
+        Some(Constraint::FixedValue(0x1ACF_FC1D)),
+    ));
+    disassembler.add_field(field(
+        "frame_type",
+        UnitType::Bit(4),
+        Some(Constraint::Enum(vec![
+            ("TM".to_string(), 0),
+            ("TC".to_string(), 1),
+            ("AOS".to_string(), 2),
+        ])),
+    ));
+    disassembler.add_field(field(
+        "count",
+        UnitType::Bit(4),
+        Some(Constraint::Range(0, 9)),
+    ));
+    disassembler.add_field(field("data", UnitType::Uint(16), None));
+    disassembler
+}
+
+#[test]
+fn test_valid_frame_has_no_errors() {
+    let disassembler = create_disassembler();
+    let frame = [0x1A, 0xCF, 0xFC, 0x1D, 0x13, 0xAB, 0xCD];
+
+    let (fields, errors) = disassembler.disassemble_frame_validated(&frame).unwrap();
+
+    assert_eq!(fields.len(), 4);
+    assert!(errors.is_empty(), "unexpected errors: {errors:?}");
+}
+
+#[test]
+fn test_sync_marker_mismatch_is_reported() {
+    let disassembler = create_disassembler();
+    let frame = [0x1A, 0xCF, 0xFC, 0x1E, 0x13, 0xAB, 0xCD];
+
+    let (fields, errors) = disassembler.disassemble_frame_validated(&frame).unwrap();
+
+    // 约束违反不中断解析
+    assert_eq!(fields[0].value, vec![0x1A, 0xCF, 0xFC, 0x1E]);
+    assert_eq!(fields[3].value, vec![0xAB, 0xCD]);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].field_name, "sync");
+    assert!(errors[0].error_message.contains("固定值 449838109"));
+    assert!(errors[0].error_message.contains("0x1ACFFC1E"));
+}
+
+#[test]
+fn test_out_of_range_enum_is_reported() {
+    let disassembler = create_disassembler();
+    // frame_type = 7 不在枚举中，count = 0xC 超出范围
+    let frame = [0x1A, 0xCF, 0xFC, 0x1D, 0x7C, 0x00, 0x00];
+
+    let (_, errors) = disassembler.disassemble_frame_validated(&frame).unwrap();
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].field_name, "frame_type");
+    assert!(errors[0].error_message.contains("枚举 [TM=0, TC=1, AOS=2]"));
+    assert!(errors[0].error_message.contains("actual 7"));
+    assert_eq!(errors[1].field_name, "count");
+    assert!(errors[1].error_message.contains("范围 [0..=9]"));
+    assert!(errors[1].error_message.contains("actual 12"));
+}
+
+#[test]
+fn test_layered_disassembler_collects_errors_per_layer() {
+    let mut outer = FrameDisassembler::new();
+    outer.add_field(field(
+        "header",
+        UnitType::Uint(8),
+        Some(Constraint::FixedValue(0xA5)),
+    ));
+    outer.add_field(field("payload", UnitType::Uint(56), None));
+
+    let mut layered = LayeredDisassembler::new();
+    layered.add_layer("outer".to_string(), outer, Some("payload".to_string()));
+    layered.add_layer("inner".to_string(), create_disassembler(), None);
+
+    let raw = [0xA4, 0x1A, 0xCF, 0xFC, 0x1D, 0x7C, 0x00, 0x00];
+    let result = layered.disassemble_layers(&raw).unwrap();
+
+    assert!(result.has_errors());
+    let locations: Vec<(usize, &str)> = result
+        .errors
+        .iter()
+        .map(|e| (e.layer_index, e.field_name.as_str()))
+        .collect();
+    assert_eq!(
+        locations,
+        vec![(0, "header"), (1, "frame_type"), (1, "count")]
+    );
+}
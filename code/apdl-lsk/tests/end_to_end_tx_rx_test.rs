@@ -36,6 +36,8 @@ fn test_end_to_end_ccsds_space_packet() {
         associate: vec![],
         desc: "Packet Version".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let type_field = SyntaxUnit {
@@ -52,6 +54,8 @@ fn test_end_to_end_ccsds_space_packet() {
         associate: vec![],
         desc: "Packet Type".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let sec_hdr_flag_field = SyntaxUnit {
@@ -68,6 +72,8 @@ fn test_end_to_end_ccsds_space_packet() {
         associate: vec![],
         desc: "Secondary Header Flag".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let apid_field = SyntaxUnit {
@@ -84,6 +90,8 @@ fn test_end_to_end_ccsds_space_packet() {
         associate: vec![],
         desc: "Application Process ID".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let seq_flags_field = SyntaxUnit {
@@ -100,6 +108,8 @@ fn test_end_to_end_ccsds_space_packet() {
         associate: vec![],
         desc: "Sequence Flags".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let pkt_seq_cnt_field = SyntaxUnit {
@@ -116,6 +126,8 @@ fn test_end_to_end_ccsds_space_packet() {
         associate: vec![],
         desc: "Packet Sequence Count".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let pkt_len_field = SyntaxUnit {
@@ -132,6 +144,8 @@ fn test_end_to_end_ccsds_space_packet() {
         associate: vec![],
         desc: "Packet Data Length".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let data_field = SyntaxUnit {
@@ -148,6 +162,8 @@ fn test_end_to_end_ccsds_space_packet() {
         associate: vec![],
         desc: "Packet Data".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     // 添加所有字段
@@ -336,6 +352,8 @@ fn test_end_to_end_with_sync_marker() {
         associate: vec![],
         desc: "Sync Marker".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let frame_id_field = SyntaxUnit {
@@ -352,6 +370,8 @@ fn test_end_to_end_with_sync_marker() {
         associate: vec![],
         desc: "Frame ID".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let data_field = SyntaxUnit {
@@ -368,6 +388,8 @@ fn test_end_to_end_with_sync_marker() {
         associate: vec![],
         desc: "Data".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     tx_assembler.add_field(sync_field.clone());
@@ -24,6 +24,8 @@ fn create_tm_frame_disassembler() -> FrameDisassembler {
         associate: vec![],
         desc: "TM Version".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let scid_field = SyntaxUnit {
@@ -40,6 +42,8 @@ fn create_tm_frame_disassembler() -> FrameDisassembler {
         associate: vec![],
         desc: "Spacecraft ID".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let vcid_field = SyntaxUnit {
@@ -56,6 +60,8 @@ fn create_tm_frame_disassembler() -> FrameDisassembler {
         associate: vec![],
         desc: "Virtual Channel ID".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let frame_seq_field = SyntaxUnit {
@@ -72,6 +78,8 @@ fn create_tm_frame_disassembler() -> FrameDisassembler {
         associate: vec![],
         desc: "Frame Sequence Number".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     // TM数据字段（净荷）
@@ -89,6 +97,8 @@ fn create_tm_frame_disassembler() -> FrameDisassembler {
         associate: vec![],
         desc: "TM Data Field".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     disassembler.add_field(version_field);
@@ -119,6 +129,8 @@ fn create_space_packet_disassembler() -> FrameDisassembler {
         associate: vec![],
         desc: "Packet Version".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let pkt_type_field = SyntaxUnit {
@@ -135,6 +147,8 @@ fn create_space_packet_disassembler() -> FrameDisassembler {
         associate: vec![],
         desc: "Packet Type".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let sec_hdr_flag_field = SyntaxUnit {
@@ -151,6 +165,8 @@ fn create_space_packet_disassembler() -> FrameDisassembler {
         associate: vec![],
         desc: "Secondary Header Flag".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let apid_field = SyntaxUnit {
@@ -167,6 +183,8 @@ fn create_space_packet_disassembler() -> FrameDisassembler {
         associate: vec![],
         desc: "Application Process ID".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let seq_flags_field = SyntaxUnit {
@@ -183,6 +201,8 @@ fn create_space_packet_disassembler() -> FrameDisassembler {
         associate: vec![],
         desc: "Sequence Flags".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let pkt_seq_cnt_field = SyntaxUnit {
@@ -199,6 +219,8 @@ fn create_space_packet_disassembler() -> FrameDisassembler {
         associate: vec![],
         desc: "Packet Sequence Count".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let pkt_len_field = SyntaxUnit {
@@ -215,6 +237,8 @@ fn create_space_packet_disassembler() -> FrameDisassembler {
         associate: vec![],
         desc: "Packet Length".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     // 包数据（净荷）
@@ -232,6 +256,8 @@ fn create_space_packet_disassembler() -> FrameDisassembler {
         associate: vec![],
         desc: "Packet Data".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     disassembler.add_field(pkt_version_field);
@@ -354,6 +380,8 @@ fn test_three_layer_protocol_stack() {
         associate: vec![],
         desc: "Outer Header".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
     let outer_payload = SyntaxUnit {
         field_id: "outer_payload".to_string(),
@@ -369,6 +397,8 @@ fn test_three_layer_protocol_stack() {
         associate: vec![],
         desc: "Outer Payload".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
     outer_disassembler.add_field(outer_header);
     outer_disassembler.add_field(outer_payload);
@@ -395,6 +425,8 @@ fn test_three_layer_protocol_stack() {
         associate: vec![],
         desc: "Middle Header".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
     let middle_payload = SyntaxUnit {
         field_id: "middle_payload".to_string(),
@@ -410,6 +442,8 @@ fn test_three_layer_protocol_stack() {
         associate: vec![],
         desc: "Middle Payload".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
     middle_disassembler.add_field(middle_header);
     middle_disassembler.add_field(middle_payload);
@@ -436,6 +470,8 @@ fn test_three_layer_protocol_stack() {
         associate: vec![],
         desc: "Inner Header".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
     let inner_data = SyntaxUnit {
         field_id: "inner_data".to_string(),
@@ -451,6 +487,8 @@ fn test_three_layer_protocol_stack() {
         associate: vec![],
         desc: "Inner Data".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
     inner_disassembler.add_field(inner_header);
     inner_disassembler.add_field(inner_data);
@@ -0,0 +1,173 @@
+//! 分层重组引擎集成测试
+//!
+//! 验证三层CCSDS协议栈（TM帧 / 空间包 / 应用数据）拆包后重组得到原始帧
+
+use apdl_core::*;
+use apdl_lsk::{FrameDisassembler, LayeredDisassembler, LayeredReassembler};
+use apdl_poem::FrameAssembler;
+
+fn field(field_id: &str, unit_type: UnitType, length: LengthDesc) -> SyntaxUnit {
+    SyntaxUnit::new(
+        field_id,
+        unit_type,
+        length,
+        ScopeDesc::Global("ccsds".to_string()),
+    )
+}
+
+fn bits(field_id: &str, width: u8) -> SyntaxUnit {
+    field(
+        field_id,
+        UnitType::Bit(width),
+        LengthDesc {
+            size: width as usize,
+            unit: LengthUnit::Bit,
+        },
+    )
+}
+
+fn bytes(field_id: &str, size: usize) -> SyntaxUnit {
+    field(
+        field_id,
+        UnitType::Uint((size * 8) as u8),
+        LengthDesc {
+            size,
+            unit: LengthUnit::Byte,
+        },
+    )
+}
+
+fn dynamic(field_id: &str) -> SyntaxUnit {
+    field(
+        field_id,
+        UnitType::RawData,
+        LengthDesc {
+            size: 0,
+            unit: LengthUnit::Dynamic,
+        },
+    )
+}
+
+/// 各层定义：（层名称，字段，语义规则，净荷字段）
+fn ccsds_stack() -> Vec<(
+    &'static str,
+    Vec<SyntaxUnit>,
+    Vec<SemanticRule>,
+    &'static str,
+)> {
+    vec![
+        (
+            "TM Frame",
+            vec![
+                bits("tm_version", 2),
+                bits("tm_scid", 10),
+                bits("tm_vcid", 3),
+                bits("tm_ocf_flag", 1),
+                bytes("tm_mc_count", 1),
+                bytes("tm_vc_count", 1),
+                dynamic("tm_data_field"),
+            ],
+            vec![],
+            "tm_data_field",
+        ),
+        (
+            "Space Packet",
+            vec![
+                bits("pkt_version", 3),
+                bits("pkt_type", 1),
+                bits("sec_hdr_flag", 1),
+                bits("apid", 11),
+                bits("seq_flags", 2),
+                bits("seq_count", 14),
+                bytes("pkt_len", 2),
+                dynamic("pkt_data"),
+            ],
+            vec![SemanticRule::LengthRule {
+                field_name: "pkt_len".to_string(),
+                expression: "len(pkt_data) - 1".to_string(),
+            }],
+            "pkt_data",
+        ),
+        (
+            "Application",
+            vec![bytes("sec_time", 4), dynamic("app_data")],
+            vec![],
+            "app_data",
+        ),
+    ]
+}
+
+fn build_engines() -> (LayeredDisassembler, LayeredReassembler) {
+    let mut disassembler = LayeredDisassembler::new();
+    let mut reassembler = LayeredReassembler::new();
+    for (layer_name, units, rules, payload_field) in ccsds_stack() {
+        let mut layer_disassembler = FrameDisassembler::new();
+        let mut layer_assembler = FrameAssembler::new();
+        for unit in units {
+            layer_disassembler.add_field(unit.clone());
+            layer_assembler.add_field(unit);
+        }
+        for rule in rules {
+            layer_disassembler.add_semantic_rule(rule.clone());
+            layer_assembler.add_semantic_rule(rule);
+        }
+        disassembler.add_layer(
+            layer_name.to_string(),
+            layer_disassembler,
+            Some(payload_field.to_string()),
+        );
+        reassembler.add_layer(
+            layer_name.to_string(),
+            Box::new(layer_assembler),
+            Some(payload_field.to_string()),
+        );
+    }
+    (disassembler, reassembler)
+}
+
+#[test]
+fn test_three_layer_ccsds_round_trip() {
+    let frame = vec![
+        0x0A, 0x82, 0x05, 0x03, // TM帧头：SCID 0x2A、VCID 1、主/虚拟信道帧计数
+        0x09, 0x23, 0xC0, 0x07, 0x00,
+        0x06, // 空间包主导头：APID 0x123、序列计数7、包长6
+        0x12, 0x34, 0x56, 0x78, // 副导头时间码
+        0xAA, 0xBB, 0xCC, // 应用数据
+    ];
+    let (disassembler, mut reassembler) = build_engines();
+
+    let result = disassembler.disassemble_layers(&frame).unwrap();
+    assert_eq!(result.layer_count(), 3);
+    assert_eq!(result.application_data, vec![0xAA, 0xBB, 0xCC]);
+
+    assert_eq!(reassembler.reassemble(&result).unwrap(), frame);
+}
+
+#[test]
+fn test_reassemble_recomputes_inner_length_after_edit() {
+    let frame = vec![
+        0x0A, 0x82, 0x05, 0x03, 0x09, 0x23, 0xC0, 0x07, 0x00, 0x06, 0x12, 0x34, 0x56, 0x78, 0xAA,
+        0xBB, 0xCC,
+    ];
+    let (disassembler, mut reassembler) = build_engines();
+
+    // 修改应用数据后重组，空间包长度字段按长度规则重新计算
+    let mut result = disassembler.disassemble_layers(&frame).unwrap();
+    result.set_application_data(vec![0xAA]);
+    let rebuilt = reassembler.reassemble(&result).unwrap();
+    assert_eq!(&rebuilt[8..10], &[0x00, 0x04]);
+    assert_eq!(rebuilt.len(), frame.len() - 2);
+}
+
+#[test]
+fn test_reassemble_rejects_layer_count_mismatch() {
+    let (disassembler, _) = build_engines();
+    let result = disassembler
+        .disassemble_layers(&[
+            0x0A, 0x82, 0x05, 0x03, 0x09, 0x23, 0xC0, 0x07, 0x00, 0x01, 0x12, 0x34, 0x56, 0x78,
+        ])
+        .unwrap();
+
+    let mut reassembler = LayeredReassembler::new();
+    assert!(reassembler.reassemble(&result).is_err());
+}
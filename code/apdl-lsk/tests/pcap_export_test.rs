@@ -0,0 +1,71 @@
+//! pcap导出测试
+//!
+//! 验证仿真流量导出的pcap文件全局头和包记录头结构
+
+use apdl_lsk::simulator::{SimulatorConfig, PCAP_LINKTYPE_USER0};
+use apdl_lsk::traffic_generator::{TrafficConfig, TrafficType};
+use apdl_lsk::{ProtocolSimulator, TrafficGenerator};
+
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes(bytes[offset..offset + 2].try_into().unwrap())
+}
+
+fn cbr_generator() -> TrafficGenerator {
+    TrafficGenerator::new(TrafficConfig {
+        traffic_type: TrafficType::Constant,
+        rate_kbps: 4.0,
+        packet_size_min: 100,
+        packet_size_max: 100,
+        interval_ms: 50,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_export_pcap_header_and_first_record() {
+    let mut simulator = ProtocolSimulator::new(SimulatorConfig::default());
+    simulator.set_pcap_link_type(149);
+    let mut generator = cbr_generator();
+    simulator.capture_traffic(&mut generator, 1_000).unwrap();
+
+    // 4kbps、50ms时隙：每4个时隙累积一个100字节数据包，1秒内5个
+    let captured = simulator.captured_frames();
+    assert_eq!(captured.len(), 5);
+    assert_eq!(captured[0].timestamp_us, 150_000);
+    assert_eq!(captured[1].timestamp_us, 350_000);
+
+    let path = std::env::temp_dir().join(format!("apdl_pcap_export_{}.pcap", std::process::id()));
+    simulator.export_pcap(&path).unwrap();
+    let bytes = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    // 全局头24字节
+    assert_eq!(u32_at(&bytes, 0), 0xA1B2_C3D4);
+    assert_eq!(u16_at(&bytes, 4), 2);
+    assert_eq!(u16_at(&bytes, 6), 4);
+    assert_eq!(u32_at(&bytes, 8), 0);
+    assert_eq!(u32_at(&bytes, 12), 0);
+    assert_eq!(u32_at(&bytes, 16), 65535);
+    assert_eq!(u32_at(&bytes, 20), 149);
+
+    // 第一条包记录头16字节，随后为帧数据
+    assert_eq!(u32_at(&bytes, 24), 0);
+    assert_eq!(u32_at(&bytes, 28), 150_000);
+    assert_eq!(u32_at(&bytes, 32), 100);
+    assert_eq!(u32_at(&bytes, 36), 100);
+    assert_eq!(&bytes[40..140], captured[0].data.as_slice());
+    assert_eq!(bytes.len(), 24 + 5 * (16 + 100));
+}
+
+#[test]
+fn test_default_link_type_and_empty_capture() {
+    let simulator = ProtocolSimulator::new(SimulatorConfig::default());
+    let mut bytes = Vec::new();
+    simulator.write_pcap(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), 24);
+    assert_eq!(u32_at(&bytes, 20), PCAP_LINKTYPE_USER0);
+}
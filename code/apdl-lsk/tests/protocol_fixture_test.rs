@@ -0,0 +1,92 @@
+//! 协议基准样例测试
+//!
+//! 验证CCSDS空间包和CAN 2.0帧样例按包定义组装得到期望帧，且期望帧解析后还原各字段取值
+
+use apdl_core::PackageDefinition;
+use apdl_lsk::fixtures::{can_frame, ccsds_space_packet, ProtocolFixture};
+use apdl_lsk::FrameDisassembler;
+use apdl_poem::FrameAssembler;
+
+fn create_assembler(package: &PackageDefinition) -> FrameAssembler {
+    let mut assembler = FrameAssembler::new();
+    for layer in &package.layers {
+        for unit in &layer.units {
+            assembler.add_field(unit.clone());
+        }
+        for rule in &layer.rules {
+            assembler.add_semantic_rule(rule.clone());
+        }
+    }
+    assembler
+}
+
+fn create_disassembler(package: &PackageDefinition) -> FrameDisassembler {
+    let mut disassembler = FrameDisassembler::new();
+    for layer in &package.layers {
+        for unit in &layer.units {
+            disassembler.add_field(unit.clone());
+        }
+    }
+    disassembler
+}
+
+/// 组装结果与期望帧逐字节一致，解析期望帧得到原字段值，且校验和规则验证通过
+fn assert_round_trip((package, values, expected_frame): ProtocolFixture) {
+    let mut assembler = create_assembler(&package);
+    for (field_name, value) in &values {
+        assembler.set_field_value(field_name, value).unwrap();
+    }
+    let frame = assembler.assemble_frame().unwrap();
+    assert_eq!(frame, expected_frame, "{} assembled frame", package.name);
+
+    let fields = create_disassembler(&package)
+        .disassemble_frame(&expected_frame)
+        .unwrap();
+    for (field_name, value) in &values {
+        assert_eq!(
+            &fields[field_name], value,
+            "{} field {field_name}",
+            package.name
+        );
+    }
+
+    for rule in package.layers.iter().flat_map(|layer| &layer.rules) {
+        if let apdl_core::SemanticRule::ChecksumRange {
+            algorithm,
+            start_field,
+            end_field,
+            ..
+        } = rule
+        {
+            assembler
+                .validate_checksum_rule(&expected_frame, algorithm, start_field, end_field)
+                .unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_ccsds_space_packet_fixture() {
+    let (package, values, expected_frame) = ccsds_space_packet();
+
+    // 计算字段：包数据长度和CRC由规则写入
+    let fields = create_disassembler(&package)
+        .disassemble_frame(&expected_frame)
+        .unwrap();
+    assert_eq!(fields["pkt_len"], vec![0x00, 0x07]);
+    assert_eq!(fields["pkt_crc"], vec![0xE8, 0x49]);
+
+    assert_round_trip((package, values, expected_frame));
+}
+
+#[test]
+fn test_can_frame_fixture() {
+    let (package, values, expected_frame) = can_frame();
+
+    let fields = create_disassembler(&package)
+        .disassemble_frame(&expected_frame)
+        .unwrap();
+    assert_eq!(fields["can_crc"], vec![0x14, 0x01]);
+
+    assert_round_trip((package, values, expected_frame));
+}
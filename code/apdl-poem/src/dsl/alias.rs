@@ -0,0 +1,185 @@
+//! DSL别名（宏）展开模块
+//!
+//! 支持将重复出现的字段片段定义为别名：
+//!
+//! ```text
+//! define PRIMARY_HEADER = {
+//!     field: version; type: Bit(3); length: 3bit; scope: layer(net); cover: entire_field;
+//!     field: apid; type: Bit(11); length: 11bit; scope: layer(net); cover: entire_field;
+//! }
+//!
+//! use PRIMARY_HEADER;
+//! ```
+//!
+//! 别名在解析前以文本方式展开，别名内部可以继续引用其他别名，递归引用会报错
+
+use std::collections::HashMap;
+
+/// 别名表：别名名称 -> 定义内容
+type AliasTable = HashMap<String, String>;
+
+/// 输入中的一行及其行序号
+type NumberedLine<'a> = (usize, &'a str);
+
+/// 别名展开器
+pub struct AliasExpander;
+
+impl AliasExpander {
+    /// 展开输入中的所有别名
+    ///
+    /// 移除`define NAME = { ... }`定义块，并将每个`use NAME;`行替换为别名内容
+    pub fn expand(input: &str) -> Result<String, String> {
+        let lines: Vec<String> = Self::expand_lines(input)?
+            .into_iter()
+            .map(|(_, line)| line)
+            .collect();
+        Ok(lines.join("\n"))
+    }
+
+    /// 展开输入中的所有别名，返回展开后的各行及其在输入中的行序号
+    ///
+    /// 别名内容展开出的行取其`use`行的序号
+    pub fn expand_lines(input: &str) -> Result<Vec<(usize, String)>, String> {
+        let (aliases, body) = Self::collect_definitions(input)?;
+        let mut stack = Vec::new();
+        let mut expanded = Vec::new();
+        for (index, line) in body {
+            if Self::parse_use(line).is_none() {
+                expanded.push((index, line.to_string()));
+                continue;
+            }
+            let content = Self::expand_text(line, &aliases, &mut stack)?;
+            expanded.extend(
+                content
+                    .lines()
+                    .map(|content_line| (index, content_line.to_string())),
+            );
+        }
+        Ok(expanded)
+    }
+
+    /// 收集别名定义，返回别名表和去除定义块后的各行（附行序号）
+    fn collect_definitions(input: &str) -> Result<(AliasTable, Vec<NumberedLine<'_>>), String> {
+        let mut aliases = HashMap::new();
+        let mut remaining = Vec::new();
+        let mut lines = input.lines().enumerate();
+
+        while let Some((index, line)) = lines.next() {
+            let trimmed = line.trim();
+            let Some(after_define) = trimmed.strip_prefix("define ") else {
+                remaining.push((index, line));
+                continue;
+            };
+
+            let Some((name, rest)) = after_define.split_once('=') else {
+                return Err(format!("Invalid alias definition: {trimmed}"));
+            };
+            let name = name.trim();
+            if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                return Err(format!("Invalid alias name: '{name}'"));
+            }
+
+            let rest = rest.trim_start();
+            if !rest.starts_with('{') {
+                return Err(format!("Alias '{name}' definition must start with {{"));
+            }
+
+            // 收集直到花括号平衡
+            let mut definition = String::from(rest);
+            let mut brace_count = Self::brace_delta(rest);
+            while brace_count > 0 {
+                let Some((_, next_line)) = lines.next() else {
+                    return Err(format!("Unmatched braces in alias definition: {name}"));
+                };
+                definition.push('\n');
+                definition.push_str(next_line);
+                brace_count += Self::brace_delta(next_line);
+            }
+
+            let definition = definition.trim_end();
+            let Some(content) = definition
+                .strip_prefix('{')
+                .and_then(|s| s.strip_suffix('}'))
+            else {
+                return Err(format!("Invalid alias definition body: {name}"));
+            };
+
+            if aliases
+                .insert(name.to_string(), content.to_string())
+                .is_some()
+            {
+                return Err(format!("Duplicate alias definition: {name}"));
+            }
+        }
+
+        Ok((aliases, remaining))
+    }
+
+    /// 递归展开文本中的`use`引用
+    fn expand_text(
+        text: &str,
+        aliases: &AliasTable,
+        stack: &mut Vec<String>,
+    ) -> Result<String, String> {
+        let mut expanded = Vec::new();
+
+        for line in text.lines() {
+            let Some(name) = Self::parse_use(line) else {
+                expanded.push(line.to_string());
+                continue;
+            };
+
+            if stack.iter().any(|n| n == name) {
+                stack.push(name.to_string());
+                return Err(format!("Recursive alias: {}", stack.join(" -> ")));
+            }
+
+            let Some(content) = aliases.get(name) else {
+                return Err(format!("Undefined alias: {name}"));
+            };
+
+            stack.push(name.to_string());
+            let content = Self::expand_text(content, aliases, stack)?;
+            stack.pop();
+
+            let indent = &line[..line.len() - line.trim_start().len()];
+            for content_line in content.lines() {
+                let content_line = content_line.trim();
+                if !content_line.is_empty() {
+                    expanded.push(format!("{indent}{content_line}"));
+                }
+            }
+        }
+
+        Ok(expanded.join("\n"))
+    }
+
+    /// 解析`use NAME;`行，返回别名名称
+    fn parse_use(line: &str) -> Option<&str> {
+        let name = line.trim().strip_prefix("use ")?;
+        let name = name.trim_end_matches(';').trim();
+        if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            Some(name)
+        } else {
+            None
+        }
+    }
+
+    /// 计算一行中花括号的净增量，双引号内的文本（含`\"`等转义）不计入
+    fn brace_delta(line: &str) -> i32 {
+        let mut count = 0;
+        let mut in_quotes = false;
+        let mut escaped = false;
+        for c in line.chars() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' if in_quotes => escaped = true,
+                '"' => in_quotes = !in_quotes,
+                '{' if !in_quotes => count += 1,
+                '}' if !in_quotes => count -= 1,
+                _ => {}
+            }
+        }
+        count
+    }
+}
@@ -161,6 +161,14 @@ impl JsonParser {
                     .unwrap_or("")
                     .to_string(),
                 pack_unpack_spec,
+                unit_label: field
+                    .get("unit")
+                    .and_then(|u| u.as_str())
+                    .map(|u| u.to_string()),
+                long_description: field
+                    .get("long_description")
+                    .and_then(|d| d.as_str())
+                    .map(|d| d.to_string()),
             };
 
             units.push(syntax_unit);
@@ -449,6 +457,8 @@ impl JsonParser {
                 associate: vec![],
                 desc: unit["description"].as_str().unwrap_or("").to_string(),
                 pack_unpack_spec,
+                unit_label: unit["unit"].as_str().map(|u| u.to_string()),
+                long_description: unit["long_description"].as_str().map(|d| d.to_string()),
             };
 
             units.push(syntax_unit);
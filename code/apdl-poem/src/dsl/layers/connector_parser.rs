@@ -6,7 +6,10 @@ use apdl_core::{
     ConnectorConfig, ConnectorDefinition, DataPlacementConfig, DataPlacementStrategy,
     FieldMappingEntry, HeaderPointerConfig,
 };
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+
+/// 连接器属性表，按键排序以保证解析和序列化结果的顺序确定
+pub type PropertyMap = BTreeMap<String, String>;
 
 /// 连接器解析器
 pub struct ConnectorParser;
@@ -121,8 +124,8 @@ impl ConnectorParser {
     }
 
     /// 解析属性列表
-    fn parse_properties(text: &str) -> Result<HashMap<String, String>, String> {
-        let mut properties = HashMap::new();
+    fn parse_properties(text: &str) -> Result<PropertyMap, String> {
+        let mut properties = PropertyMap::new();
         let mut current_key = String::new();
         let mut current_value = String::new();
         let mut in_key = true;
@@ -303,7 +306,7 @@ impl ConnectorParser {
     }
 
     /// 解析对象（花括号内容）
-    fn parse_object(obj_text: &str) -> Result<HashMap<String, String>, String> {
+    fn parse_object(obj_text: &str) -> Result<PropertyMap, String> {
         let content = Self::extract_braced_content(obj_text)?;
         Self::parse_properties(content)
     }
@@ -894,6 +897,50 @@ mod tests {
         assert!(!placement.config_params.is_empty());
     }
 
+    #[test]
+    fn test_connector_serialization_is_deterministic() {
+        let dsl = r#"
+        connector ordered_connector {
+            type: "field_mapping";
+            source_package: "source_pkt";
+            target_package: "target_pkt";
+            config: {
+                mappings: [];
+                placement_strategy: {
+                    strategy: "pointer_based";
+                    target_field: "data_field";
+                    config: {
+                        pointer_field: "ptr_field";
+                        map_id: "map_id";
+                        offset: "4";
+                        align: "2";
+                        mode: "fixed";
+                    };
+                };
+            };
+            desc: "Connector with several placement params";
+        }
+        "#;
+
+        let first = ConnectorParser::parse_connector_definition(dsl).unwrap();
+        let second = ConnectorParser::parse_connector_definition(dsl).unwrap();
+        assert_eq!(
+            serde_json::to_string(&first).unwrap(),
+            serde_json::to_string(&second).unwrap()
+        );
+
+        let placement = first.config.data_placement.unwrap();
+        let keys: Vec<&str> = placement
+            .config_params
+            .iter()
+            .map(|(key, _)| key.as_str())
+            .collect();
+        assert_eq!(
+            keys,
+            vec!["align", "map_id", "mode", "offset", "pointer_field"]
+        );
+    }
+
     #[test]
     fn test_parse_connector_definition_basic() {
         let dsl = r#"
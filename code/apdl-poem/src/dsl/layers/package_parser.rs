@@ -196,6 +196,9 @@ impl PackageParser {
         let mut alg_str = String::new();
         let mut associate_str = String::new();
         let mut desc_str = String::new();
+        let mut unit_label = None;
+        let mut long_description = None;
+        let mut byte_order = None;
 
         // 解析语法单元内容
         for line in unit_content.lines() {
@@ -222,6 +225,14 @@ impl PackageParser {
                 associate_str = Self::extract_simple_value(line)?;
             } else if line.starts_with("desc:") {
                 desc_str = Self::extract_quoted_value(line)?;
+            } else if line.starts_with("unit:") {
+                unit_label = Some(Self::extract_simple_value(line)?);
+            } else if line.starts_with("long_desc:") {
+                long_description = Some(Self::extract_quoted_value(line)?);
+            } else if line.starts_with("endian:") {
+                byte_order = Some(crate::dsl::parser_utils::parse_byte_order(
+                    &Self::extract_simple_value(line)?,
+                )?);
             }
         }
 
@@ -266,7 +277,9 @@ impl PackageParser {
             alg,
             associate,
             desc: desc_str,
-            pack_unpack_spec: None,
+            pack_unpack_spec: crate::dsl::parser_utils::byte_order_pack_spec(byte_order),
+            unit_label,
+            long_description,
         })
     }
 
@@ -292,6 +305,8 @@ impl PackageParser {
             Ok(UnitType::RawData)
         } else if type_str == "Ip6Addr" {
             Ok(UnitType::Ip6Addr)
+        } else if type_str.starts_with("CUC(") || type_str.starts_with("CDS(") {
+            crate::dsl::parser_utils::parse_unit_type(type_str)
         } else {
             Err(format!("Unknown type: {type_str}"))
         }
@@ -303,6 +318,9 @@ impl PackageParser {
         if let Some(num_str) = length_str.strip_suffix("byte") {
             let num_str = num_str.trim();
             if let Ok(size) = num_str.parse::<usize>() {
+                if size == 0 {
+                    return Err(format!("Zero-length field is not allowed: {length_str}"));
+                }
                 Ok(LengthDesc {
                     size,
                     unit: LengthUnit::Byte,
@@ -313,6 +331,9 @@ impl PackageParser {
         } else if let Some(num_str) = length_str.strip_suffix("bit") {
             let num_str = num_str.trim();
             if let Ok(size) = num_str.parse::<usize>() {
+                if size == 0 {
+                    return Err(format!("Zero-length field is not allowed: {length_str}"));
+                }
                 Ok(LengthDesc {
                     size,
                     unit: LengthUnit::Bit,
@@ -357,7 +378,7 @@ impl PackageParser {
         {
             if let Some(pos) = inner.find("→") {
                 let first = inner[..pos].trim();
-                let second = inner[pos + 1..].trim();
+                let second = inner[pos + "→".len()..].trim();
                 Ok(ScopeDesc::CrossLayer(first.to_string(), second.to_string()))
             } else {
                 Err(format!("Invalid cross_layer format: {scope_str}"))
@@ -374,32 +395,7 @@ impl PackageParser {
 
     /// 解析覆盖描述
     fn parse_cover_desc(cover_str: &str) -> Result<CoverDesc, String> {
-        let cover_str = cover_str.trim();
-        if cover_str == "entire_field" {
-            Ok(CoverDesc::EntireField)
-        } else if cover_str.contains('[') && cover_str.contains(']') {
-            // 解析 field[offset..offset] 格式
-            if let Some(open_bracket) = cover_str.find('[') {
-                if let Some(close_bracket) = cover_str.find(']') {
-                    let field_part = &cover_str[..open_bracket];
-                    let range_part = &cover_str[open_bracket + 1..close_bracket];
-
-                    if let Some(double_dot) = range_part.find("..") {
-                        let start_str = &range_part[..double_dot];
-                        let end_str = &range_part[double_dot + 2..];
-
-                        if let (Ok(start), Ok(end)) =
-                            (start_str.parse::<usize>(), end_str.parse::<usize>())
-                        {
-                            return Ok(CoverDesc::Range(field_part.to_string(), start, end));
-                        }
-                    }
-                }
-            }
-            Ok(CoverDesc::Expression(cover_str.to_string()))
-        } else {
-            Ok(CoverDesc::Expression(cover_str.to_string()))
-        }
+        crate::dsl::parser_utils::parse_cover_desc(cover_str)
     }
 
     /// 解析约束条件
@@ -481,14 +477,7 @@ impl PackageParser {
 
     /// 解析算法
     fn parse_algorithm(alg_str: &str) -> Result<apdl_core::AlgorithmAst, String> {
-        let alg_str = alg_str.trim();
-        match alg_str {
-            "crc16" => Ok(apdl_core::AlgorithmAst::Crc16),
-            "crc32" => Ok(apdl_core::AlgorithmAst::Crc32),
-            "crc15" => Ok(apdl_core::AlgorithmAst::Crc15), // CAN协议专用
-            "xor_sum" => Ok(apdl_core::AlgorithmAst::XorSum),
-            _ => Ok(apdl_core::AlgorithmAst::Custom(alg_str.to_string())),
-        }
+        crate::dsl::parser_utils::parse_algorithm(alg_str)
     }
 
     /// 分割语法单元定义
@@ -1,3 +1,4 @@
+pub mod alias;
 pub mod field_mapping_parser;
 pub mod json_parser;
 pub mod layers;
@@ -2,10 +2,11 @@
 //!
 //! 使用简单的字符串处理实现APDL DSL的解析，支持字段定义和协议结构描述
 
-use apdl_core::{CoverDesc, LengthDesc, SemanticRule, SyntaxUnit, UnitType};
+use apdl_core::{CoverDesc, DslParseError, LengthDesc, SemanticRule, SyntaxUnit, UnitType};
 use serde_json;
 
 // 导入其他模块的函数
+use crate::dsl::alias::AliasExpander;
 use crate::dsl::field_mapping_parser::FieldMappingParser;
 use crate::dsl::layers::{
     connector_parser::ConnectorParser, package_parser::PackageParser,
@@ -17,6 +18,137 @@ use crate::dsl::semantic_rule_parsers::SemanticRuleParsers;
 /// DSL解析器实现
 pub struct DslParserImpl;
 
+/// 包含其他DSL文件的指令，形如`@include "common_fields.apdl"`
+const INCLUDE_DIRECTIVE: &str = "@include";
+
+/// 指向输入中出错片段的解析错误，`token`是所在行的子切片
+struct TokenError<'a> {
+    token: &'a str,
+    message: String,
+}
+
+impl<'a> TokenError<'a> {
+    fn new(token: &'a str, message: impl Into<String>) -> Self {
+        Self {
+            token,
+            message: message.into(),
+        }
+    }
+
+    /// 转换为带行列号的错误，行号为片段所在物理行在原始输入中的行号
+    fn locate(self, statement: &Statement) -> DslParseError {
+        let offset = (self.token.as_ptr() as usize).wrapping_sub(statement.text.as_ptr() as usize);
+        // 定位片段所在的物理行，行间的连接空格归入前一行末尾
+        let line = statement
+            .lines
+            .iter()
+            .rev()
+            .find(|line| line.offset <= offset)
+            .unwrap_or(&statement.lines[0]);
+        let position = (line.start + offset.saturating_sub(line.offset)).min(line.raw.len());
+        let column = line
+            .raw
+            .get(..position)
+            .map_or(1, |prefix| prefix.chars().count() + 1);
+        DslParseError::Syntax {
+            line: line.index + 1,
+            column,
+            token: self.token.to_string(),
+            message: self.message,
+        }
+    }
+}
+
+/// 以`field:`或`rule:`开头的一条定义语句，可跨多个物理行
+struct Statement<'a> {
+    /// 各物理行去除首尾空白后以空格连接的文本
+    text: String,
+    lines: Vec<StatementLine<'a>>,
+}
+
+/// 语句中的一个物理行
+struct StatementLine<'a> {
+    /// 展开后输入中的原始行
+    raw: &'a str,
+    /// 在原始输入中的行序号（别名展开出的行为其`use`行）
+    index: usize,
+    /// 去除首部空白后内容在`raw`中的起始字节
+    start: usize,
+    /// 该行内容在语句文本中的起始字节
+    offset: usize,
+}
+
+impl<'a> Statement<'a> {
+    /// 将别名展开后的各行切分为语句
+    ///
+    /// `field:`、`rule:`或`@include`开始新语句；当前语句未以`;`结尾时，
+    /// 其余非空非注释行续接到当前语句，否则报告未知语句
+    fn split(lines: &'a [(usize, String)]) -> Result<Vec<Self>, DslParseError> {
+        let mut statements: Vec<Self> = Vec::new();
+        for (index, raw) in lines {
+            let (index, raw) = (*index, raw.as_str());
+            let content = raw.trim();
+            if content.is_empty() || content.starts_with("//") {
+                continue;
+            }
+            let starts_statement = content.starts_with("field:")
+                || content.starts_with("rule:")
+                || content.starts_with(INCLUDE_DIRECTIVE);
+            let statement = match statements.last_mut() {
+                Some(statement) if !starts_statement && !statement.text.ends_with(';') => {
+                    statement.text.push(' ');
+                    statement
+                }
+                _ if !starts_statement => {
+                    let token = content.split(';').next().unwrap_or(content).trim_end();
+                    return Err(DslParseError::Syntax {
+                        line: index + 1,
+                        column: raw[..raw.len() - content.len()].chars().count() + 1,
+                        token: token.to_string(),
+                        message: format!(
+                            "Unknown statement, expected 'field:', 'rule:' or '{INCLUDE_DIRECTIVE}'"
+                        ),
+                    });
+                }
+                _ => {
+                    statements.push(Self {
+                        text: String::new(),
+                        lines: Vec::new(),
+                    });
+                    statements.last_mut().unwrap()
+                }
+            };
+            statement.lines.push(StatementLine {
+                raw,
+                index,
+                start: raw.len() - raw.trim_start().len(),
+                offset: statement.text.len(),
+            });
+            statement.text.push_str(content);
+        }
+        Ok(statements)
+    }
+
+    fn is_rule(&self) -> bool {
+        self.text.starts_with("rule:")
+    }
+
+    /// 若为`@include "file"`指令，返回被包含的文件名
+    fn include_target(&self) -> Option<Result<&str, TokenError<'_>>> {
+        let rest = self.text.strip_prefix(INCLUDE_DIRECTIVE)?;
+        let quoted = rest.trim().trim_end_matches(';').trim_end();
+        let target = quoted
+            .strip_prefix('"')
+            .and_then(|q| q.strip_suffix('"'))
+            .filter(|name| !name.is_empty() && !name.contains('"'));
+        Some(
+            target.ok_or_else(|| {
+                TokenError::new(quoted, "Include target must be a quoted file name")
+            }),
+        )
+    }
+}
+
 impl Default for DslParserImpl {
     fn default() -> Self {
         Self::new()
@@ -30,27 +162,35 @@ impl DslParserImpl {
 
     /// 解析语法单元定义
     pub fn parse_syntax_unit(&self, input: &str) -> Result<SyntaxUnit, String> {
-        Self::parse_syntax_unit_internal(input)
+        Self::parse_syntax_unit_internal(input).map_err(|e| e.message)
     }
 
     /// 解析多个语法单元定义（协议结构）
-    pub fn parse_protocol_structure(&self, input: &str) -> Result<Vec<SyntaxUnit>, String> {
+    ///
+    /// 解析失败时返回`DslParseError::Syntax`，指出出错的行、列和片段
+    pub fn parse_protocol_structure(&self, input: &str) -> Result<Vec<SyntaxUnit>, DslParseError> {
         let mut units = Vec::new();
 
-        // 按行分割输入，过滤掉注释和空行，逐行解析
-        for line in input.lines() {
-            let trimmed_line = line.trim();
-            // 跳过注释行（以//开头）和空行
-            if !trimmed_line.is_empty()
-                && !trimmed_line.starts_with("//")
-                && !trimmed_line.starts_with("rule:")
-            {
-                match Self::parse_syntax_unit_internal(trimmed_line) {
-                    Ok(unit) => {
-                        units.push(unit);
-                    }
-                    Err(e) => return Err(format!("Parse error on line '{trimmed_line}': {e}")),
+        // 展开别名定义（define/use）
+        let expanded = AliasExpander::expand_lines(input).map_err(DslParseError::ParseError)?;
+
+        // 按语句切分输入（定义可跨多行，注释和空行已过滤），逐条解析
+        for statement in Statement::split(&expanded)? {
+            if statement.is_rule() {
+                continue;
+            }
+            if statement.include_target().is_some() {
+                let error = TokenError::new(
+                    &statement.text,
+                    "Include directives require parse_with_resolver",
+                );
+                return Err(error.locate(&statement));
+            }
+            match Self::parse_syntax_unit_internal(&statement.text) {
+                Ok(unit) => {
+                    units.push(unit);
                 }
+                Err(e) => return Err(e.locate(&statement)),
             }
         }
 
@@ -58,31 +198,98 @@ impl DslParserImpl {
     }
 
     /// 解析协议语义规则
-    pub fn parse_semantic_rules(&self, input: &str) -> Result<Vec<SemanticRule>, String> {
+    ///
+    /// 解析失败时返回`DslParseError::Syntax`，指出出错的行、列和片段；
+    /// `@include`指令需通过`parse_with_resolver`解析，此处遇到时报错
+    pub fn parse_semantic_rules(&self, input: &str) -> Result<Vec<SemanticRule>, DslParseError> {
         let mut rules = Vec::new();
 
-        for line in input.lines() {
-            let trimmed_line = line.trim();
-            if !trimmed_line.is_empty()
-                && !trimmed_line.starts_with("//")
-                && trimmed_line.starts_with("rule:")
-            {
-                match Self::parse_semantic_rule_internal(trimmed_line) {
-                    Ok(rule) => {
-                        rules.push(rule);
-                    }
-                    Err(e) => {
-                        return Err(format!(
-                            "Semantic rule parse error on line '{trimmed_line}': {e}"
-                        ))
-                    }
+        // 展开别名定义（define/use）
+        let expanded = AliasExpander::expand_lines(input).map_err(DslParseError::ParseError)?;
+
+        for statement in Statement::split(&expanded)? {
+            if statement.include_target().is_some() {
+                let error = TokenError::new(
+                    &statement.text,
+                    "Include directives require parse_with_resolver",
+                );
+                return Err(error.locate(&statement));
+            }
+            if !statement.is_rule() {
+                continue;
+            }
+            match Self::parse_semantic_rule_internal(&statement.text) {
+                Ok(rule) => {
+                    rules.push(rule);
                 }
+                Err(e) => return Err(e.locate(&statement)),
             }
         }
 
         Ok(rules)
     }
 
+    /// 解析带`@include`指令的DSL，返回字段定义和语义规则
+    ///
+    /// 被包含的文件经`resolver`按名称加载，其定义按指令所在位置原位展开；
+    /// 循环包含时报错，被包含文件中的语法错误以文件名为前缀
+    pub fn parse_with_resolver(
+        &self,
+        input: &str,
+        resolver: &dyn Fn(&str) -> Result<String, String>,
+    ) -> Result<(Vec<SyntaxUnit>, Vec<SemanticRule>), DslParseError> {
+        let mut units = Vec::new();
+        let mut rules = Vec::new();
+        Self::parse_included(input, resolver, &mut Vec::new(), &mut units, &mut rules)?;
+        Ok((units, rules))
+    }
+
+    /// 解析一个文件的内容，`stack`记录正在展开的包含链
+    fn parse_included(
+        input: &str,
+        resolver: &dyn Fn(&str) -> Result<String, String>,
+        stack: &mut Vec<String>,
+        units: &mut Vec<SyntaxUnit>,
+        rules: &mut Vec<SemanticRule>,
+    ) -> Result<(), DslParseError> {
+        let expanded = AliasExpander::expand_lines(input).map_err(DslParseError::ParseError)?;
+
+        for statement in Statement::split(&expanded)? {
+            let Some(target) = statement.include_target() else {
+                if statement.is_rule() {
+                    let rule = Self::parse_semantic_rule_internal(&statement.text)
+                        .map_err(|e| e.locate(&statement))?;
+                    rules.push(rule);
+                } else {
+                    let unit = Self::parse_syntax_unit_internal(&statement.text)
+                        .map_err(|e| e.locate(&statement))?;
+                    units.push(unit);
+                }
+                continue;
+            };
+
+            let name = target.map_err(|e| e.locate(&statement))?;
+            if stack.iter().any(|included| included == name) {
+                stack.push(name.to_string());
+                return Err(DslParseError::ParseError(format!(
+                    "Cyclic include: {}",
+                    stack.join(" -> ")
+                )));
+            }
+            let content = resolver(name)
+                .map_err(|e| DslParseError::ParseError(format!("Cannot include '{name}': {e}")))?;
+
+            stack.push(name.to_string());
+            Self::parse_included(&content, resolver, stack, units, rules).map_err(|e| match e {
+                DslParseError::Syntax { .. } => DslParseError::ParseError(format!("{name}:{e}")),
+                e => e,
+            })?;
+            stack.pop();
+        }
+
+        Ok(())
+    }
+
     /// 解析包定义
     /// 解析包定义（优先使用JSON格式）
     ///
@@ -112,6 +319,9 @@ impl DslParserImpl {
         // 如果不是JSON格式或JSON解析失败，使用传统的DSL解析
         let mut packages = Vec::new();
 
+        // 展开别名定义（define/use）
+        let input = AliasExpander::expand(input)?;
+
         // 查找包定义
         let mut lines = input.lines().peekable();
 
@@ -327,7 +537,7 @@ impl DslParserImpl {
         Ok(stacks)
     }
 
-    fn parse_syntax_unit_internal(input: &str) -> Result<SyntaxUnit, String> {
+    fn parse_syntax_unit_internal(input: &str) -> Result<SyntaxUnit, TokenError<'_>> {
         let input = input.trim();
 
         // 解析field
@@ -350,14 +560,20 @@ impl DslParserImpl {
         let mut alg = None;
         let mut associate = Vec::new();
         let mut desc = String::new();
+        let mut unit_label = None;
+        let mut long_description = None;
+        let mut byte_order = None;
 
         let remaining = input;
-        for part in remaining.split(';') {
+        for part in split_unescaped_semicolons(remaining) {
             let part = part.trim();
             if let Some(stripped) = part.strip_prefix("constraint:") {
-                constraint = Some(parse_constraint(stripped.trim())?);
+                let stripped = stripped.trim();
+                constraint =
+                    Some(parse_constraint(stripped).map_err(|e| TokenError::new(stripped, e))?);
             } else if let Some(stripped) = part.strip_prefix("alg:") {
-                alg = Some(parse_algorithm(stripped.trim())?);
+                let stripped = stripped.trim();
+                alg = Some(parse_algorithm(stripped).map_err(|e| TokenError::new(stripped, e))?);
             } else if let Some(stripped) = part.strip_prefix("associate:") {
                 associate = stripped
                     .trim()
@@ -365,7 +581,15 @@ impl DslParserImpl {
                     .map(|s| s.trim().to_string())
                     .collect();
             } else if let Some(stripped) = part.strip_prefix("desc:") {
-                desc = stripped.trim().trim_matches('"').to_string();
+                desc = unquote_text(stripped);
+            } else if let Some(stripped) = part.strip_prefix("unit:") {
+                unit_label = Some(unquote_text(stripped));
+            } else if let Some(stripped) = part.strip_prefix("long_desc:") {
+                long_description = Some(unquote_text(stripped));
+            } else if let Some(stripped) = part.strip_prefix("endian:") {
+                byte_order = Some(
+                    parse_byte_order(stripped).map_err(|e| TokenError::new(stripped.trim(), e))?,
+                );
             }
         }
 
@@ -379,101 +603,68 @@ impl DslParserImpl {
             alg,
             associate,
             desc,
-            pack_unpack_spec: None,
+            pack_unpack_spec: byte_order_pack_spec(byte_order),
+            unit_label,
+            long_description,
         })
     }
 
-    fn extract_field(input: &str) -> Result<(&str, String), String> {
-        if let Some(start) = input.find("field:") {
-            let start_pos = start + 6;
-            if let Some(end) = input[start_pos..].find(';').map(|i| start_pos + i) {
-                let field = input[start_pos..end].trim();
-                Ok((&input[end + 1..], field.to_string()))
-            } else {
-                Ok(("", input[start_pos..].trim().to_string()))
-            }
-        } else {
-            Err("field: not found".to_string())
+    /// 提取`key: value;`中的取值，返回剩余输入和取值片段
+    ///
+    /// 找不到键时错误片段为剩余输入的开头
+    fn extract_part<'a>(input: &'a str, key: &str) -> Result<(&'a str, &'a str), TokenError<'a>> {
+        let Some(start) = input.find(key) else {
+            return Err(TokenError::new(
+                input.trim_start(),
+                format!("{key} not found"),
+            ));
+        };
+        let start_pos = start + key.len();
+        match input[start_pos..].find(';').map(|i| start_pos + i) {
+            Some(end) => Ok((&input[end + 1..], input[start_pos..end].trim())),
+            None => Ok(("", input[start_pos..].trim())),
         }
     }
 
-    fn extract_type(input: &str) -> Result<(&str, UnitType), String> {
-        if let Some(start) = input.find("type:") {
-            let start_pos = start + 5;
-            if let Some(end) = input[start_pos..].find(';').map(|i| start_pos + i) {
-                let type_str = input[start_pos..end].trim();
-                let unit_type = parse_unit_type(type_str)?;
-                Ok((&input[end + 1..], unit_type))
-            } else {
-                let type_str = input[start_pos..].trim();
-                let unit_type = parse_unit_type(type_str)?;
-                Ok(("", unit_type))
-            }
-        } else {
-            Err("type: not found".to_string())
-        }
+    fn extract_field(input: &str) -> Result<(&str, String), TokenError<'_>> {
+        let (input, field) = Self::extract_part(input, "field:")?;
+        Ok((input, field.to_string()))
     }
 
-    fn extract_length(input: &str) -> Result<(&str, LengthDesc), String> {
-        if let Some(start) = input.find("length:") {
-            let start_pos = start + 7;
-            if let Some(end) = input[start_pos..].find(';').map(|i| start_pos + i) {
-                let length_str = input[start_pos..end].trim();
-                let length_desc = parse_length_desc(length_str)?;
-                Ok((&input[end + 1..], length_desc))
-            } else {
-                let length_str = input[start_pos..].trim();
-                let length_desc = parse_length_desc(length_str)?;
-                Ok(("", length_desc))
-            }
-        } else {
-            Err("length: not found".to_string())
-        }
+    fn extract_type(input: &str) -> Result<(&str, UnitType), TokenError<'_>> {
+        let (input, type_str) = Self::extract_part(input, "type:")?;
+        let unit_type = parse_unit_type(type_str).map_err(|e| TokenError::new(type_str, e))?;
+        Ok((input, unit_type))
     }
 
-    fn extract_scope(input: &str) -> Result<(&str, apdl_core::ScopeDesc), String> {
-        if let Some(start) = input.find("scope:") {
-            let start_pos = start + 6;
-            if let Some(end) = input[start_pos..].find(';').map(|i| start_pos + i) {
-                let scope_str = input[start_pos..end].trim();
-                let scope_desc = parse_scope_desc(scope_str)?;
-                Ok((&input[end + 1..], scope_desc))
-            } else {
-                let scope_str = input[start_pos..].trim();
-                let scope_desc = parse_scope_desc(scope_str)?;
-                Ok(("", scope_desc))
-            }
-        } else {
-            Err("scope: not found".to_string())
-        }
+    fn extract_length(input: &str) -> Result<(&str, LengthDesc), TokenError<'_>> {
+        let (input, length_str) = Self::extract_part(input, "length:")?;
+        let length_desc =
+            parse_length_desc(length_str).map_err(|e| TokenError::new(length_str, e))?;
+        Ok((input, length_desc))
     }
 
-    fn extract_cover(input: &str) -> Result<(&str, CoverDesc), String> {
-        if let Some(start) = input.find("cover:") {
-            let start_pos = start + 6;
-            if let Some(end) = input[start_pos..].find(';').map(|i| start_pos + i) {
-                let cover_str = input[start_pos..end].trim();
-                let cover_desc = parse_cover_desc(cover_str)?;
-                Ok((&input[end + 1..], cover_desc))
-            } else {
-                let cover_str = input[start_pos..].trim();
-                let cover_desc = parse_cover_desc(cover_str)?;
-                Ok(("", cover_desc))
-            }
-        } else {
-            Err("cover: not found".to_string())
-        }
+    fn extract_scope(input: &str) -> Result<(&str, apdl_core::ScopeDesc), TokenError<'_>> {
+        let (input, scope_str) = Self::extract_part(input, "scope:")?;
+        let scope_desc = parse_scope_desc(scope_str).map_err(|e| TokenError::new(scope_str, e))?;
+        Ok((input, scope_desc))
+    }
+
+    fn extract_cover(input: &str) -> Result<(&str, CoverDesc), TokenError<'_>> {
+        let (input, cover_str) = Self::extract_part(input, "cover:")?;
+        let cover_desc = parse_cover_desc(cover_str).map_err(|e| TokenError::new(cover_str, e))?;
+        Ok((input, cover_desc))
     }
 
     // 解析语义规则的内部实现
-    fn parse_semantic_rule_internal(input: &str) -> Result<SemanticRule, String> {
+    fn parse_semantic_rule_internal(input: &str) -> Result<SemanticRule, TokenError<'_>> {
         let input = input.trim();
 
         // 提取 "rule:type(" 部分
         let after_rule = if let Some(stripped) = input.strip_prefix("rule:") {
             stripped.trim_start()
         } else {
-            return Err("Not a rule definition".to_string());
+            return Err(TokenError::new(input, "Not a rule definition"));
         };
 
         // 查找第一个'('的位置
@@ -503,7 +694,7 @@ impl DslParserImpl {
                         paren_count -= 1;
                         if paren_count == 0 {
                             // 找到了匹配的右括号
-                            let params = &params_str[..pos].trim();
+                            let params = params_str[..pos].trim();
                             return Self::create_semantic_rule(rule_type, params);
                         }
                     }
@@ -511,14 +702,21 @@ impl DslParserImpl {
                 }
             }
 
-            Err("Unmatched parenthesis in rule".to_string())
+            Err(TokenError::new(
+                &after_rule[paren_pos..],
+                "Unmatched parenthesis in rule",
+            ))
         } else {
-            Err("No parameters found for rule".to_string())
+            Err(TokenError::new(after_rule, "No parameters found for rule"))
         }
     }
 
-    fn create_semantic_rule(rule_type: &str, params: &str) -> Result<SemanticRule, String> {
-        match rule_type {
+    /// 按规则类型解析参数，未知类型的错误指向类型名，参数错误指向参数
+    fn create_semantic_rule<'a>(
+        rule_type: &'a str,
+        params: &'a str,
+    ) -> Result<SemanticRule, TokenError<'a>> {
+        let rule = match rule_type {
             "field_mapping" => {
                 // 使用新的字段映射解析器
                 FieldMappingParser::parse_field_mapping_rule(params)
@@ -531,7 +729,9 @@ impl DslParserImpl {
             "order" => SemanticRuleParsers::parse_order(params),
             "pointer" => SemanticRuleParsers::parse_pointer(params),
             "algorithm" => SemanticRuleParsers::parse_algorithm(params),
+            "algorithm_select" => SemanticRuleParsers::parse_algorithm_select(params),
             "length_rule" => SemanticRuleParsers::parse_length_rule(params),
+            "alignment" => SemanticRuleParsers::parse_alignment(params),
             "routing_dispatch" => SemanticRuleParsers::parse_routing_dispatch(params),
             "sequence_control" => SemanticRuleParsers::parse_sequence_control(params),
             "validation" => SemanticRuleParsers::parse_validation(params),
@@ -551,8 +751,14 @@ impl DslParserImpl {
             "address_resolution" => SemanticRuleParsers::parse_address_resolution(params),
             "security" => SemanticRuleParsers::parse_security(params),
             "redundancy" => SemanticRuleParsers::parse_redundancy(params),
-            _ => Err(format!("Unknown rule type: {rule_type}")),
-        }
+            _ => {
+                return Err(TokenError::new(
+                    rule_type,
+                    format!("Unknown rule type: {rule_type}"),
+                ))
+            }
+        };
+        rule.map_err(|e| TokenError::new(params, e))
     }
 }
 
@@ -573,6 +779,61 @@ mod tests {
         assert_eq!(unit.desc, "CCSDS sync marker");
     }
 
+    #[test]
+    fn test_parse_field_unit_label() {
+        let parser = DslParserImpl::new();
+        let dsl = r#"field: temperature; type: Uint16; length: 2byte; scope: layer(application); cover: entire_field; unit: "K"; long_desc: "Sampled once per second"; desc: "Board temperature""#;
+
+        let unit = parser.parse_syntax_unit(dsl).unwrap();
+        assert_eq!(unit.unit_label.as_deref(), Some("K"));
+        assert_eq!(
+            unit.long_description.as_deref(),
+            Some("Sampled once per second")
+        );
+        assert_eq!(unit.desc, "Board temperature");
+    }
+
+    #[test]
+    fn test_parse_time_code_field() {
+        let parser = DslParserImpl::new();
+        let dsl = r#"field: obt; type: CUC(4,2); length: 6byte; scope: layer(application); cover: entire_field; desc: "On-board time""#;
+
+        let unit = parser.parse_syntax_unit(dsl).unwrap();
+        assert_eq!(unit.unit_type, UnitType::CucTime { coarse: 4, fine: 2 });
+        assert_eq!(unit.bit_width(), Some(48));
+
+        let dsl = r#"field: utc; type: CDS(2,0); length: 6byte; scope: layer(application); cover: entire_field"#;
+        let unit = parser.parse_syntax_unit(dsl).unwrap();
+        assert_eq!(
+            unit.unit_type,
+            UnitType::CdsTime {
+                day_bytes: 2,
+                submilli_bytes: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_time_code_field_rejects_invalid_sizes() {
+        let parser = DslParserImpl::new();
+        for (type_str, expected) in [
+            ("CUC(0,2)", "coarse=0"),
+            ("CUC(5,0)", "coarse=5"),
+            ("CUC(4,4)", "fine=4"),
+            ("CUC(200,100)", "coarse=200"),
+            ("CDS(1,0)", "day_bytes=1"),
+            ("CDS(2,3)", "submilli_bytes=3"),
+            ("CDS(255,255)", "day_bytes=255"),
+        ] {
+            let dsl = format!(
+                "field: t; type: {type_str}; length: 6byte; scope: layer(application); cover: entire_field"
+            );
+            let error = parser.parse_syntax_unit(&dsl).unwrap_err();
+            assert!(error.contains(type_str), "{type_str}: {error}");
+            assert!(error.contains(expected), "{type_str}: {error}");
+        }
+    }
+
     #[test]
     fn test_parse_complex_field() {
         let parser = DslParserImpl::new();
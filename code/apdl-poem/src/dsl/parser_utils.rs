@@ -2,9 +2,11 @@
 //!
 //! 包含DSL解析器使用的通用辅助函数
 
+use apdl_core::utils::time_code::{check_cds_format, check_cuc_format};
+use apdl_core::utils::Crc16Params;
 use apdl_core::{
-    AlgorithmAst, ChecksumAlgorithm, Constraint, CoverDesc, LengthDesc, LengthUnit, ScopeDesc,
-    UnitType,
+    AlgorithmAst, ByteOrder, ChecksumAlgorithm, Constraint, CoverDesc, LengthDesc, LengthUnit,
+    PackUnpackSpec, ScopeDesc, UnitType,
 };
 
 /// 解析单元类型
@@ -29,17 +31,53 @@ pub fn parse_unit_type(type_str: &str) -> Result<UnitType, String> {
         Ok(UnitType::RawData)
     } else if type_str == "Ip6Addr" {
         Ok(UnitType::Ip6Addr)
+    } else if let Some(inner) = type_str
+        .strip_prefix("CUC(")
+        .and_then(|s| s.strip_suffix(')'))
+    {
+        let (coarse, fine) = parse_time_code_args(inner, type_str)?;
+        check_cuc_format(coarse, fine).map_err(|e| format!("{type_str}: {e}"))?;
+        Ok(UnitType::CucTime { coarse, fine })
+    } else if let Some(inner) = type_str
+        .strip_prefix("CDS(")
+        .and_then(|s| s.strip_suffix(')'))
+    {
+        let (day_bytes, submilli_bytes) = parse_time_code_args(inner, type_str)?;
+        check_cds_format(day_bytes, submilli_bytes).map_err(|e| format!("{type_str}: {e}"))?;
+        Ok(UnitType::CdsTime {
+            day_bytes,
+            submilli_bytes,
+        })
     } else {
         Err(format!("Unknown type: {type_str}"))
     }
 }
 
+/// 解析时间码类型参数，如 CUC(4,2) 中的 "4,2"
+fn parse_time_code_args(inner: &str, type_str: &str) -> Result<(u8, u8), String> {
+    let Some((first, second)) = inner.split_once(',') else {
+        return Err(format!("Invalid time code type: {type_str}"));
+    };
+    let first = first
+        .trim()
+        .parse::<u8>()
+        .map_err(|_| format!("Invalid time code type: {type_str}"))?;
+    let second = second
+        .trim()
+        .parse::<u8>()
+        .map_err(|_| format!("Invalid time code type: {type_str}"))?;
+    Ok((first, second))
+}
+
 /// 解析长度描述
 pub fn parse_length_desc(length_str: &str) -> Result<LengthDesc, String> {
     let length_str = length_str.trim();
     if let Some(num_str) = length_str.strip_suffix("byte") {
         let num_str = num_str.trim();
         if let Ok(size) = num_str.parse::<usize>() {
+            if size == 0 {
+                return Err(format!("Zero-length field is not allowed: {length_str}"));
+            }
             Ok(LengthDesc {
                 size,
                 unit: LengthUnit::Byte,
@@ -50,6 +88,9 @@ pub fn parse_length_desc(length_str: &str) -> Result<LengthDesc, String> {
     } else if let Some(num_str) = length_str.strip_suffix("bit") {
         let num_str = num_str.trim();
         if let Ok(size) = num_str.parse::<usize>() {
+            if size == 0 {
+                return Err(format!("Zero-length field is not allowed: {length_str}"));
+            }
             Ok(LengthDesc {
                 size,
                 unit: LengthUnit::Bit,
@@ -93,7 +134,7 @@ pub fn parse_scope_desc(scope_str: &str) -> Result<ScopeDesc, String> {
         if let Some(layers) = stripped.strip_suffix(')') {
             if let Some(pos) = layers.find("→") {
                 let first = layers[..pos].trim();
-                let second = layers[pos + 1..].trim();
+                let second = layers[pos + "→".len()..].trim();
                 Ok(ScopeDesc::CrossLayer(first.to_string(), second.to_string()))
             } else {
                 Err(format!("Invalid cross_layer format: {scope_str}"))
@@ -118,19 +159,29 @@ pub fn parse_cover_desc(cover_str: &str) -> Result<CoverDesc, String> {
     if cover_str == "entire_field" {
         Ok(CoverDesc::EntireField)
     } else if cover_str.contains('[') && cover_str.contains(']') {
-        // 解析 field[offset..offset] 格式
+        // 解析 field[start..end]（不含end）和 field[start..=end]（含end）格式
         if let Some(open_bracket) = cover_str.find('[') {
             if let Some(close_bracket) = cover_str.find(']') {
                 let field_part = &cover_str[..open_bracket];
                 let range_part = &cover_str[open_bracket + 1..close_bracket];
 
                 if let Some(double_dot) = range_part.find("..") {
-                    let start_str = &range_part[..double_dot];
+                    let start_str = range_part[..double_dot].trim();
                     let end_str = &range_part[double_dot + 2..];
+                    let (end_str, inclusive) = match end_str.strip_prefix('=') {
+                        Some(end_str) => (end_str, true),
+                        None => (end_str, false),
+                    };
 
                     if let (Ok(start), Ok(end)) =
-                        (start_str.parse::<usize>(), end_str.parse::<usize>())
+                        (start_str.parse::<usize>(), end_str.trim().parse::<usize>())
                     {
+                        let end = if inclusive {
+                            end.checked_add(1)
+                                .ok_or_else(|| format!("Cover range end overflows: {cover_str}"))?
+                        } else {
+                            end
+                        };
                         return Ok(CoverDesc::Range(field_part.to_string(), start, end));
                     }
                 }
@@ -235,8 +286,16 @@ pub fn parse_constraint(constraint_str: &str) -> Result<Constraint, String> {
 }
 
 /// 解析算法
+///
+/// `crc16(poly=0x8005,init=0x0000)`形式可指定CRC16参数，未指定的参数取CCSDS默认值
 pub fn parse_algorithm(alg_str: &str) -> Result<AlgorithmAst, String> {
     let alg_str = alg_str.trim();
+    if let Some(args) = alg_str
+        .strip_prefix("crc16(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        return parse_crc16_params(args).map(AlgorithmAst::Crc16With);
+    }
     match alg_str {
         "crc16" => Ok(AlgorithmAst::Crc16),
         "crc32" => Ok(AlgorithmAst::Crc32),
@@ -246,6 +305,110 @@ pub fn parse_algorithm(alg_str: &str) -> Result<AlgorithmAst, String> {
     }
 }
 
+/// 解析CRC16参数列表，如`poly=0x8005,init=0x0000,refin=true`
+fn parse_crc16_params(args: &str) -> Result<Crc16Params, String> {
+    let mut params = Crc16Params::CCSDS;
+    for arg in args.split(',').map(str::trim).filter(|arg| !arg.is_empty()) {
+        let (key, value) = arg
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid crc16 parameter: {arg}"))?;
+        let value = value.trim();
+        match key.trim() {
+            "poly" => params.poly = parse_crc16_value(value)?,
+            "init" => params.init = parse_crc16_value(value)?,
+            "xorout" => params.xorout = parse_crc16_value(value)?,
+            "refin" => params.refin = parse_crc16_flag(value)?,
+            "refout" => params.refout = parse_crc16_flag(value)?,
+            other => return Err(format!("Unknown crc16 parameter: {other}")),
+        }
+    }
+    Ok(params)
+}
+
+fn parse_crc16_value(value: &str) -> Result<u16, String> {
+    match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex_str) => u16::from_str_radix(hex_str, 16),
+        None => value.parse::<u16>(),
+    }
+    .map_err(|_| format!("Invalid crc16 parameter value: {value}"))
+}
+
+fn parse_crc16_flag(value: &str) -> Result<bool, String> {
+    value
+        .parse::<bool>()
+        .map_err(|_| format!("Invalid crc16 flag value: {value}"))
+}
+
+/// 解析字段字节序（`endian: le|be`）
+pub fn parse_byte_order(endian_str: &str) -> Result<ByteOrder, String> {
+    match endian_str.trim() {
+        "le" | "little" | "little_endian" => Ok(ByteOrder::LittleEndian),
+        "be" | "big" | "big_endian" => Ok(ByteOrder::BigEndian),
+        other => Err(format!("Invalid endian: {other}")),
+    }
+}
+
+/// 由字段声明的字节序生成字段级打包规范
+pub fn byte_order_pack_spec(byte_order: Option<ByteOrder>) -> Option<PackUnpackSpec> {
+    byte_order.map(|byte_order| PackUnpackSpec {
+        byte_order,
+        ..PackUnpackSpec::default()
+    })
+}
+
+/// 按未转义的`;`切分定义，`\;`保留在片段中，由`unescape_text`还原
+pub fn split_unescaped_semicolons(input: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+    for (index, c) in input.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            ';' => {
+                parts.push(&input[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&input[start..]);
+    parts
+}
+
+/// 还原描述文本中的`\;`、`\\`、`\"`和`\n`转义，其余反斜杠原样保留
+pub fn unescape_text(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some(next @ (';' | '\\' | '"'))) => {
+                result.push(next);
+                chars.next();
+            }
+            ('\\', Some('n')) => {
+                result.push('\n');
+                chars.next();
+            }
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+/// 去除文本值两端的双引号并还原转义，内容中转义的`\"`得以保留
+pub fn unquote_text(value: &str) -> String {
+    let value = value.trim();
+    let inner = value
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .unwrap_or(value);
+    unescape_text(inner)
+}
+
 /// 解析校验和算法
 pub fn parse_checksum_algorithm(alg_str: &str) -> Result<ChecksumAlgorithm, String> {
     match alg_str {
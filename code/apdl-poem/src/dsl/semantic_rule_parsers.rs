@@ -23,6 +23,11 @@ impl SemanticRuleParsers {
         checksum_rules::parse_checksum_range(params, rule_type)
     }
 
+    /// 解析算法选择规则
+    pub fn parse_algorithm_select(params: &str) -> Result<SemanticRule, String> {
+        checksum_rules::parse_algorithm_select(params)
+    }
+
     /// 解析依赖关系规则
     pub fn parse_dependency(params: &str) -> Result<SemanticRule, String> {
         dependency_rules::parse_dependency(params)
@@ -53,6 +58,11 @@ impl SemanticRuleParsers {
         control_rules::parse_length_rule(params)
     }
 
+    /// 解析对齐规则
+    pub fn parse_alignment(params: &str) -> Result<SemanticRule, String> {
+        control_rules::parse_alignment(params)
+    }
+
     /// 解析路由分发规则
     pub fn parse_routing_dispatch(params: &str) -> Result<SemanticRule, String> {
         routing_rules::parse_routing_dispatch(params)
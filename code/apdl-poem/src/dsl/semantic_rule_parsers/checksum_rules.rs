@@ -4,9 +4,27 @@
 
 use apdl_core::{ChecksumAlgorithm, SemanticRule};
 
+use crate::dsl::parser_utils::parse_algorithm;
+
 /// 解析校验和范围规则
 pub fn parse_checksum_range(params: &str, rule_type: &str) -> Result<SemanticRule, String> {
-    // 解析范围，例如 "field1 to field2" 或 "start: field1 to field2"
+    // 解析范围，例如 "field1 to field2" 或 "start: field1 to field2; covers_self: false"
+    let (params, covers_self) = match params.split_once(';') {
+        Some((params, option)) => {
+            let covers_self = match option.trim().strip_prefix("covers_self:").map(str::trim) {
+                Some("true") => true,
+                Some("false") => false,
+                _ => {
+                    return Err(format!(
+                        "Invalid checksum range option '{}', expected 'covers_self: true|false'",
+                        option.trim()
+                    ))
+                }
+            };
+            (params, covers_self)
+        }
+        None => (params, true),
+    };
     let params = params.trim();
     let parts: Vec<&str> = params.split(" to ").collect();
     if parts.len() == 2 {
@@ -25,8 +43,56 @@ pub fn parse_checksum_range(params: &str, rule_type: &str) -> Result<SemanticRul
             },
             start_field: start_field.to_string(),
             end_field: parts[1].trim().to_string(),
+            covers_self,
         })
     } else {
         Err("Invalid checksum range format, expected 'field1 to field2'".to_string())
     }
 }
+
+/// 解析算法选择规则
+pub fn parse_algorithm_select(params: &str) -> Result<SemanticRule, String> {
+    // 解析算法选择，例如 "mode: 0 => crc16, 1 => crc32; target: fecf"
+    let (params, target_field) = match params.split_once(';') {
+        Some((params, target)) => {
+            let Some(target) = target.trim().strip_prefix("target:") else {
+                return Err(format!(
+                    "Invalid algorithm select option '{}', expected 'target: field'",
+                    target.trim()
+                ));
+            };
+            (params, Some(target.trim().to_string()))
+        }
+        None => (params, None),
+    };
+    let Some((selector_field, cases_str)) = params.trim().split_once(':') else {
+        return Err(
+            "Invalid algorithm select format, expected 'selector: value => algorithm, ...'"
+                .to_string(),
+        );
+    };
+
+    let mut cases = Vec::new();
+    for case in cases_str.split(',') {
+        let Some((value_str, alg_str)) = case.split_once("=>") else {
+            return Err(format!(
+                "Invalid algorithm select case '{}', expected 'value => algorithm'",
+                case.trim()
+            ));
+        };
+        let value_str = value_str.trim();
+        let value = if let Some(hex) = value_str.strip_prefix("0x") {
+            u64::from_str_radix(hex, 16)
+        } else {
+            value_str.parse::<u64>()
+        }
+        .map_err(|_| format!("Invalid algorithm select value: {value_str}"))?;
+        cases.push((value, parse_algorithm(alg_str.trim())?));
+    }
+
+    Ok(SemanticRule::AlgorithmSelect {
+        selector_field: selector_field.trim().to_string(),
+        target_field,
+        cases,
+    })
+}
@@ -82,6 +82,37 @@ pub fn parse_length_rule(params: &str) -> Result<SemanticRule, String> {
     }
 }
 
+/// 解析对齐规则
+pub fn parse_alignment(params: &str) -> Result<SemanticRule, String> {
+    // 解析对齐规则，例如 "field: payload; boundary: 4"
+    let mut field_name = None;
+    let mut boundary = None;
+    for part in params.split(';') {
+        if let Some((key, value)) = part.split_once(':') {
+            match key.trim() {
+                "field" => field_name = Some(value.trim().to_string()),
+                "boundary" => {
+                    let value = value.trim().trim_end_matches("byte");
+                    boundary = Some(
+                        value
+                            .parse::<usize>()
+                            .map_err(|_| format!("Invalid alignment boundary: {value}"))?,
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    match (field_name, boundary) {
+        (Some(field_name), Some(boundary)) if boundary > 0 => Ok(SemanticRule::Alignment {
+            field_name,
+            boundary,
+        }),
+        _ => Err("Invalid alignment format, expected 'field: field_name; boundary: N'".to_string()),
+    }
+}
+
 /// 解析序列控制规则
 pub fn parse_sequence_control(params: &str) -> Result<SemanticRule, String> {
     // 解析序列控制规则
@@ -13,7 +13,13 @@ pub fn parse_multiplexing(params: &str) -> Result<SemanticRule, String> {
     let mut route_target = String::new();
     let mut description = String::new();
 
-    if params.contains("field:") && params.contains("condition:") && params.contains("route_to:") {
+    // 路由目标可写作route_to:或route:
+    let route_key = if params.contains("route_to:") {
+        "route_to:"
+    } else {
+        "route:"
+    };
+    if params.contains("field:") && params.contains("condition:") && params.contains(route_key) {
         if let Some(field_start) = params.find("field:") {
             if let Some(semi_pos) = params[field_start..].find(';').map(|p| p + field_start) {
                 field_name = params[field_start + 6..semi_pos].trim().to_string();
@@ -29,10 +35,10 @@ pub fn parse_multiplexing(params: &str) -> Result<SemanticRule, String> {
             }
         }
 
-        if let Some(route_start) = params.find("route_to:") {
-            let remaining = &params[route_start + 9..];
-            if let Some(semi_pos) = remaining.find(';').map(|p| p + route_start + 9) {
-                route_target = remaining[..semi_pos - (route_start + 9)].trim().to_string();
+        if let Some(route_start) = params.find(route_key) {
+            let remaining = &params[route_start + route_key.len()..];
+            if let Some(semi_pos) = remaining.find(';') {
+                route_target = remaining[..semi_pos].trim().to_string();
             } else {
                 route_target = remaining.trim().to_string();
             }
@@ -0,0 +1,8 @@
+//! 常用类型预导入
+//!
+//! 在`apdl_core::prelude`的基础上引入DSL解析器和帧组装器
+
+pub use apdl_core::prelude::*;
+
+pub use crate::dsl::parser::DslParserImpl;
+pub use crate::standard_units::frame_assembler::{FrameAssembler, FrameStream, FrameTemplate};
@@ -74,6 +74,7 @@ impl ConnectorEngine {
                 child_packet_queue: VecDeque::new(),
                 parent_assembler: target_assembler.clone(),
                 remaining_child_data: Vec::new(),
+                stream_offset: 0,
                 _packet_type: dispatch_flag.clone(),
             });
         child_packet_queue.child_packet_queue.push_back(child_data);
@@ -94,6 +95,7 @@ impl ConnectorEngine {
                 child_packet_queue: VecDeque::new(),
                 parent_assembler: FrameAssembler::new(),
                 remaining_child_data: Vec::new(),
+                stream_offset: 0,
                 _packet_type: parent_type.to_string(),
             });
         queue_item.child_packet_queue.push_back(child_data);
@@ -135,11 +137,17 @@ impl ConnectorEngine {
             }
             DataPlacementStrategy::StreamBased => {
                 // 流式放置策略：类似MPDU但不使用指针
-                packet_builder_stream::build_stream_packet(
+                match packet_builder_stream::build_stream_packet(
                     &mut self.child_packet_queues,
                     selected_dispatch_flag,
-                )
-                .map(|packet| (packet, selected_dispatch_flag.clone()))
+                    placement_config,
+                ) {
+                    Ok(packet) => packet.map(|packet| (packet, selected_dispatch_flag.clone())),
+                    Err(e) => {
+                        eprintln!("Error building stream packet: {e}");
+                        None
+                    }
+                }
             }
             DataPlacementStrategy::Custom(_) => {
                 // 自定义策略，暂时返回None
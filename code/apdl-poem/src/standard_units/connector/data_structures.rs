@@ -19,6 +19,8 @@ pub(super) struct MultiplexQueue {
     pub parent_assembler: FrameAssembler,
     /// 剩余的子包数据
     pub remaining_child_data: Vec<u8>,
+    /// 剩余子包数据在原子包中的偏移（流式分段使用）
+    pub stream_offset: usize,
     /// 包类型标识
     pub _packet_type: String,
 }
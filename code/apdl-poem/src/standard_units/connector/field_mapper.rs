@@ -4,6 +4,9 @@
 
 use std::collections::HashMap;
 
+use super::field_mapping::parse_default_value;
+use crate::standard_units::frame_assembler::CustomAlgorithmRegistry;
+
 /// 映射函数类型定义
 type MappingFunction = Box<dyn Fn(&[u8]) -> Vec<u8> + Send + Sync>;
 
@@ -82,25 +85,103 @@ impl FieldMapper {
     ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
         if let Some(func) = self.mapping_functions.get(mapping_function_name) {
             Ok(func(source_value))
+        } else if let Some(func) = CustomAlgorithmRegistry::lookup_global(mapping_function_name) {
+            // 未在映射器中注册时查找全局自定义算法
+            Ok(func(source_value))
         } else {
             Err(format!("Unknown mapping function: {mapping_function_name}").into())
         }
     }
 
     /// 执行枚举映射
+    ///
+    /// 精确匹配优先于通配符；多个通配符模式都匹配时取字面字符最多（最具体）的一条，
+    /// 具体程度相同时取先声明的一条
     pub fn map_enum(
         &self,
         source_value: &str,
         enum_mappings: Option<&Vec<apdl_core::EnumMappingEntry>>,
     ) -> Option<String> {
-        if let Some(mappings) = enum_mappings {
-            for mapping in mappings {
-                if Self::matches_enum_pattern(source_value, &mapping.source_enum) {
-                    return Some(mapping.target_enum.clone());
-                }
+        Self::match_enum(source_value, enum_mappings?).map(|mapping| mapping.target_enum.clone())
+    }
+
+    /// 查找与源枚举值最匹配的枚举映射条目，匹配规则同[`FieldMapper::map_enum`]
+    pub fn match_enum<'a>(
+        source_value: &str,
+        enum_mappings: &'a [apdl_core::EnumMappingEntry],
+    ) -> Option<&'a apdl_core::EnumMappingEntry> {
+        let mut best: Option<(&apdl_core::EnumMappingEntry, (bool, usize))> = None;
+        for mapping in enum_mappings {
+            if !Self::matches_enum_pattern(source_value, &mapping.source_enum) {
+                continue;
             }
+            let specificity = Self::pattern_specificity(&mapping.source_enum);
+            if best.is_none_or(|(_, best_specificity)| specificity > best_specificity) {
+                best = Some((mapping, specificity));
+            }
+        }
+        best.map(|(mapping, _)| mapping)
+    }
+
+    /// 将枚举映射的目标值转换为字节
+    ///
+    /// 目标值按数值解析（十进制或`0x`十六进制，大端8字节），无法解析时取其UTF-8字节；
+    /// 连接器引擎和帧组装器写入目标字段前都按字段宽度去除多余的高位零字节
+    pub fn enum_target_bytes(target_enum: &str) -> Vec<u8> {
+        parse_default_value(target_enum).unwrap_or_else(|_| target_enum.as_bytes().to_vec())
+    }
+
+    /// 模式的具体程度：是否为精确值，以及字面（非通配符）字符数
+    fn pattern_specificity(pattern: &str) -> (bool, usize) {
+        if pattern == "any" {
+            return (false, 0);
         }
-        None
+        let literal = pattern.chars().filter(|c| !matches!(c, '*' | '?')).count();
+        (literal == pattern.chars().count(), literal)
+    }
+
+    /// 执行掩码映射表查找
+    ///
+    /// 源值与各条目的`mask`按字节相与后和`src_masked`比较，返回第一个匹配条目的`dst`；
+    /// 均不匹配时返回解析后的默认值
+    pub fn map_mask(
+        source_value: &[u8],
+        mask_table: &[apdl_core::MaskMappingEntry],
+        default_value: &str,
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        match Self::match_mask(source_value, mask_table) {
+            Some(entry) => {
+                log::debug!(
+                    "Mask mapping matched: source={source_value:02X?} & mask={:02X?} = {:02X?} -> dst={:02X?}",
+                    entry.mask,
+                    entry.src_masked,
+                    entry.dst
+                );
+                Ok(entry.dst.clone())
+            }
+            None => {
+                log::debug!(
+                    "Mask mapping not matched for source={source_value:02X?}, using default={default_value}"
+                );
+                parse_default_value(default_value)
+            }
+        }
+    }
+
+    /// 按声明顺序查找第一个匹配源值的掩码映射条目，掩码长度须与源值一致
+    pub fn match_mask<'a>(
+        source_value: &[u8],
+        mask_table: &'a [apdl_core::MaskMappingEntry],
+    ) -> Option<&'a apdl_core::MaskMappingEntry> {
+        mask_table.iter().find(|entry| {
+            entry.mask.len() == source_value.len()
+                && entry.src_masked.len() == source_value.len()
+                && source_value
+                    .iter()
+                    .zip(&entry.mask)
+                    .map(|(src, mask)| src & mask)
+                    .eq(entry.src_masked.iter().copied())
+        })
     }
 
     /// 检查源枚举值是否匹配模式（支持通配符）
@@ -226,6 +307,35 @@ mod tests {
         assert_eq!(result, vec![0xAB]); // 应该返回高字节
     }
 
+    fn mask_entry(mask: &[u8], src_masked: &[u8], dst: &[u8]) -> apdl_core::MaskMappingEntry {
+        apdl_core::MaskMappingEntry {
+            mask: mask.to_vec(),
+            src_masked: src_masked.to_vec(),
+            dst: dst.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_mask_mapping_first_match_wins() {
+        // 两个条目的掩码重叠，0x0481同时匹配二者
+        let table = vec![
+            mask_entry(&[0xFF, 0xF0], &[0x04, 0x80], &[0x01]),
+            mask_entry(&[0xFF, 0x00], &[0x04, 0x00], &[0x02]),
+            mask_entry(&[0xFF], &[0x05], &[0x03]),
+        ];
+
+        let result = FieldMapper::map_mask(&[0x04, 0x81], &table, "0").unwrap();
+        assert_eq!(result, vec![0x01]);
+
+        let result = FieldMapper::map_mask(&[0x04, 0x11], &table, "0").unwrap();
+        assert_eq!(result, vec![0x02]);
+
+        // 单字节条目不匹配双字节源值，回退到默认值
+        let result = FieldMapper::map_mask(&[0x05, 0x00], &table, "0x7F").unwrap();
+        assert_eq!(result, 0x7Fu64.to_be_bytes().to_vec());
+        assert!(FieldMapper::map_mask(&[0x05, 0x00], &table, "none").is_err());
+    }
+
     #[test]
     fn test_unknown_mapping_function() {
         let mapper = FieldMapper::new();
@@ -1,7 +1,9 @@
 //! 字段映射功能模块
 
+use super::field_mapper::FieldMapper;
 use crate::standard_units::frame_assembler::core::FrameAssembler;
-use apdl_core::FieldMappingEntry;
+use crate::standard_units::frame_assembler::CustomAlgorithmRegistry;
+use apdl_core::{EnumMappingEntry, FieldMappingEntry};
 
 /// 应用映射逻辑
 pub(super) fn apply_mapping_logic(
@@ -9,9 +11,9 @@ pub(super) fn apply_mapping_logic(
     mapping_logic: &str,
     default_value: &str,
     mask_table: Option<&[apdl_core::MaskMappingEntry]>,
+    algorithms: &CustomAlgorithmRegistry,
 ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
     match mapping_logic {
-        "identity" => Ok(source_value.to_vec()),
         "hash_mod_64" => {
             // 简单的哈希实现
             let hash_value = simple_hash(source_value);
@@ -25,66 +27,28 @@ pub(super) fn apply_mapping_logic(
             Ok(vec![((result >> 8) & 0xFF) as u8, (result & 0xFF) as u8])
         }
         "mask_table" => {
-            // 使用掩码映射表
-            if let Some(table) = mask_table {
-                apply_mask_mapping_table(source_value, table, default_value)
-            } else {
-                // 没有提供掩码映射表，使用默认值
-                parse_default_value(default_value)
-            }
-        }
-        _ => {
-            // 如果映射逻辑无法识别，使用默认值
-            parse_default_value(default_value)
+            // 使用掩码映射表，没有提供时使用默认值
+            FieldMapper::map_mask(source_value, mask_table.unwrap_or_default(), default_value)
         }
+        "" => parse_default_value(default_value),
+        // 恒等映射或已注册的自定义算法（先查目标组装器实例，再查全局注册表）
+        logic => Ok(algorithms.apply_mapping(logic, source_value)?),
     }
 }
 
-/// 应用掩码映射表查找
-///
-/// 根据掩码映射表，将源值应用掩码后查找对应的目标值
+/// 应用枚举映射，源值按UTF-8文本与`source_enum`模式匹配
 ///
-/// # 参数
-/// - `source_value`: 源字段值，如 [0x04, 0x81]
-/// - `mask_table`: 掩码映射表
-/// - `default_value`: 未匹配时的默认值
-///
-/// # 返回
-/// 匹配的目标值或默认值
-pub(super) fn apply_mask_mapping_table(
+/// 目标值由[`FieldMapper::enum_target_bytes`]转换；未匹配任何条目时使用默认值
+pub(super) fn apply_enum_mapping(
     source_value: &[u8],
-    mask_table: &[apdl_core::MaskMappingEntry],
+    enum_mappings: &[EnumMappingEntry],
     default_value: &str,
 ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
-    // 遍历掩码映射表
-    for entry in mask_table {
-        // 检查长度是否匹配
-        if entry.mask.len() != source_value.len() {
-            continue;
-        }
-
-        // 应用掩码
-        let masked_value: Vec<u8> = source_value
-            .iter()
-            .zip(entry.mask.iter())
-            .map(|(src, mask)| src & mask)
-            .collect();
-
-        // 检查是否匹配期望的掩码值
-        if masked_value == entry.src_masked {
-            println!(
-                "Mask mapping matched: source={:02X?} & mask={:02X?} = {:02X?} -> dst={:02X?}",
-                source_value, entry.mask, masked_value, entry.dst
-            );
-            return Ok(entry.dst.clone());
-        }
+    let source_str = String::from_utf8_lossy(source_value);
+    match FieldMapper::match_enum(&source_str, enum_mappings) {
+        Some(entry) => Ok(FieldMapper::enum_target_bytes(&entry.target_enum)),
+        None => parse_default_value(default_value),
     }
-
-    // 未匹配，使用默认值
-    println!(
-        "Mask mapping not matched for source={source_value:02X?}, using default={default_value}"
-    );
-    parse_default_value(default_value)
 }
 
 /// 简单的哈希函数
@@ -98,7 +62,9 @@ fn simple_hash(data: &[u8]) -> u64 {
 }
 
 /// 解析默认值
-fn parse_default_value(default_value: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+pub(super) fn parse_default_value(
+    default_value: &str,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
     if let Some(hex_str) = default_value.strip_prefix("0x") {
         let value = u64::from_str_radix(hex_str, 16)
             .map_err(|_| format!("Invalid hex value: {default_value}"))?;
@@ -130,13 +96,21 @@ pub(super) fn apply_field_mapping_rules(
         }
         // 获取源字段值
         if let Ok(source_value) = source_assembler.get_field_value(&mapping.source_field) {
-            // 统一应用映射逻辑
-            let mapped_value = apply_mapping_logic(
-                &source_value,
-                &mapping.mapping_logic,
-                &mapping.default_value,
-                mapping.mask_mapping_table.as_deref(),
-            )?;
+            // 有枚举映射时按枚举映射取值，否则统一应用映射逻辑；数值结果按目标字段宽度截取
+            let mapped_value = match mapping.enum_mappings.as_deref() {
+                Some(enum_mappings) => {
+                    apply_enum_mapping(&source_value, enum_mappings, &mapping.default_value)?
+                }
+                None => apply_mapping_logic(
+                    &source_value,
+                    &mapping.mapping_logic,
+                    &mapping.default_value,
+                    mapping.mask_mapping_table.as_deref(),
+                    &target_assembler.custom_algorithms,
+                )?,
+            };
+            let mapped_value =
+                target_assembler.fit_field_width(&mapping.target_field, mapped_value);
 
             // 设置目标字段值
             target_assembler
@@ -8,6 +8,10 @@ mod packet_builder_stream;
 // 公开模块
 pub mod connector_engine;
 pub mod field_mapper;
+pub mod mpdu_reassembler;
+pub mod stream_fragmenter;
 
 pub use connector_engine::*;
 pub use field_mapper::*;
+pub use mpdu_reassembler::*;
+pub use stream_fragmenter::*;
@@ -0,0 +1,209 @@
+//! MPDU (PointerBased)策略包重组模块
+//!
+//! 与MPDU包构建相反，依据首导头指针从连续的父包中恢复原始的变长子包
+
+use crate::standard_units::frame_assembler::core::FrameAssembler;
+use apdl_core::DataPlacementConfig;
+
+/// 首导头指针：数据区中没有子包起始（仅有续传数据）
+pub const MPDU_NO_PACKET_START: u16 = 0x07FF;
+/// 首导头指针：数据区仅包含填充码
+pub const MPDU_IDLE_DATA: u16 = 0x07FE;
+
+/// 子包长度函数：根据子包开头的字节返回子包总长度，字节不足以确定长度时返回None
+pub type PacketLengthFn = Box<dyn Fn(&[u8]) -> Option<usize>>;
+
+/// CCSDS空间包长度：6字节主导头，包数据长度字段（第5、6字节）加1为数据域长度
+pub fn ccsds_space_packet_length(header: &[u8]) -> Option<usize> {
+    if header.len() < 6 {
+        return None;
+    }
+    Some(6 + u16::from_be_bytes([header[4], header[5]]) as usize + 1)
+}
+
+/// MPDU重组器
+///
+/// 按顺序接收父包，跨父包拼接子包；首导头指针与续传数据不一致时丢弃不完整的子包并从指针位置重新开始
+pub struct MpduReassembler {
+    pointer_offset: usize,
+    pointer_len: usize,
+    data_offset: usize,
+    data_len: usize,
+    padding: u8,
+    packet_length: PacketLengthFn,
+    pending: Vec<u8>,
+    discarded_bytes: usize,
+}
+
+impl MpduReassembler {
+    /// 根据父包中首导头指针和数据区的字节位置创建重组器
+    pub fn new(
+        pointer_offset: usize,
+        pointer_len: usize,
+        data_offset: usize,
+        data_len: usize,
+        packet_length: PacketLengthFn,
+    ) -> Self {
+        Self {
+            pointer_offset,
+            pointer_len,
+            data_offset,
+            data_len,
+            padding: 0xFF,
+            packet_length,
+            pending: Vec::new(),
+            discarded_bytes: 0,
+        }
+    }
+
+    /// 根据父包模板和MPDU配置创建重组器，与MPDU包构建使用相同的配置
+    pub fn from_template(
+        parent_template: &FrameAssembler,
+        mpdu_config: &DataPlacementConfig,
+        packet_length: PacketLengthFn,
+    ) -> Result<Self, String> {
+        let config_param = |key: &str| {
+            mpdu_config
+                .config_params
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value.as_str())
+        };
+
+        let pointer_field =
+            config_param("pointer_field").ok_or("MPDU config is missing 'pointer_field'")?;
+        let field_layout = |field_name: &str| -> Result<(usize, usize), String> {
+            let offset = parent_template
+                .get_field_position(field_name)
+                .map_err(|e| format!("Failed to locate field '{field_name}': {e}"))?;
+            let size = parent_template
+                .get_field_size_by_name(field_name)
+                .map_err(|e| format!("Failed to get field size for '{field_name}': {e}"))?;
+            Ok((offset, size))
+        };
+        let (pointer_offset, pointer_len) = field_layout(pointer_field)?;
+        let (data_offset, data_len) = field_layout(&mpdu_config.target_field)?;
+
+        let mut reassembler = Self::new(
+            pointer_offset,
+            pointer_len,
+            data_offset,
+            data_len,
+            packet_length,
+        );
+        if let Some(padding_value) = config_param("padding_value") {
+            if let Ok(pad_byte) = u8::from_str_radix(padding_value.trim_start_matches("0x"), 16) {
+                reassembler.padding = pad_byte;
+            }
+        }
+        Ok(reassembler)
+    }
+
+    /// 设置填充码，数据区中剩余字节全部为填充码时视为包区结束
+    pub fn set_padding(&mut self, padding: u8) {
+        self.padding = padding;
+    }
+
+    /// 获取因首导头指针不一致或缺少包起始而丢弃的字节数
+    pub fn discarded_bytes(&self) -> usize {
+        self.discarded_bytes
+    }
+
+    /// 获取尚未完整的子包字节数
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// 接收一个父包，返回其中完成重组的子包
+    pub fn push_frame(&mut self, frame: &[u8]) -> Result<Vec<Vec<u8>>, String> {
+        let pointer_end = self.pointer_offset + self.pointer_len;
+        let data_end = self.data_offset + self.data_len;
+        if frame.len() < pointer_end.max(data_end) {
+            return Err(format!(
+                "MPDU frame too short: need {} bytes, got {}",
+                pointer_end.max(data_end),
+                frame.len()
+            ));
+        }
+
+        let pointer = frame[self.pointer_offset..pointer_end]
+            .iter()
+            .fold(0u64, |acc, &byte| (acc << 8) | byte as u64) as u16
+            & MPDU_NO_PACKET_START;
+        let data = &frame[self.data_offset..data_end];
+        let mut packets = Vec::new();
+
+        match pointer {
+            MPDU_IDLE_DATA => {}
+            MPDU_NO_PACKET_START => {
+                if self.pending.is_empty() {
+                    // 未同步到包起始，无法使用续传数据
+                    self.discarded_bytes += data.len();
+                } else {
+                    self.extend_pending(data, &mut packets);
+                }
+            }
+            pointer => {
+                let start = pointer as usize;
+                if start > data.len() {
+                    return Err(format!(
+                        "First header pointer {start} exceeds MPDU data length {}",
+                        data.len()
+                    ));
+                }
+
+                // 指针之前的字节为上一个子包的续传数据，必须恰好完成该子包
+                let head = &data[..start];
+                if self.pending.is_empty() {
+                    self.discarded_bytes += head.len();
+                } else {
+                    let consumed = self.extend_pending(head, &mut packets);
+                    if !self.pending.is_empty() || consumed < head.len() {
+                        self.discarded_bytes += self.pending.len() + head.len() - consumed;
+                        self.pending.clear();
+                    }
+                }
+
+                let mut pos = start;
+                while pos < data.len() && !data[pos..].iter().all(|&byte| byte == self.padding) {
+                    pos += self.extend_pending(&data[pos..], &mut packets);
+                    if !self.pending.is_empty() {
+                        // 子包延续到下一个父包
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(packets)
+    }
+
+    /// 将字节追加到当前子包，子包完整时移入输出，返回消耗的字节数
+    fn extend_pending(&mut self, bytes: &[u8], packets: &mut Vec<Vec<u8>>) -> usize {
+        let mut consumed = 0;
+        loop {
+            match (self.packet_length)(&self.pending) {
+                Some(total) => {
+                    let total = total.max(1);
+                    let take = total
+                        .saturating_sub(self.pending.len())
+                        .min(bytes.len() - consumed);
+                    self.pending
+                        .extend_from_slice(&bytes[consumed..consumed + take]);
+                    consumed += take;
+                    if self.pending.len() >= total {
+                        packets.push(std::mem::take(&mut self.pending));
+                    }
+                    return consumed;
+                }
+                None => {
+                    // 长度尚无法确定，逐字节读取子包头
+                    if consumed == bytes.len() {
+                        return consumed;
+                    }
+                    self.pending.push(bytes[consumed]);
+                    consumed += 1;
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! MPDU (PointerBased)策略包构建模块
 
 use super::data_structures::MultiplexQueue;
+use super::mpdu_reassembler::{MPDU_IDLE_DATA, MPDU_NO_PACKET_START};
 use crate::standard_units::frame_assembler::core::FrameAssembler;
 use apdl_core::DataPlacementConfig;
 use std::collections::HashMap;
@@ -42,9 +43,6 @@ pub(super) fn build_mpdu_packet(
         let mut current_data = vec![];
         let mut used_bytes = 0;
 
-        // 首导头指针位置初始化为0
-        let mut pointer_pos = 0;
-
         // 如果有剩余的子包数据，先填充到当前包
         if !current_queue.remaining_child_data.is_empty() {
             let space_left = capacity;
@@ -68,20 +66,25 @@ pub(super) fn build_mpdu_packet(
             }
         }
 
-        // indicate that the current packet is not full
-        if used_bytes < capacity {
-            pointer_pos = 0xFFFF;
-            if used_bytes > 0 {
-                pointer_pos = 0x07FF;
-            }
-        }
+        // 首导头指针：仅有续传数据时为0x07FF（包括续传数据占满整个数据区），
+        // 没有任何数据时为0xFFFF（空包，稍后转为0x07FE）
+        let mut pointer_pos: u16 = if used_bytes > 0 {
+            MPDU_NO_PACKET_START
+        } else {
+            0xFFFF
+        };
 
         // 从队列中获取子包并填充
         while used_bytes < capacity && !current_queue.child_packet_queue.is_empty() {
             if let Some(mut child) = current_queue.child_packet_queue.pop_front() {
                 if let Ok(child_data) = child.assembler.assemble_frame() {
-                    if pointer_pos == 0xFFFF || pointer_pos == 0x07FF {
-                        // 只要有子包，当前pos就是指针位置
+                    if pointer_pos == 0xFFFF || pointer_pos == MPDU_NO_PACKET_START {
+                        // 只要有子包，当前pos就是指针位置；11位指针无法表示0x07FE及之后的偏移
+                        if used_bytes >= MPDU_IDLE_DATA as usize {
+                            return Err(format!(
+                                "First header pointer {used_bytes} exceeds the 11-bit range"
+                            ));
+                        }
                         pointer_pos = used_bytes as u16;
                     }
 
@@ -132,7 +135,7 @@ pub(super) fn build_mpdu_packet(
 
     // 如果是空包
     if pointer_pos == 0xFFFF {
-        pointer_pos = 0x07FE;
+        pointer_pos = MPDU_IDLE_DATA;
     }
 
     // 设置首导头指针
@@ -202,9 +205,23 @@ fn set_mpdu_pointer(
         // 根据CCSDS标准，首导头指针指向MPDU包区中第一个完整包的第一个字节位置
         // 首导头指针值等于第一个完整包在MPDU数据区中的偏移量
 
-        let pointer_value = pointer_pos;
+        // 指针占字段的低11位，字段中更高的位（如M_PDU导头的5位备用位）保持模板中的取值
+        let current = parent_assembler
+            .get_field_value(pointer_field_name)
+            .map_err(|e| format!("Failed to read pointer field '{pointer_field_name}': {e}"))?;
+        if current.len() < 2 || current.len() > 8 {
+            return Err(format!(
+                "Pointer field '{pointer_field_name}' must be 2 to 8 bytes wide to hold an 11-bit pointer, got {}",
+                current.len()
+            ));
+        }
+        let pointer_mask = MPDU_NO_PACKET_START as u64;
+        let current_value = current
+            .iter()
+            .fold(0u64, |acc, &byte| (acc << 8) | byte as u64);
+        let pointer_value = (current_value & !pointer_mask) | (pointer_pos as u64 & pointer_mask);
 
-        let pointer_bytes = pointer_value.to_be_bytes().to_vec();
+        let pointer_bytes = pointer_value.to_be_bytes()[8 - current.len()..].to_vec();
 
         // 设置指针字段值
         parent_assembler
@@ -1,16 +1,32 @@
 //! Stream策略包构建模块
 
 use super::data_structures::MultiplexQueue;
+use super::stream_fragmenter::StreamFragmenter;
+use apdl_core::DataPlacementConfig;
 use std::collections::HashMap;
 
 /// 构建流式放置包
+///
+/// 配置了`flag_field`时按分段方式每次输出一个定长父包，超出数据区的子包跨父包续传；
+/// 否则连接队列中子包的数据
 pub(super) fn build_stream_packet(
     child_packet_queues: &mut HashMap<String, MultiplexQueue>,
     parent_type: &str,
-) -> Option<Vec<u8>> {
+    placement_config: &DataPlacementConfig,
+) -> Result<Option<Vec<u8>>, String> {
+    let segmented = placement_config
+        .config_params
+        .iter()
+        .any(|(key, _)| key == "flag_field");
+    if segmented {
+        return build_stream_segment(child_packet_queues, parent_type, placement_config);
+    }
+
     // 第一阶段：收集数据（持有可变引用）
     let (result, should_remove) = {
-        let current_queue = child_packet_queues.get_mut(parent_type)?;
+        let Some(current_queue) = child_packet_queues.get_mut(parent_type) else {
+            return Ok(None);
+        };
 
         let mut result = Vec::new();
 
@@ -38,8 +54,53 @@ pub(super) fn build_stream_packet(
     }
 
     if result.is_empty() {
-        None
+        Ok(None)
     } else {
-        Some(result)
+        Ok(Some(result))
+    }
+}
+
+/// 输出一个流式分段父包：优先续传上一个子包的剩余数据，否则取出下一个子包
+fn build_stream_segment(
+    child_packet_queues: &mut HashMap<String, MultiplexQueue>,
+    parent_type: &str,
+    placement_config: &DataPlacementConfig,
+) -> Result<Option<Vec<u8>>, String> {
+    let Some(current_queue) = child_packet_queues.get_mut(parent_type) else {
+        return Ok(None);
+    };
+    let fragmenter =
+        StreamFragmenter::from_template(&current_queue.parent_assembler, placement_config)?;
+
+    if current_queue.remaining_child_data.is_empty() {
+        let Some(mut child) = current_queue.child_packet_queue.pop_front() else {
+            child_packet_queues.remove(parent_type);
+            return Ok(None);
+        };
+        current_queue.remaining_child_data = child
+            .assembler
+            .assemble_frame()
+            .map_err(|e| format!("Failed to assemble child packet: {e}"))?;
+        current_queue.stream_offset = 0;
+    }
+
+    let take = current_queue
+        .remaining_child_data
+        .len()
+        .min(fragmenter.capacity());
+    let last = take == current_queue.remaining_child_data.len();
+    let frame = fragmenter.build_segment(
+        &current_queue.remaining_child_data[..take],
+        current_queue.stream_offset,
+        last,
+    )?;
+    current_queue.remaining_child_data.drain(..take);
+    current_queue.stream_offset += take;
+
+    // 只有当child_packet_queue为空且没有剩余数据时，才移除队列
+    if current_queue.child_packet_queue.is_empty() && current_queue.remaining_child_data.is_empty()
+    {
+        child_packet_queues.remove(parent_type);
     }
+    Ok(Some(frame))
 }
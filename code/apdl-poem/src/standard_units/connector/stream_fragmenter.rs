@@ -0,0 +1,352 @@
+//! Stream (StreamBased)策略分段与重组模块
+//!
+//! 将超过父包数据区容量的子包(SDU)切分到多个定长父包中，每个父包携带分段标志、段偏移和有效长度；
+//! 重组时依据相同的配置从连续的父包中恢复原始SDU
+//!
+//! 配置参数（`DataPlacementConfig::config_params`）：
+//! - `flag_field`：分段标志字段（必需），取值见[`SegmentFlag`]
+//! - `offset_field`：本段数据在SDU中的字节偏移（可选），重组时用于检查分段连续性
+//! - `length_field`：本段数据区中有效数据的字节数（必需），SDU末尾可能与填充码相同，不能按填充码判断边界
+//! - `padding_value`：末段数据区的填充码，默认0xFF
+//!
+//! 上述字段须按字节对齐
+
+use crate::standard_units::frame_assembler::core::FrameAssembler;
+use apdl_core::DataPlacementConfig;
+
+/// 分段标志，取值与CCSDS空间包序列标志一致
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentFlag {
+    /// 中间段
+    Continuation,
+    /// 首段
+    First,
+    /// 末段
+    Last,
+    /// 未分段（SDU完整放入一个父包）
+    Unsegmented,
+}
+
+impl SegmentFlag {
+    /// 标志字段的取值
+    pub fn value(self) -> u64 {
+        match self {
+            SegmentFlag::Continuation => 0b00,
+            SegmentFlag::First => 0b01,
+            SegmentFlag::Last => 0b10,
+            SegmentFlag::Unsegmented => 0b11,
+        }
+    }
+
+    /// 由标志字段的取值解析，仅使用低2位
+    pub fn from_value(value: u64) -> Self {
+        match value & 0b11 {
+            0b00 => SegmentFlag::Continuation,
+            0b01 => SegmentFlag::First,
+            0b10 => SegmentFlag::Last,
+            _ => SegmentFlag::Unsegmented,
+        }
+    }
+
+    /// 根据段在SDU中的位置确定标志
+    fn for_segment(first: bool, last: bool) -> Self {
+        match (first, last) {
+            (true, true) => SegmentFlag::Unsegmented,
+            (true, false) => SegmentFlag::First,
+            (false, true) => SegmentFlag::Last,
+            (false, false) => SegmentFlag::Continuation,
+        }
+    }
+}
+
+/// 读取配置参数
+fn config_param<'a>(config: &'a DataPlacementConfig, key: &str) -> Option<&'a str> {
+    config
+        .config_params
+        .iter()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.as_str())
+}
+
+/// 解析填充码，支持十六进制（可带0x前缀）和十进制，默认0xFF
+fn padding_byte(config: &DataPlacementConfig) -> u8 {
+    config_param(config, "padding_value")
+        .and_then(|value| {
+            u8::from_str_radix(value.trim_start_matches("0x"), 16)
+                .ok()
+                .or_else(|| value.parse().ok())
+        })
+        .unwrap_or(0xFF)
+}
+
+/// 将数值编码为指定长度的大端字节，超出字段范围时报错
+fn encode_value(field_name: &str, value: u64, size: usize) -> Result<Vec<u8>, String> {
+    if size < 8 && value >> (size * 8) != 0 {
+        return Err(format!(
+            "Value {value} does not fit in {size}-byte field '{field_name}'"
+        ));
+    }
+    let bytes = value.to_be_bytes();
+    Ok(bytes[8 - size.min(8)..].to_vec())
+}
+
+/// 父包中字段的字节位置
+#[derive(Debug, Clone, Copy)]
+struct FieldSlot {
+    offset: usize,
+    len: usize,
+}
+
+impl FieldSlot {
+    fn locate(parent_template: &FrameAssembler, field_name: &str) -> Result<Self, String> {
+        let offset = parent_template
+            .get_field_position(field_name)
+            .map_err(|e| format!("Failed to locate field '{field_name}': {e}"))?;
+        let len = parent_template
+            .get_field_size_by_name(field_name)
+            .map_err(|e| format!("Failed to get field size for '{field_name}': {e}"))?;
+        Ok(Self { offset, len })
+    }
+
+    fn end(&self) -> usize {
+        self.offset + self.len
+    }
+
+    fn read<'a>(&self, frame: &'a [u8]) -> &'a [u8] {
+        &frame[self.offset..self.end()]
+    }
+
+    fn read_value(&self, frame: &[u8]) -> u64 {
+        self.read(frame)
+            .iter()
+            .fold(0u64, |acc, &byte| (acc << 8) | byte as u64)
+    }
+}
+
+/// 流式分段器
+///
+/// 以父包为模板，为每一段设置数据区、分段标志、段偏移和有效长度后组装父包
+pub struct StreamFragmenter {
+    parent_template: FrameAssembler,
+    data_field: String,
+    flag_field: String,
+    offset_field: Option<String>,
+    length_field: String,
+    capacity: usize,
+    padding: u8,
+}
+
+impl StreamFragmenter {
+    /// 根据父包模板和流式放置配置创建分段器
+    pub fn from_template(
+        parent_template: &FrameAssembler,
+        config: &DataPlacementConfig,
+    ) -> Result<Self, String> {
+        let flag_field =
+            config_param(config, "flag_field").ok_or("Stream config is missing 'flag_field'")?;
+        let length_field = config_param(config, "length_field")
+            .ok_or("Stream config is missing 'length_field'")?;
+        let capacity = parent_template
+            .get_field_size_by_name(&config.target_field)
+            .map_err(|e| {
+                format!(
+                    "Failed to get field size for '{}': {}",
+                    config.target_field, e
+                )
+            })?;
+        if capacity == 0 {
+            return Err(format!(
+                "Stream data field '{}' has no capacity",
+                config.target_field
+            ));
+        }
+
+        Ok(Self {
+            parent_template: parent_template.clone(),
+            data_field: config.target_field.clone(),
+            flag_field: flag_field.to_string(),
+            offset_field: config_param(config, "offset_field").map(str::to_string),
+            length_field: length_field.to_string(),
+            capacity,
+            padding: padding_byte(config),
+        })
+    }
+
+    /// 每个父包数据区可容纳的字节数
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// 将SDU切分为连续的父包，空SDU生成一个仅含填充的未分段父包
+    pub fn fragment(&self, sdu: &[u8]) -> Result<Vec<Vec<u8>>, String> {
+        if sdu.is_empty() {
+            return Ok(vec![self.build_segment(sdu, 0, true)?]);
+        }
+        sdu.chunks(self.capacity)
+            .enumerate()
+            .map(|(index, chunk)| {
+                let offset = index * self.capacity;
+                self.build_segment(chunk, offset, offset + chunk.len() == sdu.len())
+            })
+            .collect()
+    }
+
+    /// 组装一段父包：`offset`为本段在SDU中的偏移，`last`表示本段为SDU的最后一段
+    pub fn build_segment(
+        &self,
+        chunk: &[u8],
+        offset: usize,
+        last: bool,
+    ) -> Result<Vec<u8>, String> {
+        if chunk.len() > self.capacity {
+            return Err(format!(
+                "Segment of {} bytes exceeds stream capacity {}",
+                chunk.len(),
+                self.capacity
+            ));
+        }
+
+        let mut parent = self.parent_template.clone();
+        let flag = SegmentFlag::for_segment(offset == 0, last);
+        Self::set_numeric_field(&mut parent, &self.flag_field, flag.value())?;
+        if let Some(offset_field) = &self.offset_field {
+            Self::set_numeric_field(&mut parent, offset_field, offset as u64)?;
+        }
+        Self::set_numeric_field(&mut parent, &self.length_field, chunk.len() as u64)?;
+
+        let mut data = chunk.to_vec();
+        data.resize(self.capacity, self.padding);
+        parent
+            .set_field_value(&self.data_field, &data)
+            .map_err(|e| format!("Failed to set data field '{}': {e}", self.data_field))?;
+        parent
+            .assemble_frame()
+            .map_err(|e| format!("Failed to assemble stream segment: {e}"))
+    }
+
+    /// 按字段宽度写入大端数值
+    fn set_numeric_field(
+        parent: &mut FrameAssembler,
+        field_name: &str,
+        value: u64,
+    ) -> Result<(), String> {
+        let size = parent
+            .get_field_size_by_name(field_name)
+            .map_err(|e| format!("Failed to get field size for '{field_name}': {e}"))?;
+        let bytes = encode_value(field_name, value, size)?;
+        parent
+            .set_field_value(field_name, &bytes)
+            .map_err(|e| format!("Failed to set field '{field_name}': {e}"))
+    }
+}
+
+/// 流式重组器
+///
+/// 按顺序接收父包并拼接分段；分段标志或段偏移不连续时丢弃不完整的SDU并等待下一个首段
+pub struct StreamReassembler {
+    data: FieldSlot,
+    flag: FieldSlot,
+    offset: Option<FieldSlot>,
+    length: FieldSlot,
+    pending: Option<Vec<u8>>,
+    discarded_bytes: usize,
+}
+
+impl StreamReassembler {
+    /// 根据父包模板和流式放置配置创建重组器，与分段器使用相同的配置
+    pub fn from_template(
+        parent_template: &FrameAssembler,
+        config: &DataPlacementConfig,
+    ) -> Result<Self, String> {
+        let flag_field =
+            config_param(config, "flag_field").ok_or("Stream config is missing 'flag_field'")?;
+        let length_field = config_param(config, "length_field")
+            .ok_or("Stream config is missing 'length_field'")?;
+        let optional_slot = |key: &str| {
+            config_param(config, key)
+                .map(|field_name| FieldSlot::locate(parent_template, field_name))
+                .transpose()
+        };
+
+        Ok(Self {
+            data: FieldSlot::locate(parent_template, &config.target_field)?,
+            flag: FieldSlot::locate(parent_template, flag_field)?,
+            offset: optional_slot("offset_field")?,
+            length: FieldSlot::locate(parent_template, length_field)?,
+            pending: None,
+            discarded_bytes: 0,
+        })
+    }
+
+    /// 获取因分段不连续而丢弃的字节数
+    pub fn discarded_bytes(&self) -> usize {
+        self.discarded_bytes
+    }
+
+    /// 获取尚未完整的SDU字节数
+    pub fn pending_len(&self) -> usize {
+        self.pending.as_ref().map_or(0, Vec::len)
+    }
+
+    /// 接收一个父包，SDU完成重组时返回该SDU
+    pub fn push_frame(&mut self, frame: &[u8]) -> Result<Option<Vec<u8>>, String> {
+        let slots = [
+            Some(self.data),
+            Some(self.flag),
+            self.offset,
+            Some(self.length),
+        ];
+        let required = slots
+            .iter()
+            .flatten()
+            .map(FieldSlot::end)
+            .max()
+            .unwrap_or(0);
+        if frame.len() < required {
+            return Err(format!(
+                "Stream frame too short: need {required} bytes, got {}",
+                frame.len()
+            ));
+        }
+
+        let flag = SegmentFlag::from_value(self.flag.read_value(frame));
+        let data = self.data.read(frame);
+        let valid = self.length.read_value(frame) as usize;
+        if valid > data.len() {
+            return Err(format!(
+                "Segment length {valid} exceeds stream data length {}",
+                data.len()
+            ));
+        }
+        let segment = &data[..valid];
+
+        if matches!(flag, SegmentFlag::First | SegmentFlag::Unsegmented) {
+            // 新的SDU开始，之前未完成的SDU不再完整
+            if let Some(incomplete) = self.pending.take() {
+                self.discarded_bytes += incomplete.len();
+            }
+            self.pending = Some(Vec::new());
+        }
+
+        let Some(pending) = self.pending.as_mut() else {
+            // 未同步到首段，无法使用续传分段
+            self.discarded_bytes += segment.len();
+            return Ok(None);
+        };
+
+        if let Some(offset) = self.offset {
+            let expected = pending.len() as u64;
+            if offset.read_value(frame) != expected {
+                self.discarded_bytes += pending.len() + segment.len();
+                self.pending = None;
+                return Ok(None);
+            }
+        }
+
+        pending.extend_from_slice(segment);
+        if matches!(flag, SegmentFlag::Last | SegmentFlag::Unsegmented) {
+            return Ok(self.pending.take());
+        }
+        Ok(None)
+    }
+}
@@ -250,6 +250,8 @@ mod tests {
             length: 2,
             position: 0,
             constraints: vec![Constraint::Range(0, 100)],
+            unit_label: None,
+            long_description: None,
         };
 
         let field_unit = FieldUnit::new(field_def);
@@ -265,6 +267,8 @@ mod tests {
             length: 2,
             position: 0,
             constraints: vec![Constraint::Range(0, 1000)],
+            unit_label: None,
+            long_description: None,
         };
 
         let mut field_unit = FieldUnit::new(field_def);
@@ -290,6 +294,8 @@ mod tests {
             length: 1,
             position: 0,
             constraints: vec![Constraint::Range(10, 20)],
+            unit_label: None,
+            long_description: None,
         };
 
         let mut field_unit = FieldUnit::new(field_def);
@@ -0,0 +1,217 @@
+//! 算法选择规则处理器
+//!
+//! 根据选择字段（如模式字段）的取值，在组装和解析时选择校验算法
+
+use apdl_core::utils::decode_uint;
+use apdl_core::{AlgorithmAst, ChecksumAlgorithm, ProtocolError, SemanticRule, UnitType};
+use apdl_lsk::frame_disassembler::extract_bit_field_with_numbering;
+
+use crate::standard_units::frame_assembler::core::FrameAssembler;
+
+impl FrameAssembler {
+    /// 获取算法选择规则的选择字段和候选算法
+    fn algorithm_select_rule(&self) -> Option<(&str, &[(u64, AlgorithmAst)])> {
+        self.semantic_rules.iter().find_map(|rule| match rule {
+            SemanticRule::AlgorithmSelect {
+                selector_field,
+                cases,
+                ..
+            } => Some((selector_field.as_str(), cases.as_slice())),
+            _ => None,
+        })
+    }
+
+    /// 根据选择字段取值确定校验算法
+    ///
+    /// # 返回
+    /// - `Ok(None)`: 未定义算法选择规则
+    /// - `Ok(Some(algorithm))`: 选中的校验算法
+    /// - `Err(ProtocolError)`: 取值没有对应的算法，或选中的算法不是校验算法
+    pub fn select_checksum_algorithm(
+        &self,
+        selector_value: u64,
+    ) -> Result<Option<ChecksumAlgorithm>, ProtocolError> {
+        let Some((selector_field, cases)) = self.algorithm_select_rule() else {
+            return Ok(None);
+        };
+
+        let Some((_, alg_ast)) = cases.iter().find(|(value, _)| *value == selector_value) else {
+            return Err(ProtocolError::ValidationError(format!(
+                "No algorithm selected for {selector_field} = {selector_value}"
+            )));
+        };
+
+        alg_ast.checksum_algorithm().map(Some).ok_or_else(|| {
+            ProtocolError::ValidationError(format!(
+                "Selected algorithm {alg_ast:?} is not a checksum algorithm"
+            ))
+        })
+    }
+
+    /// 获取选择字段的索引
+    fn selector_index(&self, selector_field: &str) -> Result<usize, ProtocolError> {
+        self.field_index
+            .get(selector_field)
+            .copied()
+            .ok_or_else(|| ProtocolError::FieldNotFound(selector_field.to_string()))
+    }
+
+    /// 按字段字节序将选择字段的字节值解码为数值
+    fn decode_selector(&self, selector_field: &str, bytes: &[u8]) -> Result<u64, ProtocolError> {
+        decode_uint(bytes, self.get_field_byte_order(selector_field)).ok_or_else(|| {
+            ProtocolError::ValidationError(format!(
+                "Selector field {selector_field} is wider than 64 bits"
+            ))
+        })
+    }
+
+    /// 组装时根据已设置的选择字段值确定校验算法
+    pub fn selected_checksum_algorithm(&self) -> Result<Option<ChecksumAlgorithm>, ProtocolError> {
+        let Some((selector_field, _)) = self.algorithm_select_rule() else {
+            return Ok(None);
+        };
+
+        let index = self.selector_index(selector_field)?;
+        let selector_value = if let UnitType::Bit(_) = self.fields[index].unit_type {
+            self.get_bit_field_value(selector_field)?
+        } else {
+            self.decode_selector(selector_field, &self.get_field_value(selector_field)?)?
+        };
+
+        self.select_checksum_algorithm(selector_value)
+    }
+
+    /// 解析时从帧数据中读取选择字段并确定校验算法
+    pub fn selected_checksum_algorithm_in_frame(
+        &self,
+        frame_data: &[u8],
+    ) -> Result<Option<ChecksumAlgorithm>, ProtocolError> {
+        let Some((selector_field, _)) = self.algorithm_select_rule() else {
+            return Ok(None);
+        };
+
+        // 与多路复用字段的读取方式一致：位字段按位偏移提取，字节字段按字段字节序解码
+        let index = self.selector_index(selector_field)?;
+        let field = &self.fields[index];
+        let bit_offset = self.calculate_field_bit_offset(index)?;
+        let selector_value = if let UnitType::Bit(bits) = field.unit_type {
+            extract_bit_field_with_numbering(
+                frame_data,
+                bit_offset,
+                bits as usize,
+                self.bit_numbering,
+            )?
+        } else {
+            let start = bit_offset / 8;
+            let end = start + self.get_field_size(field)?;
+            if end > frame_data.len() {
+                return Err(ProtocolError::InvalidFrameFormat(format!(
+                    "Selector field {selector_field} exceeds frame size"
+                )));
+            }
+            self.decode_selector(selector_field, &frame_data[start..end])?
+        };
+
+        self.select_checksum_algorithm(selector_value)
+    }
+
+    /// 获取算法选择规则对应的校验字段索引
+    ///
+    /// 优先使用规则指定的目标字段，否则按定义顺序查找首个声明了候选算法之一的字段
+    fn algorithm_select_target(&self) -> Result<Option<usize>, ProtocolError> {
+        let Some(SemanticRule::AlgorithmSelect {
+            target_field,
+            cases,
+            ..
+        }) = self
+            .semantic_rules
+            .iter()
+            .find(|rule| matches!(rule, SemanticRule::AlgorithmSelect { .. }))
+        else {
+            return Ok(None);
+        };
+
+        if let Some(target_field) = target_field {
+            return self
+                .field_index
+                .get(target_field)
+                .copied()
+                .map(Some)
+                .ok_or_else(|| {
+                    ProtocolError::FieldNotFound(format!(
+                        "Algorithm select target field not found: {target_field}"
+                    ))
+                });
+        }
+        Ok(self.fields.iter().position(|field| {
+            field
+                .alg
+                .as_ref()
+                .is_some_and(|alg| cases.iter().any(|(_, case_alg)| case_alg == alg))
+        }))
+    }
+
+    /// 确定校验和范围规则实际使用的算法
+    ///
+    /// 规则的校验字段是算法选择规则的目标字段时使用选中的算法（无法确定目标字段时对所有规则生效），
+    /// 其余规则使用自身的算法，同一帧中的其他校验和不受算法选择影响
+    pub fn governed_checksum_algorithm(
+        &self,
+        algorithm: &ChecksumAlgorithm,
+        checksum_index: Option<usize>,
+        selected: Option<&ChecksumAlgorithm>,
+    ) -> Result<ChecksumAlgorithm, ProtocolError> {
+        let Some(selected) = selected else {
+            return Ok(algorithm.clone());
+        };
+        match self.algorithm_select_target()? {
+            Some(target) if checksum_index != Some(target) => Ok(algorithm.clone()),
+            _ => Ok(selected.clone()),
+        }
+    }
+
+    /// 验证帧数据中的校验和，存在算法选择规则时其目标字段使用帧中选择字段对应的算法
+    pub fn verify_checksums(&self, frame_data: &[u8]) -> Result<(), ProtocolError> {
+        let selected = self.selected_checksum_algorithm_in_frame(frame_data)?;
+
+        for rule in self.semantic_rules.iter() {
+            let SemanticRule::ChecksumRange {
+                algorithm,
+                start_field,
+                end_field,
+                covers_self,
+            } = rule
+            else {
+                continue;
+            };
+
+            let start_field = start_field.trim_start_matches("start: ").trim();
+            let end_field = end_field.trim_start_matches("end: ").trim();
+            // 每条规则独立确定校验字段，同一帧中可以有多个校验字段
+            let Some(target_index) = self.resolve_checksum_field(algorithm, end_field)? else {
+                continue;
+            };
+            let algorithm =
+                self.governed_checksum_algorithm(algorithm, Some(target_index), selected.as_ref())?;
+
+            let data = self.checksum_input(
+                frame_data,
+                start_field,
+                end_field,
+                Some(target_index),
+                *covers_self,
+            )?;
+            let expected = self.compute_field_checksum(&algorithm, Some(target_index), &data);
+            let actual = self.read_checksum_from_field(frame_data, target_index)?;
+            if expected != actual {
+                return Err(ProtocolError::ChecksumMismatch {
+                    field: self.fields[target_index].field_id.clone(),
+                    expected,
+                    actual,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
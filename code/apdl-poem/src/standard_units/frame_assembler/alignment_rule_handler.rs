@@ -0,0 +1,14 @@
+//! 对齐规则处理器
+//!
+//! 处理字段对齐规则，计算字段前需要插入的填充字节
+
+use apdl_core::SemanticRule;
+
+use crate::standard_units::frame_assembler::core::FrameAssembler;
+
+impl FrameAssembler {
+    /// 获取字段位于给定字节偏移时需要在其前插入的填充字节数
+    pub fn alignment_padding(&self, field_name: &str, offset: usize) -> usize {
+        SemanticRule::alignment_padding(&self.semantic_rules, field_name, offset)
+    }
+}
@@ -2,9 +2,15 @@
 //!
 //! 处理与校验和相关的语义规则，包括CRC、XOR等算法
 
-use apdl_core::{AlgorithmAst, ChecksumAlgorithm, ProtocolError};
+use apdl_core::utils::calculate_crc16_with;
+use apdl_core::{
+    AlgorithmAst, ByteOrder, ChecksumAlgorithm, CoverDesc, LengthUnit, ProtocolError, SemanticRule,
+    SyntaxUnit, UnitType,
+};
+use std::ops::Range;
 
 use crate::standard_units::frame_assembler::core::FrameAssembler;
+use crate::standard_units::frame_assembler::utils::{bytes_to_u64_be, bytes_to_u64_le};
 
 impl FrameAssembler {
     /// 应用校验和规则
@@ -15,111 +21,374 @@ impl FrameAssembler {
         start_field: &str,
         end_field: &str,
     ) -> Result<(), ProtocolError> {
-        // 根据字段名找到在帧数据中的位置
+        self.apply_checksum_rule_with(frame_data, algorithm, start_field, end_field, true, None)
+    }
+
+    /// 应用校验和规则
+    ///
+    /// `covers_self`为规则的同名选项；校验字段为算法选择规则的目标字段时使用`selected`算法
+    pub fn apply_checksum_rule_with(
+        &mut self,
+        frame_data: &mut [u8],
+        algorithm: &ChecksumAlgorithm,
+        start_field: &str,
+        end_field: &str,
+        covers_self: bool,
+        selected: Option<&ChecksumAlgorithm>,
+    ) -> Result<(), ProtocolError> {
+        let checksum_index = self.resolve_checksum_field(algorithm, end_field)?;
+        let algorithm = &self.governed_checksum_algorithm(algorithm, checksum_index, selected)?;
+        let data = self.checksum_input(
+            frame_data,
+            start_field,
+            end_field,
+            checksum_index,
+            covers_self,
+        )?;
+        let checksum = self.compute_field_checksum(algorithm, checksum_index, &data);
+
+        if let Some(field_index) = checksum_index {
+            self.check_checksum_scope(field_index, start_field, end_field)?;
+            self.write_checksum_to_field(frame_data, field_index, checksum)?;
+        }
+
+        println!(
+            "Calculated checksum {algorithm:?} for range {start_field} to {end_field}: {checksum:?}"
+        );
+        Ok(())
+    }
+
+    /// 为结束于`end_field`的校验和范围规则显式指定校验字段，优先于自动发现
+    pub fn set_checksum_field(&mut self, end_field: &str, checksum_field: &str) {
+        self.checksum_fields
+            .insert(end_field.to_string(), checksum_field.to_string());
+    }
+
+    /// 获取校验和范围规则参与计算的字节，组装和验证时使用同一范围
+    ///
+    /// 计算`start_field`到`end_field`的字节，其中的校验字段在`covers_self`时按全零计入，
+    /// 否则排除。校验字段的`cover`为`target[a..b]`区间时，改为计算该区间内的字节
+    pub fn checksum_input(
+        &self,
+        frame_data: &[u8],
+        start_field: &str,
+        end_field: &str,
+        checksum_index: Option<usize>,
+        covers_self: bool,
+    ) -> Result<Vec<u8>, ProtocolError> {
         let start_pos = self.get_field_position(start_field)?;
         let end_pos =
             self.get_field_position(end_field)? + self.get_field_size_by_name(end_field)?;
 
-        if end_pos > frame_data.len() {
+        let checksum_range = match checksum_index {
+            Some(index) => {
+                let offset = self.calculate_field_offset(index)?;
+                Some(offset..offset + self.get_field_size(&self.fields[index])?)
+            }
+            None => None,
+        };
+        // 校验字段声明了区间覆盖时只计算覆盖区间内的字节
+        let span = match checksum_index.map(|index| &self.fields[index]) {
+            Some(field) if matches!(field.cover, CoverDesc::Range(..)) => self
+                .cover_byte_range(&field.field_id)?
+                .unwrap_or(start_pos..end_pos),
+            _ => start_pos..end_pos,
+        };
+        if span.end > frame_data.len() {
             return Err(ProtocolError::InvalidFrameFormat(
                 "Field range exceeds frame size".to_string(),
             ));
         }
 
-        let data_to_checksum = &frame_data[start_pos..end_pos];
-        let checksum: u64 = match algorithm {
-            ChecksumAlgorithm::CRC16 => self.calculate_crc16(data_to_checksum) as u64,
-            ChecksumAlgorithm::CRC32 => self.calculate_crc32(data_to_checksum) as u64,
-            ChecksumAlgorithm::CRC15 => self.calculate_crc15(data_to_checksum) as u64, // CAN协议专用
-            ChecksumAlgorithm::XOR => {
-                crate::standard_units::frame_assembler::utils::calculate_xor(data_to_checksum)
-                    as u64
-            }
-        };
+        Ok(span
+            .filter_map(|pos| match &checksum_range {
+                Some(range) if range.contains(&pos) => covers_self.then_some(0),
+                _ => Some(frame_data[pos]),
+            })
+            .collect())
+    }
 
-        // 确定校验和应该写入哪个字段
-        // 策略：检查所有字段，找到应用了匹配算法的字段
-        let mut found_matching_field = false;
-        for (field_name, &field_index) in &self.field_index {
-            let field = &self.fields[field_index];
-
-            // 检查字段是否应用了校验算法
-            if let Some(ref alg_ast) = field.alg {
-                // 如果算法匹配，则将校验和写入该字段
-                if self.checksum_algorithm_matches(alg_ast, algorithm) {
-                    let field_size = self.get_field_size(field)?;
-                    let field_offset = self.calculate_field_offset(field_index)?;
-
-                    // 调试信息
-                    let checksum_bytes = self.u64_to_bytes(checksum, field_size);
-                    println!(
-                        "DEBUG: Writing checksum {:?} to field {} at offset {}, field_size: {}, frame_data length: {}",
-                        checksum_bytes, field_name, field_offset, field_size, frame_data.len()
-                    );
-
-                    // 将校验和写入帧数据
-                    for (i, &byte) in checksum_bytes.iter().enumerate() {
-                        let write_pos = field_offset + i;
-                        if write_pos < frame_data.len() {
-                            frame_data[write_pos] = byte;
-                            println!("DEBUG: Wrote byte {byte:02X} to position {write_pos}");
-                        } else {
-                            println!("DEBUG: Cannot write byte {:02X} to position {}, exceeds frame length {}", byte, write_pos, frame_data.len());
-                        }
-                    }
+    /// 确定校验和范围规则的校验字段
+    ///
+    /// 依次使用：显式指定的字段；紧随`end_field`之后且`alg`与规则算法匹配的字段；
+    /// `alg`与规则算法匹配的唯一字段；紧随`end_field`之后的字段；常见的校验字段名称。
+    /// 存在多个算法匹配的字段且都不紧随范围之后，或紧随其后的字段无法容纳校验值时报错
+    pub fn resolve_checksum_field(
+        &self,
+        algorithm: &ChecksumAlgorithm,
+        end_field: &str,
+    ) -> Result<Option<usize>, ProtocolError> {
+        if let Some(checksum_field) = self.checksum_fields.get(end_field) {
+            return self
+                .field_index
+                .get(checksum_field)
+                .copied()
+                .map(Some)
+                .ok_or_else(|| {
+                    ProtocolError::FieldNotFound(format!(
+                        "Checksum field not found: {checksum_field}"
+                    ))
+                });
+        }
 
-                    // 同时更新字段值存储
-                    self.field_values.insert(field_name.clone(), checksum_bytes);
+        let alg_matches = |index: usize| {
+            self.fields[index]
+                .alg
+                .as_ref()
+                .is_some_and(|alg_ast| self.checksum_algorithm_matches(alg_ast, algorithm))
+        };
+        // 同一算法可用于多个校验字段（如头部CRC和数据CRC），紧随范围之后的匹配字段优先
+        if let Some(&end_index) = self.field_index.get(end_field) {
+            let adjacent = end_index + 1;
+            if adjacent < self.fields.len() && alg_matches(adjacent) {
+                return Ok(Some(adjacent));
+            }
+        }
 
-                    found_matching_field = true;
-                    break; // 找到并处理了一个校验字段后退出
-                }
+        let candidates: Vec<usize> = (0..self.fields.len())
+            .filter(|&index| alg_matches(index))
+            .collect();
+        match candidates.as_slice() {
+            [index] => return Ok(Some(*index)),
+            [] => {}
+            _ => {
+                let names: Vec<&str> = candidates
+                    .iter()
+                    .map(|&index| self.fields[index].field_id.as_str())
+                    .collect();
+                return Err(ProtocolError::InvalidFieldDefinition(format!(
+                    "Ambiguous checksum field for {algorithm:?} range ending at {end_field}: {}",
+                    names.join(", ")
+                )));
             }
         }
 
-        // 如果没有找到匹配算法的字段，尝试根据常见的校验字段名称来查找
-        if !found_matching_field {
-            // 尝试其他常见的校验字段名称
-            for field_name in ["fecf", "crc", "checksum", "crc_field", "check_field"] {
-                if let Some(&field_index) = self.field_index.get(field_name) {
-                    let field = &self.fields[field_index];
-                    let field_size = self.get_field_size(field)?;
-                    let field_offset = self.calculate_field_offset(field_index)?;
-
-                    // 调试信息
-                    let checksum_bytes = self.u64_to_bytes(checksum, field_size);
-                    println!(
-                        "DEBUG: Writing checksum {:?} to field {} at offset {}, field_size: {}, frame_data length: {}",
-                        checksum_bytes, field_name, field_offset, field_size, frame_data.len()
-                    );
-
-                    // 将校验和写入帧数据
-                    for (i, &byte) in checksum_bytes.iter().enumerate() {
-                        let write_pos = field_offset + i;
-                        if write_pos < frame_data.len() {
-                            frame_data[write_pos] = byte;
-                            println!("DEBUG: Wrote byte {byte:02X} to position {write_pos}");
-                        } else {
-                            println!("DEBUG: Cannot write byte {:02X} to position {}, exceeds frame length {}", byte, write_pos, frame_data.len());
-                        }
-                    }
+        // 约定：紧随校验范围之后的字段为校验字段，该字段须为校验类型或宽度可容纳校验值
+        if let Some(&end_index) = self.field_index.get(end_field) {
+            if let Some(neighbour) = self.fields.get(end_index + 1) {
+                if !self.checksum_width_compatible(neighbour, algorithm) {
+                    return Err(ProtocolError::InvalidFieldDefinition(format!(
+                        "Field {} after {end_field} cannot hold a {algorithm:?} checksum; \
+                         declare its alg or specify the checksum field",
+                        neighbour.field_id
+                    )));
+                }
+                return Ok(Some(end_index + 1));
+            }
+        }
 
-                    // 同时更新字段值存储
-                    self.field_values
-                        .insert(field_name.to_string(), checksum_bytes);
+        Ok(["fecf", "crc", "checksum", "crc_field", "check_field"]
+            .iter()
+            .find_map(|field_name| self.field_index.get(*field_name).copied()))
+    }
 
-                    break; // 找到并处理了一个校验字段后退出
+    /// 按校验字段的覆盖关系对校验和范围规则排序
+    ///
+    /// 范围覆盖了其他规则校验字段的规则（如覆盖头部CRC的整帧CRC）排在其后计算，
+    /// 其余规则保持定义顺序；存在循环覆盖时报错
+    pub fn order_checksum_rules<'a>(
+        &self,
+        rules: &[&'a SemanticRule],
+    ) -> Result<Vec<&'a SemanticRule>, ProtocolError> {
+        // 每条规则的（范围起始索引，范围结束索引，校验字段索引）
+        let mut spans = Vec::with_capacity(rules.len());
+        for rule in rules {
+            let span = match rule {
+                SemanticRule::ChecksumRange {
+                    algorithm,
+                    start_field,
+                    end_field,
+                    ..
+                } => {
+                    let start_field = start_field.trim_start_matches("start: ").trim();
+                    let end_field = end_field.trim_start_matches("end: ").trim();
+                    match (
+                        self.field_index.get(start_field),
+                        self.field_index.get(end_field),
+                    ) {
+                        (Some(&start), Some(&end)) => self
+                            .resolve_checksum_field(algorithm, end_field)?
+                            .map(|target| (start, end, target)),
+                        _ => None,
+                    }
                 }
+                _ => None,
+            };
+            spans.push(span);
+        }
+
+        // 规则a的范围覆盖规则b的校验字段时，a依赖b
+        let depends_on = |a: usize, b: usize| match (spans[a], spans[b]) {
+            (Some((start, end, target)), Some((_, _, other))) => {
+                target != other && (start..=end).contains(&other)
             }
+            _ => false,
+        };
+
+        let mut done = vec![false; rules.len()];
+        let mut order = Vec::with_capacity(rules.len());
+        while order.len() < rules.len() {
+            let ready = (0..rules.len())
+                .find(|&a| !done[a] && (0..rules.len()).all(|b| done[b] || !depends_on(a, b)));
+            let Some(index) = ready else {
+                let fields: Vec<&str> = (0..rules.len())
+                    .filter(|&index| !done[index])
+                    .filter_map(|index| spans[index])
+                    .map(|(_, _, target)| self.fields[target].field_id.as_str())
+                    .collect();
+                return Err(ProtocolError::DependencyError(format!(
+                    "Circular checksum dependency between fields: {}",
+                    fields.join(", ")
+                )));
+            };
+            done[index] = true;
+            order.push(rules[index]);
         }
+        Ok(order)
+    }
 
-        println!(
-            "Calculated checksum {algorithm:?} for range {start_field} to {end_field}: {checksum:?}"
+    /// 获取字段`cover`声明覆盖的帧内字节区间
+    ///
+    /// `entire_field`覆盖字段自身；`target[start..end]`覆盖目标字段内的半开区间，
+    /// `target[start..=end]`包含end；表达式覆盖返回None
+    pub fn cover_byte_range(
+        &self,
+        field_name: &str,
+    ) -> Result<Option<Range<usize>>, ProtocolError> {
+        let field = self
+            .field_index
+            .get(field_name)
+            .map(|&index| &self.fields[index])
+            .ok_or_else(|| ProtocolError::FieldNotFound(field_name.to_string()))?;
+        let (target, cover) = match &field.cover {
+            CoverDesc::EntireField => (field_name, &field.cover),
+            CoverDesc::Range(target, ..) => (target.as_str(), &field.cover),
+            CoverDesc::Expression(_) => return Ok(None),
+        };
+
+        let target_pos = self.get_field_position(target)?;
+        let target_size = self.get_field_size_by_name(target)?;
+        let invalid_cover = || {
+            ProtocolError::InvalidFieldDefinition(format!(
+                "Cover {:?} of field '{field_name}' is empty or exceeds {target_size}-byte field '{target}'",
+                field.cover
+            ))
+        };
+        let range = cover.byte_range(target_size).ok_or_else(invalid_cover)?;
+        let start = target_pos
+            .checked_add(range.start)
+            .ok_or_else(invalid_cover)?;
+        let end = target_pos
+            .checked_add(range.end)
+            .ok_or_else(invalid_cover)?;
+        Ok(Some(start..end))
+    }
+
+    /// 检查校验范围内的字段是否都在校验字段的作用范围内
+    ///
+    /// 层范围的校验字段覆盖其他层的字段时，启用严格作用域检查则报错，否则仅记录警告；
+    /// 端到端校验应将校验字段声明为`global`或`cross_layer`范围
+    fn check_checksum_scope(
+        &self,
+        checksum_index: usize,
+        start_field: &str,
+        end_field: &str,
+    ) -> Result<(), ProtocolError> {
+        let (Some(&start), Some(&end)) = (
+            self.field_index.get(start_field),
+            self.field_index.get(end_field),
+        ) else {
+            return Ok(());
+        };
+
+        let checksum_field = &self.fields[checksum_index];
+        let uncovered: Vec<&str> = self.fields[start..=end]
+            .iter()
+            .filter(|field| !checksum_field.scope.covers_field(field))
+            .map(|field| field.field_id.as_str())
+            .collect();
+        if uncovered.is_empty() {
+            return Ok(());
+        }
+
+        let message = format!(
+            "Checksum field '{}' with scope {:?} covers fields outside its scope: {}",
+            checksum_field.field_id,
+            checksum_field.scope,
+            uncovered.join(", ")
         );
+        if self.strict_checksum_scope {
+            return Err(ProtocolError::ValidationError(message));
+        }
+        log::warn!("{message}");
+        Ok(())
+    }
+
+    /// 将校验和按校验字段声明的宽度和字节序写入帧数据
+    ///
+    /// 字段宽于校验和时高位补零，校验和超出字段宽度时报错
+    pub fn write_checksum_to_field(
+        &mut self,
+        frame_data: &mut [u8],
+        field_index: usize,
+        checksum: u64,
+    ) -> Result<(), ProtocolError> {
+        let field = &self.fields[field_index];
+        let field_name = field.field_id.clone();
+        let field_size = self.get_field_size(field)?;
+        let field_offset = self.calculate_field_offset(field_index)?;
+
+        if field_size < 8 && checksum >> (field_size * 8) != 0 {
+            return Err(ProtocolError::ValueOutOfRange(format!(
+                "Checksum {checksum:#X} does not fit in {field_size}-byte field {field_name}"
+            )));
+        }
+        if field_offset + field_size > frame_data.len() {
+            return Err(ProtocolError::InvalidFrameFormat(format!(
+                "Checksum field {field_name} exceeds frame size"
+            )));
+        }
+
+        // 内部存储统一使用大端，写入帧时按字段字节序转换
+        let checksum_bytes = self.u64_to_bytes(checksum, field_size);
+        let frame_bytes = match self.get_field_byte_order(&field_name) {
+            ByteOrder::BigEndian => checksum_bytes.clone(),
+            ByteOrder::LittleEndian => checksum_bytes.iter().rev().copied().collect(),
+        };
+        frame_data[field_offset..field_offset + field_size].copy_from_slice(&frame_bytes);
+
+        // 同时更新字段值存储
+        self.field_values.insert(field_name, checksum_bytes);
         Ok(())
     }
 
+    /// 读取校验字段中的校验和（按字段字节序）
+    pub fn read_checksum_from_field(
+        &self,
+        frame_data: &[u8],
+        field_index: usize,
+    ) -> Result<u64, ProtocolError> {
+        let field = &self.fields[field_index];
+        let field_size = self.get_field_size(field)?;
+        let field_offset = self.calculate_field_offset(field_index)?;
+        if field_offset + field_size > frame_data.len() {
+            return Err(ProtocolError::InvalidFrameFormat(format!(
+                "Checksum field {} exceeds frame size",
+                field.field_id
+            )));
+        }
+
+        let bytes = &frame_data[field_offset..field_offset + field_size];
+        Ok(match self.get_field_byte_order(&field.field_id) {
+            ByteOrder::BigEndian => bytes_to_u64_be(bytes),
+            ByteOrder::LittleEndian => bytes_to_u64_le(bytes),
+        })
+    }
+
     /// 验证校验和规则
+    ///
+    /// 重新计算范围内的校验和并与校验字段中的值比较，不一致时返回`ChecksumMismatch`；
+    /// 无法确定校验字段时不做比较
     pub fn validate_checksum_rule(
         &self,
         frame_data: &[u8],
@@ -127,32 +396,117 @@ impl FrameAssembler {
         start_field: &str,
         end_field: &str,
     ) -> Result<(), ProtocolError> {
-        // 验证帧数据中的校验和是否正确
-        let start_pos = self.get_field_position(start_field)?;
-        let end_pos =
-            self.get_field_position(end_field)? + self.get_field_size_by_name(end_field)?;
+        self.validate_checksum_rule_with(frame_data, algorithm, start_field, end_field, true)
+    }
 
-        if end_pos > frame_data.len() {
-            return Err(ProtocolError::InvalidFrameFormat(
-                "Field range exceeds frame size".to_string(),
-            ));
+    /// 验证校验和规则，`covers_self`为规则的同名选项
+    pub fn validate_checksum_rule_with(
+        &self,
+        frame_data: &[u8],
+        algorithm: &ChecksumAlgorithm,
+        start_field: &str,
+        end_field: &str,
+        covers_self: bool,
+    ) -> Result<(), ProtocolError> {
+        let checksum_index = self.resolve_checksum_field(algorithm, end_field)?;
+        let data = self.checksum_input(
+            frame_data,
+            start_field,
+            end_field,
+            checksum_index,
+            covers_self,
+        )?;
+        let calculated_checksum = self.compute_field_checksum(algorithm, checksum_index, &data);
+
+        let Some(field_index) = checksum_index else {
+            return Ok(());
+        };
+        let stored_checksum = self.read_checksum_from_field(frame_data, field_index)?;
+        if stored_checksum != calculated_checksum {
+            return Err(ProtocolError::ChecksumMismatch {
+                field: self.fields[field_index].field_id.clone(),
+                expected: calculated_checksum,
+                actual: stored_checksum,
+            });
+        }
+        Ok(())
+    }
+
+    /// 验证帧中的全部校验和规则
+    ///
+    /// 存在算法选择规则时按帧中选择字段对应的算法验证（见`verify_checksums`）
+    pub fn validate_checksum_rules(&self, frame_data: &[u8]) -> Result<(), ProtocolError> {
+        if self
+            .selected_checksum_algorithm_in_frame(frame_data)?
+            .is_some()
+        {
+            return self.verify_checksums(frame_data);
+        }
+
+        for rule in self.semantic_rules.iter() {
+            if let SemanticRule::ChecksumRange {
+                algorithm,
+                start_field,
+                end_field,
+                covers_self,
+            } = rule
+            {
+                self.validate_checksum_rule_with(
+                    frame_data,
+                    algorithm,
+                    start_field.trim_start_matches("start: ").trim(),
+                    end_field.trim_start_matches("end: ").trim(),
+                    *covers_self,
+                )?;
+            }
         }
+        Ok(())
+    }
 
-        let data_to_checksum = &frame_data[start_pos..end_pos];
-        let calculated_checksum: u64 = match algorithm {
-            ChecksumAlgorithm::CRC16 => self.calculate_crc16(data_to_checksum) as u64,
-            ChecksumAlgorithm::CRC32 => self.calculate_crc32(data_to_checksum) as u64,
-            ChecksumAlgorithm::CRC15 => self.calculate_crc15(data_to_checksum) as u64, // CAN协议专用
+    /// 按指定算法计算校验和
+    pub fn compute_checksum(&self, algorithm: &ChecksumAlgorithm, data: &[u8]) -> u64 {
+        match algorithm {
+            ChecksumAlgorithm::CRC16 => self.calculate_crc16(data) as u64,
+            ChecksumAlgorithm::CRC32 => self.calculate_crc32(data) as u64,
+            ChecksumAlgorithm::CRC15 => self.calculate_crc15(data) as u64, // CAN协议专用
             ChecksumAlgorithm::XOR => {
-                crate::standard_units::frame_assembler::utils::calculate_xor(data_to_checksum)
-                    as u64
+                crate::standard_units::frame_assembler::utils::calculate_xor(data) as u64
             }
-        };
+        }
+    }
 
-        println!(
-            "Validated checksum {algorithm:?} for range {start_field} to {end_field}: {calculated_checksum:?}"
-        );
-        Ok(())
+    /// 按校验字段的`alg`计算校验和，字段指定了CRC16参数时使用这些参数
+    pub fn compute_field_checksum(
+        &self,
+        algorithm: &ChecksumAlgorithm,
+        checksum_index: Option<usize>,
+        data: &[u8],
+    ) -> u64 {
+        let field_alg = checksum_index.and_then(|index| self.fields[index].alg.as_ref());
+        match (algorithm, field_alg) {
+            (ChecksumAlgorithm::CRC16, Some(AlgorithmAst::Crc16With(params))) => {
+                calculate_crc16_with(*params, data) as u64
+            }
+            _ => self.compute_checksum(algorithm, data),
+        }
+    }
+
+    /// 字段能否作为校验字段：声明了`alg`，或为不超过8字节的定长数值字段且宽度不小于校验值
+    fn checksum_width_compatible(&self, field: &SyntaxUnit, algorithm: &ChecksumAlgorithm) -> bool {
+        if field.alg.is_some() {
+            return true;
+        }
+        let checksum_bytes = match algorithm {
+            ChecksumAlgorithm::CRC16 | ChecksumAlgorithm::CRC15 => 2,
+            ChecksumAlgorithm::CRC32 => 4,
+            ChecksumAlgorithm::XOR => 1,
+        };
+        let field_bytes = match field.length.unit {
+            LengthUnit::Byte => field.length.size,
+            LengthUnit::Bit => field.length.size.div_ceil(8),
+            _ => return false,
+        };
+        field.unit_type != UnitType::RawData && (checksum_bytes..=8).contains(&field_bytes)
     }
 
     /// 检查算法AST是否与ChecksumAlgorithm匹配
@@ -163,8 +517,10 @@ impl FrameAssembler {
     ) -> bool {
         matches!(
             (alg_ast, algorithm),
-            (AlgorithmAst::Crc16, ChecksumAlgorithm::CRC16)
-                | (AlgorithmAst::Crc32, ChecksumAlgorithm::CRC32)
+            (
+                AlgorithmAst::Crc16 | AlgorithmAst::Crc16With(_),
+                ChecksumAlgorithm::CRC16
+            ) | (AlgorithmAst::Crc32, ChecksumAlgorithm::CRC32)
                 | (AlgorithmAst::Crc15, ChecksumAlgorithm::CRC15)
                 | (AlgorithmAst::XorSum, ChecksumAlgorithm::XOR)
         )
@@ -1,76 +1,91 @@
 //! 条件规则处理器
 //!
-//! 处理条件相关的语义规则
+//! 处理条件相关的语义规则：`fieldC if fieldA.value == 0x01`形式的条件不成立时，
+//! 目标字段在本次组装中省略
 
-use apdl_core::ProtocolError;
+use apdl_core::utils::condition::FieldComparison;
+use apdl_core::{ProtocolError, SemanticRule, UnitType};
+use std::sync::Arc;
 
 use crate::standard_units::frame_assembler::core::FrameAssembler;
+use crate::standard_units::frame_assembler::utils::bytes_to_u64_be;
 
 impl FrameAssembler {
-    /// 应用条件规则
-    pub fn apply_conditional_rule(
-        &mut self,
-        condition: &str,
-        _frame_data: &mut [u8],
-    ) -> Result<(), ProtocolError> {
-        // 解析条件表达式，例如 "fieldC if fieldA.value == 0x01"
-        // 这里我们实现一个简单的条件处理逻辑
-
-        println!("Applying conditional rule: {condition}");
-
-        // 检查条件是否包含 "if" 关键字
-        if condition.contains("if") {
-            let parts: Vec<&str> = condition.split(" if ").collect();
-            if parts.len() == 2 {
-                let target_field = parts[0].trim();
-                let condition_part = parts[1].trim();
-
-                // 解析条件，例如 "fieldA.value == 0x01"
-                if let Some(op_pos) = condition_part.find("==") {
-                    let field_expr = condition_part[..op_pos].trim();
-                    let value_str = condition_part[op_pos + 2..].trim();
-
-                    // 解析字段表达式，例如 "fieldA.value"
-                    if field_expr.contains('.') {
-                        let field_parts: Vec<&str> = field_expr.split('.').collect();
-                        if field_parts.len() >= 2 {
-                            let field_name = field_parts[0];
-
-                            // 获取字段值
-                            if let Ok(field_value) = self.get_field_value(field_name) {
-                                // 解析期望值
-                                let expected_value =
-                                    if let Some(hex_str) = value_str.strip_prefix("0x") {
-                                        u64::from_str_radix(hex_str, 16).unwrap_or(0)
-                                    } else {
-                                        value_str.parse::<u64>().unwrap_or(0)
-                                    };
+    /// 按当前字段值重新计算所有条件规则，确定本次组装省略的字段
+    pub fn apply_conditional_rules(&mut self) -> Result<(), ProtocolError> {
+        self.skipped_fields.clear();
+        let rules = Arc::clone(&self.semantic_rules);
+        for rule in rules.iter() {
+            if let SemanticRule::Conditional { condition } = rule {
+                self.apply_conditional_rule(condition)?;
+            }
+        }
+        Ok(())
+    }
 
-                                // 比较值
-                                let actual_value = self.bytes_to_u64(&field_value);
-                                if actual_value == expected_value {
-                                    // 条件满足，可以对目标字段进行操作
-                                    println!(
-                                        "Condition satisfied: {field_name} == {expected_value}, processing {target_field}"
-                                    );
+    /// 应用条件规则
+    ///
+    /// 条件形如`fieldC if fieldA.value == 0x01`，不成立时省略fieldC；
+    /// 不含`if`的条件不控制字段，直接忽略
+    pub fn apply_conditional_rule(&mut self, condition: &str) -> Result<(), ProtocolError> {
+        let Some((target_field, expression)) = condition.split_once(" if ") else {
+            return Ok(());
+        };
+        let target_field = target_field.trim_start_matches("field: ").trim();
+        if !self.evaluate_condition(expression)? {
+            self.skipped_fields.insert(target_field.to_string());
+        }
+        Ok(())
+    }
 
-                                    // 这里可以根据条件执行特定操作
-                                    // 例如设置目标字段的值或执行其他处理
-                                } else {
-                                    println!(
-                                        "Condition not satisfied: {field_name} = {actual_value}, expected {expected_value}"
-                                    );
-                                }
-                            }
-                        }
-                    }
+    /// 计算条件表达式
+    ///
+    /// 支持`field.value OP literal`比较（`== != < <= > >=`，十六进制或十进制字面量），
+    /// 多个比较可用`&&`和`||`连接，`&&`优先
+    pub fn evaluate_condition(&self, expression: &str) -> Result<bool, ProtocolError> {
+        for alternative in expression.split("||") {
+            let mut all_hold = true;
+            for comparison in alternative.split("&&") {
+                if !self.evaluate_comparison(comparison)? {
+                    all_hold = false;
+                    break;
                 }
             }
-        } else {
-            // 如果没有 if 条件，可能是一个简单的条件表达式
-            println!("Processing simple condition: {condition}");
+            if all_hold {
+                return Ok(true);
+            }
         }
+        Ok(false)
+    }
 
-        Ok(())
+    /// 计算单个比较表达式
+    fn evaluate_comparison(&self, comparison: &str) -> Result<bool, ProtocolError> {
+        let comparison = FieldComparison::parse(comparison).ok_or_else(|| {
+            ProtocolError::InvalidExpression(format!("Invalid condition: {}", comparison.trim()))
+        })?;
+        let actual = self.condition_field_value(&comparison.field_name)?;
+        Ok(comparison.holds_for(actual))
+    }
+
+    /// 获取条件中引用字段的数值
+    fn condition_field_value(&self, field_name: &str) -> Result<u64, ProtocolError> {
+        let Some(&index) = self.field_index.get(field_name) else {
+            return Err(ProtocolError::FieldNotFound(format!(
+                "Condition field not found: {field_name}"
+            )));
+        };
+        if let UnitType::Bit(_) = self.fields[index].unit_type {
+            return self.get_bit_field_value(field_name);
+        }
+
+        let value = self.get_field_value(field_name)?;
+        if value.len() > 8 {
+            return Err(ProtocolError::InvalidExpression(format!(
+                "Condition field {field_name} is wider than 64 bits"
+            )));
+        }
+        Ok(bytes_to_u64_be(
+            &self.convert_field_value_for_storage(field_name, &value),
+        ))
     }
 }
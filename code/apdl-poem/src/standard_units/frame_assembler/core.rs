@@ -2,25 +2,54 @@
 //!
 //! 包含 FrameAssembler 结构体定义和基础功能方法
 
-use apdl_core::{BitOrder, ByteOrder, LengthUnit, PackUnpackSpec, ProtocolError, SemanticRule, SyntaxUnit, UnitType};
-use std::collections::HashMap;
+use apdl_core::{
+    BitNumbering, BitOrder, ByteOrder, ConstraintMode, LengthUnit, PackUnpackSpec, ProtocolError,
+    ScopeDesc, SemanticRule, SyntaxUnit, UnitType,
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use super::custom_algorithm_handler::CustomAlgorithmRegistry;
+use super::rule_handler::RuleHandler;
+use super::utils::bytes_to_u64_be;
 
 /// 协议帧组装器
 #[derive(Clone)]
 pub struct FrameAssembler {
-    pub fields: Vec<SyntaxUnit>,
-    pub semantic_rules: Vec<SemanticRule>,
-    pub field_index: HashMap<String, usize>,
+    // 字段定义和语义规则通过Arc共享，由同一模板创建的实例之间不重复拷贝
+    pub fields: Arc<Vec<SyntaxUnit>>,
+    pub semantic_rules: Arc<Vec<SemanticRule>>,
+    pub field_index: Arc<HashMap<String, usize>>,
     // 添加字段值存储
     pub field_values: HashMap<String, Vec<u8>>,
     // 添加bit字段值存储
     pub bit_field_values: HashMap<String, u8>,
     // 添加字段字节序映射
     pub field_byte_orders: HashMap<String, ByteOrder>,
+    // 层级默认字节序（层名 -> 字节序），字段未单独声明时使用
+    pub layer_byte_orders: HashMap<String, ByteOrder>,
     // 添加字段位序映射
     pub field_bit_orders: HashMap<String, BitOrder>,
     // 包级别的打包/拆包规范
     pub pack_unpack_spec: Option<PackUnpackSpec>,
+    // 字节内位编号方式（MSB-0 / LSB-0）
+    pub bit_numbering: BitNumbering,
+    // 约束检查模式（默认Lenient，不检查）
+    pub constraint_mode: ConstraintMode,
+    // 校验字段覆盖其作用范围外的字段时是否报错（默认仅警告）
+    pub strict_checksum_scope: bool,
+    // 用户注册的自定义规则处理器
+    pub rule_handlers: Vec<Arc<dyn RuleHandler>>,
+    // 实例级自定义算法注册表（未找到时查找全局注册表）
+    pub custom_algorithms: CustomAlgorithmRegistry,
+    // 显式指定的校验字段（校验范围结束字段 -> 校验字段）
+    pub checksum_fields: HashMap<String, String>,
+    // 指针规则的数据区起始字段（指针字段 -> 数据区起始字段），未指定时为指针字段的下一个字段
+    pub pointer_data_zones: HashMap<String, String>,
+    // 多路复用规则对最近一次组装或解析的帧的路由结果（路由目标 -> 帧列表）
+    pub routed_outputs: HashMap<String, Vec<Vec<u8>>>,
+    // 条件规则不成立而在本次组装中省略的字段
+    pub skipped_fields: HashSet<String>,
 }
 
 impl Default for FrameAssembler {
@@ -32,14 +61,24 @@ impl Default for FrameAssembler {
 impl FrameAssembler {
     pub fn new() -> Self {
         Self {
-            fields: Vec::new(),
-            semantic_rules: Vec::new(),
-            field_index: HashMap::new(),
+            fields: Arc::new(Vec::new()),
+            semantic_rules: Arc::new(Vec::new()),
+            field_index: Arc::new(HashMap::new()),
             field_values: HashMap::new(),
             bit_field_values: HashMap::new(),
             field_byte_orders: HashMap::new(),
+            layer_byte_orders: HashMap::new(),
             field_bit_orders: HashMap::new(),
             pack_unpack_spec: None,
+            bit_numbering: BitNumbering::Msb0,
+            constraint_mode: ConstraintMode::Lenient,
+            strict_checksum_scope: false,
+            rule_handlers: Vec::new(),
+            custom_algorithms: CustomAlgorithmRegistry::new(),
+            checksum_fields: HashMap::new(),
+            pointer_data_zones: HashMap::new(),
+            routed_outputs: HashMap::new(),
+            skipped_fields: HashSet::new(),
         }
     }
 
@@ -55,6 +94,60 @@ impl FrameAssembler {
         self.pack_unpack_spec = Some(spec);
     }
 
+    /// 设置字节内位编号方式
+    pub fn set_bit_numbering(&mut self, numbering: BitNumbering) {
+        self.bit_numbering = numbering;
+    }
+
+    /// 设置约束检查模式
+    ///
+    /// Strict模式下设置字段值和组装帧时拒绝违反字段约束的值
+    pub fn set_constraint_mode(&mut self, mode: ConstraintMode) {
+        self.constraint_mode = mode;
+    }
+
+    /// 设置校验范围作用域检查是否严格
+    ///
+    /// 严格时层范围的校验字段覆盖其他层的字段会导致组装失败，否则仅记录警告
+    pub fn set_strict_checksum_scope(&mut self, strict: bool) {
+        self.strict_checksum_scope = strict;
+    }
+
+    /// 在Strict模式下检查字段值是否满足字段约束
+    fn check_field_constraint(&self, field: &SyntaxUnit, value: u64) -> Result<(), ProtocolError> {
+        match (&field.constraint, self.constraint_mode) {
+            (Some(constraint), ConstraintMode::Strict) => {
+                constraint.check_value(&field.field_id, value)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 在Strict模式下检查所有已设置的字段值
+    fn check_field_constraints(&self) -> Result<(), ProtocolError> {
+        if self.constraint_mode == ConstraintMode::Lenient {
+            return Ok(());
+        }
+
+        for field in self.fields.iter() {
+            if self.skipped_fields.contains(&field.field_id) {
+                continue;
+            }
+            let value = if let Some(&bit_value) = self.bit_field_values.get(&field.field_id) {
+                bit_value as u64
+            } else if let Some(stored) = self.field_values.get(&field.field_id) {
+                if stored.is_empty() || stored.len() > 8 {
+                    continue;
+                }
+                bytes_to_u64_be(stored)
+            } else {
+                continue;
+            };
+            self.check_field_constraint(field, value)?;
+        }
+        Ok(())
+    }
+
     /// 获取默认的字节序（从包级别配置或默认大端）
     fn default_byte_order(&self) -> ByteOrder {
         self.pack_unpack_spec
@@ -71,7 +164,23 @@ impl FrameAssembler {
             .unwrap_or(BitOrder::MsbFirst)
     }
 
+    /// 添加字段定义并检查字段名是否重复
+    ///
+    /// 字段名已存在时返回错误，不修改已有定义
+    pub fn try_add_field(&mut self, field: SyntaxUnit) -> Result<(), ProtocolError> {
+        if self.field_index.contains_key(&field.field_id) {
+            return Err(ProtocolError::InvalidFieldDefinition(format!(
+                "Duplicate field id: {}",
+                field.field_id
+            )));
+        }
+        self.add_field(field);
+        Ok(())
+    }
+
     /// 添加字段定义
+    ///
+    /// 不检查重复字段名，重复时字段索引指向最后添加的字段；需要检测重复时使用`try_add_field`
     pub fn add_field(&mut self, field: SyntaxUnit) {
         let field_name = field.field_id.clone();
         let index = self.fields.len();
@@ -94,13 +203,13 @@ impl FrameAssembler {
             }
         }
 
-        self.fields.push(field);
-        self.field_index.insert(field_name, index);
+        Arc::make_mut(&mut self.fields).push(field);
+        Arc::make_mut(&mut self.field_index).insert(field_name, index);
     }
 
     /// 添加语义规则
     pub fn add_semantic_rule(&mut self, rule: SemanticRule) {
-        self.semantic_rules.push(rule);
+        Arc::make_mut(&mut self.semantic_rules).push(rule);
     }
 
     /// 设置字段字节序
@@ -110,12 +219,26 @@ impl FrameAssembler {
             .insert(clean_field_name.to_string(), byte_order);
     }
 
-    /// 获取字段字节序，如果没有设置则返回默认值（从包配置或大端）
+    /// 设置层内字段的默认字节序，字段级字节序优先
+    pub fn set_layer_byte_order(&mut self, layer: &str, byte_order: ByteOrder) {
+        self.layer_byte_orders.insert(layer.to_string(), byte_order);
+    }
+
+    /// 获取字段字节序
+    ///
+    /// 依次使用字段级字节序、字段所在层（`layer(name)`范围）的默认字节序、包配置，最后为大端
     pub fn get_field_byte_order(&self, field_name: &str) -> ByteOrder {
         let clean_field_name = field_name.trim_start_matches("field: ").trim();
-        self.field_byte_orders
+        if let Some(&byte_order) = self.field_byte_orders.get(clean_field_name) {
+            return byte_order;
+        }
+
+        self.field_index
             .get(clean_field_name)
-            .copied()
+            .and_then(|&index| match &self.fields[index].scope {
+                ScopeDesc::Layer(layer) => self.layer_byte_orders.get(layer).copied(),
+                _ => None,
+            })
             .unwrap_or_else(|| self.default_byte_order())
     }
 
@@ -136,7 +259,7 @@ impl FrameAssembler {
     }
 
     /// 转换字段值以供内部存储（统一使用大端字节序）
-    fn convert_field_value_for_storage(&self, field_name: &str, external_value: &[u8]) -> Vec<u8> {
+    pub(super) fn convert_field_value_for_storage(&self, field_name: &str, external_value: &[u8]) -> Vec<u8> {
         let byte_order = self.get_field_byte_order(field_name);
 
         match byte_order {
@@ -149,7 +272,7 @@ impl FrameAssembler {
     }
 
     /// 从内部存储转换字段值以供外部使用
-    fn convert_field_value_from_storage(&self, field_name: &str, internal_value: &[u8]) -> Vec<u8> {
+    pub(super) fn convert_field_value_from_storage(&self, field_name: &str, internal_value: &[u8]) -> Vec<u8> {
         let byte_order = self.get_field_byte_order(field_name);
 
         match byte_order {
@@ -163,24 +286,109 @@ impl FrameAssembler {
 
     /// 组装协议帧
     pub fn assemble_frame(&mut self) -> Result<Vec<u8>, ProtocolError> {
+        let mut frame_data = Vec::new();
+        self.assemble_frame_into(&mut frame_data)?;
+        Ok(frame_data)
+    }
+
+    /// 预测组装后的帧长度（字节），不实际组装帧
+    ///
+    /// 连续的bit字段按组装时的方式合并计算，动态长度字段使用当前字段值的长度
+    pub fn predicted_length(&self) -> Result<usize, ProtocolError> {
+        let mut total_bytes = 0usize;
+        let mut pending_bits = 0usize;
+
+        for field in self.fields.iter() {
+            if let UnitType::Bit(bits) = field.unit_type {
+                if pending_bits == 0 {
+                    total_bytes += self.alignment_padding(&field.field_id, total_bytes);
+                }
+                pending_bits += bits as usize;
+            } else {
+                // 非bit字段前未满8bit的部分补齐为一个字节
+                total_bytes += pending_bits.div_ceil(8);
+                pending_bits = 0;
+                total_bytes += self.alignment_padding(&field.field_id, total_bytes);
+
+                total_bytes += match self.field_values.get(&field.field_id) {
+                    Some(value) => value.len(),
+                    None => self.get_field_size(field)?,
+                };
+            }
+        }
+
+        Ok(total_bytes + pending_bits.div_ceil(8))
+    }
+
+    /// 批量组装协议帧
+    ///
+    /// 每组字段值在组装器当前字段值的基础上设置，未给出的字段保持原值；
+    /// 各次组装复用同一帧缓冲区，完成后恢复组装器原有字段值
+    pub fn assemble_batch(
+        &mut self,
+        values: &[HashMap<String, Vec<u8>>],
+    ) -> Result<Vec<Vec<u8>>, ProtocolError> {
+        let base_values = self.field_values.clone();
+        let result = self.assemble_batch_with_base(values, &base_values);
+        self.field_values = base_values;
+        result
+    }
+
+    /// 基于给定的初始字段值依次组装每组字段值
+    fn assemble_batch_with_base(
+        &mut self,
+        values: &[HashMap<String, Vec<u8>>],
+        base_values: &HashMap<String, Vec<u8>>,
+    ) -> Result<Vec<Vec<u8>>, ProtocolError> {
+        let mut frames = Vec::with_capacity(values.len());
+        let mut frame_data = Vec::new();
+
+        for value_set in values {
+            self.field_values.clone_from(base_values);
+            for (field_name, value) in value_set {
+                self.store_field_value(field_name, value)?;
+            }
+
+            self.assemble_frame_into(&mut frame_data)?;
+            frames.push(frame_data.clone());
+        }
+
+        Ok(frames)
+    }
+
+    /// 组装协议帧到给定缓冲区，缓冲区原有内容会被清空
+    fn assemble_frame_into(&mut self, frame_data: &mut Vec<u8>) -> Result<(), ProtocolError> {
         // 正确的bit字段打包逻辑：
         // 1. 按字段添加顺序遍历
         // 2. 连续的bit字段累积到bit_buffer中
         // 3. 当累积满8bit或遇到非bit字段时，将bit_buffer写入frame_data
         // 4. 非bit字段直接写入frame_data
 
-        let mut frame_data = Vec::new();
-        let mut bit_buffer: u64 = 0; // 用于收集连续bit字段的缓冲区（使用u64支持大字段）
+        self.apply_conditional_rules()?;
+        self.check_field_constraints()?;
+
+        frame_data.clear();
+        // 用于收集连续bit字段的缓冲区：最多7个未写出的bit加一个64bit字段，使用u128避免丢失高位
+        let mut bit_buffer: u128 = 0;
         let mut total_bits_used: u32 = 0; // 当前缓冲区中已使用的bit总数
 
-        // 按顺序处理所有字段
-        for field in &self.fields {
+        // 按顺序处理所有字段，跳过条件不成立的字段
+        for field in self.fields.iter() {
+            if self.skipped_fields.contains(&field.field_id) {
+                continue;
+            }
             if let UnitType::Bit(bits) = field.unit_type {
                 // 获取bit字段值
                 let bit_value = self.get_bit_field_value(&field.field_id)?;
 
                 // 验证bit值不超过字段大小限制
-                let max_value = (1u64 << bits) - 1;
+                if bits > 64 {
+                    return Err(ProtocolError::InvalidFrameFormat(format!(
+                        "Bit field {} is wider than 64 bits",
+                        field.field_id
+                    )));
+                }
+                let max_value = u64::MAX >> (64 - bits as u32);
                 if bit_value > max_value {
                     return Err(ProtocolError::ValueOutOfRange(format!(
                         "Bit field {} value {} exceeds maximum value {}",
@@ -188,30 +396,54 @@ impl FrameAssembler {
                     )));
                 }
 
-                // 将bit值添加到累积缓冲区中
-                // 按顺序放置：先出现的bit放在高位，后出现的bit放在低位
-                bit_buffer = (bit_buffer << (bits as u32)) | (bit_value & max_value);
-                total_bits_used += bits as u32;
-
-                // 如果累计的bit数达到或超过8位，将已满的字节写入帧
-                while total_bits_used >= 8 {
-                    // 取出最高的8位
-                    let byte_to_write = ((bit_buffer >> (total_bits_used - 8)) & 0xFF) as u8;
-                    frame_data.push(byte_to_write);
-                    total_bits_used -= 8;
-                    // 保留剩余的低位
-                    bit_buffer &= (1u64 << total_bits_used) - 1;
+                // 对齐规则只作用于从字节边界开始的bit字段
+                if total_bits_used == 0 {
+                    let padding = self.alignment_padding(&field.field_id, frame_data.len());
+                    frame_data.resize(frame_data.len() + padding, 0);
+                }
+
+                match self.bit_numbering {
+                    BitNumbering::Msb0 => {
+                        // 将bit值添加到累积缓冲区中
+                        // 按顺序放置：先出现的bit放在高位，后出现的bit放在低位
+                        bit_buffer = (bit_buffer << (bits as u32)) | bit_value as u128;
+                        total_bits_used += bits as u32;
+
+                        // 如果累计的bit数达到或超过8位，将已满的字节写入帧
+                        while total_bits_used >= 8 {
+                            // 取出最高的8位
+                            let byte_to_write =
+                                ((bit_buffer >> (total_bits_used - 8)) & 0xFF) as u8;
+                            frame_data.push(byte_to_write);
+                            total_bits_used -= 8;
+                            // 保留剩余的低位
+                            bit_buffer &= (1u128 << total_bits_used) - 1;
+                        }
+                    }
+                    BitNumbering::Lsb0 => {
+                        // LSB-0：先出现的bit放在字节低位，逐字节从低位写出
+                        bit_buffer |= (bit_value as u128) << total_bits_used;
+                        total_bits_used += bits as u32;
+
+                        while total_bits_used >= 8 {
+                            frame_data.push((bit_buffer & 0xFF) as u8);
+                            bit_buffer >>= 8;
+                            total_bits_used -= 8;
+                        }
+                    }
                 }
             } else {
                 // 遇到非bit字段时，先刷新bit缓冲区（如果有未满8bit的）
                 if total_bits_used > 0 {
-                    // 将剩余bit左对齐到字节边界
-                    let remaining_byte = ((bit_buffer << (8 - total_bits_used)) & 0xFF) as u8;
-                    frame_data.push(remaining_byte);
+                    frame_data.push(self.flush_bit_buffer(bit_buffer, total_bits_used));
                     bit_buffer = 0;
                     total_bits_used = 0;
                 }
 
+                // 按对齐规则插入填充字节
+                let padding = self.alignment_padding(&field.field_id, frame_data.len());
+                frame_data.resize(frame_data.len() + padding, 0);
+
                 // 然后添加非bit字段
                 let field_bytes = self.get_field_bytes(&field.field_id)?;
                 frame_data.extend_from_slice(&field_bytes);
@@ -220,18 +452,32 @@ impl FrameAssembler {
 
         // 最后如果还有未满8bit的bit字段，也写入（整字节对齐）
         if total_bits_used > 0 {
-            // 将剩余bit左对齐到字节边界
-            let remaining_byte = ((bit_buffer << (8 - total_bits_used)) & 0xFF) as u8;
-            frame_data.push(remaining_byte);
+            frame_data.push(self.flush_bit_buffer(bit_buffer, total_bits_used));
         }
 
         // 第一阶段：应用非长度、非CRC规则（如SequenceControl等）
-        self.apply_other_semantic_rules(&mut frame_data)?;
+        self.apply_other_semantic_rules(frame_data)?;
+
+        // 执行用户注册的自定义规则处理器
+        self.apply_custom_rule_handlers(frame_data)?;
 
         // 第二阶段：应用长度和CRC等需要在完整帧基础上计算的规则
-        self.apply_length_and_crc_rules(&mut frame_data)?;
+        self.apply_length_and_crc_rules(frame_data)?;
 
-        Ok(frame_data)
+        // 最后按多路复用规则路由完整的帧
+        self.apply_multiplexing_rules(frame_data)?;
+
+        Ok(())
+    }
+
+    /// 将未满8bit的缓冲区输出为一个字节
+    fn flush_bit_buffer(&self, bit_buffer: u128, total_bits_used: u32) -> u8 {
+        match self.bit_numbering {
+            // 将剩余bit左对齐到字节边界
+            BitNumbering::Msb0 => ((bit_buffer << (8 - total_bits_used)) & 0xFF) as u8,
+            // LSB-0下剩余bit已经位于字节低位
+            BitNumbering::Lsb0 => (bit_buffer & 0xFF) as u8,
+        }
     }
 
     /// 应用其他语义规则（在长度和CRC规则之前）
@@ -241,9 +487,9 @@ impl FrameAssembler {
         frame_data: &mut [u8],
     ) -> Result<(), ProtocolError> {
         // 克隆语义规则以避免借用冲突
-        let rules_to_process: Vec<_> = self.semantic_rules.clone();
+        let rules_to_process = Arc::clone(&self.semantic_rules);
 
-        for rule in &rules_to_process {
+        for rule in rules_to_process.iter() {
             match rule {
                 SemanticRule::SequenceControl {
                     field_name,
@@ -260,6 +506,26 @@ impl FrameAssembler {
                         frame_data,
                     )?;
                 }
+                SemanticRule::Pointer {
+                    pointer_field,
+                    target_field,
+                } => {
+                    self.apply_pointer_rule(pointer_field, target_field, frame_data)?;
+                }
+                SemanticRule::Algorithm {
+                    field_name,
+                    algorithm,
+                } => {
+                    // 校验字段上的算法声明由校验和规则处理，注册了处理器的规则由处理器执行，
+                    // 其余按已注册的自定义算法执行，未注册的算法报错
+                    let handled = self
+                        .rule_handlers
+                        .iter()
+                        .any(|handler| handler.applies(rule));
+                    if !handled && !self.is_checksum_target(field_name)? {
+                        self.apply_custom_algorithm(field_name, algorithm, frame_data)?;
+                    }
+                }
                 // 其他非长度、非CRC规则可以在这里添加
                 _ => {
                     // 跳过长度规则和校验和规则，它们在第二阶段处理
@@ -271,14 +537,29 @@ impl FrameAssembler {
     }
 
     /// 解析协议帧
+    ///
+    /// 按字段定义切分帧数据；字段布局覆盖整帧时验证帧中的校验和，并按多路复用规则路由该帧
+    ///
+    /// 解析不计算条件规则：先清空上次组装省略的字段，按完整字段布局切分
+    ///
+    /// 字段值保持帧中的字节序，与`get_field_value`返回的形式一致
     pub fn parse_frame(
         &mut self,
         frame_data: &[u8],
     ) -> Result<Vec<(String, Vec<u8>)>, ProtocolError> {
+        if frame_data.is_empty() && !self.fields.is_empty() {
+            return Err(ProtocolError::InvalidFrameFormat(
+                "Empty frame data for non-empty frame definition".to_string(),
+            ));
+        }
+
+        self.skipped_fields.clear();
         let mut parsed_fields = Vec::new();
         let mut offset = 0;
 
-        for field in &self.fields {
+        for field in self.fields.iter() {
+            // 跳过对齐填充字节
+            offset += self.alignment_padding(&field.field_id, offset);
             let field_size = self.get_field_size(field)?;
             if offset + field_size > frame_data.len() {
                 return Err(ProtocolError::InvalidFrameFormat(format!(
@@ -292,24 +573,77 @@ impl FrameAssembler {
             offset += field_size;
         }
 
+        // 字段布局与帧长度一致时才能定位校验字段，校验和不匹配的帧视为损坏
+        if offset == frame_data.len() {
+            self.validate_checksum_rules(frame_data)?;
+        }
+        self.apply_multiplexing_rules(frame_data)?;
+
         Ok(parsed_fields)
     }
 
     /// 设置字段值
+    ///
+    /// 字段由长度规则或校验和规则计算时，设置的值会在组装时被覆盖：
+    /// Strict模式报错，Lenient模式记录警告后仍保存该值。
+    /// 字段由序列控制规则维护时记录警告，设置的值作为序列的当前值
     pub fn set_field_value(&mut self, field_name: &str, value: &[u8]) -> Result<(), ProtocolError> {
+        let clean_field_name = field_name.trim_start_matches("field: ").trim();
+        if let Some(rule_kind) = self.overwriting_rule_kind(clean_field_name) {
+            let message = format!(
+                "Field '{clean_field_name}' is computed by a {rule_kind} rule; the value set will be overwritten during assembly"
+            );
+            match self.constraint_mode {
+                ConstraintMode::Strict => return Err(ProtocolError::ValidationError(message)),
+                ConstraintMode::Lenient => log::warn!("{message}"),
+            }
+        } else if self.is_sequence_controlled(clean_field_name) {
+            log::warn!(
+                "Field '{clean_field_name}' is maintained by a sequence_control rule; the value set restarts the sequence"
+            );
+        }
+
+        self.store_field_value(field_name, value)?;
+        println!("Setting field {clean_field_name} to value: {value:?}");
+        Ok(())
+    }
+
+    /// 获取组装时会覆盖字段值的语义规则类型（长度规则目标字段或校验和规则的校验字段）
+    pub fn overwriting_rule_kind(&self, field_name: &str) -> Option<&'static str> {
+        let index = *self.field_index.get(field_name)?;
+        self.semantic_rules.iter().find_map(|rule| {
+            let overwrites = match rule {
+                SemanticRule::LengthRule {
+                    field_name: target, ..
+                } => target.trim_start_matches("field: ").trim() == field_name,
+                SemanticRule::ChecksumRange {
+                    algorithm,
+                    end_field,
+                    ..
+                } => {
+                    let end_field = end_field.trim_start_matches("end: ").trim();
+                    matches!(
+                        self.resolve_checksum_field(algorithm, end_field),
+                        Ok(Some(checksum_index)) if checksum_index == index
+                    )
+                }
+                _ => false,
+            };
+            overwrites.then(|| rule.kind())
+        })
+    }
+
+    /// 校验并存储字段值
+    fn store_field_value(&mut self, field_name: &str, value: &[u8]) -> Result<(), ProtocolError> {
         // 清理字段名，移除可能的前缀
         let clean_field_name = field_name.trim_start_matches("field: ").trim();
 
         let Some(&index) = self.field_index.get(clean_field_name) else {
-            return Err(ProtocolError::FieldNotFound(format!(
-                "Field not found: {clean_field_name}"
-            )));
+            return Err(ProtocolError::FieldNotFound(clean_field_name.to_string()));
         };
 
         let Some(field) = self.fields.get(index) else {
-            return Err(ProtocolError::FieldNotFound(format!(
-                "Field not found: {clean_field_name}"
-            )));
+            return Err(ProtocolError::FieldNotFound(clean_field_name.to_string()));
         };
 
         // 对于动态长度字段，跳过长度验证
@@ -328,11 +662,14 @@ impl FrameAssembler {
 
         // 根据字段的字节序处理数据
         let processed_value = self.convert_field_value_for_storage(field_name, value);
+        // 动态长度字段的空值不含取值，不检查约束
+        if !processed_value.is_empty() && processed_value.len() <= 8 {
+            self.check_field_constraint(field, bytes_to_u64_be(&processed_value))?;
+        }
 
         // 存储字段值
         self.field_values
             .insert(clean_field_name.to_string(), processed_value);
-        println!("Setting field {clean_field_name} to value: {value:?}");
         Ok(())
     }
 
@@ -347,9 +684,7 @@ impl FrameAssembler {
         } else {
             // 如果没有显式设置的值，检查字段定义中是否有固定值约束
             let Some(&index) = self.field_index.get(clean_field_name) else {
-                return Err(ProtocolError::FieldNotFound(format!(
-                    "Field not found: {clean_field_name}"
-                )));
+                return Err(ProtocolError::FieldNotFound(clean_field_name.to_string()));
             };
 
             let Some(field) = self.fields.get(index) else {
@@ -370,7 +705,7 @@ impl FrameAssembler {
                     val >>= 8;
                 }
                 bytes.reverse(); // 高位在前
-                Ok(bytes)
+                Ok(self.convert_field_value_from_storage(field_name, &bytes))
             } else {
                 // 如果不是固定值约束或没有约束定义，返回零填充的默认值
                 let size = self.get_field_size(field)?;
@@ -389,9 +724,7 @@ impl FrameAssembler {
         } else {
             // 如果字段值未设置，检查字段定义中是否有固定值约束作为默认值
             let Some(&index) = self.field_index.get(clean_field_name) else {
-                return Err(ProtocolError::FieldNotFound(format!(
-                    "Field not found: {clean_field_name}"
-                )));
+                return Err(ProtocolError::FieldNotFound(clean_field_name.to_string()));
             };
 
             let Some(field) = self.fields.get(index) else {
@@ -412,7 +745,7 @@ impl FrameAssembler {
                     val >>= 8;
                 }
                 bytes.reverse(); // 高位在前
-                Ok(bytes)
+                Ok(self.convert_field_value_from_storage(field_name, &bytes))
             } else {
                 // 如果不是固定值约束或没有约束定义，返回零填充的默认值
                 let size = self.get_field_size(field)?;
@@ -423,6 +756,9 @@ impl FrameAssembler {
 
     /// 获取字段大小
     pub fn get_field_size(&self, field: &SyntaxUnit) -> Result<usize, ProtocolError> {
+        if self.skipped_fields.contains(&field.field_id) {
+            return Ok(0);
+        }
         match field.length.unit {
             LengthUnit::Bit => {
                 // 对于bit字段，如果字段类型是Bit，我们返回字节大小（向上取整）
@@ -435,11 +771,11 @@ impl FrameAssembler {
             }
             LengthUnit::Byte => Ok(field.length.size),
             LengthUnit::Dynamic => {
-                // 对于动态长度字段，尝试从已存储的值中获取大小
+                // 对于动态长度字段，使用已存储值的长度，显式设置的空值为0字节
                 if let Some(stored_value) = self.field_values.get(&field.field_id) {
                     Ok(stored_value.len())
                 } else {
-                    // 默认大小
+                    // 未设置值时默认1字节
                     Ok(1)
                 }
             }
@@ -463,9 +799,7 @@ impl FrameAssembler {
                 )))
             }
         } else {
-            Err(ProtocolError::FieldNotFound(format!(
-                "Field not found: {clean_field_name}"
-            )))
+            Err(ProtocolError::FieldNotFound(clean_field_name.to_string()))
         }
     }
 
@@ -475,22 +809,83 @@ impl FrameAssembler {
         if let Some(&index) = self.field_index.get(clean_field_name) {
             self.calculate_field_offset(index)
         } else {
-            Err(ProtocolError::FieldNotFound(format!(
-                "Field not found: {clean_field_name}"
-            )))
+            Err(ProtocolError::FieldNotFound(clean_field_name.to_string()))
         }
     }
 
-    /// 计算字段在帧中的偏移量
+    /// 计算字段在帧中的字节偏移量
+    ///
+    /// 与组装时的打包方式一致：连续的bit字段紧密排列，非bit字段从下一个字节边界开始；
+    /// bit字段返回其起始bit所在的字节
     pub fn calculate_field_offset(&self, field_index: usize) -> Result<usize, ProtocolError> {
-        let mut offset = 0;
-        for i in 0..field_index {
-            if let Some(field) = self.fields.get(i) {
-                let field_size = self.get_field_size(field)?;
-                offset += field_size;
+        Ok(self.calculate_field_bit_offset(field_index)? / 8)
+    }
+
+    /// 计算字段在帧中的起始bit偏移
+    pub fn calculate_field_bit_offset(&self, field_index: usize) -> Result<usize, ProtocolError> {
+        let mut bit_offset = 0usize;
+        for (i, field) in self.fields.iter().enumerate().take(field_index + 1) {
+            // 省略的字段不占位，也不触发字节对齐
+            if i != field_index && self.skipped_fields.contains(&field.field_id) {
+                continue;
+            }
+            if !matches!(field.unit_type, UnitType::Bit(_)) {
+                bit_offset = bit_offset.div_ceil(8) * 8;
             }
+            bit_offset += self.alignment_padding_bits(field, bit_offset);
+            if i == field_index {
+                break;
+            }
+            bit_offset += match field.unit_type {
+                UnitType::Bit(bits) => bits as usize,
+                _ => self.get_field_size(field)? * 8,
+            };
         }
-        Ok(offset)
+        Ok(bit_offset)
+    }
+
+    /// 更新帧数据中指定字段的值
+    ///
+    /// 动态长度字段允许新值与原值长度不同，帧数据随之伸缩，后续字段整体平移；
+    /// 字段偏移量按字段值实时计算，更新字段值后后续字段的偏移量随之改变。
+    /// 固定长度字段的新值长度必须与字段长度一致
+    pub fn update_frame_data_at_position(
+        &mut self,
+        frame_data: &mut Vec<u8>,
+        field_idx: usize,
+        new_value: &[u8],
+    ) -> Result<(), ProtocolError> {
+        let Some(field) = self.fields.get(field_idx) else {
+            return Err(ProtocolError::FieldNotFound(format!(
+                "Field definition not found for index: {field_idx}"
+            )));
+        };
+
+        let field_name = field.field_id.clone();
+        let is_dynamic = field.length.unit == LengthUnit::Dynamic;
+        let start_pos = self.calculate_field_offset(field_idx)?;
+        let old_size = self.get_field_size(field)?;
+
+        if !is_dynamic && new_value.len() != old_size {
+            return Err(ProtocolError::LengthError(format!(
+                "Field {field_name} expected {old_size} bytes, got {} bytes",
+                new_value.len()
+            )));
+        }
+
+        if start_pos + old_size > frame_data.len() {
+            return Err(ProtocolError::InvalidFrameFormat(format!(
+                "Field {field_name} position exceeds frame size"
+            )));
+        }
+
+        // 替换字段原有范围，长度变化时后续数据随之平移
+        frame_data.splice(start_pos..start_pos + old_size, new_value.iter().copied());
+
+        // 同步更新字段值，使后续字段的偏移量按新长度计算
+        let stored_value = self.convert_field_value_for_storage(&field_name, new_value);
+        self.field_values.insert(field_name, stored_value);
+        Ok(())
     }
 
     /// 将u64值转换为指定长度的字节数组
@@ -530,9 +925,7 @@ impl FrameAssembler {
         let clean_field_name = field_name.trim_start_matches("field: ").trim();
 
         let Some(&index) = self.field_index.get(clean_field_name) else {
-            return Err(ProtocolError::FieldNotFound(format!(
-                "Field not found: {clean_field_name}"
-            )));
+            return Err(ProtocolError::FieldNotFound(clean_field_name.to_string()));
         };
 
         let Some(field) = self.fields.get(index) else {
@@ -550,6 +943,7 @@ impl FrameAssembler {
                     "Value {value} exceeds maximum value {max_value} for {bits}-bit field {clean_field_name}"
                 )));
             }
+            self.check_field_constraint(field, value as u64)?;
 
             self.bit_field_values
                 .insert(clean_field_name.to_string(), value);
@@ -568,9 +962,7 @@ impl FrameAssembler {
         let clean_field_name = field_name.trim_start_matches("field: ").trim();
 
         let Some(&index) = self.field_index.get(clean_field_name) else {
-            return Err(ProtocolError::FieldNotFound(format!(
-                "Field not found: {clean_field_name}"
-            )));
+            return Err(ProtocolError::FieldNotFound(clean_field_name.to_string()));
         };
 
         let Some(field) = self.fields.get(index) else {
@@ -591,13 +983,12 @@ impl FrameAssembler {
                 // 从字节数组中提取bit值
                 if !bytes.is_empty() {
                     // 对于多字节的bit字段（如11位的apid），需要组合字节
-                    let mut value = 0u64;
-                    for &byte in bytes {
-                        value = (value << 8) | (byte as u64);
-                    }
+                    let value = bytes
+                        .iter()
+                        .fold(0u128, |value, &byte| (value << 8) | byte as u128);
                     // 截取到bit字段的实际位数
-                    let mask = (1u64 << bits) - 1;
-                    return Ok(value & mask);
+                    let mask = u64::MAX >> (64 - bits.clamp(1, 64) as u32);
+                    return Ok(value as u64 & mask);
                 }
             }
 
@@ -699,9 +1090,7 @@ impl FrameAssembler {
         let clean_field_name = field_name.trim_start_matches("field: ").trim();
 
         let Some(&field_index) = self.field_index.get(clean_field_name) else {
-            return Err(ProtocolError::FieldNotFound(format!(
-                "Field not found: {clean_field_name}"
-            )));
+            return Err(ProtocolError::FieldNotFound(clean_field_name.to_string()));
         };
 
         let Some(field) = self.fields.get(field_index) else {
@@ -714,10 +1103,12 @@ impl FrameAssembler {
         let mut total_bits_before = 0usize;
         for i in 0..field_index {
             if let Some(prev_field) = self.fields.get(i) {
+                total_bits_before += self.alignment_padding_bits(prev_field, total_bits_before);
                 let field_bits = self.get_field_bit_length(prev_field)?;
                 total_bits_before += field_bits;
             }
         }
+        total_bits_before += self.alignment_padding_bits(field, total_bits_before);
 
         // 获取当前字段的bit长度
         let field_bit_length = self.get_field_bit_length(field)?;
@@ -725,6 +1116,15 @@ impl FrameAssembler {
         Ok((total_bits_before, field_bit_length))
     }
 
+    /// 获取字段位于给定bit偏移时对齐填充占用的bit数，未处于字节边界时不对齐
+    fn alignment_padding_bits(&self, field: &SyntaxUnit, bit_offset: usize) -> usize {
+        if bit_offset.is_multiple_of(8) {
+            self.alignment_padding(&field.field_id, bit_offset / 8) * 8
+        } else {
+            0
+        }
+    }
+
     /// 获取字段的bit长度
     ///
     /// # 参数
@@ -769,7 +1169,7 @@ impl FrameAssembler {
     /// 计算数据域的起始位置（字节偏移）
     ///
     /// 用于计算父包中数据域字段（如tm_data_field）的实际字节偏移量。
-    /// 这个偏移量是所有前置字段（包含bit字段整字节对齐后）占用的总字节数。
+    /// 这个偏移量是所有前置字段及对齐填充占用的总字节数。
     ///
     /// # 参数
     /// - `data_field_name`: 数据域字段名称
@@ -791,27 +1191,8 @@ impl FrameAssembler {
         &self,
         data_field_name: &str,
     ) -> Result<usize, ProtocolError> {
-        let clean_field_name = data_field_name.trim_start_matches("field: ").trim();
-
-        let Some(&field_index) = self.field_index.get(clean_field_name) else {
-            return Err(ProtocolError::FieldNotFound(format!(
-                "Field not found: {clean_field_name}"
-            )));
-        };
-
-        // 计算该字段之前所有字段占用的总bit数
-        let mut total_bits_before = 0usize;
-        for i in 0..field_index {
-            if let Some(prev_field) = self.fields.get(i) {
-                let field_bits = self.get_field_bit_length(prev_field)?;
-                total_bits_before += field_bits;
-            }
-        }
-
-        // 将bit数向上取整为字节数（bit字段需要整字节对齐）
-        let byte_offset = total_bits_before.div_ceil(8);
-
-        Ok(byte_offset)
+        let (bit_offset, _) = self.get_field_bit_position(data_field_name)?;
+        Ok(bit_offset / 8)
     }
 
     /// 打印所有字段的bit级布局信息
@@ -1,21 +1,158 @@
 //! 自定义算法处理器
 //!
-//! 处理用户自定义的算法规则
+//! 处理用户自定义的算法规则，算法按名称注册到组装器实例或全局注册表
 
-use apdl_core::ProtocolError;
+use apdl_core::{ProtocolError, SemanticRule};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
 
 use crate::standard_units::frame_assembler::core::FrameAssembler;
 
+/// 自定义算法函数
+pub type CustomAlgorithmFn = Arc<dyn Fn(&[u8]) -> Vec<u8> + Send + Sync>;
+
+/// 自定义算法注册表
+///
+/// 用于执行`AlgorithmAst::Custom`及自定义`mapping_logic`
+#[derive(Clone, Default)]
+pub struct CustomAlgorithmRegistry {
+    algorithms: HashMap<String, CustomAlgorithmFn>,
+}
+
+impl CustomAlgorithmRegistry {
+    /// 创建空的注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 获取全局注册表
+    pub fn global() -> &'static RwLock<CustomAlgorithmRegistry> {
+        static GLOBAL: OnceLock<RwLock<CustomAlgorithmRegistry>> = OnceLock::new();
+        GLOBAL.get_or_init(|| RwLock::new(CustomAlgorithmRegistry::new()))
+    }
+
+    /// 向全局注册表注册算法
+    pub fn register_global<F>(name: &str, func: F)
+    where
+        F: Fn(&[u8]) -> Vec<u8> + Send + Sync + 'static,
+    {
+        if let Ok(mut registry) = Self::global().write() {
+            registry.register(name, func);
+        }
+    }
+
+    /// 注册算法，同名算法会被覆盖
+    pub fn register<F>(&mut self, name: &str, func: F)
+    where
+        F: Fn(&[u8]) -> Vec<u8> + Send + Sync + 'static,
+    {
+        self.algorithms.insert(name.to_string(), Arc::new(func));
+    }
+
+    /// 在全局注册表中按名称查找算法
+    pub fn lookup_global(name: &str) -> Option<CustomAlgorithmFn> {
+        Self::global()
+            .read()
+            .ok()
+            .and_then(|registry| registry.algorithms.get(name).cloned())
+    }
+
+    /// 按名称查找算法，先查找本注册表，再查找全局注册表
+    pub fn lookup(&self, name: &str) -> Option<CustomAlgorithmFn> {
+        self.algorithms
+            .get(name)
+            .cloned()
+            .or_else(|| Self::lookup_global(name))
+    }
+
+    /// 执行指定名称的算法
+    pub fn apply(&self, name: &str, data: &[u8]) -> Result<Vec<u8>, ProtocolError> {
+        let func = self
+            .lookup(name)
+            .ok_or_else(|| ProtocolError::Other(format!("Unknown custom algorithm: {name}")))?;
+        Ok(func(data))
+    }
+
+    /// 执行映射逻辑：恒等映射的各个别名直接返回输入，其余名称按已注册的算法执行
+    pub fn apply_mapping(&self, name: &str, data: &[u8]) -> Result<Vec<u8>, ProtocolError> {
+        match name {
+            "identity" | "direct" | "passthrough" => Ok(data.to_vec()),
+            _ => self.apply(name, data),
+        }
+    }
+}
+
 impl FrameAssembler {
+    /// 向组装器实例注册自定义算法
+    pub fn register_custom_algorithm<F>(&mut self, name: &str, func: F)
+    where
+        F: Fn(&[u8]) -> Vec<u8> + Send + Sync + 'static,
+    {
+        self.custom_algorithms.register(name, func);
+    }
+
+    /// 字段是否为校验字段：带`alg`声明，或是某条校验和范围规则的校验字段
+    ///
+    /// 校验字段上的算法规则只声明校验算法，由校验和规则写入，不按自定义算法执行
+    pub fn is_checksum_target(&self, field_name: &str) -> Result<bool, ProtocolError> {
+        let clean_field_name = field_name.trim_start_matches("field: ").trim();
+        let Some(&index) = self.field_index.get(clean_field_name) else {
+            return Ok(false);
+        };
+        if self.fields[index].alg.is_some() {
+            return Ok(true);
+        }
+        for rule in self.semantic_rules.iter() {
+            if let SemanticRule::ChecksumRange {
+                algorithm,
+                end_field,
+                ..
+            } = rule
+            {
+                let end_field = end_field.trim_start_matches("end: ").trim();
+                if self.resolve_checksum_field(algorithm, end_field)? == Some(index) {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+
     /// 应用自定义算法规则
+    ///
+    /// 以字段当前在帧中的字节为输入，算法输出写回该字段，输出长度必须与字段长度一致
     pub fn apply_custom_algorithm(
         &mut self,
         field_name: &str,
         algorithm: &str,
-        _frame_data: &mut [u8],
+        frame_data: &mut [u8],
     ) -> Result<(), ProtocolError> {
-        // 应用自定义算法到指定字段
-        println!("Applied custom algorithm {algorithm} to field {field_name}");
+        let clean_field_name = field_name.trim_start_matches("field: ").trim();
+        let field_offset = self.get_field_position(clean_field_name)?;
+        let field_size = self.get_field_size_by_name(clean_field_name)?;
+        if field_offset + field_size > frame_data.len() {
+            return Err(ProtocolError::InvalidFrameFormat(format!(
+                "Field {clean_field_name} exceeds frame size"
+            )));
+        }
+
+        let field_range = field_offset..field_offset + field_size;
+        let output = self
+            .custom_algorithms
+            .apply(algorithm, &frame_data[field_range.clone()])?;
+        if output.len() != field_size {
+            return Err(ProtocolError::LengthError(format!(
+                "Custom algorithm {algorithm} produced {} bytes for {field_size}-byte field {clean_field_name}",
+                output.len()
+            )));
+        }
+
+        frame_data[field_range].copy_from_slice(&output);
+        let stored_value = self.convert_field_value_for_storage(clean_field_name, &output);
+        self.field_values
+            .insert(clean_field_name.to_string(), stored_value);
+
+        println!("Applied custom algorithm {algorithm} to field {clean_field_name}");
         Ok(())
     }
 }
@@ -5,6 +5,7 @@
 use apdl_core::{FieldMappingEntry, ProtocolError};
 use hex;
 
+use crate::standard_units::connector::field_mapper::FieldMapper;
 use crate::standard_units::frame_assembler::core::FrameAssembler;
 
 impl FrameAssembler {
@@ -60,6 +61,7 @@ impl FrameAssembler {
         )?;
 
         // 将映射后的值设置到目标字段
+        let mapped_value = self.fit_field_width(&mapping.target_field, mapped_value);
         self.set_field_value(&mapping.target_field, &mapped_value)?;
 
         println!(
@@ -86,10 +88,6 @@ impl FrameAssembler {
 
         // 如果没有枚举映射或枚举映射不匹配，则应用常规映射逻辑
         match mapping_logic {
-            "identity" | "direct" | "passthrough" => {
-                // 恒等映射，直接返回源值
-                Ok(source_value.to_vec())
-            }
             logic if logic.contains("hash") => {
                 // 哈希映射逻辑，例如 "hash_mod_64", "hash(x) % 64"
                 self.apply_hash_mapping(source_value, logic)
@@ -106,10 +104,8 @@ impl FrameAssembler {
                 // 掩码映射逻辑
                 self.apply_mask_mapping(source_value, logic)
             }
-            _ => {
-                // 默认使用恒等映射
-                Ok(source_value.to_vec())
-            }
+            // 恒等映射或已注册的自定义算法，未知名称报错
+            logic => self.custom_algorithms.apply_mapping(logic, source_value),
         }
     }
 
@@ -122,18 +118,22 @@ impl FrameAssembler {
         // 将源值转换为字符串进行匹配
         let source_str = String::from_utf8_lossy(source_value).to_string();
 
-        for enum_mapping in enum_mappings {
-            // 使用通配符匹配算法
-            if crate::standard_units::frame_assembler::utils::wildcard_match(
-                &source_str,
-                &enum_mapping.source_enum,
-            ) {
-                // 返回目标枚举值
-                return Some(enum_mapping.target_enum.as_bytes().to_vec());
-            }
-        }
+        // 精确匹配优先，其次取最具体的通配符模式
+        FieldMapper::match_enum(&source_str, enum_mappings)
+            .map(|enum_mapping| FieldMapper::enum_target_bytes(&enum_mapping.target_enum))
+    }
 
-        None
+    /// 将数值映射结果截取为目标字段宽度（仅去除高位的零字节）
+    pub(crate) fn fit_field_width(&self, field_name: &str, value: Vec<u8>) -> Vec<u8> {
+        let Ok(current) = self.get_field_value(field_name) else {
+            return value;
+        };
+        let excess = value.len().saturating_sub(current.len());
+        if excess > 0 && value[..excess].iter().all(|&byte| byte == 0) {
+            value[excess..].to_vec()
+        } else {
+            value
+        }
     }
 
     /// 应用哈希映射
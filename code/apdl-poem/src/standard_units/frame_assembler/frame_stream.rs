@@ -0,0 +1,144 @@
+//! 连续帧流
+//!
+//! 基于帧模板连续组装背靠背的帧：字段值在帧间保持，序列控制规则自动递增序列字段，
+//! 时间同步规则对应的字段自动写入时间戳
+
+use apdl_core::{ProtocolError, SemanticRule};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::core::FrameAssembler;
+use super::template::FrameTemplate;
+use super::utils::u64_to_bytes_be;
+
+/// 时间戳来源
+#[derive(Debug, Clone, Copy)]
+enum TimeSource {
+    /// 系统时间（毫秒）
+    SystemMillis,
+    /// 从start开始每帧递增step，用于可重复的测试
+    Counter { start: u64, step: u64 },
+}
+
+/// 连续帧流
+///
+/// 持有一个模板实例，每次调用`next`组装一帧；单帧覆盖值只作用于当前帧
+pub struct FrameStream {
+    assembler: FrameAssembler,
+    time_source: TimeSource,
+    frames_emitted: u64,
+}
+
+impl FrameStream {
+    /// 根据帧模板创建帧流
+    pub fn new(template: &FrameTemplate) -> Self {
+        Self {
+            assembler: template.instance(),
+            time_source: TimeSource::SystemMillis,
+            frames_emitted: 0,
+        }
+    }
+
+    /// 设置在后续各帧中保持的字段值
+    pub fn set_field_value(&mut self, field_name: &str, value: &[u8]) -> Result<(), ProtocolError> {
+        self.assembler.set_field_value(field_name, value)
+    }
+
+    /// 使用计数时间源：第n帧（从0开始）的时间戳为`start + n * step`
+    pub fn set_time_source(&mut self, start: u64, step: u64) {
+        self.time_source = TimeSource::Counter { start, step };
+    }
+
+    /// 组装下一帧
+    ///
+    /// 依次写入时间戳字段、应用单帧覆盖值并组装；组装后覆盖字段恢复原值，
+    /// 但组装过程中被规则更新的字段（如被覆盖的序列字段）保留更新后的值
+    pub fn next(&mut self, overrides: &[(&str, &[u8])]) -> Result<Vec<u8>, ProtocolError> {
+        self.write_timestamps()?;
+
+        let mut saved = Vec::with_capacity(overrides.len());
+        let mut applied = Ok(());
+        for (field_name, value) in overrides {
+            let field_name = field_name.trim_start_matches("field: ").trim();
+            let previous = self.assembler.field_values.get(field_name).cloned();
+            applied = self.assembler.set_field_value(field_name, value);
+            if applied.is_err() {
+                break;
+            }
+            saved.push((field_name, *value, previous));
+        }
+
+        let frame = applied.and_then(|_| self.assembler.assemble_frame());
+
+        for (field_name, value, previous) in saved {
+            if self
+                .assembler
+                .field_values
+                .get(field_name)
+                .map(Vec::as_slice)
+                != Some(value)
+            {
+                continue;
+            }
+            match previous {
+                Some(previous) => {
+                    self.assembler
+                        .field_values
+                        .insert(field_name.to_string(), previous);
+                }
+                None => {
+                    self.assembler.field_values.remove(field_name);
+                }
+            }
+        }
+
+        let frame = frame?;
+        self.frames_emitted += 1;
+        Ok(frame)
+    }
+
+    /// 已组装的帧数
+    pub fn frames_emitted(&self) -> u64 {
+        self.frames_emitted
+    }
+
+    /// 获取内部组装器，用于读取当前字段值
+    pub fn assembler(&self) -> &FrameAssembler {
+        &self.assembler
+    }
+
+    /// 将当前时间戳写入时间同步规则对应的字段（大端，超出字段宽度的高位截断）
+    fn write_timestamps(&mut self) -> Result<(), ProtocolError> {
+        let fields: Vec<String> = self
+            .assembler
+            .semantic_rules
+            .iter()
+            .filter_map(|rule| match rule {
+                SemanticRule::TimeSynchronization { field_name, .. } => {
+                    Some(field_name.trim_start_matches("field: ").trim().to_string())
+                }
+                _ => None,
+            })
+            .collect();
+        if fields.is_empty() {
+            return Ok(());
+        }
+
+        let timestamp = match self.time_source {
+            TimeSource::SystemMillis => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            TimeSource::Counter { start, step } => {
+                start.wrapping_add(step.wrapping_mul(self.frames_emitted))
+            }
+        };
+
+        for field_name in fields {
+            let size = self.assembler.get_field_size_by_name(&field_name)?;
+            let mut bytes = vec![0u8; size.saturating_sub(8)];
+            bytes.extend(u64_to_bytes_be(timestamp, size.min(8)));
+            self.assembler.set_field_value(&field_name, &bytes)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+//! 分层重组接口实现
+//!
+//! 为 FrameAssembler 实现 apdl-lsk 的 LayerAssembler，使其可作为 LayeredReassembler 的单层组装器
+
+use apdl_core::ProtocolError;
+use apdl_lsk::LayerAssembler;
+use std::collections::HashMap;
+
+use crate::standard_units::frame_assembler::core::FrameAssembler;
+
+impl LayerAssembler for FrameAssembler {
+    /// 设置拆包得到的字段值后组装本层帧
+    ///
+    /// 由长度规则或校验和规则计算的字段不设置，组装时重新计算；未定义的字段被忽略
+    fn assemble_layer(
+        &mut self,
+        fields: &HashMap<String, Vec<u8>>,
+    ) -> Result<Vec<u8>, ProtocolError> {
+        for (field_name, value) in fields {
+            if !self.field_index.contains_key(field_name)
+                || self.overwriting_rule_kind(field_name).is_some()
+            {
+                continue;
+            }
+            self.set_field_value(field_name, value)?;
+        }
+        self.assemble_frame()
+    }
+}
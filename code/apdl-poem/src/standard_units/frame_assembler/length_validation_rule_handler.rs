@@ -98,7 +98,7 @@ impl FrameAssembler {
 
         // 查找数据字段
         let mut data_field_size = 0;
-        for field in &self.fields {
+        for field in self.fields.iter() {
             if self.is_data_field(field) {
                 data_field_size = self.get_field_size(field)? as u64;
                 break;
@@ -269,7 +269,7 @@ impl FrameAssembler {
             return Ok(total_len);
         } else if expr_lower.contains("data_length") {
             // 查找数据字段长度
-            for field in &self.fields {
+            for field in self.fields.iter() {
                 if self.is_data_field(field) {
                     return Ok(self.get_field_size(field)? as u64);
                 }
@@ -291,7 +291,7 @@ impl FrameAssembler {
     /// 计算头部长度
     fn calculate_header_length(&self) -> Result<u64, ProtocolError> {
         let mut header_len = 0;
-        for field in &self.fields {
+        for field in self.fields.iter() {
             if self.is_header_field(field) {
                 header_len += self.get_field_size(field)? as u64;
             }
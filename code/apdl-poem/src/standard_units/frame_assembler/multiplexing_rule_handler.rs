@@ -2,51 +2,120 @@
 //!
 //! 处理多路复用相关的语义规则
 
-use apdl_core::ProtocolError;
+use apdl_core::{ByteOrder, ProtocolError, SemanticRule, UnitType};
+use apdl_lsk::frame_disassembler::extract_bit_field_with_numbering;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 use crate::standard_units::frame_assembler::core::FrameAssembler;
+use crate::standard_units::frame_assembler::utils::{bytes_to_u64_be, u64_to_bytes_be};
 
 impl FrameAssembler {
+    /// 按全部多路复用规则路由帧，条件满足时将帧记录到对应的路由目标
+    ///
+    /// 路由结果只保存当前帧，每次组装或解析前清空上一帧的路由
+    pub fn apply_multiplexing_rules(&mut self, frame_data: &[u8]) -> Result<(), ProtocolError> {
+        self.routed_outputs.clear();
+        let rules = Arc::clone(&self.semantic_rules);
+        for rule in rules.iter() {
+            if let SemanticRule::Multiplexing {
+                field_name,
+                condition,
+                route_target,
+                description,
+            } = rule
+            {
+                self.apply_multiplexing_rule(
+                    field_name,
+                    condition,
+                    route_target,
+                    description,
+                    frame_data,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
     /// 应用多路复用规则
+    ///
+    /// 从帧中读取条件引用的字段值（条件左侧为其他字段名时读取该字段，否则读取`field_name`），
+    /// 条件满足时将帧追加到`route_target`的输出中
     pub fn apply_multiplexing_rule(
         &mut self,
         field_name: &str,
         condition: &str,
         route_target: &str,
         description: &str,
-        _frame_data: &mut [u8],
+        frame_data: &[u8],
     ) -> Result<(), ProtocolError> {
-        println!(
-            "Applying multiplexing rule: {description} for field {field_name} with condition {condition} and route to {route_target}"
-        );
+        let field_name = field_name.trim_start_matches("field: ").trim();
+        let condition_field = self
+            .condition_field_name(condition)
+            .unwrap_or(field_name)
+            .to_string();
+        let field_value = self.read_field_from_frame(frame_data, &condition_field)?;
 
-        // 获取字段值
-        let field_value = if let Ok(value) = self.get_field_value(field_name) {
-            value
-        } else {
+        if self.evaluate_multiplexing_condition(&condition_field, condition, &field_value)? {
+            log::debug!("Multiplexing rule {description}: routing frame to {route_target}");
+            self.routed_outputs
+                .entry(route_target.to_string())
+                .or_default()
+                .push(frame_data.to_vec());
+        }
+
+        Ok(())
+    }
+
+    /// 获取最近一次组装或解析的帧的路由结果（路由目标 -> 帧）
+    pub fn routed_outputs(&self) -> &HashMap<String, Vec<Vec<u8>>> {
+        &self.routed_outputs
+    }
+
+    /// 取出并清空最近一次的路由结果
+    pub fn take_routed_outputs(&mut self) -> HashMap<String, Vec<Vec<u8>>> {
+        std::mem::take(&mut self.routed_outputs)
+    }
+
+    /// 条件左侧为已定义字段名时返回该字段名
+    fn condition_field_name<'a>(&self, condition: &'a str) -> Option<&'a str> {
+        let left = condition.split(['=', '!', '<', '>']).next()?.trim();
+        self.field_index.contains_key(left).then_some(left)
+    }
+
+    /// 从帧数据中读取字段值，返回大端字节（bit字段按位读取）
+    fn read_field_from_frame(
+        &self,
+        frame_data: &[u8],
+        field_name: &str,
+    ) -> Result<Vec<u8>, ProtocolError> {
+        let Some(&index) = self.field_index.get(field_name) else {
             return Err(ProtocolError::FieldNotFound(format!(
                 "Multiplexing field {field_name} not found"
             )));
         };
+        let field = &self.fields[index];
+        let bit_offset = self.calculate_field_bit_offset(index)?;
 
-        // 根据条件判断是否需要进行多路复用处理
-        let should_multiplex =
-            self.evaluate_multiplexing_condition(field_name, condition, &field_value)?;
-
-        if should_multiplex {
-            println!(
-                "Multiplexing condition met for field {field_name}: routing to {route_target}"
-            );
-            // TODO: 在实际应用中，这里可能会根据条件将数据路由到不同的处理路径
-            // 在实际应用中，这里可能会根据条件将数据路由到不同的处理路径
-            // 当前我们只是记录路由决策
-        } else {
-            println!(
-                "Multiplexing condition not met for field {field_name}: no routing to {route_target}"
-            );
+        if let UnitType::Bit(bits) = field.unit_type {
+            let bits = bits as usize;
+            let value =
+                extract_bit_field_with_numbering(frame_data, bit_offset, bits, self.bit_numbering)?;
+            return Ok(u64_to_bytes_be(value, bits.div_ceil(8)));
         }
 
-        Ok(())
+        let start = bit_offset / 8;
+        let end = start + self.get_field_size(field)?;
+        if end > frame_data.len() {
+            return Err(ProtocolError::InvalidFrameFormat(format!(
+                "Multiplexing field {field_name} exceeds frame size"
+            )));
+        }
+        let bytes = &frame_data[start..end];
+        Ok(match self.get_field_byte_order(field_name) {
+            ByteOrder::BigEndian => bytes.to_vec(),
+            ByteOrder::LittleEndian => bytes.iter().rev().copied().collect(),
+        })
     }
 
     /// 评估多路复用条件
@@ -65,6 +134,15 @@ impl FrameAssembler {
             }
             "always_route" => Ok(true),
             "never_route" => Ok(false),
+            _ if condition.starts_with("equals") => {
+                // equals(N) 或 equals N：字段值等于N
+                let value_expr = condition["equals".len()..]
+                    .trim()
+                    .trim_start_matches('(')
+                    .trim_end_matches(')');
+                let expected_value = self.multiplex_parse_value_expression(value_expr)?;
+                Ok(bytes_to_u64_be(field_value) == expected_value)
+            }
             _ => {
                 // 尝试解析更复杂的条件表达式
                 self.parse_complex_condition(field_name, condition, field_value)
@@ -96,7 +174,7 @@ impl FrameAssembler {
             self.multiplex_parse_contains_condition(field_name, condition, field_value)
         } else {
             // 如果无法解析，假设条件为真
-            println!("Unknown condition format '{condition}', defaulting to true");
+            log::debug!("Unknown condition format '{condition}', defaulting to true");
             Ok(true)
         }
     }
@@ -116,7 +194,7 @@ impl FrameAssembler {
             if field_expr == field_name {
                 // 解析期望值
                 let expected_value = self.multiplex_parse_value_expression(value_expr)?;
-                let actual_value = self.bytes_to_u64(field_value);
+                let actual_value = bytes_to_u64_be(field_value);
 
                 Ok(actual_value == expected_value)
             } else {
@@ -142,7 +220,7 @@ impl FrameAssembler {
 
             if field_expr == field_name {
                 let expected_value = self.multiplex_parse_value_expression(value_expr)?;
-                let actual_value = self.bytes_to_u64(field_value);
+                let actual_value = bytes_to_u64_be(field_value);
 
                 Ok(actual_value >= expected_value)
             } else {
@@ -167,7 +245,7 @@ impl FrameAssembler {
 
             if field_expr == field_name {
                 let expected_value = self.multiplex_parse_value_expression(value_expr)?;
-                let actual_value = self.bytes_to_u64(field_value);
+                let actual_value = bytes_to_u64_be(field_value);
 
                 Ok(actual_value > expected_value)
             } else {
@@ -192,7 +270,7 @@ impl FrameAssembler {
 
             if field_expr == field_name {
                 let expected_value = self.multiplex_parse_value_expression(value_expr)?;
-                let actual_value = self.bytes_to_u64(field_value);
+                let actual_value = bytes_to_u64_be(field_value);
 
                 Ok(actual_value <= expected_value)
             } else {
@@ -217,7 +295,7 @@ impl FrameAssembler {
 
             if field_expr == field_name {
                 let expected_value = self.multiplex_parse_value_expression(value_expr)?;
-                let actual_value = self.bytes_to_u64(field_value);
+                let actual_value = bytes_to_u64_be(field_value);
 
                 Ok(actual_value < expected_value)
             } else {
@@ -242,7 +320,7 @@ impl FrameAssembler {
 
             if field_expr == field_name {
                 let expected_value = self.multiplex_parse_value_expression(value_expr)?;
-                let actual_value = self.bytes_to_u64(field_value);
+                let actual_value = bytes_to_u64_be(field_value);
 
                 Ok(actual_value != expected_value)
             } else {
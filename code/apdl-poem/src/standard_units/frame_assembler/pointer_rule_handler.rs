@@ -1,21 +1,124 @@
 //! 指针规则处理器
 //!
-//! 处理字段间的指针关系规则
+//! 处理字段间的指针关系规则：指针字段记录目标字段相对数据区起始的字节偏移（如CCSDS首导头指针）
 
-use apdl_core::ProtocolError;
+use apdl_core::utils::bit_ops::set_bits_with_numbering;
+use apdl_core::{ProtocolError, UnitType};
 
 use crate::standard_units::frame_assembler::core::FrameAssembler;
+use crate::standard_units::frame_assembler::utils::u64_to_bytes_be;
 
 impl FrameAssembler {
     /// 应用指针规则
+    ///
+    /// 将`pos(target_field) - pos(数据区起始字段)`按指针字段宽度写入帧；
+    /// 目标字段未定义或为空时写入全1的空闲值（表示数据区内没有包起始）
     pub fn apply_pointer_rule(
         &mut self,
         pointer_field: &str,
         target_field: &str,
-        _frame_data: &mut [u8],
+        frame_data: &mut [u8],
     ) -> Result<(), ProtocolError> {
-        // 指针字段指向目标字段的逻辑处理
-        println!("Applied pointer rule: {pointer_field} points to {target_field}");
+        let pointer_field = pointer_field.trim_start_matches("field: ").trim();
+        let target_field = target_field.trim_start_matches("field: ").trim();
+        let Some(&pointer_index) = self.field_index.get(pointer_field) else {
+            return Err(ProtocolError::FieldNotFound(format!(
+                "Pointer field not found: {pointer_field}"
+            )));
+        };
+
+        let width = match self.fields[pointer_index].unit_type {
+            UnitType::Bit(bits) => bits as u32,
+            _ => (self.get_field_size(&self.fields[pointer_index])? * 8).min(64) as u32,
+        };
+        if width == 0 {
+            return Err(ProtocolError::InvalidFieldDefinition(format!(
+                "Pointer field {pointer_field} has zero width"
+            )));
+        }
+        let idle_value = u64::MAX >> (64 - width);
+
+        let target_present = self.field_index.contains_key(target_field)
+            && self.get_field_size_by_name(target_field)? > 0;
+        let pointer_value = if target_present {
+            let zone_start = self.pointer_data_zone_start(pointer_field, pointer_index)?;
+            let target_pos = self.get_field_position(target_field)?;
+            let offset = target_pos.checked_sub(zone_start).ok_or_else(|| {
+                ProtocolError::ValueOutOfRange(format!(
+                    "Pointer target {target_field} precedes the data zone of {pointer_field}"
+                ))
+            })? as u64;
+            if offset >= idle_value {
+                return Err(ProtocolError::ValueOutOfRange(format!(
+                    "Pointer offset {offset} does not fit in {width}-bit field {pointer_field}"
+                )));
+            }
+            offset
+        } else {
+            idle_value
+        };
+
+        self.write_pointer_to_frame(frame_data, pointer_index, pointer_value)
+    }
+
+    /// 指定指针字段的数据区起始字段，未指定时使用指针字段的下一个字段
+    pub fn set_pointer_data_zone(&mut self, pointer_field: &str, zone_start_field: &str) {
+        self.pointer_data_zones
+            .insert(pointer_field.to_string(), zone_start_field.to_string());
+    }
+
+    /// 获取指针字段对应数据区的起始字节位置
+    fn pointer_data_zone_start(
+        &self,
+        pointer_field: &str,
+        pointer_index: usize,
+    ) -> Result<usize, ProtocolError> {
+        match self.pointer_data_zones.get(pointer_field) {
+            Some(zone_start_field) => self.get_field_position(zone_start_field),
+            None if pointer_index + 1 < self.fields.len() => {
+                self.calculate_field_offset(pointer_index + 1)
+            }
+            None => Err(ProtocolError::InvalidFieldDefinition(format!(
+                "Pointer field {pointer_field} has no following data zone"
+            ))),
+        }
+    }
+
+    /// 将指针值写入帧数据并更新字段值存储
+    fn write_pointer_to_frame(
+        &mut self,
+        frame_data: &mut [u8],
+        field_index: usize,
+        value: u64,
+    ) -> Result<(), ProtocolError> {
+        let field_name = self.fields[field_index].field_id.clone();
+        let bit_offset = self.calculate_field_bit_offset(field_index)?;
+
+        if let UnitType::Bit(bits) = self.fields[field_index].unit_type {
+            let bits = bits as usize;
+            if bit_offset + bits > frame_data.len() * 8 {
+                return Err(ProtocolError::InvalidFrameFormat(format!(
+                    "Pointer field {field_name} exceeds frame size"
+                )));
+            }
+            set_bits_with_numbering(frame_data, bit_offset, bits, value, self.bit_numbering);
+            self.bit_field_values.remove(&field_name);
+            self.field_values
+                .insert(field_name, u64_to_bytes_be(value, bits.div_ceil(8)));
+            return Ok(());
+        }
+
+        let start = bit_offset / 8;
+        let size = self.get_field_size(&self.fields[field_index])?;
+        if start + size > frame_data.len() {
+            return Err(ProtocolError::InvalidFrameFormat(format!(
+                "Pointer field {field_name} exceeds frame size"
+            )));
+        }
+        let stored = u64_to_bytes_be(value, size);
+        let frame_bytes = self.convert_field_value_from_storage(&field_name, &stored);
+        frame_data[start..start + size].copy_from_slice(&frame_bytes);
+        self.field_values.insert(field_name, stored);
         Ok(())
     }
 }
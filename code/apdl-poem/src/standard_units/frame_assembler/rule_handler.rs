@@ -0,0 +1,54 @@
+//! 自定义规则处理器
+//!
+//! 允许用户注册自定义的语义规则处理器，在组装时与内置规则一同执行
+
+use apdl_core::{ProtocolError, SemanticRule};
+use std::sync::Arc;
+
+use crate::standard_units::frame_assembler::core::FrameAssembler;
+
+/// 语义规则处理器
+///
+/// 处理器在字段打包和内置状态规则（如SequenceControl）之后、长度和校验和规则之前执行，
+/// 处理器修改后的帧数据会参与后续的长度和校验和计算
+pub trait RuleHandler: Send + Sync {
+    /// 判断处理器是否处理该规则
+    fn applies(&self, rule: &SemanticRule) -> bool;
+
+    /// 将规则应用到帧数据
+    fn apply(
+        &self,
+        asm: &mut FrameAssembler,
+        rule: &SemanticRule,
+        frame: &mut Vec<u8>,
+    ) -> Result<(), ProtocolError>;
+}
+
+impl FrameAssembler {
+    /// 注册自定义规则处理器，按注册顺序执行
+    pub fn register_rule_handler<H: RuleHandler + 'static>(&mut self, handler: H) {
+        self.rule_handlers.push(Arc::new(handler));
+    }
+
+    /// 对每条语义规则执行所有适用的自定义处理器
+    pub fn apply_custom_rule_handlers(
+        &mut self,
+        frame_data: &mut Vec<u8>,
+    ) -> Result<(), ProtocolError> {
+        if self.rule_handlers.is_empty() {
+            return Ok(());
+        }
+
+        // 克隆处理器和规则以避免借用冲突
+        let handlers = self.rule_handlers.clone();
+        let rules = Arc::clone(&self.semantic_rules);
+
+        for rule in rules.iter() {
+            for handler in handlers.iter().filter(|handler| handler.applies(rule)) {
+                handler.apply(self, rule, frame_data)?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! 处理序列控制相关的语义规则
 
-use apdl_core::ProtocolError;
+use apdl_core::{ProtocolError, SemanticRule};
 
 use crate::standard_units::frame_assembler::core::FrameAssembler;
 
@@ -16,7 +16,7 @@ impl FrameAssembler {
         description: &str,
         _frame_data: &mut [u8],
     ) -> Result<(), ProtocolError> {
-        println!(
+        log::debug!(
             "Applying sequence control rule: {description} with trigger {trigger_condition} and algorithm {algorithm}"
         );
 
@@ -39,6 +39,14 @@ impl FrameAssembler {
         Ok(())
     }
 
+    /// 字段是否由序列控制规则维护
+    pub fn is_sequence_controlled(&self, field_name: &str) -> bool {
+        self.semantic_rules.iter().any(|rule| {
+            matches!(rule, SemanticRule::SequenceControl { field_name: target, .. }
+                if target.trim_start_matches("field: ").trim() == field_name)
+        })
+    }
+
     /// 增加序列号
     fn increment_sequence_number(
         &mut self,
@@ -71,7 +79,7 @@ impl FrameAssembler {
                 self.field_values
                     .insert(clean_field_name.to_string(), new_bytes);
 
-                println!("Updated {field_name} from {current_value} to {new_value}");
+                log::debug!("Updated {field_name} from {current_value} to {new_value}");
             }
         }
 
@@ -114,6 +122,8 @@ mod tests {
             associate: vec![],
             desc: "Sequence Count Field".to_string(),
             pack_unpack_spec: None,
+            unit_label: None,
+            long_description: None,
         };
         assembler.add_field(seq_field);
 
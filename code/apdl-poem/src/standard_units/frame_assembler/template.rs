@@ -0,0 +1,63 @@
+//! 帧模板
+//!
+//! 保存已解析的不可变帧定义，按需创建共享字段布局的 FrameAssembler 实例
+
+use apdl_core::{SemanticRule, SyntaxUnit};
+use std::sync::Arc;
+
+use super::core::FrameAssembler;
+
+/// 帧模板（不可变的帧定义）
+///
+/// 保存一个不含字段值的原型组装器，字段定义、字段索引和语义规则通过Arc共享，
+/// 每个实例只持有自己的字段值
+#[derive(Clone)]
+pub struct FrameTemplate {
+    prototype: FrameAssembler,
+}
+
+impl FrameTemplate {
+    /// 根据字段定义和语义规则创建模板
+    pub fn new(fields: Vec<SyntaxUnit>, semantic_rules: Vec<SemanticRule>) -> Self {
+        let mut assembler = FrameAssembler::new();
+        for field in fields {
+            assembler.add_field(field);
+        }
+        for rule in semantic_rules {
+            assembler.add_semantic_rule(rule);
+        }
+        Self::from_assembler(&assembler)
+    }
+
+    /// 从已配置好的 FrameAssembler 提取帧定义（不包含字段值）
+    pub fn from_assembler(assembler: &FrameAssembler) -> Self {
+        let mut prototype = assembler.clone();
+        prototype.field_values.clear();
+        prototype.bit_field_values.clear();
+        prototype.routed_outputs.clear();
+        prototype.skipped_fields.clear();
+        Self { prototype }
+    }
+
+    /// 创建新的组装器实例，字段布局与模板共享，字段值为空
+    pub fn instance(&self) -> FrameAssembler {
+        self.prototype.clone()
+    }
+
+    /// 获取字段定义
+    pub fn fields(&self) -> &[SyntaxUnit] {
+        &self.prototype.fields
+    }
+
+    /// 获取语义规则
+    pub fn semantic_rules(&self) -> &[SemanticRule] {
+        &self.prototype.semantic_rules
+    }
+
+    /// 判断组装器实例是否与模板共享同一份帧定义
+    pub fn is_shared_with(&self, assembler: &FrameAssembler) -> bool {
+        Arc::ptr_eq(&self.prototype.fields, &assembler.fields)
+            && Arc::ptr_eq(&self.prototype.semantic_rules, &assembler.semantic_rules)
+            && Arc::ptr_eq(&self.prototype.field_index, &assembler.field_index)
+    }
+}
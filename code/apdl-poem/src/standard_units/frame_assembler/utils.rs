@@ -112,18 +112,7 @@ pub fn calculate_hash(data: &[u8]) -> u64 {
 
 /// 计算CRC16校验和
 pub fn calculate_crc16(data: &[u8]) -> u16 {
-    let mut crc: u16 = 0xFFFF;
-    for &byte in data {
-        crc ^= (byte as u16) << 8;
-        for _ in 0..8 {
-            if (crc & 0x8000) != 0 {
-                crc = (crc << 1) ^ 0x1021;
-            } else {
-                crc <<= 1;
-            }
-        }
-    }
-    crc
+    apdl_core::utils::calculate_ccsds_crc_table(data)
 }
 
 /// 计算简单校验和
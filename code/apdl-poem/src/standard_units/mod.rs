@@ -7,4 +7,6 @@ pub mod field_unit;
 pub mod frame_assembler;
 
 pub use field_unit::FieldUnit;
-pub use frame_assembler::FrameAssembler;
+pub use frame_assembler::{
+    CustomAlgorithmRegistry, FrameAssembler, FrameTemplate, RuleHandler,
+};
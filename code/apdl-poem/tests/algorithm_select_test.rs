@@ -0,0 +1,172 @@
+//! 条件算法选择测试
+//!
+//! 验证AlgorithmSelect规则根据模式字段在CRC16和CRC32之间选择校验算法，且只作用于其目标校验字段
+
+mod common;
+
+use apdl_core::{AlgorithmAst, ChecksumAlgorithm, ProtocolError, SemanticRule};
+use apdl_poem::{DslParserImpl, FrameAssembler};
+use common::assembler_from_dsl;
+
+const FRAME_DSL: &str = r#"
+field: mode; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "Mode"
+field: data; type: Uint32; length: 4byte; scope: layer(link); cover: entire_field; desc: "Data"
+field: fecf; type: Uint32; length: 4byte; scope: layer(link); cover: entire_field; alg: crc16; desc: "Frame check"
+rule: checksum_range(start: mode to data);
+rule: algorithm_select(mode: 0 => crc16, 1 => crc32);
+"#;
+
+fn create_assembler() -> FrameAssembler {
+    assembler_from_dsl(FRAME_DSL)
+}
+
+fn assemble_with_mode(assembler: &mut FrameAssembler, mode: u8) -> Vec<u8> {
+    assembler.set_field_value("mode", &[mode]).unwrap();
+    assembler
+        .set_field_value("data", &[0x12, 0x34, 0x56, 0x78])
+        .unwrap();
+    assembler.assemble_frame().unwrap()
+}
+
+#[test]
+fn test_parse_algorithm_select_rule() {
+    let rules = DslParserImpl::new()
+        .parse_semantic_rules(FRAME_DSL)
+        .unwrap();
+    assert!(rules.contains(&SemanticRule::AlgorithmSelect {
+        selector_field: "mode".to_string(),
+        target_field: None,
+        cases: vec![(0, AlgorithmAst::Crc16), (1, AlgorithmAst::Crc32)],
+    }));
+}
+
+#[test]
+fn test_mode_selects_crc16_or_crc32() {
+    let mut assembler = create_assembler();
+
+    let crc16_frame = assemble_with_mode(&mut assembler, 0);
+    let crc16 = assembler.calculate_crc16(&crc16_frame[..5]) as u32;
+    assert_eq!(&crc16_frame[5..], &crc16.to_be_bytes());
+    assert_eq!(&crc16_frame[5..7], &[0x00, 0x00]);
+
+    let crc32_frame = assemble_with_mode(&mut assembler, 1);
+    let crc32 = assembler.compute_checksum(&ChecksumAlgorithm::CRC32, &crc32_frame[..5]) as u32;
+    assert_eq!(&crc32_frame[5..], &crc32.to_be_bytes());
+    assert_ne!(&crc16_frame[5..], &crc32_frame[5..]);
+
+    // 解析时根据帧中的模式字段选择算法进行校验
+    assert!(assembler.verify_checksums(&crc16_frame).is_ok());
+    assert!(assembler.verify_checksums(&crc32_frame).is_ok());
+
+    // 模式字段被篡改后，按另一种算法校验失败
+    let mut tampered = crc16_frame.clone();
+    tampered[0] = 1;
+    assert!(matches!(
+        assembler.verify_checksums(&tampered),
+        Err(ProtocolError::ChecksumMismatch { ref field, .. }) if field == "fecf"
+    ));
+    // 存在算法选择规则时validate_checksum_rules委托给verify_checksums，同样报告校验字段
+    assert!(matches!(
+        assembler.validate_checksum_rules(&tampered),
+        Err(ProtocolError::ChecksumMismatch { ref field, .. }) if field == "fecf"
+    ));
+}
+
+#[test]
+fn test_unknown_mode_rejected() {
+    let mut assembler = create_assembler();
+    assembler.set_field_value("mode", &[7]).unwrap();
+    assert!(matches!(
+        assembler.assemble_frame(),
+        Err(ProtocolError::ValidationError(_))
+    ));
+}
+
+const TWO_CRC_DSL: &str = r#"
+field: mode; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "Mode"
+field: hdr; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Header"
+field: hcrc; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; alg: crc16; desc: "Header check"
+field: data; type: Uint32; length: 4byte; scope: layer(link); cover: entire_field; desc: "Data"
+field: fecf; type: Uint32; length: 4byte; scope: layer(link); cover: entire_field; desc: "Frame check"
+rule: algorithm_select(mode: 0 => crc16, 1 => crc32; target: fecf);
+rule: checksum_range(start: mode to data);
+rule: crc_range(start: mode to hdr);
+"#;
+
+#[test]
+fn test_algorithm_select_only_governs_its_target_checksum() {
+    let rules = DslParserImpl::new()
+        .parse_semantic_rules(TWO_CRC_DSL)
+        .unwrap();
+    assert!(rules.contains(&SemanticRule::AlgorithmSelect {
+        selector_field: "mode".to_string(),
+        target_field: Some("fecf".to_string()),
+        cases: vec![(0, AlgorithmAst::Crc16), (1, AlgorithmAst::Crc32)],
+    }));
+
+    let mut assembler = assembler_from_dsl(TWO_CRC_DSL);
+    assembler.set_field_value("hdr", &[0x0A, 0x0B]).unwrap();
+    for (mode, algorithm) in [(0, ChecksumAlgorithm::CRC16), (1, ChecksumAlgorithm::CRC32)] {
+        let frame = assemble_with_mode(&mut assembler, mode);
+
+        // 头部CRC16不受算法选择影响，且先于覆盖它的整帧校验计算
+        let hcrc = assembler.calculate_crc16(&frame[..3]);
+        assert_eq!(&frame[3..5], &hcrc.to_be_bytes(), "mode {mode}");
+        let fecf = assembler.compute_checksum(&algorithm, &frame[..9]) as u32;
+        assert_eq!(&frame[9..], &fecf.to_be_bytes(), "mode {mode}");
+
+        assert!(assembler.verify_checksums(&frame).is_ok());
+        let mut tampered = frame.clone();
+        tampered[3] ^= 0xFF;
+        assert!(assembler.verify_checksums(&tampered).is_err());
+    }
+}
+
+#[test]
+fn test_packed_bit_selector() {
+    let mut assembler = assembler_from_dsl(
+        r#"
+field: ver; type: Bit(4); length: 4bit; scope: layer(link); cover: entire_field; desc: "Version"
+field: mode; type: Bit(4); length: 4bit; scope: layer(link); cover: entire_field; desc: "Mode"
+field: data; type: Uint32; length: 4byte; scope: layer(link); cover: entire_field; desc: "Data"
+field: fecf; type: Uint32; length: 4byte; scope: layer(link); cover: entire_field; alg: crc16; desc: "Frame check"
+rule: checksum_range(start: ver to data);
+rule: algorithm_select(mode: 0 => crc16, 1 => crc32);
+"#,
+    );
+    assembler.set_bit_field_value("ver", 1).unwrap();
+    assembler.set_bit_field_value("mode", 1).unwrap();
+    assembler
+        .set_field_value("data", &[0x12, 0x34, 0x56, 0x78])
+        .unwrap();
+    let frame = assembler.assemble_frame().unwrap();
+
+    // 选择字段与版本字段共用首字节，只取其低4位
+    assert_eq!(frame[0], 0x11);
+    let crc32 = assembler.compute_checksum(&ChecksumAlgorithm::CRC32, &frame[..5]) as u32;
+    assert_eq!(&frame[5..], &crc32.to_be_bytes());
+    assert!(assembler.verify_checksums(&frame).is_ok());
+}
+
+#[test]
+fn test_little_endian_selector() {
+    let mut assembler = assembler_from_dsl(
+        r#"
+field: mode; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; endian: le; desc: "Mode"
+field: data; type: Uint32; length: 4byte; scope: layer(link); cover: entire_field; desc: "Data"
+field: fecf; type: Uint32; length: 4byte; scope: layer(link); cover: entire_field; alg: crc16; desc: "Frame check"
+rule: checksum_range(start: mode to data);
+rule: algorithm_select(mode: 0 => crc16, 1 => crc32);
+"#,
+    );
+    assembler.set_field_value("mode", &[1, 0]).unwrap();
+    assembler
+        .set_field_value("data", &[0x12, 0x34, 0x56, 0x78])
+        .unwrap();
+    let frame = assembler.assemble_frame().unwrap();
+
+    assert_eq!(&frame[..2], &[0x01, 0x00]);
+    let crc32 = assembler.compute_checksum(&ChecksumAlgorithm::CRC32, &frame[..6]) as u32;
+    assert_eq!(&frame[6..], &crc32.to_be_bytes());
+    assert!(assembler.verify_checksums(&frame).is_ok());
+}
@@ -0,0 +1,108 @@
+//! 字段对齐测试
+//!
+//! 验证对齐规则在字段前插入填充字节，解析时跳过填充字节
+
+mod common;
+
+use apdl_core::SemanticRule;
+use apdl_poem::{DslParserImpl, FrameAssembler};
+use common::{assembler_from_dsl, disassembler_from_dsl};
+
+const FRAME_DSL: &str = r#"
+field: header; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "Header"
+field: tag; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Tag"
+field: payload; type: Uint32; length: 4byte; scope: layer(link); cover: entire_field; desc: "Payload"
+field: trailer; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "Trailer"
+rule: alignment(field: payload; boundary: 4);
+"#;
+
+fn create_assembler() -> FrameAssembler {
+    let mut assembler = assembler_from_dsl(FRAME_DSL);
+    assembler.set_field_value("header", &[0x11]).unwrap();
+    assembler.set_field_value("tag", &[0x22, 0x33]).unwrap();
+    assembler
+        .set_field_value("payload", &[0xDE, 0xAD, 0xBE, 0xEF])
+        .unwrap();
+    assembler.set_field_value("trailer", &[0x44]).unwrap();
+    assembler
+}
+
+#[test]
+fn test_parse_alignment_rule() {
+    let rules = DslParserImpl::new()
+        .parse_semantic_rules(FRAME_DSL)
+        .unwrap();
+    assert_eq!(
+        rules,
+        vec![SemanticRule::Alignment {
+            field_name: "payload".to_string(),
+            boundary: 4,
+        }]
+    );
+}
+
+#[test]
+fn test_alignment_inserts_pad_bytes() {
+    let mut assembler = create_assembler();
+    let frame = assembler.assemble_frame().unwrap();
+
+    // header和tag共3字节，payload前插入1个填充字节对齐到4字节边界
+    assert_eq!(assembler.alignment_padding("payload", 3), 1);
+    assert_eq!(
+        frame,
+        vec![0x11, 0x22, 0x33, 0x00, 0xDE, 0xAD, 0xBE, 0xEF, 0x44]
+    );
+    assert_eq!(assembler.predicted_length().unwrap(), frame.len());
+    assert_eq!(assembler.get_field_position("payload").unwrap(), 4);
+    assert_eq!(assembler.get_field_position("trailer").unwrap(), 8);
+    assert_eq!(assembler.calculate_data_field_offset("payload").unwrap(), 4);
+    assert_eq!(
+        assembler.get_field_bit_position("payload").unwrap(),
+        (32, 32)
+    );
+}
+
+#[test]
+fn test_alignment_pad_count_follows_offset() {
+    let mut assembler = create_assembler();
+    assembler.add_semantic_rule(SemanticRule::Alignment {
+        field_name: "tag".to_string(),
+        boundary: 8,
+    });
+    let frame = assembler.assemble_frame().unwrap();
+
+    // tag对齐到8字节边界插入7个填充字节，payload此时已位于偏移10，再插入2个
+    assert_eq!(frame.len(), 1 + 7 + 2 + 2 + 4 + 1);
+    assert_eq!(&frame[1..8], &[0x00; 7]);
+    assert_eq!(&frame[8..10], &[0x22, 0x33]);
+    assert_eq!(&frame[10..12], &[0x00; 2]);
+    assert_eq!(&frame[12..16], &[0xDE, 0xAD, 0xBE, 0xEF]);
+}
+
+#[test]
+fn test_parse_frame_skips_pad_bytes() {
+    let mut assembler = create_assembler();
+    let frame = assembler.assemble_frame().unwrap();
+
+    let parsed = assembler.parse_frame(&frame).unwrap();
+    assert_eq!(
+        parsed,
+        vec![
+            ("header".to_string(), vec![0x11]),
+            ("tag".to_string(), vec![0x22, 0x33]),
+            ("payload".to_string(), vec![0xDE, 0xAD, 0xBE, 0xEF]),
+            ("trailer".to_string(), vec![0x44]),
+        ]
+    );
+
+    let disassembler = disassembler_from_dsl(FRAME_DSL);
+    let fields = disassembler.parse_frame_named(&frame).unwrap();
+    assert_eq!(fields[2].name, "payload");
+    assert_eq!(fields[2].value, vec![0xDE, 0xAD, 0xBE, 0xEF]);
+    assert_eq!(fields[2].bit_offset, 32);
+    assert_eq!(fields[3].value, vec![0x44]);
+    assert_eq!(
+        disassembler.get_field_bit_position("payload").unwrap(),
+        (32, 32)
+    );
+}
@@ -0,0 +1,70 @@
+//! 批量组装功能测试
+//!
+//! 验证assemble_batch的结果与逐帧组装结果一致
+
+mod common;
+
+use apdl_poem::FrameAssembler;
+use common::assembler_from_dsl;
+use std::collections::HashMap;
+
+const FRAME_DSL: &str = r#"
+field: sync; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; constraint: fixed(0xEB90); desc: "Sync"
+field: version; type: Bit(3); length: 3bit; scope: layer(link); cover: entire_field; desc: "Version"
+field: apid; type: Bit(5); length: 5bit; scope: layer(link); cover: entire_field; desc: "APID"
+field: counter; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Counter"
+field: data; type: RawData; length: dynamic; scope: layer(application); cover: entire_field; desc: "Data"
+"#;
+
+fn create_assembler() -> FrameAssembler {
+    assembler_from_dsl(FRAME_DSL)
+}
+
+fn value_set(i: usize) -> HashMap<String, Vec<u8>> {
+    let mut values = HashMap::new();
+    values.insert("version".to_string(), vec![(i % 8) as u8]);
+    values.insert("apid".to_string(), vec![(i % 32) as u8]);
+    values.insert("counter".to_string(), (i as u16).to_be_bytes().to_vec());
+    values.insert("data".to_string(), vec![i as u8; 1 + i % 4]);
+    values
+}
+
+#[test]
+fn test_assemble_batch_matches_single_assemble() {
+    let value_sets: Vec<_> = (0..10_000).map(value_set).collect();
+
+    let mut assembler = create_assembler();
+    let frames = assembler.assemble_batch(&value_sets).unwrap();
+    assert_eq!(frames.len(), value_sets.len());
+
+    for (values, frame) in value_sets.iter().zip(&frames) {
+        let mut single = create_assembler();
+        for (field_name, value) in values {
+            single.set_field_value(field_name, value).unwrap();
+        }
+        assert_eq!(&single.assemble_frame().unwrap(), frame);
+    }
+
+    assert_eq!(frames[1], vec![0xEB, 0x90, 0x21, 0x00, 0x01, 0x01, 0x01]);
+}
+
+#[test]
+fn test_assemble_batch_restores_field_values() {
+    let mut assembler = create_assembler();
+    assembler.set_field_value("counter", &[0x12, 0x34]).unwrap();
+
+    let frames = assembler
+        .assemble_batch(&[HashMap::new(), value_set(5)])
+        .unwrap();
+
+    // 未给出的字段沿用组装器原有的字段值
+    assert_eq!(&frames[0][3..5], &[0x12, 0x34]);
+    assert_eq!(&frames[1][3..5], &[0x00, 0x05]);
+
+    // 批量组装结束后恢复原有字段值
+    assert_eq!(
+        assembler.get_field_value("counter").unwrap(),
+        vec![0x12, 0x34]
+    );
+    assert!(!assembler.field_values.contains_key("data"));
+}
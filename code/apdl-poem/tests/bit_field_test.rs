@@ -2,7 +2,9 @@
 //!
 //! 验证FrameAssembler对bit级别字段的支持
 
-use apdl_core::{Constraint, CoverDesc, LengthDesc, LengthUnit, ScopeDesc, SyntaxUnit, UnitType};
+use apdl_core::{
+    BitNumbering, Constraint, CoverDesc, LengthDesc, LengthUnit, ScopeDesc, SyntaxUnit, UnitType,
+};
 use apdl_poem::standard_units::frame_assembler::core::FrameAssembler;
 
 #[test]
@@ -22,6 +24,8 @@ fn test_bit_field_handling() {
         associate: vec![],
         desc: "1-bit flag field".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let bit_field_2 = SyntaxUnit {
@@ -38,6 +42,8 @@ fn test_bit_field_handling() {
         associate: vec![],
         desc: "1-bit flag field".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let bit_field_3 = SyntaxUnit {
@@ -54,6 +60,8 @@ fn test_bit_field_handling() {
         associate: vec![],
         desc: "3-bit field".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     // 2. 创建FrameAssembler并添加字段
@@ -108,6 +116,8 @@ fn test_bit_field_with_explicit_values() {
         associate: vec![],
         desc: "4-bit control field".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let mut assembler = FrameAssembler::new();
@@ -146,6 +156,8 @@ fn test_mixed_bit_and_byte_fields() {
         associate: vec![],
         desc: "1-bit flag 1".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let byte_field = SyntaxUnit {
@@ -162,6 +174,8 @@ fn test_mixed_bit_and_byte_fields() {
         associate: vec![],
         desc: "1-byte data".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let bit_field_2 = SyntaxUnit {
@@ -178,6 +192,8 @@ fn test_mixed_bit_and_byte_fields() {
         associate: vec![],
         desc: "2-bit flag 2".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let bit_field_3 = SyntaxUnit {
@@ -194,6 +210,8 @@ fn test_mixed_bit_and_byte_fields() {
         associate: vec![],
         desc: "5-bit flag 3".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let mut assembler = FrameAssembler::new();
@@ -235,3 +253,97 @@ fn test_mixed_bit_and_byte_fields() {
     // 测试通过,证明frame assembler按添加顺序正确打包bit字段
     println!("Expected: [0x80, 0xFF, 0x9E], Actual: {frame:?}");
 }
+
+#[test]
+fn test_bit_numbering_msb0_vs_lsb0() {
+    // 同样的字段定义：mode(2bit) + counter(6bit)
+    let make_field = |name: &str, bits: u8| {
+        SyntaxUnit::new(
+            name,
+            UnitType::Bit(bits),
+            LengthDesc {
+                size: bits as usize,
+                unit: LengthUnit::Bit,
+            },
+            ScopeDesc::Global("test".to_string()),
+        )
+    };
+
+    let mut assembler = FrameAssembler::new();
+    assembler.add_field(make_field("mode", 2));
+    assembler.add_field(make_field("counter", 6));
+    assembler.set_field_value("mode", &[0x01]).unwrap();
+    assembler.set_field_value("counter", &[0x27]).unwrap();
+
+    // MSB-0：01 + 100111 => 0110_0111
+    let msb0_frame = assembler.assemble_frame().unwrap();
+    assert_eq!(msb0_frame, vec![0x67]);
+
+    // LSB-0：mode位于低2位，counter位于高6位 => 1001_1101
+    assembler.set_bit_numbering(BitNumbering::Lsb0);
+    let lsb0_frame = assembler.assemble_frame().unwrap();
+    assert_eq!(lsb0_frame, vec![0x9D]);
+}
+
+/// 按指定位编号先组帧再拆帧，返回帧和拆出的各字段值
+fn round_trip(
+    numbering: BitNumbering,
+    fields: &[(&str, u8, &[u8])],
+) -> (Vec<u8>, std::collections::HashMap<String, Vec<u8>>) {
+    let make_field = |name: &str, bits: u8| {
+        SyntaxUnit::new(
+            name,
+            UnitType::Bit(bits),
+            LengthDesc {
+                size: bits as usize,
+                unit: LengthUnit::Bit,
+            },
+            ScopeDesc::Global("test".to_string()),
+        )
+    };
+
+    let mut assembler = FrameAssembler::new();
+    assembler.set_bit_numbering(numbering);
+    let mut disassembler = apdl_lsk::FrameDisassembler::new();
+    disassembler.set_bit_numbering(numbering);
+    for &(name, bits, value) in fields {
+        assembler.add_field(make_field(name, bits));
+        assembler.set_field_value(name, value).unwrap();
+        disassembler.add_field(make_field(name, bits));
+    }
+    let frame = assembler.assemble_frame().unwrap();
+    let parsed = disassembler.disassemble_frame(&frame).unwrap();
+    (frame, parsed)
+}
+
+#[test]
+fn test_lsb0_multi_byte_field_round_trip() {
+    // LSB-0：mode占首字节低4位，id的低4位在首字节高4位，高8位在第二个字节
+    let (frame, fields) = round_trip(
+        BitNumbering::Lsb0,
+        &[("mode", 4, &[0x05]), ("id", 12, &[0x0A, 0xBC])],
+    );
+    assert_eq!(frame, vec![0xC5, 0xAB]);
+    assert_eq!(fields["mode"], vec![0x05]);
+    assert_eq!(fields["id"], vec![0x0A, 0xBC]);
+}
+
+#[test]
+fn test_wide_bit_field_keeps_high_bits() {
+    let wide: &[u8] = &[0xFE, 0xDC, 0xBA, 0x98, 0x76, 0x54, 0x32, 0x10];
+    for numbering in [BitNumbering::Msb0, BitNumbering::Lsb0] {
+        // 64bit字段从第3个bit开始，跨越9个字节，不能丢失高位
+        let (frame, fields) = round_trip(
+            numbering,
+            &[
+                ("mode", 3, &[0x05]),
+                ("wide", 64, wide),
+                ("flag", 5, &[0x11]),
+            ],
+        );
+        assert_eq!(frame.len(), 9, "{numbering:?}");
+        assert_eq!(fields["mode"], vec![0x05], "{numbering:?}");
+        assert_eq!(fields["wide"], wide, "{numbering:?}");
+        assert_eq!(fields["flag"], vec![0x11], "{numbering:?}");
+    }
+}
@@ -27,6 +27,8 @@ fn test_ccsds_space_packet_bit_packing() {
         associate: vec![],
         desc: "数据包版本号".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let pkt_type = SyntaxUnit {
@@ -43,6 +45,8 @@ fn test_ccsds_space_packet_bit_packing() {
         associate: vec![],
         desc: "包类型".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let sec_hdr_flag = SyntaxUnit {
@@ -59,6 +63,8 @@ fn test_ccsds_space_packet_bit_packing() {
         associate: vec![],
         desc: "二级头标志".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let apid = SyntaxUnit {
@@ -75,6 +81,8 @@ fn test_ccsds_space_packet_bit_packing() {
         associate: vec![],
         desc: "应用进程ID".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let seq_flags = SyntaxUnit {
@@ -91,6 +99,8 @@ fn test_ccsds_space_packet_bit_packing() {
         associate: vec![],
         desc: "序列标志".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let pkt_seq_cnt = SyntaxUnit {
@@ -107,6 +117,8 @@ fn test_ccsds_space_packet_bit_packing() {
         associate: vec![],
         desc: "包序列计数".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let mut assembler = FrameAssembler::new();
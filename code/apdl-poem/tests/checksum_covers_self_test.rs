@@ -0,0 +1,97 @@
+//! 校验字段自身覆盖测试
+//!
+//! 验证校验和规则的`covers_self`选项：默认将范围内（置零的）校验字段计入计算，
+//! 关闭时排除校验字段，组装和验证使用同一范围
+
+mod common;
+
+use apdl_core::{ChecksumAlgorithm, SemanticRule};
+use apdl_poem::{DslParserImpl, FrameAssembler};
+use common::assembler_from_dsl;
+
+const FIELDS_DSL: &str = r#"
+field: header; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Header"
+field: data; type: Uint32; length: 4byte; scope: layer(link); cover: entire_field; desc: "Data"
+field: fecf; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; alg: crc16; desc: "Frame check"
+"#;
+
+fn create_assembler(rule: &str) -> FrameAssembler {
+    let mut assembler = assembler_from_dsl(&format!("{FIELDS_DSL}{rule}\n"));
+    assembler.set_field_value("header", &[0x1A, 0xCF]).unwrap();
+    assembler
+        .set_field_value("data", &[0x01, 0x02, 0x03, 0x04])
+        .unwrap();
+    assembler
+}
+
+#[test]
+fn test_parse_covers_self_option() {
+    let parser = DslParserImpl::new();
+    let rules = parser
+        .parse_semantic_rules("rule: crc_range(start: header to fecf; covers_self: false);")
+        .unwrap();
+    assert_eq!(
+        rules,
+        vec![SemanticRule::ChecksumRange {
+            algorithm: ChecksumAlgorithm::CRC16,
+            start_field: "header".to_string(),
+            end_field: "fecf".to_string(),
+            covers_self: false,
+        }]
+    );
+
+    let rules = parser
+        .parse_semantic_rules("rule: crc_range(start: header to fecf);")
+        .unwrap();
+    assert!(matches!(
+        rules[0],
+        SemanticRule::ChecksumRange {
+            covers_self: true,
+            ..
+        }
+    ));
+
+    assert!(parser
+        .parse_semantic_rules("rule: crc_range(start: header to fecf; covers_self: maybe);")
+        .is_err());
+}
+
+#[test]
+fn test_range_before_checksum_field() {
+    let mut assembler = create_assembler("rule: crc_range(start: header to data);");
+    let frame = assembler.assemble_frame().unwrap();
+
+    // CRC-16/CCITT-FALSE(1A CF 01 02 03 04)
+    assert_eq!(&frame[6..], &[0xC9, 0xD4]);
+    assert!(assembler.validate_checksum_rules(&frame).is_ok());
+}
+
+#[test]
+fn test_checksum_covers_zeroed_self_by_default() {
+    let mut assembler = create_assembler("rule: crc_range(start: header to fecf);");
+    let frame = assembler.assemble_frame().unwrap();
+
+    // CRC-16/CCITT-FALSE(1A CF 01 02 03 04 00 00)
+    assert_eq!(&frame[6..], &[0x27, 0x35]);
+
+    // 验证时校验字段已写入校验和，仍按全零计算
+    let input = assembler
+        .checksum_input(&frame, "header", "fecf", Some(2), true)
+        .unwrap();
+    assert_eq!(input, vec![0x1A, 0xCF, 0x01, 0x02, 0x03, 0x04, 0x00, 0x00]);
+    assert!(assembler.validate_checksum_rules(&frame).is_ok());
+}
+
+#[test]
+fn test_checksum_excludes_self_when_disabled() {
+    let mut assembler =
+        create_assembler("rule: crc_range(start: header to fecf; covers_self: false);");
+    let frame = assembler.assemble_frame().unwrap();
+
+    assert_eq!(&frame[6..], &[0xC9, 0xD4]);
+    let input = assembler
+        .checksum_input(&frame, "header", "fecf", Some(2), false)
+        .unwrap();
+    assert_eq!(input, vec![0x1A, 0xCF, 0x01, 0x02, 0x03, 0x04]);
+    assert!(assembler.validate_checksum_rules(&frame).is_ok());
+}
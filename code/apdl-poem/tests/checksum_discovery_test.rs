@@ -0,0 +1,120 @@
+//! 校验字段自动发现测试
+//!
+//! 验证校验和范围规则按约定定位校验字段，并在存在歧义或相邻字段无法容纳校验值时报错
+
+mod common;
+
+use apdl_core::{ChecksumAlgorithm, ProtocolError, SemanticRule};
+use apdl_poem::FrameAssembler;
+use common::assembler_from_dsl;
+
+fn create_assembler(dsl: &str) -> FrameAssembler {
+    let mut assembler = assembler_from_dsl(dsl);
+    assembler.add_semantic_rule(SemanticRule::ChecksumRange {
+        algorithm: ChecksumAlgorithm::CRC16,
+        start_field: "header".to_string(),
+        end_field: "data".to_string(),
+        covers_self: true,
+    });
+    assembler.set_field_value("header", &[0x1A, 0xCF]).unwrap();
+    assembler.set_field_value("data", &[0x01, 0x02]).unwrap();
+    assembler
+}
+
+#[test]
+fn test_discovers_trailing_fecf_field() {
+    // fecf未声明alg，按紧随校验范围之后的约定定位
+    let mut assembler = create_assembler(
+        r#"
+        field: header; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Header"
+        field: data; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Data"
+        field: fecf; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Frame check"
+        "#,
+    );
+
+    let index = assembler
+        .resolve_checksum_field(&ChecksumAlgorithm::CRC16, "data")
+        .unwrap();
+    assert_eq!(index, Some(2));
+
+    let frame = assembler.assemble_frame().unwrap();
+    let crc = assembler.compute_checksum(&ChecksumAlgorithm::CRC16, &frame[..4]) as u16;
+    assert_eq!(&frame[4..], &crc.to_be_bytes());
+}
+
+#[test]
+fn test_ambiguous_checksum_fields_error() {
+    let dsl = r#"
+        field: header; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Header"
+        field: data; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Data"
+        field: spare; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Spare"
+        field: packet_crc; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; alg: crc16; desc: "Packet check"
+        field: fecf; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; alg: crc16; desc: "Frame check"
+        "#;
+    let mut assembler = create_assembler(dsl);
+
+    // 两个匹配字段都不紧随校验范围之后
+    match assembler.assemble_frame() {
+        Err(ProtocolError::InvalidFieldDefinition(msg)) => {
+            assert!(msg.contains("packet_crc") && msg.contains("fecf"));
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+
+    // 显式指定校验字段后消除歧义
+    assembler.set_checksum_field("data", "fecf");
+    let frame = assembler.assemble_frame().unwrap();
+    let crc = assembler.compute_checksum(&ChecksumAlgorithm::CRC16, &frame[..4]) as u16;
+    assert_eq!(&frame[8..], &crc.to_be_bytes());
+    assert_eq!(&frame[6..8], &[0x00, 0x00]);
+}
+
+#[test]
+fn test_same_algorithm_checksums_follow_their_ranges() {
+    let mut assembler = assembler_from_dsl(
+        r#"
+        field: hdr; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Header"
+        field: hcrc; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; alg: crc16; desc: "Header check"
+        field: data; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Data"
+        field: dcrc; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; alg: crc16; desc: "Data check"
+        rule: crc_range(start: hdr to hdr);
+        rule: crc_range(start: data to data);
+        "#,
+    );
+    assembler.set_field_value("hdr", &[0x1A, 0xCF]).unwrap();
+    assembler.set_field_value("data", &[0x01, 0x02]).unwrap();
+
+    // 两个CRC16字段各自紧随其校验范围，不构成歧义
+    assert_eq!(
+        assembler.resolve_checksum_field(&ChecksumAlgorithm::CRC16, "hdr"),
+        Ok(Some(1))
+    );
+    assert_eq!(
+        assembler.resolve_checksum_field(&ChecksumAlgorithm::CRC16, "data"),
+        Ok(Some(3))
+    );
+
+    let frame = assembler.assemble_frame().unwrap();
+    let hcrc = assembler.compute_checksum(&ChecksumAlgorithm::CRC16, &frame[..2]) as u16;
+    let dcrc = assembler.compute_checksum(&ChecksumAlgorithm::CRC16, &frame[4..6]) as u16;
+    assert_eq!(&frame[2..4], &hcrc.to_be_bytes());
+    assert_eq!(&frame[6..], &dcrc.to_be_bytes());
+    assert!(assembler.validate_checksum_rules(&frame).is_ok());
+}
+
+#[test]
+fn test_incompatible_trailing_field_errors() {
+    // 紧随校验范围之后的字段既未声明alg，宽度也放不下CRC16
+    let dsl = r#"
+        field: header; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Header"
+        field: data; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Data"
+        field: flags; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "Flags"
+        "#;
+    let mut assembler = create_assembler(dsl);
+
+    match assembler.resolve_checksum_field(&ChecksumAlgorithm::CRC16, "data") {
+        Err(ProtocolError::InvalidFieldDefinition(msg)) => assert!(msg.contains("flags")),
+        other => panic!("unexpected result: {other:?}"),
+    }
+    assert!(assembler.assemble_frame().is_err());
+}
@@ -0,0 +1,80 @@
+//! 校验和写入位置测试
+//!
+//! 验证校验和按校验字段声明的宽度和字节序写入帧
+
+mod common;
+
+use apdl_core::{ByteOrder, ChecksumAlgorithm, ProtocolError, SemanticRule};
+use apdl_poem::FrameAssembler;
+use common::assembler_from_dsl;
+
+fn create_assembler(
+    fecf_type: &str,
+    fecf_len: usize,
+    alg: &str,
+    algorithm: ChecksumAlgorithm,
+) -> FrameAssembler {
+    let dsl = format!(
+        r#"
+        field: header; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Header"
+        field: data; type: Uint32; length: 4byte; scope: layer(link); cover: entire_field; desc: "Data"
+        field: fecf; type: {fecf_type}; length: {fecf_len}byte; scope: layer(link); cover: entire_field; alg: {alg}; desc: "Frame check"
+        "#
+    );
+
+    let mut assembler = assembler_from_dsl(&dsl);
+    assembler.add_semantic_rule(SemanticRule::ChecksumRange {
+        algorithm,
+        start_field: "header".to_string(),
+        end_field: "data".to_string(),
+        covers_self: true,
+    });
+    assembler.set_field_value("header", &[0x1A, 0xCF]).unwrap();
+    assembler
+        .set_field_value("data", &[0x01, 0x02, 0x03, 0x04])
+        .unwrap();
+    assembler
+}
+
+#[test]
+fn test_crc16_into_little_endian_field() {
+    let mut assembler = create_assembler("Uint16", 2, "crc16", ChecksumAlgorithm::CRC16);
+    assembler.set_field_byte_order("fecf", ByteOrder::LittleEndian);
+
+    let frame = assembler.assemble_frame().unwrap();
+    let crc = assembler.calculate_crc16(&frame[..6]);
+    assert_eq!(&frame[6..], &crc.to_le_bytes());
+
+    // 对外读取时按字段字节序返回
+    assert_eq!(
+        assembler.get_field_value("fecf").unwrap(),
+        crc.to_le_bytes()
+    );
+}
+
+#[test]
+fn test_crc32_into_big_endian_field() {
+    let mut assembler = create_assembler("Uint32", 4, "crc32", ChecksumAlgorithm::CRC32);
+
+    let frame = assembler.assemble_frame().unwrap();
+    let crc = assembler.compute_checksum(&ChecksumAlgorithm::CRC32, &frame[..6]) as u32;
+    assert_eq!(&frame[6..], &crc.to_be_bytes());
+}
+
+#[test]
+fn test_crc16_zero_extended_into_wider_field() {
+    let mut assembler = create_assembler("Uint32", 4, "crc16", ChecksumAlgorithm::CRC16);
+    let frame = assembler.assemble_frame().unwrap();
+    let crc = assembler.calculate_crc16(&frame[..6]) as u32;
+    assert_eq!(&frame[6..], &crc.to_be_bytes());
+}
+
+#[test]
+fn test_checksum_too_wide_for_field() {
+    let mut assembler = create_assembler("Uint16", 2, "crc32", ChecksumAlgorithm::CRC32);
+
+    assert!(matches!(
+        assembler.assemble_frame(),
+        Err(ProtocolError::ValueOutOfRange(_))
+    ));
+}
@@ -0,0 +1,117 @@
+//! 校验和回写往返测试
+//!
+//! 验证组装时各校验算法的结果按校验字段宽度以大端写入帧，且组装结果通过校验和验证
+
+mod common;
+
+use apdl_core::{ChecksumAlgorithm, ProtocolError, SemanticRule};
+use apdl_poem::FrameAssembler;
+use common::assembler_from_dsl;
+
+fn ccsds_like_assembler(
+    alg: &str,
+    fecf_len: usize,
+    algorithm: ChecksumAlgorithm,
+) -> FrameAssembler {
+    let dsl = format!(
+        r#"
+        field: sync_flag; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; constraint: fixed(0xEB90); desc: "Sync flag"
+        field: apid; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; constraint: range(0..=2047); desc: "APID"
+        field: seq_count; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Sequence count"
+        field: data_field; type: RawData; length: dynamic; scope: layer(link); cover: entire_field; desc: "Data"
+        field: fecf; type: Uint{bits}; length: {fecf_len}byte; scope: layer(link); cover: entire_field; alg: {alg}; desc: "Frame check"
+        "#,
+        bits = fecf_len * 8
+    );
+
+    let mut assembler = assembler_from_dsl(&dsl);
+    assembler.add_semantic_rule(SemanticRule::ChecksumRange {
+        algorithm,
+        start_field: "sync_flag".to_string(),
+        end_field: "data_field".to_string(),
+        covers_self: true,
+    });
+    assembler
+        .set_field_value("sync_flag", &[0xEB, 0x90])
+        .unwrap();
+    assembler.set_field_value("apid", &[0x01, 0x23]).unwrap();
+    assembler
+        .set_field_value("seq_count", &[0x00, 0x2A])
+        .unwrap();
+    assembler
+        .set_field_value("data_field", &[0xDE, 0xAD, 0xBE, 0xEF, 0x55])
+        .unwrap();
+    assembler
+}
+
+fn assert_round_trip(alg: &str, fecf_len: usize, algorithm: ChecksumAlgorithm) {
+    let mut assembler = ccsds_like_assembler(alg, fecf_len, algorithm.clone());
+    let frame = assembler.assemble_frame().unwrap();
+    assert_eq!(frame.len(), 11 + fecf_len);
+
+    let checksum = assembler.compute_checksum(&algorithm, &frame[..11]);
+    let expected = &checksum.to_be_bytes()[8 - fecf_len..];
+    assert_eq!(&frame[11..], expected, "{alg} checksum bytes");
+    assert_eq!(assembler.get_field_value("fecf").unwrap(), expected);
+
+    assembler
+        .validate_checksum_rule(&frame, &algorithm, "sync_flag", "data_field")
+        .unwrap();
+}
+
+#[test]
+fn test_crc16_round_trip() {
+    assert_round_trip("crc16", 2, ChecksumAlgorithm::CRC16);
+
+    // CRC-16/CCITT-FALSE参考值
+    let mut assembler = ccsds_like_assembler("crc16", 2, ChecksumAlgorithm::CRC16);
+    assert_eq!(assembler.calculate_crc16(b"123456789"), 0x29B1);
+    let frame = assembler.assemble_frame().unwrap();
+    assert_ne!(&frame[11..], &[0x00, 0x00]);
+}
+
+#[test]
+fn test_crc32_round_trip() {
+    assert_round_trip("crc32", 4, ChecksumAlgorithm::CRC32);
+}
+
+#[test]
+fn test_crc15_round_trip() {
+    assert_round_trip("crc15", 2, ChecksumAlgorithm::CRC15);
+}
+
+#[test]
+fn test_xor_round_trip() {
+    assert_round_trip("xor_sum", 1, ChecksumAlgorithm::XOR);
+}
+
+#[test]
+fn test_corrupted_frame_reports_mismatch() {
+    let mut assembler = ccsds_like_assembler("crc16", 2, ChecksumAlgorithm::CRC16);
+    let frame = assembler.assemble_frame().unwrap();
+    let stored = u16::from_be_bytes([frame[11], frame[12]]) as u64;
+
+    let mut corrupted = frame.clone();
+    corrupted[7] ^= 0xFF;
+    let expected = assembler.compute_checksum(&ChecksumAlgorithm::CRC16, &corrupted[..11]);
+    assert_eq!(
+        assembler.validate_checksum_rule(
+            &corrupted,
+            &ChecksumAlgorithm::CRC16,
+            "sync_flag",
+            "data_field"
+        ),
+        Err(ProtocolError::ChecksumMismatch {
+            field: "fecf".to_string(),
+            expected,
+            actual: stored,
+        })
+    );
+
+    // 解析时同样拒绝损坏的帧
+    assert!(assembler.parse_frame(&frame).is_ok());
+    assert!(matches!(
+        assembler.parse_frame(&corrupted),
+        Err(ProtocolError::ChecksumMismatch { .. })
+    ));
+}
@@ -0,0 +1,70 @@
+//! 校验范围作用域测试
+//!
+//! 验证端到端校验字段可覆盖多层字段，层范围的校验字段在严格作用域检查下拒绝覆盖其他层字段
+
+mod common;
+
+use apdl_core::{ChecksumAlgorithm, ConstraintMode, ProtocolError, ScopeDesc};
+use apdl_poem::FrameAssembler;
+use common::assembler_from_dsl;
+
+fn create_assembler(fecf_scope: &str) -> FrameAssembler {
+    let dsl = format!(
+        r#"
+        field: vcid; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Link header"
+        field: apid; type: Uint16; length: 2byte; scope: layer(net); cover: entire_field; desc: "Packet header"
+        field: payload; type: RawData; length: dynamic; scope: layer(application); cover: entire_field; desc: "Payload"
+        field: fecf; type: Uint16; length: 2byte; scope: {fecf_scope}; cover: entire_field; alg: crc16; desc: "End-to-end check"
+        rule: crc_range(start: vcid to payload);
+        "#
+    );
+
+    let mut assembler = assembler_from_dsl(&dsl);
+    assembler.set_field_value("vcid", &[0x01, 0x23]).unwrap();
+    assembler.set_field_value("apid", &[0x07, 0xFF]).unwrap();
+    assembler
+        .set_field_value("payload", &[0xA1, 0xA2, 0xA3])
+        .unwrap();
+    assembler.set_strict_checksum_scope(true);
+    assembler
+}
+
+#[test]
+fn test_global_crc_spans_two_layers() {
+    for scope in ["global(end2end)", "cross_layer(net→link)"] {
+        let mut assembler = create_assembler(scope);
+        let frame = assembler.assemble_frame().unwrap();
+
+        // 校验覆盖链路层和网络层字段以及负载
+        let crc = assembler.compute_checksum(&ChecksumAlgorithm::CRC16, &frame[..7]) as u16;
+        assert_eq!(&frame[7..], &crc.to_be_bytes(), "scope {scope}");
+    }
+
+    let assembler = create_assembler("cross_layer(net→link)");
+    assert_eq!(
+        assembler.fields[3].scope,
+        ScopeDesc::CrossLayer("net".to_string(), "link".to_string())
+    );
+}
+
+#[test]
+fn test_layer_crc_over_other_layer_fields() {
+    let mut assembler = create_assembler("layer(link)");
+    match assembler.assemble_frame() {
+        Err(ProtocolError::ValidationError(msg)) => {
+            assert!(msg.contains("apid") && !msg.contains("payload"));
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+
+    // 作用域检查独立于约束检查模式
+    assembler.set_constraint_mode(ConstraintMode::Lenient);
+    assert!(assembler.assemble_frame().is_err());
+
+    // 关闭严格作用域检查后仅记录警告，仍写入校验和
+    assembler.set_constraint_mode(ConstraintMode::Strict);
+    assembler.set_strict_checksum_scope(false);
+    let frame = assembler.assemble_frame().unwrap();
+    let crc = assembler.compute_checksum(&ChecksumAlgorithm::CRC16, &frame[..7]) as u16;
+    assert_eq!(&frame[7..], &crc.to_be_bytes());
+}
@@ -0,0 +1,31 @@
+//! 集成测试共用的辅助函数
+#![allow(dead_code)]
+
+use apdl_lsk::FrameDisassembler;
+use apdl_poem::{DslParserImpl, FrameAssembler};
+
+/// 由DSL定义创建组装器，添加其中的全部字段和语义规则
+pub fn assembler_from_dsl(dsl: &str) -> FrameAssembler {
+    let parser = DslParserImpl::new();
+    let mut assembler = FrameAssembler::new();
+    for unit in parser.parse_protocol_structure(dsl).unwrap() {
+        assembler.add_field(unit);
+    }
+    for rule in parser.parse_semantic_rules(dsl).unwrap() {
+        assembler.add_semantic_rule(rule);
+    }
+    assembler
+}
+
+/// 由DSL定义创建拆包器，添加其中的全部字段和语义规则
+pub fn disassembler_from_dsl(dsl: &str) -> FrameDisassembler {
+    let parser = DslParserImpl::new();
+    let mut disassembler = FrameDisassembler::new();
+    for unit in parser.parse_protocol_structure(dsl).unwrap() {
+        disassembler.add_field(unit);
+    }
+    for rule in parser.parse_semantic_rules(dsl).unwrap() {
+        disassembler.add_semantic_rule(rule);
+    }
+    disassembler
+}
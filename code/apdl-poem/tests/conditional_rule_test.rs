@@ -0,0 +1,98 @@
+//! 条件规则测试
+//!
+//! 验证条件规则按字段值决定可选字段是否参与组帧
+
+mod common;
+
+use apdl_core::ProtocolError;
+use apdl_poem::FrameAssembler;
+use common::assembler_from_dsl;
+
+const FRAME_DSL: &str = r#"
+field: pkt_type; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "Packet type"
+field: count; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "Sample count"
+field: sec_header; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Optional secondary header"
+field: extra; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "Optional extra byte"
+field: data; type: RawData; length: 2byte; scope: layer(link); cover: entire_field; desc: "Data"
+"#;
+
+const CONDITIONAL_RULES: &str = r#"
+rule: conditional(sec_header if pkt_type.value == 0x01);
+rule: conditional(extra if count.value > 10);
+"#;
+
+fn create_assembler(pkt_type: u8, count: u8) -> FrameAssembler {
+    let mut assembler = assembler_from_dsl(&format!("{FRAME_DSL}{CONDITIONAL_RULES}"));
+    assembler.set_field_value("pkt_type", &[pkt_type]).unwrap();
+    assembler.set_field_value("count", &[count]).unwrap();
+    assembler
+        .set_field_value("sec_header", &[0xBE, 0xEF])
+        .unwrap();
+    assembler.set_field_value("extra", &[0x77]).unwrap();
+    assembler.set_field_value("data", &[0xD0, 0xD1]).unwrap();
+    assembler
+}
+
+#[test]
+fn test_conditions_true_include_optional_fields() {
+    let mut assembler = create_assembler(0x01, 11);
+    let frame = assembler.assemble_frame().unwrap();
+    assert_eq!(frame, vec![0x01, 0x0B, 0xBE, 0xEF, 0x77, 0xD0, 0xD1]);
+}
+
+#[test]
+fn test_conditions_false_skip_optional_fields() {
+    let mut assembler = create_assembler(0x02, 10);
+    let frame = assembler.assemble_frame().unwrap();
+    assert_eq!(frame, vec![0x02, 0x0A, 0xD0, 0xD1]);
+    assert_eq!(assembler.get_field_position("data").unwrap(), 2);
+}
+
+#[test]
+fn test_evaluate_condition_operators() {
+    let assembler = create_assembler(0x01, 10);
+    for (expression, expected) in [
+        ("pkt_type.value == 0x01", true),
+        ("pkt_type.value != 1", false),
+        ("count.value < 10", false),
+        ("count.value <= 10", true),
+        ("count.value > 0x09", true),
+        ("count.value >= 11", false),
+        ("pkt_type.value == 2 || count.value == 10", true),
+        ("pkt_type.value == 1 && count.value > 10", false),
+    ] {
+        assert_eq!(
+            assembler.evaluate_condition(expression).unwrap(),
+            expected,
+            "{expression}"
+        );
+    }
+}
+
+#[test]
+fn test_evaluate_condition_rejects_invalid_expression() {
+    let assembler = create_assembler(0x01, 10);
+    assert!(matches!(
+        assembler.evaluate_condition("count.value ~ 3"),
+        Err(ProtocolError::InvalidExpression(_))
+    ));
+    assert!(matches!(
+        assembler.evaluate_condition("missing.value == 3"),
+        Err(ProtocolError::FieldNotFound(_))
+    ));
+}
+
+#[test]
+fn test_parse_after_skipping_uses_full_layout() {
+    let mut assembler = create_assembler(0x02, 10);
+    assembler.assemble_frame().unwrap();
+    assert!(assembler.skipped_fields.contains("sec_header"));
+
+    // 解析清空上次组装省略的字段，按全部字段切分
+    let fields = assembler
+        .parse_frame(&[0x01, 0x0B, 0xBE, 0xEF, 0x77, 0xD0, 0xD1])
+        .unwrap();
+    assert!(assembler.skipped_fields.is_empty());
+    assert_eq!(fields[2], ("sec_header".to_string(), vec![0xBE, 0xEF]));
+    assert_eq!(fields[4], ("data".to_string(), vec![0xD0, 0xD1]));
+}
@@ -0,0 +1,64 @@
+//! 约束检查模式测试
+//!
+//! 验证Strict模式拒绝违反约束的字段值，Lenient模式允许构造异常帧
+
+mod common;
+
+use apdl_core::{ConstraintMode, ProtocolError};
+use apdl_poem::FrameAssembler;
+use common::assembler_from_dsl;
+
+const FRAME_DSL: &str = r#"
+field: version; type: Bit(3); length: 3bit; scope: layer(link); cover: entire_field; constraint: fixed(1); desc: "Version"
+field: flags; type: Bit(5); length: 5bit; scope: layer(link); cover: entire_field; desc: "Flags"
+field: count; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; constraint: range(1..=10); desc: "Count"
+"#;
+
+fn create_assembler() -> FrameAssembler {
+    assembler_from_dsl(FRAME_DSL)
+}
+
+#[test]
+fn test_lenient_mode_accepts_out_of_range_value() {
+    let mut assembler = create_assembler();
+    assert_eq!(assembler.constraint_mode, ConstraintMode::Lenient);
+
+    assembler.set_field_value("count", &[0x20]).unwrap();
+    assembler.set_bit_field_value("version", 5).unwrap();
+
+    let frame = assembler.assemble_frame().unwrap();
+    assert_eq!(frame, vec![0xA0, 0x20]);
+}
+
+#[test]
+fn test_strict_mode_rejects_out_of_range_value() {
+    let mut assembler = create_assembler();
+    assembler.set_constraint_mode(ConstraintMode::Strict);
+
+    assert!(matches!(
+        assembler.set_field_value("count", &[0x20]),
+        Err(ProtocolError::ConstraintViolation(_))
+    ));
+    assert!(matches!(
+        assembler.set_bit_field_value("version", 5),
+        Err(ProtocolError::ConstraintViolation(_))
+    ));
+
+    // 满足约束的值正常组装
+    assembler.set_field_value("count", &[0x05]).unwrap();
+    assembler.set_bit_field_value("version", 1).unwrap();
+    assert_eq!(assembler.assemble_frame().unwrap(), vec![0x20, 0x05]);
+}
+
+#[test]
+fn test_strict_mode_rejects_assembly_of_previously_set_value() {
+    let mut assembler = create_assembler();
+    assembler.set_field_value("count", &[0x20]).unwrap();
+
+    // 切换到Strict模式后，组装时检查已设置的字段值
+    assembler.set_constraint_mode(ConstraintMode::Strict);
+    assert!(matches!(
+        assembler.assemble_frame(),
+        Err(ProtocolError::ConstraintViolation(_))
+    ));
+}
@@ -0,0 +1,92 @@
+//! 覆盖区间测试
+//!
+//! 验证`field[a..b]`为不含b的半开区间、`field[a..=b]`包含b，并在覆盖计算中一致生效
+
+mod common;
+
+use apdl_core::{CoverDesc, ProtocolError};
+use apdl_poem::dsl::parser_utils::parse_cover_desc;
+use apdl_poem::FrameAssembler;
+use common::assembler_from_dsl;
+
+fn create_assembler(cover: &str) -> FrameAssembler {
+    let dsl = format!(
+        r#"
+field: header; type: Uint32; length: 4byte; scope: layer(link); cover: entire_field; desc: "Header"
+field: hcrc; type: Uint16; length: 2byte; scope: layer(link); cover: {cover}; alg: crc16; desc: "Header check"
+rule: crc_range(start: header to header);
+"#
+    );
+    assembler_from_dsl(&dsl)
+}
+
+fn assemble(assembler: &mut FrameAssembler) -> Vec<u8> {
+    assembler
+        .set_field_value("header", &[0x12, 0x34, 0x56, 0x78])
+        .unwrap();
+    assembler.assemble_frame().unwrap()
+}
+
+#[test]
+fn test_parse_exclusive_and_inclusive_ranges() {
+    assert_eq!(
+        parse_cover_desc("header[0..2]").unwrap(),
+        CoverDesc::Range("header".to_string(), 0, 2)
+    );
+    assert_eq!(
+        parse_cover_desc("header[0..=2]").unwrap(),
+        CoverDesc::Range("header".to_string(), 0, 3)
+    );
+    assert_eq!(
+        parse_cover_desc("header[1..=1]").unwrap().byte_range(4),
+        Some(1..2)
+    );
+}
+
+#[test]
+fn test_cover_range_byte_counts() {
+    let exclusive = create_assembler("header[0..2]");
+    let range = exclusive.cover_byte_range("hcrc").unwrap().unwrap();
+    assert_eq!(range, 0..2);
+    assert_eq!(range.len(), 2);
+
+    let inclusive = create_assembler("header[0..=2]");
+    let range = inclusive.cover_byte_range("hcrc").unwrap().unwrap();
+    assert_eq!(range, 0..3);
+    assert_eq!(range.len(), 3);
+
+    // entire_field覆盖字段自身
+    assert_eq!(exclusive.cover_byte_range("header").unwrap(), Some(0..4));
+}
+
+#[test]
+fn test_parse_inclusive_range_end_overflow_errors() {
+    assert!(parse_cover_desc(&format!("header[0..={}]", usize::MAX)).is_err());
+}
+
+#[test]
+fn test_checksum_computed_over_cover_range() {
+    let mut exclusive = create_assembler("header[0..2]");
+    let frame = assemble(&mut exclusive);
+    let crc = exclusive.calculate_crc16(&frame[..2]);
+    assert_eq!(&frame[4..], &crc.to_be_bytes());
+    assert!(exclusive.verify_checksums(&frame).is_ok());
+
+    let mut inclusive = create_assembler("header[0..=2]");
+    let frame = assemble(&mut inclusive);
+    let crc = inclusive.calculate_crc16(&frame[..3]);
+    assert_eq!(&frame[4..], &crc.to_be_bytes());
+    assert!(inclusive.verify_checksums(&frame).is_ok());
+}
+
+#[test]
+fn test_cover_range_exceeding_field_errors() {
+    let assembler = create_assembler("header[2..=4]");
+    assert!(matches!(
+        assembler.cover_byte_range("hcrc"),
+        Err(ProtocolError::InvalidFieldDefinition(_))
+    ));
+
+    let assembler = create_assembler("header[2..2]");
+    assert!(assembler.cover_byte_range("hcrc").is_err());
+}
@@ -0,0 +1,94 @@
+//! CRC16参数配置测试
+//!
+//! 验证`alg: crc16(...)`解析出的多项式和初值参数在组装和校验时生效
+
+mod common;
+
+use apdl_core::utils::{calculate_crc16_with, Crc16Params};
+use apdl_core::{AlgorithmAst, ChecksumAlgorithm, SemanticRule};
+use apdl_poem::{DslParserImpl, FrameAssembler};
+use common::assembler_from_dsl;
+
+fn create_assembler(alg: &str) -> FrameAssembler {
+    let dsl = format!(
+        r#"
+        field: header; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Header"
+        field: data; type: RawData; length: 4byte; scope: layer(link); cover: entire_field; desc: "Data"
+        field: crc; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; alg: {alg}; desc: "CRC"
+        "#
+    );
+
+    let mut assembler = assembler_from_dsl(&dsl);
+    assembler.add_semantic_rule(SemanticRule::ChecksumRange {
+        algorithm: ChecksumAlgorithm::CRC16,
+        start_field: "header".to_string(),
+        end_field: "data".to_string(),
+        covers_self: true,
+    });
+    assembler.set_field_value("header", &[0x12, 0x34]).unwrap();
+    assembler
+        .set_field_value("data", &[0x01, 0x02, 0x03, 0x04])
+        .unwrap();
+    assembler
+}
+
+#[test]
+fn test_parse_crc16_params() {
+    let parser = DslParserImpl::new();
+    let units = parser
+        .parse_protocol_structure(
+            r#"field: crc; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; alg: crc16(poly=0x8005, init=0x0000, refin=true, refout=true); desc: "CRC""#,
+        )
+        .unwrap();
+    assert_eq!(
+        units[0].alg,
+        Some(AlgorithmAst::Crc16With(Crc16Params::ARC))
+    );
+
+    // 未指定的参数取CCSDS默认值
+    let units = parser
+        .parse_protocol_structure(
+            r#"field: crc; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; alg: crc16(poly=0x8005); desc: "CRC""#,
+        )
+        .unwrap();
+    assert_eq!(
+        units[0].alg,
+        Some(AlgorithmAst::Crc16With(Crc16Params {
+            poly: 0x8005,
+            ..Crc16Params::CCSDS
+        }))
+    );
+
+    assert!(parser
+        .parse_protocol_structure(
+            r#"field: crc; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; alg: crc16(width=16); desc: "CRC""#,
+        )
+        .is_err());
+}
+
+#[test]
+fn test_assemble_with_custom_polynomial() {
+    let mut assembler = create_assembler("crc16(poly=0x8005,init=0x0000)");
+    let frame = assembler.assemble_frame().unwrap();
+
+    let params = Crc16Params {
+        poly: 0x8005,
+        init: 0x0000,
+        ..Crc16Params::CCSDS
+    };
+    let expected = calculate_crc16_with(params, &frame[..6]);
+    assert_eq!(&frame[6..], &expected.to_be_bytes());
+    assert_ne!(expected, assembler.calculate_crc16(&frame[..6]));
+
+    assembler
+        .validate_checksum_rule(&frame, &ChecksumAlgorithm::CRC16, "header", "data")
+        .unwrap();
+}
+
+#[test]
+fn test_plain_crc16_keeps_ccsds_params() {
+    let mut assembler = create_assembler("crc16");
+    let frame = assembler.assemble_frame().unwrap();
+    let expected = calculate_crc16_with(Crc16Params::CCSDS, &frame[..6]);
+    assert_eq!(&frame[6..], &expected.to_be_bytes());
+}
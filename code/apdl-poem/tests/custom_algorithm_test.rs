@@ -0,0 +1,147 @@
+//! 自定义算法注册表测试
+//!
+//! 验证注册的自定义算法可通过algorithm规则和连接器字段映射执行
+
+mod common;
+
+use apdl_core::{FieldMappingEntry, ProtocolError};
+use apdl_poem::standard_units::connector::{ConnectorEngine, FieldMapper};
+use apdl_poem::{CustomAlgorithmRegistry, FrameAssembler};
+use common::assembler_from_dsl;
+
+const FRAME_DSL: &str = r#"
+field: header; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Header"
+field: payload; type: Uint32; length: 4byte; scope: layer(application); cover: entire_field; desc: "Payload"
+rule: algorithm(field: payload uses reverse_bytes);
+"#;
+
+const MAPPING_DSL: &str = r#"
+field: src; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Source"
+field: dst; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Destination"
+"#;
+
+fn mapping(logic: &str) -> FieldMappingEntry {
+    FieldMappingEntry {
+        source_field: "src".to_string(),
+        target_field: "dst".to_string(),
+        mapping_logic: logic.to_string(),
+        default_value: "0".to_string(),
+        enum_mappings: None,
+        mask_mapping_table: None,
+    }
+}
+
+fn reverse_bytes(data: &[u8]) -> Vec<u8> {
+    data.iter().rev().copied().collect()
+}
+
+fn create_assembler() -> FrameAssembler {
+    let mut assembler = assembler_from_dsl(FRAME_DSL);
+    assembler.set_field_value("header", &[0xAA, 0xBB]).unwrap();
+    assembler
+        .set_field_value("payload", &[0x01, 0x02, 0x03, 0x04])
+        .unwrap();
+    assembler
+}
+
+#[test]
+fn test_reverse_bytes_applied_to_field() {
+    let mut assembler = create_assembler();
+    assembler.register_custom_algorithm("reverse_bytes", reverse_bytes);
+
+    let frame = assembler.assemble_frame().unwrap();
+    assert_eq!(frame, vec![0xAA, 0xBB, 0x04, 0x03, 0x02, 0x01]);
+    assert_eq!(
+        assembler.get_field_value("payload").unwrap(),
+        vec![0x04, 0x03, 0x02, 0x01]
+    );
+}
+
+#[test]
+fn test_unknown_custom_algorithm_errors() {
+    let mut assembler = create_assembler();
+    let mut frame = vec![0xAA, 0xBB, 0x01, 0x02, 0x03, 0x04];
+
+    let result = assembler.apply_custom_algorithm("payload", "no_such_algorithm", &mut frame);
+    match result {
+        Err(ProtocolError::Other(msg)) => assert!(msg.contains("no_such_algorithm")),
+        other => panic!("unexpected result: {other:?}"),
+    }
+
+    // algorithm规则引用未注册的算法时组装失败，而不是原样输出字段
+    match assembler.assemble_frame() {
+        Err(ProtocolError::Other(msg)) => assert!(msg.contains("reverse_bytes")),
+        other => panic!("unexpected result: {other:?}"),
+    }
+}
+
+#[test]
+fn test_global_algorithm_used_by_field_mapper() {
+    CustomAlgorithmRegistry::register_global("reverse_bytes_global", reverse_bytes);
+
+    let mapper = FieldMapper::new();
+    let mapped = mapper
+        .map_field(&[0x12, 0x34, 0x56], "reverse_bytes_global")
+        .unwrap();
+    assert_eq!(mapped, vec![0x56, 0x34, 0x12]);
+
+    // 全局注册的算法对所有组装器实例可见
+    let mut assembler = create_assembler();
+    let mut frame = vec![0xAA, 0xBB, 0x01, 0x02, 0x03, 0x04];
+    assembler
+        .apply_custom_algorithm("payload", "reverse_bytes_global", &mut frame)
+        .unwrap();
+    assert_eq!(&frame[2..], &[0x04, 0x03, 0x02, 0x01]);
+}
+
+#[test]
+fn test_mapping_logic_in_assembler_rule() {
+    let mut assembler = assembler_from_dsl(MAPPING_DSL);
+    assembler.set_field_value("src", &[0x12, 0x34]).unwrap();
+    assembler.register_custom_algorithm("swap_instance", reverse_bytes);
+    let mut frame = Vec::new();
+
+    for (logic, expected) in [("swap_instance", [0x34, 0x12]), ("direct", [0x12, 0x34])] {
+        assembler
+            .apply_field_mapping_rule("a", "b", &[mapping(logic)], "", &mut frame)
+            .unwrap();
+        assert_eq!(
+            assembler.get_field_value("dst").unwrap(),
+            expected,
+            "{logic}"
+        );
+    }
+
+    // 未注册的映射逻辑报错，不再按恒等映射处理
+    let result =
+        assembler.apply_field_mapping_rule("a", "b", &[mapping("no_such_mapping")], "", &mut frame);
+    match result {
+        Err(ProtocolError::Other(msg)) => assert!(msg.contains("no_such_mapping")),
+        other => panic!("unexpected result: {other:?}"),
+    }
+}
+
+#[test]
+fn test_connector_mapping_uses_target_instance_registry() {
+    let mut source = assembler_from_dsl(MAPPING_DSL);
+    source.set_field_value("src", &[0x12, 0x34]).unwrap();
+    let mut target = assembler_from_dsl(MAPPING_DSL);
+    target.register_custom_algorithm("swap_instance", reverse_bytes);
+    let engine = ConnectorEngine::new();
+
+    for (logic, expected) in [
+        ("swap_instance", [0x34, 0x12]),
+        ("passthrough", [0x12, 0x34]),
+        ("identity", [0x12, 0x34]),
+    ] {
+        engine
+            .apply_field_mapping_rules(&source, &mut target, "", &[mapping(logic)])
+            .unwrap();
+        assert_eq!(target.get_field_value("dst").unwrap(), expected, "{logic}");
+    }
+
+    let error = engine
+        .apply_field_mapping_rules(&source, &mut target, "", &[mapping("no_such_mapping")])
+        .unwrap_err();
+    assert!(error.to_string().contains("no_such_mapping"));
+}
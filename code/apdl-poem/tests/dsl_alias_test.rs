@@ -0,0 +1,107 @@
+//! DSL别名功能测试
+//!
+//! 验证define/use别名在解析时展开，并检测递归别名
+
+use apdl_poem::dsl::alias::AliasExpander;
+use apdl_poem::DslParserImpl;
+
+const PRIMARY_HEADER: &str = r#"
+define PRIMARY_HEADER = {
+    field: version; type: Bit(3); length: 3bit; scope: layer(network); cover: entire_field; constraint: fixed(0); desc: "Version"
+    field: apid; type: Bit(11); length: 11bit; scope: layer(network); cover: entire_field; desc: "APID"
+    field: pkt_len; type: Uint16; length: 2byte; scope: layer(network); cover: entire_field; desc: "Packet length"
+}
+"#;
+
+#[test]
+fn test_alias_used_in_two_layers() {
+    let parser = DslParserImpl::new();
+
+    let telemetry_layer = format!(
+        r#"{PRIMARY_HEADER}
+        use PRIMARY_HEADER;
+        field: tm_data; type: RawData; length: dynamic; scope: layer(application); cover: entire_field; desc: "Telemetry data"
+        "#
+    );
+    let telecommand_layer = format!(
+        r#"{PRIMARY_HEADER}
+        use PRIMARY_HEADER;
+        field: tc_data; type: RawData; length: dynamic; scope: layer(application); cover: entire_field; desc: "Telecommand data"
+        "#
+    );
+
+    let tm_units = parser.parse_protocol_structure(&telemetry_layer).unwrap();
+    let tc_units = parser.parse_protocol_structure(&telecommand_layer).unwrap();
+
+    // 别名展开为3个头部字段 + 1个数据字段
+    assert_eq!(tm_units.len(), 4);
+    assert_eq!(tc_units.len(), 4);
+
+    // 两层中的头部字段展开结果完全一致
+    assert_eq!(tm_units[..3], tc_units[..3]);
+    assert_eq!(tm_units[0].field_id, "version");
+    assert_eq!(tm_units[1].field_id, "apid");
+    assert_eq!(tm_units[2].field_id, "pkt_len");
+
+    assert_eq!(tm_units[3].field_id, "tm_data");
+    assert_eq!(tc_units[3].field_id, "tc_data");
+}
+
+#[test]
+fn test_nested_alias_expansion() {
+    let dsl = r#"
+    define SYNC = {
+        field: sync; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; constraint: fixed(0xEB90)
+    }
+    define FRAME_HEADER = {
+        use SYNC;
+        field: frame_id; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field
+    }
+    use FRAME_HEADER;
+    "#;
+
+    let units = DslParserImpl::new().parse_protocol_structure(dsl).unwrap();
+    let names: Vec<&str> = units.iter().map(|u| u.field_id.as_str()).collect();
+    assert_eq!(names, vec!["sync", "frame_id"]);
+}
+
+#[test]
+fn test_recursive_alias_detected() {
+    let dsl = r#"
+    define A = {
+        use B;
+    }
+    define B = {
+        use A;
+    }
+    use A;
+    "#;
+
+    let err = AliasExpander::expand(dsl).unwrap_err();
+    assert!(err.contains("Recursive alias"), "unexpected error: {err}");
+    assert!(err.contains("A -> B -> A"), "unexpected error: {err}");
+
+    let self_recursive = "define LOOP = {\n    use LOOP;\n}\nuse LOOP;";
+    assert!(AliasExpander::expand(self_recursive).is_err());
+}
+
+#[test]
+fn test_undefined_alias() {
+    let err = AliasExpander::expand("use MISSING;").unwrap_err();
+    assert!(err.contains("Undefined alias"));
+}
+
+#[test]
+fn test_braces_in_quoted_text_are_ignored() {
+    let dsl = r#"
+define HDR = {
+    field: flag; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "open { brace"
+    field: mark; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "quote \" and } brace"
+}
+use HDR;
+"#;
+    let units = DslParserImpl::new().parse_protocol_structure(dsl).unwrap();
+    assert_eq!(units.len(), 2);
+    assert_eq!(units[0].desc, "open { brace");
+    assert_eq!(units[1].desc, r#"quote " and } brace"#);
+}
@@ -0,0 +1,111 @@
+//! DSL错误位置测试
+//!
+//! 验证解析错误指出出错的行号、列号和片段
+
+use apdl_core::DslParseError;
+use apdl_poem::DslParserImpl;
+
+const FRAME_DSL: &str = r#"// 帧定义
+field: version; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "Version"
+    field: apid; type: Uint16; length: twobyte; scope: layer(link); cover: entire_field; desc: "APID"
+rule: checksum_range(start: version to apid);
+"#;
+
+#[test]
+fn test_malformed_length_reports_column() {
+    let err = DslParserImpl::new()
+        .parse_protocol_structure(FRAME_DSL)
+        .unwrap_err();
+
+    let line = FRAME_DSL.lines().nth(2).unwrap();
+    let expected_column = line.find("twobyte").unwrap() + 1;
+    assert_eq!(
+        err,
+        DslParseError::Syntax {
+            line: 3,
+            column: expected_column,
+            token: "twobyte".to_string(),
+            message: "Invalid byte length: twobyte".to_string(),
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        format!("3:{expected_column}: Invalid byte length: twobyte (at 'twobyte')")
+    );
+}
+
+#[test]
+fn test_column_counts_characters() {
+    // 中文字段名位于出错片段之前，列号按字符而非字节计
+    let dsl =
+        r#"field: 版本; type: Float8; length: 1byte; scope: layer(link); cover: entire_field"#;
+    let err = DslParserImpl::new()
+        .parse_protocol_structure(dsl)
+        .unwrap_err();
+
+    let DslParseError::Syntax {
+        line,
+        column,
+        token,
+        ..
+    } = err
+    else {
+        panic!("expected a positioned error, got {err:?}");
+    };
+    assert_eq!((line, column, token.as_str()), (1, 18, "Float8"));
+}
+
+#[test]
+fn test_semantic_rule_errors_report_position() {
+    let dsl = "field: a; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field\n\
+               rule: checksum_rnage(start: a to a);\n";
+    let err = DslParserImpl::new().parse_semantic_rules(dsl).unwrap_err();
+
+    assert_eq!(
+        err,
+        DslParseError::Syntax {
+            line: 2,
+            column: 7,
+            token: "checksum_rnage".to_string(),
+            message: "Unknown rule type: checksum_rnage".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_error_line_is_the_statement_line() {
+    // 出错行与未使用的别名定义中的一行文本相同，行号仍指向出错语句本身
+    let dsl = "define UNUSED = {\n\
+               field: a; type: Float8; length: 1byte; scope: layer(link); cover: entire_field\n\
+               }\n\
+               field: b; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field\n\
+               field: a; type: Float8; length: 1byte; scope: layer(link); cover: entire_field\n";
+    let err = DslParserImpl::new()
+        .parse_protocol_structure(dsl)
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            DslParseError::Syntax {
+                line: 5,
+                column: 17,
+                ..
+            }
+        ),
+        "{err:?}"
+    );
+
+    // 别名内容中的错误指向其use行
+    let dsl = "define BAD = {\n\
+               field: a; type: Float8; length: 1byte; scope: layer(link); cover: entire_field\n\
+               }\n\
+               field: b; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field\n\
+               use BAD;\n";
+    let err = DslParserImpl::new()
+        .parse_protocol_structure(dsl)
+        .unwrap_err();
+    assert!(
+        matches!(err, DslParseError::Syntax { line: 5, .. }),
+        "{err:?}"
+    );
+}
@@ -0,0 +1,49 @@
+//! 重复字段检测测试
+//!
+//! 验证try_add_field拒绝重复的字段名且不影响已添加的字段
+
+use apdl_core::ProtocolError;
+use apdl_poem::{DslParserImpl, FrameAssembler};
+
+const FRAME_DSL: &str = r#"
+field: version; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "Version"
+field: payload; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Payload"
+field: version; type: Uint32; length: 4byte; scope: layer(link); cover: entire_field; desc: "Duplicate version"
+"#;
+
+#[test]
+fn test_try_add_field_rejects_duplicate_id() {
+    let units = DslParserImpl::new()
+        .parse_protocol_structure(FRAME_DSL)
+        .unwrap();
+    let mut assembler = FrameAssembler::new();
+    assembler.try_add_field(units[0].clone()).unwrap();
+    assembler.try_add_field(units[1].clone()).unwrap();
+
+    match assembler.try_add_field(units[2].clone()) {
+        Err(ProtocolError::InvalidFieldDefinition(msg)) => {
+            assert!(msg.contains("version"));
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+
+    // 重复字段未被添加，原字段定义保持不变
+    assert_eq!(assembler.fields.len(), 2);
+    assert_eq!(assembler.get_field_size_by_name("version").unwrap(), 1);
+    assert_eq!(assembler.get_field_position("payload").unwrap(), 1);
+}
+
+#[test]
+fn test_add_field_keeps_overwriting_behavior() {
+    let units = DslParserImpl::new()
+        .parse_protocol_structure(FRAME_DSL)
+        .unwrap();
+    let mut assembler = FrameAssembler::new();
+    for unit in units {
+        assembler.add_field(unit);
+    }
+
+    // 不检查重复的add_field保持原有行为：索引指向最后添加的字段
+    assert_eq!(assembler.fields.len(), 3);
+    assert_eq!(assembler.get_field_size_by_name("version").unwrap(), 4);
+}
@@ -0,0 +1,56 @@
+//! 空定义与零长度字段测试
+//!
+//! 验证零长度字段在解析时被拒绝，空定义组装为空帧，空输入解析报错
+
+use apdl_core::ProtocolError;
+use apdl_poem::{DslParserImpl, FrameAssembler};
+
+#[test]
+fn test_zero_length_field_rejected_at_parse() {
+    let parser = DslParserImpl::new();
+
+    let byte_dsl = r#"field: empty; type: Uint8; length: 0byte; scope: layer(link); cover: entire_field; desc: "Empty""#;
+    let err = parser
+        .parse_protocol_structure(byte_dsl)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Zero-length field"), "unexpected error: {err}");
+
+    let bit_dsl = r#"field: empty; type: Bit(1); length: 0bit; scope: layer(link); cover: entire_field; desc: "Empty""#;
+    let err = parser
+        .parse_protocol_structure(bit_dsl)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Zero-length field"), "unexpected error: {err}");
+}
+
+#[test]
+fn test_empty_definition_assembles_empty_frame() {
+    let parser = DslParserImpl::new();
+    let units = parser
+        .parse_protocol_structure("// 仅包含注释的空定义\n")
+        .unwrap();
+    assert!(units.is_empty());
+
+    let mut assembler = FrameAssembler::new();
+    assert_eq!(assembler.assemble_frame().unwrap(), Vec::<u8>::new());
+    assert!(assembler.parse_frame(&[]).unwrap().is_empty());
+}
+
+#[test]
+fn test_parse_empty_input_with_fields_errors() {
+    let parser = DslParserImpl::new();
+    let units = parser
+        .parse_protocol_structure(
+            r#"field: version; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "Version""#,
+        )
+        .unwrap();
+
+    let mut assembler = FrameAssembler::new();
+    for unit in units {
+        assembler.add_field(unit);
+    }
+
+    let result = assembler.parse_frame(&[]);
+    assert!(matches!(result, Err(ProtocolError::InvalidFrameFormat(_))));
+}
@@ -0,0 +1,65 @@
+//! 空负载测试
+//!
+//! 验证显式设置为空的动态长度字段组装为0字节，解析时帧尾无剩余字节则得到空值
+
+mod common;
+
+use apdl_core::ConstraintMode;
+use apdl_poem::FrameAssembler;
+use common::{assembler_from_dsl, disassembler_from_dsl};
+
+const FRAME_DSL: &str = r#"
+field: header; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Header"
+field: length; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "Payload length"
+field: payload; type: RawData; length: dynamic; scope: layer(link); cover: entire_field; constraint: range(1..=255); desc: "Payload"
+rule: length_rule(length equals "len(payload)");
+"#;
+
+fn create_assembler() -> FrameAssembler {
+    let mut assembler = assembler_from_dsl(FRAME_DSL);
+    assembler.set_field_value("header", &[0x12, 0x34]).unwrap();
+    assembler
+}
+
+#[test]
+fn test_assemble_empty_trailing_payload() {
+    let mut assembler = create_assembler();
+    // 空值不参与约束检查，严格模式下也可以设置
+    assembler.set_constraint_mode(ConstraintMode::Strict);
+    // 未设置时动态字段默认占1字节，显式设置空值后为0字节
+    assert_eq!(assembler.get_field_size_by_name("payload").unwrap(), 1);
+    assembler.set_field_value("payload", &[]).unwrap();
+
+    assert_eq!(assembler.get_field_size_by_name("payload").unwrap(), 0);
+    assert_eq!(assembler.predicted_length().unwrap(), 3);
+    let frame = assembler.assemble_frame().unwrap();
+    assert_eq!(frame, vec![0x12, 0x34, 0x00]);
+}
+
+#[test]
+fn test_parse_empty_trailing_payload() {
+    let mut assembler = create_assembler();
+    assembler.set_field_value("payload", &[]).unwrap();
+    let frame = assembler.assemble_frame().unwrap();
+
+    let parsed = assembler.parse_frame(&frame).unwrap();
+    assert_eq!(parsed.last().unwrap(), &("payload".to_string(), Vec::new()));
+
+    let disassembler = disassembler_from_dsl(FRAME_DSL);
+    let fields = disassembler.parse_frame_named(&frame).unwrap();
+    assert_eq!(fields.len(), 3);
+    assert_eq!(fields[2].name, "payload");
+    assert!(fields[2].value.is_empty());
+    assert!(disassembler.disassemble_frame(&frame).unwrap()["payload"].is_empty());
+}
+
+#[test]
+fn test_non_empty_payload_after_empty() {
+    let mut assembler = create_assembler();
+    assembler.set_field_value("payload", &[]).unwrap();
+    assert_eq!(assembler.assemble_frame().unwrap().len(), 3);
+
+    assembler.set_field_value("payload", &[0xAA, 0xBB]).unwrap();
+    let frame = assembler.assemble_frame().unwrap();
+    assert_eq!(frame, vec![0x12, 0x34, 0x02, 0xAA, 0xBB]);
+}
@@ -141,3 +141,133 @@ fn test_complex_wildcard_patterns() {
     let result = mapper.map_enum("other_resp_ack", Some(&enum_mappings));
     assert_eq!(result, None);
 }
+
+fn tlm_mappings() -> Vec<EnumMappingEntry> {
+    [("TLM_*", "1"), ("TLM_HK01", "2"), ("TLM_HK??", "3")]
+        .iter()
+        .map(|(source, target)| EnumMappingEntry {
+            source_enum: source.to_string(),
+            target_enum: target.to_string(),
+        })
+        .collect()
+}
+
+#[test]
+fn test_exact_match_takes_precedence_over_wildcards() {
+    use apdl_poem::standard_units::connector::field_mapper::FieldMapper;
+
+    let mapper = FieldMapper::new();
+    let enum_mappings = tlm_mappings();
+
+    // 精确匹配优先，即使通配符条目先声明
+    let result = mapper.map_enum("TLM_HK01", Some(&enum_mappings));
+    assert_eq!(result, Some("2".to_string()));
+
+    // 字面字符更多的通配符模式更具体
+    let result = mapper.map_enum("TLM_HK02", Some(&enum_mappings));
+    assert_eq!(result, Some("3".to_string()));
+
+    let result = mapper.map_enum("TLM_DIAG", Some(&enum_mappings));
+    assert_eq!(result, Some("1".to_string()));
+
+    let result = mapper.map_enum("CMD_RST0", Some(&enum_mappings));
+    assert_eq!(result, None);
+}
+
+#[test]
+fn test_connector_engine_enum_mapping_falls_back_to_default() {
+    use apdl_core::FieldMappingEntry;
+    use apdl_poem::standard_units::connector::ConnectorEngine;
+    use apdl_poem::standard_units::frame_assembler::core::FrameAssembler;
+
+    let parser = DslParserImpl::new();
+    let assembler_with = |dsl: &str| {
+        let mut assembler = FrameAssembler::new();
+        for unit in parser.parse_protocol_structure(dsl).unwrap() {
+            assembler.add_field(unit);
+        }
+        assembler
+    };
+    let mappings = vec![FieldMappingEntry {
+        source_field: "src_type".to_string(),
+        target_field: "vcid".to_string(),
+        mapping_logic: "identity".to_string(),
+        default_value: "7".to_string(),
+        enum_mappings: Some(tlm_mappings()),
+        mask_mapping_table: None,
+    }];
+    let engine = ConnectorEngine::new();
+
+    for (source, expected) in [
+        ("TLM_HK01", 2u8),
+        ("TLM_HK02", 3),
+        ("TLM_DIAG", 1),
+        ("CMD_RST0", 7),
+    ] {
+        let mut source_assembler = assembler_with(
+            r#"field: src_type; type: RawData; length: 8byte; scope: layer(app); cover: entire_field; desc: "Source type""#,
+        );
+        source_assembler
+            .set_field_value("src_type", source.as_bytes())
+            .unwrap();
+        let mut target_assembler = assembler_with(
+            r#"field: vcid; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "VCID""#,
+        );
+
+        engine
+            .apply_field_mapping_rules(&source_assembler, &mut target_assembler, "", &mappings)
+            .unwrap();
+        assert_eq!(
+            target_assembler.get_field_value("vcid").unwrap(),
+            vec![expected],
+            "source {source}"
+        );
+    }
+}
+
+#[test]
+fn test_assembler_and_connector_map_enum_targets_to_same_bytes() {
+    use apdl_core::FieldMappingEntry;
+    use apdl_poem::standard_units::connector::ConnectorEngine;
+    use apdl_poem::standard_units::frame_assembler::core::FrameAssembler;
+
+    const SOURCE_DSL: &str = r#"field: src_type; type: RawData; length: 8byte; scope: layer(app); cover: entire_field; desc: "Source type""#;
+    const TARGET_DSL: &str = r#"field: vcid; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "VCID""#;
+
+    let parser = DslParserImpl::new();
+    let assembler_with = |dsl: &str| {
+        let mut assembler = FrameAssembler::new();
+        for unit in parser.parse_protocol_structure(dsl).unwrap() {
+            assembler.add_field(unit);
+        }
+        assembler
+    };
+    let mappings = vec![FieldMappingEntry {
+        source_field: "src_type".to_string(),
+        target_field: "vcid".to_string(),
+        mapping_logic: "identity".to_string(),
+        default_value: "7".to_string(),
+        enum_mappings: Some(tlm_mappings()),
+        mask_mapping_table: None,
+    }];
+
+    // 连接器引擎：源、目标字段位于两个组装器
+    let mut source_assembler = assembler_with(SOURCE_DSL);
+    source_assembler
+        .set_field_value("src_type", b"TLM_HK01")
+        .unwrap();
+    let mut target_assembler = assembler_with(TARGET_DSL);
+    ConnectorEngine::new()
+        .apply_field_mapping_rules(&source_assembler, &mut target_assembler, "", &mappings)
+        .unwrap();
+
+    // 帧组装器：源、目标字段位于同一组装器
+    let mut assembler = assembler_with(&format!("{SOURCE_DSL}\n{TARGET_DSL}"));
+    assembler.set_field_value("src_type", b"TLM_HK01").unwrap();
+    assembler
+        .apply_field_mapping_rule("app", "link", &mappings, "enum", &mut [])
+        .unwrap();
+
+    assert_eq!(target_assembler.get_field_value("vcid").unwrap(), vec![2]);
+    assert_eq!(assembler.get_field_value("vcid").unwrap(), vec![2]);
+}
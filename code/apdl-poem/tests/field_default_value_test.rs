@@ -22,6 +22,8 @@ fn test_field_default_value_with_fixed_constraint() {
         associate: vec![],
         desc: "Field with fixed value constraint".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     // 2. 创建另一个没有约束的字段
@@ -39,6 +41,8 @@ fn test_field_default_value_with_fixed_constraint() {
         associate: vec![],
         desc: "Normal field without constraint".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     // 3. 创建FrameAssembler并添加字段
@@ -83,6 +87,8 @@ fn test_field_override_fixed_value() {
         associate: vec![],
         desc: "Field with fixed value constraint".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     let mut assembler = FrameAssembler::new();
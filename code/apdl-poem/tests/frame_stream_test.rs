@@ -0,0 +1,77 @@
+//! 连续帧流测试
+//!
+//! 验证FrameStream连续组装的帧中序列字段自动递增、时间戳自动写入、其他字段值在帧间保持
+
+use apdl_core::SemanticRule;
+use apdl_poem::{DslParserImpl, FrameStream, FrameTemplate};
+
+const FRAME_DSL: &str = r#"
+field: sync; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; constraint: fixed(0xEB90); desc: "Sync"
+field: seq; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Sequence count"
+field: time; type: Uint32; length: 4byte; scope: layer(link); cover: entire_field; desc: "Timestamp"
+field: payload; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Payload"
+"#;
+
+fn create_stream() -> FrameStream {
+    let units = DslParserImpl::new()
+        .parse_protocol_structure(FRAME_DSL)
+        .unwrap();
+    let rules = vec![
+        SemanticRule::SequenceControl {
+            field_name: "seq".to_string(),
+            trigger_condition: "on_transmission".to_string(),
+            algorithm: "increment_seq".to_string(),
+            description: "Increment per frame".to_string(),
+        },
+        SemanticRule::TimeSynchronization {
+            field_name: "time".to_string(),
+            algorithm: "time_sync_alg".to_string(),
+            description: "Frame timestamp".to_string(),
+        },
+    ];
+    let mut stream = FrameStream::new(&FrameTemplate::new(units, rules));
+    stream.set_time_source(1000, 250);
+    stream
+}
+
+#[test]
+fn test_stream_increments_sequence_and_persists_fields() {
+    let mut stream = create_stream();
+    stream.set_field_value("payload", &[0xBE, 0xEF]).unwrap();
+
+    let frames: Vec<Vec<u8>> = (0..5).map(|_| stream.next(&[]).unwrap()).collect();
+    assert_eq!(stream.frames_emitted(), 5);
+
+    for (i, frame) in frames.iter().enumerate() {
+        assert_eq!(&frame[0..2], &[0xEB, 0x90]);
+        assert_eq!(&frame[2..4], &(i as u16).to_be_bytes());
+        assert_eq!(&frame[4..8], &(1000 + 250 * i as u32).to_be_bytes());
+        assert_eq!(&frame[8..10], &[0xBE, 0xEF]);
+    }
+}
+
+#[test]
+fn test_stream_overrides_apply_to_single_frame() {
+    let mut stream = create_stream();
+    stream.set_field_value("payload", &[0x00, 0x01]).unwrap();
+
+    assert_eq!(&stream.next(&[]).unwrap()[8..10], &[0x00, 0x01]);
+    let frame = stream.next(&[("payload", &[0xFF, 0xFF])]).unwrap();
+    assert_eq!(&frame[8..10], &[0xFF, 0xFF]);
+    assert_eq!(&stream.next(&[]).unwrap()[8..10], &[0x00, 0x01]);
+
+    // 覆盖序列字段后序列从覆盖值继续递增
+    let frame = stream.next(&[("seq", &[0x01, 0x00])]).unwrap();
+    assert_eq!(&frame[2..4], &[0x01, 0x00]);
+    assert_eq!(&stream.next(&[]).unwrap()[2..4], &[0x01, 0x01]);
+
+    // 覆盖失败时不组装，已应用的覆盖值被恢复
+    assert!(stream
+        .next(&[("payload", &[0x12, 0x34]), ("missing", &[0x00])])
+        .is_err());
+    assert_eq!(
+        stream.assembler().get_field_value("payload").unwrap(),
+        vec![0x00, 0x01]
+    );
+    assert_eq!(stream.frames_emitted(), 5);
+}
@@ -0,0 +1,109 @@
+//! 帧模板功能测试
+//!
+//! 验证FrameTemplate创建的实例共享帧定义并能独立组装
+
+use apdl_core::{
+    Constraint, ConstraintMode, LengthDesc, LengthUnit, ScopeDesc, SyntaxUnit, UnitType,
+};
+use apdl_poem::{FrameAssembler, FrameTemplate};
+
+fn create_template() -> FrameTemplate {
+    let sync_field = SyntaxUnit {
+        constraint: Some(Constraint::FixedValue(0xEB90)),
+        desc: "Sync word".to_string(),
+        ..SyntaxUnit::new(
+            "sync",
+            UnitType::Uint(16),
+            LengthDesc {
+                size: 2,
+                unit: LengthUnit::Byte,
+            },
+            ScopeDesc::Global("test".to_string()),
+        )
+    };
+
+    let counter_field = SyntaxUnit {
+        desc: "Frame counter".to_string(),
+        ..SyntaxUnit::new(
+            "counter",
+            UnitType::Uint(8),
+            LengthDesc {
+                size: 1,
+                unit: LengthUnit::Byte,
+            },
+            ScopeDesc::Global("test".to_string()),
+        )
+    };
+
+    FrameTemplate::new(vec![sync_field, counter_field], vec![])
+}
+
+#[test]
+fn test_template_instances_share_definition() {
+    let template = create_template();
+
+    let mut instances: Vec<FrameAssembler> = (0..10).map(|_| template.instance()).collect();
+
+    // 所有实例共享同一份帧定义
+    for instance in &instances {
+        assert!(template.is_shared_with(instance));
+        assert_eq!(instance.get_field_names(), vec!["sync", "counter"]);
+    }
+
+    // 每个实例独立设置字段值并组装
+    for (i, instance) in instances.iter_mut().enumerate() {
+        instance.set_field_value("counter", &[i as u8]).unwrap();
+    }
+
+    for (i, instance) in instances.iter_mut().enumerate() {
+        let frame = instance.assemble_frame().unwrap();
+        assert_eq!(frame, vec![0xEB, 0x90, i as u8]);
+    }
+
+    // 组装后仍然共享定义，模板本身不包含字段值
+    assert!(instances
+        .iter()
+        .all(|instance| template.is_shared_with(instance)));
+    let mut fresh = template.instance();
+    assert_eq!(fresh.assemble_frame().unwrap(), vec![0xEB, 0x90, 0x00]);
+}
+
+#[test]
+fn test_instance_add_field_does_not_affect_template() {
+    let template = create_template();
+    let mut instance = template.instance();
+
+    instance.add_field(SyntaxUnit {
+        desc: "Extra field".to_string(),
+        ..SyntaxUnit::new(
+            "extra",
+            UnitType::Uint(8),
+            LengthDesc {
+                size: 1,
+                unit: LengthUnit::Byte,
+            },
+            ScopeDesc::Global("test".to_string()),
+        )
+    });
+
+    // 修改实例的字段布局时复制一份定义，模板保持不变
+    assert!(!template.is_shared_with(&instance));
+    assert_eq!(template.fields().len(), 2);
+    assert_eq!(instance.fields.len(), 3);
+}
+
+#[test]
+fn test_from_assembler_keeps_configuration_without_values() {
+    let mut assembler = create_template().instance();
+    assembler.constraint_mode = ConstraintMode::Strict;
+    assembler.strict_checksum_scope = true;
+    assembler.set_field_value("counter", &[0x05]).unwrap();
+    assembler.skipped_fields.insert("counter".to_string());
+
+    // 模板保留组装器的全部配置，但不保留字段值和上次组装的状态
+    let instance = FrameTemplate::from_assembler(&assembler).instance();
+    assert_eq!(instance.constraint_mode, ConstraintMode::Strict);
+    assert!(instance.strict_checksum_scope);
+    assert!(instance.field_values.is_empty());
+    assert!(instance.skipped_fields.is_empty());
+}
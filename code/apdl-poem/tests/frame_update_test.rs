@@ -0,0 +1,68 @@
+//! 帧数据原位更新测试
+//!
+//! 验证动态长度字段更新为不同长度的值时，后续字段正确平移
+
+mod common;
+
+use apdl_core::ProtocolError;
+use apdl_poem::FrameAssembler;
+use common::assembler_from_dsl;
+
+const FRAME_DSL: &str = r#"
+field: header; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Header"
+field: data; type: RawData; length: dynamic; scope: layer(application); cover: entire_field; desc: "Data"
+field: trailer; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Trailer"
+"#;
+
+fn create_frame() -> (FrameAssembler, Vec<u8>) {
+    let mut assembler = assembler_from_dsl(FRAME_DSL);
+    assembler.set_field_value("header", &[0xAA, 0xBB]).unwrap();
+    assembler.set_field_value("data", &[0x01, 0x02]).unwrap();
+    assembler.set_field_value("trailer", &[0xCC, 0xDD]).unwrap();
+
+    let frame = assembler.assemble_frame().unwrap();
+    assert_eq!(frame, vec![0xAA, 0xBB, 0x01, 0x02, 0xCC, 0xDD]);
+    (assembler, frame)
+}
+
+#[test]
+fn test_grow_dynamic_field_shifts_downstream_fields() {
+    let (mut assembler, mut frame) = create_frame();
+
+    assembler
+        .update_frame_data_at_position(&mut frame, 1, &[0x09, 0x08, 0x07, 0x06])
+        .unwrap();
+    assert_eq!(frame, vec![0xAA, 0xBB, 0x09, 0x08, 0x07, 0x06, 0xCC, 0xDD]);
+
+    // 后续字段的偏移量随之更新
+    assert_eq!(assembler.get_field_position("trailer").unwrap(), 6);
+    let parsed = assembler.parse_frame(&frame).unwrap();
+    assert_eq!(parsed[1].1, vec![0x09, 0x08, 0x07, 0x06]);
+    assert_eq!(parsed[2].1, vec![0xCC, 0xDD]);
+
+    // 更新trailer写入平移后的位置
+    assembler
+        .update_frame_data_at_position(&mut frame, 2, &[0x11, 0x22])
+        .unwrap();
+    assert_eq!(&frame[6..], &[0x11, 0x22]);
+}
+
+#[test]
+fn test_shrink_dynamic_field() {
+    let (mut assembler, mut frame) = create_frame();
+
+    assembler
+        .update_frame_data_at_position(&mut frame, 1, &[0x05])
+        .unwrap();
+    assert_eq!(frame, vec![0xAA, 0xBB, 0x05, 0xCC, 0xDD]);
+    assert_eq!(assembler.get_field_position("trailer").unwrap(), 3);
+}
+
+#[test]
+fn test_fixed_field_size_change_rejected() {
+    let (mut assembler, mut frame) = create_frame();
+
+    let result = assembler.update_frame_data_at_position(&mut frame, 0, &[0x01, 0x02, 0x03]);
+    assert!(matches!(result, Err(ProtocolError::LengthError(_))));
+    assert_eq!(frame, vec![0xAA, 0xBB, 0x01, 0x02, 0xCC, 0xDD]);
+}
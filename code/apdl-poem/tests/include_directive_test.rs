@@ -0,0 +1,112 @@
+//! DSL包含指令测试
+//!
+//! 验证`@include`按解析器回调加载文件、原位展开定义并检测循环包含
+
+use apdl_core::{DslParseError, SemanticRule};
+use apdl_poem::DslParserImpl;
+use std::collections::HashMap;
+
+fn resolver(files: &[(&str, &str)]) -> impl Fn(&str) -> Result<String, String> {
+    let files: HashMap<String, String> = files
+        .iter()
+        .map(|(name, content)| (name.to_string(), content.to_string()))
+        .collect();
+    move |name: &str| {
+        files
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("no such file: {name}"))
+    }
+}
+
+const COMMON_FIELDS: &str = r#"// 公共主导头字段
+field: version; type: Bit(3); length: 3bit; scope: layer(net); cover: entire_field; desc: "Version"
+field: apid; type: Bit(11); length: 11bit; scope: layer(net); cover: entire_field; desc: "APID"
+"#;
+
+const TRAILER: &str = r#"@include "common_fields.apdl"
+field: crc; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; alg: crc16; desc: "CRC"
+rule: checksum_range(start: version to apid);
+"#;
+
+#[test]
+fn test_include_splices_units_in_place() {
+    let files = resolver(&[
+        ("common_fields.apdl", COMMON_FIELDS),
+        ("trailer.apdl", TRAILER),
+    ]);
+    let dsl = r#"field: sync; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Sync"
+@include "common_fields.apdl"
+field: data; type: RawData; length: 0; scope: layer(app); cover: entire_field; desc: "Data"
+@include "trailer.apdl";
+"#;
+
+    let (units, rules) = DslParserImpl::new()
+        .parse_with_resolver(dsl, &files)
+        .unwrap();
+
+    let names: Vec<_> = units.iter().map(|unit| unit.field_id.as_str()).collect();
+    assert_eq!(
+        names,
+        ["sync", "version", "apid", "data", "version", "apid", "crc"]
+    );
+    assert_eq!(rules.len(), 1);
+    assert!(matches!(rules[0], SemanticRule::ChecksumRange { .. }));
+}
+
+#[test]
+fn test_cyclic_include_is_rejected() {
+    let files = resolver(&[
+        ("a.apdl", "@include \"b.apdl\""),
+        ("b.apdl", "@include \"a.apdl\""),
+    ]);
+    let err = DslParserImpl::new()
+        .parse_with_resolver("@include \"a.apdl\"", &files)
+        .unwrap_err();
+
+    assert_eq!(
+        err,
+        DslParseError::ParseError("Cyclic include: a.apdl -> b.apdl -> a.apdl".to_string())
+    );
+}
+
+#[test]
+fn test_include_errors_name_the_file() {
+    let files = resolver(&[(
+        "bad.apdl",
+        "field: x; type: Uint8; length: onebyte; scope: layer(app); cover: entire_field; desc: \"X\"",
+    )]);
+    let parser = DslParserImpl::new();
+
+    let err = parser
+        .parse_with_resolver("@include \"bad.apdl\"", &files)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Parse error: bad.apdl:1:32: Invalid byte length: onebyte (at 'onebyte')"
+    );
+
+    let err = parser
+        .parse_with_resolver("@include \"missing.apdl\"", &files)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Parse error: Cannot include 'missing.apdl': no such file: missing.apdl"
+    );
+
+    // 未提供解析回调时包含指令报错
+    assert!(parser
+        .parse_protocol_structure("@include \"bad.apdl\"")
+        .is_err());
+    let err = parser
+        .parse_semantic_rules("rule: checksum_range(start: a to b);\n@include \"rules.apdl\"")
+        .unwrap_err();
+    assert!(
+        matches!(
+            &err,
+            DslParseError::Syntax { line: 2, column: 1, message, .. }
+                if message == "Include directives require parse_with_resolver"
+        ),
+        "{err:?}"
+    );
+}
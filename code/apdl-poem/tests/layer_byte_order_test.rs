@@ -0,0 +1,110 @@
+//! 层级默认字节序测试
+//!
+//! 验证层内字段未声明字节序时使用层默认字节序，字段级字节序优先，且拆包器按同样的字节序解码
+
+mod common;
+
+use apdl_core::ByteOrder;
+use apdl_lsk::FrameDisassembler;
+use apdl_poem::FrameAssembler;
+use common::{assembler_from_dsl, disassembler_from_dsl};
+
+const FRAME_DSL: &str = r#"
+field: sync; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Sync marker"
+field: count; type: Uint16; length: 2byte; scope: layer(app); cover: entire_field; desc: "Sample count"
+field: offset; type: Uint32; length: 4byte; scope: layer(app); cover: entire_field; desc: "Sample offset"
+field: tag; type: Uint16; length: 2byte; scope: layer(app); cover: entire_field; desc: "Tag"
+"#;
+
+fn create_assembler() -> FrameAssembler {
+    let mut assembler = assembler_from_dsl(FRAME_DSL);
+    // 帧头为大端，负载子协议默认小端，tag字段单独声明为大端
+    assembler.set_layer_byte_order("app", ByteOrder::LittleEndian);
+    assembler.set_field_byte_order("tag", ByteOrder::BigEndian);
+    assembler
+}
+
+fn create_disassembler() -> FrameDisassembler {
+    let mut disassembler = disassembler_from_dsl(FRAME_DSL);
+    disassembler.set_layer_byte_order("app", ByteOrder::LittleEndian);
+    disassembler.set_field_byte_order("tag", ByteOrder::BigEndian);
+    disassembler
+}
+
+#[test]
+fn test_layer_default_with_field_override() {
+    let assembler = create_assembler();
+
+    assert_eq!(assembler.get_field_byte_order("sync"), ByteOrder::BigEndian);
+    assert_eq!(
+        assembler.get_field_byte_order("count"),
+        ByteOrder::LittleEndian
+    );
+    assert_eq!(
+        assembler.get_field_byte_order("offset"),
+        ByteOrder::LittleEndian
+    );
+    assert_eq!(assembler.get_field_byte_order("tag"), ByteOrder::BigEndian);
+}
+
+#[test]
+fn test_assemble_mixed_endian_frame() {
+    let mut assembler = create_assembler();
+    assembler.set_field_value("sync", &[0x1A, 0xCF]).unwrap();
+    assembler.set_field_value("count", &[0x34, 0x12]).unwrap();
+    assembler
+        .set_field_value("offset", &[0x78, 0x56, 0x34, 0x12])
+        .unwrap();
+    assembler.set_field_value("tag", &[0xAB, 0xCD]).unwrap();
+
+    let frame = assembler.assemble_frame().unwrap();
+    assert_eq!(
+        frame,
+        vec![0x1A, 0xCF, 0x34, 0x12, 0x78, 0x56, 0x34, 0x12, 0xAB, 0xCD]
+    );
+
+    // 对外读取时按各字段生效的字节序返回
+    assert_eq!(
+        assembler.get_field_value("count").unwrap(),
+        vec![0x34, 0x12]
+    );
+    assert_eq!(assembler.get_field_value("tag").unwrap(), vec![0xAB, 0xCD]);
+}
+
+#[test]
+fn test_layer_default_does_not_affect_other_layers() {
+    let mut assembler = assembler_from_dsl(FRAME_DSL);
+    assembler.set_layer_byte_order("net", ByteOrder::LittleEndian);
+
+    assert_eq!(assembler.get_field_byte_order("sync"), ByteOrder::BigEndian);
+    assert_eq!(
+        assembler.get_field_byte_order("count"),
+        ByteOrder::BigEndian
+    );
+}
+
+#[test]
+fn test_little_endian_layer_round_trip() {
+    let mut assembler = create_assembler();
+    assembler.set_field_value("sync", &[0x1A, 0xCF]).unwrap();
+    assembler.set_field_value("count", &[0x34, 0x12]).unwrap();
+    assembler
+        .set_field_value("offset", &[0x78, 0x56, 0x34, 0x12])
+        .unwrap();
+    assembler.set_field_value("tag", &[0xAB, 0xCD]).unwrap();
+    let frame = assembler.assemble_frame().unwrap();
+
+    let parsed = create_disassembler().parse_frame_named(&frame).unwrap();
+    let value = |name: &str| {
+        let field = parsed.iter().find(|field| field.name == name).unwrap();
+        (field.numeric_value(), field.byte_order)
+    };
+    assert_eq!(value("sync"), (Some(0x1ACF), ByteOrder::BigEndian));
+    assert_eq!(value("count"), (Some(0x1234), ByteOrder::LittleEndian));
+    assert_eq!(
+        value("offset"),
+        (Some(0x1234_5678), ByteOrder::LittleEndian)
+    );
+    assert_eq!(value("tag"), (Some(0xABCD), ByteOrder::BigEndian));
+    assert_eq!(parsed[1].decoded, "4660");
+}
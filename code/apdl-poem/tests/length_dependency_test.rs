@@ -0,0 +1,66 @@
+//! 嵌套长度依赖测试
+//!
+//! 验证长度规则按val()引用的依赖顺序求值，并检测循环依赖
+
+mod common;
+
+use apdl_core::{ProtocolError, SemanticRule};
+use apdl_poem::FrameAssembler;
+use common::assembler_from_dsl;
+
+const FRAME_DSL: &str = r#"
+field: total_len; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Total length"
+field: header; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Header"
+field: payload_len; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Payload length"
+field: payload; type: RawData; length: dynamic; scope: layer(application); cover: entire_field; desc: "Payload"
+field: trailer; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Trailer"
+rule: length_rule(field: total_len equals "val(payload_len) + len(total_len) + len(header) + len(payload_len) + len(trailer)");
+rule: length_rule(field: payload_len equals "len(payload)");
+"#;
+
+fn create_assembler(dsl: &str) -> FrameAssembler {
+    let mut assembler = assembler_from_dsl(dsl);
+    assembler.set_field_value("header", &[0x1A, 0xCF]).unwrap();
+    assembler.set_field_value("trailer", &[0x55, 0xAA]).unwrap();
+    assembler
+}
+
+#[test]
+fn test_total_length_depends_on_payload_length() {
+    let mut assembler = create_assembler(FRAME_DSL);
+
+    for payload_len in [3usize, 10, 257] {
+        assembler
+            .set_field_value("payload", &vec![0xA5; payload_len])
+            .unwrap();
+        let frame = assembler.assemble_frame().unwrap();
+
+        // total_len规则声明在前，仍使用本次计算的payload_len
+        let total = u16::from_be_bytes([frame[0], frame[1]]) as usize;
+        let payload_field = u16::from_be_bytes([frame[4], frame[5]]) as usize;
+        assert_eq!(payload_field, payload_len);
+        assert_eq!(total, payload_len + 8);
+        assert_eq!(total, frame.len());
+    }
+}
+
+#[test]
+fn test_circular_length_dependency_errors() {
+    let mut assembler = create_assembler(FRAME_DSL);
+    assembler.add_semantic_rule(SemanticRule::LengthRule {
+        field_name: "header".to_string(),
+        expression: "val(trailer) + 1".to_string(),
+    });
+    assembler.add_semantic_rule(SemanticRule::LengthRule {
+        field_name: "trailer".to_string(),
+        expression: "val(header) + 1".to_string(),
+    });
+    assembler.set_field_value("payload", &[0x01]).unwrap();
+
+    match assembler.assemble_frame() {
+        Err(ProtocolError::DependencyError(msg)) => {
+            assert!(msg.contains("header") && msg.contains("trailer"));
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+}
@@ -0,0 +1,100 @@
+//! 长度表达式求值测试
+//!
+//! 验证长度表达式的运算符优先级、嵌套括号、饱和减法以及len()/pos()/total_length替换
+
+mod common;
+
+use apdl_core::{ProtocolError, SemanticRule};
+use apdl_poem::FrameAssembler;
+use common::assembler_from_dsl;
+
+fn create_assembler() -> FrameAssembler {
+    let dsl = r#"
+        field: header; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Header"
+        field: bit_len; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Payload length in bits"
+        field: payload; type: RawData; length: dynamic; scope: layer(link); cover: entire_field; desc: "Payload"
+    "#;
+
+    let mut assembler = assembler_from_dsl(dsl);
+    assembler.set_field_value("header", &[0xAB, 0xCD]).unwrap();
+    assembler
+        .set_field_value("payload", &[0x01, 0x02, 0x03, 0x04, 0x05])
+        .unwrap();
+    assembler
+}
+
+#[test]
+fn test_operator_precedence() {
+    let assembler = create_assembler();
+    let eval = |expr: &str| assembler.evaluate_length_expression(expr, &[]).unwrap();
+
+    assert_eq!(eval("2 + 3 * 4"), 14);
+    assert_eq!(eval("20 - 6 / 2"), 17);
+    assert_eq!(eval("10 - 4 - 3"), 3);
+    assert_eq!(eval("64 / 4 / 2"), 8);
+    assert_eq!(eval("len(payload) * 8 + 1"), 41);
+    assert_eq!(eval("0x10 * 2"), 32);
+}
+
+#[test]
+fn test_nested_parentheses() {
+    let assembler = create_assembler();
+    let eval = |expr: &str| assembler.evaluate_length_expression(expr, &[]).unwrap();
+
+    assert_eq!(eval("(len(payload) + 3) * 8"), 64);
+    assert_eq!(eval("((2 + 3) * (4 - 1))"), 15);
+    assert_eq!(eval("(pos(payload) + len(payload)) - (1)"), 8);
+    assert_eq!(eval("max(len(payload), 2) * (1 + 1)"), 10);
+    assert_eq!(eval("(2 * (3 + (4 - 1))) / 3"), 4);
+}
+
+#[test]
+fn test_saturating_semantics() {
+    let assembler = create_assembler();
+    let eval = |expr: &str| assembler.evaluate_length_expression(expr, &[]).unwrap();
+
+    assert_eq!(eval("len(payload) - 10"), 0);
+    assert_eq!(eval("(1 - 2) * 5 + 3"), 3);
+    assert_eq!(eval("18446744073709551615 + 1"), u64::MAX);
+
+    assert_eq!(
+        assembler.evaluate_length_expression("len(payload) / (2 - 2)", &[]),
+        Err(ProtocolError::InvalidExpression(
+            "Division by zero".to_string()
+        ))
+    );
+    assert!(assembler
+        .evaluate_length_expression("(len(payload) + 1", &[])
+        .is_err());
+}
+
+#[test]
+fn test_total_length_substitution() {
+    let assembler = create_assembler();
+    let frame = [0u8; 12];
+
+    assert_eq!(
+        assembler
+            .evaluate_length_expression("total_length - 3", &frame)
+            .unwrap(),
+        9
+    );
+    assert_eq!(
+        assembler
+            .evaluate_length_expression("(total_length - pos(payload)) * 8", &frame)
+            .unwrap(),
+        64
+    );
+}
+
+#[test]
+fn test_length_rule_with_parenthesized_expression() {
+    let mut assembler = create_assembler();
+    assembler.add_semantic_rule(SemanticRule::LengthRule {
+        field_name: "bit_len".to_string(),
+        expression: "(len(payload) + 3) * 8".to_string(),
+    });
+
+    let frame = assembler.assemble_frame().unwrap();
+    assert_eq!(&frame[2..4], &[0x00, 0x40]);
+}
@@ -0,0 +1,131 @@
+//! 小端字段编码测试
+//!
+//! 验证DSL中`endian: le`声明的字段在组装、长度和校验和回写以及解析时均按小端处理
+
+mod common;
+
+use apdl_core::{ByteOrder, ChecksumAlgorithm, SemanticRule};
+use apdl_poem::{DslParserImpl, FrameAssembler};
+use common::assembler_from_dsl;
+
+const FRAME_DSL: &str = r#"
+field: msg_id; type: Uint16; length: 2byte; scope: layer(app); cover: entire_field; constraint: fixed(0x1234); endian: le; desc: "Message id"
+field: msg_len; type: Uint16; length: 2byte; scope: layer(app); cover: entire_field; endian: le; desc: "Payload length"
+field: counter; type: Uint32; length: 4byte; scope: layer(app); cover: entire_field; endian: be; desc: "Counter"
+field: payload; type: RawData; length: dynamic; scope: layer(app); cover: entire_field; desc: "Payload"
+field: crc; type: Uint16; length: 2byte; scope: layer(app); cover: entire_field; alg: crc16; endian: le; desc: "CRC"
+"#;
+
+fn create_assembler() -> FrameAssembler {
+    let mut assembler = assembler_from_dsl(FRAME_DSL);
+    assembler.add_semantic_rule(SemanticRule::LengthRule {
+        field_name: "msg_len".to_string(),
+        expression: "len(payload)".to_string(),
+    });
+    assembler.add_semantic_rule(SemanticRule::ChecksumRange {
+        algorithm: ChecksumAlgorithm::CRC16,
+        start_field: "msg_id".to_string(),
+        end_field: "payload".to_string(),
+        covers_self: true,
+    });
+    assembler
+        .set_field_value("counter", &[0x00, 0x00, 0x01, 0x02])
+        .unwrap();
+    assembler
+        .set_field_value("payload", &[0xAA, 0xBB, 0xCC])
+        .unwrap();
+    assembler
+}
+
+#[test]
+fn test_parse_endian_attribute() {
+    let assembler = create_assembler();
+    assert_eq!(
+        assembler.get_field_byte_order("msg_id"),
+        ByteOrder::LittleEndian
+    );
+    assert_eq!(
+        assembler.get_field_byte_order("counter"),
+        ByteOrder::BigEndian
+    );
+    assert_eq!(
+        assembler.get_field_byte_order("payload"),
+        ByteOrder::BigEndian
+    );
+
+    let parser = DslParserImpl::new();
+    assert!(parser
+        .parse_protocol_structure(
+            r#"field: x; type: Uint16; length: 2byte; scope: layer(app); cover: entire_field; endian: middle; desc: "X""#
+        )
+        .is_err());
+}
+
+#[test]
+fn test_uint16_serializes_little_endian() {
+    let mut assembler = create_assembler();
+    let frame = assembler.assemble_frame().unwrap();
+
+    // 固定值0x1234按小端写为34 12，长度3写为03 00，大端计数器不受影响
+    assert_eq!(&frame[0..2], &[0x34, 0x12]);
+    assert_eq!(&frame[2..4], &[0x03, 0x00]);
+    assert_eq!(&frame[4..8], &[0x00, 0x00, 0x01, 0x02]);
+    assert_eq!(&frame[8..11], &[0xAA, 0xBB, 0xCC]);
+
+    let crc = assembler.calculate_crc16(&frame[..11]);
+    assert_eq!(&frame[11..], &crc.to_le_bytes());
+    assert_eq!(
+        assembler.get_field_value("msg_len").unwrap(),
+        vec![0x03, 0x00]
+    );
+    assert_eq!(assembler.get_field_value("crc").unwrap(), crc.to_le_bytes());
+}
+
+#[test]
+fn test_parse_frame_round_trip() {
+    let mut assembler = create_assembler();
+    let frame = assembler.assemble_frame().unwrap();
+
+    let parsed = assembler.parse_frame(&frame).unwrap();
+    for (field_name, value) in &parsed {
+        assert_eq!(
+            &assembler.get_field_value(field_name).unwrap(),
+            value,
+            "{field_name}"
+        );
+    }
+
+    // 解析出的字段值可直接用于重新组装同一帧
+    let mut rebuilt = create_assembler();
+    for (field_name, value) in parsed {
+        if rebuilt.overwriting_rule_kind(&field_name).is_none() {
+            rebuilt.set_field_value(&field_name, &value).unwrap();
+        }
+    }
+    assert_eq!(rebuilt.assemble_frame().unwrap(), frame);
+}
+
+#[test]
+fn test_little_endian_algorithm_selector() {
+    let dsl = r#"
+field: mode; type: Uint16; length: 2byte; scope: layer(app); cover: entire_field; endian: le; desc: "Mode"
+field: payload; type: Uint32; length: 4byte; scope: layer(app); cover: entire_field; desc: "Payload"
+field: crc; type: Uint32; length: 4byte; scope: layer(app); cover: entire_field; alg: crc16; desc: "CRC"
+rule: checksum_range(start: mode to payload);
+rule: algorithm_select(mode: 0 => crc16, 1 => crc32);
+"#;
+    let mut assembler = assembler_from_dsl(dsl);
+    assembler.set_field_value("mode", &[0x01, 0x00]).unwrap();
+    assembler
+        .set_field_value("payload", &[0x12, 0x34, 0x56, 0x78])
+        .unwrap();
+    let mut frame = assembler.assemble_frame().unwrap();
+
+    // 接收端未设置选择字段，按帧中的小端值1选择CRC32验证
+    let receiver = assembler_from_dsl(dsl);
+    assert!(receiver.validate_checksum_rules(&frame).is_ok());
+
+    // 按大端写入的选择字段读作256，没有对应的算法
+    frame[..2].copy_from_slice(&[0x00, 0x01]);
+    assert!(receiver.validate_checksum_rules(&frame).is_err());
+}
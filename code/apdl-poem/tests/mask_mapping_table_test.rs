@@ -77,3 +77,50 @@ fn test_parse_mask_mapping_table_decimal() {
         assert_eq!(table[0].dst, vec![53]);
     }
 }
+
+#[test]
+fn test_connector_mask_default_fits_target_field() {
+    use apdl_core::{FieldMappingEntry, MaskMappingEntry};
+    use apdl_poem::standard_units::connector::ConnectorEngine;
+    use apdl_poem::standard_units::frame_assembler::core::FrameAssembler;
+
+    let parser = DslParserImpl::new();
+    let assembler_with = |dsl: &str| {
+        let mut assembler = FrameAssembler::new();
+        for unit in parser.parse_protocol_structure(dsl).unwrap() {
+            assembler.add_field(unit);
+        }
+        assembler
+    };
+    let mappings = vec![FieldMappingEntry {
+        source_field: "apid".to_string(),
+        target_field: "vcid".to_string(),
+        mapping_logic: "mask_table".to_string(),
+        default_value: "0x7F".to_string(),
+        enum_mappings: None,
+        mask_mapping_table: Some(vec![MaskMappingEntry {
+            mask: vec![0xFF, 0xF0],
+            src_masked: vec![0x04, 0x80],
+            dst: vec![0x35],
+        }]),
+    }];
+
+    for (apid, expected) in [([0x04, 0x81], 0x35), ([0x05, 0x00], 0x7F)] {
+        let mut source_assembler = assembler_with(
+            r#"field: apid; type: Uint16; length: 2byte; scope: layer(net); cover: entire_field; desc: "APID""#,
+        );
+        source_assembler.set_field_value("apid", &apid).unwrap();
+        let mut target_assembler = assembler_with(
+            r#"field: vcid; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "VCID""#,
+        );
+
+        // 未匹配时的默认值按1字节目标字段截取
+        ConnectorEngine::new()
+            .apply_field_mapping_rules(&source_assembler, &mut target_assembler, "", &mappings)
+            .unwrap();
+        assert_eq!(
+            target_assembler.get_field_value("vcid").unwrap(),
+            vec![expected]
+        );
+    }
+}
@@ -38,6 +38,8 @@ fn test_mpdu_comprehensive_scenario() {
         associate: vec![],
         desc: "测试数据字段".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     // 为每个子包创建不同长度的字段定义
@@ -220,6 +222,8 @@ fn create_parent_template_with_data_field_size(data_size: usize) -> FrameAssembl
         associate: vec![],
         desc: "MPDU首导头指针".to_string(),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     // 添加数据字段
@@ -237,6 +241,8 @@ fn create_parent_template_with_data_field_size(data_size: usize) -> FrameAssembl
         associate: vec![],
         desc: format!("数据字段 ({data_size} 字节)"),
         pack_unpack_spec: None,
+        unit_label: None,
+        long_description: None,
     };
 
     assembler.add_field(pointer_field);
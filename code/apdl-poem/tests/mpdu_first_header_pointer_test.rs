@@ -0,0 +1,126 @@
+//! MPDU首导头指针测试
+//!
+//! 验证变长子包装入定长M_PDU时，11位首导头指针指向数据区中第一个子包的起始位置，
+//! 子包跨越整个数据区时写入"无包起始"值
+
+use apdl_core::{
+    ConnectorConfig, DataPlacementConfig, DataPlacementStrategy, LengthDesc, LengthUnit, ScopeDesc,
+    SyntaxUnit, UnitType,
+};
+use apdl_poem::standard_units::connector::{ConnectorEngine, MPDU_NO_PACKET_START};
+use apdl_poem::standard_units::frame_assembler::core::FrameAssembler;
+
+const DATA_ZONE: usize = 10;
+
+fn make_field(name: &str, unit_type: UnitType, size: usize, unit: LengthUnit) -> SyntaxUnit {
+    SyntaxUnit::new(
+        name,
+        unit_type,
+        LengthDesc { size, unit },
+        ScopeDesc::Global("test".to_string()),
+    )
+}
+
+/// 子包：1字节长度字段 + 数据
+fn create_child(index: u8, total_len: usize) -> FrameAssembler {
+    let data_len = total_len - 1;
+    let mut assembler = FrameAssembler::new();
+    assembler.add_field(make_field("len", UnitType::Uint(8), 1, LengthUnit::Byte));
+    assembler.add_field(make_field(
+        "data",
+        UnitType::RawData,
+        data_len,
+        LengthUnit::Byte,
+    ));
+    assembler.set_field_value("len", &[data_len as u8]).unwrap();
+    let data: Vec<u8> = (0..data_len).map(|i| index * 0x10 + i as u8).collect();
+    assembler.set_field_value("data", &data).unwrap();
+    assembler
+}
+
+/// M_PDU：5位备用位 + 11位首导头指针 + 10字节数据区
+fn create_mpdu() -> FrameAssembler {
+    let mut assembler = FrameAssembler::new();
+    assembler.add_field(make_field("spare", UnitType::Bit(5), 5, LengthUnit::Bit));
+    assembler.add_field(make_field(
+        "first_header_pointer",
+        UnitType::Bit(11),
+        11,
+        LengthUnit::Bit,
+    ));
+    assembler.add_field(make_field(
+        "data",
+        UnitType::RawData,
+        DATA_ZONE,
+        LengthUnit::Byte,
+    ));
+    assembler
+}
+
+/// 依次装入给定总长度的子包，返回生成的M_PDU
+fn build_mpdus(packet_lens: &[usize]) -> Vec<Vec<u8>> {
+    let config = DataPlacementConfig {
+        strategy: DataPlacementStrategy::PointerBased,
+        target_field: "data".to_string(),
+        config_params: vec![
+            (
+                "pointer_field".to_string(),
+                "first_header_pointer".to_string(),
+            ),
+            ("padding_value".to_string(), "0xFF".to_string()),
+        ],
+    };
+    let connector_config = ConnectorConfig {
+        mappings: vec![],
+        header_pointers: None,
+        data_placement: Some(config.clone()),
+    };
+
+    let mut engine = ConnectorEngine::new();
+    for (index, &len) in packet_lens.iter().enumerate() {
+        let mut child = create_child(index as u8 + 1, len);
+        let mut parent = create_mpdu();
+        engine
+            .connect(&mut child, &mut parent, "vc0", &connector_config)
+            .unwrap();
+    }
+
+    let mut frames = Vec::new();
+    while let Some((frame, _)) = engine.build_packet(&config) {
+        frames.push(frame);
+    }
+    frames
+}
+
+fn pointer_of(frame: &[u8]) -> u16 {
+    u16::from_be_bytes([frame[0], frame[1]]) & MPDU_NO_PACKET_START
+}
+
+#[test]
+fn test_pointer_marks_packet_starting_mid_frame() {
+    // 子包1占4字节，子包2从偏移4开始并续传到下一个M_PDU
+    let frames = build_mpdus(&[4, 12, 3]);
+    let pointers: Vec<_> = frames.iter().map(|frame| pointer_of(frame)).collect();
+    // 第二个M_PDU中子包2的剩余6字节之后子包3从偏移6开始
+    assert_eq!(pointers, [0, 6]);
+    assert!(frames.iter().all(|frame| frame.len() == 2 + DATA_ZONE));
+    // 备用位保持为0
+    assert!(frames.iter().all(|frame| frame[0] & 0xF8 == 0));
+
+    let second = &frames[1][2..];
+    assert_eq!(second[6], 2, "子包3的长度字段位于指针所指位置");
+    assert_eq!(&second[9..], &[0xFF]);
+}
+
+#[test]
+fn test_packet_spanning_whole_mpdu_uses_no_packet_start() {
+    // 子包2共25字节：第一个M_PDU中占6字节，第二个M_PDU全部为其续传数据，第三个M_PDU中剩余9字节后子包3开始
+    let frames = build_mpdus(&[4, 25, 3]);
+    let pointers: Vec<_> = frames.iter().map(|frame| pointer_of(frame)).collect();
+    assert_eq!(pointers, [0, MPDU_NO_PACKET_START, 9, MPDU_NO_PACKET_START]);
+
+    // 最后一个M_PDU只有子包3的续传数据和填充
+    let last = &frames[3][2..];
+    assert_eq!(&last[..2], &[0x30, 0x31]);
+    assert!(last[2..].iter().all(|&byte| byte == 0xFF));
+}
@@ -0,0 +1,155 @@
+//! MPDU重组测试
+//!
+//! 验证由MPDU包构建生成的父包经首导头指针重组后得到原始子包
+
+use apdl_core::{
+    ConnectorConfig, DataPlacementConfig, DataPlacementStrategy, LengthDesc, LengthUnit, ScopeDesc,
+    SyntaxUnit, UnitType,
+};
+use apdl_poem::standard_units::connector::{
+    ConnectorEngine, MpduReassembler, MPDU_IDLE_DATA, MPDU_NO_PACKET_START,
+};
+use apdl_poem::standard_units::frame_assembler::core::FrameAssembler;
+
+fn make_field(name: &str, unit_type: UnitType, size: usize) -> SyntaxUnit {
+    SyntaxUnit::new(
+        name,
+        unit_type,
+        LengthDesc {
+            size,
+            unit: LengthUnit::Byte,
+        },
+        ScopeDesc::Global("test".to_string()),
+    )
+}
+
+/// 子包：1字节长度字段 + 数据
+fn create_child(index: u8, data_len: usize) -> FrameAssembler {
+    let mut assembler = FrameAssembler::new();
+    assembler.add_field(make_field("len", UnitType::Uint(8), 1));
+    assembler.add_field(make_field("data", UnitType::RawData, data_len));
+    assembler.set_field_value("len", &[data_len as u8]).unwrap();
+    let data: Vec<u8> = (0..data_len).map(|i| index * 0x10 + i as u8).collect();
+    assembler.set_field_value("data", &data).unwrap();
+    assembler
+}
+
+/// 父包：2字节首导头指针 + 8字节数据区
+fn create_parent() -> FrameAssembler {
+    let mut assembler = FrameAssembler::new();
+    assembler.add_field(make_field("pointer", UnitType::Uint(16), 2));
+    assembler.add_field(make_field("data", UnitType::RawData, 8));
+    assembler.set_field_value("pointer", &[0x00, 0x00]).unwrap();
+    assembler
+}
+
+fn mpdu_config() -> DataPlacementConfig {
+    DataPlacementConfig {
+        strategy: DataPlacementStrategy::PointerBased,
+        target_field: "data".to_string(),
+        config_params: vec![
+            ("pointer_field".to_string(), "pointer".to_string()),
+            ("padding_value".to_string(), "0xFF".to_string()),
+        ],
+    }
+}
+
+fn child_packet_length(packet: &[u8]) -> Option<usize> {
+    packet.first().map(|&len| len as usize + 1)
+}
+
+/// 使用MPDU包构建生成父包，返回原始子包和父包
+fn build_frames(data_lens: &[usize]) -> (Vec<Vec<u8>>, Vec<Vec<u8>>) {
+    let config = mpdu_config();
+    let connector_config = ConnectorConfig {
+        mappings: vec![],
+        header_pointers: None,
+        data_placement: Some(config.clone()),
+    };
+
+    let mut engine = ConnectorEngine::new();
+    let mut originals = Vec::new();
+    for (index, &data_len) in data_lens.iter().enumerate() {
+        let mut child = create_child(index as u8 + 1, data_len);
+        originals.push(child.assemble_frame().unwrap());
+        let mut parent = create_parent();
+        engine
+            .connect(&mut child, &mut parent, "vc0", &connector_config)
+            .unwrap();
+    }
+
+    let mut frames = Vec::new();
+    while let Some((frame, _)) = engine.build_packet(&config) {
+        frames.push(frame);
+    }
+    (originals, frames)
+}
+
+fn create_reassembler() -> MpduReassembler {
+    MpduReassembler::from_template(
+        &create_parent(),
+        &mpdu_config(),
+        Box::new(child_packet_length),
+    )
+    .unwrap()
+}
+
+fn pointer_of(frame: &[u8]) -> u16 {
+    u16::from_be_bytes([frame[0], frame[1]])
+}
+
+#[test]
+fn test_reassembly_yields_original_packets() {
+    let (originals, frames) = build_frames(&[4, 11, 2, 19, 6]);
+    assert_eq!(frames.len(), 6);
+    // 19字节数据的子包跨越3个父包，中间的父包没有包起始
+    assert!(frames
+        .iter()
+        .any(|frame| pointer_of(frame) == MPDU_NO_PACKET_START));
+
+    let mut reassembler = create_reassembler();
+    let mut packets = Vec::new();
+    for frame in &frames {
+        packets.extend(reassembler.push_frame(frame).unwrap());
+    }
+
+    assert_eq!(packets, originals);
+    assert_eq!(reassembler.pending_len(), 0);
+    assert_eq!(reassembler.discarded_bytes(), 0);
+}
+
+#[test]
+fn test_reassembly_resynchronizes_after_lost_frame() {
+    let (originals, frames) = build_frames(&[4, 11, 2, 19, 6]);
+
+    // 丢失第二个父包：跨越该父包的子包被丢弃，从下一个首导头指针处恢复
+    let mut reassembler = create_reassembler();
+    let mut packets = Vec::new();
+    for (index, frame) in frames.iter().enumerate() {
+        if index != 1 {
+            packets.extend(reassembler.push_frame(frame).unwrap());
+        }
+    }
+
+    assert_eq!(packets[0], originals[0]);
+    assert_eq!(&packets[1..], &originals[2..]);
+    assert!(reassembler.discarded_bytes() > 0);
+}
+
+#[test]
+fn test_idle_and_no_start_frames_without_sync() {
+    let mut reassembler = create_reassembler();
+
+    let mut idle = MPDU_IDLE_DATA.to_be_bytes().to_vec();
+    idle.extend_from_slice(&[0xFF; 8]);
+    assert!(reassembler.push_frame(&idle).unwrap().is_empty());
+    assert_eq!(reassembler.discarded_bytes(), 0);
+
+    // 尚未同步到包起始时，无包起始父包中的续传数据被丢弃
+    let mut continuation = MPDU_NO_PACKET_START.to_be_bytes().to_vec();
+    continuation.extend_from_slice(&[0x11; 8]);
+    assert!(reassembler.push_frame(&continuation).unwrap().is_empty());
+    assert_eq!(reassembler.discarded_bytes(), 8);
+
+    assert!(reassembler.push_frame(&[0x00, 0x00, 0x01]).is_err());
+}
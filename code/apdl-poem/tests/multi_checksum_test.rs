@@ -0,0 +1,104 @@
+//! 多校验和测试
+//!
+//! 验证同一帧中的多个校验和范围规则分别写入各自的校验字段，并按覆盖关系确定计算顺序
+
+mod common;
+
+use apdl_core::{ChecksumAlgorithm, ProtocolError, SemanticRule};
+use apdl_poem::FrameAssembler;
+use common::assembler_from_dsl;
+
+const FRAME_DSL: &str = r#"
+field: version; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "Version"
+field: hdr_len; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "Header length"
+field: hcrc; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; alg: crc16; desc: "Header check"
+field: payload; type: Uint32; length: 4byte; scope: layer(link); cover: entire_field; desc: "Payload"
+field: pcrc; type: Uint32; length: 4byte; scope: layer(link); cover: entire_field; alg: crc32; desc: "Frame check"
+"#;
+
+fn checksum_range(algorithm: ChecksumAlgorithm, start: &str, end: &str) -> SemanticRule {
+    SemanticRule::ChecksumRange {
+        algorithm,
+        start_field: start.to_string(),
+        end_field: end.to_string(),
+        covers_self: true,
+    }
+}
+
+fn create_assembler(rules: Vec<SemanticRule>) -> FrameAssembler {
+    let mut assembler = assembler_from_dsl(FRAME_DSL);
+    for rule in rules {
+        assembler.add_semantic_rule(rule);
+    }
+    assembler.set_field_value("version", &[0x01]).unwrap();
+    assembler.set_field_value("hdr_len", &[0x04]).unwrap();
+    assembler
+        .set_field_value("payload", &[0xDE, 0xAD, 0xBE, 0xEF])
+        .unwrap();
+    assembler
+}
+
+#[test]
+fn test_header_crc16_and_payload_crc32() {
+    // 整帧CRC32声明在前，但其范围覆盖头部CRC16，须在头部CRC16之后计算
+    let mut assembler = create_assembler(vec![
+        checksum_range(ChecksumAlgorithm::CRC32, "version", "payload"),
+        checksum_range(ChecksumAlgorithm::CRC16, "version", "hdr_len"),
+    ]);
+    let frame = assembler.assemble_frame().unwrap();
+    assert_eq!(frame.len(), 12);
+
+    let hcrc = assembler.compute_checksum(&ChecksumAlgorithm::CRC16, &frame[..2]) as u16;
+    assert_eq!(&frame[2..4], &hcrc.to_be_bytes());
+    assert_ne!(hcrc, 0);
+
+    let pcrc = assembler.compute_checksum(&ChecksumAlgorithm::CRC32, &frame[..8]) as u32;
+    assert_eq!(&frame[8..12], &pcrc.to_be_bytes());
+    assert_eq!(
+        assembler.get_field_value("hcrc").unwrap(),
+        hcrc.to_be_bytes().to_vec()
+    );
+
+    assembler.verify_checksums(&frame).unwrap();
+}
+
+#[test]
+fn test_each_checksum_validated_independently() {
+    let mut assembler = create_assembler(vec![
+        checksum_range(ChecksumAlgorithm::CRC16, "version", "hdr_len"),
+        checksum_range(ChecksumAlgorithm::CRC32, "version", "payload"),
+    ]);
+    let frame = assembler.assemble_frame().unwrap();
+
+    // 只破坏负载：头部CRC16仍然正确，整帧CRC32不匹配
+    let mut corrupted = frame.clone();
+    corrupted[5] ^= 0xFF;
+    match assembler.verify_checksums(&corrupted) {
+        Err(ProtocolError::ChecksumMismatch { field, .. }) => assert_eq!(field, "pcrc"),
+        other => panic!("unexpected result: {other:?}"),
+    }
+
+    // 破坏头部：头部CRC16先检出错误
+    let mut corrupted = frame;
+    corrupted[1] ^= 0xFF;
+    match assembler.verify_checksums(&corrupted) {
+        Err(ProtocolError::ChecksumMismatch { field, .. }) => assert_eq!(field, "hcrc"),
+        other => panic!("unexpected result: {other:?}"),
+    }
+}
+
+#[test]
+fn test_circular_checksum_coverage_errors() {
+    // 两个范围互相覆盖对方的校验字段，无法确定计算顺序
+    let mut assembler = create_assembler(vec![
+        checksum_range(ChecksumAlgorithm::CRC16, "version", "pcrc"),
+        checksum_range(ChecksumAlgorithm::CRC32, "version", "payload"),
+    ]);
+
+    match assembler.assemble_frame() {
+        Err(ProtocolError::DependencyError(msg)) => {
+            assert!(msg.contains("hcrc") && msg.contains("pcrc"));
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+}
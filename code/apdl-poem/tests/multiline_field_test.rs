@@ -0,0 +1,73 @@
+//! 多行字段定义测试
+//!
+//! 验证跨多个物理行的字段定义与单行定义解析结果一致
+
+use apdl_core::DslParseError;
+use apdl_poem::DslParserImpl;
+
+const WRAPPED_DSL: &str = r#"// 主导头
+field: version; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "Version"
+field: apid; type: Uint16
+    ; length: 2byte; scope: layer(net)
+    // 范围约束
+    ; cover: entire_field; constraint: range(0..=2047)
+    ; desc: "APID"
+rule: checksum_range(start: version to apid);
+field: length; type: Uint16; length: 2byte; scope: layer(net); cover: entire_field; desc: "Length"
+"#;
+
+const SINGLE_LINE_APID: &str = r#"field: apid; type: Uint16; length: 2byte; scope: layer(net); cover: entire_field; constraint: range(0..=2047); desc: "APID""#;
+
+#[test]
+fn test_field_wrapped_across_four_lines() {
+    let parser = DslParserImpl::new();
+    let units = parser.parse_protocol_structure(WRAPPED_DSL).unwrap();
+
+    let names: Vec<_> = units.iter().map(|unit| unit.field_id.as_str()).collect();
+    assert_eq!(names, ["version", "apid", "length"]);
+    assert_eq!(
+        units[1],
+        parser.parse_syntax_unit(SINGLE_LINE_APID).unwrap()
+    );
+
+    let rules = parser.parse_semantic_rules(WRAPPED_DSL).unwrap();
+    assert_eq!(rules.len(), 1);
+}
+
+#[test]
+fn test_error_in_continuation_line_reports_physical_line() {
+    let dsl = "field: apid\n  ; type: Uint16\n  ; length: twobyte\n  ; scope: layer(net); cover: entire_field; desc: \"APID\"";
+    let err = DslParserImpl::new()
+        .parse_protocol_structure(dsl)
+        .unwrap_err();
+
+    assert_eq!(
+        err,
+        DslParseError::Syntax {
+            line: 3,
+            column: 13,
+            token: "twobyte".to_string(),
+            message: "Invalid byte length: twobyte".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_line_after_terminated_statement_is_not_a_continuation() {
+    // 上一条定义已以`;`结束，拼写错误的关键字不会被并入其desc
+    let dsl = "field: version; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: \"Version\";\n\
+               \x20 feild: apid; type: Uint16; length: 2byte; scope: layer(net); cover: entire_field";
+    let err = DslParserImpl::new()
+        .parse_protocol_structure(dsl)
+        .unwrap_err();
+
+    assert_eq!(
+        err,
+        DslParseError::Syntax {
+            line: 2,
+            column: 3,
+            token: "feild: apid".to_string(),
+            message: "Unknown statement, expected 'field:', 'rule:' or '@include'".to_string(),
+        }
+    );
+}
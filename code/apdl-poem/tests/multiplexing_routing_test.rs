@@ -0,0 +1,107 @@
+//! 多路复用路由测试
+//!
+//! 验证多路复用规则按帧中字段值将帧路由到不同目标，路由结果只保存当前帧
+
+mod common;
+
+use apdl_poem::FrameAssembler;
+use common::assembler_from_dsl;
+use std::collections::HashMap;
+
+const FRAME_DSL: &str = r#"
+field: version; type: Bit(2); length: 2bit; scope: layer(link); cover: entire_field; desc: "Version"
+field: scid; type: Bit(10); length: 10bit; scope: layer(link); cover: entire_field; desc: "Spacecraft id"
+field: vcid; type: Bit(3); length: 3bit; scope: layer(link); cover: entire_field; desc: "Virtual channel id"
+field: ocf_flag; type: Bit(1); length: 1bit; scope: layer(link); cover: entire_field; desc: "OCF flag"
+field: data; type: RawData; length: 2byte; scope: layer(link); cover: entire_field; desc: "Data"
+"#;
+
+// 按字节对齐的帧头，用于解析端
+const BYTE_ALIGNED_DSL: &str = r#"
+field: scid; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Spacecraft id"
+field: vcid; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "Virtual channel id"
+field: data; type: RawData; length: 2byte; scope: layer(link); cover: entire_field; desc: "Data"
+"#;
+
+const ROUTING_RULES: &str = r#"
+rule: multiplexing(field: vcid; condition: vcid == 0x01; route_to: housekeeping; desc: "VC1遥测");
+rule: multiplexing(field: vcid; condition: vcid == 0x02; route_to: payload; desc: "VC2载荷数据");
+"#;
+
+fn create_assembler(dsl: &str) -> FrameAssembler {
+    let mut assembler = assembler_from_dsl(&format!("{dsl}{ROUTING_RULES}"));
+    assembler.set_field_value("scid", &[0x00, 0x2A]).unwrap();
+    assembler
+}
+
+fn assemble_on_vc(assembler: &mut FrameAssembler, vcid: u8, data: &[u8]) -> Vec<u8> {
+    assembler.set_field_value("vcid", &[vcid]).unwrap();
+    assembler.set_field_value("data", data).unwrap();
+    assembler.assemble_frame().unwrap()
+}
+
+/// 将当前帧的路由结果追加到汇总中
+fn collect_routes(assembler: &mut FrameAssembler, routed: &mut HashMap<String, Vec<Vec<u8>>>) {
+    for (target, frames) in assembler.take_routed_outputs() {
+        routed.entry(target).or_default().extend(frames);
+    }
+}
+
+#[test]
+fn test_route_two_vcids_on_assembly() {
+    let mut assembler = create_assembler(FRAME_DSL);
+    let mut routed = HashMap::new();
+    let vc1_frame = assemble_on_vc(&mut assembler, 1, &[0x11, 0x11]);
+    collect_routes(&mut assembler, &mut routed);
+    let vc2_frame = assemble_on_vc(&mut assembler, 2, &[0x22, 0x22]);
+    assert_eq!(
+        assembler.routed_outputs()["payload"],
+        vec![vc2_frame.clone()]
+    );
+    collect_routes(&mut assembler, &mut routed);
+    let vc3_frame = assemble_on_vc(&mut assembler, 3, &[0x33, 0x33]);
+    collect_routes(&mut assembler, &mut routed);
+    let vc1_second = assemble_on_vc(&mut assembler, 1, &[0x12, 0x12]);
+    collect_routes(&mut assembler, &mut routed);
+    assert_ne!(vc1_frame, vc2_frame);
+    assert_ne!(vc3_frame, vc1_second);
+
+    assert_eq!(routed.len(), 2);
+    assert_eq!(routed["housekeeping"], vec![vc1_frame, vc1_second]);
+    assert_eq!(routed["payload"], vec![vc2_frame]);
+}
+
+#[test]
+fn test_route_parsed_frames_by_vcid() {
+    let mut sender = create_assembler(BYTE_ALIGNED_DSL);
+    let frames: Vec<Vec<u8>> = [(2, 0xA0), (1, 0xB0), (2, 0xC0)]
+        .iter()
+        .map(|&(vcid, byte)| assemble_on_vc(&mut sender, vcid, &[byte, byte]))
+        .collect();
+
+    // 接收端只解析帧，路由由帧中的vcid字段决定
+    let mut receiver = create_assembler(BYTE_ALIGNED_DSL);
+    let mut routed = HashMap::new();
+    for frame in &frames {
+        receiver.parse_frame(frame).unwrap();
+        collect_routes(&mut receiver, &mut routed);
+    }
+
+    assert_eq!(
+        routed["payload"],
+        vec![frames[0].clone(), frames[2].clone()]
+    );
+    assert_eq!(routed["housekeeping"], vec![frames[1].clone()]);
+    assert!(receiver.routed_outputs().is_empty());
+}
+
+#[test]
+fn test_routes_only_hold_current_frame() {
+    let mut assembler = create_assembler(FRAME_DSL);
+    let vc1_frame = assemble_on_vc(&mut assembler, 1, &[0x11, 0x11]);
+    assert_eq!(assembler.routed_outputs()["housekeeping"], vec![vc1_frame]);
+
+    // 未匹配任何规则的帧不保留上一帧的路由
+    assemble_on_vc(&mut assembler, 3, &[0x33, 0x33]);
+    assert!(assembler.routed_outputs().is_empty());
+}
@@ -0,0 +1,149 @@
+//! 被覆盖字段设置警告测试
+//!
+//! 验证为长度规则或校验和规则计算的字段设置值时，Lenient模式记录警告，Strict模式报错；
+//! 为序列控制规则维护的字段设置值时记录警告
+
+mod common;
+
+use apdl_core::{ConstraintMode, ProtocolError};
+use apdl_poem::FrameAssembler;
+use log::{Level, Log, Metadata, Record};
+use std::sync::{Mutex, Once};
+
+const FRAME_DSL: &str = r#"
+field: header; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Header"
+field: length; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "Payload length"
+field: payload; type: RawData; length: dynamic; scope: layer(link); cover: entire_field; desc: "Payload"
+field: fecf; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; alg: crc16; desc: "Frame check"
+rule: length_rule(length equals "len(payload)");
+rule: crc_range(start: header to payload);
+"#;
+
+const SEQUENCE_DSL: &str = r#"
+field: seq_count; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Sequence count"
+field: data; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Data"
+rule: sequence_control(field: seq_count; trigger: on_transmission; algorithm: increment_seq; desc: "Sequence");
+"#;
+
+/// 收集警告日志的测试日志器
+struct CaptureLogger {
+    warnings: Mutex<Vec<String>>,
+}
+
+impl Log for CaptureLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Warn
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            self.warnings
+                .lock()
+                .unwrap()
+                .push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CaptureLogger = CaptureLogger {
+    warnings: Mutex::new(Vec::new()),
+};
+use common::assembler_from_dsl;
+static INIT: Once = Once::new();
+
+/// 获取包含指定字段名的警告
+fn warnings_for(field_name: &str) -> Vec<String> {
+    let pattern = format!("'{field_name}'");
+    LOGGER
+        .warnings
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|message| message.contains(&pattern))
+        .cloned()
+        .collect()
+}
+
+fn init_logger() {
+    INIT.call_once(|| {
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Warn);
+    });
+}
+
+fn create_assembler() -> FrameAssembler {
+    init_logger();
+    assembler_from_dsl(FRAME_DSL)
+}
+
+fn create_sequence_assembler() -> FrameAssembler {
+    init_logger();
+    assembler_from_dsl(SEQUENCE_DSL)
+}
+
+#[test]
+fn test_warn_on_length_rule_field() {
+    let mut assembler = create_assembler();
+    assert!(warnings_for("length").is_empty());
+
+    assembler.set_field_value("length", &[0x10]).unwrap();
+    let warnings = warnings_for("length");
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("length_rule"));
+
+    // 组装时长度字段仍由规则计算
+    assembler.set_field_value("header", &[0x12, 0x34]).unwrap();
+    assembler.set_field_value("payload", &[0xAA, 0xBB]).unwrap();
+    let frame = assembler.assemble_frame().unwrap();
+    assert_eq!(frame[2], 0x02);
+    assert!(warnings_for("header").is_empty());
+    assert!(warnings_for("payload").is_empty());
+}
+
+#[test]
+fn test_warn_on_checksum_field() {
+    let mut assembler = create_assembler();
+    assert_eq!(
+        assembler.overwriting_rule_kind("fecf"),
+        Some("checksum_range")
+    );
+    assert_eq!(assembler.overwriting_rule_kind("header"), None);
+
+    assembler.set_field_value("fecf", &[0x00, 0x00]).unwrap();
+    assert!(!warnings_for("fecf").is_empty());
+}
+
+#[test]
+fn test_error_on_computed_field_in_strict_mode() {
+    let mut assembler = create_assembler();
+    assembler.set_constraint_mode(ConstraintMode::Strict);
+
+    assert!(matches!(
+        assembler.set_field_value("length", &[0x10]),
+        Err(ProtocolError::ValidationError(_))
+    ));
+    assert!(assembler.set_field_value("header", &[0x12, 0x34]).is_ok());
+}
+
+#[test]
+fn test_warn_on_sequence_controlled_field() {
+    let mut assembler = create_sequence_assembler();
+    assembler.set_constraint_mode(ConstraintMode::Strict);
+    assert!(assembler.is_sequence_controlled("seq_count"));
+    assert!(!assembler.is_sequence_controlled("data"));
+
+    // 设置的值作为序列的当前值，Strict模式下也只记录警告
+    assembler
+        .set_field_value("seq_count", &[0x00, 0x05])
+        .unwrap();
+    let warnings = warnings_for("seq_count");
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("sequence_control"));
+
+    assembler.set_field_value("data", &[0x12, 0x34]).unwrap();
+    let frame = assembler.assemble_frame().unwrap();
+    assert_eq!(&frame[..2], &[0x00, 0x05]);
+    assert!(warnings_for("data").is_empty());
+}
@@ -0,0 +1,58 @@
+//! 紧密打包偏移测试
+//!
+//! 验证字段字节偏移与组帧时的bit紧密打包一致，且声明了字节长度的数据字段按声明长度拆包
+
+mod common;
+
+use apdl_lsk::FrameDisassembler;
+use apdl_poem::FrameAssembler;
+use common::{assembler_from_dsl, disassembler_from_dsl};
+
+const FRAME_DSL: &str = r#"
+field: version; type: Bit(3); length: 3bit; scope: layer(link); cover: entire_field; desc: "Version"
+field: apid; type: Bit(5); length: 5bit; scope: layer(link); cover: entire_field; desc: "APID"
+field: length; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Length"
+field: data; type: RawData; length: 2byte; scope: layer(link); cover: entire_field; desc: "Data"
+field: trailer; type: Uint8; length: 1byte; scope: layer(link); cover: entire_field; desc: "Trailer"
+"#;
+
+fn create_assembler() -> FrameAssembler {
+    assembler_from_dsl(FRAME_DSL)
+}
+
+fn create_disassembler() -> FrameDisassembler {
+    disassembler_from_dsl(FRAME_DSL)
+}
+
+#[test]
+fn test_field_position_follows_bit_packing() {
+    let assembler = create_assembler();
+    let positions: Vec<usize> = ["version", "apid", "length", "data", "trailer"]
+        .iter()
+        .map(|name| assembler.get_field_position(name).unwrap())
+        .collect();
+    // 两个bit字段共享首字节，之后的字段从下一个字节边界开始
+    assert_eq!(positions, [0, 0, 1, 3, 5]);
+}
+
+#[test]
+fn test_declared_raw_data_length_is_honoured_when_parsing() {
+    let mut assembler = create_assembler();
+    assembler.set_field_value("version", &[0x01]).unwrap();
+    assembler.set_field_value("apid", &[0x05]).unwrap();
+    assembler.set_field_value("length", &[0x00, 0x02]).unwrap();
+    assembler.set_field_value("data", &[0xAA, 0xBB]).unwrap();
+    assembler.set_field_value("trailer", &[0x7E]).unwrap();
+    let frame = assembler.assemble_frame().unwrap();
+    assert_eq!(frame, [0x25, 0x00, 0x02, 0xAA, 0xBB, 0x7E]);
+
+    let fields = create_disassembler().disassemble_frame(&frame).unwrap();
+    assert_eq!(fields["data"], [0xAA, 0xBB]);
+    assert_eq!(fields["trailer"], [0x7E]);
+}
+
+#[test]
+fn test_raw_data_beyond_frame_end_is_rejected() {
+    let result = create_disassembler().disassemble_frame(&[0x25, 0x00, 0x02, 0xAA]);
+    assert!(result.is_err());
+}
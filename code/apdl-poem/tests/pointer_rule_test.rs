@@ -0,0 +1,82 @@
+//! 指针规则测试
+//!
+//! 验证首导头指针按目标字段相对数据区起始的偏移写入，目标为空时写入全1空闲值
+
+mod common;
+
+use apdl_poem::FrameAssembler;
+use common::assembler_from_dsl;
+
+// 类M_PDU结构：5bit备用 + 11bit首导头指针，数据区由续包片段和新包组成
+const MPDU_DSL: &str = r#"
+field: spare; type: Bit(5); length: 5bit; scope: layer(link); cover: entire_field; desc: "Spare"
+field: fhp; type: Bit(11); length: 11bit; scope: layer(link); cover: entire_field; desc: "First header pointer"
+field: continuation; type: RawData; length: dynamic; scope: layer(link); cover: entire_field; desc: "Continuation of previous packet"
+field: first_packet; type: RawData; length: dynamic; scope: layer(link); cover: entire_field; desc: "First packet header"
+"#;
+
+const POINTER_RULES: &str = r#"
+rule: pointer(field: fhp points_to first_packet);
+"#;
+
+fn create_assembler() -> FrameAssembler {
+    let mut assembler = assembler_from_dsl(&format!("{MPDU_DSL}{POINTER_RULES}"));
+    assembler.set_field_value("spare", &[0x00]).unwrap();
+    assembler
+}
+
+#[test]
+fn test_pointer_offset_to_first_packet() {
+    let mut assembler = create_assembler();
+    assembler
+        .set_field_value("continuation", &[0xAA, 0xBB, 0xCC])
+        .unwrap();
+    assembler
+        .set_field_value("first_packet", &[0x08, 0x01])
+        .unwrap();
+
+    let frame = assembler.assemble_frame().unwrap();
+    assert_eq!(frame, vec![0x00, 0x03, 0xAA, 0xBB, 0xCC, 0x08, 0x01]);
+    assert_eq!(assembler.get_field_value("fhp").unwrap(), vec![0x00, 0x03]);
+}
+
+#[test]
+fn test_pointer_idle_when_no_packet_starts() {
+    let mut assembler = create_assembler();
+    assembler
+        .set_field_value("continuation", &[0xAA, 0xBB, 0xCC, 0xDD])
+        .unwrap();
+    assembler.set_field_value("first_packet", &[]).unwrap();
+
+    let frame = assembler.assemble_frame().unwrap();
+    assert_eq!(frame, vec![0x07, 0xFF, 0xAA, 0xBB, 0xCC, 0xDD]);
+    assert_eq!(assembler.get_field_value("fhp").unwrap(), vec![0x07, 0xFF]);
+}
+
+#[test]
+fn test_pointer_with_explicit_data_zone() {
+    let mut assembler = create_assembler();
+    assembler.set_pointer_data_zone("fhp", "first_packet");
+    assembler
+        .set_field_value("continuation", &[0xAA, 0xBB])
+        .unwrap();
+    assembler.set_field_value("first_packet", &[0x08]).unwrap();
+
+    let frame = assembler.assemble_frame().unwrap();
+    assert_eq!(&frame[..2], &[0x00, 0x00]);
+}
+
+#[test]
+fn test_zero_width_pointer_rejected() {
+    let dsl = r#"
+field: ptr; type: RawData; length: dynamic; scope: layer(link); cover: entire_field; desc: "Empty pointer"
+field: first_packet; type: RawData; length: 1byte; scope: layer(link); cover: entire_field; desc: "First packet"
+rule: pointer(field: ptr points_to first_packet);
+"#;
+    let mut assembler = assembler_from_dsl(dsl);
+    assembler.set_field_value("ptr", &[]).unwrap();
+    assembler.set_field_value("first_packet", &[0x08]).unwrap();
+
+    let error = assembler.assemble_frame().unwrap_err();
+    assert!(error.to_string().contains("zero width"));
+}
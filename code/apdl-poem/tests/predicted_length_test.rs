@@ -0,0 +1,50 @@
+//! 帧长度预测测试
+//!
+//! 验证不组装帧即可得到与实际组装结果一致的帧长度
+
+mod common;
+
+use apdl_poem::FrameAssembler;
+use common::assembler_from_dsl;
+
+const FRAME_DSL: &str = r#"
+field: version; type: Bit(3); length: 3bit; scope: layer(link); cover: entire_field; desc: "Version"
+field: flags; type: Bit(4); length: 4bit; scope: layer(link); cover: entire_field; desc: "Flags"
+field: length; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Length"
+field: data; type: RawData; length: dynamic; scope: layer(application); cover: entire_field; desc: "Data"
+field: fecf; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Frame check"
+"#;
+
+fn create_assembler() -> FrameAssembler {
+    let mut assembler = assembler_from_dsl(FRAME_DSL);
+    assembler.set_field_value("version", &[0x01]).unwrap();
+    assembler.set_field_value("flags", &[0x05]).unwrap();
+    assembler.set_field_value("length", &[0x00, 0x00]).unwrap();
+    assembler
+}
+
+#[test]
+fn test_predicted_length_matches_assembled_frame() {
+    let mut assembler = create_assembler();
+
+    for payload_len in [1usize, 5, 64, 300] {
+        assembler
+            .set_field_value("data", &vec![0xA5; payload_len])
+            .unwrap();
+
+        let predicted = assembler.predicted_length().unwrap();
+        let frame = assembler.assemble_frame().unwrap();
+        assert_eq!(predicted, frame.len(), "payload length {payload_len}");
+        // 7bit补齐为1字节 + length + data + fecf
+        assert_eq!(predicted, 1 + 2 + payload_len + 2);
+    }
+}
+
+#[test]
+fn test_predicted_length_without_dynamic_value() {
+    let mut assembler = create_assembler();
+
+    // 动态字段未设置值时与组装时的默认大小一致
+    let predicted = assembler.predicted_length().unwrap();
+    assert_eq!(predicted, assembler.assemble_frame().unwrap().len());
+}
@@ -0,0 +1,73 @@
+//! 自定义规则处理器测试
+//!
+//! 验证注册的处理器与内置规则一同执行
+
+mod common;
+
+use apdl_core::{ChecksumAlgorithm, ProtocolError, SemanticRule};
+use apdl_poem::{FrameAssembler, RuleHandler};
+use common::assembler_from_dsl;
+
+const FRAME_DSL: &str = r#"
+field: header; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Header"
+field: magic; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Proprietary magic"
+field: fecf; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; alg: crc16; desc: "Frame check"
+rule: algorithm(field: magic uses fill_magic);
+"#;
+
+/// 将Algorithm规则指定的字段填充为常量
+struct FillMagicHandler {
+    value: [u8; 2],
+}
+
+impl RuleHandler for FillMagicHandler {
+    fn applies(&self, rule: &SemanticRule) -> bool {
+        matches!(rule, SemanticRule::Algorithm { algorithm, .. } if algorithm == "fill_magic")
+    }
+
+    fn apply(
+        &self,
+        asm: &mut FrameAssembler,
+        rule: &SemanticRule,
+        frame: &mut Vec<u8>,
+    ) -> Result<(), ProtocolError> {
+        let SemanticRule::Algorithm { field_name, .. } = rule else {
+            return Ok(());
+        };
+        let field_name = field_name.trim_start_matches("field: ").trim();
+        let offset = asm.get_field_position(field_name)?;
+        frame[offset..offset + 2].copy_from_slice(&self.value);
+        Ok(())
+    }
+}
+
+fn create_assembler() -> FrameAssembler {
+    let mut assembler = assembler_from_dsl(FRAME_DSL);
+    assembler.add_semantic_rule(SemanticRule::ChecksumRange {
+        algorithm: ChecksumAlgorithm::CRC16,
+        start_field: "header".to_string(),
+        end_field: "magic".to_string(),
+        covers_self: true,
+    });
+    assembler.set_field_value("header", &[0x12, 0x34]).unwrap();
+    assembler
+}
+
+#[test]
+fn test_registered_handler_runs() {
+    // 未注册处理器时，fill_magic既不是处理器规则也不是已注册的算法，组装报错
+    let mut plain = create_assembler();
+    let error = plain.assemble_frame().unwrap_err();
+    assert!(error.to_string().contains("fill_magic"));
+
+    let mut assembler = create_assembler();
+    assembler.register_rule_handler(FillMagicHandler {
+        value: [0xCA, 0xFE],
+    });
+    let frame = assembler.assemble_frame().unwrap();
+    assert_eq!(&frame[..4], &[0x12, 0x34, 0xCA, 0xFE]);
+
+    // 自定义处理器在校验和规则之前执行，校验和包含处理器写入的数据
+    let crc = assembler.calculate_crc16(&frame[..4]);
+    assert_eq!(&frame[4..], &crc.to_be_bytes());
+}
@@ -143,6 +143,7 @@ fn test_individual_semantic_rules() -> Result<(), ProtocolError> {
             algorithm: _,
             start_field,
             end_field,
+            ..
         } = &semantic_rules[0]
         {
             assert_eq!(start_field, "sync_flag");
@@ -0,0 +1,220 @@
+//! 流式分段测试
+//!
+//! 验证超过父包数据区的子包被切分到多个定长父包，并经分段标志和段偏移重组为原始子包
+
+use apdl_core::{
+    ConnectorConfig, DataPlacementConfig, DataPlacementStrategy, LengthDesc, LengthUnit, ScopeDesc,
+    SyntaxUnit, UnitType,
+};
+use apdl_poem::standard_units::connector::{
+    ConnectorEngine, SegmentFlag, StreamFragmenter, StreamReassembler,
+};
+use apdl_poem::standard_units::frame_assembler::core::FrameAssembler;
+
+const FRAME_SIZE: usize = 1024;
+/// 父包头部：1字节分段标志 + 2字节段偏移 + 2字节有效长度
+const HEADER_SIZE: usize = 5;
+
+fn make_field(name: &str, unit_type: UnitType, size: usize) -> SyntaxUnit {
+    SyntaxUnit::new(
+        name,
+        unit_type,
+        LengthDesc {
+            size,
+            unit: LengthUnit::Byte,
+        },
+        ScopeDesc::Global("test".to_string()),
+    )
+}
+
+/// 父包：分段标志 + 段偏移 + 有效长度 + 数据区，共1024字节
+fn create_parent() -> FrameAssembler {
+    let mut assembler = FrameAssembler::new();
+    assembler.add_field(make_field("flags", UnitType::Uint(8), 1));
+    assembler.add_field(make_field("offset", UnitType::Uint(16), 2));
+    assembler.add_field(make_field("length", UnitType::Uint(16), 2));
+    assembler.add_field(make_field(
+        "data",
+        UnitType::RawData,
+        FRAME_SIZE - HEADER_SIZE,
+    ));
+    assembler
+}
+
+fn stream_config() -> DataPlacementConfig {
+    let config_params = vec![
+        ("flag_field".to_string(), "flags".to_string()),
+        ("offset_field".to_string(), "offset".to_string()),
+        ("length_field".to_string(), "length".to_string()),
+        ("padding_value".to_string(), "0xFF".to_string()),
+    ];
+    DataPlacementConfig {
+        strategy: DataPlacementStrategy::StreamBased,
+        target_field: "data".to_string(),
+        config_params,
+    }
+}
+
+fn sdu(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 7 % 251) as u8).collect()
+}
+
+fn reassemble(frames: &[Vec<u8>], config: &DataPlacementConfig) -> Vec<Vec<u8>> {
+    let mut reassembler = StreamReassembler::from_template(&create_parent(), config).unwrap();
+    let mut sdus = Vec::new();
+    for frame in frames {
+        sdus.extend(reassembler.push_frame(frame).unwrap());
+    }
+    assert_eq!(reassembler.pending_len(), 0);
+    assert_eq!(reassembler.discarded_bytes(), 0);
+    sdus
+}
+
+#[test]
+fn test_fragment_5000_byte_sdu_into_1024_byte_frames() {
+    let config = stream_config();
+    let original = sdu(5000);
+    let fragmenter = StreamFragmenter::from_template(&create_parent(), &config).unwrap();
+    assert_eq!(fragmenter.capacity(), 1019);
+
+    let frames = fragmenter.fragment(&original).unwrap();
+    assert_eq!(frames.len(), 5);
+    assert!(frames.iter().all(|frame| frame.len() == FRAME_SIZE));
+
+    let flags: Vec<_> = frames
+        .iter()
+        .map(|frame| SegmentFlag::from_value(frame[0] as u64))
+        .collect();
+    assert_eq!(
+        flags,
+        [
+            SegmentFlag::First,
+            SegmentFlag::Continuation,
+            SegmentFlag::Continuation,
+            SegmentFlag::Continuation,
+            SegmentFlag::Last,
+        ]
+    );
+    let offsets: Vec<_> = frames
+        .iter()
+        .map(|frame| u16::from_be_bytes([frame[1], frame[2]]))
+        .collect();
+    assert_eq!(offsets, [0, 1019, 2038, 3057, 4076]);
+    // 末段有效数据924字节，其余为填充码
+    let last = &frames[4];
+    assert_eq!(u16::from_be_bytes([last[3], last[4]]), 924);
+    assert!(last[HEADER_SIZE + 924..].iter().all(|&byte| byte == 0xFF));
+
+    assert_eq!(reassemble(&frames, &config), vec![original]);
+}
+
+#[test]
+fn test_sdu_ending_with_padding_value_is_preserved() {
+    let config = stream_config();
+    let fragmenter = StreamFragmenter::from_template(&create_parent(), &config).unwrap();
+
+    // SDU末尾与填充码相同，按有效长度重组
+    let mut short = sdu(300);
+    short.extend_from_slice(&[0xFF, 0xFF]);
+    let frames = fragmenter.fragment(&short).unwrap();
+    assert_eq!(frames.len(), 1);
+    assert_eq!(
+        SegmentFlag::from_value(frames[0][0] as u64),
+        SegmentFlag::Unsegmented
+    );
+
+    let mut all_frames = frames;
+    let mut original = sdu(5000);
+    original.push(0xFF);
+    all_frames.extend(fragmenter.fragment(&original).unwrap());
+    assert_eq!(reassemble(&all_frames, &config), vec![short, original]);
+}
+
+#[test]
+fn test_stream_config_requires_length_field() {
+    let mut config = stream_config();
+    config
+        .config_params
+        .retain(|(key, _)| key != "length_field");
+
+    let expected = "Stream config is missing 'length_field'";
+    let err = StreamFragmenter::from_template(&create_parent(), &config).err();
+    assert_eq!(err.as_deref(), Some(expected));
+    let err = StreamReassembler::from_template(&create_parent(), &config).err();
+    assert_eq!(err.as_deref(), Some(expected));
+}
+
+#[test]
+fn test_reassembly_discards_sdu_with_lost_segment() {
+    let config = stream_config();
+    let fragmenter = StreamFragmenter::from_template(&create_parent(), &config).unwrap();
+    let first = sdu(5000);
+    let second = sdu(2000);
+    let mut frames = fragmenter.fragment(&first).unwrap();
+    frames.extend(fragmenter.fragment(&second).unwrap());
+
+    // 丢失第一个SDU的第三段：段偏移不连续，该SDU被丢弃，第二个SDU正常重组
+    frames.remove(2);
+    let mut reassembler = StreamReassembler::from_template(&create_parent(), &config).unwrap();
+    let mut sdus = Vec::new();
+    for frame in &frames {
+        sdus.extend(reassembler.push_frame(frame).unwrap());
+    }
+    assert_eq!(sdus, vec![second]);
+    assert_eq!(reassembler.discarded_bytes(), 5000 - 1019);
+}
+
+#[test]
+fn test_connector_engine_stream_placement_fragments_child_packets() {
+    let config = stream_config();
+    let connector_config = ConnectorConfig {
+        mappings: vec![],
+        header_pointers: None,
+        data_placement: Some(config.clone()),
+    };
+
+    let mut engine = ConnectorEngine::new();
+    let mut originals = Vec::new();
+    for len in [5000, 100] {
+        let mut child = FrameAssembler::new();
+        child.add_field(make_field("payload", UnitType::RawData, len));
+        child.set_field_value("payload", &sdu(len)).unwrap();
+        originals.push(child.assemble_frame().unwrap());
+        let mut parent = create_parent();
+        engine
+            .connect(&mut child, &mut parent, "vc0", &connector_config)
+            .unwrap();
+    }
+
+    let mut frames = Vec::new();
+    while let Some((frame, _)) = engine.build_packet(&config) {
+        frames.push(frame);
+    }
+    assert_eq!(frames.len(), 6);
+    assert!(frames.iter().all(|frame| frame.len() == FRAME_SIZE));
+    assert_eq!(reassemble(&frames, &config), originals);
+}
+
+#[test]
+fn test_connector_engine_stream_build_error_yields_no_packet() {
+    let mut config = stream_config();
+    config
+        .config_params
+        .retain(|(key, _)| key != "length_field");
+    let connector_config = ConnectorConfig {
+        mappings: vec![],
+        header_pointers: None,
+        data_placement: Some(config.clone()),
+    };
+
+    let mut engine = ConnectorEngine::new();
+    let mut child = FrameAssembler::new();
+    child.add_field(make_field("payload", UnitType::RawData, 100));
+    let mut parent = create_parent();
+    engine
+        .connect(&mut child, &mut parent, "vc0", &connector_config)
+        .unwrap();
+
+    // 缺少length_field时构建失败，记录错误后不产出父包
+    assert_eq!(engine.build_packet(&config), None);
+}
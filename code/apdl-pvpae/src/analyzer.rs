@@ -2,6 +2,7 @@
 //!
 //! 实现协议性能统计分析功能
 
+use apdl_core::{PackageDefinition, ProtocolStackDefinition};
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
@@ -16,6 +17,51 @@ pub struct PerformanceMetrics {
     pub packet_loss_rate: f64,     // 丢包率 (%)
 }
 
+/// 单层开销：该层固定字段字节数与其承载的净荷字节数
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerOverhead {
+    pub name: String,
+    pub header_bytes: usize,
+    pub payload_bytes: usize,
+}
+
+impl LayerOverhead {
+    /// 该层的总字节数
+    pub fn total_bytes(&self) -> usize {
+        self.header_bytes + self.payload_bytes
+    }
+}
+
+/// 协议栈开销报告，各层按从外到内排列
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverheadReport {
+    pub layers: Vec<LayerOverhead>,
+    /// 最内层承载的应用数据字节数
+    pub payload_bytes: usize,
+    /// 最外层帧的总字节数
+    pub total_bytes: usize,
+}
+
+impl OverheadReport {
+    /// 端到端协议效率：应用数据 / 总字节数
+    pub fn efficiency(&self) -> f64 {
+        if self.total_bytes == 0 {
+            0.0
+        } else {
+            self.payload_bytes as f64 / self.total_bytes as f64
+        }
+    }
+
+    /// 端到端开销比例：各层固定字段 / 总字节数
+    pub fn overhead_ratio(&self) -> f64 {
+        if self.total_bytes == 0 {
+            0.0
+        } else {
+            1.0 - self.efficiency()
+        }
+    }
+}
+
 /// 性能分析器
 #[derive(Default)]
 pub struct PerformanceAnalyzer {
@@ -56,6 +102,48 @@ impl PerformanceAnalyzer {
         }
     }
 
+    /// 分析协议栈各层的头部开销
+    ///
+    /// 协议栈只记录包名，包定义从`packages`中按名称查找。每层的固定长度字段（含尾部校验等）
+    /// 计为开销，动态长度和表达式长度字段视为承载内层的净荷；
+    /// 最内层的净荷取代表性的应用数据长度`payload_size`
+    pub fn analyze_overhead(
+        &self,
+        stack: &ProtocolStackDefinition,
+        packages: &[PackageDefinition],
+        payload_size: usize,
+    ) -> OverheadReport {
+        let mut layers = Vec::new();
+        let mut payload_bytes = payload_size;
+        for package in stack
+            .packages
+            .iter()
+            .rev()
+            .filter_map(|name| packages.iter().find(|package| &package.name == name))
+        {
+            let header_bits: usize = package
+                .layers
+                .iter()
+                .flat_map(|layer| &layer.units)
+                .filter_map(|unit| unit.length.fixed_bits())
+                .sum();
+            let layer = LayerOverhead {
+                name: package.name.clone(),
+                header_bytes: header_bits.div_ceil(8),
+                payload_bytes,
+            };
+            payload_bytes = layer.total_bytes();
+            layers.push(layer);
+        }
+        layers.reverse();
+
+        OverheadReport {
+            layers,
+            payload_bytes: payload_size,
+            total_bytes: payload_bytes,
+        }
+    }
+
     /// 获取分析结果
     pub fn get_analysis_results(&self) -> &HashMap<String, PerformanceMetrics> {
         &self.metrics
@@ -68,3 +156,113 @@ impl PerformanceAnalyzer {
         self.total_processed = 0;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apdl_core::{LayerDefinition, LengthDesc, LengthUnit, ScopeDesc, SyntaxUnit, UnitType};
+
+    fn field(name: &str, unit_type: UnitType, size: usize, unit: LengthUnit) -> SyntaxUnit {
+        SyntaxUnit::new(
+            name,
+            unit_type,
+            LengthDesc { size, unit },
+            ScopeDesc::Global("test".to_string()),
+        )
+    }
+
+    fn package(name: &str, units: Vec<SyntaxUnit>) -> PackageDefinition {
+        let mut package = PackageDefinition::new(
+            name.to_string(),
+            name.to_string(),
+            "telemetry".to_string(),
+            String::new(),
+        );
+        package.layers.push(LayerDefinition {
+            name: name.to_string(),
+            units,
+            rules: vec![],
+        });
+        package
+    }
+
+    /// TM传输帧（6字节主头 + 数据域 + 2字节FECF）承载空间包（6字节主头 + 数据域）
+    fn ccsds_packages() -> Vec<PackageDefinition> {
+        let tm_frame = package(
+            "tm_frame",
+            vec![
+                field("version", UnitType::Bit(2), 2, LengthUnit::Bit),
+                field("scid", UnitType::Bit(10), 10, LengthUnit::Bit),
+                field("vcid", UnitType::Bit(3), 3, LengthUnit::Bit),
+                field("ocf_flag", UnitType::Bit(1), 1, LengthUnit::Bit),
+                field("mc_count", UnitType::Uint(8), 1, LengthUnit::Byte),
+                field("vc_count", UnitType::Uint(8), 1, LengthUnit::Byte),
+                field("data_status", UnitType::Uint(16), 2, LengthUnit::Byte),
+                field("data", UnitType::RawData, 0, LengthUnit::Dynamic),
+                field("fecf", UnitType::Uint(16), 2, LengthUnit::Byte),
+            ],
+        );
+        let space_packet = package(
+            "space_packet",
+            vec![
+                field("version", UnitType::Bit(3), 3, LengthUnit::Bit),
+                field("type", UnitType::Bit(1), 1, LengthUnit::Bit),
+                field("sec_hdr_flag", UnitType::Bit(1), 1, LengthUnit::Bit),
+                field("apid", UnitType::Bit(11), 11, LengthUnit::Bit),
+                field("seq_flags", UnitType::Bit(2), 2, LengthUnit::Bit),
+                field("seq_count", UnitType::Bit(14), 14, LengthUnit::Bit),
+                field("length", UnitType::Uint(16), 2, LengthUnit::Byte),
+                field(
+                    "data",
+                    UnitType::RawData,
+                    0,
+                    LengthUnit::Expression("length + 1".to_string()),
+                ),
+            ],
+        );
+        vec![space_packet, tm_frame]
+    }
+
+    #[test]
+    fn test_ccsds_stack_overhead() {
+        let mut stack = ProtocolStackDefinition::new("ccsds_tm".to_string(), String::new());
+        stack.packages = vec!["tm_frame".to_string(), "space_packet".to_string()];
+        let analyzer = PerformanceAnalyzer::new();
+
+        let report = analyzer.analyze_overhead(&stack, &ccsds_packages(), 100);
+
+        assert_eq!(
+            report.layers,
+            vec![
+                LayerOverhead {
+                    name: "tm_frame".to_string(),
+                    header_bytes: 8,
+                    payload_bytes: 106,
+                },
+                LayerOverhead {
+                    name: "space_packet".to_string(),
+                    header_bytes: 6,
+                    payload_bytes: 100,
+                },
+            ]
+        );
+        assert_eq!(report.total_bytes, 114);
+        assert!((report.efficiency() - 100.0 / 114.0).abs() < 1e-9);
+        assert!((report.overhead_ratio() - 14.0 / 114.0).abs() < 1e-9);
+
+        // 较长的应用数据摊薄固定开销
+        let report = analyzer.analyze_overhead(&stack, &ccsds_packages(), 1000);
+        assert_eq!(report.total_bytes, 1014);
+        assert!((report.efficiency() - 1000.0 / 1014.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_empty_stack_overhead() {
+        let stack = ProtocolStackDefinition::new("empty".to_string(), String::new());
+        let report = PerformanceAnalyzer::new().analyze_overhead(&stack, &[], 0);
+
+        assert!(report.layers.is_empty());
+        assert_eq!(report.efficiency(), 0.0);
+        assert_eq!(report.overhead_ratio(), 0.0);
+    }
+}
@@ -0,0 +1,297 @@
+//! 协议定义加载验证模块
+//!
+//! 反序列化协议定义的同时执行布局和字段引用验证，使定义错误在加载时暴露
+
+use crate::verifier::{LengthRuleIssue, ProtocolVerifier};
+use apdl_core::{PackageDefinition, SemanticRule, SyntaxUnit};
+use std::collections::HashSet;
+use std::fmt;
+
+/// 协议定义错误
+#[derive(Debug, Clone, PartialEq)]
+pub enum DefinitionError {
+    /// JSON反序列化失败
+    Deserialize(String),
+    /// 同一层内字段名重复
+    DuplicateField { layer: String, field_name: String },
+    /// 语义规则引用了不存在的字段
+    UnknownFieldReference { rule: String, field_name: String },
+    /// 约束取值超出字段位宽
+    ConstraintWidth(String),
+    /// 长度规则的目标字段或引用字段不存在
+    LengthRule(LengthRuleIssue),
+}
+
+impl fmt::Display for DefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DefinitionError::Deserialize(message) => {
+                write!(f, "Failed to deserialize definition: {message}")
+            }
+            DefinitionError::DuplicateField { layer, field_name } => {
+                write!(f, "Duplicate field '{field_name}' in layer '{layer}'")
+            }
+            DefinitionError::UnknownFieldReference { rule, field_name } => {
+                write!(f, "Rule {rule} references unknown field '{field_name}'")
+            }
+            DefinitionError::ConstraintWidth(message) => write!(f, "{message}"),
+            DefinitionError::LengthRule(issue) => write!(f, "{issue}"),
+        }
+    }
+}
+
+/// 加载时验证的协议定义
+pub trait ValidatedDefinition: Sized {
+    /// 从JSON反序列化并验证定义，返回全部定义错误
+    fn from_json_validated(json: &str) -> Result<Self, Vec<DefinitionError>>;
+}
+
+impl ValidatedDefinition for PackageDefinition {
+    fn from_json_validated(json: &str) -> Result<Self, Vec<DefinitionError>> {
+        let package: PackageDefinition = serde_json::from_str(json)
+            .map_err(|e| vec![DefinitionError::Deserialize(e.to_string())])?;
+
+        let errors = ProtocolVerifier::new().verify_package_definition(&package);
+        if errors.is_empty() {
+            Ok(package)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl ProtocolVerifier {
+    /// 验证包定义：层内字段名唯一、约束不超出字段位宽、语义规则引用的字段均存在
+    ///
+    /// 不同层可以有同名字段，规则可以引用包内任意层的字段
+    pub fn verify_package_definition(&self, package: &PackageDefinition) -> Vec<DefinitionError> {
+        let fields: Vec<SyntaxUnit> = package
+            .layers
+            .iter()
+            .flat_map(|layer| layer.units.iter().cloned())
+            .collect();
+        let rules: Vec<SemanticRule> = package
+            .layers
+            .iter()
+            .flat_map(|layer| layer.rules.iter().cloned())
+            .collect();
+
+        let mut errors = Vec::new();
+        for layer in &package.layers {
+            let mut layer_names = HashSet::new();
+            for field in &layer.units {
+                if !layer_names.insert(field.field_id.as_str()) {
+                    errors.push(DefinitionError::DuplicateField {
+                        layer: layer.name.clone(),
+                        field_name: field.field_id.clone(),
+                    });
+                }
+            }
+        }
+        let names: HashSet<&str> = fields.iter().map(|field| field.field_id.as_str()).collect();
+
+        errors.extend(
+            fields
+                .iter()
+                .filter_map(|field| field.check_constraint_width().err())
+                .map(|e| DefinitionError::ConstraintWidth(e.to_string())),
+        );
+
+        for rule in &rules {
+            for field_name in rule_field_references(rule) {
+                if !names.contains(field_name) {
+                    errors.push(DefinitionError::UnknownFieldReference {
+                        rule: rule.kind().to_string(),
+                        field_name: field_name.to_string(),
+                    });
+                }
+            }
+        }
+
+        // 长度表达式可能依赖运行时数据，只报告引用错误
+        errors.extend(
+            self.verify_length_rules(&fields, &rules)
+                .into_iter()
+                .filter(|issue| {
+                    matches!(
+                        issue,
+                        LengthRuleIssue::MissingField { .. }
+                            | LengthRuleIssue::UnknownReference { .. }
+                    )
+                })
+                .map(DefinitionError::LengthRule),
+        );
+        errors
+    }
+}
+
+/// 去除DSL规则参数中字段名的`start: `、`end: `、`field: `前缀
+pub(crate) fn clean(name: &str) -> &str {
+    ["start: ", "end: ", "field: "]
+        .iter()
+        .fold(name.trim(), |name, prefix| name.trim_start_matches(prefix))
+        .trim()
+}
+
+/// 获取结构性语义规则引用的字段名，长度规则由长度规则验证处理
+pub(crate) fn rule_field_references(rule: &SemanticRule) -> Vec<&str> {
+    let names: Vec<&String> = match rule {
+        SemanticRule::ChecksumRange {
+            start_field,
+            end_field,
+            ..
+        } => vec![start_field, end_field],
+        SemanticRule::Dependency {
+            dependent_field,
+            dependency_field,
+        } => vec![dependent_field, dependency_field],
+        SemanticRule::Order {
+            first_field,
+            second_field,
+        } => vec![first_field, second_field],
+        SemanticRule::Pointer {
+            pointer_field,
+            target_field,
+        } => vec![pointer_field, target_field],
+        SemanticRule::AlgorithmSelect {
+            selector_field,
+            target_field,
+            ..
+        } => std::iter::once(selector_field)
+            .chain(target_field)
+            .collect(),
+        SemanticRule::Alignment { field_name, .. } => vec![field_name],
+        _ => Vec::new(),
+    };
+    names.into_iter().map(|name| clean(name)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apdl_core::{
+        ChecksumAlgorithm, Constraint, LayerDefinition, LengthDesc, LengthUnit, ScopeDesc, UnitType,
+    };
+
+    fn make_field(name: &str, unit_type: UnitType, size: usize) -> SyntaxUnit {
+        SyntaxUnit::new(
+            name,
+            unit_type,
+            LengthDesc {
+                size,
+                unit: LengthUnit::Byte,
+            },
+            ScopeDesc::Layer("link".to_string()),
+        )
+    }
+
+    fn package_json(rules: Vec<SemanticRule>) -> String {
+        let mut package = PackageDefinition::new(
+            "tm".to_string(),
+            "TM Frame".to_string(),
+            "telemetry".to_string(),
+            "Test frame".to_string(),
+        );
+        package.layers.push(LayerDefinition {
+            name: "link".to_string(),
+            units: vec![
+                make_field("version", UnitType::Uint(8), 1),
+                make_field("length", UnitType::Uint(16), 2),
+                make_field("data", UnitType::Uint(32), 4),
+                make_field("fecf", UnitType::Uint(16), 2),
+            ],
+            rules,
+        });
+        serde_json::to_string(&package).unwrap()
+    }
+
+    fn checksum_range(start: &str, end: &str) -> SemanticRule {
+        SemanticRule::ChecksumRange {
+            algorithm: ChecksumAlgorithm::CRC16,
+            start_field: start.to_string(),
+            end_field: end.to_string(),
+            covers_self: true,
+        }
+    }
+
+    #[test]
+    fn test_load_valid_definition() {
+        let json = package_json(vec![
+            checksum_range("version", "data"),
+            SemanticRule::LengthRule {
+                field_name: "length".to_string(),
+                expression: "len(data) + len(fecf)".to_string(),
+            },
+        ]);
+
+        let package = PackageDefinition::from_json_validated(&json).unwrap();
+        assert_eq!(package.name, "tm");
+        assert_eq!(package.layers[0].units.len(), 4);
+    }
+
+    #[test]
+    fn test_bad_field_references_fail_at_load() {
+        let json = package_json(vec![
+            checksum_range("version", "payload"),
+            SemanticRule::LengthRule {
+                field_name: "length".to_string(),
+                expression: "len(trailer) + 2".to_string(),
+            },
+        ]);
+
+        let errors = PackageDefinition::from_json_validated(&json).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                DefinitionError::UnknownFieldReference {
+                    rule: "checksum_range".to_string(),
+                    field_name: "payload".to_string(),
+                },
+                DefinitionError::LengthRule(LengthRuleIssue::UnknownReference {
+                    field_name: "length".to_string(),
+                    reference: "trailer".to_string(),
+                }),
+            ]
+        );
+        assert!(errors[0].to_string().contains("payload"));
+    }
+
+    #[test]
+    fn test_layout_and_syntax_errors_fail_at_load() {
+        let mut package: PackageDefinition = serde_json::from_str(&package_json(vec![])).unwrap();
+        package.layers[0].units[0].constraint = Some(Constraint::FixedValue(0x1FF));
+        package.layers[0]
+            .units
+            .push(make_field("data", UnitType::Uint(8), 1));
+        let json = serde_json::to_string(&package).unwrap();
+
+        let errors = PackageDefinition::from_json_validated(&json).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0],
+            DefinitionError::DuplicateField {
+                layer: "link".to_string(),
+                field_name: "data".to_string()
+            }
+        );
+        assert!(matches!(errors[1], DefinitionError::ConstraintWidth(_)));
+
+        let errors = PackageDefinition::from_json_validated("{\"name\": \"tm\"").unwrap_err();
+        assert!(matches!(errors[..], [DefinitionError::Deserialize(_)]));
+    }
+
+    #[test]
+    fn test_same_field_name_allowed_in_different_layers() {
+        let mut package: PackageDefinition = serde_json::from_str(&package_json(vec![])).unwrap();
+        package.layers.push(LayerDefinition {
+            name: "net".to_string(),
+            units: vec![
+                make_field("version", UnitType::Uint(8), 1),
+                make_field("data", UnitType::Uint(16), 2),
+            ],
+            rules: vec![checksum_range("version", "data")],
+        });
+        let json = serde_json::to_string(&package).unwrap();
+        assert!(PackageDefinition::from_json_validated(&json).is_ok());
+    }
+}
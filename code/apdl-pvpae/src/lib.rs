@@ -3,9 +3,14 @@
 //! This crate provides verification and performance analysis for the APDL system.
 
 pub mod analyzer;
+pub mod definition;
+pub mod prelude;
 pub mod reporter;
 pub mod verifier;
 
-pub use analyzer::PerformanceAnalyzer;
-pub use reporter::ReportGenerator;
-pub use verifier::ProtocolVerifier;
+pub use analyzer::{LayerOverhead, OverheadReport, PerformanceAnalyzer};
+pub use definition::{DefinitionError, ValidatedDefinition};
+pub use reporter::{RecordStatus, ReportGenerator, ReportRecord, StructuredReport};
+pub use verifier::{
+    ConditionalIssue, LayoutIssue, LengthRuleIssue, ProtocolVerifier, RouteReport, UnroutedValues,
+};
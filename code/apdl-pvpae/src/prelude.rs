@@ -0,0 +1,9 @@
+//! 常用类型预导入
+//!
+//! 在`apdl_core::prelude`的基础上引入协议验证和报告相关类型
+
+pub use apdl_core::prelude::*;
+
+pub use crate::{
+    DefinitionError, PerformanceAnalyzer, ProtocolVerifier, ReportGenerator, ValidatedDefinition,
+};
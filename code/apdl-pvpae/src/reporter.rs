@@ -3,7 +3,14 @@
 //! 实现协议验证与性能分析报告的生成
 
 use crate::analyzer::PerformanceMetrics;
-use std::collections::HashMap;
+use crate::definition::{clean, rule_field_references};
+use apdl_core::utils::{bytes_to_hex, ValueFormat};
+use apdl_core::{ParsedField, SemanticRule, SyntaxUnit};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// CSV报告的表头，列顺序固定
+pub const CSV_HEADER: &str = "category,name,status,detail";
 
 /// 报告类型
 #[derive(Debug, Clone)]
@@ -22,12 +29,55 @@ pub struct ValidationResult {
     pub details: Option<String>,
 }
 
+/// 结构化报告记录的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordStatus {
+    Pass,
+    Fail,
+    /// 性能指标等不区分通过与否的记录
+    Info,
+}
+
+impl RecordStatus {
+    /// 稳定的状态码，与序列化结果一致
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RecordStatus::Pass => "pass",
+            RecordStatus::Fail => "fail",
+            RecordStatus::Info => "info",
+        }
+    }
+}
+
+/// 结构化报告记录，每条验证结果一条，每个性能指标值一条
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportRecord {
+    /// `validation`或`performance`
+    pub category: String,
+    pub name: String,
+    pub status: RecordStatus,
+    pub detail: String,
+}
+
+/// JSON报告
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructuredReport {
+    pub title: String,
+    pub author: String,
+    pub passed: usize,
+    pub failed: usize,
+    pub records: Vec<ReportRecord>,
+}
+
 /// 报告生成器
 pub struct ReportGenerator {
     report_title: String,
     report_author: String,
     results: Vec<ValidationResult>,
     metrics: HashMap<String, PerformanceMetrics>,
+    fields: Vec<ParsedField>,
+    value_format: ValueFormat,
 }
 
 impl ReportGenerator {
@@ -37,9 +87,21 @@ impl ReportGenerator {
             report_author: author,
             results: Vec::new(),
             metrics: HashMap::new(),
+            fields: Vec::new(),
+            value_format: ValueFormat::Auto,
         }
     }
 
+    /// 设置字段值和约束的显示进制
+    pub fn set_value_format(&mut self, format: ValueFormat) {
+        self.value_format = format;
+    }
+
+    /// 添加已解析的字段
+    pub fn add_parsed_fields(&mut self, fields: Vec<ParsedField>) {
+        self.fields.extend(fields);
+    }
+
     /// 添加验证结果
     pub fn add_validation_result(&mut self, result: ValidationResult) {
         self.results.push(result);
@@ -117,6 +179,39 @@ impl ReportGenerator {
         report
     }
 
+    /// 生成字段值报告
+    pub fn generate_field_report(&self) -> String {
+        let mut report = String::new();
+        let report_title = &self.report_title;
+        let report_author = &self.report_author;
+        report.push_str(&format!("# {report_title}\n\n"));
+        report.push_str(&format!("Author: {report_author}\n\n"));
+        report.push_str("## Field Values\n\n");
+        report.push_str("| Field | Value | Constraint |\n|-------|-------|------------|\n");
+
+        for field in &self.fields {
+            let bit_width = Some(field.bit_len as u32).filter(|&bits| bits > 0);
+            let value = match field.numeric_value() {
+                Some(value) => self.value_format.format_field_value(
+                    value,
+                    bit_width,
+                    field.constraint.as_ref(),
+                ),
+                None => bytes_to_hex(&field.value),
+            };
+            let constraint = field
+                .constraint
+                .as_ref()
+                .map_or("-".to_string(), |constraint| {
+                    self.value_format.format_constraint(constraint, bit_width)
+                });
+            let name = &field.name;
+            report.push_str(&format!("| {name} | {value} | {constraint} |\n"));
+        }
+
+        report
+    }
+
     /// 生成汇总报告
     pub fn generate_summary_report(&self) -> String {
         let mut report = String::new();
@@ -165,9 +260,365 @@ impl ReportGenerator {
         report
     }
 
+    /// 生成语义规则覆盖情况章节
+    ///
+    /// 列出每条语义规则涉及的字段及字段是否存在，仅作概览，不判定规则是否合法
+    pub fn rule_coverage_section(fields: &[SyntaxUnit], rules: &[SemanticRule]) -> String {
+        let names: HashSet<&str> = fields.iter().map(|field| field.field_id.as_str()).collect();
+        let mut section = String::new();
+        section.push_str("## Semantic Rule Coverage\n\n");
+        section.push_str("| # | Rule | Fields | Status |\n|---|------|--------|--------|\n");
+
+        let mut missing_rules = 0;
+        for (i, rule) in rules.iter().enumerate() {
+            let touched = rule_touched_fields(rule);
+            let missing: Vec<&str> = touched
+                .iter()
+                .copied()
+                .filter(|name| !names.contains(name))
+                .collect();
+            let field_list = if touched.is_empty() {
+                "-".to_string()
+            } else {
+                touched.join(", ")
+            };
+            let status = if touched.is_empty() {
+                "➖ no field references".to_string()
+            } else if missing.is_empty() {
+                "✅ ok".to_string()
+            } else {
+                missing_rules += 1;
+                format!("❌ missing: {}", missing.join(", "))
+            };
+            let index = i + 1;
+            let kind = rule.kind();
+            section.push_str(&format!("| {index} | {kind} | {field_list} | {status} |\n"));
+        }
+
+        let total = rules.len();
+        section.push_str(&format!(
+            "\n**Summary: {total} rule(s), {missing_rules} referencing missing fields**\n"
+        ));
+        section
+    }
+
+    /// 汇总验证结果和性能指标为结构化记录
+    ///
+    /// 验证结果按添加顺序排列；性能指标按名称排序，每个指标值一条记录，
+    /// 名称为`<指标名>.<字段>`，时间以微秒为单位
+    pub fn records(&self) -> Vec<ReportRecord> {
+        let mut records: Vec<ReportRecord> = self
+            .results
+            .iter()
+            .map(|result| ReportRecord {
+                category: "validation".to_string(),
+                name: result.message.clone(),
+                status: if result.passed {
+                    RecordStatus::Pass
+                } else {
+                    RecordStatus::Fail
+                },
+                detail: result.details.clone().unwrap_or_default(),
+            })
+            .collect();
+
+        let mut names: Vec<&String> = self.metrics.keys().collect();
+        names.sort();
+        for name in names {
+            let metrics = &self.metrics[name];
+            let values = [
+                (
+                    "processing_time_us",
+                    metrics.processing_time.as_secs_f64() * 1e6,
+                ),
+                ("throughput_pps", metrics.throughput),
+                ("latency_us", metrics.latency.as_secs_f64() * 1e6),
+                ("utilization", metrics.utilization),
+                ("error_rate", metrics.error_rate),
+                ("packet_loss_rate", metrics.packet_loss_rate),
+            ];
+            records.extend(values.iter().map(|(key, value)| ReportRecord {
+                category: "performance".to_string(),
+                name: format!("{name}.{key}"),
+                status: RecordStatus::Info,
+                detail: format!("{value:.3}"),
+            }));
+        }
+        records
+    }
+
+    /// 生成JSON格式报告
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        let records = self.records();
+        let passed = records
+            .iter()
+            .filter(|record| record.status == RecordStatus::Pass)
+            .count();
+        let failed = records
+            .iter()
+            .filter(|record| record.status == RecordStatus::Fail)
+            .count();
+        serde_json::to_string_pretty(&StructuredReport {
+            title: self.report_title.clone(),
+            author: self.report_author.clone(),
+            passed,
+            failed,
+            records,
+        })
+    }
+
+    /// 生成CSV格式报告，表头为[`CSV_HEADER`]
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        csv.push_str(CSV_HEADER);
+        csv.push('\n');
+        for record in self.records() {
+            let row = [
+                record.category.as_str(),
+                record.name.as_str(),
+                record.status.as_str(),
+                record.detail.as_str(),
+            ]
+            .map(csv_escape)
+            .join(",");
+            csv.push_str(&row);
+            csv.push('\n');
+        }
+        csv
+    }
+
     /// 重置报告生成器
     pub fn reset(&mut self) {
         self.results.clear();
         self.metrics.clear();
+        self.fields.clear();
+    }
+}
+
+/// 对含逗号、引号或换行的CSV字段加引号
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// 获取语义规则涉及的本包字段（已去除DSL前缀），跨包字段映射不计入
+fn rule_touched_fields(rule: &SemanticRule) -> Vec<&str> {
+    match rule {
+        SemanticRule::ChecksumRange { .. }
+        | SemanticRule::Dependency { .. }
+        | SemanticRule::Order { .. }
+        | SemanticRule::Pointer { .. }
+        | SemanticRule::AlgorithmSelect { .. }
+        | SemanticRule::Alignment { .. } => rule_field_references(rule),
+        SemanticRule::RoutingDispatch { fields, .. } => {
+            fields.iter().map(|name| clean(name)).collect()
+        }
+        SemanticRule::Validation {
+            field_name,
+            range_start,
+            range_end,
+            ..
+        } => vec![clean(field_name), clean(range_start), clean(range_end)],
+        SemanticRule::Algorithm { field_name, .. }
+        | SemanticRule::LengthRule { field_name, .. }
+        | SemanticRule::SequenceControl { field_name, .. }
+        | SemanticRule::Synchronization { field_name, .. }
+        | SemanticRule::LengthValidation { field_name, .. }
+        | SemanticRule::Multiplexing { field_name, .. }
+        | SemanticRule::PriorityProcessing { field_name, .. }
+        | SemanticRule::PeriodicTransmission { field_name, .. }
+        | SemanticRule::FlowControl { field_name, .. }
+        | SemanticRule::TimeSynchronization { field_name, .. }
+        | SemanticRule::AddressResolution { field_name, .. }
+        | SemanticRule::Security { field_name, .. }
+        | SemanticRule::Redundancy { field_name, .. } => vec![clean(field_name)],
+        SemanticRule::Conditional { .. }
+        | SemanticRule::StateMachine { .. }
+        | SemanticRule::MessageFiltering { .. }
+        | SemanticRule::ErrorDetection { .. }
+        | SemanticRule::FieldMapping { .. } => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apdl_core::{
+        ByteOrder, ChecksumAlgorithm, Constraint, LengthDesc, LengthUnit, ScopeDesc, UnitType,
+    };
+
+    fn parsed_field(
+        name: &str,
+        value: Vec<u8>,
+        bit_len: usize,
+        constraint: Constraint,
+    ) -> ParsedField {
+        ParsedField {
+            name: name.to_string(),
+            raw: value.clone(),
+            value,
+            constraint: Some(constraint),
+            bit_offset: 0,
+            bit_len,
+            byte_order: ByteOrder::BigEndian,
+            decoded: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_field_report_auto_format() {
+        let mut generator = ReportGenerator::new("Frame".to_string(), "tester".to_string());
+        generator.add_parsed_fields(vec![
+            parsed_field("sync", vec![0xEB, 0x90], 16, Constraint::FixedValue(0xEB90)),
+            parsed_field("apid", vec![0x00, 0x64], 11, Constraint::Range(0, 2047)),
+        ]);
+
+        let report = generator.generate_field_report();
+        assert!(report.contains("| sync | 0xEB90 | fixed(0xEB90) |"));
+        assert!(report.contains("| apid | 100 | range(0..=2047) |"));
+
+        generator.set_value_format(ValueFormat::Dec);
+        let report = generator.generate_field_report();
+        assert!(report.contains("| sync | 60304 | fixed(60304) |"));
+        assert!(report.contains("| apid | 100 | range(0..=2047) |"));
+
+        generator.set_value_format(ValueFormat::Hex);
+        let report = generator.generate_field_report();
+        assert!(report.contains("| apid | 0x064 | range(0x000..=0x7FF) |"));
+    }
+
+    fn syntax_unit(name: &str) -> SyntaxUnit {
+        SyntaxUnit::new(
+            name,
+            UnitType::Uint(8),
+            LengthDesc {
+                size: 1,
+                unit: LengthUnit::Byte,
+            },
+            ScopeDesc::Layer("link".to_string()),
+        )
+    }
+
+    #[test]
+    fn test_rule_coverage_section() {
+        let fields: Vec<SyntaxUnit> = ["version", "length", "data", "fecf"]
+            .iter()
+            .map(|name| syntax_unit(name))
+            .collect();
+        let rules = vec![
+            SemanticRule::ChecksumRange {
+                algorithm: ChecksumAlgorithm::CRC16,
+                start_field: "start: version".to_string(),
+                end_field: "end: data".to_string(),
+                covers_self: true,
+            },
+            SemanticRule::LengthRule {
+                field_name: "length".to_string(),
+                expression: "len(data)".to_string(),
+            },
+            SemanticRule::Order {
+                first_field: "version".to_string(),
+                second_field: "trailer".to_string(),
+            },
+            SemanticRule::Conditional {
+                condition: "version == 1".to_string(),
+            },
+        ];
+
+        let section = ReportGenerator::rule_coverage_section(&fields, &rules);
+        assert!(section.contains("## Semantic Rule Coverage"));
+        assert!(section.contains("| 1 | checksum_range | version, data | ✅ ok |"));
+        assert!(section.contains("| 2 | length_rule | length | ✅ ok |"));
+        assert!(section.contains("| 3 | order | version, trailer | ❌ missing: trailer |"));
+        assert!(section.contains("| 4 | conditional | - | ➖ no field references |"));
+        assert!(section.contains("4 rule(s), 1 referencing missing fields"));
+    }
+
+    #[test]
+    fn test_rule_coverage_section_from_dsl() {
+        let dsl = r#"
+field: sync; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Sync"
+field: data; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Data"
+field: fecf; type: Uint16; length: 2byte; scope: layer(link); cover: entire_field; desc: "Check"
+rule: algorithm(field: fecf uses crc16);
+rule: validation(field: fecf; algorithm: crc16_verification; range: from(sync) to(data); desc: "CRC");
+rule: crc_range(start: sync to data);
+"#;
+        let parser = apdl_poem::DslParserImpl::new();
+        let fields = parser.parse_protocol_structure(dsl).unwrap();
+        let rules = parser.parse_semantic_rules(dsl).unwrap();
+
+        // DSL规则参数中的`field: `前缀不被当作字段名的一部分
+        let section = ReportGenerator::rule_coverage_section(&fields, &rules);
+        assert!(section.contains("| 1 | algorithm | fecf | ✅ ok |"));
+        assert!(section.contains("| 2 | validation | fecf, sync, data | ✅ ok |"));
+        assert!(section.contains("3 rule(s), 0 referencing missing fields"));
+    }
+
+    fn generator_with_results() -> ReportGenerator {
+        let mut generator = ReportGenerator::new("TM Frame".to_string(), "tester".to_string());
+        generator.add_validation_result(ValidationResult {
+            passed: true,
+            message: "sync_marker".to_string(),
+            details: None,
+        });
+        generator.add_validation_result(ValidationResult {
+            passed: false,
+            message: "length_rule".to_string(),
+            details: Some("expected 12, actual 10, \"length\" field".to_string()),
+        });
+        generator.add_performance_metrics(
+            "decode".to_string(),
+            PerformanceMetrics {
+                throughput: 1250.5,
+                latency: std::time::Duration::from_micros(40),
+                ..Default::default()
+            },
+        );
+        generator
+    }
+
+    #[test]
+    fn test_json_report_round_trip() {
+        let generator = generator_with_results();
+        let json = generator.to_json().unwrap();
+
+        let report: StructuredReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(report.title, "TM Frame");
+        assert_eq!(report.passed, 1);
+        assert_eq!(report.failed, 1);
+        assert_eq!(report.records, generator.records());
+        assert_eq!(report.records.len(), 8);
+        assert_eq!(report.records[1].status, RecordStatus::Fail);
+        assert!(json.contains("\"status\": \"fail\""));
+
+        let throughput = report
+            .records
+            .iter()
+            .find(|record| record.name == "decode.throughput_pps")
+            .unwrap();
+        assert_eq!(throughput.status, RecordStatus::Info);
+        assert_eq!(throughput.detail, "1250.500");
+    }
+
+    #[test]
+    fn test_csv_report_rows() {
+        let csv = generator_with_results().to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(lines.len(), 1 + 2 + 6);
+        assert_eq!(lines[1], "validation,sync_marker,pass,");
+        assert_eq!(
+            lines[2],
+            "validation,length_rule,fail,\"expected 12, actual 10, \"\"length\"\" field\""
+        );
+        assert!(lines.contains(&"performance,decode.latency_us,info,40.000"));
+
+        let empty = ReportGenerator::new("Empty".to_string(), "tester".to_string());
+        assert_eq!(empty.to_csv(), format!("{CSV_HEADER}\n"));
     }
 }
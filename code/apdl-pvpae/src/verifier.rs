@@ -3,8 +3,22 @@
 //! 实现协议合理性的验证功能
 
 use crate::reporter::ValidationResult;
-use apdl_core::ProtocolUnit;
+use apdl_core::utils::condition::{CompareOp, FieldComparison};
+use apdl_core::utils::expression::{evaluate_expression, BinaryOp, ExpressionContext};
+use apdl_core::utils::find_pattern_offsets;
+use apdl_core::{
+    Constraint, LengthUnit, PackageDefinition, ProtocolStackDefinition, ProtocolUnit, SemanticRule,
+    SyntaxUnit, UnitType,
+};
+use apdl_poem::FrameAssembler;
 use std::collections::HashMap;
+use std::fmt;
+
+/// 未指定时动态长度字段的最大字节数
+pub const DEFAULT_MAX_DYNAMIC_FIELD_SIZE: usize = 65535;
+
+/// 布局问题中表示帧尾的字段名
+pub const FRAME_END: &str = "<end>";
 
 /// 验证类型
 #[derive(Debug, Clone)]
@@ -16,10 +30,196 @@ pub enum VerificationType {
     ComplianceCheck, // 符合性验证
 }
 
+/// 长度规则问题
+#[derive(Debug, Clone, PartialEq)]
+pub enum LengthRuleIssue {
+    /// 长度规则的目标字段不存在
+    MissingField { field_name: String },
+    /// 长度表达式引用了不存在的字段
+    UnknownReference {
+        field_name: String,
+        reference: String,
+    },
+    /// 长度表达式无法静态分析
+    UnsupportedExpression {
+        field_name: String,
+        expression: String,
+    },
+    /// 长度表达式的最大值超出目标字段位宽
+    Overflow {
+        field_name: String,
+        max_value: u64,
+        capacity: u64,
+    },
+}
+
+impl fmt::Display for LengthRuleIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LengthRuleIssue::MissingField { field_name } => {
+                write!(f, "Length rule target field '{field_name}' not found")
+            }
+            LengthRuleIssue::UnknownReference {
+                field_name,
+                reference,
+            } => write!(
+                f,
+                "Length rule for '{field_name}' references unknown field '{reference}'"
+            ),
+            LengthRuleIssue::UnsupportedExpression {
+                field_name,
+                expression,
+            } => write!(
+                f,
+                "Length rule for '{field_name}' has unsupported expression '{expression}'"
+            ),
+            LengthRuleIssue::Overflow {
+                field_name,
+                max_value,
+                capacity,
+            } => write!(
+                f,
+                "Length field '{field_name}' may overflow: value can reach {max_value}, field holds at most {capacity}"
+            ),
+        }
+    }
+}
+
+/// 条件规则问题
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConditionalIssue {
+    /// 条件引用了不存在的字段
+    UnknownField {
+        condition: String,
+        field_name: String,
+    },
+    /// 条件与引用字段的约束或位宽矛盾，永远不成立
+    Unsatisfiable {
+        condition: String,
+        field_name: String,
+    },
+}
+
+impl fmt::Display for ConditionalIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConditionalIssue::UnknownField {
+                condition,
+                field_name,
+            } => write!(
+                f,
+                "Condition '{condition}' references unknown field '{field_name}'"
+            ),
+            ConditionalIssue::Unsatisfiable {
+                condition,
+                field_name,
+            } => write!(
+                f,
+                "Condition '{condition}' can never hold for the values allowed for '{field_name}'"
+            ),
+        }
+    }
+}
+
+/// 帧布局问题
+///
+/// 字段按定义顺序依次占据`LengthDesc`声明的位置，字段类型的实际位宽超出该位置时
+/// 与后续字段重叠，不足时留下未说明的空隙
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayoutIssue {
+    /// 字段`a`的数据延伸进字段`b`（`b`为`FRAME_END`时超出帧尾）
+    Overlap { a: String, b: String, bytes: usize },
+    /// 字段`after`与`before`之间存在未被任何字段占用的空隙
+    Gap {
+        after: String,
+        before: String,
+        bytes: usize,
+    },
+    /// 动态长度或表达式长度的字段无法静态检查重叠（警告）
+    UncheckedLength { field_name: String },
+}
+
+impl LayoutIssue {
+    /// 是否仅为警告
+    pub fn is_warning(&self) -> bool {
+        matches!(self, LayoutIssue::UncheckedLength { .. })
+    }
+}
+
+impl fmt::Display for LayoutIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutIssue::Overlap { a, b, bytes } => {
+                write!(f, "Field '{a}' overlaps '{b}' by {bytes} byte(s)")
+            }
+            LayoutIssue::Gap {
+                after,
+                before,
+                bytes,
+            } => write!(
+                f,
+                "Unexplained gap of {bytes} byte(s) between '{after}' and '{before}'"
+            ),
+            LayoutIssue::UncheckedLength { field_name } => write!(
+                f,
+                "Field '{field_name}' has a dynamic length, overlaps cannot be checked"
+            ),
+        }
+    }
+}
+
+/// 多路复用路由可达性分析结果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RouteReport {
+    /// 协议栈中（入口包除外）没有任何可成立的路由指向的包
+    pub unreachable_targets: Vec<String>,
+    /// 约束允许但没有任何路由匹配的字段取值，对应的数据会被丢弃
+    pub unrouted_values: Vec<UnroutedValues>,
+}
+
+impl RouteReport {
+    /// 所有目标可达且所有取值都有路由
+    pub fn is_complete(&self) -> bool {
+        self.unreachable_targets.is_empty() && self.unrouted_values.is_empty()
+    }
+}
+
+/// 某个路由字段上没有匹配路由的取值区间（闭区间）
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnroutedValues {
+    pub package: String,
+    pub field_name: String,
+    pub ranges: Vec<(u64, u64)>,
+}
+
+impl fmt::Display for UnroutedValues {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ranges: Vec<String> = self
+            .ranges
+            .iter()
+            .map(|(min, max)| {
+                if min == max {
+                    min.to_string()
+                } else {
+                    format!("{min}..={max}")
+                }
+            })
+            .collect();
+        write!(
+            f,
+            "Package '{}' field '{}' has no route for value(s) {}",
+            self.package,
+            self.field_name,
+            ranges.join(", ")
+        )
+    }
+}
+
 /// 协议验证器
 #[derive(Default)]
 pub struct ProtocolVerifier {
     verifications: HashMap<String, VerificationType>,
+    max_dynamic_field_size: Option<usize>,
 }
 
 impl ProtocolVerifier {
@@ -120,6 +320,414 @@ impl ProtocolVerifier {
         }
     }
 
+    /// 执行约束位宽验证，检查枚举值、固定值和范围边界是否超出字段位宽
+    pub fn verify_constraint_widths(&self, units: &[SyntaxUnit]) -> ValidationResult {
+        let errors: Vec<String> = units
+            .iter()
+            .filter_map(|unit| unit.check_constraint_width().err())
+            .map(|e| e.to_string())
+            .collect();
+        let passed = errors.is_empty();
+        ValidationResult {
+            passed,
+            message: "Constraint width verification".to_string(),
+            details: if passed {
+                None
+            } else {
+                Some(errors.join("; "))
+            },
+        }
+    }
+
+    /// 执行同步标志唯一性验证，检查同步标志位于帧首且未在帧中其余位置出现
+    pub fn verify_sync_marker_unique(&self, frame: &[u8], marker: &[u8]) -> ValidationResult {
+        let mut errors = Vec::new();
+        if !frame.starts_with(marker) {
+            errors.push("Frame does not start with sync marker".to_string());
+        }
+        let offsets: Vec<String> = find_pattern_offsets(frame, marker)
+            .into_iter()
+            .filter(|&offset| offset > 0)
+            .map(|offset| offset.to_string())
+            .collect();
+        if !offsets.is_empty() {
+            errors.push(format!(
+                "Sync marker found in payload at offsets: {}",
+                offsets.join(", ")
+            ));
+        }
+
+        let passed = errors.is_empty();
+        ValidationResult {
+            passed,
+            message: "Sync marker uniqueness verification".to_string(),
+            details: if passed {
+                None
+            } else {
+                Some(errors.join("; "))
+            },
+        }
+    }
+
+    /// 执行校验范围验证，检查校验和规则覆盖的字段是否都在校验字段的作用范围内
+    ///
+    /// 层范围的校验字段只能覆盖本层字段和承载上层数据的RawData字段，
+    /// 跨越多层的端到端校验需将校验字段声明为`global`或`cross_layer`范围
+    pub fn verify_checksum_scopes(
+        &self,
+        fields: &[SyntaxUnit],
+        rules: &[SemanticRule],
+    ) -> ValidationResult {
+        let position = |name: &str| {
+            let name = name
+                .trim_start_matches("start: ")
+                .trim_start_matches("end: ")
+                .trim();
+            fields.iter().position(|field| field.field_id == name)
+        };
+
+        let mut assembler = FrameAssembler::new();
+        for field in fields {
+            assembler.add_field(field.clone());
+        }
+        for rule in rules {
+            assembler.add_semantic_rule(rule.clone());
+        }
+
+        let mut errors = Vec::new();
+        for rule in rules {
+            let SemanticRule::ChecksumRange {
+                algorithm,
+                start_field,
+                end_field,
+                ..
+            } = rule
+            else {
+                continue;
+            };
+            let (Some(start), Some(end)) = (position(start_field), position(end_field)) else {
+                continue;
+            };
+
+            // 校验字段按组装器的规则确定
+            let end_field = &fields[end].field_id;
+            let checksum_field = match assembler.resolve_checksum_field(algorithm, end_field) {
+                Ok(Some(index)) => &fields[index],
+                Ok(None) => continue,
+                Err(e) => {
+                    errors.push(e.to_string());
+                    continue;
+                }
+            };
+
+            let uncovered: Vec<&str> = fields[start..=end.max(start)]
+                .iter()
+                .filter(|field| !checksum_field.scope.covers_field(field))
+                .map(|field| field.field_id.as_str())
+                .collect();
+            if !uncovered.is_empty() {
+                errors.push(format!(
+                    "Checksum field '{}' with scope {:?} covers fields outside its scope: {}",
+                    checksum_field.field_id,
+                    checksum_field.scope,
+                    uncovered.join(", ")
+                ));
+            }
+        }
+
+        let passed = errors.is_empty();
+        ValidationResult {
+            passed,
+            message: "Checksum scope verification".to_string(),
+            details: if passed {
+                None
+            } else {
+                Some(errors.join("; "))
+            },
+        }
+    }
+
+    /// 设置长度规则验证中动态长度字段的最大字节数
+    pub fn set_max_dynamic_field_size(&mut self, size: usize) {
+        self.max_dynamic_field_size = Some(size);
+    }
+
+    /// 执行长度规则验证，检查目标字段和引用字段是否存在，以及表达式的最大值是否超出目标字段位宽
+    ///
+    /// 动态长度字段按最大字节数（默认[`DEFAULT_MAX_DYNAMIC_FIELD_SIZE`]）计算最大帧长度
+    pub fn verify_length_rules(
+        &self,
+        fields: &[SyntaxUnit],
+        rules: &[SemanticRule],
+    ) -> Vec<LengthRuleIssue> {
+        let layout = FieldLayout::new(
+            fields,
+            self.max_dynamic_field_size
+                .unwrap_or(DEFAULT_MAX_DYNAMIC_FIELD_SIZE),
+        );
+        let mut issues = Vec::new();
+
+        for rule in rules {
+            let SemanticRule::LengthRule {
+                field_name,
+                expression,
+            } = rule
+            else {
+                continue;
+            };
+            let field_name = field_name.trim_start_matches("field: ").trim();
+            let Some(target) = fields.iter().find(|field| field.field_id == field_name) else {
+                issues.push(LengthRuleIssue::MissingField {
+                    field_name: field_name.to_string(),
+                });
+                continue;
+            };
+
+            let range = match evaluate_expression(expression, &layout) {
+                Ok(range) => range,
+                Err(RangeError::UnknownField(reference)) => {
+                    issues.push(LengthRuleIssue::UnknownReference {
+                        field_name: field_name.to_string(),
+                        reference,
+                    });
+                    continue;
+                }
+                Err(RangeError::Unsupported) => {
+                    issues.push(LengthRuleIssue::UnsupportedExpression {
+                        field_name: field_name.to_string(),
+                        expression: expression.clone(),
+                    });
+                    continue;
+                }
+            };
+
+            if let Some(bits) = target.bit_width() {
+                let capacity = max_value_for_bits(bits as usize);
+                if range.max > capacity {
+                    issues.push(LengthRuleIssue::Overflow {
+                        field_name: field_name.to_string(),
+                        max_value: range.max,
+                        capacity,
+                    });
+                }
+            }
+        }
+        issues
+    }
+
+    /// 执行条件规则验证，检查条件引用的字段是否存在，以及条件能否被字段约束和位宽允许的取值满足
+    ///
+    /// 支持`target if field.value <op> value`和`field <op> value`形式，`<op>`为比较运算符；
+    /// 无法静态分析的条件不报告问题
+    pub fn verify_conditionals(
+        &self,
+        fields: &[SyntaxUnit],
+        rules: &[SemanticRule],
+    ) -> Vec<ConditionalIssue> {
+        let mut issues = Vec::new();
+
+        for rule in rules {
+            let SemanticRule::Conditional { condition } = rule else {
+                continue;
+            };
+            let expression = condition
+                .split_once(" if ")
+                .map_or(condition.as_str(), |(_, expression)| expression);
+            let Some(comparison) = FieldComparison::parse(expression) else {
+                continue;
+            };
+            let Some(field) = fields
+                .iter()
+                .find(|field| field.field_id == comparison.field_name)
+            else {
+                issues.push(ConditionalIssue::UnknownField {
+                    condition: condition.clone(),
+                    field_name: comparison.field_name,
+                });
+                continue;
+            };
+
+            if !comparison_satisfiable(&comparison, field) {
+                issues.push(ConditionalIssue::Unsatisfiable {
+                    condition: condition.clone(),
+                    field_name: comparison.field_name,
+                });
+            }
+        }
+        issues
+    }
+
+    /// 静态检查帧布局中的字段重叠和空隙
+    ///
+    /// 各字段从前一字段声明长度的末尾开始，比较字段类型的位宽与声明长度：
+    /// 类型更宽时报告与后续字段的重叠，类型更窄时报告空隙。
+    /// 动态长度字段之后的位置不确定，重叠检查到该字段为止并给出警告
+    pub fn check_layout(&self, fields: &[SyntaxUnit]) -> Vec<LayoutIssue> {
+        let mut issues = Vec::new();
+        let slots: Vec<Option<usize>> = fields
+            .iter()
+            .map(|field| field.length.fixed_bits())
+            .collect();
+        let name_at = |index: usize| {
+            fields
+                .get(index)
+                .map_or(FRAME_END.to_string(), |field| field.field_id.clone())
+        };
+
+        for (index, field) in fields.iter().enumerate() {
+            let Some(slot) = slots[index] else {
+                issues.push(LayoutIssue::UncheckedLength {
+                    field_name: field.field_id.clone(),
+                });
+                continue;
+            };
+            let Some(data_bits) = type_bit_width(&field.unit_type) else {
+                continue;
+            };
+
+            if data_bits < slot {
+                issues.push(LayoutIssue::Gap {
+                    after: field.field_id.clone(),
+                    before: name_at(index + 1),
+                    bytes: (slot - data_bits).div_ceil(8),
+                });
+            } else if data_bits > slot {
+                // 溢出部分相对于下一字段起始位置的区间[0, spill)
+                let spill = data_bits - slot;
+                let mut start = 0;
+                let mut next = index + 1;
+                while start < spill {
+                    let overlapped = match slots.get(next) {
+                        Some(Some(next_slot)) => spill.min(start + next_slot) - start,
+                        // 动态长度字段已单独警告，无法确定重叠范围
+                        Some(None) => break,
+                        None => spill - start,
+                    };
+                    issues.push(LayoutIssue::Overlap {
+                        a: field.field_id.clone(),
+                        b: name_at(next),
+                        bytes: overlapped.div_ceil(8),
+                    });
+                    if next >= fields.len() {
+                        break;
+                    }
+                    start += overlapped;
+                    next += 1;
+                }
+            }
+        }
+        issues
+    }
+
+    /// 分析协议栈中多路复用路由的可达性
+    ///
+    /// 协议栈只记录包名，包的字段和规则从`packages`中按名称查找。
+    /// 路由条件支持`equals N`、`always_route`、`never_route`及`field <op> value`形式；
+    /// 无法解析的条件视为可能成立，其所在字段不报告未路由取值。
+    /// 只有带枚举、范围或固定值约束的字段才检查未路由取值
+    pub fn check_routes(
+        &self,
+        stack: &ProtocolStackDefinition,
+        packages: &[PackageDefinition],
+    ) -> RouteReport {
+        let mut reachable = Vec::new();
+        let mut unrouted_values = Vec::new();
+
+        for package in stack
+            .packages
+            .iter()
+            .filter_map(|name| packages.iter().find(|package| &package.name == name))
+        {
+            let fields: Vec<&SyntaxUnit> = package
+                .layers
+                .iter()
+                .flat_map(|layer| &layer.units)
+                .collect();
+            // 按条件字段分组的已覆盖区间，None表示存在无法分析的条件
+            let mut coverage: Vec<(String, Option<Intervals>)> = Vec::new();
+
+            for rule in package.layers.iter().flat_map(|layer| &layer.rules) {
+                let SemanticRule::Multiplexing {
+                    field_name,
+                    condition,
+                    route_target,
+                    ..
+                } = rule
+                else {
+                    continue;
+                };
+                let field_name = field_name.trim_start_matches("field: ").trim();
+                let (condition_field, matched) = match route_condition(condition, field_name) {
+                    Some((condition_field, intervals)) => (condition_field, Some(intervals)),
+                    None => (field_name.to_string(), None),
+                };
+                let domain = fields
+                    .iter()
+                    .find(|field| field.field_id == condition_field)
+                    .map(|field| field_domain(field));
+
+                let satisfiable = match (&matched, &domain) {
+                    (Some(matched), Some(domain)) => domain.iter().any(|range| {
+                        !subtract_intervals(*range, &merge_intervals(matched.clone()))
+                            .contains(range)
+                    }),
+                    (Some(matched), None) => !matched.is_empty(),
+                    (None, _) => true,
+                };
+                if satisfiable {
+                    reachable.push(route_target.as_str());
+                }
+
+                match coverage
+                    .iter_mut()
+                    .find(|(name, _)| *name == condition_field)
+                {
+                    Some((_, covered)) => match (covered.as_mut(), matched) {
+                        (Some(covered), Some(matched)) => covered.extend(matched),
+                        _ => *covered = None,
+                    },
+                    None => coverage.push((condition_field, matched)),
+                }
+            }
+
+            for (field_name, covered) in coverage {
+                let Some(covered) = covered else {
+                    continue;
+                };
+                let Some(field) = fields.iter().find(|field| field.field_id == field_name) else {
+                    continue;
+                };
+                if field.constraint.is_none() {
+                    continue;
+                }
+                let covered = merge_intervals(covered);
+                let ranges: Vec<(u64, u64)> = field_domain(field)
+                    .into_iter()
+                    .flat_map(|range| subtract_intervals(range, &covered))
+                    .collect();
+                if !ranges.is_empty() {
+                    unrouted_values.push(UnroutedValues {
+                        package: package.name.clone(),
+                        field_name,
+                        ranges,
+                    });
+                }
+            }
+        }
+
+        let unreachable_targets = stack
+            .packages
+            .iter()
+            .skip(1)
+            .filter(|name| !reachable.contains(&name.as_str()))
+            .cloned()
+            .collect();
+        RouteReport {
+            unreachable_targets,
+            unrouted_values,
+        }
+    }
+
     /// 运行所有验证
     pub fn run_all_verifications(&self) -> Vec<ValidationResult> {
         // 这里只返回示例结果，实际实现会更复杂
@@ -142,3 +750,760 @@ impl ProtocolVerifier {
         self.verifications.clear();
     }
 }
+
+/// 指定bit数的无符号整数最大值
+fn max_value_for_bits(bits: usize) -> u64 {
+    if bits >= 64 {
+        u64::MAX
+    } else {
+        (1u64 << bits) - 1
+    }
+}
+
+/// 判断字段是否存在满足比较的取值
+fn comparison_satisfiable(comparison: &FieldComparison, field: &SyntaxUnit) -> bool {
+    let capacity = field
+        .bit_width()
+        .map_or(u64::MAX, |bits| max_value_for_bits(bits as usize));
+    let candidates: Vec<u64> = match &field.constraint {
+        Some(Constraint::FixedValue(value)) => vec![*value],
+        Some(Constraint::Enum(entries)) => entries.iter().map(|(_, value)| *value).collect(),
+        Some(Constraint::Range(min, max)) => {
+            return range_satisfiable(comparison, *min, (*max).min(capacity));
+        }
+        Some(Constraint::Custom(_)) | None => return range_satisfiable(comparison, 0, capacity),
+    };
+    candidates
+        .into_iter()
+        .filter(|value| *value <= capacity)
+        .any(|value| comparison.holds_for(value))
+}
+
+/// 判断闭区间内是否存在满足比较的取值
+fn range_satisfiable(comparison: &FieldComparison, min: u64, max: u64) -> bool {
+    if min > max {
+        return false;
+    }
+    let value = comparison.value;
+    match comparison.op {
+        CompareOp::Eq => (min..=max).contains(&value),
+        CompareOp::Ne => min != max || min != value,
+        CompareOp::Lt => min < value,
+        CompareOp::Le => min <= value,
+        CompareOp::Gt => max > value,
+        CompareOp::Ge => max >= value,
+    }
+}
+
+/// 闭区间列表
+type Intervals = Vec<(u64, u64)>;
+
+/// 解析多路复用条件，返回条件字段名和条件成立的取值区间
+///
+/// `equals N`等不指明字段的条件作用于规则的`field_name`
+fn route_condition(condition: &str, field_name: &str) -> Option<(String, Vec<(u64, u64)>)> {
+    let condition = condition.trim();
+    match condition {
+        "always_route" => return Some((field_name.to_string(), vec![(0, u64::MAX)])),
+        "never_route" => return Some((field_name.to_string(), Vec::new())),
+        _ => {}
+    }
+    if let Some(value) = condition.strip_prefix("equals") {
+        let value = value
+            .trim()
+            .trim_start_matches('(')
+            .trim_end_matches(')')
+            .trim();
+        let value = match value
+            .strip_prefix("0x")
+            .or_else(|| value.strip_prefix("0X"))
+        {
+            Some(hex) => u64::from_str_radix(hex, 16).ok()?,
+            None => value.parse().ok()?,
+        };
+        return Some((field_name.to_string(), vec![(value, value)]));
+    }
+    let comparison = FieldComparison::parse(condition)?;
+    let intervals = comparison.intervals();
+    Some((comparison.field_name, intervals))
+}
+
+/// 字段约束和位宽允许的取值区间
+fn field_domain(field: &SyntaxUnit) -> Vec<(u64, u64)> {
+    let capacity = field
+        .bit_width()
+        .map_or(u64::MAX, |bits| max_value_for_bits(bits as usize));
+    let ranges = match &field.constraint {
+        Some(Constraint::FixedValue(value)) => vec![(*value, *value)],
+        Some(Constraint::Enum(entries)) => {
+            entries.iter().map(|(_, value)| (*value, *value)).collect()
+        }
+        Some(Constraint::Range(min, max)) => vec![(*min, *max)],
+        Some(Constraint::Custom(_)) | None => vec![(0, capacity)],
+    };
+    ranges
+        .into_iter()
+        .map(|(min, max)| (min, max.min(capacity)))
+        .filter(|(min, max)| min <= max)
+        .collect()
+}
+
+/// 排序并合并相交或相邻的区间
+fn merge_intervals(mut intervals: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
+    intervals.sort_unstable();
+    let mut merged: Vec<(u64, u64)> = Vec::new();
+    for (min, max) in intervals {
+        match merged.last_mut() {
+            Some(last) if min <= last.1.saturating_add(1) => last.1 = last.1.max(max),
+            _ => merged.push((min, max)),
+        }
+    }
+    merged
+}
+
+/// 从区间中扣除已合并的覆盖区间，返回剩余部分
+fn subtract_intervals(range: (u64, u64), covered: &[(u64, u64)]) -> Vec<(u64, u64)> {
+    let mut remaining = Vec::new();
+    let mut start = range.0;
+    for &(min, max) in covered {
+        if max < start {
+            continue;
+        }
+        if min > range.1 {
+            break;
+        }
+        if min > start {
+            remaining.push((start, min - 1));
+        }
+        match max.checked_add(1) {
+            Some(next) if next <= range.1 => start = next,
+            _ => return remaining,
+        }
+    }
+    remaining.push((start, range.1));
+    remaining
+}
+
+/// 字段类型本身的位宽，原始数据类型没有固定位宽
+fn type_bit_width(unit_type: &UnitType) -> Option<usize> {
+    match unit_type {
+        UnitType::Uint(bits) | UnitType::Bit(bits) => Some(*bits as usize),
+        UnitType::RawData => None,
+        _ => unit_type.fixed_byte_size().map(|size| size * 8),
+    }
+}
+
+/// 取值区间
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ValueRange {
+    min: u64,
+    max: u64,
+}
+
+impl ValueRange {
+    fn exact(value: u64) -> Self {
+        Self {
+            min: value,
+            max: value,
+        }
+    }
+}
+
+/// 字段布局：各字段的字节数区间和起始字节偏移区间
+struct FieldLayout<'a> {
+    fields: &'a [SyntaxUnit],
+    sizes: Vec<ValueRange>,
+    positions: Vec<ValueRange>,
+    total: ValueRange,
+}
+
+impl<'a> FieldLayout<'a> {
+    fn new(fields: &'a [SyntaxUnit], max_dynamic_size: usize) -> Self {
+        let bit_ranges: Vec<ValueRange> = fields
+            .iter()
+            .map(|field| match field.bit_width() {
+                Some(bits) => ValueRange::exact(bits as u64),
+                None => match field.length.unit {
+                    LengthUnit::Dynamic | LengthUnit::Expression(_) => ValueRange {
+                        min: 0,
+                        max: max_dynamic_size as u64 * 8,
+                    },
+                    _ => ValueRange::exact(field.length.size as u64 * 8),
+                },
+            })
+            .collect();
+
+        let mut positions = Vec::with_capacity(fields.len());
+        let mut offset = ValueRange::exact(0);
+        for bits in &bit_ranges {
+            positions.push(ValueRange {
+                min: offset.min / 8,
+                max: offset.max / 8,
+            });
+            offset.min += bits.min;
+            offset.max += bits.max;
+        }
+
+        Self {
+            fields,
+            sizes: bit_ranges
+                .iter()
+                .map(|bits| ValueRange {
+                    min: bits.min.div_ceil(8),
+                    max: bits.max.div_ceil(8),
+                })
+                .collect(),
+            positions,
+            total: ValueRange {
+                min: offset.min.div_ceil(8),
+                max: offset.max.div_ceil(8),
+            },
+        }
+    }
+
+    fn index_of(&self, name: &str) -> Result<usize, RangeError> {
+        let name = name.trim().trim_start_matches("field:").trim();
+        self.fields
+            .iter()
+            .position(|field| field.field_id == name)
+            .ok_or_else(|| RangeError::UnknownField(name.to_string()))
+    }
+}
+
+/// 区间求值错误
+enum RangeError {
+    UnknownField(String),
+    Unsupported,
+}
+
+/// 按取值区间求值长度表达式
+impl ExpressionContext for FieldLayout<'_> {
+    type Value = ValueRange;
+    type Error = RangeError;
+
+    fn number(&self, value: u64) -> ValueRange {
+        ValueRange::exact(value)
+    }
+
+    fn binary(
+        &self,
+        op: BinaryOp,
+        left: ValueRange,
+        right: ValueRange,
+    ) -> Result<ValueRange, RangeError> {
+        Ok(match op {
+            BinaryOp::Add => ValueRange {
+                min: left.min.saturating_add(right.min),
+                max: left.max.saturating_add(right.max),
+            },
+            BinaryOp::Sub => ValueRange {
+                min: left.min.saturating_sub(right.max),
+                max: left.max.saturating_sub(right.min),
+            },
+            BinaryOp::Mul => ValueRange {
+                min: left.min.saturating_mul(right.min),
+                max: left.max.saturating_mul(right.max),
+            },
+            BinaryOp::Div => {
+                if right.min == 0 {
+                    return Err(RangeError::Unsupported);
+                }
+                ValueRange {
+                    min: left.min / right.max,
+                    max: left.max / right.min,
+                }
+            }
+            BinaryOp::Min => ValueRange {
+                min: left.min.min(right.min),
+                max: left.max.min(right.max),
+            },
+            BinaryOp::Max => ValueRange {
+                min: left.min.max(right.min),
+                max: left.max.max(right.max),
+            },
+        })
+    }
+
+    fn function(&self, name: &str, field_name: &str) -> Result<ValueRange, RangeError> {
+        let index = self.index_of(field_name)?;
+        match name {
+            "len" => Ok(self.sizes[index]),
+            "pos" => Ok(self.positions[index]),
+            "val" => {
+                let bits = self.fields[index]
+                    .bit_width()
+                    .ok_or(RangeError::Unsupported)?;
+                Ok(ValueRange {
+                    min: 0,
+                    max: max_value_for_bits(bits as usize),
+                })
+            }
+            _ => Err(RangeError::Unsupported),
+        }
+    }
+
+    fn identifier(&self, name: &str) -> Result<ValueRange, RangeError> {
+        if name == "total_length" {
+            return Ok(self.total);
+        }
+        match name.strip_suffix("_length") {
+            Some(field_name) => self.function("len", field_name),
+            None => Err(RangeError::Unsupported),
+        }
+    }
+
+    fn syntax_error(&self, _expression: &str, _reason: &str) -> RangeError {
+        RangeError::Unsupported
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apdl_core::{
+        AlgorithmAst, ChecksumAlgorithm, LayerDefinition, LengthDesc, ScopeDesc, UnitType,
+    };
+
+    fn make_field(name: &str, unit_type: UnitType, size: usize, unit: LengthUnit) -> SyntaxUnit {
+        SyntaxUnit::new(
+            name,
+            unit_type,
+            LengthDesc { size, unit },
+            ScopeDesc::Layer("link".to_string()),
+        )
+    }
+
+    fn two_layer_fields(checksum_scope: ScopeDesc) -> Vec<SyntaxUnit> {
+        let link_header = make_field("vcid", UnitType::Uint(16), 2, LengthUnit::Byte);
+        let mut apid = make_field("apid", UnitType::Uint(16), 2, LengthUnit::Byte);
+        apid.scope = ScopeDesc::Layer("net".to_string());
+        let mut payload = make_field("payload", UnitType::RawData, 0, LengthUnit::Dynamic);
+        payload.scope = ScopeDesc::Layer("application".to_string());
+        let mut fecf = make_field("fecf", UnitType::Uint(16), 2, LengthUnit::Byte);
+        fecf.alg = Some(AlgorithmAst::Crc16);
+        fecf.scope = checksum_scope;
+        vec![link_header, apid, payload, fecf]
+    }
+
+    #[test]
+    fn test_checksum_scope_reports_ambiguous_checksum_field() {
+        let verifier = ProtocolVerifier::new();
+        let rules = vec![SemanticRule::ChecksumRange {
+            algorithm: ChecksumAlgorithm::CRC16,
+            start_field: "vcid".to_string(),
+            end_field: "apid".to_string(),
+            covers_self: true,
+        }];
+        let mut fields = two_layer_fields(ScopeDesc::Global("end2end".to_string()));
+        let mut hcrc = make_field("hcrc", UnitType::Uint(16), 2, LengthUnit::Byte);
+        hcrc.alg = Some(AlgorithmAst::Crc16);
+        fields.insert(0, hcrc);
+
+        // 与组装器一致：两个CRC16校验字段都不紧随校验范围时无法确定校验字段
+        let result = verifier.verify_checksum_scopes(&fields, &rules);
+        assert!(!result.passed);
+        let details = result.details.unwrap();
+        assert!(details.contains("hcrc") && details.contains("fecf"));
+    }
+
+    #[test]
+    fn test_constraint_widths_reject_over_wide_fixed_value() {
+        let verifier = ProtocolVerifier::new();
+        let mut version = make_field("version", UnitType::Bit(3), 3, LengthUnit::Bit);
+        version.constraint = Some(Constraint::FixedValue(0x08));
+        let mut sync = make_field("sync", UnitType::Uint(16), 2, LengthUnit::Byte);
+        sync.constraint = Some(Constraint::FixedValue(0xEB90));
+
+        let result = verifier.verify_constraint_widths(&[sync.clone(), version]);
+        assert!(!result.passed);
+        let details = result.details.unwrap();
+        assert!(details.contains("'version' fixed value 0x8 exceeds 3-bit width"));
+        assert!(!details.contains("sync"));
+
+        assert!(verifier.verify_constraint_widths(&[sync]).passed);
+    }
+
+    #[test]
+    fn test_sync_marker_unique() {
+        let verifier = ProtocolVerifier::new();
+        let marker = [0xEB, 0x90];
+
+        assert!(
+            verifier
+                .verify_sync_marker_unique(&[0xEB, 0x90, 0x01, 0x02], &marker)
+                .passed
+        );
+
+        // 负载中出现同步标志字节
+        let result = verifier.verify_sync_marker_unique(&[0xEB, 0x90, 0x01, 0xEB, 0x90], &marker);
+        assert!(!result.passed);
+        assert_eq!(
+            result.details.as_deref(),
+            Some("Sync marker found in payload at offsets: 3")
+        );
+
+        // 帧首不是同步标志
+        let result = verifier.verify_sync_marker_unique(&[0x00, 0xEB, 0x90], &marker);
+        assert!(!result.passed);
+        let details = result.details.unwrap();
+        assert!(details.contains("Frame does not start with sync marker"));
+        assert!(details.contains("offsets: 1"));
+    }
+
+    #[test]
+    fn test_checksum_scope_across_layers() {
+        let verifier = ProtocolVerifier::new();
+        let rules = vec![SemanticRule::ChecksumRange {
+            algorithm: ChecksumAlgorithm::CRC16,
+            start_field: "vcid".to_string(),
+            end_field: "payload".to_string(),
+            covers_self: true,
+        }];
+
+        // 层范围的校验字段不能覆盖网络层字段
+        let fields = two_layer_fields(ScopeDesc::Layer("link".to_string()));
+        let result = verifier.verify_checksum_scopes(&fields, &rules);
+        assert!(!result.passed);
+        let details = result.details.unwrap();
+        assert!(details.contains("apid") && !details.contains("payload"));
+
+        // 端到端校验和跨层校验覆盖两层字段
+        let fields = two_layer_fields(ScopeDesc::Global("end2end".to_string()));
+        assert!(verifier.verify_checksum_scopes(&fields, &rules).passed);
+        let fields = two_layer_fields(ScopeDesc::CrossLayer("net".to_string(), "link".to_string()));
+        assert!(verifier.verify_checksum_scopes(&fields, &rules).passed);
+    }
+
+    fn large_payload_fields(length_bytes: usize) -> Vec<SyntaxUnit> {
+        vec![
+            make_field("sync", UnitType::Uint(16), 2, LengthUnit::Byte),
+            make_field(
+                "frame_len",
+                UnitType::Uint(length_bytes as u8 * 8),
+                length_bytes,
+                LengthUnit::Byte,
+            ),
+            make_field("payload", UnitType::RawData, 0, LengthUnit::Dynamic),
+            make_field("fecf", UnitType::Uint(16), 2, LengthUnit::Byte),
+        ]
+    }
+
+    fn length_rule(field_name: &str, expression: &str) -> SemanticRule {
+        SemanticRule::LengthRule {
+            field_name: field_name.to_string(),
+            expression: expression.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_one_byte_length_field_over_large_payload() {
+        let verifier = ProtocolVerifier::new();
+        let fields = large_payload_fields(1);
+        let rules = vec![length_rule("frame_len", "(total_length - 1)")];
+
+        let issues = verifier.verify_length_rules(&fields, &rules);
+        assert_eq!(
+            issues,
+            vec![LengthRuleIssue::Overflow {
+                field_name: "frame_len".to_string(),
+                max_value: 65539,
+                capacity: 255,
+            }]
+        );
+
+        // 限制动态字段大小后不再溢出
+        let mut verifier = ProtocolVerifier::new();
+        verifier.set_max_dynamic_field_size(200);
+        assert!(verifier.verify_length_rules(&fields, &rules).is_empty());
+    }
+
+    #[test]
+    fn test_two_byte_length_field_fits_payload() {
+        let verifier = ProtocolVerifier::new();
+        let fields = large_payload_fields(2);
+        let rules = vec![length_rule("frame_len", "len(payload)")];
+        assert!(verifier.verify_length_rules(&fields, &rules).is_empty());
+
+        // pos()/len()组合表达式：payload最大65535字节加上fecf超出16位
+        let rules = vec![length_rule(
+            "frame_len",
+            "pos(fecf) + len(fecf) - pos(payload)",
+        )];
+        let issues = verifier.verify_length_rules(&fields, &rules);
+        assert!(matches!(
+            issues.as_slice(),
+            [LengthRuleIssue::Overflow {
+                max_value: 65537,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn test_missing_fields_in_length_rules() {
+        let verifier = ProtocolVerifier::new();
+        let fields = large_payload_fields(2);
+        let rules = vec![
+            length_rule("no_such_len", "len(payload)"),
+            length_rule("frame_len", "len(missing) + 4"),
+        ];
+
+        let issues = verifier.verify_length_rules(&fields, &rules);
+        assert_eq!(
+            issues,
+            vec![
+                LengthRuleIssue::MissingField {
+                    field_name: "no_such_len".to_string(),
+                },
+                LengthRuleIssue::UnknownReference {
+                    field_name: "frame_len".to_string(),
+                    reference: "missing".to_string(),
+                },
+            ]
+        );
+        assert!(issues[1].to_string().contains("missing"));
+    }
+    fn conditional(condition: &str) -> SemanticRule {
+        SemanticRule::Conditional {
+            condition: condition.to_string(),
+        }
+    }
+
+    fn versioned_fields() -> Vec<SyntaxUnit> {
+        let mut version = make_field("version", UnitType::Uint(8), 1, LengthUnit::Byte);
+        version.constraint = Some(Constraint::Range(0, 7));
+        let secondary = make_field("secondary_header", UnitType::Uint(16), 2, LengthUnit::Byte);
+        vec![version, secondary]
+    }
+
+    #[test]
+    fn test_impossible_condition() {
+        let verifier = ProtocolVerifier::new();
+        let rules = vec![conditional("secondary_header if version.value == 9")];
+
+        let issues = verifier.verify_conditionals(&versioned_fields(), &rules);
+        assert_eq!(
+            issues,
+            vec![ConditionalIssue::Unsatisfiable {
+                condition: "secondary_header if version.value == 9".to_string(),
+                field_name: "version".to_string(),
+            }]
+        );
+
+        // 超出范围上界的比较同样不可能成立
+        let rules = vec![conditional("version > 7")];
+        assert_eq!(
+            verifier
+                .verify_conditionals(&versioned_fields(), &rules)
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_possible_condition() {
+        let verifier = ProtocolVerifier::new();
+        let rules = vec![
+            conditional("secondary_header if version.value == 0x03"),
+            conditional("secondary_header if version.value >= 7"),
+            conditional("version != 9"),
+            // 无法静态分析的条件不报告
+            conditional("secondary_header if crc_enabled"),
+        ];
+        assert!(verifier
+            .verify_conditionals(&versioned_fields(), &rules)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_condition_beyond_field_width_or_unknown_field() {
+        let verifier = ProtocolVerifier::new();
+        let mut fields = versioned_fields();
+        fields[0].constraint = None;
+        let rules = vec![
+            conditional("secondary_header if version.value == 300"),
+            conditional("secondary_header if flags.value == 1"),
+        ];
+
+        let issues = verifier.verify_conditionals(&fields, &rules);
+        assert!(matches!(
+            issues.as_slice(),
+            [
+                ConditionalIssue::Unsatisfiable { .. },
+                ConditionalIssue::UnknownField { field_name, .. },
+            ] if field_name == "flags"
+        ));
+    }
+
+    #[test]
+    fn test_layout_detects_overlap() {
+        let verifier = ProtocolVerifier::new();
+        // apid声明为1字节却是16位类型，溢出的1字节覆盖了seq
+        let fields = vec![
+            make_field("version", UnitType::Uint(8), 1, LengthUnit::Byte),
+            make_field("apid", UnitType::Uint(16), 1, LengthUnit::Byte),
+            make_field("seq", UnitType::Uint(8), 1, LengthUnit::Byte),
+            make_field("crc", UnitType::Uint(16), 2, LengthUnit::Byte),
+        ];
+
+        assert_eq!(
+            verifier.check_layout(&fields),
+            vec![LayoutIssue::Overlap {
+                a: "apid".to_string(),
+                b: "seq".to_string(),
+                bytes: 1,
+            }]
+        );
+
+        // 跨越多个后续字段并超出帧尾
+        let fields = vec![
+            make_field("header", UnitType::Uint(32), 1, LengthUnit::Byte),
+            make_field("flag", UnitType::Bit(4), 4, LengthUnit::Bit),
+            make_field("tail", UnitType::Uint(8), 1, LengthUnit::Byte),
+        ];
+        let issues = verifier.check_layout(&fields);
+        assert_eq!(issues.len(), 3);
+        assert_eq!(
+            issues[2],
+            LayoutIssue::Overlap {
+                a: "header".to_string(),
+                b: FRAME_END.to_string(),
+                bytes: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_layout_detects_one_byte_gap() {
+        let verifier = ProtocolVerifier::new();
+        let fields = vec![
+            make_field("version", UnitType::Bit(3), 3, LengthUnit::Bit),
+            make_field("apid", UnitType::Bit(13), 13, LengthUnit::Bit),
+            make_field("length", UnitType::Uint(16), 3, LengthUnit::Byte),
+            make_field("payload", UnitType::RawData, 0, LengthUnit::Dynamic),
+        ];
+
+        let issues = verifier.check_layout(&fields);
+        assert_eq!(
+            issues,
+            vec![
+                LayoutIssue::Gap {
+                    after: "length".to_string(),
+                    before: "payload".to_string(),
+                    bytes: 1,
+                },
+                LayoutIssue::UncheckedLength {
+                    field_name: "payload".to_string(),
+                },
+            ]
+        );
+        assert!(!issues[0].is_warning());
+        assert!(issues[1].is_warning());
+        assert_eq!(
+            issues[0].to_string(),
+            "Unexplained gap of 1 byte(s) between 'length' and 'payload'"
+        );
+    }
+
+    fn routed_package(fields: Vec<SyntaxUnit>, routes: &[(&str, &str, &str)]) -> PackageDefinition {
+        let mut package = PackageDefinition::new(
+            "frame".to_string(),
+            "Frame".to_string(),
+            "telemetry".to_string(),
+            String::new(),
+        );
+        package.layers.push(LayerDefinition {
+            name: "link".to_string(),
+            units: fields,
+            rules: routes
+                .iter()
+                .map(
+                    |(field_name, condition, target)| SemanticRule::Multiplexing {
+                        field_name: field_name.to_string(),
+                        condition: condition.to_string(),
+                        route_target: target.to_string(),
+                        description: String::new(),
+                    },
+                )
+                .collect(),
+        });
+        package
+    }
+
+    fn stack_of(names: &[&str]) -> ProtocolStackDefinition {
+        let mut stack = ProtocolStackDefinition::new("stack".to_string(), String::new());
+        stack.packages = names.iter().map(|name| name.to_string()).collect();
+        stack
+    }
+
+    #[test]
+    fn test_routes_exhaustive() {
+        let verifier = ProtocolVerifier::new();
+        let mut ptype = make_field("ptype", UnitType::Bit(1), 1, LengthUnit::Bit);
+        ptype.constraint = Some(Constraint::Enum(vec![
+            ("TM".to_string(), 0),
+            ("TC".to_string(), 1),
+        ]));
+        let mut vcid = make_field("vcid", UnitType::Bit(3), 3, LengthUnit::Bit);
+        vcid.constraint = Some(Constraint::Range(0, 7));
+        let package = routed_package(
+            vec![ptype, vcid],
+            &[
+                ("ptype", "equals 0", "tm_processor"),
+                ("ptype", "ptype == 1", "tc_processor"),
+                ("vcid", "vcid < 4", "realtime"),
+                ("vcid", "vcid.value >= 4", "playback"),
+            ],
+        );
+        let stack = stack_of(&[
+            "frame",
+            "tm_processor",
+            "tc_processor",
+            "realtime",
+            "playback",
+        ]);
+
+        let report = verifier.check_routes(&stack, &[package]);
+        assert!(report.is_complete(), "unexpected report: {report:?}");
+    }
+
+    #[test]
+    fn test_routes_incomplete() {
+        let verifier = ProtocolVerifier::new();
+        let mut vcid = make_field("vcid", UnitType::Bit(3), 3, LengthUnit::Bit);
+        vcid.constraint = Some(Constraint::Range(0, 7));
+        let package = routed_package(
+            vec![vcid],
+            &[
+                ("vcid", "vcid == 0", "realtime"),
+                ("vcid", "vcid >= 4", "playback"),
+                // 3位字段永远不会等于9
+                ("vcid", "vcid == 9", "idle"),
+                ("field: vcid", "pgn_based_routing", "custom"),
+            ],
+        );
+        let stack = stack_of(&["frame", "realtime", "playback", "idle", "custom", "orphan"]);
+
+        let report = verifier.check_routes(&stack, std::slice::from_ref(&package));
+        assert_eq!(report.unreachable_targets, vec!["idle", "orphan"]);
+        // 存在无法分析的条件时不报告未路由取值
+        assert!(report.unrouted_values.is_empty());
+
+        let mut package = package;
+        package.layers[0].rules.pop();
+        let report = verifier.check_routes(&stack, &[package]);
+        assert_eq!(
+            report.unrouted_values,
+            vec![UnroutedValues {
+                package: "frame".to_string(),
+                field_name: "vcid".to_string(),
+                ranges: vec![(1, 3)],
+            }]
+        );
+        assert_eq!(
+            report.unrouted_values[0].to_string(),
+            "Package 'frame' field 'vcid' has no route for value(s) 1..=3"
+        );
+        assert!(!report.is_complete());
+    }
+}
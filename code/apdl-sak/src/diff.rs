@@ -0,0 +1,462 @@
+//! 协议版本差异模块
+//!
+//! 比较两个版本的协议包定义，生成字段和语义规则的变更记录（changelog）
+
+use apdl_core::utils::ValueFormat;
+use apdl_core::{PackageDefinition, SemanticRule, SyntaxUnit};
+
+use crate::exporters::{format_length, ExportFormatHandler};
+use crate::generator::SpecGenerator;
+
+/// 字段的单项变化
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldDetail {
+    /// 字段类型变化
+    Type { old: String, new: String },
+    /// 字段长度变化
+    Length { old: String, new: String },
+    /// 约束变化（`None`表示无约束）
+    Constraint {
+        old: Option<String>,
+        new: Option<String>,
+    },
+    /// 字段在包内的bit偏移变化（`None`表示前面存在动态长度字段，偏移不固定）
+    Position {
+        old: Option<usize>,
+        new: Option<usize>,
+    },
+}
+
+/// 字段变更
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldChange {
+    Added {
+        package: String,
+        field: String,
+    },
+    Removed {
+        package: String,
+        field: String,
+    },
+    /// 类型、长度、约束和位置均不变而名称变化的字段
+    Renamed {
+        package: String,
+        old_name: String,
+        new_name: String,
+    },
+    Changed {
+        package: String,
+        field: String,
+        details: Vec<FieldDetail>,
+    },
+}
+
+/// 语义规则变更
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleChange {
+    Added { package: String, rule: SemanticRule },
+    Removed { package: String, rule: SemanticRule },
+}
+
+/// 两个协议版本之间的差异
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpecDiff {
+    pub packages_added: Vec<String>,
+    pub packages_removed: Vec<String>,
+    pub fields: Vec<FieldChange>,
+    pub rules: Vec<RuleChange>,
+}
+
+impl SpecDiff {
+    /// 两个版本是否完全一致
+    pub fn is_empty(&self) -> bool {
+        self.packages_added.is_empty()
+            && self.packages_removed.is_empty()
+            && self.fields.is_empty()
+            && self.rules.is_empty()
+    }
+
+    /// 生成Markdown格式的changelog章节
+    pub fn changelog(&self) -> String {
+        let mut log = String::from("## Changelog\n\n");
+        if self.is_empty() {
+            log.push_str("No changes.\n");
+            return log;
+        }
+        for package in &self.packages_added {
+            log.push_str(&format!("- Added package `{package}`\n"));
+        }
+        for package in &self.packages_removed {
+            log.push_str(&format!("- Removed package `{package}`\n"));
+        }
+        for change in &self.fields {
+            let line = match change {
+                FieldChange::Added { package, field } => {
+                    format!("Added field `{package}.{field}`")
+                }
+                FieldChange::Removed { package, field } => {
+                    format!("Removed field `{package}.{field}`")
+                }
+                FieldChange::Renamed {
+                    package,
+                    old_name,
+                    new_name,
+                } => format!("Renamed field `{package}.{old_name}` to `{new_name}`"),
+                FieldChange::Changed {
+                    package,
+                    field,
+                    details,
+                } => {
+                    let details: Vec<String> = details.iter().map(describe_detail).collect();
+                    format!("Changed field `{package}.{field}`: {}", details.join("; "))
+                }
+            };
+            log.push_str(&format!("- {line}\n"));
+        }
+        for change in &self.rules {
+            let line = match change {
+                RuleChange::Added { package, rule } => {
+                    format!("Added {} rule in `{package}`: {rule:?}", rule.kind())
+                }
+                RuleChange::Removed { package, rule } => {
+                    format!("Removed {} rule in `{package}`: {rule:?}", rule.kind())
+                }
+            };
+            log.push_str(&format!("- {line}\n"));
+        }
+        log
+    }
+
+    /// 通过指定导出器输出changelog章节
+    pub fn export(&self, exporter: &dyn ExportFormatHandler) -> String {
+        exporter.export(&self.changelog())
+    }
+}
+
+impl SpecGenerator {
+    /// 比较两个版本的协议包定义
+    ///
+    /// 包按名称匹配，字段按`field_id`匹配，语义规则按内容匹配
+    pub fn diff(old: &[PackageDefinition], new: &[PackageDefinition]) -> SpecDiff {
+        let mut diff = SpecDiff::default();
+        for package in old {
+            match new.iter().find(|candidate| candidate.name == package.name) {
+                Some(new_package) => diff_package(package, new_package, &mut diff),
+                None => diff.packages_removed.push(package.name.clone()),
+            }
+        }
+        for package in new {
+            if !old.iter().any(|candidate| candidate.name == package.name) {
+                diff.packages_added.push(package.name.clone());
+            }
+        }
+        diff
+    }
+}
+
+/// 包内字段及其bit偏移
+struct PlacedField<'a> {
+    unit: &'a SyntaxUnit,
+    bit_offset: Option<usize>,
+}
+
+/// 按定义顺序展开包内所有字段并计算bit偏移
+fn placed_fields(package: &PackageDefinition) -> Vec<PlacedField<'_>> {
+    let mut offset = Some(0usize);
+    let mut fields = Vec::new();
+    for unit in package.layers.iter().flat_map(|layer| &layer.units) {
+        fields.push(PlacedField {
+            unit,
+            bit_offset: offset,
+        });
+        offset = offset
+            .zip(unit.length.fixed_bits())
+            .map(|(offset, bits)| offset + bits);
+    }
+    fields
+}
+
+fn diff_package(old: &PackageDefinition, new: &PackageDefinition, diff: &mut SpecDiff) {
+    let package = &old.name;
+    let old_fields = placed_fields(old);
+    let new_fields = placed_fields(new);
+    let find = |fields: &[PlacedField], name: &str| {
+        fields.iter().position(|field| field.unit.field_id == name)
+    };
+
+    let mut removed: Vec<&PlacedField> = Vec::new();
+    for old_field in &old_fields {
+        match find(&new_fields, &old_field.unit.field_id) {
+            Some(index) => {
+                let details = field_details(old_field, &new_fields[index]);
+                if !details.is_empty() {
+                    diff.fields.push(FieldChange::Changed {
+                        package: package.clone(),
+                        field: old_field.unit.field_id.clone(),
+                        details,
+                    });
+                }
+            }
+            None => removed.push(old_field),
+        }
+    }
+    let mut added: Vec<&PlacedField> = new_fields
+        .iter()
+        .filter(|field| find(&old_fields, &field.unit.field_id).is_none())
+        .collect();
+
+    // 定义和位置完全相同的删除/新增字段视为重命名
+    for old_field in removed {
+        let rename = added
+            .iter()
+            .position(|new_field| field_details(old_field, new_field).is_empty());
+        match rename {
+            Some(index) => {
+                let new_field = added.remove(index);
+                diff.fields.push(FieldChange::Renamed {
+                    package: package.clone(),
+                    old_name: old_field.unit.field_id.clone(),
+                    new_name: new_field.unit.field_id.clone(),
+                });
+            }
+            None => diff.fields.push(FieldChange::Removed {
+                package: package.clone(),
+                field: old_field.unit.field_id.clone(),
+            }),
+        }
+    }
+    for new_field in added {
+        diff.fields.push(FieldChange::Added {
+            package: package.clone(),
+            field: new_field.unit.field_id.clone(),
+        });
+    }
+
+    let old_rules: Vec<&SemanticRule> = old.layers.iter().flat_map(|layer| &layer.rules).collect();
+    let mut new_rules: Vec<&SemanticRule> =
+        new.layers.iter().flat_map(|layer| &layer.rules).collect();
+    for rule in old_rules {
+        match new_rules.iter().position(|candidate| *candidate == rule) {
+            Some(index) => {
+                new_rules.remove(index);
+            }
+            None => diff.rules.push(RuleChange::Removed {
+                package: package.clone(),
+                rule: rule.clone(),
+            }),
+        }
+    }
+    for rule in new_rules {
+        diff.rules.push(RuleChange::Added {
+            package: package.clone(),
+            rule: rule.clone(),
+        });
+    }
+}
+
+/// 比较同一字段在两个版本中的定义
+fn field_details(old: &PlacedField, new: &PlacedField) -> Vec<FieldDetail> {
+    let mut details = Vec::new();
+    if old.unit.unit_type != new.unit.unit_type {
+        details.push(FieldDetail::Type {
+            old: format!("{:?}", old.unit.unit_type),
+            new: format!("{:?}", new.unit.unit_type),
+        });
+    }
+    if old.unit.length != new.unit.length {
+        details.push(FieldDetail::Length {
+            old: format_length(old.unit),
+            new: format_length(new.unit),
+        });
+    }
+    if old.unit.constraint != new.unit.constraint {
+        let describe = |unit: &SyntaxUnit| {
+            unit.constraint
+                .as_ref()
+                .map(|constraint| ValueFormat::Auto.format_constraint(constraint, unit.bit_width()))
+        };
+        details.push(FieldDetail::Constraint {
+            old: describe(old.unit),
+            new: describe(new.unit),
+        });
+    }
+    if old.bit_offset != new.bit_offset {
+        details.push(FieldDetail::Position {
+            old: old.bit_offset,
+            new: new.bit_offset,
+        });
+    }
+    details
+}
+
+fn describe_detail(detail: &FieldDetail) -> String {
+    let offset = |offset: &Option<usize>| match offset {
+        Some(bits) => format!("bit {bits}"),
+        None => "dynamic".to_string(),
+    };
+    match detail {
+        FieldDetail::Type { old, new } => format!("type {old} -> {new}"),
+        FieldDetail::Length { old, new } => format!("length {old} -> {new}"),
+        FieldDetail::Constraint { old, new } => format!(
+            "constraint {} -> {}",
+            old.as_deref().unwrap_or("none"),
+            new.as_deref().unwrap_or("none")
+        ),
+        FieldDetail::Position { old, new } => {
+            format!("position {} -> {}", offset(old), offset(new))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exporters::MarkdownExporter;
+    use apdl_core::{
+        ChecksumAlgorithm, Constraint, LayerDefinition, LengthDesc, LengthUnit, ScopeDesc, UnitType,
+    };
+
+    fn unit(name: &str, bits: usize) -> SyntaxUnit {
+        SyntaxUnit::new(
+            name,
+            UnitType::Bit(bits as u8),
+            LengthDesc {
+                size: bits,
+                unit: LengthUnit::Bit,
+            },
+            ScopeDesc::Layer("header".to_string()),
+        )
+    }
+
+    fn space_packet(units: Vec<SyntaxUnit>, rules: Vec<SemanticRule>) -> PackageDefinition {
+        let mut package = PackageDefinition::new(
+            "space_packet".to_string(),
+            "Space Packet".to_string(),
+            "telemetry".to_string(),
+            String::new(),
+        );
+        package.layers.push(LayerDefinition {
+            name: "header".to_string(),
+            units,
+            rules,
+        });
+        package
+    }
+
+    fn crc_rule() -> SemanticRule {
+        SemanticRule::ChecksumRange {
+            algorithm: ChecksumAlgorithm::CRC16,
+            start_field: "version".to_string(),
+            end_field: "apid".to_string(),
+            covers_self: true,
+        }
+    }
+
+    fn length_rule() -> SemanticRule {
+        SemanticRule::LengthRule {
+            field_name: "length".to_string(),
+            expression: "len(data) - 1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_diff_detects_renamed_field() {
+        let mut version = unit("version", 3);
+        version.constraint = Some(Constraint::FixedValue(0));
+        let old = space_packet(
+            vec![version.clone(), unit("type", 1), unit("apid", 11)],
+            vec![],
+        );
+        let new = space_packet(vec![version, unit("pkt_type", 1), unit("apid", 11)], vec![]);
+
+        let diff = SpecGenerator::diff(&[old], &[new]);
+
+        assert_eq!(
+            diff.fields,
+            vec![FieldChange::Renamed {
+                package: "space_packet".to_string(),
+                old_name: "type".to_string(),
+                new_name: "pkt_type".to_string(),
+            }]
+        );
+        assert!(diff.rules.is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_widened_field_and_position_shift() {
+        let mut old_apid = unit("apid", 11);
+        old_apid.constraint = Some(Constraint::Range(0, 2047));
+        let mut new_apid = unit("apid", 13);
+        new_apid.constraint = Some(Constraint::Range(0, 8191));
+        let old = space_packet(vec![unit("version", 3), old_apid, unit("seq", 16)], vec![]);
+        let new = space_packet(vec![unit("version", 3), new_apid, unit("seq", 16)], vec![]);
+
+        let diff = SpecGenerator::diff(&[old], &[new]);
+
+        assert_eq!(diff.fields.len(), 2);
+        let FieldChange::Changed { field, details, .. } = &diff.fields[0] else {
+            panic!("expected changed apid, got {:?}", diff.fields[0]);
+        };
+        assert_eq!(field, "apid");
+        assert_eq!(
+            details,
+            &vec![
+                FieldDetail::Type {
+                    old: "Bit(11)".to_string(),
+                    new: "Bit(13)".to_string(),
+                },
+                FieldDetail::Length {
+                    old: "11bit".to_string(),
+                    new: "13bit".to_string(),
+                },
+                FieldDetail::Constraint {
+                    old: Some("range(0..=2047)".to_string()),
+                    new: Some("range(0..=8191)".to_string()),
+                },
+            ]
+        );
+        assert_eq!(
+            diff.fields[1],
+            FieldChange::Changed {
+                package: "space_packet".to_string(),
+                field: "seq".to_string(),
+                details: vec![FieldDetail::Position {
+                    old: Some(14),
+                    new: Some(16),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn test_diff_reports_removed_rule_in_changelog() {
+        let fields = vec![unit("version", 3), unit("apid", 11), unit("length", 16)];
+        let old = space_packet(fields.clone(), vec![crc_rule(), length_rule()]);
+        let new = space_packet(fields, vec![length_rule()]);
+
+        let diff = SpecGenerator::diff(&[old], &[new]);
+
+        assert!(diff.fields.is_empty());
+        assert_eq!(
+            diff.rules,
+            vec![RuleChange::Removed {
+                package: "space_packet".to_string(),
+                rule: crc_rule(),
+            }]
+        );
+
+        let changelog = diff.export(&MarkdownExporter);
+        assert!(changelog.starts_with("# Protocol Specification\n\n## Changelog\n\n"));
+        assert!(
+            changelog.contains("- Removed checksum_range rule in `space_packet`: ChecksumRange")
+        );
+    }
+
+    #[test]
+    fn test_diff_of_identical_versions_is_empty() {
+        let packages = [space_packet(vec![unit("version", 3)], vec![crc_rule()])];
+        let diff = SpecGenerator::diff(&packages, &packages);
+        assert!(diff.is_empty());
+        assert_eq!(diff.changelog(), "## Changelog\n\nNo changes.\n");
+    }
+}
@@ -2,13 +2,19 @@
 //!
 //! 提供多种格式的协议规范导出功能
 
-use std::collections::HashMap;
+use apdl_core::utils::ValueFormat;
+use apdl_core::{ConnectorDefinition, LengthUnit, PackageDefinition, SyntaxUnit, UnitType};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+use crate::templates::TemplateEngine;
 
 /// 导出格式枚举
 #[derive(Debug, Clone)]
 pub enum ExportFormat {
     Markdown,
     Json,
+    Html,
 }
 
 /// 协议规范导出器
@@ -30,6 +36,7 @@ impl ProtocolExporter {
         match format {
             ExportFormat::Markdown => Ok(MarkdownExporter.export(content)),
             ExportFormat::Json => Ok(JsonExporter.export(content)),
+            ExportFormat::Html => Ok(HtmlExporter.export(content)),
         }
     }
 }
@@ -48,14 +55,658 @@ impl ExportFormatHandler for MarkdownExporter {
     }
 }
 
+impl MarkdownExporter {
+    /// 导出字段表格（包含单位和详细说明列）
+    pub fn export_field_table(&self, units: &[SyntaxUnit]) -> String {
+        let mut table = String::from(
+            "| Field | Type | Length | Unit | Description | Details |\n\
+             |-------|------|--------|------|-------------|---------|\n",
+        );
+        for unit in units {
+            table.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {} |\n",
+                escape_markdown_cell(&unit.field_id),
+                format_unit_type(&unit.unit_type),
+                escape_markdown_cell(&format_length(unit)),
+                escape_markdown_cell(unit.unit_label.as_deref().unwrap_or("-")),
+                escape_markdown_cell(&unit.desc),
+                escape_markdown_cell(unit.long_description.as_deref().unwrap_or(""))
+            ));
+        }
+        table
+    }
+
+    /// 导出包含约束列的字段表格，约束取值按指定进制显示
+    pub fn export_field_table_with_format(
+        &self,
+        units: &[SyntaxUnit],
+        format: ValueFormat,
+    ) -> String {
+        let mut table = String::from(
+            "| Field | Type | Length | Constraint | Description |\n\
+             |-------|------|--------|------------|-------------|\n",
+        );
+        for unit in units {
+            let constraint = unit
+                .constraint
+                .as_ref()
+                .map_or("-".to_string(), |constraint| {
+                    format.format_constraint(constraint, unit.bit_width())
+                });
+            table.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                escape_markdown_cell(&unit.field_id),
+                format_unit_type(&unit.unit_type),
+                escape_markdown_cell(&format_length(unit)),
+                escape_markdown_cell(&constraint),
+                escape_markdown_cell(&unit.desc)
+            ));
+        }
+        table
+    }
+}
+
+/// HTML导出器
+pub struct HtmlExporter;
+
+impl ExportFormatHandler for HtmlExporter {
+    fn export(&self, content: &str) -> String {
+        format!(
+            "<html><body><h1>Protocol Specification</h1>\n{}</body></html>",
+            escape_html(content)
+        )
+    }
+}
+
+impl HtmlExporter {
+    /// 导出字段表格（包含单位和详细说明列）
+    pub fn export_field_table(&self, units: &[SyntaxUnit]) -> String {
+        let mut table = String::from(
+            "<table>\n<tr><th>Field</th><th>Type</th><th>Length</th><th>Unit</th><th>Description</th><th>Details</th></tr>\n",
+        );
+        for unit in units {
+            table.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape_html(&unit.field_id),
+                escape_html(&format_unit_type(&unit.unit_type)),
+                escape_html(&format_length(unit)),
+                escape_html(unit.unit_label.as_deref().unwrap_or("-")),
+                escape_html(&unit.desc),
+                escape_html(unit.long_description.as_deref().unwrap_or(""))
+            ));
+        }
+        table.push_str("</table>\n");
+        table
+    }
+}
+
+impl HtmlExporter {
+    /// 导出带锚点的完整HTML规范文档
+    ///
+    /// 每个包、层、字段渲染为带`id`锚点的章节，字段锚点带所在层名前缀，重名锚点追加序号；
+    /// 字段的`associate`引用和连接器的源/目标包渲染为指向其定义的内部链接，
+    /// 无法解析的引用保留为纯文本
+    pub fn export_document(
+        &self,
+        title: &str,
+        packages: &[PackageDefinition],
+        connectors: &[ConnectorDefinition],
+    ) -> String {
+        let anchors = DocumentAnchors::new(packages, connectors);
+        let mut content = String::new();
+        for (package_index, package) in packages.iter().enumerate() {
+            content.push_str(&format!(
+                "<section id=\"{}\" class=\"package\">\n<h2>{} ({})</h2>\n<p>{}</p>\n",
+                anchors.packages[package_index],
+                escape_html(&package.display_name),
+                escape_html(&package.name),
+                escape_html(&package.description)
+            ));
+            for (layer_index, layer) in package.layers.iter().enumerate() {
+                content.push_str(&format!(
+                    "<section id=\"{}\" class=\"layer\">\n<h3>Layer {}</h3>\n",
+                    anchors.layers[package_index][layer_index],
+                    escape_html(&layer.name)
+                ));
+                for (unit_index, unit) in layer.units.iter().enumerate() {
+                    let anchor = &anchors.fields[package_index][layer_index][unit_index];
+                    content.push_str(&self.render_field(
+                        anchor,
+                        package_index,
+                        unit,
+                        packages,
+                        &anchors,
+                    ));
+                }
+                content.push_str("</section>\n");
+            }
+            content.push_str("</section>\n");
+        }
+
+        if !connectors.is_empty() {
+            content.push_str("<section id=\"connectors\">\n<h2>Connectors</h2>\n");
+            for (connector, anchor) in connectors.iter().zip(&anchors.connectors) {
+                content.push_str(&format!(
+                    "<section id=\"{}\" class=\"connector\">\n<h3>{}</h3>\n\
+                     <p>Source: {} &rarr; Target: {}</p>\n<p>{}</p>\n</section>\n",
+                    anchor,
+                    escape_html(&connector.name),
+                    anchors.package_link(&connector.source_package, packages),
+                    anchors.package_link(&connector.target_package, packages),
+                    escape_html(&connector.description)
+                ));
+            }
+            content.push_str("</section>\n");
+        }
+
+        let context = serde_json::json!({ "title": escape_html(title), "content": content });
+        TemplateEngine::new().render("html_document", &context)
+    }
+
+    /// 渲染单个字段章节
+    fn render_field(
+        &self,
+        anchor: &str,
+        package_index: usize,
+        unit: &SyntaxUnit,
+        packages: &[PackageDefinition],
+        anchors: &DocumentAnchors,
+    ) -> String {
+        let mut section = format!(
+            "<section id=\"{}\" class=\"field\">\n<h4>{}</h4>\n<p>{} / {} &mdash; {}</p>\n",
+            anchor,
+            escape_html(&unit.field_id),
+            escape_html(&format_unit_type(&unit.unit_type)),
+            escape_html(&format_length(unit)),
+            escape_html(&unit.desc)
+        );
+        if !unit.associate.is_empty() {
+            let links: Vec<String> = unit
+                .associate
+                .iter()
+                .map(|name| anchors.field_link(name, package_index, packages))
+                .collect();
+            section.push_str(&format!("<p>Associated: {}</p>\n", links.join(", ")));
+        }
+        section.push_str("</section>\n");
+        section
+    }
+}
+
+/// 将名称转换为可用作HTML `id`的片段
+fn anchor_part(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// HTML文档中各章节的锚点，按文档顺序分配
+///
+/// 字段锚点以所在层的锚点为前缀；名称经`anchor_part`转换后可能相同（如空格与`-`），
+/// 已占用的锚点追加`-2`、`-3`等序号保证唯一
+struct DocumentAnchors {
+    packages: Vec<String>,
+    layers: Vec<Vec<String>>,
+    fields: Vec<Vec<Vec<String>>>,
+    connectors: Vec<String>,
+}
+
+impl DocumentAnchors {
+    fn new(packages: &[PackageDefinition], connectors: &[ConnectorDefinition]) -> Self {
+        let mut used = HashSet::new();
+        let mut unique = |base: String| {
+            let mut anchor = base.clone();
+            let mut suffix = 2;
+            while !used.insert(anchor.clone()) {
+                anchor = format!("{base}-{suffix}");
+                suffix += 1;
+            }
+            anchor
+        };
+
+        let mut anchors = Self {
+            packages: Vec::new(),
+            layers: Vec::new(),
+            fields: Vec::new(),
+            connectors: Vec::new(),
+        };
+        for package in packages {
+            let package_anchor = unique(format!("pkg-{}", anchor_part(&package.name)));
+            let mut layers = Vec::new();
+            let mut fields = Vec::new();
+            for layer in &package.layers {
+                let layer_anchor = unique(format!(
+                    "{package_anchor}-layer-{}",
+                    anchor_part(&layer.name)
+                ));
+                fields.push(
+                    layer
+                        .units
+                        .iter()
+                        .map(|unit| {
+                            unique(format!(
+                                "{layer_anchor}-field-{}",
+                                anchor_part(&unit.field_id)
+                            ))
+                        })
+                        .collect(),
+                );
+                layers.push(layer_anchor);
+            }
+            anchors.packages.push(package_anchor);
+            anchors.layers.push(layers);
+            anchors.fields.push(fields);
+        }
+        anchors.connectors = connectors
+            .iter()
+            .map(|connector| unique(format!("connector-{}", anchor_part(&connector.name))))
+            .collect();
+        anchors
+    }
+
+    /// 生成指向包定义的链接，包未定义时返回纯文本
+    fn package_link(&self, name: &str, packages: &[PackageDefinition]) -> String {
+        match packages.iter().position(|package| package.name == name) {
+            Some(index) => format!(
+                "<a href=\"#{}\">{}</a>",
+                self.packages[index],
+                escape_html(name)
+            ),
+            None => escape_html(name),
+        }
+    }
+
+    /// 生成指向关联字段首个定义的链接，优先在当前包内查找，其次在其他包中查找
+    fn field_link(&self, name: &str, current: usize, packages: &[PackageDefinition]) -> String {
+        let find =
+            |package_index: usize| {
+                packages[package_index].layers.iter().enumerate().find_map(
+                    |(layer_index, layer)| {
+                        layer
+                            .units
+                            .iter()
+                            .position(|unit| unit.field_id == name)
+                            .map(|unit_index| &self.fields[package_index][layer_index][unit_index])
+                    },
+                )
+            };
+        match find(current).or_else(|| (0..packages.len()).find_map(find)) {
+            Some(anchor) => format!("<a href=\"#{}\">{}</a>", anchor, escape_html(name)),
+            None => escape_html(name),
+        }
+    }
+}
+
+/// 格式化单元类型
+fn format_unit_type(unit_type: &UnitType) -> String {
+    match unit_type {
+        UnitType::Uint(bits) => format!("Uint{bits}"),
+        UnitType::Bit(bits) => format!("Bit({bits})"),
+        UnitType::RawData => "RawData".to_string(),
+        UnitType::Ip6Addr => "Ip6Addr".to_string(),
+        UnitType::CucTime { coarse, fine } => format!("CUC({coarse},{fine})"),
+        UnitType::CdsTime {
+            day_bytes,
+            submilli_bytes,
+        } => format!("CDS({day_bytes},{submilli_bytes})"),
+    }
+}
+
+/// 格式化长度描述
+pub(crate) fn format_length(unit: &SyntaxUnit) -> String {
+    match &unit.length.unit {
+        LengthUnit::Byte => format!("{}byte", unit.length.size),
+        LengthUnit::Bit => format!("{}bit", unit.length.size),
+        LengthUnit::Dynamic => "dynamic".to_string(),
+        LengthUnit::Expression(expr) => expr.clone(),
+    }
+}
+
+/// 转义Markdown表格单元格：`|`会被当作列分隔符，换行会结束表格行
+fn escape_markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+/// 转义HTML特殊字符
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
 /// JSON导出器
 pub struct JsonExporter;
 
 impl ExportFormatHandler for JsonExporter {
     fn export(&self, content: &str) -> String {
-        format!(
-            "{{\"specification\": \"{}\"}}",
-            content.replace("\"", "\\\"")
-        )
+        serde_json::json!({ "specification": content }).to_string()
+    }
+}
+
+impl JsonExporter {
+    /// 将包定义（含各层语法单元和语义规则）导出为结构化JSON
+    ///
+    /// 对象字段按名称排序输出以保证结果稳定，约束中的数值以`"0x.."`十六进制字符串表示
+    pub fn export_packages(
+        &self,
+        packages: &[PackageDefinition],
+    ) -> Result<String, serde_json::Error> {
+        let mut value = serde_json::to_value(packages)?;
+        for_each_constraint_value(&mut value, &mut |number| {
+            if let Some(n) = number.as_u64() {
+                *number = Value::String(format!("0x{n:X}"));
+            }
+        });
+        serde_json::to_string_pretty(&serde_json::json!({ "packages": value }))
+    }
+
+    /// 从`export_packages`导出的JSON还原包定义
+    pub fn import_packages(&self, json: &str) -> Result<Vec<PackageDefinition>, serde_json::Error> {
+        let mut root: Value = serde_json::from_str(json)?;
+        let mut packages = root
+            .get_mut("packages")
+            .map(Value::take)
+            .unwrap_or(Value::Null);
+        for_each_constraint_value(&mut packages, &mut |number| {
+            let parsed = number
+                .as_str()
+                .and_then(|text| text.strip_prefix("0x"))
+                .and_then(|hex| u64::from_str_radix(hex, 16).ok());
+            if let Some(n) = parsed {
+                *number = Value::from(n);
+            }
+        });
+        serde_json::from_value(packages)
+    }
+}
+
+/// 对包定义JSON中每个约束取值（范围边界、固定值、枚举值）调用`f`
+fn for_each_constraint_value(packages: &mut Value, f: &mut dyn FnMut(&mut Value)) {
+    let Some(packages) = packages.as_array_mut() else {
+        return;
+    };
+    let units = packages
+        .iter_mut()
+        .filter_map(|package| package.get_mut("layers")?.as_array_mut())
+        .flatten()
+        .filter_map(|layer| layer.get_mut("units")?.as_array_mut())
+        .flatten();
+    for unit in units {
+        let Some(constraint) = unit.get_mut("constraint").and_then(Value::as_object_mut) else {
+            continue;
+        };
+        for (kind, values) in constraint.iter_mut() {
+            match kind.as_str() {
+                "FixedValue" => f(values),
+                "Range" => values
+                    .as_array_mut()
+                    .into_iter()
+                    .flatten()
+                    .for_each(&mut *f),
+                "Enum" => values
+                    .as_array_mut()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|entry| entry.get_mut(1))
+                    .for_each(&mut *f),
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apdl_core::{LengthDesc, ScopeDesc};
+
+    fn temperature_unit() -> SyntaxUnit {
+        SyntaxUnit {
+            desc: "Board temperature".to_string(),
+            unit_label: Some("K".to_string()),
+            long_description: Some("Sampled once per second".to_string()),
+            ..SyntaxUnit::new(
+                "temperature",
+                UnitType::Uint(16),
+                LengthDesc {
+                    size: 2,
+                    unit: LengthUnit::Byte,
+                },
+                ScopeDesc::Layer("application".to_string()),
+            )
+        }
+    }
+
+    #[test]
+    fn test_markdown_field_table_shows_unit_label() {
+        let table = MarkdownExporter.export_field_table(&[temperature_unit()]);
+
+        assert!(table.contains("| Unit |"));
+        assert!(table.contains(
+            "| temperature | Uint16 | 2byte | K | Board temperature | Sampled once per second |"
+        ));
+    }
+
+    #[test]
+    fn test_markdown_field_table_escapes_pipes_and_newlines() {
+        let mut unit = temperature_unit();
+        unit.desc = "Hot | cold".to_string();
+        unit.long_description = Some("Line one\nLine two".to_string());
+        let table = MarkdownExporter.export_field_table(&[unit]);
+
+        assert!(table.contains("| Hot \\| cold | Line one<br>Line two |"));
+        // 表头之后只有一行数据，每行的未转义分隔符数量与表头一致
+        let rows: Vec<&str> = table.lines().collect();
+        assert_eq!(rows.len(), 3);
+        let separators = |row: &str| row.replace("\\|", "").matches('|').count();
+        assert_eq!(separators(rows[2]), separators(rows[0]));
+    }
+
+    #[test]
+    fn test_markdown_field_table_value_format() {
+        use apdl_core::Constraint;
+
+        let mut sync = temperature_unit();
+        sync.field_id = "sync".to_string();
+        sync.desc = "Sync marker".to_string();
+        sync.constraint = Some(Constraint::FixedValue(0xEB90));
+        let mut temperature = temperature_unit();
+        temperature.constraint = Some(Constraint::Range(200, 400));
+
+        let table = MarkdownExporter.export_field_table_with_format(
+            &[sync.clone(), temperature.clone()],
+            ValueFormat::Auto,
+        );
+        assert!(table.contains("| sync | Uint16 | 2byte | fixed(0xEB90) | Sync marker |"));
+        assert!(table
+            .contains("| temperature | Uint16 | 2byte | range(200..=400) | Board temperature |"));
+
+        let table = MarkdownExporter.export_field_table_with_format(&[sync], ValueFormat::Dec);
+        assert!(table.contains("| fixed(60304) |"));
+    }
+
+    fn telemetry_package() -> apdl_core::PackageDefinition {
+        use apdl_core::{ChecksumAlgorithm, Constraint, LayerDefinition, SemanticRule};
+
+        let mut sync = temperature_unit();
+        sync.field_id = "sync".to_string();
+        sync.constraint = Some(Constraint::FixedValue(0xEB90));
+        let mut mode = temperature_unit();
+        mode.field_id = "mode".to_string();
+        mode.constraint = Some(Constraint::Enum(vec![
+            ("SAFE".to_string(), 0x0),
+            ("NOMINAL".to_string(), 0x1F),
+        ]));
+        let mut temperature = temperature_unit();
+        temperature.constraint = Some(Constraint::Range(200, 400));
+
+        let mut package = apdl_core::PackageDefinition::new(
+            "hk".to_string(),
+            "Housekeeping".to_string(),
+            "telemetry".to_string(),
+            "Housekeeping telemetry".to_string(),
+        );
+        package.layers.push(LayerDefinition {
+            name: "application".to_string(),
+            units: vec![sync, mode, temperature],
+            rules: vec![SemanticRule::ChecksumRange {
+                algorithm: ChecksumAlgorithm::CRC16,
+                start_field: "sync".to_string(),
+                end_field: "temperature".to_string(),
+                covers_self: true,
+            }],
+        });
+        package
+    }
+
+    #[test]
+    fn test_json_export_round_trips_package() {
+        let packages = [telemetry_package()];
+        let json = JsonExporter.export_packages(&packages).unwrap();
+
+        assert!(json.contains("\"FixedValue\": \"0xEB90\""));
+        assert!(json.contains("\"0xC8\",\n"));
+        assert!(json.contains("\"0x1F\""));
+        // 对象字段按名称排序
+        let description = json.find("\"description\"").unwrap();
+        let layers = json.find("\"layers\"").unwrap();
+        let name = json.find("\"name\": \"hk\"").unwrap();
+        assert!(description < layers && layers < name);
+        assert_eq!(json, JsonExporter.export_packages(&packages).unwrap());
+
+        let imported = JsonExporter.import_packages(&json).unwrap();
+        assert_eq!(imported, packages);
+    }
+
+    #[test]
+    fn test_json_export_escapes_specification_text() {
+        let exported = JsonExporter.export("say \"hi\"\nC:\\path");
+        let value: serde_json::Value = serde_json::from_str(&exported).unwrap();
+        assert_eq!(value["specification"], "say \"hi\"\nC:\\path");
+    }
+
+    #[test]
+    fn test_html_document_anchors_and_cross_references() {
+        use apdl_core::{ConnectorConfig, ConnectorDefinition};
+
+        let mut tm = telemetry_package();
+        tm.layers[0].units[0].associate = vec!["temperature".to_string()];
+        tm.layers[0].units[1].associate = vec!["apid".to_string(), "unknown".to_string()];
+
+        let mut apid = temperature_unit();
+        apid.field_id = "apid".to_string();
+        let mut space_packet = apdl_core::PackageDefinition::new(
+            "space packet".to_string(),
+            "Space Packet".to_string(),
+            "encapsulating".to_string(),
+            String::new(),
+        );
+        space_packet.layers.push(apdl_core::LayerDefinition {
+            name: "header".to_string(),
+            units: vec![apid],
+            rules: vec![],
+        });
+
+        let connector = ConnectorDefinition {
+            name: "hk_to_sp".to_string(),
+            connector_type: "field_mapping".to_string(),
+            source_package: "hk".to_string(),
+            target_package: "space packet".to_string(),
+            config: ConnectorConfig {
+                mappings: vec![],
+                header_pointers: None,
+                data_placement: None,
+            },
+            description: "Wrap housekeeping".to_string(),
+        };
+
+        let packages = [tm, space_packet];
+        let html = HtmlExporter.export_document("Spec <v1>", &packages, &[connector]);
+
+        assert!(html.starts_with("<html>"));
+        assert!(html.contains("<title>Spec &lt;v1&gt;</title>"));
+        for anchor in [
+            "pkg-hk",
+            "pkg-hk-layer-application",
+            "pkg-hk-layer-application-field-sync",
+            "pkg-hk-layer-application-field-mode",
+            "pkg-space-packet",
+            "pkg-space-packet-layer-header",
+            "pkg-space-packet-layer-header-field-apid",
+            "connector-hk_to_sp",
+        ] {
+            assert!(
+                html.contains(&format!("id=\"{anchor}\"")),
+                "missing anchor {anchor}"
+            );
+        }
+
+        // 每个内部链接都指向文档中存在的锚点
+        let hrefs: Vec<&str> = html
+            .split("href=\"#")
+            .skip(1)
+            .map(|rest| &rest[..rest.find('"').unwrap()])
+            .collect();
+        assert_eq!(
+            hrefs,
+            vec![
+                "pkg-hk-layer-application-field-temperature",
+                "pkg-space-packet-layer-header-field-apid",
+                "pkg-hk",
+                "pkg-space-packet",
+            ]
+        );
+        for href in hrefs {
+            assert!(
+                html.contains(&format!("id=\"{href}\"")),
+                "dangling href {href}"
+            );
+        }
+        assert!(html.contains(
+            "<p>Associated: <a href=\"#pkg-space-packet-layer-header-field-apid\">apid</a>, unknown</p>"
+        ));
+    }
+
+    #[test]
+    fn test_html_document_anchors_are_unique() {
+        let mut package = telemetry_package();
+        let layer = &mut package.layers[0];
+        layer.units[0].field_id = "a b".to_string();
+        layer.units[1].field_id = "a-b".to_string();
+        layer.units[1].associate = vec!["a-b".to_string()];
+        let mut second = layer.clone();
+        second.name = "second".to_string();
+        package.layers.push(second);
+
+        let html = HtmlExporter.export_document("Spec", &[package], &[]);
+        let ids: Vec<&str> = html
+            .split(" id=\"")
+            .skip(1)
+            .map(|rest| &rest[..rest.find('"').unwrap()])
+            .collect();
+        let unique: std::collections::HashSet<&str> = ids.iter().copied().collect();
+        assert_eq!(unique.len(), ids.len(), "duplicate anchors in {ids:?}");
+        assert!(ids.contains(&"pkg-hk-layer-application-field-a-b"));
+        assert!(ids.contains(&"pkg-hk-layer-application-field-a-b-2"));
+        assert!(ids.contains(&"pkg-hk-layer-second-field-a-b"));
+        assert!(html.contains("<a href=\"#pkg-hk-layer-application-field-a-b-2\">a-b</a>"));
+    }
+
+    #[test]
+    fn test_html_field_table_shows_unit_label() {
+        let table = HtmlExporter.export_field_table(&[temperature_unit()]);
+
+        assert!(table.contains("<th>Unit</th>"));
+        assert!(table.contains("<td>K</td>"));
+        assert!(table.contains("<td>Sampled once per second</td>"));
     }
 }
@@ -2,7 +2,24 @@
 //!
 //! 实现协议规范的生成功能
 
-use std::collections::HashMap;
+use apdl_core::{PackageDefinition, SyntaxUnit, UnitType};
+use std::collections::{BTreeMap, HashMap};
+
+/// 各类别（字段类型或规则类型）的使用次数
+type UsageCounts<'a> = BTreeMap<&'a str, usize>;
+
+/// 字段在帧中的位置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldLayout {
+    /// 字段名
+    pub name: String,
+    /// 起始字节（从0开始）
+    pub byte_offset: usize,
+    /// 起始字节内的起始位（0为最高位）
+    pub bit_offset: u8,
+    /// 位宽（动态长度字段为0）
+    pub bit_width: usize,
+}
 
 /// 规范生成器
 pub struct SpecGenerator {
@@ -47,4 +64,272 @@ impl SpecGenerator {
     pub fn register_template(&mut self, name: String, template: String) {
         self.templates.insert(name, template);
     }
+
+    /// 生成协议能力矩阵（Markdown表格）
+    ///
+    /// 每行一个协议，列为用到的字段类型和语义规则类型，单元格为使用次数，未使用为`-`
+    pub fn capability_matrix(defs: &[PackageDefinition]) -> String {
+        let usages: Vec<(UsageCounts, UsageCounts)> = defs
+            .iter()
+            .map(|def| {
+                let mut field_types = BTreeMap::new();
+                let mut rule_kinds = BTreeMap::new();
+                for layer in &def.layers {
+                    for unit in &layer.units {
+                        *field_types
+                            .entry(unit_type_category(&unit.unit_type))
+                            .or_insert(0) += 1;
+                    }
+                    for rule in &layer.rules {
+                        *rule_kinds.entry(rule.kind()).or_insert(0) += 1;
+                    }
+                }
+                (field_types, rule_kinds)
+            })
+            .collect();
+
+        // 字段类型列在前，规则类型列在后，各自按名称排序
+        let mut field_columns: Vec<&str> = usages
+            .iter()
+            .flat_map(|(field_types, _)| field_types.keys().copied())
+            .collect();
+        field_columns.sort_unstable();
+        field_columns.dedup();
+        let mut rule_columns: Vec<&str> = usages
+            .iter()
+            .flat_map(|(_, rule_kinds)| rule_kinds.keys().copied())
+            .collect();
+        rule_columns.sort_unstable();
+        rule_columns.dedup();
+
+        let columns = field_columns.len() + rule_columns.len();
+        let mut matrix = String::from("| Protocol |");
+        for column in field_columns.iter().chain(&rule_columns) {
+            matrix.push_str(&format!(" {column} |"));
+        }
+        matrix.push_str(&format!("\n|----------|{}\n", "---|".repeat(columns)));
+
+        let cell = |counts: &UsageCounts, column: &str| match counts.get(column) {
+            Some(count) => format!(" ✓ {count} |"),
+            None => " - |".to_string(),
+        };
+        for (def, (field_types, rule_kinds)) in defs.iter().zip(&usages) {
+            matrix.push_str(&format!("| {} |", def.name));
+            for column in &field_columns {
+                matrix.push_str(&cell(field_types, column));
+            }
+            for column in &rule_columns {
+                matrix.push_str(&cell(rule_kinds, column));
+            }
+            matrix.push('\n');
+        }
+        matrix
+    }
+}
+
+impl SpecGenerator {
+    /// 按`LengthDesc`计算各字段的起始字节、起始位和位宽
+    ///
+    /// bit字段按位紧密排列，可跨越字节边界。动态长度字段之后的偏移无法确定，
+    /// 布局在第一个动态长度字段处截止（该字段以位宽0列出）
+    pub fn compute_layout(units: &[SyntaxUnit]) -> Vec<FieldLayout> {
+        let mut layout = Vec::with_capacity(units.len());
+        let mut offset = 0usize;
+        for unit in units {
+            let bit_width = unit.length.fixed_bits();
+            layout.push(FieldLayout {
+                name: unit.field_id.clone(),
+                byte_offset: offset / 8,
+                bit_offset: (offset % 8) as u8,
+                bit_width: bit_width.unwrap_or(0),
+            });
+            match bit_width {
+                Some(bits) => offset += bits,
+                None => break,
+            }
+        }
+        layout
+    }
+
+    /// 生成字段布局表（Markdown表格）
+    pub fn layout_table(units: &[SyntaxUnit]) -> String {
+        let mut table = String::from(
+            "| Field | Byte | Bit | Width |\n\
+             |-------|------|-----|-------|\n",
+        );
+        for field in Self::compute_layout(units) {
+            let width = match field.bit_width {
+                0 => "dynamic".to_string(),
+                bits => bits.to_string(),
+            };
+            table.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                field.name, field.byte_offset, field.bit_offset, width
+            ));
+        }
+        table
+    }
+}
+
+/// 获取字段类型在能力矩阵中的列名
+fn unit_type_category(unit_type: &UnitType) -> &'static str {
+    match unit_type {
+        UnitType::Uint(_) => "Uint",
+        UnitType::Bit(_) => "Bit",
+        UnitType::RawData => "RawData",
+        UnitType::Ip6Addr => "Ip6Addr",
+        UnitType::CucTime { .. } => "CucTime",
+        UnitType::CdsTime { .. } => "CdsTime",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apdl_core::{
+        ChecksumAlgorithm, LayerDefinition, LengthDesc, LengthUnit, ScopeDesc, SemanticRule,
+        SyntaxUnit,
+    };
+
+    fn unit(name: &str, unit_type: UnitType) -> SyntaxUnit {
+        let length = match unit_type {
+            UnitType::Bit(bits) => LengthDesc {
+                size: bits as usize,
+                unit: LengthUnit::Bit,
+            },
+            UnitType::Uint(bits) => LengthDesc {
+                size: bits as usize / 8,
+                unit: LengthUnit::Byte,
+            },
+            _ => LengthDesc {
+                size: 0,
+                unit: LengthUnit::Dynamic,
+            },
+        };
+        SyntaxUnit::new(
+            name,
+            unit_type,
+            length,
+            ScopeDesc::Layer("link".to_string()),
+        )
+    }
+
+    fn package(name: &str, units: Vec<SyntaxUnit>, rules: Vec<SemanticRule>) -> PackageDefinition {
+        let mut package = PackageDefinition::new(
+            name.to_string(),
+            name.to_string(),
+            "telemetry".to_string(),
+            String::new(),
+        );
+        package.layers.push(LayerDefinition {
+            name: "link".to_string(),
+            units,
+            rules,
+        });
+        package
+    }
+
+    #[test]
+    fn test_capability_matrix_marks_used_rules() {
+        let checksum = |start: &str, end: &str| SemanticRule::ChecksumRange {
+            algorithm: ChecksumAlgorithm::CRC16,
+            start_field: start.to_string(),
+            end_field: end.to_string(),
+            covers_self: true,
+        };
+        let tm = package(
+            "tm",
+            vec![
+                unit("version", UnitType::Bit(2)),
+                unit("length", UnitType::Uint(16)),
+                unit("data", UnitType::RawData),
+            ],
+            vec![
+                checksum("version", "length"),
+                checksum("version", "data"),
+                SemanticRule::LengthRule {
+                    field_name: "length".to_string(),
+                    expression: "len(data)".to_string(),
+                },
+            ],
+        );
+        let can = package(
+            "can",
+            vec![unit("id", UnitType::Uint(16))],
+            vec![SemanticRule::Alignment {
+                field_name: "id".to_string(),
+                boundary: 2,
+            }],
+        );
+
+        let matrix = SpecGenerator::capability_matrix(&[tm, can]);
+        let lines: Vec<&str> = matrix.lines().collect();
+        assert_eq!(
+            lines[0],
+            "| Protocol | Bit | RawData | Uint | alignment | checksum_range | length_rule |"
+        );
+        assert_eq!(lines[1], "|----------|---|---|---|---|---|---|");
+        assert_eq!(lines[2], "| tm | ✓ 1 | ✓ 1 | ✓ 1 | - | ✓ 2 | ✓ 1 |");
+        assert_eq!(lines[3], "| can | - | - | ✓ 1 | ✓ 1 | - | - |");
+    }
+
+    fn layout(name: &str, byte_offset: usize, bit_offset: u8, bit_width: usize) -> FieldLayout {
+        FieldLayout {
+            name: name.to_string(),
+            byte_offset,
+            bit_offset,
+            bit_width,
+        }
+    }
+
+    #[test]
+    fn test_compute_layout_packs_sub_byte_fields() {
+        let units = vec![
+            unit("version", UnitType::Bit(3)),
+            unit("flags", UnitType::Bit(5)),
+            unit("type", UnitType::Uint(8)),
+            unit("spare", UnitType::Bit(3)),
+            unit("apid", UnitType::Bit(11)),
+            unit("priority", UnitType::Bit(5)),
+            unit("count", UnitType::Uint(8)),
+        ];
+
+        assert_eq!(
+            SpecGenerator::compute_layout(&units),
+            vec![
+                layout("version", 0, 0, 3),
+                layout("flags", 0, 3, 5),
+                layout("type", 1, 0, 8),
+                layout("spare", 2, 0, 3),
+                // apid跨越第2、3字节
+                layout("apid", 2, 3, 11),
+                layout("priority", 3, 6, 5),
+                // 前面的bit字段未对齐到字节边界，Uint8同样跨越字节
+                layout("count", 4, 3, 8),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_layout_table_stops_at_dynamic_field() {
+        let units = vec![
+            unit("header", UnitType::Bit(5)),
+            unit("length", UnitType::Uint(16)),
+            unit("data", UnitType::RawData),
+            unit("crc", UnitType::Uint(16)),
+        ];
+
+        let table = SpecGenerator::layout_table(&units);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "| Field | Byte | Bit | Width |",
+                "|-------|------|-----|-------|",
+                "| header | 0 | 0 | 5 |",
+                "| length | 0 | 5 | 16 |",
+                "| data | 2 | 5 | dynamic |",
+            ]
+        );
+    }
 }
@@ -2,10 +2,12 @@
 //!
 //! This crate provides automatic generation of protocol specifications for the APDL system.
 
+pub mod diff;
 pub mod exporters;
 pub mod generator;
 pub mod templates;
 
-pub use exporters::MarkdownExporter;
-pub use generator::SpecGenerator;
+pub use diff::{FieldChange, FieldDetail, RuleChange, SpecDiff};
+pub use exporters::{HtmlExporter, JsonExporter, MarkdownExporter};
+pub use generator::{FieldLayout, SpecGenerator};
 pub use templates::TemplateEngine;
@@ -1,7 +1,13 @@
 //! 模板引擎模块
 //!
 //! 实现协议规范模板的管理和渲染功能
+//!
+//! 模板语法：
+//! - `{{name}}`、`{{field.desc}}`：按路径从上下文取值，`{{this}}`为当前迭代元素，`{{@index}}`为迭代序号
+//! - `{{#each fields}} ... {{/each}}`：遍历数组，块内以数组元素为当前上下文
+//! - `{{#if has_constraint}} ... {{else}} ... {{/if}}`：按取值真假选择分支
 
+use serde_json::Value;
 use std::collections::HashMap;
 
 /// 模板引擎
@@ -19,6 +25,14 @@ impl Default for TemplateEngine {
             include_str!("../templates/ccsds_standard.template").to_string(),
         );
 
+        // 添加HTML文档模板
+        templates.insert(
+            "html_document".to_string(),
+            "<html><head><meta charset=\"utf-8\"><title>{{title}}</title></head><body>\n\
+             <h1>{{title}}</h1>\n{{content}}</body></html>\n"
+                .to_string(),
+        );
+
         // 添加默认模板
         templates.insert(
             "default".to_string(),
@@ -35,18 +49,28 @@ impl TemplateEngine {
     }
 
     /// 渲染模板
-    pub fn render(&self, template_name: &str, context: &HashMap<String, String>) -> String {
-        match self.templates.get(template_name) {
-            Some(template) => {
-                let mut result = template.clone();
-                for (key, value) in context {
-                    let placeholder = format!("{{{{{key}}}}}");
-                    result = result.replace(&placeholder, value);
-                }
-                result
+    ///
+    /// 上下文中找不到的占位符原样保留；块标签不匹配时返回错误描述
+    pub fn render(&self, template_name: &str, context: &Value) -> String {
+        let Some(template) = self.templates.get(template_name) else {
+            return format!("Template '{template_name}' not found");
+        };
+        let mut tokens = tokenize(template).into_iter();
+        let nodes = match parse_block(&mut tokens) {
+            Ok((nodes, BlockEnd::Eof)) => nodes,
+            Ok((_, end)) => {
+                return format!("Template '{template_name}' error: unexpected {end:?}");
             }
-            None => format!("Template '{template_name}' not found"),
-        }
+            Err(message) => return format!("Template '{template_name}' error: {message}"),
+        };
+
+        let mut output = String::new();
+        let mut scopes = vec![Scope {
+            value: context,
+            index: None,
+        }];
+        render_nodes(&nodes, &mut scopes, &mut output);
+        output
     }
 
     /// 注册新模板
@@ -60,7 +84,248 @@ impl TemplateEngine {
     }
 }
 
+/// 模板词法单元
+enum Token<'a> {
+    Text(&'a str),
+    /// `{{ }}`内的标签文本（已去除首尾空白）
+    Tag(&'a str),
+}
+
+/// 模板语法树节点
+enum Node {
+    Text(String),
+    Variable(String),
+    Each(String, Vec<Node>),
+    If(String, Vec<Node>, Vec<Node>),
+}
+
+/// 块解析的结束位置
+#[derive(Debug)]
+enum BlockEnd {
+    Eof,
+    Else,
+    EndEach,
+    EndIf,
+}
+
+/// 渲染作用域：当前上下文值及迭代序号
+struct Scope<'a> {
+    value: &'a Value,
+    index: Option<usize>,
+}
+
+/// 将模板切分为文本和标签，未闭合的`{{`按文本处理
+fn tokenize(template: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+        }
+        tokens.push(Token::Tag(rest[start + 2..start + 2 + len].trim()));
+        rest = &rest[start + 2 + len + 2..];
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest));
+    }
+    tokens
+}
+
+/// 解析节点直到模板结束或遇到`{{else}}`、`{{/each}}`、`{{/if}}`
+fn parse_block<'a>(
+    tokens: &mut impl Iterator<Item = Token<'a>>,
+) -> Result<(Vec<Node>, BlockEnd), String> {
+    let mut nodes = Vec::new();
+    while let Some(token) = tokens.next() {
+        let tag = match token {
+            Token::Text(text) => {
+                nodes.push(Node::Text(text.to_string()));
+                continue;
+            }
+            Token::Tag(tag) => tag,
+        };
+        match tag {
+            "else" => return Ok((nodes, BlockEnd::Else)),
+            "/each" => return Ok((nodes, BlockEnd::EndEach)),
+            "/if" => return Ok((nodes, BlockEnd::EndIf)),
+            _ => {}
+        }
+        if let Some(path) = tag.strip_prefix("#each ") {
+            match parse_block(tokens)? {
+                (body, BlockEnd::EndEach) => nodes.push(Node::Each(path.trim().to_string(), body)),
+                (_, end) => return Err(format!("{{{{#each {path}}}}} closed by {end:?}")),
+            }
+        } else if let Some(path) = tag.strip_prefix("#if ") {
+            let (then_branch, end) = parse_block(tokens)?;
+            let (else_branch, end) = match end {
+                BlockEnd::Else => parse_block(tokens)?,
+                end => (Vec::new(), end),
+            };
+            match end {
+                BlockEnd::EndIf => {
+                    nodes.push(Node::If(path.trim().to_string(), then_branch, else_branch))
+                }
+                end => return Err(format!("{{{{#if {path}}}}} closed by {end:?}")),
+            }
+        } else {
+            nodes.push(Node::Variable(tag.to_string()));
+        }
+    }
+    Ok((nodes, BlockEnd::Eof))
+}
+
+/// 按作用域从内到外查找路径对应的值
+fn lookup<'a>(scopes: &[Scope<'a>], path: &str) -> Option<&'a Value> {
+    let current = scopes.last()?;
+    if path == "this" {
+        return Some(current.value);
+    }
+    let (path, scopes) = match path.strip_prefix("this.") {
+        Some(rest) => (rest, std::slice::from_ref(current)),
+        None => (path, scopes),
+    };
+    scopes.iter().rev().find_map(|scope| {
+        path.split('.')
+            .try_fold(scope.value, |value, key| match value {
+                Value::Object(map) => map.get(key),
+                Value::Array(items) => items.get(key.parse::<usize>().ok()?),
+                _ => None,
+            })
+    })
+}
+
+/// 判断值在`{{#if}}`中是否为真
+fn is_truthy(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => false,
+        Some(Value::Bool(flag)) => *flag,
+        Some(Value::Number(number)) => number.as_f64() != Some(0.0),
+        Some(Value::String(text)) => !text.is_empty(),
+        Some(Value::Array(items)) => !items.is_empty(),
+        Some(Value::Object(map)) => !map.is_empty(),
+    }
+}
+
+fn render_nodes<'a>(nodes: &'a [Node], scopes: &mut Vec<Scope<'a>>, output: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Variable(path) if path == "@index" => {
+                match scopes.last().and_then(|scope| scope.index) {
+                    Some(index) => output.push_str(&index.to_string()),
+                    None => output.push_str("{{@index}}"),
+                }
+            }
+            Node::Variable(path) => match lookup(scopes, path) {
+                Some(Value::String(text)) => output.push_str(text),
+                Some(Value::Null) => {}
+                Some(value) => output.push_str(&value.to_string()),
+                // 未提供的占位符原样保留
+                None => output.push_str(&format!("{{{{{path}}}}}")),
+            },
+            Node::Each(path, body) => {
+                let Some(Value::Array(items)) = lookup(scopes, path) else {
+                    continue;
+                };
+                for (index, item) in items.iter().enumerate() {
+                    scopes.push(Scope {
+                        value: item,
+                        index: Some(index),
+                    });
+                    render_nodes(body, scopes, output);
+                    scopes.pop();
+                }
+            }
+            Node::If(path, then_branch, else_branch) => {
+                let branch = if is_truthy(lookup(scopes, path)) {
+                    then_branch
+                } else {
+                    else_branch
+                };
+                render_nodes(branch, scopes, output);
+            }
+        }
+    }
+}
+
 // 如果模板文件不存在，创建一个默认的
 pub fn ensure_default_templates() {
     // 这里可以确保必要的模板文件存在
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn engine_with(name: &str, template: &str) -> TemplateEngine {
+        let mut engine = TemplateEngine::new();
+        engine.register_template(name.to_string(), template.to_string());
+        engine
+    }
+
+    #[test]
+    fn test_render_field_table_with_each() {
+        let engine = engine_with(
+            "fields",
+            "| # | Field | Bits | Constraint |\n\
+             {{#each fields}}| {{@index}} | {{name}} | {{bits}} | \
+             {{#if constraint}}{{constraint}}{{else}}-{{/if}} |\n{{/each}}\
+             Protocol: {{protocol}}",
+        );
+        let context = json!({
+            "protocol": "TM",
+            "fields": [
+                {"name": "version", "bits": 2, "constraint": "fixed(0)"},
+                {"name": "scid", "bits": 10, "constraint": null},
+                {"name": "vcid", "bits": 3},
+            ],
+        });
+
+        assert_eq!(
+            engine.render("fields", &context),
+            "| # | Field | Bits | Constraint |\n\
+             | 0 | version | 2 | fixed(0) |\n\
+             | 1 | scid | 10 | - |\n\
+             | 2 | vcid | 3 | - |\n\
+             Protocol: TM"
+        );
+    }
+
+    #[test]
+    fn test_render_conditional_note_section() {
+        let engine = engine_with(
+            "notes",
+            "{{#if has_constraint}}## Notes\n{{#each notes}}- {{this}} ({{spec.name}})\n{{/each}}{{/if}}end",
+        );
+
+        let with_notes = json!({
+            "has_constraint": true,
+            "spec": {"name": "CCSDS 133.0-B"},
+            "notes": ["APID 2047 is reserved", "Sequence count wraps at 16384"],
+        });
+        assert_eq!(
+            engine.render("notes", &with_notes),
+            "## Notes\n- APID 2047 is reserved (CCSDS 133.0-B)\n\
+             - Sequence count wraps at 16384 (CCSDS 133.0-B)\nend"
+        );
+
+        let without_notes = json!({"has_constraint": false, "notes": ["hidden"]});
+        assert_eq!(engine.render("notes", &without_notes), "end");
+    }
+
+    #[test]
+    fn test_render_keeps_plain_substitution() {
+        let engine = TemplateEngine::new();
+        let rendered = engine.render("default", &json!({"title": "TM Frame"}));
+        assert_eq!(rendered, "# TM Frame\n\n{{content}}");
+
+        let engine = engine_with("broken", "{{#each fields}}{{name}}{{/if}}");
+        assert!(engine
+            .render("broken", &json!({}))
+            .starts_with("Template 'broken' error"));
+    }
+}